@@ -0,0 +1,65 @@
+//! Per pixel vs bulk color operations on a 1024x1024 image
+//! NOTE: Run with `--features simd` to measure the SIMD path of the bulk operations
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use raylib_rs_native::prelude::*;
+
+const PIXELS: usize = 1024 * 1024;
+
+/// Deterministic pixel data, every byte value is used
+#[allow(clippy::cast_possible_truncation)]
+fn pixels() -> Vec<u8> {
+    (0..PIXELS * 4).map(|i| (i.wrapping_mul(2_654_435_761) >> 7) as u8).collect()
+}
+
+fn bench_tint(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tint");
+    let mut data = pixels();
+    let tint = Color::new(200, 100, 50, 150);
+    group.bench_function("per_pixel", |b| b.iter(|| {
+        for px in data.chunks_exact_mut(4) {
+            let color = Color::new(px[0], px[1], px[2], px[3]).tint(black_box(tint));
+            px.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }));
+    group.bench_function("bulk", |b| b.iter(|| tint_slice(colors_from_rgba8_mut(&mut data), black_box(tint))));
+    group.finish();
+}
+
+fn bench_lerp(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lerp");
+    let (from, to) = (pixels(), pixels().into_iter().rev().collect::<Vec<u8>>());
+    let mut out = vec![Color::BLANK; PIXELS];
+    group.bench_function("per_pixel", |b| b.iter(|| {
+        for ((out, a), b) in out.iter_mut().zip(from.chunks_exact(4)).zip(to.chunks_exact(4)) {
+            *out = Color::new(a[0], a[1], a[2], a[3]).lerp_to(Color::new(b[0], b[1], b[2], b[3]), black_box(0.25));
+        }
+    }));
+    group.bench_function("bulk", |b| b.iter(|| lerp_slices(colors_from_rgba8(&from), colors_from_rgba8(&to), black_box(0.25), &mut out)));
+    group.finish();
+}
+
+fn bench_premultiply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("premultiply");
+    let data = pixels();
+    group.bench_function("per_pixel", |b| b.iter_batched_ref(|| data.clone(), |data| {
+        for px in data.chunks_exact_mut(4) {
+            let color = Color::new(px[0], px[1], px[2], px[3]).premultiply();
+            px.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }, criterion::BatchSize::LargeInput));
+    group.bench_function("bulk", |b| b.iter_batched_ref(|| data.clone(), |data| premultiply_slice(colors_from_rgba8_mut(data)), criterion::BatchSize::LargeInput));
+    group.finish();
+}
+
+fn bench_image(c: &mut Criterion) {
+    let mut group = c.benchmark_group("image");
+    let mut image = Image::blank(1024, 1024, PixelFormat::UncompressedR8G8B8A8);
+    image.data = pixels();
+    group.bench_function("brightness", |b| b.iter(|| image.color_brightness(black_box(1))));
+    group.bench_function("contrast", |b| b.iter(|| image.color_contrast(black_box(1.0))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_tint, bench_lerp, bench_premultiply, bench_image);
+criterion_main!(benches);
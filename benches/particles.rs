@@ -0,0 +1,39 @@
+//! CPU particle simulation baseline with 100k alive particles
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use raylib_rs_native::prelude::*;
+
+const PARTICLES: usize = 100_000;
+
+/// Full pool of long-lived particles, emitting as fast as particles die
+fn emitter() -> ParticleEmitter {
+    ParticleEmitter::new(EmitterConfig {
+        rate: PARTICLES as f32,
+        burst: PARTICLES,
+        max_particles: PARTICLES,
+        lifetime: (1.0, 3.0),
+        spread: Radians::PI,
+        speed: (10.0, 100.0),
+        angular_velocity: (-2.0, 2.0),
+        gravity: Vector2::new(0.0, 98.0),
+        linear_damping: 0.5,
+        angular_damping: 0.5,
+        size_start: 4.0,
+        size_end: 0.0,
+        color: Gradient::linear(Color::YELLOW, Color::RED),
+        seed: 1,
+        ..EmitterConfig::default()
+    })
+}
+
+fn bench_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("particles");
+    let mut emitter = emitter();
+    group.bench_function("update", |b| b.iter(|| emitter.update(black_box(1.0/60.0))));
+    group.bench_function("instances", |b| b.iter(|| emitter.instances().fold(0.0, |sum, instance| sum + instance.size)));
+    group.bench_function("instances_by_age", |b| b.iter(|| emitter.instances_by_age().len()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_update);
+criterion_main!(benches);
@@ -0,0 +1,453 @@
+//! Audio device, software mixing of playing voices into the output backend
+
+use crate::{config::{AUDIO_DEVICE_CHANNELS, MAX_AUDIO_BUFFER_POOL_CHANNELS}, prelude::*, tracelog};
+
+/// Sample rate used when the backend doesn't impose one
+pub const AUDIO_DEVICE_DEFAULT_SAMPLE_RATE: usize = 44100;
+
+/// Audio output backend, pulls frames from [`AudioDevice::mix`]
+pub trait AudioBackend: std::fmt::Debug {
+    /// Output frames per second
+    fn sample_rate(&self) -> usize;
+
+    /// Start or stop pulling frames
+    fn set_playing(&mut self, playing: bool);
+}
+
+/// Backend without output, frames are only mixed when [`AudioDevice::mix`] is called explicitly
+#[derive(Debug, Default)]
+pub struct NullAudio;
+
+impl AudioBackend for NullAudio {
+    fn sample_rate(&self) -> usize {
+        AUDIO_DEVICE_DEFAULT_SAMPLE_RATE
+    }
+
+    fn set_playing(&mut self, _playing: bool) {}
+}
+
+/// Sound played by a voice
+#[derive(Debug, Clone)]
+struct VoiceSource {
+    handle: VoiceHandle,
+    sound: Sound,
+    /// Playback position in sound frames, fractional with pitch or sample rate conversion
+    cursor: f64,
+}
+
+/// Audio device, plays sounds on a fixed pool of voices
+/// NOTE: Voices are recycled automatically when playback ends, their handles become stale
+#[derive(Debug)]
+pub struct AudioDevice {
+    backend: Box<dyn AudioBackend>,
+    voices: VoicePool,
+    /// Playing sound of each pool slot
+    sources: Vec<Option<VoiceSource>>,
+    master_volume: f32,
+    paused: bool,
+    /// Window background behavior, applied on top of master volume and pause state
+    background: BackgroundAudio,
+}
+
+impl Default for AudioDevice {
+    fn default() -> Self {
+        Self::new(Box::new(NullAudio), MAX_AUDIO_BUFFER_POOL_CHANNELS, StealPolicy::default())
+    }
+}
+
+impl AudioDevice {
+    /// Create device on a backend with the given number of voices
+    #[must_use]
+    pub fn new(backend: Box<dyn AudioBackend>, voices: usize, policy: StealPolicy) -> Self {
+        let mut backend = backend;
+        backend.set_playing(true);
+        Self {
+            backend,
+            voices: VoicePool::new(voices, policy),
+            sources: vec![None; voices],
+            master_volume: 1.0,
+            paused: false,
+            background: BackgroundAudio::Keep,
+        }
+    }
+
+    /// Set output backend, playing voices continue on the new backend
+    pub fn set_backend(&mut self, backend: Box<dyn AudioBackend>) {
+        self.backend = backend;
+        self.backend.set_playing(!self.is_output_paused());
+        tracelog!(Info, "AUDIO: Device backend set (sample rate: {})", self.backend.sample_rate());
+    }
+
+    /// Get output frames per second
+    #[inline]
+    #[must_use]
+    pub fn sample_rate(&self) -> usize {
+        self.backend.sample_rate()
+    }
+
+    /// Get voice pool, to query playing voices
+    #[inline]
+    #[must_use]
+    pub fn voices(&self) -> &VoicePool {
+        &self.voices
+    }
+
+    /// Set policy applied when playing a sound with all voices in use
+    #[inline]
+    pub fn set_steal_policy(&mut self, policy: StealPolicy) {
+        self.voices.set_policy(policy);
+    }
+
+    /// Play a sound on a free voice, `None` if the pool is full and policy rejects it
+    /// NOTE: Sound samples are shared, the sound can be dropped while playing
+    pub fn play(&mut self, sound: &Sound, params: PlayParams) -> Option<VoiceHandle> {
+        let handle = self.voices.play(params)?;
+        self.sources[handle.index()] = Some(VoiceSource { handle, sound: sound.clone(), cursor: 0.0 });
+        Some(handle)
+    }
+
+    /// Check if a voice is still playing
+    #[inline]
+    #[must_use]
+    pub fn is_playing(&self, handle: VoiceHandle) -> bool {
+        self.voices.is_playing(handle)
+    }
+
+    /// Set voice volume, no-op if the voice already ended
+    #[inline]
+    pub fn set_volume(&mut self, handle: VoiceHandle, volume: f32) {
+        self.voices.set_volume(handle, volume);
+    }
+
+    /// Set voice pitch, no-op if the voice already ended
+    #[inline]
+    pub fn set_pitch(&mut self, handle: VoiceHandle, pitch: f32) {
+        self.voices.set_pitch(handle, pitch);
+    }
+
+    /// Set voice pan, no-op if the voice already ended
+    #[inline]
+    pub fn set_pan(&mut self, handle: VoiceHandle, pan: f32) {
+        self.voices.set_pan(handle, pan);
+    }
+
+    /// Set voice spatial position, no-op if the voice already ended
+    #[inline]
+    pub fn set_position(&mut self, handle: VoiceHandle, position: Option<Vector2>) {
+        self.voices.set_position(handle, position);
+    }
+
+    /// Stop a voice, no-op if the voice already ended
+    pub fn stop(&mut self, handle: VoiceHandle) {
+        self.voices.stop(handle);
+        self.release_source(handle.index());
+    }
+
+    /// Stop all voices
+    pub fn stop_all(&mut self) {
+        self.voices.stop_all();
+        self.sources.fill(None);
+    }
+
+    /// Set master volume, applied to every voice
+    #[inline]
+    pub fn set_master_volume(&mut self, volume: f32) {
+        self.master_volume = volume.max(0.0);
+    }
+
+    /// Get master volume
+    #[inline]
+    #[must_use]
+    pub fn get_master_volume(&self) -> f32 {
+        self.master_volume
+    }
+
+    /// Pause or resume output, voices keep their playback position
+    pub fn set_paused(&mut self, paused: bool) {
+        let was_paused = self.is_output_paused();
+        self.paused = paused;
+        if self.is_output_paused() != was_paused {
+            self.backend.set_playing(was_paused);
+        }
+    }
+
+    /// Check if output is paused
+    #[inline]
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Set window background behavior, master volume and pause state set by the user are kept
+    /// NOTE: Set every frame from the window frame policy, `Keep` while in foreground
+    pub(crate) fn set_background(&mut self, background: BackgroundAudio) {
+        let was_paused = self.is_output_paused();
+        self.background = background;
+        if self.is_output_paused() != was_paused {
+            self.backend.set_playing(was_paused);
+        }
+    }
+
+    /// Check if output is silenced, by the user or by the window being in background
+    fn is_output_paused(&self) -> bool {
+        self.paused || self.background == BackgroundAudio::Pause
+    }
+
+    /// Master volume with background ducking applied
+    fn output_volume(&self) -> f32 {
+        match self.background {
+            BackgroundAudio::Duck(level) => self.master_volume * level.clamp(0.0, 1.0),
+            BackgroundAudio::Keep | BackgroundAudio::Pause => self.master_volume,
+        }
+    }
+
+    /// Drop the sound of a slot if its voice ended (stopped or stolen)
+    fn release_source(&mut self, index: usize) {
+        if let Some(source) = &self.sources[index] {
+            if !self.voices.is_playing(source.handle) {
+                self.sources[index] = None;
+            }
+        }
+    }
+
+    /// Mix playing voices into `out` (interleaved, `AUDIO_DEVICE_CHANNELS` per frame), releasing voices that end
+    /// NOTE: Called by the backend for every output buffer, silence is written while paused.
+    /// Spatial positions are kept for the caller, they don't affect mixing
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn mix(&mut self, out: &mut [f32]) {
+        out.fill(0.0);
+        if self.is_output_paused() {
+            return;
+        }
+
+        let sample_rate = self.backend.sample_rate() as f64;
+        let master_volume = self.output_volume();
+        for index in 0..self.sources.len() {
+            self.release_source(index);
+            let Some(source) = &mut self.sources[index] else { continue };
+            let Some(&params) = self.voices.params(source.handle) else { continue };
+
+            let step = f64::from(params.pitch.max(0.0)) * source.sound.sample_rate as f64 / sample_rate;
+            let volume = params.volume * master_volume;
+            let pan = params.pan.clamp(0.0, 1.0);
+            let gains = [volume * (2.0 - 2.0*pan).min(1.0), volume * (2.0*pan).min(1.0)];
+            let samples = source.sound.samples();
+
+            for frame in out.chunks_exact_mut(AUDIO_DEVICE_CHANNELS) {
+                let position = source.cursor as usize;
+                if position >= source.sound.frame_count {
+                    break;
+                }
+                let input = &samples[position*AUDIO_DEVICE_CHANNELS..][..AUDIO_DEVICE_CHANNELS];
+                for ((sample, input), gain) in frame.iter_mut().zip(input).zip(gains) {
+                    *sample += input * gain;
+                }
+                source.cursor += step;
+            }
+
+            if source.cursor as usize >= source.sound.frame_count {
+                let handle = source.handle;
+                self.voices.release(handle);
+                self.sources[index] = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mono sound at the device sample rate, copied to both channels
+    fn sound(samples: &[f32]) -> Sound {
+        Sound::from_wave(&Wave {
+            frame_count: samples.len(),
+            sample_rate: AUDIO_DEVICE_DEFAULT_SAMPLE_RATE,
+            sample_size: 32,
+            channels: 1,
+            data: samples.iter().flat_map(|sample| sample.to_le_bytes()).collect(),
+        })
+    }
+
+    /// Backend recording every playing state change
+    #[derive(Debug, Default)]
+    struct PlayingLog(std::rc::Rc<std::cell::RefCell<Vec<bool>>>);
+
+    impl AudioBackend for PlayingLog {
+        fn sample_rate(&self) -> usize {
+            AUDIO_DEVICE_DEFAULT_SAMPLE_RATE
+        }
+
+        fn set_playing(&mut self, playing: bool) {
+            self.0.borrow_mut().push(playing);
+        }
+    }
+
+    fn device(voices: usize, policy: StealPolicy) -> AudioDevice {
+        AudioDevice::new(Box::new(NullAudio), voices, policy)
+    }
+
+    fn mix(device: &mut AudioDevice, frames: usize) -> Vec<f32> {
+        let mut out = vec![f32::NAN; frames*AUDIO_DEVICE_CHANNELS];
+        device.mix(&mut out);
+        out
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_voice_ends_naturally() {
+        let mut device = device(4, StealPolicy::Oldest);
+        let handle = device.play(&sound(&[0.5, 0.25, 1.0]), PlayParams::default()).unwrap();
+        assert_eq!(mix(&mut device, 2), [0.5, 0.5, 0.25, 0.25]);
+        assert!(device.is_playing(handle));
+
+        // Last frame, then silence, voice is released
+        assert_eq!(mix(&mut device, 2), [1.0, 1.0, 0.0, 0.0]);
+        assert!(!device.is_playing(handle));
+        assert_eq!(device.voices().voices().count(), 0);
+
+        // Stale handle is ignored
+        device.set_volume(handle, 0.0);
+        device.set_pitch(handle, 2.0);
+        device.set_pan(handle, 0.0);
+        device.set_position(handle, Some(Vector2::ZERO));
+        device.stop(handle);
+        assert!(device.voices().params(handle).is_none());
+
+        // Empty sounds end on the first mix
+        let handle = device.play(&sound(&[]), PlayParams::default()).unwrap();
+        assert_eq!(mix(&mut device, 1), [0.0, 0.0]);
+        assert!(!device.is_playing(handle));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_voice_generation_safety() {
+        let mut device = device(1, StealPolicy::Oldest);
+        let beep = sound(&[1.0; 8]);
+        let first = device.play(&beep, PlayParams::default()).unwrap();
+        device.stop(first);
+        assert!(!device.is_playing(first));
+
+        // Recycled slot gets a new generation, the stale handle can't control it
+        let second = device.play(&beep, PlayParams { volume: 0.5, ..PlayParams::default() }).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(first.index(), second.index());
+        device.set_volume(first, 0.0);
+        device.stop(first);
+        assert!(device.is_playing(second));
+        assert_eq!(device.voices().params(second).unwrap().volume, 0.5);
+        assert_eq!(mix(&mut device, 1), [0.5, 0.5]);
+
+        // Same after natural end
+        assert_eq!(mix(&mut device, 8)[..2], [0.5, 0.5]);
+        assert!(!device.is_playing(second));
+        let third = device.play(&beep, PlayParams::default()).unwrap();
+        device.stop(second);
+        assert!(device.is_playing(third));
+        assert!(![first, second].contains(&third));
+    }
+
+    #[test]
+    fn test_voice_steal_oldest() {
+        let mut device = device(2, StealPolicy::Oldest);
+        let beep = sound(&[1.0; 8]);
+        let a = device.play(&beep, PlayParams::default()).unwrap();
+        let b = device.play(&beep, PlayParams::default()).unwrap();
+        let c = device.play(&beep, PlayParams::default()).unwrap();
+        assert_eq!((device.is_playing(a), device.is_playing(b), device.is_playing(c)), (false, true, true));
+        assert_eq!(c.index(), a.index());
+        let d = device.play(&beep, PlayParams::default()).unwrap();
+        assert_eq!((device.is_playing(b), device.is_playing(c), device.is_playing(d)), (false, true, true));
+    }
+
+    #[test]
+    fn test_voice_steal_quietest() {
+        let mut device = device(2, StealPolicy::Quietest);
+        let beep = sound(&[1.0; 8]);
+        let loud = device.play(&beep, PlayParams { volume: 0.9, ..PlayParams::default() }).unwrap();
+        let quiet = device.play(&beep, PlayParams { volume: 0.2, ..PlayParams::default() }).unwrap();
+        let new = device.play(&beep, PlayParams { volume: 0.5, ..PlayParams::default() }).unwrap();
+        assert_eq!((device.is_playing(loud), device.is_playing(quiet), device.is_playing(new)), (true, false, true));
+
+        // Volume changes are taken into account
+        device.set_volume(loud, 0.1);
+        let newest = device.play(&beep, PlayParams::default()).unwrap();
+        assert_eq!((device.is_playing(loud), device.is_playing(new), device.is_playing(newest)), (false, true, true));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_voice_steal_reject() {
+        let mut device = device(2, StealPolicy::Reject);
+        let beep = sound(&[1.0; 8]);
+        let a = device.play(&beep, PlayParams::default()).unwrap();
+        let b = device.play(&beep, PlayParams::default()).unwrap();
+        assert!(device.play(&beep, PlayParams::default()).is_none());
+        assert!(device.is_playing(a) && device.is_playing(b));
+        assert_eq!(mix(&mut device, 1), [2.0, 2.0]);
+
+        // Policy can be changed on a full pool
+        device.set_steal_policy(StealPolicy::Oldest);
+        assert!(device.play(&beep, PlayParams::default()).is_some());
+        assert!(!device.is_playing(a));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_device_mixing() {
+        let mut device = device(4, StealPolicy::Oldest);
+        let beep = sound(&[1.0, 0.5, 0.25, 0.125]);
+
+        // Volume, pan and master volume
+        let left = device.play(&beep, PlayParams { volume: 0.5, pan: 0.0, ..PlayParams::default() }).unwrap();
+        device.set_master_volume(2.0);
+        assert_eq!(mix(&mut device, 1), [1.0, 0.0]);
+        device.set_pan(left, 0.75);
+        assert_eq!(mix(&mut device, 1), [0.25, 0.5]);
+        device.stop_all();
+        device.set_master_volume(1.0);
+
+        // Pitch skips frames, pausing keeps the position
+        let fast = device.play(&beep, PlayParams { pitch: 2.0, ..PlayParams::default() }).unwrap();
+        device.set_paused(true);
+        assert_eq!(mix(&mut device, 2), [0.0; 4]);
+        assert!(device.is_paused() && device.is_playing(fast));
+        device.set_paused(false);
+        assert_eq!(mix(&mut device, 3), [1.0, 1.0, 0.25, 0.25, 0.0, 0.0]);
+        assert!(!device.is_playing(fast));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_background_audio() {
+        let log = PlayingLog::default();
+        let states = log.0.clone();
+        let mut device = AudioDevice::new(Box::new(log), 4, StealPolicy::Oldest);
+        let beep = sound(&[1.0; 16]);
+        device.play(&beep, PlayParams::default()).unwrap();
+        device.set_master_volume(0.5);
+
+        // Ducking scales output without touching the user master volume
+        device.set_background(BackgroundAudio::Duck(0.5));
+        assert_eq!(mix(&mut device, 1), [0.25, 0.25]);
+        assert_eq!(device.get_master_volume(), 0.5);
+        device.set_background(BackgroundAudio::Keep);
+        assert_eq!(mix(&mut device, 1), [0.5, 0.5]);
+
+        // Background pause stops the backend once and keeps the user pause state
+        device.set_background(BackgroundAudio::Pause);
+        device.set_background(BackgroundAudio::Pause);
+        assert_eq!(mix(&mut device, 1), [0.0, 0.0]);
+        assert!(!device.is_paused());
+        device.set_background(BackgroundAudio::Keep);
+        assert_eq!(*states.borrow(), [true, false, true]);
+
+        // Paused by the user stays paused when the window comes back to foreground
+        device.set_paused(true);
+        device.set_background(BackgroundAudio::Pause);
+        device.set_background(BackgroundAudio::Keep);
+        assert_eq!(mix(&mut device, 1), [0.0, 0.0]);
+        assert_eq!(*states.borrow(), [true, false, true, false]);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod device;
+pub mod voice;
+pub mod wave;
+pub mod music;
+mod wav;
@@ -0,0 +1,208 @@
+//! Music streaming, decoded while playing from a file or embedded bytes
+
+use std::{fs::File, io::{BufReader, Read, Seek}, path::Path};
+use crate::{config::AUDIO_DEVICE_CHANNELS, prelude::*, tracelog, utils::file_type};
+use super::{wav::WavReader, wave::to_device_channels};
+
+/// Seekable source of music data
+trait MusicSource: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> MusicSource for T {}
+
+/// Music, audio stream decoded in chunks while playing
+/// NOTE: Anything longer than ~10 seconds should be streamed instead of loaded as a [`Sound`]
+pub struct Music {
+    /// Total number of frames (considering channels)
+    pub frame_count: usize,
+    /// Frequency (samples per second)
+    pub sample_rate: usize,
+    /// Music looping enable
+    pub looping: bool,
+    decoder: WavReader<Box<dyn MusicSource>>,
+    /// Decoded frames, in the source channel count
+    buffer: Vec<f32>,
+}
+
+impl std::fmt::Debug for Music {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Music")
+            .field("frame_count", &self.frame_count)
+            .field("sample_rate", &self.sample_rate)
+            .field("looping", &self.looping)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Music {
+    /// Load music stream from file, the file is read while playing
+    #[must_use]
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        let path = path.as_ref();
+        let Ok(file) = File::open(path) else {
+            tracelog!(Warning, "FILEIO: [{}] Failed to open file", path.display());
+            return None;
+        };
+        Self::from_source(&file_type(path), Box::new(BufReader::new(file)))
+    }
+
+    /// Load music stream from data, `file_type` refers to extension: i.e. `.wav`
+    /// NOTE: Data is decoded while playing, static data is never copied
+    ///
+    /// Streaming embedded music:
+    /// ```ignore
+    /// let music = Music::load_from_memory(".wav", include_bytes!("../resources/music.wav"));
+    /// ```
+    #[must_use]
+    pub fn load_from_memory(file_type: &str, data: impl Into<MemoryData>) -> Option<Self> {
+        Self::from_source(file_type, Box::new(data.into().reader()))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_source(file_type: &str, source: Box<dyn MusicSource>) -> Option<Self> {
+        if !(cfg!(feature = "support_fileformat_wav") && file_type.eq_ignore_ascii_case(".wav")) {
+            tracelog!(Warning, "STREAM: [{file_type}] File format not supported");
+            return None;
+        }
+
+        let Ok(decoder) = WavReader::new(source) else {
+            tracelog!(Warning, "STREAM: Failed to load music data");
+            return None;
+        };
+
+        let music = Self {
+            frame_count: decoder.frame_count as usize,
+            sample_rate: decoder.sample_rate,
+            looping: true, // Looping enabled by default
+            decoder,
+            buffer: Vec::new(),
+        };
+        tracelog!(Info, "STREAM: Data loaded successfully ({} Hz, {} channels, {} frames)", music.sample_rate, music.decoder.channels, music.frame_count);
+        Some(music)
+    }
+
+    /// Decode the next frames in the device format: interleaved 32 bit float, `AUDIO_DEVICE_CHANNELS` channels
+    /// NOTE: Looping music restarts at the end, otherwise less frames than requested are returned at the end.
+    /// Returns the number of frames written
+    pub fn read_frames(&mut self, out: &mut [f32]) -> usize {
+        let frames = out.len() / AUDIO_DEVICE_CHANNELS;
+        let channels = self.decoder.channels;
+        let mut written = 0;
+
+        while written < frames {
+            self.buffer.resize((frames - written) * channels, 0.0);
+            let read = match self.decoder.read_frames(&mut self.buffer) {
+                Ok(read) => read,
+                Err(e) => {
+                    tracelog!(Warning, "STREAM: Failed to decode music data: {e}");
+                    break;
+                }
+            };
+
+            if read == 0 {
+                if self.looping && self.frame_count > 0 && self.decoder.seek(0).is_ok() {
+                    continue;
+                }
+                break;
+            }
+
+            to_device_channels(&self.buffer[..read * channels], channels, &mut out[written * AUDIO_DEVICE_CHANNELS..(written + read) * AUDIO_DEVICE_CHANNELS]);
+            written += read;
+        }
+        written
+    }
+
+    /// Seek music to a position (in seconds), clamped to the music length
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+    pub fn seek(&mut self, position: Seconds) {
+        let frame = (position.max(0.0) * self.sample_rate as f32) as u64;
+        if let Err(e) = self.decoder.seek(frame) {
+            tracelog!(Warning, "STREAM: Failed to seek music: {e}");
+        }
+    }
+
+    /// Get music time length (in seconds)
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn time_length(&self) -> Seconds {
+        self.frame_count as f32 / self.sample_rate as f32
+    }
+
+    /// Get current music time played (in seconds)
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn time_played(&self) -> Seconds {
+        self.decoder.position() as f32 / self.sample_rate as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use crate::{audio::wav::tests::wav_16, utils::tests::write_temp_file};
+    use super::*;
+
+    fn read_all(music: &mut Music) -> Vec<f32> {
+        let mut samples = Vec::new();
+        let mut chunk = [0.0; 6];
+        loop {
+            let frames = music.read_frames(&mut chunk);
+            if frames == 0 {
+                break samples;
+            }
+            samples.extend(&chunk[..frames * AUDIO_DEVICE_CHANNELS]);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_music_memory_and_file_parity() {
+        let samples: Vec<i16> = (0..16).map(|i| i * 1024).collect();
+        let wav = wav_16(1, 8, &samples);
+        let sound = Sound::load_from_memory(".wav", &wav).unwrap();
+
+        let path = write_temp_file("music_parity.wav", &wav);
+        let mut from_file = Music::load(&path).unwrap();
+        let mut from_static = Music::load_from_memory(".wav", &*Vec::leak(wav.clone())).unwrap();
+        let mut from_shared = Music::load_from_memory(".wav", Arc::<[u8]>::from(wav)).unwrap();
+
+        for music in [&mut from_file, &mut from_static, &mut from_shared] {
+            music.looping = false;
+            assert_eq!(music.frame_count, 16);
+            assert_eq!(music.time_length(), 2.0);
+            assert_eq!(read_all(music), sound.samples());
+            assert_eq!(music.time_played(), 2.0);
+        }
+        drop(from_file);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_music_looping_and_seek() {
+        let wav = wav_16(2, 4, &[0, 0, 8192, 8192, 16384, 16384, 24576, 24576]);
+        let mut music = Music::load_from_memory(".wav", wav).unwrap();
+        assert!(music.looping);
+
+        // Looping wraps around to the start
+        let mut out = [0.0; 12];
+        assert_eq!(music.read_frames(&mut out), 6);
+        assert_eq!(out, [0.0, 0.0, 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 0.0, 0.0, 0.25, 0.25]);
+        assert_eq!(music.time_played(), 0.5);
+
+        music.seek(0.75);
+        music.looping = false;
+        assert_eq!(music.read_frames(&mut out), 1);
+        assert_eq!(out[..2], [0.75, 0.75]);
+        assert_eq!(music.read_frames(&mut out), 0);
+
+        music.seek(100.0);
+        assert_eq!(music.time_played(), 1.0);
+    }
+
+    #[test]
+    fn test_music_unsupported() {
+        assert!(Music::load_from_memory(".mp3", wav_16(1, 8000, &[0])).is_none());
+        assert!(Music::load_from_memory(".wav", b"RIFF").is_none());
+        assert!(Music::load("missing_file.wav").is_none());
+    }
+}
@@ -0,0 +1,236 @@
+//! Voice management, generation-counted handles for fire-and-forget sound playback
+
+use crate::{config::MAX_AUDIO_BUFFER_POOL_CHANNELS, prelude::*, tracelog};
+
+/// Handle to a playing voice
+/// NOTE: Handles become stale once the voice ends, stale handles are silently ignored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VoiceHandle {
+    index: u32,
+    generation: u32,
+}
+
+impl VoiceHandle {
+    /// Pool slot used by the voice
+    #[inline]
+    pub(crate) const fn index(self) -> usize {
+        self.index as usize
+    }
+}
+
+/// Sound playback parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayParams {
+    /// Volume, 1.0 is max level
+    pub volume: f32,
+    /// Pitch, 1.0 is base level
+    pub pitch: f32,
+    /// Pan, 0.5 is center
+    pub pan: f32,
+    /// Position for spatial playback, `None` for non-spatial sounds
+    pub spatial: Option<Vector2>,
+}
+
+impl Default for PlayParams {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            pitch: 1.0,
+            pan: 0.5,
+            spatial: None,
+        }
+    }
+}
+
+/// Policy applied when playing a sound with all voices in use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StealPolicy {
+    /// Stop the voice that started first
+    #[default]
+    Oldest,
+    /// Stop the voice with the lowest volume
+    Quietest,
+    /// Don't play the new sound
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Voice {
+    params: PlayParams,
+    /// Play order, used to find the oldest voice
+    sequence: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct VoiceSlot {
+    /// Incremented every time the slot is released
+    generation: u32,
+    voice: Option<Voice>,
+}
+
+/// Fixed-capacity pool of voices
+/// NOTE: Audio backend releases voices when playback ends with [`VoicePool::release`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoicePool {
+    slots: Vec<VoiceSlot>,
+    policy: StealPolicy,
+    next_sequence: u64,
+}
+
+impl Default for VoicePool {
+    fn default() -> Self {
+        Self::new(MAX_AUDIO_BUFFER_POOL_CHANNELS, StealPolicy::default())
+    }
+}
+
+impl VoicePool {
+    /// Create pool with the given number of voices
+    #[must_use]
+    pub fn new(capacity: usize, policy: StealPolicy) -> Self {
+        Self {
+            slots: vec![VoiceSlot { generation: 0, voice: None }; capacity],
+            policy,
+            next_sequence: 0,
+        }
+    }
+
+    /// Maximum number of simultaneous voices
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Policy applied when the pool is full
+    #[inline]
+    #[must_use]
+    pub const fn policy(&self) -> StealPolicy {
+        self.policy
+    }
+
+    /// Set policy applied when the pool is full
+    #[inline]
+    pub fn set_policy(&mut self, policy: StealPolicy) {
+        self.policy = policy;
+    }
+
+    fn voice(&self, handle: VoiceHandle) -> Option<&Voice> {
+        self.slots.get(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.voice.as_ref())
+    }
+
+    fn voice_mut(&mut self, handle: VoiceHandle) -> Option<&mut Voice> {
+        self.slots.get_mut(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.voice.as_mut())
+    }
+
+    /// Find a slot for a new voice, stealing one according to policy if required
+    fn acquire(&mut self) -> Option<usize> {
+        if let Some(index) = self.slots.iter().position(|slot| slot.voice.is_none()) {
+            return Some(index);
+        }
+
+        let busy = self.slots.iter().enumerate()
+            .filter_map(|(index, slot)| slot.voice.map(|voice| (index, voice)));
+        let stolen = match self.policy {
+            StealPolicy::Oldest => busy.min_by_key(|(_, voice)| voice.sequence),
+            StealPolicy::Quietest => busy.min_by(|(_, a), (_, b)| a.params.volume.total_cmp(&b.params.volume)),
+            StealPolicy::Reject => None,
+        };
+        let (index, _) = stolen?;
+        tracelog!(Debug, "AUDIO: Voice [{index}] stolen");
+        self.release_slot(index);
+        Some(index)
+    }
+
+    fn release_slot(&mut self, index: usize) {
+        let slot = &mut self.slots[index];
+        if slot.voice.take().is_some() {
+            slot.generation = slot.generation.wrapping_add(1);
+        }
+    }
+
+    /// Start a voice, `None` if the pool is full and policy rejects it
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn play(&mut self, params: PlayParams) -> Option<VoiceHandle> {
+        let index = self.acquire()?;
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        let slot = &mut self.slots[index];
+        slot.voice = Some(Voice { params, sequence });
+        Some(VoiceHandle { index: index as u32, generation: slot.generation })
+    }
+
+    /// Check if a voice is still playing
+    #[inline]
+    #[must_use]
+    pub fn is_playing(&self, handle: VoiceHandle) -> bool {
+        self.voice(handle).is_some()
+    }
+
+    /// Get voice playback parameters, `None` if the voice already ended
+    #[must_use]
+    pub fn params(&self, handle: VoiceHandle) -> Option<&PlayParams> {
+        self.voice(handle).map(|voice| &voice.params)
+    }
+
+    /// Set voice volume, no-op if the voice already ended
+    pub fn set_volume(&mut self, handle: VoiceHandle, volume: f32) {
+        if let Some(voice) = self.voice_mut(handle) {
+            voice.params.volume = volume;
+        }
+    }
+
+    /// Set voice pitch, no-op if the voice already ended
+    pub fn set_pitch(&mut self, handle: VoiceHandle, pitch: f32) {
+        if let Some(voice) = self.voice_mut(handle) {
+            voice.params.pitch = pitch;
+        }
+    }
+
+    /// Set voice pan, no-op if the voice already ended
+    pub fn set_pan(&mut self, handle: VoiceHandle, pan: f32) {
+        if let Some(voice) = self.voice_mut(handle) {
+            voice.params.pan = pan;
+        }
+    }
+
+    /// Set voice spatial position, no-op if the voice already ended
+    pub fn set_position(&mut self, handle: VoiceHandle, position: Option<Vector2>) {
+        if let Some(voice) = self.voice_mut(handle) {
+            voice.params.spatial = position;
+        }
+    }
+
+    /// Stop a voice, no-op if the voice already ended
+    pub fn stop(&mut self, handle: VoiceHandle) {
+        if self.is_playing(handle) {
+            self.release_slot(handle.index as usize);
+        }
+    }
+
+    /// Release a voice whose playback ended, invalidating its handles
+    /// NOTE: Called by the audio backend, equivalent to [`VoicePool::stop`]
+    #[inline]
+    pub fn release(&mut self, handle: VoiceHandle) {
+        self.stop(handle);
+    }
+
+    /// Stop all voices
+    pub fn stop_all(&mut self) {
+        for index in 0..self.slots.len() {
+            self.release_slot(index);
+        }
+    }
+
+    /// Get playing voices
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn voices(&self) -> impl Iterator<Item = (VoiceHandle, &PlayParams)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot.voice.as_ref().map(|voice| (VoiceHandle { index: index as u32, generation: slot.generation }, &voice.params))
+        })
+    }
+}
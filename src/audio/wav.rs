@@ -0,0 +1,302 @@
+//! WAV (RIFF) decoding: PCM 8/16/24/32 bit and IEEE float 32/64 bit samples, read frame by frame
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+const WAVE_FORMAT_PCM: u16 = 0x0001;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x0003;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// Sample encoding of a WAV file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SampleEncoding {
+    /// Unsigned 8 bit
+    U8,
+    /// Signed little endian 16, 24 or 32 bit
+    Int(usize),
+    /// Little endian 32 or 64 bit float
+    Float(usize),
+}
+
+impl SampleEncoding {
+    fn from_format(format_tag: u16, bits: u16) -> Option<Self> {
+        match (format_tag, bits) {
+            (WAVE_FORMAT_PCM, 8) => Some(Self::U8),
+            (WAVE_FORMAT_PCM, 16 | 24 | 32) => Some(Self::Int(usize::from(bits / 8))),
+            (WAVE_FORMAT_IEEE_FLOAT, 32 | 64) => Some(Self::Float(usize::from(bits / 8))),
+            _ => None,
+        }
+    }
+
+    /// Bytes per sample
+    fn size(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::Int(size) | Self::Float(size) => size,
+        }
+    }
+
+    /// Decode one sample, normalized to `[-1..1]`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn decode(self, bytes: &[u8]) -> f32 {
+        match self {
+            Self::U8 => (f32::from(bytes[0]) - 128.0) / 128.0,
+            Self::Int(size) => {
+                // Place sample in the high bytes of an i32, the sign comes with it
+                let mut value = [0u8; 4];
+                value[4 - size..].copy_from_slice(&bytes[..size]);
+                i32::from_le_bytes(value) as f32 / 2_147_483_648.0
+            }
+            Self::Float(4) => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            Self::Float(_) => f64::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]) as f32,
+        }
+    }
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// WAV decoder over a seekable reader, only the sample data is read after the header
+#[derive(Debug)]
+pub(crate) struct WavReader<R> {
+    reader: R,
+    encoding: SampleEncoding,
+    /// Number of channels
+    pub(crate) channels: usize,
+    /// Frequency (samples per second)
+    pub(crate) sample_rate: usize,
+    /// Total number of frames (one sample per channel)
+    pub(crate) frame_count: u64,
+    /// Offset of the first frame in the reader
+    data_start: u64,
+    /// Next frame to read
+    position: u64,
+}
+
+impl<R: Read + Seek> WavReader<R> {
+    /// Parse WAV header, leaving the reader at the first frame
+    /// # Errors
+    /// Returns an error if the data is not a supported WAV file, or the reader fails
+    pub(crate) fn new(mut reader: R) -> io::Result<Self> {
+        let header: [u8; 12] = read_array(&mut reader)?;
+        if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+            return Err(invalid_data("not a RIFF WAVE file"));
+        }
+
+        let mut format = None;
+        loop {
+            let chunk: [u8; 8] = read_array(&mut reader).map_err(|_| invalid_data("missing data chunk"))?;
+            let chunk_size = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+            match &chunk[0..4] {
+                b"fmt " => {
+                    if chunk_size < 16 {
+                        return Err(invalid_data("fmt chunk too small"));
+                    }
+                    let mut fmt = vec![0; chunk_size as usize];
+                    reader.read_exact(&mut fmt)?;
+                    let mut format_tag = u16::from_le_bytes([fmt[0], fmt[1]]);
+                    let channels = u16::from_le_bytes([fmt[2], fmt[3]]);
+                    let sample_rate = u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]);
+                    let bits = u16::from_le_bytes([fmt[14], fmt[15]]);
+                    if format_tag == WAVE_FORMAT_EXTENSIBLE && fmt.len() >= 26 {
+                        // Sub-format GUID starts with the actual format tag
+                        format_tag = u16::from_le_bytes([fmt[24], fmt[25]]);
+                    }
+                    let encoding = SampleEncoding::from_format(format_tag, bits)
+                        .ok_or_else(|| invalid_data("sample format not supported"))?;
+                    if channels == 0 || sample_rate == 0 {
+                        return Err(invalid_data("invalid channels or sample rate"));
+                    }
+                    format = Some((encoding, usize::from(channels), sample_rate as usize));
+                }
+                b"data" => {
+                    let (encoding, channels, sample_rate) = format.ok_or_else(|| invalid_data("data chunk before fmt chunk"))?;
+                    let data_start = reader.stream_position()?;
+                    // NOTE: Truncated files (or streamed ones, with size 0xFFFFFFFF) end where the data ends
+                    let data_end = reader.seek(SeekFrom::End(0))?;
+                    let data_size = u64::from(chunk_size).min(data_end - data_start);
+                    reader.seek(SeekFrom::Start(data_start))?;
+
+                    return Ok(Self {
+                        reader,
+                        encoding,
+                        channels,
+                        sample_rate,
+                        frame_count: data_size / (encoding.size() * channels) as u64,
+                        data_start,
+                        position: 0,
+                    });
+                }
+                _ => {
+                    // Chunks are padded to an even size
+                    reader.seek(SeekFrom::Current(i64::from(chunk_size) + i64::from(chunk_size & 1)))?;
+                }
+            }
+        }
+    }
+
+    /// Bytes per frame
+    fn frame_size(&self) -> usize {
+        self.encoding.size() * self.channels
+    }
+
+    /// Read interleaved samples normalized to `[-1..1]`, returns the number of frames read
+    /// NOTE: Reads up to `samples.len() / channels` frames, 0 at the end of data
+    /// # Errors
+    /// Returns an error if the reader fails
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn read_frames(&mut self, samples: &mut [f32]) -> io::Result<usize> {
+        let frames = ((samples.len() / self.channels) as u64).min(self.frame_count - self.position) as usize;
+        let mut bytes = vec![0; frames * self.frame_size()];
+        self.reader.read_exact(&mut bytes)?;
+        self.position += frames as u64;
+
+        let sample_size = self.encoding.size();
+        for (sample, bytes) in samples.iter_mut().zip(bytes.chunks_exact(sample_size)) {
+            *sample = self.encoding.decode(bytes);
+        }
+        Ok(frames)
+    }
+
+    /// Move to a frame, clamped to the end of data
+    /// # Errors
+    /// Returns an error if the reader fails
+    pub(crate) fn seek(&mut self, frame: u64) -> io::Result<()> {
+        let frame = frame.min(self.frame_count);
+        self.reader.seek(SeekFrom::Start(self.data_start + frame * self.frame_size() as u64))?;
+        self.position = frame;
+        Ok(())
+    }
+
+    /// Next frame to read
+    pub(crate) fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::Cursor;
+    use super::*;
+
+    /// Build a WAV file with a fmt chunk of the given format, an unknown odd-sized chunk and the data chunk
+    pub(crate) fn wav_bytes(format_tag: u16, channels: u16, sample_rate: u32, bits: u16, data: &[u8]) -> Vec<u8> {
+        let mut fmt = Vec::new();
+        fmt.extend(format_tag.to_le_bytes());
+        fmt.extend(channels.to_le_bytes());
+        fmt.extend(sample_rate.to_le_bytes());
+        fmt.extend((sample_rate * u32::from(channels * bits / 8)).to_le_bytes());
+        fmt.extend((channels * bits / 8).to_le_bytes());
+        fmt.extend(bits.to_le_bytes());
+
+        let mut body = b"WAVE".to_vec();
+        for (id, chunk) in [(b"fmt ", fmt.as_slice()), (b"LIST", &[1, 2, 3]), (b"data", data)] {
+            body.extend(id);
+            body.extend(u32::try_from(chunk.len()).unwrap().to_le_bytes());
+            body.extend(chunk);
+            if chunk.len() % 2 == 1 {
+                body.push(0);
+            }
+        }
+
+        let mut wav = b"RIFF".to_vec();
+        wav.extend(u32::try_from(body.len()).unwrap().to_le_bytes());
+        wav.extend(body);
+        wav
+    }
+
+    /// Build a 16 bit PCM WAV file
+    pub(crate) fn wav_16(channels: u16, sample_rate: u32, samples: &[i16]) -> Vec<u8> {
+        let data: Vec<u8> = samples.iter().flat_map(|sample| sample.to_le_bytes()).collect();
+        wav_bytes(WAVE_FORMAT_PCM, channels, sample_rate, 16, &data)
+    }
+
+    type Decoded = (WavReader<Cursor<Vec<u8>>>, Vec<f32>);
+
+    fn decode(wav: &[u8]) -> io::Result<Decoded> {
+        let mut reader = WavReader::new(Cursor::new(wav.to_vec()))?;
+        let mut samples = vec![0.0; usize::try_from(reader.frame_count).unwrap() * reader.channels];
+        let frames = reader.read_frames(&mut samples)?;
+        assert_eq!(frames as u64, reader.frame_count);
+        Ok((reader, samples))
+    }
+
+    #[test]
+    fn test_decode_pcm16() {
+        let (reader, samples) = decode(&wav_16(2, 44100, &[0, 16384, -32768, 32767])).unwrap();
+        assert_eq!((reader.channels, reader.sample_rate, reader.frame_count), (2, 44100, 2));
+        assert_eq!(samples[..3], [0.0, 0.5, -1.0]);
+        assert!((samples[3] - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_decode_sample_encodings() {
+        let expected = [0.5, -0.5];
+        let cases = [
+            wav_bytes(WAVE_FORMAT_PCM, 1, 8000, 8, &[192, 64]),
+            wav_bytes(WAVE_FORMAT_PCM, 1, 8000, 24, &[0, 0, 0x40, 0, 0, 0xC0]),
+            wav_bytes(WAVE_FORMAT_PCM, 1, 8000, 32, &[0, 0, 0, 0x40, 0, 0, 0, 0xC0]),
+            wav_bytes(WAVE_FORMAT_IEEE_FLOAT, 1, 8000, 32, &[0.5f32.to_le_bytes(), (-0.5f32).to_le_bytes()].concat()),
+            wav_bytes(WAVE_FORMAT_IEEE_FLOAT, 1, 8000, 64, &[0.5f64.to_le_bytes(), (-0.5f64).to_le_bytes()].concat()),
+        ];
+        for wav in cases {
+            assert_eq!(decode(&wav).unwrap().1, expected);
+        }
+    }
+
+    #[test]
+    fn test_decode_extensible() {
+        let mut wav = wav_16(1, 8000, &[16384]);
+        // Rewrite fmt chunk as WAVE_FORMAT_EXTENSIBLE with a PCM sub-format
+        let mut extension = vec![22, 0, 16, 0, 4, 0, 0, 0];
+        extension.extend(WAVE_FORMAT_PCM.to_le_bytes());
+        extension.extend([0; 14]);
+        wav[16..20].copy_from_slice(&40u32.to_le_bytes());
+        wav[20..22].copy_from_slice(&WAVE_FORMAT_EXTENSIBLE.to_le_bytes());
+        wav.splice(36..36, extension);
+        assert_eq!(decode(&wav).unwrap().1, [0.5]);
+    }
+
+    #[test]
+    fn test_decode_truncated_data() {
+        let mut wav = wav_16(2, 8000, &[1, 2, 3, 4]);
+        wav.truncate(wav.len() - 3);
+        let (reader, samples) = decode(&wav).unwrap();
+        assert_eq!(reader.frame_count, 1);
+        assert_eq!(samples.len(), 2);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let wav = wav_16(1, 8000, &[0]);
+        assert!(WavReader::new(Cursor::new(&wav[..20])).is_err());
+        assert!(WavReader::new(Cursor::new(b"RIFX\0\0\0\0WAVE".as_slice())).is_err());
+        assert!(WavReader::new(Cursor::new(wav_bytes(0x0002, 1, 8000, 4, &[0]))).is_err());
+        assert!(WavReader::new(Cursor::new(wav_bytes(WAVE_FORMAT_PCM, 0, 8000, 16, &[0, 0]))).is_err());
+
+        // Data chunk before fmt chunk
+        let mut swapped = b"RIFF\0\0\0\0WAVEdata\x02\0\0\0\0\0".to_vec();
+        swapped.extend(&wav[12..36]);
+        assert!(WavReader::new(Cursor::new(swapped)).is_err());
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut reader = WavReader::new(Cursor::new(wav_16(1, 8000, &[0, 8192, 16384, 24576]))).unwrap();
+        reader.seek(2).unwrap();
+        assert_eq!(reader.position(), 2);
+        let mut samples = [0.0; 4];
+        assert_eq!(reader.read_frames(&mut samples).unwrap(), 2);
+        assert_eq!(samples[..2], [0.5, 0.75]);
+        assert_eq!(reader.read_frames(&mut samples).unwrap(), 0);
+
+        reader.seek(10).unwrap();
+        assert_eq!(reader.position(), 4);
+    }
+}
@@ -0,0 +1,198 @@
+//! Wave and sound loading, from files or embedded bytes
+
+use std::{path::Path, sync::Arc};
+use crate::{config::AUDIO_DEVICE_CHANNELS, tracelog, utils::load_file_data};
+use super::wav::WavReader;
+
+/// Wave, audio wave data
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Wave {
+    /// Total number of frames (considering channels)
+    pub frame_count: usize,
+    /// Frequency (samples per second)
+    pub sample_rate: usize,
+    /// Bit depth (bits per sample): 8, 16, 32 (float)
+    pub sample_size: usize,
+    /// Number of channels (1-mono, 2-stereo, ...)
+    pub channels: usize,
+    /// Buffer data, little endian samples
+    pub data: Vec<u8>,
+}
+
+impl Wave {
+    /// Load wave data from file
+    #[must_use]
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        let (file_type, data) = load_file_data(path.as_ref())?;
+        Self::load_from_memory(&file_type, &data)
+    }
+
+    /// Load wave from memory buffer, `file_type` refers to extension: i.e. `.wav`
+    /// NOTE: WAV samples are converted to 16 bit
+    ///
+    /// Loading an embedded wave:
+    /// ```ignore
+    /// let wave = Wave::load_from_memory(".wav", include_bytes!("../resources/sound.wav"));
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn load_from_memory(file_type: &str, data: &[u8]) -> Option<Self> {
+        if !(cfg!(feature = "support_fileformat_wav") && file_type.eq_ignore_ascii_case(".wav")) {
+            tracelog!(Warning, "WAVE: Data format not supported");
+            return None;
+        }
+
+        let decoded = WavReader::new(std::io::Cursor::new(data)).and_then(|mut reader| {
+            let mut samples = vec![0.0; reader.frame_count as usize * reader.channels];
+            reader.read_frames(&mut samples)?;
+            Ok((reader, samples))
+        });
+        let Ok((reader, samples)) = decoded else {
+            tracelog!(Warning, "WAVE: Failed to load WAV data");
+            return None;
+        };
+
+        let wave = Self {
+            frame_count: reader.frame_count as usize,
+            sample_rate: reader.sample_rate,
+            sample_size: 16,
+            channels: reader.channels,
+            data: samples.into_iter()
+                .flat_map(|sample| ((sample * 32768.0).clamp(-32768.0, 32767.0) as i16).to_le_bytes())
+                .collect(),
+        };
+        tracelog!(Info, "WAVE: Data loaded successfully ({} Hz, {} bit, {} channels)", wave.sample_rate, wave.sample_size, wave.channels);
+        Some(wave)
+    }
+
+    /// Get interleaved samples, normalized to `[-1..1]`
+    /// NOTE: Unsupported sample sizes yield no samples
+    pub fn samples(&self) -> impl Iterator<Item = f32> + '_ {
+        let supported = matches!(self.sample_size, 8 | 16 | 32);
+        self.data.chunks_exact((self.sample_size / 8).max(1))
+            .take_while(move |_| supported)
+            .map(|bytes| match *bytes {
+                [byte] => (f32::from(byte) - 128.0) / 128.0,
+                [low, high] => f32::from(i16::from_le_bytes([low, high])) / 32768.0,
+                [b0, b1, b2, b3] => f32::from_le_bytes([b0, b1, b2, b3]),
+                _ => 0.0,
+            })
+    }
+}
+
+/// Convert interleaved frames to the device channel count
+/// NOTE: Mono is copied to every channel, extra channels are dropped
+pub(crate) fn to_device_channels(samples: &[f32], channels: usize, out: &mut [f32]) {
+    for (frame, out) in samples.chunks_exact(channels).zip(out.chunks_exact_mut(AUDIO_DEVICE_CHANNELS)) {
+        for (channel, sample) in out.iter_mut().enumerate() {
+            *sample = frame[channel.min(channels - 1)];
+        }
+    }
+}
+
+/// Sound, audio data converted to the device format: 32 bit float, `AUDIO_DEVICE_CHANNELS` channels
+/// NOTE: Samples are shared between clones, keep the sample rate of the source wave
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sound {
+    /// Total number of frames (considering channels)
+    pub frame_count: usize,
+    /// Frequency (samples per second)
+    pub sample_rate: usize,
+    /// Interleaved samples
+    data: Arc<[f32]>,
+}
+
+impl Sound {
+    /// Load sound from file
+    #[must_use]
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        Wave::load(path).map(|wave| Self::from_wave(&wave))
+    }
+
+    /// Load sound from memory buffer, `file_type` refers to extension: i.e. `.wav`
+    ///
+    /// Loading an embedded sound:
+    /// ```ignore
+    /// let sound = Sound::load_from_memory(".wav", include_bytes!("../resources/coin.wav"));
+    /// ```
+    #[must_use]
+    pub fn load_from_memory(file_type: &str, data: &[u8]) -> Option<Self> {
+        Wave::load_from_memory(file_type, data).map(|wave| Self::from_wave(&wave))
+    }
+
+    /// Load sound from wave data, converted to the device format
+    #[must_use]
+    pub fn from_wave(wave: &Wave) -> Self {
+        let samples: Vec<f32> = wave.samples().collect();
+        let frame_count = samples.len().checked_div(wave.channels).unwrap_or(0);
+        let mut data = vec![0.0; frame_count * AUDIO_DEVICE_CHANNELS];
+        if frame_count > 0 {
+            to_device_channels(&samples, wave.channels, &mut data);
+        }
+        Self { frame_count, sample_rate: wave.sample_rate, data: data.into() }
+    }
+
+    /// Get interleaved samples, `AUDIO_DEVICE_CHANNELS` per frame
+    #[inline]
+    #[must_use]
+    pub fn samples(&self) -> &[f32] {
+        &self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{audio::wav::tests::wav_16, utils::tests::write_temp_file};
+    use super::*;
+
+    #[test]
+    fn test_wave_file_parity() {
+        let wav = wav_16(2, 22050, &[0, 100, -100, 32767, -32768, 5]);
+        let path = write_temp_file("wave_parity.wav", &wav);
+        let from_file = Wave::load(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let from_memory = Wave::load_from_memory(".WAV", &wav).unwrap();
+        assert_eq!(from_file, from_memory);
+        assert_eq!((from_memory.frame_count, from_memory.sample_rate, from_memory.sample_size, from_memory.channels), (3, 22050, 16, 2));
+        assert_eq!(from_memory.data, wav[wav.len() - 12..]);
+    }
+
+    #[test]
+    fn test_wave_unsupported() {
+        let wav = wav_16(1, 8000, &[0]);
+        assert!(Wave::load_from_memory(".ogg", &wav).is_none());
+        assert!(Wave::load_from_memory(".wav", &wav[..30]).is_none());
+        assert!(Wave::load("missing_file.wav").is_none());
+    }
+
+    #[test]
+    fn test_wave_samples() {
+        let wave = |sample_size, data: Vec<u8>| Wave { frame_count: 1, sample_rate: 8000, sample_size, channels: 1, data };
+        assert_eq!(wave(8, vec![64]).samples().collect::<Vec<_>>(), [-0.5]);
+        assert_eq!(wave(16, 16384i16.to_le_bytes().to_vec()).samples().collect::<Vec<_>>(), [0.5]);
+        assert_eq!(wave(32, 0.25f32.to_le_bytes().to_vec()).samples().collect::<Vec<_>>(), [0.25]);
+        assert_eq!(wave(24, vec![0; 3]).samples().count(), 0);
+    }
+
+    #[test]
+    fn test_sound_file_parity() {
+        let wav = wav_16(1, 8000, &[16384, -16384, 0]);
+        let path = write_temp_file("sound_parity.wav", &wav);
+        let from_file = Sound::load(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let from_memory = Sound::load_from_memory(".wav", &wav).unwrap();
+        assert_eq!(from_file, from_memory);
+        assert_eq!((from_memory.frame_count, from_memory.sample_rate), (3, 8000));
+        // Mono is copied to both device channels
+        assert_eq!(from_memory.samples(), [0.5, 0.5, -0.5, -0.5, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_sound_from_multichannel_wave() {
+        let wave = Wave::load_from_memory(".wav", &wav_16(3, 8000, &[16384, -16384, 8192])).unwrap();
+        assert_eq!(Sound::from_wave(&wave).samples(), [0.5, -0.5]);
+        assert_eq!(Sound::from_wave(&Wave::default()).frame_count, 0);
+    }
+}
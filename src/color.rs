@@ -1,7 +1,8 @@
 use crate::prelude::*;
 
 /// Color, 4 components, R8G8B8A8 (32bit)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
 #[must_use]
 pub struct Color {
     /// Color red value
@@ -14,6 +15,9 @@ pub struct Color {
     pub a: u8,
 }
 
+// Color must stay layout compatible with R8G8B8A8 pixel data
+const _: () = assert!(std::mem::size_of::<Color>() == 4 && std::mem::align_of::<Color>() == 1);
+
 /// Constructs an opaque RGB color
 #[inline]
 pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
@@ -74,10 +78,545 @@ impl Color {
     pub fn alpha(self, a: Percent) -> Self {
         todo!()
     }
+
+    /// Get color multiplied with another color
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn tint(self, tint: Self) -> Self {
+        let mul = |c: u8, t: u8| (f32::from(c) / 255.0 * (f32::from(t) / 255.0) * 255.0) as u8;
+        Self {
+            r: mul(self.r, tint.r),
+            g: mul(self.g, tint.g),
+            b: mul(self.b, tint.b),
+            a: mul(self.a, tint.a),
+        }
+    }
+
+    /// Get color with RGB channels multiplied by alpha
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn premultiply(self) -> Self {
+        let alpha = f32::from(self.a) / 255.0;
+        Self {
+            r: (f32::from(self.r) * alpha) as u8,
+            g: (f32::from(self.g) * alpha) as u8,
+            b: (f32::from(self.b) * alpha) as u8,
+            a: self.a,
+        }
+    }
+
+    /// Get color with `brightness` [-255..255] added to RGB channels, clamped
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn brightness(self, brightness: i32) -> Self {
+        let brightness = brightness.clamp(-255, 255);
+        let add = |c: u8| (i32::from(c) + brightness).clamp(0, 255) as u8;
+        Self {
+            r: add(self.r),
+            g: add(self.g),
+            b: add(self.b),
+            a: self.a,
+        }
+    }
+
+    /// Get color with RGB channels contrast changed by `contrast` [-100..100]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn contrast(self, contrast: f32) -> Self {
+        let factor = contrast_factor(contrast);
+        let scale = |c: u8| (((f32::from(c) / 255.0 - 0.5) * factor + 0.5) * 255.0).clamp(0.0, 255.0) as u8;
+        Self {
+            r: scale(self.r),
+            g: scale(self.g),
+            b: scale(self.b),
+            a: self.a,
+        }
+    }
+}
+
+/// Get contrast multiplier from a contrast value [-100..100]
+fn contrast_factor(contrast: f32) -> f32 {
+    let factor = (100.0 + contrast.clamp(-100.0, 100.0)) / 100.0;
+    factor * factor
+}
+
+/// Convert sRGB channel [0..255] to linear [0..1]
+fn srgb_to_linear(value: u8) -> f32 {
+    let c = f32::from(value) / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+/// Convert linear channel [0..1] to sRGB [0..255]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn linear_to_srgb(value: f32) -> u8 {
+    let c = value.clamp(0.0, 1.0);
+    let c = if c <= 0.003_130_8 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+    (c * 255.0).round() as u8
+}
+
+impl Color {
+    /// Get WCAG relative luminance [0..1], alpha is ignored
+    #[must_use]
+    pub fn relative_luminance(self) -> f32 {
+        0.2126 * srgb_to_linear(self.r) + 0.7152 * srgb_to_linear(self.g) + 0.0722 * srgb_to_linear(self.b)
+    }
+
+    /// Get WCAG contrast ratio between two colors [1..21], order doesn't matter
+    #[must_use]
+    pub fn contrast_ratio(self, other: Self) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Check if text color over background meets WCAG AA contrast (4.5:1, 3:1 for large text)
+    #[must_use]
+    pub fn meets_wcag_aa(self, background: Self, large_text: bool) -> bool {
+        self.contrast_ratio(background) >= if large_text { 3.0 } else { 4.5 }
+    }
+}
+
+/// Color vision deficiency kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorblindKind {
+    /// No red cones
+    Protanopia,
+    /// No green cones
+    Deuteranopia,
+    /// No blue cones
+    Tritanopia,
+}
+
+/// Colorblind-safe palettes
+pub struct Palette;
+
+impl Palette {
+    /// Okabe-Ito palette: black, orange, sky blue, bluish green, yellow, blue, vermillion, reddish purple
+    pub const OKABE_ITO: [Color; 8] = [
+        rgb(  0,   0,   0),
+        rgb(230, 159,   0),
+        rgb( 86, 180, 233),
+        rgb(  0, 158, 115),
+        rgb(240, 228,  66),
+        rgb(  0, 114, 178),
+        rgb(213,  94,   0),
+        rgb(204, 121, 167),
+    ];
+
+    /// Paul Tol bright scheme: blue, red, green, yellow, cyan, purple, grey
+    pub const TOL_BRIGHT: [Color; 7] = [
+        rgb( 68, 119, 170),
+        rgb(238, 102, 119),
+        rgb( 34, 136,  51),
+        rgb(204, 187,  68),
+        rgb(102, 204, 238),
+        rgb(170,  51, 119),
+        rgb(187, 187, 187),
+    ];
+
+    /// Paul Tol vibrant scheme: orange, blue, cyan, magenta, red, teal, grey
+    pub const TOL_VIBRANT: [Color; 7] = [
+        rgb(238, 119,  51),
+        rgb(  0, 119, 187),
+        rgb( 51, 187, 238),
+        rgb(238,  51, 119),
+        rgb(204,  51,  17),
+        rgb(  0, 153, 136),
+        rgb(187, 187, 187),
+    ];
+
+    /// Paul Tol muted scheme: rose, indigo, sand, green, cyan, wine, teal, olive, purple
+    pub const TOL_MUTED: [Color; 9] = [
+        rgb(204, 102, 119),
+        rgb( 51,  34, 136),
+        rgb(221, 204, 119),
+        rgb( 17, 119,  51),
+        rgb(136, 204, 238),
+        rgb(136,  34,  85),
+        rgb( 68, 170, 153),
+        rgb(153, 153,  51),
+        rgb(170,  68, 153),
+    ];
+
+    /// Simulate how a color is seen with a color vision deficiency (Machado et al. 2009, full severity)
+    /// NOTE: Simulation is done in linear RGB, alpha is kept
+    pub fn colorblind_simulate(color: Color, kind: ColorblindKind) -> Color {
+        let matrix = match kind {
+            ColorblindKind::Protanopia => [
+                [ 0.152_286,  1.052_583, -0.204_868],
+                [ 0.114_503,  0.786_281,  0.099_216],
+                [-0.003_882, -0.048_116,  1.051_998],
+            ],
+            ColorblindKind::Deuteranopia => [
+                [ 0.367_322,  0.860_646, -0.227_968],
+                [ 0.280_085,  0.672_501,  0.047_413],
+                [-0.011_820,  0.042_940,  0.968_881],
+            ],
+            ColorblindKind::Tritanopia => [
+                [ 1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411,  0.930_809,  0.147_602],
+                [ 0.004_733,  0.691_367,  0.303_900],
+            ],
+        };
+
+        let linear = [srgb_to_linear(color.r), srgb_to_linear(color.g), srgb_to_linear(color.b)];
+        let channel = |row: [f32; 3]| linear_to_srgb(row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2]);
+        Color {
+            r: channel(matrix[0]),
+            g: channel(matrix[1]),
+            b: channel(matrix[2]),
+            a: color.a,
+        }
+    }
+}
+
+/// Get colors from R8G8B8A8 pixel data without copying, trailing bytes are ignored
+pub fn colors_from_rgba8(data: &[u8]) -> &[Color] {
+    bytemuck::cast_slice(&data[..data.len() / 4 * 4])
+}
+
+/// Get mutable colors from R8G8B8A8 pixel data without copying, trailing bytes are ignored
+pub fn colors_from_rgba8_mut(data: &mut [u8]) -> &mut [Color] {
+    let len = data.len() / 4 * 4;
+    bytemuck::cast_slice_mut(&mut data[..len])
+}
+
+/// Get R8G8B8A8 pixel data from colors without copying
+#[must_use]
+pub fn colors_as_rgba8(colors: &[Color]) -> &[u8] {
+    bytemuck::cast_slice(colors)
+}
+
+/// Get R8G8B8A8 pixel data from colors
+#[must_use]
+pub fn colors_to_rgba8(colors: &[Color]) -> Vec<u8> {
+    colors_as_rgba8(colors).to_vec()
+}
+
+/// Copy colors into R8G8B8A8 pixel data, up to the shortest length
+pub fn write_colors_rgba8(colors: &[Color], data: &mut [u8]) {
+    let len = colors.len().min(data.len() / 4);
+    colors_from_rgba8_mut(data)[..len].copy_from_slice(&colors[..len]);
+}
+
+/// Tint all colors, same result as [`Color::tint`] per color
+pub fn tint_slice(colors: &mut [Color], tint: Color) {
+    #[cfg(feature = "simd")]
+    let colors = simd::tint(colors, tint);
+    for color in colors {
+        *color = color.tint(tint);
+    }
+}
+
+/// Interpolate colors pairwise into `out`, up to the shortest length, same result as [`LerpTo::lerp_to`] per color
+pub fn lerp_slices(a: &[Color], b: &[Color], amount: Percent, out: &mut [Color]) {
+    let len = out.len().min(a.len()).min(b.len());
+    let (a, b, out) = (&a[..len], &b[..len], &mut out[..len]);
+    #[cfg(feature = "simd")]
+    let (a, b, out) = simd::lerp(a, b, amount, out);
+    for ((out, a), b) in out.iter_mut().zip(a).zip(b) {
+        *out = a.lerp_to(*b, amount);
+    }
+}
+
+/// Premultiply all colors by their alpha, same result as [`Color::premultiply`] per color
+pub fn premultiply_slice(colors: &mut [Color]) {
+    #[cfg(feature = "simd")]
+    let colors = simd::premultiply(colors);
+    for color in colors {
+        *color = color.premultiply();
+    }
+}
+
+/// Change brightness of all colors, same result as [`Color::brightness`] per color
+pub fn brightness_slice(colors: &mut [Color], brightness: i32) {
+    #[cfg(feature = "simd")]
+    let colors = simd::brightness(colors, brightness);
+    for color in colors {
+        *color = color.brightness(brightness);
+    }
+}
+
+/// Change contrast of all colors, same result as [`Color::contrast`] per color
+pub fn contrast_slice(colors: &mut [Color], contrast: f32) {
+    #[cfg(feature = "simd")]
+    let colors = simd::contrast(colors, contrast);
+    for color in colors {
+        *color = color.contrast(contrast);
+    }
+}
+
+/// SIMD paths of the bulk operations: two colors per vector, with the same float operations as the
+/// per color functions so results are identical. Each function returns the colors left to process
+#[cfg(feature = "simd")]
+mod simd {
+    use wide::{f32x8, CmpEq};
+    use super::{contrast_factor, Color};
+
+    /// Lanes holding the alpha channel of both colors
+    fn alpha_mask() -> f32x8 {
+        f32x8::from([0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]).cmp_eq(f32x8::splat(1.0))
+    }
+
+    fn load(pair: &[Color]) -> f32x8 {
+        let bytes: &[u8] = bytemuck::cast_slice(pair);
+        f32x8::from(std::array::from_fn::<f32, 8, _>(|i| f32::from(bytes[i])))
+    }
+
+    /// Store lanes truncated towards zero, as `f32 as u8` does for values in [0..255]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn store(lanes: f32x8, pair: &mut [Color]) {
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(pair);
+        for (byte, lane) in bytes.iter_mut().zip(lanes.trunc_int().to_array()) {
+            *byte = lane as u8;
+        }
+    }
+
+    /// Apply `op` to pairs of colors, returns the odd color left
+    fn map_pairs(colors: &mut [Color], op: impl Fn(f32x8) -> f32x8) -> &mut [Color] {
+        let mut pairs = colors.chunks_exact_mut(2);
+        for pair in &mut pairs {
+            store(op(load(pair)), pair);
+        }
+        pairs.into_remainder()
+    }
+
+    pub(super) fn tint(colors: &mut [Color], tint: Color) -> &mut [Color] {
+        let tint = load(&[tint, tint]) / f32x8::splat(255.0);
+        map_pairs(colors, |lanes| lanes / f32x8::splat(255.0) * tint * f32x8::splat(255.0))
+    }
+
+    pub(super) fn lerp<'a>(a: &'a [Color], b: &'a [Color], amount: f32, out: &'a mut [Color]) -> (&'a [Color], &'a [Color], &'a mut [Color]) {
+        let factor = amount.clamp(0.0, 1.0);
+        let (from, to) = (f32x8::splat(1.0 - factor), f32x8::splat(factor));
+        let done = out.len() / 2 * 2;
+        for ((out, a), b) in out.chunks_exact_mut(2).zip(a.chunks_exact(2)).zip(b.chunks_exact(2)) {
+            store(from * load(a) + to * load(b), out);
+        }
+        (&a[done..], &b[done..], &mut out[done..])
+    }
+
+    pub(super) fn premultiply(colors: &mut [Color]) -> &mut [Color] {
+        let mask = alpha_mask();
+        map_pairs(colors, |lanes| {
+            let alpha = lanes.to_array();
+            let alpha = f32x8::from([alpha[3], alpha[3], alpha[3], alpha[3], alpha[7], alpha[7], alpha[7], alpha[7]]) / f32x8::splat(255.0);
+            mask.blend(lanes, lanes * alpha)
+        })
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub(super) fn brightness(colors: &mut [Color], brightness: i32) -> &mut [Color] {
+        let (mask, brightness) = (alpha_mask(), f32x8::splat(brightness.clamp(-255, 255) as f32));
+        map_pairs(colors, |lanes| mask.blend(lanes, (lanes + brightness).max(f32x8::ZERO).min(f32x8::splat(255.0))))
+    }
+
+    pub(super) fn contrast(colors: &mut [Color], contrast: f32) -> &mut [Color] {
+        let (mask, factor, half) = (alpha_mask(), f32x8::splat(contrast_factor(contrast)), f32x8::splat(0.5));
+        map_pairs(colors, |lanes| {
+            let scaled = ((lanes / f32x8::splat(255.0) - half) * factor + half) * f32x8::splat(255.0);
+            mask.blend(lanes, scaled.max(f32x8::ZERO).min(f32x8::splat(255.0)))
+        })
+    }
 }
 
 impl LerpTo for Color {
+    /// Get color lerp interpolation between two colors, factor [0.0f..1.0f]
+    #[allow(clippy::cast_sign_loss)]
     fn lerp_to(self, target: Self, amount: Percent) -> Self {
-        todo!()
+        let factor = amount.clamp(0.0, 1.0);
+        let lerp = |a: u8, b: u8| ((1.0 - factor) * f32::from(a) + factor * f32::from(b)) as u8;
+        Self {
+            r: lerp(self.r, target.r),
+            g: lerp(self.g, target.g),
+            b: lerp(self.b, target.b),
+            a: lerp(self.a, target.a),
+        }
+    }
+}
+
+/// Color gradient, colors interpolated between stops at positions [0..1]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Gradient {
+    /// Stops sorted by position
+    stops: Vec<(Percent, Color)>,
+}
+
+impl Gradient {
+    /// Create gradient from stops, sorted by position
+    #[must_use]
+    pub fn new(stops: impl IntoIterator<Item = (Percent, Color)>) -> Self {
+        let mut stops: Vec<_> = stops.into_iter().collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops }
+    }
+
+    /// Create gradient from start color to end color
+    #[must_use]
+    pub fn linear(start: Color, end: Color) -> Self {
+        Self { stops: vec![(0.0, start), (1.0, end)] }
+    }
+
+    /// Get gradient stops
+    pub fn stops(&self) -> &[(Percent, Color)] {
+        &self.stops
+    }
+
+    /// Get color at position, clamped to the first and last stops
+    /// NOTE: Empty gradients are transparent
+    pub fn sample(&self, t: Percent) -> Color {
+        let next = self.stops.partition_point(|&(position, _)| position <= t);
+        match (next.checked_sub(1).map(|i| self.stops[i]), self.stops.get(next)) {
+            (Some((start, a)), Some(&(end, b))) => a.lerp_to(b, t.normalize_between(start, end)),
+            (Some((_, color)), None) | (None, Some(&(_, color))) => color,
+            (None, None) => Color::BLANK,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    fn color() -> impl Strategy<Value = Color> {
+        any::<[u8; 4]>().prop_map(|[r, g, b, a]| Color::new(r, g, b, a))
+    }
+
+    #[test]
+    fn test_color_layout() {
+        assert_eq!((std::mem::size_of::<Color>(), std::mem::align_of::<Color>()), (4, 1));
+        assert_eq!(colors_as_rgba8(&[Color::new(1, 2, 3, 4)]), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_colors_from_rgba8_zero_copy() {
+        let mut data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let colors = colors_from_rgba8(&data);
+        assert_eq!(colors, [Color::new(1, 2, 3, 4), Color::new(5, 6, 7, 8)]);
+        assert!(std::ptr::eq(colors.as_ptr().cast::<u8>(), data.as_ptr()));
+
+        colors_from_rgba8_mut(&mut data)[1].a = 0;
+        assert_eq!(data, [1, 2, 3, 4, 5, 6, 7, 0, 9]);
+        assert!(colors_from_rgba8(&data[..3]).is_empty());
+
+        write_colors_rgba8(&[Color::WHITE; 3], &mut data);
+        assert_eq!(data, [255, 255, 255, 255, 255, 255, 255, 255, 9]);
+        assert_eq!(colors_to_rgba8(&[Color::BLANK, Color::RED]), [0, 0, 0, 0, 230, 41, 55, 255]);
+    }
+
+    #[test]
+    fn test_brightness_and_contrast() {
+        let color = Color::new(10, 128, 250, 77);
+        assert_eq!(color.brightness(10), Color::new(20, 138, 255, 77));
+        assert_eq!(color.brightness(-1000), Color::new(0, 0, 0, 77));
+        // Full negative contrast is mid gray, full positive contrast pushes channels away from it
+        assert_eq!(color.contrast(-100.0), Color::new(127, 127, 127, 77));
+        assert_eq!(color.contrast(100.0), Color::new(0, 129, 255, 77));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_wcag_contrast() {
+        // Published WCAG 2 figures
+        assert_eq!(Color::BLACK.relative_luminance(), 0.0);
+        assert!((Color::WHITE.relative_luminance() - 1.0).abs() < 1e-6);
+        assert!((Color::BLACK.contrast_ratio(Color::WHITE) - 21.0).abs() < 1e-4);
+        assert_eq!(Color::WHITE.contrast_ratio(Color::BLACK), Color::BLACK.contrast_ratio(Color::WHITE));
+        assert_eq!(Color::GRAY.contrast_ratio(Color::GRAY), 1.0);
+        assert!((rgb(255, 0, 0).relative_luminance() - 0.2126).abs() < 1e-6);
+        assert!((rgb(0, 255, 0).relative_luminance() - 0.7152).abs() < 1e-6);
+        assert!((rgb(0, 0, 255).relative_luminance() - 0.0722).abs() < 1e-6);
+
+        // #767676 is the lightest gray passing AA over white (4.54:1), #777777 fails (4.48:1)
+        assert!((rgb(0x76, 0x76, 0x76).contrast_ratio(Color::WHITE) - 4.54).abs() < 0.005);
+        assert!((rgb(0x77, 0x77, 0x77).contrast_ratio(Color::WHITE) - 4.48).abs() < 0.005);
+        assert!(rgb(0x76, 0x76, 0x76).meets_wcag_aa(Color::WHITE, false));
+        assert!(!rgb(0x77, 0x77, 0x77).meets_wcag_aa(Color::WHITE, false));
+        // Pure red over white is 4.0:1, enough for large text only
+        assert!(rgb(255, 0, 0).meets_wcag_aa(Color::WHITE, true));
+        assert!(!rgb(255, 0, 0).meets_wcag_aa(Color::WHITE, false));
+    }
+
+    #[test]
+    fn test_colorblind_simulate() {
+        // Machado et al. 2009 matrices at severity 1.0, a primary maps to its matrix column (clamped)
+        let published: [(ColorblindKind, [[f32; 3]; 3]); 3] = [
+            (ColorblindKind::Protanopia, [
+                [ 0.152_286,  1.052_583, -0.204_868],
+                [ 0.114_503,  0.786_281,  0.099_216],
+                [-0.003_882, -0.048_116,  1.051_998],
+            ]),
+            (ColorblindKind::Deuteranopia, [
+                [ 0.367_322,  0.860_646, -0.227_968],
+                [ 0.280_085,  0.672_501,  0.047_413],
+                [-0.011_820,  0.042_940,  0.968_881],
+            ]),
+            (ColorblindKind::Tritanopia, [
+                [ 1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411,  0.930_809,  0.147_602],
+                [ 0.004_733,  0.691_367,  0.303_900],
+            ]),
+        ];
+        let primaries = [rgb(255, 0, 0), rgb(0, 255, 0), rgb(0, 0, 255)];
+
+        for (kind, matrix) in published {
+            for (column, primary) in primaries.into_iter().enumerate() {
+                let seen = Palette::colorblind_simulate(primary, kind);
+                let linear = [srgb_to_linear(seen.r), srgb_to_linear(seen.g), srgb_to_linear(seen.b)];
+                for (row, value) in linear.into_iter().enumerate() {
+                    let expected = matrix[row][column].clamp(0.0, 1.0);
+                    // Half an 8-bit sRGB step near white
+                    assert!((value - expected).abs() < 0.005, "{kind:?} {primary:?}[{row}]: {value} != {expected}");
+                }
+            }
+
+            // Rows sum to 1, grays are seen unchanged, alpha is kept
+            for gray in [Color::BLACK, Color::GRAY, Color::WHITE, Color::new(128, 128, 128, 128)] {
+                assert_eq!(Palette::colorblind_simulate(gray, kind), gray, "{kind:?}");
+            }
+        }
+
+        // Red and green collapse into similar yellows without green cones
+        let red = Palette::colorblind_simulate(rgb(255, 0, 0), ColorblindKind::Deuteranopia);
+        let green = Palette::colorblind_simulate(rgb(0, 255, 0), ColorblindKind::Deuteranopia);
+        assert!(red.contrast_ratio(green) < rgb(255, 0, 0).contrast_ratio(rgb(0, 255, 0)));
+    }
+
+    proptest! {
+        // Bulk operations (SIMD path with the `simd` feature) must match per color ones exactly,
+        // odd lengths cover the scalar tail
+        #[test]
+        fn prop_tint_slice(colors in prop::collection::vec(color(), 0..67), tint in color()) {
+            let mut bulk = colors.clone();
+            tint_slice(&mut bulk, tint);
+            prop_assert_eq!(bulk, colors.iter().map(|color| color.tint(tint)).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn prop_lerp_slices(a in prop::collection::vec(color(), 0..67), b in prop::collection::vec(color(), 0..67), amount in -0.5f32..1.5) {
+            let mut bulk = vec![Color::BLANK; 70];
+            lerp_slices(&a, &b, amount, &mut bulk);
+            let len = a.len().min(b.len());
+            let expected: Vec<Color> = a.iter().zip(&b).map(|(a, b)| a.lerp_to(*b, amount)).collect();
+            prop_assert_eq!(&bulk[..len], expected);
+            prop_assert!(bulk[len..].iter().all(|color| *color == Color::BLANK));
+        }
+
+        #[test]
+        fn prop_premultiply_slice(colors in prop::collection::vec(color(), 0..67)) {
+            let mut bulk = colors.clone();
+            premultiply_slice(&mut bulk);
+            prop_assert_eq!(bulk, colors.iter().map(|color| color.premultiply()).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn prop_brightness_slice(colors in prop::collection::vec(color(), 0..67), brightness in -300..300) {
+            let mut bulk = colors.clone();
+            brightness_slice(&mut bulk, brightness);
+            prop_assert_eq!(bulk, colors.iter().map(|color| color.brightness(brightness)).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn prop_contrast_slice(colors in prop::collection::vec(color(), 0..67), contrast in -120.0f32..120.0) {
+            let mut bulk = colors.clone();
+            contrast_slice(&mut bulk, contrast);
+            prop_assert_eq!(bulk, colors.iter().map(|color| color.contrast(contrast)).collect::<Vec<_>>());
+        }
     }
 }
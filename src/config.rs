@@ -49,6 +49,8 @@ pub const MAX_GAMEPAD_AXIS: usize = 8;
 pub const MAX_GAMEPAD_BUTTONS: usize = 32;
 /// Maximum vibration time in seconds
 pub const MAX_GAMEPAD_VIBRATION_TIME: f32 = 2.0;
+/// Default gamepad axis deadzone, movement under it is reported as rest position
+pub const GAMEPAD_AXIS_DEADZONE: f32 = 0.1;
 /// Maximum number of touch points supported
 pub const MAX_TOUCH_POINTS: usize = 8;
 /// Maximum number of keys in the key input queue
@@ -59,9 +61,23 @@ pub const MAX_CHAR_PRESSED_QUEUE: usize = 16;
 /// Max size allocated for decompression in MB
 pub const MAX_DECOMPRESSION_SIZE: usize = 64;
 
+/// Storage data file name, under the storage base path
+pub const STORAGE_DATA_FILE: &str = "storage.data";
+
 /// Maximum number of automation events to record
 pub const MAX_AUTOMATION_EVENTS: usize = 16384;
 
+/// Default frame rate while the window is in background (minimized or unfocused), without `WindowAlwaysRun`
+pub const BACKGROUND_THROTTLE_FPS: u32 = 10;
+
+/// Frames between two captured GIF frames (GIF frame delay is 10 centiseconds)
+pub const GIF_RECORD_FRAMERATE: u32 = 10;
+
+/// Number of frame time captures averaged by `get_fps()`
+pub const FPS_CAPTURE_FRAMES_COUNT: usize = 30;
+/// Time span covered by the `get_fps()` average, in seconds
+pub const FPS_AVERAGE_TIME_SECONDS: f64 = 0.5;
+
 //------------------------------------------------------------------------------------
 // Module: rlgl - Configuration values
 //------------------------------------------------------------------------------------
@@ -159,6 +175,16 @@ pub const SPLINE_SEGMENT_DIVISIONS: usize = 24;
 pub const MAX_TEXT_BUFFER_LENGTH: usize = 1024;
 /// Maximum number of substrings to split: TextSplit()
 pub const MAX_TEXTSPLIT_COUNT: usize = 128;
+/// Default vertical spacing between lines of text, in pixels
+pub const TEXT_LINE_SPACING: f32 = 2.0;
+/// Default font size used when loading a font with size 0
+pub const FONT_TTF_DEFAULT_SIZE: i32 = 32;
+/// First codepoint loaded by default (space)
+pub const FONT_TTF_DEFAULT_FIRST_CHAR: char = ' ';
+/// Last codepoint loaded by default (tilde)
+pub const FONT_TTF_DEFAULT_LAST_CHAR: char = '~';
+/// Padding around glyphs of loaded fonts, in pixels
+pub const FONT_TTF_DEFAULT_CHARS_PADDING: i32 = 4;
 
 //------------------------------------------------------------------------------------
 // Module: rmodels - Configuration Flags
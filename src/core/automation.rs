@@ -0,0 +1,862 @@
+//! Automation events list and compact binary serialization
+//!
+//! Binary format (version 1), integers are LEB128 varints, signed ones zigzag encoded:
+//! - Header: magic `rAEB`, version byte, flags byte, varint event count
+//!   (with the DEFLATE flag, everything after the flags byte is compressed)
+//! - Records: varint type, varint run length, zigzag frame delta,
+//!   if run length > 1: zigzag frame step, then zigzag start params,
+//!   if run length > 1: zigzag end params
+//!
+//! Runs collapse consecutive events of the same type with a constant frame step and params
+//! changing linearly (e.g. mouse position while dragging), so only first and last are stored
+//!
+//! Text format (`.rae`) is the one exported by raylib, so recordings are interchangeable
+
+use std::path::Path;
+use crate::{config::{MAX_AUTOMATION_EVENTS, MAX_GAMEPAD_BUTTONS, MAX_KEYBOARD_KEYS, MAX_MOUSE_BUTTONS}, prelude::*, tracelog};
+
+const BINARY_MAGIC: [u8; 4] = *b"rAEB";
+const BINARY_VERSION: u8 = 1;
+/// Stream after the header is compressed with DEFLATE
+const BINARY_FLAG_DEFLATE: u8 = 0x01;
+
+/// Automation event list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutomationEventList {
+    /// Events max entries (`MAX_AUTOMATION_EVENTS`)
+    pub(crate) capacity: usize,
+    /// Events entries
+    pub(crate) events: Vec<AutomationEvent>,
+}
+
+impl Default for AutomationEventList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AutomationEventList {
+    /// Create an empty automation events list, with default capacity
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            capacity: MAX_AUTOMATION_EVENTS,
+            events: Vec::new(),
+        }
+    }
+
+    /// Get recorded events
+    #[must_use]
+    pub fn events(&self) -> &[AutomationEvent] {
+        &self.events
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AutomationEventError {
+    Io(std::io::Error),
+    /// Data does not start with the binary format magic
+    InvalidHeader,
+    /// Binary format version is newer than supported
+    UnsupportedVersion(u8),
+    /// Binary stream uses flags not supported by this build
+    UnsupportedFlags(u8),
+    /// Data ended before all events were read
+    UnexpectedEof,
+    /// Event type code does not match any `AutomationEventType`
+    InvalidEventType(u64),
+    /// Decoded value does not fit the event fields
+    ValueOutOfRange,
+    /// Text line could not be parsed, line numbers start at 1
+    InvalidLine(usize),
+    /// Compressed binary stream could not be decompressed
+    Decompress(DecompressError),
+}
+
+impl std::fmt::Display for AutomationEventError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::InvalidHeader => f.write_str("invalid automation events binary header"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported automation events binary version: {v}"),
+            Self::UnsupportedFlags(flags) => write!(f, "unsupported automation events binary flags: {flags:#04x}"),
+            Self::UnexpectedEof => f.write_str("unexpected end of automation events data"),
+            Self::InvalidEventType(ty) => write!(f, "invalid automation event type: {ty}"),
+            Self::ValueOutOfRange => f.write_str("automation event value out of range"),
+            Self::InvalidLine(line) => write!(f, "invalid automation events text at line {line}"),
+            Self::Decompress(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for AutomationEventError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Decompress(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for AutomationEventError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<DecompressError> for AutomationEventError {
+    fn from(value: DecompressError) -> Self {
+        Self::Decompress(value)
+    }
+}
+
+impl From<AutomationEventType> for u32 {
+    /// Get raylib-compatible event type code
+    fn from(value: AutomationEventType) -> Self {
+        use {AutomationEventType as A, InputEventType as I, WindowEventType as W, CustomEventType as C};
+        match value {
+            A::Input(I::KeyUp)             => 1,
+            A::Input(I::KeyDown)           => 2,
+            A::Input(I::KeyPressed)        => 3,
+            A::Input(I::KeyReleased)       => 4,
+            A::Input(I::MouseButtonUp)     => 5,
+            A::Input(I::MouseButtonDown)   => 6,
+            A::Input(I::MousePosition)     => 7,
+            A::Input(I::MouseWheelMotion)  => 8,
+            A::Input(I::GamepadConnect)    => 9,
+            A::Input(I::GamepadDisconnect) => 10,
+            A::Input(I::GamepadButtonUp)   => 11,
+            A::Input(I::GamepadButtonDown) => 12,
+            A::Input(I::GamepadAxisMotion) => 13,
+            A::Input(I::TouchUp)           => 14,
+            A::Input(I::TouchDown)         => 15,
+            A::Input(I::TouchPosition)     => 16,
+            A::Input(I::Gesture)           => 17,
+            A::Window(W::Close)            => 18,
+            A::Window(W::Maximize)         => 19,
+            A::Window(W::Minimize)         => 20,
+            A::Window(W::Resize)           => 21,
+            A::Custom(C::TakeScreenshot)   => 22,
+            A::Custom(C::SetTargetFps)     => 23,
+        }
+    }
+}
+
+impl TryFrom<u32> for AutomationEventType {
+    type Error = AutomationEventError;
+
+    /// Get event type from raylib-compatible event type code
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        use {AutomationEventType as A, InputEventType as I, WindowEventType as W, CustomEventType as C};
+        Ok(match value {
+            1  => A::Input(I::KeyUp),
+            2  => A::Input(I::KeyDown),
+            3  => A::Input(I::KeyPressed),
+            4  => A::Input(I::KeyReleased),
+            5  => A::Input(I::MouseButtonUp),
+            6  => A::Input(I::MouseButtonDown),
+            7  => A::Input(I::MousePosition),
+            8  => A::Input(I::MouseWheelMotion),
+            9  => A::Input(I::GamepadConnect),
+            10 => A::Input(I::GamepadDisconnect),
+            11 => A::Input(I::GamepadButtonUp),
+            12 => A::Input(I::GamepadButtonDown),
+            13 => A::Input(I::GamepadAxisMotion),
+            14 => A::Input(I::TouchUp),
+            15 => A::Input(I::TouchDown),
+            16 => A::Input(I::TouchPosition),
+            17 => A::Input(I::Gesture),
+            18 => A::Window(W::Close),
+            19 => A::Window(W::Maximize),
+            20 => A::Window(W::Minimize),
+            21 => A::Window(W::Resize),
+            22 => A::Custom(C::TakeScreenshot),
+            23 => A::Custom(C::SetTargetFps),
+            _ => return Err(AutomationEventError::InvalidEventType(value.into())),
+        })
+    }
+}
+
+impl AutomationEventType {
+    /// Get raylib event type name, used in text format comments
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        use {AutomationEventType as A, InputEventType as I, WindowEventType as W, CustomEventType as C};
+        match self {
+            A::Input(I::KeyUp)             => "INPUT_KEY_UP",
+            A::Input(I::KeyDown)           => "INPUT_KEY_DOWN",
+            A::Input(I::KeyPressed)        => "INPUT_KEY_PRESSED",
+            A::Input(I::KeyReleased)       => "INPUT_KEY_RELEASED",
+            A::Input(I::MouseButtonUp)     => "INPUT_MOUSE_BUTTON_UP",
+            A::Input(I::MouseButtonDown)   => "INPUT_MOUSE_BUTTON_DOWN",
+            A::Input(I::MousePosition)     => "INPUT_MOUSE_POSITION",
+            A::Input(I::MouseWheelMotion)  => "INPUT_MOUSE_WHEEL_MOTION",
+            A::Input(I::GamepadConnect)    => "INPUT_GAMEPAD_CONNECT",
+            A::Input(I::GamepadDisconnect) => "INPUT_GAMEPAD_DISCONNECT",
+            A::Input(I::GamepadButtonUp)   => "INPUT_GAMEPAD_BUTTON_UP",
+            A::Input(I::GamepadButtonDown) => "INPUT_GAMEPAD_BUTTON_DOWN",
+            A::Input(I::GamepadAxisMotion) => "INPUT_GAMEPAD_AXIS_MOTION",
+            A::Input(I::TouchUp)           => "INPUT_TOUCH_UP",
+            A::Input(I::TouchDown)         => "INPUT_TOUCH_DOWN",
+            A::Input(I::TouchPosition)     => "INPUT_TOUCH_POSITION",
+            A::Input(I::Gesture)           => "INPUT_GESTURE",
+            A::Window(W::Close)            => "WINDOW_CLOSE",
+            A::Window(W::Maximize)         => "WINDOW_MAXIMIZE",
+            A::Window(W::Minimize)         => "WINDOW_MINIMIZE",
+            A::Window(W::Resize)           => "WINDOW_RESIZE",
+            A::Custom(C::TakeScreenshot)   => "ACTION_TAKE_SCREENSHOT",
+            A::Custom(C::SetTargetFps)     => "ACTION_SETTARGETFPS",
+        }
+    }
+}
+
+impl AutomationEvent {
+    /// Create an automation event
+    #[must_use]
+    pub fn new(frame: usize, ty: AutomationEventType, params: [i32; 4]) -> Self {
+        Self { frame, ty, params }
+    }
+
+    /// Get event frame
+    #[must_use]
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Get event type
+    #[must_use]
+    pub fn event_type(&self) -> AutomationEventType {
+        self.ty
+    }
+
+    /// Get event parameters
+    #[must_use]
+    pub fn params(&self) -> [i32; 4] {
+        self.params
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+#[allow(clippy::cast_sign_loss)]
+fn write_zigzag(out: &mut Vec<u8>, value: i64) {
+    write_varint(out, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, AutomationEventError> {
+        let (&first, rest) = self.bytes.split_first().ok_or(AutomationEventError::UnexpectedEof)?;
+        self.bytes = rest;
+        Ok(first)
+    }
+
+    fn varint(&mut self) -> Result<u64, AutomationEventError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(AutomationEventError::ValueOutOfRange)
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn zigzag(&mut self) -> Result<i64, AutomationEventError> {
+        let value = self.varint()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+}
+
+/// Get params step between two consecutive events
+fn params_step(prev: &AutomationEvent, next: &AutomationEvent) -> [i64; 4] {
+    std::array::from_fn(|i| i64::from(next.params[i]) - i64::from(prev.params[i]))
+}
+
+/// Get number of events starting at `events[0]` that can be collapsed in a single run
+fn run_length(events: &[AutomationEvent]) -> usize {
+    let [first, second, ..] = events else {
+        return events.len();
+    };
+    if first.ty != second.ty {
+        return 1;
+    }
+    let frame_step = second.frame.wrapping_sub(first.frame);
+    let step = params_step(first, second);
+    let mut len = 2;
+    for pair in events[1..].windows(2) {
+        let [prev, next] = pair else { unreachable!() };
+        if next.ty != first.ty || next.frame.wrapping_sub(prev.frame) != frame_step || params_step(prev, next) != step {
+            break;
+        }
+        len += 1;
+    }
+    len
+}
+
+impl AutomationEventList {
+    /// Serialize events list to compact binary format
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&BINARY_MAGIC);
+        out.push(BINARY_VERSION);
+        out.push(0); // Flags: no compression
+        write_varint(&mut out, self.events.len() as u64);
+
+        let mut prev_frame: i64 = 0;
+        let mut remaining = &self.events[..];
+        while !remaining.is_empty() {
+            let len = run_length(remaining);
+            let (first, last) = (&remaining[0], &remaining[len - 1]);
+            let frame = first.frame as i64;
+
+            write_varint(&mut out, u32::from(first.ty).into());
+            write_varint(&mut out, len as u64);
+            write_zigzag(&mut out, frame - prev_frame);
+            if len > 1 {
+                write_zigzag(&mut out, remaining[1].frame as i64 - frame);
+            }
+            for param in first.params {
+                write_zigzag(&mut out, param.into());
+            }
+            if len > 1 {
+                for param in last.params {
+                    write_zigzag(&mut out, param.into());
+                }
+            }
+
+            prev_frame = last.frame as i64;
+            remaining = &remaining[len..];
+        }
+
+        out
+    }
+
+    /// Serialize events list to compact binary format, DEFLATE compressed
+    #[must_use]
+    pub fn to_binary_compressed(&self) -> Vec<u8> {
+        let binary = self.to_binary();
+        let (header, stream) = binary.split_at(BINARY_MAGIC.len() + 2);
+        let mut out = header.to_vec();
+        out[BINARY_MAGIC.len() + 1] |= BINARY_FLAG_DEFLATE;
+        out.extend_from_slice(&compress_data(stream));
+        out
+    }
+
+    /// Deserialize events list from compact binary format
+    /// # Errors
+    /// Returns an error if data is not a valid (or supported) automation events binary stream
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, AutomationEventError> {
+        let mut reader = Reader { bytes };
+        for expected in BINARY_MAGIC {
+            if reader.byte().map_err(|_| AutomationEventError::InvalidHeader)? != expected {
+                return Err(AutomationEventError::InvalidHeader);
+            }
+        }
+        let version = reader.byte()?;
+        if version > BINARY_VERSION {
+            return Err(AutomationEventError::UnsupportedVersion(version));
+        }
+        let flags = reader.byte()?;
+        if flags & !BINARY_FLAG_DEFLATE != 0 {
+            return Err(AutomationEventError::UnsupportedFlags(flags));
+        }
+        let inflated;
+        if flags & BINARY_FLAG_DEFLATE != 0 {
+            inflated = decompress_data(reader.bytes)?;
+            reader = Reader { bytes: &inflated };
+        }
+
+        let count = usize::try_from(reader.varint()?).map_err(|_| AutomationEventError::ValueOutOfRange)?;
+        let mut events = Vec::with_capacity(count.min(MAX_AUTOMATION_EVENTS));
+        let mut prev_frame: i64 = 0;
+        while events.len() < count {
+            let ty_code = reader.varint()?;
+            let ty = u32::try_from(ty_code)
+                .map_err(|_| AutomationEventError::InvalidEventType(ty_code))
+                .and_then(AutomationEventType::try_from)?;
+            let len = reader.varint()?;
+            if len == 0 || len > (count - events.len()) as u64 {
+                return Err(AutomationEventError::ValueOutOfRange);
+            }
+            let frame = prev_frame.checked_add(reader.zigzag()?).ok_or(AutomationEventError::ValueOutOfRange)?;
+            let frame_step = if len > 1 { reader.zigzag()? } else { 0 };
+            let mut start = [0; 4];
+            for param in &mut start {
+                *param = reader.zigzag()?;
+            }
+            let mut end = start;
+            if len > 1 {
+                for param in &mut end {
+                    *param = reader.zigzag()?;
+                }
+            }
+
+            // NOTE: Decoded values are untrusted, any overflow is an invalid stream
+            let len = i64::try_from(len).map_err(|_| AutomationEventError::ValueOutOfRange)?;
+            let param_steps: [Option<i64>; 4] = std::array::from_fn(|i| {
+                if len > 1 { end[i].checked_sub(start[i]).map(|delta| delta / (len - 1)) } else { Some(0) }
+            });
+            for i in 0..len {
+                let frame = frame_step.checked_mul(i).and_then(|offset| frame.checked_add(offset));
+                let mut params = [0; 4];
+                for (param, (start, step)) in params.iter_mut().zip(start.into_iter().zip(param_steps)) {
+                    let value = step.and_then(|step| step.checked_mul(i)).and_then(|offset| start.checked_add(offset));
+                    *param = value.and_then(|value| i32::try_from(value).ok()).ok_or(AutomationEventError::ValueOutOfRange)?;
+                }
+                events.push(AutomationEvent {
+                    frame: frame.and_then(|frame| usize::try_from(frame).ok()).ok_or(AutomationEventError::ValueOutOfRange)?,
+                    ty,
+                    params,
+                });
+            }
+            prev_frame = frame_step.checked_mul(len - 1).and_then(|offset| frame.checked_add(offset)).ok_or(AutomationEventError::ValueOutOfRange)?;
+        }
+
+        Ok(Self {
+            capacity: MAX_AUTOMATION_EVENTS.max(events.len()),
+            events,
+        })
+    }
+
+    /// Export automation events list as compact binary file, DEFLATE compressed
+    /// # Errors
+    /// Returns an error if the file could not be written
+    pub fn export_binary(&self, path: impl AsRef<Path>) -> Result<(), AutomationEventError> {
+        std::fs::write(path, self.to_binary_compressed())?;
+        Ok(())
+    }
+
+    /// Load automation events list from compact binary file
+    /// NOTE: Compressed and uncompressed streams are detected from the header flags
+    /// # Errors
+    /// Returns an error if the file could not be read or is not a valid binary events file
+    pub fn load_binary(path: impl AsRef<Path>) -> Result<Self, AutomationEventError> {
+        Self::from_binary(&std::fs::read(path)?)
+    }
+}
+
+impl AutomationEventList {
+    /// Serialize events list to raylib text format
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        use std::fmt::Write;
+
+        let mut text = String::with_capacity(256 + self.events.len()*40);
+        text.push_str("#\n");
+        text.push_str("# Automation events exporter v1.0 - raylib automation events list\n");
+        text.push_str("#\n");
+        text.push_str("#    c <events_count>\n");
+        text.push_str("#    e <frame> <event_type> <param0> <param1> <param2> <param3> // <event_type_name>\n");
+        text.push_str("#\n");
+        text.push_str("# more info and bugs-report:  github.com/raysan5/raylib\n");
+        text.push_str("# feedback and support:       ray[at]raylib.com\n");
+        text.push_str("#\n");
+        text.push_str("# Copyright (c) 2023-2024 Ramon Santamaria (@raysan5)\n");
+        text.push_str("#\n\n");
+
+        // Add events data
+        _ = writeln!(text, "c {}", self.events.len());
+        for event in &self.events {
+            let [p0, p1, p2, p3] = event.params;
+            _ = writeln!(text, "e {} {} {p0} {p1} {p2} {p3} // Event: {}", event.frame, u32::from(event.ty), event.ty.name());
+        }
+
+        text
+    }
+
+    /// Deserialize events list from raylib text format
+    /// NOTE: Comments and unknown lines are skipped, events count mismatch is only logged
+    /// # Errors
+    /// Returns an error if an event line can not be parsed
+    pub fn from_text(text: &str) -> Result<Self, AutomationEventError> {
+        let mut count = None;
+        let mut events = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let invalid = || AutomationEventError::InvalidLine(index + 1);
+            let mut fields = line.split("//").next().unwrap_or_default().split_whitespace();
+            match fields.next() {
+                Some("c") => count = fields.next().and_then(|value| value.parse::<usize>().ok()),
+                Some("e") => {
+                    let mut values = [0i64; 6];
+                    for value in &mut values {
+                        *value = fields.next().and_then(|field| field.parse().ok()).ok_or_else(invalid)?;
+                    }
+                    let [frame, ty, params @ ..] = values;
+                    events.push(AutomationEvent {
+                        frame: usize::try_from(frame).map_err(|_| invalid())?,
+                        ty: u32::try_from(ty).map_err(|_| invalid()).and_then(AutomationEventType::try_from)?,
+                        params: params.map(|param| i32::try_from(param).unwrap_or_default()),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        if count.is_some_and(|count| count != events.len()) {
+            tracelog!(Warning, "AUTOMATION: Events file count mismatch, {} events read", events.len());
+        }
+
+        Ok(Self {
+            capacity: MAX_AUTOMATION_EVENTS.max(events.len()),
+            events,
+        })
+    }
+
+    /// Load automation events list from file, raylib text format (.rae) or compact binary format
+    /// # Errors
+    /// Returns an error if the file could not be read or parsed
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AutomationEventError> {
+        let data = std::fs::read(path)?;
+        if data.starts_with(&BINARY_MAGIC) {
+            Self::from_binary(&data)
+        } else {
+            Self::from_text(&String::from_utf8_lossy(&data))
+        }
+    }
+
+    /// Export automation events list as raylib text file (.rae)
+    /// # Errors
+    /// Returns an error if the file could not be written
+    pub fn export(&self, path: impl AsRef<Path>) -> Result<(), AutomationEventError> {
+        std::fs::write(path, self.to_text())?;
+        Ok(())
+    }
+
+    /// Add an event, ignored if the list is full
+    fn push(&mut self, event: AutomationEvent) {
+        if self.events.len() < self.capacity {
+            self.events.push(event);
+        }
+    }
+}
+
+impl Core<'_> {
+    /// Set automation event list to record to (or to play events from), replacing the current one
+    pub fn set_automation_event_list(&mut self, list: AutomationEventList) {
+        self.current_event_list = Some(list);
+    }
+
+    /// Take back current automation event list, i.e. after recording
+    pub fn take_automation_event_list(&mut self) -> Option<AutomationEventList> {
+        self.current_event_list.take()
+    }
+
+    /// Set automation event internal base frame to start recording
+    pub fn set_automation_event_base_frame(&mut self, frame: usize) {
+        self.time.frame_counter = frame;
+    }
+
+    /// Start recording automation events (`AutomationEventList` must be set)
+    pub fn start_automation_event_recording(&mut self) {
+        if self.current_event_list.is_none() {
+            tracelog!(Warning, "AUTOMATION: No events list set to record to");
+        }
+        self.automation_event_recording = true;
+    }
+
+    /// Stop recording automation events
+    pub fn stop_automation_event_recording(&mut self) {
+        self.automation_event_recording = false;
+    }
+
+    /// Check if automation events are being recorded
+    #[must_use]
+    pub fn is_automation_event_recording(&self) -> bool {
+        self.automation_event_recording
+    }
+
+    /// Record input state changes of the current frame into the current events list
+    /// NOTE: Keys and buttons down are recorded every frame they are down, like raylib does
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub(crate) fn record_automation_event(&mut self) {
+        if !self.automation_event_recording {
+            return;
+        }
+        let Some(list) = self.current_event_list.as_mut() else { return };
+        let frame = self.time.frame_counter;
+        let mut record = |ty: InputEventType, params: [i32; 4]| list.push(AutomationEvent { frame, ty: AutomationEventType::Input(ty), params });
+
+        // Keyboard input events recording
+        let keyboard = &self.input.keyboard;
+        for key in 0..MAX_KEYBOARD_KEYS {
+            if keyboard.previous_key_state[key] == 1 && keyboard.current_key_state[key] == 0 {
+                record(InputEventType::KeyUp, [key as i32, 0, 0, 0]);
+            }
+            if keyboard.current_key_state[key] == 1 {
+                record(InputEventType::KeyDown, [key as i32, 0, 0, 0]);
+            }
+        }
+
+        // Mouse input events recording
+        let mouse = &self.input.mouse;
+        for button in 0..MAX_MOUSE_BUTTONS {
+            if mouse.previous_button_state[button] == 1 && mouse.current_button_state[button] == 0 {
+                record(InputEventType::MouseButtonUp, [button as i32, 0, 0, 0]);
+            }
+            if mouse.current_button_state[button] == 1 {
+                record(InputEventType::MouseButtonDown, [button as i32, 0, 0, 0]);
+            }
+        }
+        let (position, previous_position) = (mouse.current_position, mouse.previous_position);
+        if position.x as i32 != previous_position.x as i32 || position.y as i32 != previous_position.y as i32 {
+            record(InputEventType::MousePosition, [position.x as i32, position.y as i32, 0, 0]);
+        }
+        let (wheel, previous_wheel) = (mouse.current_wheel_move, mouse.previous_wheel_move);
+        if wheel.x as i32 != previous_wheel.x as i32 || wheel.y as i32 != previous_wheel.y as i32 {
+            record(InputEventType::MouseWheelMotion, [wheel.x as i32, wheel.y as i32, 0, 0]);
+        }
+
+        // Touch input events recording
+        for (index, point) in self.input.touch.items.iter().enumerate() {
+            if point.previous_touch_state == 1 && point.current_touch_state == 0 {
+                record(InputEventType::TouchUp, [index as i32, 0, 0, 0]);
+            }
+            if point.current_touch_state == 1 {
+                record(InputEventType::TouchDown, [index as i32, 0, 0, 0]);
+            }
+        }
+
+        // Gamepad input events recording
+        for (index, gamepad) in self.input.gamepad.items.iter().enumerate().filter(|(_, gamepad)| gamepad.ready) {
+            for button in 0..MAX_GAMEPAD_BUTTONS {
+                if gamepad.previous_button_state[button] == 1 && gamepad.current_button_state[button] == 0 {
+                    record(InputEventType::GamepadButtonUp, [index as i32, button as i32, 0, 0]);
+                }
+                if gamepad.current_button_state[button] == 1 {
+                    record(InputEventType::GamepadButtonDown, [index as i32, button as i32, 0, 0]);
+                }
+            }
+            for (axis, &state) in gamepad.axis_state.iter().enumerate() {
+                if state.abs() > 0.1 {
+                    record(InputEventType::GamepadAxisMotion, [index as i32, axis as i32, (state*32768.0) as i32, 0]);
+                }
+            }
+        }
+    }
+
+    /// Play a recorded automation event, input events are applied to input state directly
+    /// NOTE: Events that need the platform (window state, screenshots) are handled by
+    /// [`play_automation_event`](crate::play_automation_event), returns false for those
+    #[allow(clippy::cast_precision_loss, clippy::cast_sign_loss)]
+    pub(crate) fn play_input_automation_event(&mut self, event: &AutomationEvent) -> bool {
+        let [p0, p1, p2, _] = event.params;
+        let index = |param: i32| usize::try_from(param).ok();
+        let set = |states: &mut [u8], param: i32, down: bool| {
+            if let Some(state) = index(param).and_then(|i| states.get_mut(i)) {
+                *state = u8::from(down);
+            }
+        };
+
+        match event.ty {
+            AutomationEventType::Input(ty) => match ty {
+                InputEventType::KeyUp => set(&mut self.input.keyboard.current_key_state, p0, false),
+                // NOTE: Key pressed queue is filled by the platform, it knows the key codes
+                InputEventType::KeyDown => set(&mut self.input.keyboard.current_key_state, p0, true),
+                InputEventType::KeyPressed | InputEventType::KeyReleased | InputEventType::Gesture => {}
+                InputEventType::MouseButtonUp => set(&mut self.input.mouse.current_button_state, p0, false),
+                InputEventType::MouseButtonDown => set(&mut self.input.mouse.current_button_state, p0, true),
+                InputEventType::MousePosition => self.input.mouse.current_position = Vector2::new(p0 as f32, p1 as f32),
+                InputEventType::MouseWheelMotion => self.input.mouse.current_wheel_move = Vector2::new(p0 as f32, p1 as f32),
+                InputEventType::TouchUp => {
+                    if let Some(point) = index(p0).and_then(|i| self.input.touch.items.get_mut(i)) {
+                        point.current_touch_state = 0;
+                    }
+                }
+                InputEventType::TouchDown => {
+                    let Some(i) = index(p0) else { return true };
+                    if let Some(point) = self.input.touch.items.get_mut(i) {
+                        point.current_touch_state = 1;
+                    } else if i == self.input.touch.items.len() {
+                        self.input.touch.press(i as u64, Vector2::ZERO);
+                    }
+                }
+                InputEventType::TouchPosition => {
+                    if let Some(point) = index(p0).and_then(|i| self.input.touch.items.get_mut(i)) {
+                        point.position = Vector2::new(p1 as f32, p2 as f32);
+                    }
+                }
+                InputEventType::GamepadConnect | InputEventType::GamepadDisconnect => {
+                    if let Some(gamepad) = index(p0).and_then(|i| self.input.gamepad.items.get_mut(i)) {
+                        gamepad.ready = ty == InputEventType::GamepadConnect;
+                    }
+                }
+                InputEventType::GamepadButtonUp | InputEventType::GamepadButtonDown => {
+                    if let Some(gamepad) = index(p0).and_then(|i| self.input.gamepad.items.get_mut(i)) {
+                        set(&mut gamepad.current_button_state, p1, ty == InputEventType::GamepadButtonDown);
+                    }
+                }
+                InputEventType::GamepadAxisMotion => {
+                    if let Some(axis) = index(p0).and_then(|i| self.input.gamepad.items.get_mut(i)).and_then(|gamepad| index(p1).and_then(|i| gamepad.axis_state.get_mut(i))) {
+                        *axis = p2 as f32/32768.0;
+                    }
+                }
+            },
+            AutomationEventType::Window(WindowEventType::Close) => self.window.should_close = true,
+            AutomationEventType::Custom(CustomEventType::SetTargetFps) => self.set_target_fps(p0.try_into().unwrap_or_default()),
+            AutomationEventType::Window(_) | AutomationEventType::Custom(CustomEventType::TakeScreenshot) => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// About 10 seconds of recording: mouse drags, key taps and wheel scrolls
+    fn recording() -> AutomationEventList {
+        let mut list = AutomationEventList::new();
+        for (frame, f) in (0..600).zip(0i32..) {
+            let (x, y) = (100 + 2*(f % 200), 300 - (f % 200));
+            list.events.push(AutomationEvent::new(frame, AutomationEventType::Input(InputEventType::MousePosition), [x, y, 0, 0]));
+            if f % 200 == 0 {
+                list.events.push(AutomationEvent::new(frame, AutomationEventType::Input(InputEventType::MouseButtonDown), [0, 0, 0, 0]));
+            }
+            if f % 45 == 10 {
+                list.events.push(AutomationEvent::new(frame, AutomationEventType::Input(InputEventType::KeyDown), [32, 0, 0, 0]));
+            }
+            if f % 45 == 14 {
+                list.events.push(AutomationEvent::new(frame, AutomationEventType::Input(InputEventType::KeyUp), [32, 0, 0, 0]));
+            }
+            if (300..330).contains(&f) {
+                list.events.push(AutomationEvent::new(frame, AutomationEventType::Input(InputEventType::MouseWheelMotion), [0, -1, 0, 0]));
+            }
+        }
+        list
+    }
+
+    /// Binary record: type, run length, frame delta, frame step, start params, end params
+    type Record = (u32, u64, i64, i64, [i64; 4], [i64; 4]);
+
+    /// Binary stream with the given records
+    fn binary(count: u64, records: &[Record]) -> Vec<u8> {
+        let mut out = BINARY_MAGIC.to_vec();
+        out.extend([BINARY_VERSION, 0]);
+        write_varint(&mut out, count);
+        for &(ty, len, frame_delta, frame_step, start, end) in records {
+            write_varint(&mut out, ty.into());
+            write_varint(&mut out, len);
+            write_zigzag(&mut out, frame_delta);
+            if len > 1 {
+                write_zigzag(&mut out, frame_step);
+            }
+            for param in start {
+                write_zigzag(&mut out, param);
+            }
+            if len > 1 {
+                for param in end {
+                    write_zigzag(&mut out, param);
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let list = recording();
+        assert_eq!(AutomationEventList::from_binary(&list.to_binary()).unwrap(), list);
+        assert_eq!(AutomationEventList::from_binary(&list.to_binary_compressed()).unwrap(), list);
+        assert_eq!(AutomationEventList::from_text(&list.to_text()).unwrap(), list);
+    }
+
+    #[test]
+    fn test_binary_round_trip_empty() {
+        let list = AutomationEventList::new();
+        assert_eq!(AutomationEventList::from_binary(&list.to_binary_compressed()).unwrap(), list);
+    }
+
+    #[test]
+    fn test_binary_round_trip_extreme_values() {
+        let mut list = AutomationEventList::new();
+        list.events.push(AutomationEvent::new(0, AutomationEventType::Input(InputEventType::MousePosition), [i32::MIN, i32::MAX, 0, -1]));
+        list.events.push(AutomationEvent::new(1, AutomationEventType::Input(InputEventType::MousePosition), [i32::MAX, i32::MIN, 0, -1]));
+        list.events.push(AutomationEvent::new(1, AutomationEventType::Input(InputEventType::KeyDown), [i32::MAX, 0, 0, 0]));
+        list.events.push(AutomationEvent::new(0, AutomationEventType::Input(InputEventType::KeyUp), [i32::MIN, 0, 0, 0]));
+        assert_eq!(AutomationEventList::from_binary(&list.to_binary()).unwrap(), list);
+    }
+
+    #[test]
+    fn test_binary_size() {
+        let list = recording();
+        let text = list.to_text().len();
+        let binary = list.to_binary_compressed().len();
+        assert!(binary*10 < text, "compressed binary {binary} bytes, text {text} bytes");
+    }
+
+    #[test]
+    fn test_export_binary_compressed() {
+        let path = std::env::temp_dir().join(format!("raylib_automation_{}.raeb", std::process::id()));
+        let list = recording();
+        list.export_binary(&path).unwrap();
+        let data = std::fs::read(&path).unwrap();
+        assert_ne!(data[BINARY_MAGIC.len() + 1] & BINARY_FLAG_DEFLATE, 0);
+        assert_eq!(AutomationEventList::load_binary(&path).unwrap(), list);
+        assert_eq!(AutomationEventList::load(&path).unwrap(), list);
+        _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_binary_frame_overflow() {
+        let ty = u32::from(AutomationEventType::Input(InputEventType::KeyDown));
+        let data = binary(2, &[(ty, 1, i64::MAX, 0, [0; 4], [0; 4]), (ty, 1, 1, 0, [0; 4], [0; 4])]);
+        assert!(matches!(AutomationEventList::from_binary(&data), Err(AutomationEventError::ValueOutOfRange)));
+
+        let data = binary(3, &[(ty, 3, 0, i64::MAX, [0; 4], [0; 4])]);
+        assert!(matches!(AutomationEventList::from_binary(&data), Err(AutomationEventError::ValueOutOfRange)));
+
+        let data = binary(1, &[(ty, 1, -1, 0, [0; 4], [0; 4])]);
+        assert!(matches!(AutomationEventList::from_binary(&data), Err(AutomationEventError::ValueOutOfRange)));
+    }
+
+    #[test]
+    fn test_binary_params_overflow() {
+        let ty = u32::from(AutomationEventType::Input(InputEventType::MousePosition));
+        let data = binary(2, &[(ty, 2, 0, 1, [i64::MIN, 0, 0, 0], [i64::MAX, 0, 0, 0])]);
+        assert!(matches!(AutomationEventList::from_binary(&data), Err(AutomationEventError::ValueOutOfRange)));
+
+        let data = binary(1, &[(ty, 1, 0, 0, [i64::from(i32::MAX) + 1, 0, 0, 0], [0; 4])]);
+        assert!(matches!(AutomationEventList::from_binary(&data), Err(AutomationEventError::ValueOutOfRange)));
+    }
+
+    #[test]
+    fn test_binary_malformed() {
+        let data = recording().to_binary();
+        for len in [0, 3, BINARY_MAGIC.len() + 2, data.len() - 1] {
+            assert!(AutomationEventList::from_binary(&data[..len]).is_err());
+        }
+
+        let ty = u32::from(AutomationEventType::Input(InputEventType::KeyDown));
+        let data = binary(1, &[(ty, 2, 0, 1, [0; 4], [0; 4])]);
+        assert!(matches!(AutomationEventList::from_binary(&data), Err(AutomationEventError::ValueOutOfRange)));
+
+        let data = binary(1, &[(0, 1, 0, 0, [0; 4], [0; 4])]);
+        assert!(matches!(AutomationEventList::from_binary(&data), Err(AutomationEventError::InvalidEventType(0))));
+
+        let mut data = binary(0, &[]);
+        data[BINARY_MAGIC.len()] = BINARY_VERSION + 1;
+        assert!(matches!(AutomationEventList::from_binary(&data), Err(AutomationEventError::UnsupportedVersion(_))));
+
+        // Varint longer than 64 bits
+        let mut data = binary(0, &[]);
+        data.truncate(BINARY_MAGIC.len() + 2);
+        data.extend([0xFF; 11]);
+        assert!(matches!(AutomationEventList::from_binary(&data), Err(AutomationEventError::ValueOutOfRange)));
+    }
+}
@@ -0,0 +1,174 @@
+//! File system helpers: file checks, path manipulation and directory scanning
+//!
+//! Failures are logged and reported as empty results, like raylib does,
+//! `try_` variants return the underlying `io::Error` instead
+
+use std::{io, path::{Path, PathBuf}, time::{SystemTime, UNIX_EPOCH}};
+use crate::{config::MAX_FILEPATH_CAPACITY, tracelog};
+
+/// Filter tag to include directories in directory scans
+pub const DIRECTORY_FILTER_TAG: &str = "DIR";
+
+/// Check if file exists
+#[must_use]
+pub fn file_exists(file_name: impl AsRef<Path>) -> bool {
+    file_name.as_ref().is_file()
+}
+
+/// Check if a directory path exists
+#[must_use]
+pub fn directory_exists(dir_path: impl AsRef<Path>) -> bool {
+    dir_path.as_ref().is_dir()
+}
+
+/// Check if a given path is a file or a directory
+#[must_use]
+pub fn is_path_file(path: impl AsRef<Path>) -> bool {
+    path.as_ref().is_file()
+}
+
+/// Check file extension (including point: .png, .wav), several extensions can be
+/// checked at once separated by `;` (".png;.jpg")
+/// NOTE: Extensions checking is not case-sensitive
+#[must_use]
+pub fn is_file_extension(file_name: &str, ext: &str) -> bool {
+    get_file_extension(file_name)
+        .is_some_and(|file_ext| ext.split(';').any(|ext| ext.eq_ignore_ascii_case(file_ext)))
+}
+
+/// Get file length in bytes, 0 if the file can not be read
+#[must_use]
+pub fn get_file_length(file_name: impl AsRef<Path>) -> u64 {
+    let file_name = file_name.as_ref();
+    std::fs::metadata(file_name)
+        .inspect_err(|_| tracelog!(Warning, "FILEIO: [{}] Failed to get file length", file_name.display()))
+        .map_or(0, |metadata| metadata.len())
+}
+
+/// Get pointer to extension for a filename string (includes dot: ".png")
+#[must_use]
+pub fn get_file_extension(file_name: &str) -> Option<&str> {
+    let name = get_file_name(file_name);
+    name.rfind('.').map(|dot| &name[dot..])
+}
+
+/// Get pointer to filename for a path string
+#[must_use]
+pub fn get_file_name(file_path: &str) -> &str {
+    file_path.rsplit(['/', '\\']).next().unwrap_or(file_path)
+}
+
+/// Get filename string without extension
+#[must_use]
+pub fn get_file_name_without_ext(file_path: &str) -> &str {
+    let name = get_file_name(file_path);
+    name.rfind('.').map_or(name, |dot| &name[..dot])
+}
+
+/// Get full path for a given file path (uses '/' and '\\' as separators)
+/// NOTE: Returns "." for a file name without directory and "/" for root paths
+#[must_use]
+pub fn get_directory_path(file_path: &str) -> &str {
+    match file_path.rfind(['/', '\\']) {
+        Some(0) => &file_path[..1],
+        Some(index) => &file_path[..index],
+        None => ".",
+    }
+}
+
+/// Get previous directory path for a given path
+#[must_use]
+pub fn get_prev_directory_path(dir_path: &str) -> &str {
+    get_directory_path(dir_path.trim_end_matches(['/', '\\']))
+}
+
+/// Get current working directory, empty if it can not be retrieved
+#[must_use]
+pub fn get_working_directory() -> PathBuf {
+    std::env::current_dir()
+        .inspect_err(|_| tracelog!(Warning, "SYSTEM: Failed to get working directory"))
+        .unwrap_or_default()
+}
+
+/// Change working directory, return true on success
+pub fn change_directory(dir: impl AsRef<Path>) -> bool {
+    let dir = dir.as_ref();
+    std::env::set_current_dir(dir)
+        .inspect(|()| tracelog!(Info, "SYSTEM: Working Directory: {}", dir.display()))
+        .inspect_err(|_| tracelog!(Warning, "SYSTEM: Failed to change to directory: {}", dir.display()))
+        .is_ok()
+}
+
+/// Get file modification time (last write time), in seconds since the Unix epoch, 0 on failure
+#[must_use]
+pub fn get_file_mod_time(file_name: impl AsRef<Path>) -> u64 {
+    try_get_file_mod_time(file_name)
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |time| time.as_secs())
+}
+
+/// Get file modification time (last write time)
+/// # Errors
+/// Returns an error if file metadata can not be read, or the platform does not record it
+pub fn try_get_file_mod_time(file_name: impl AsRef<Path>) -> io::Result<SystemTime> {
+    std::fs::metadata(file_name)?.modified()
+}
+
+/// Load directory filepaths
+/// NOTE: Files and directories are included, not scanning subdirectories
+#[must_use]
+pub fn load_directory_files(dir_path: impl AsRef<Path>) -> Vec<PathBuf> {
+    load_directory_files_ex(dir_path, "", false)
+}
+
+/// Load directory filepaths with extension filtering and recursive directory scan
+/// NOTE: Filter is a list of extensions (".png;.jpg"), add "DIR" to include directories,
+/// an empty filter includes everything (directories too, unless scanning subdirectories)
+#[must_use]
+pub fn load_directory_files_ex(base_path: impl AsRef<Path>, filter: &str, scan_subdirs: bool) -> Vec<PathBuf> {
+    let base_path = base_path.as_ref();
+    try_load_directory_files_ex(base_path, filter, scan_subdirs)
+        .inspect_err(|_| tracelog!(Warning, "FILEIO: Failed to open requested directory: {}", base_path.display()))
+        .unwrap_or_default()
+}
+
+/// Load directory filepaths with extension filtering and recursive directory scan
+/// NOTE: Same as [`load_directory_files_ex`], at most `MAX_FILEPATH_CAPACITY` paths are returned
+/// # Errors
+/// Returns an error if the base directory (or a subdirectory) can not be read
+pub fn try_load_directory_files_ex(base_path: impl AsRef<Path>, filter: &str, scan_subdirs: bool) -> io::Result<Vec<PathBuf>> {
+    let filter = Some(filter).filter(|filter| !filter.is_empty());
+    let mut files = Vec::new();
+    scan_directory_files(base_path.as_ref(), &mut files, filter, scan_subdirs)?;
+    Ok(files)
+}
+
+/// Scan all files and directories in a base path, optionally recursive
+fn scan_directory_files(base_path: &Path, files: &mut Vec<PathBuf>, filter: Option<&str>, scan_subdirs: bool) -> io::Result<()> {
+    for entry in std::fs::read_dir(base_path)? {
+        let path = entry?.path();
+        let include = if path.is_file() {
+            filter.is_none_or(|filter| path.to_str().is_some_and(|name| is_file_extension(name, filter)))
+        } else {
+            // NOTE: Directories are included without filter only when not recursive
+            filter.map_or(!scan_subdirs, |filter| filter.contains(DIRECTORY_FILTER_TAG))
+        };
+
+        if include {
+            if files.len() >= MAX_FILEPATH_CAPACITY {
+                tracelog!(Warning, "FILEIO: Maximum filepath scan capacity reached ({MAX_FILEPATH_CAPACITY} files)");
+                return Ok(());
+            }
+            files.push(path.clone());
+        }
+
+        if scan_subdirs && path.is_dir() {
+            scan_directory_files(&path, files, filter, scan_subdirs)?;
+            if files.len() >= MAX_FILEPATH_CAPACITY {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,222 @@
+//! User callbacks invoked at fixed points of the frame and window lifecycle
+
+use crate::{prelude::*, tracelog};
+
+/// Point of the frame or window lifecycle where hooks are invoked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HookStage {
+    /// After input polling, before user update code
+    PreUpdate,
+    /// After `begin_drawing()`
+    PreDraw,
+    /// Before `end_drawing()` swaps buffers
+    PostDraw,
+    /// After `end_drawing()`, frame completed
+    PostFrame,
+    /// Before shutdown, last chance to access the window
+    WindowClose,
+}
+
+/// Registered hook identifier, used for removal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HookId(u64);
+
+type HookCallback<'a> = Box<dyn FnMut(&mut Core<'a>) + 'a>;
+
+struct Hook<'a> {
+    id: HookId,
+    stage: HookStage,
+    /// Taken out while the callback runs
+    callback: Option<HookCallback<'a>>,
+}
+
+/// Registered hooks, in registration order
+#[derive(Default)]
+pub(crate) struct Hooks<'a> {
+    next_id: u64,
+    list: Vec<Hook<'a>>,
+    /// Stage currently running, hooks are not reentrant
+    running: Option<HookStage>,
+}
+
+impl<'a> Core<'a> {
+    /// Register a callback invoked at every `stage`, in registration order within a stage
+    /// NOTE: Hooks must not call `begin_drawing()`/`end_drawing()`, those calls are ignored with an error.
+    /// Hooks added from a hook are first invoked the next time their stage runs
+    ///
+    /// Overlay drawn on top of every frame:
+    /// ```ignore
+    /// let mut history = Vec::new();
+    /// core.add_hook(HookStage::PostDraw, move |core| {
+    ///     history.push(get_frame_time(core));
+    ///     // ...draw frame time graph from history...
+    /// });
+    /// ```
+    pub fn add_hook(&mut self, stage: HookStage, callback: impl FnMut(&mut Core<'a>) + 'a) -> HookId {
+        let id = HookId(self.hooks.next_id);
+        self.hooks.next_id += 1;
+        self.hooks.list.push(Hook { id, stage, callback: Some(Box::new(callback)) });
+        id
+    }
+
+    /// Unregister a hook, returns false if it was already removed
+    /// NOTE: A hook can remove itself, it completes its current invocation
+    pub fn remove_hook(&mut self, id: HookId) -> bool {
+        let count = self.hooks.list.len();
+        self.hooks.list.retain(|hook| hook.id != id);
+        self.hooks.list.len() != count
+    }
+
+    /// Get the stage whose hooks are currently running, `None` outside hooks
+    #[inline]
+    #[must_use]
+    pub fn running_hook_stage(&self) -> Option<HookStage> {
+        self.hooks.running
+    }
+
+    /// Check whether `begin_drawing()` is allowed, logs an error when called from a hook
+    pub(crate) fn can_begin_drawing(&self) -> bool {
+        if let Some(stage) = self.hooks.running {
+            tracelog!(Error, "DRAWING: begin_drawing() can not be called from a {stage:?} hook");
+            return false;
+        }
+        true
+    }
+
+    /// Invoke all hooks registered for a stage
+    pub(crate) fn run_hooks(&mut self, stage: HookStage) {
+        if let Some(running) = self.hooks.running {
+            tracelog!(Error, "HOOKS: Stage {stage:?} can not run from a {running:?} hook");
+            return;
+        }
+
+        let ids: Vec<HookId> = self.hooks.list.iter()
+            .filter(|hook| hook.stage == stage)
+            .map(|hook| hook.id)
+            .collect();
+
+        self.hooks.running = Some(stage);
+        for id in ids {
+            let Some(mut callback) = self.hooks.list.iter_mut()
+                .find(|hook| hook.id == id)
+                .and_then(|hook| hook.callback.take())
+            else { continue };
+
+            callback(self);
+
+            // Put callback back unless the hook was removed meanwhile
+            if let Some(hook) = self.hooks.list.iter_mut().find(|hook| hook.id == id) {
+                hook.callback = Some(callback);
+            }
+        }
+        self.hooks.running = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    type Log = Rc<RefCell<Vec<&'static str>>>;
+
+    fn logging_hook<'a>(log: &Log, name: &'static str) -> impl FnMut(&mut Core<'a>) + 'a {
+        let log = Rc::clone(log);
+        move |_| log.borrow_mut().push(name)
+    }
+
+    #[test]
+    fn test_hook_order_within_stage() {
+        let log = Log::default();
+        let mut core = Core::default();
+        core.add_hook(HookStage::PostDraw, logging_hook(&log, "first"));
+        core.add_hook(HookStage::PreDraw, logging_hook(&log, "other stage"));
+        core.add_hook(HookStage::PostDraw, logging_hook(&log, "second"));
+        core.add_hook(HookStage::PostDraw, logging_hook(&log, "third"));
+
+        core.run_hooks(HookStage::PostDraw);
+        assert_eq!(*log.borrow(), ["first", "second", "third"]);
+        assert_eq!(core.running_hook_stage(), None);
+    }
+
+    #[test]
+    fn test_remove_hook() {
+        let log = Log::default();
+        let mut core = Core::default();
+        let first = core.add_hook(HookStage::PreUpdate, logging_hook(&log, "first"));
+        core.add_hook(HookStage::PreUpdate, logging_hook(&log, "second"));
+
+        assert!(core.remove_hook(first));
+        assert!(!core.remove_hook(first), "already removed");
+        core.run_hooks(HookStage::PreUpdate);
+        assert_eq!(*log.borrow(), ["second"]);
+    }
+
+    #[test]
+    fn test_hook_removes_itself() {
+        let log = Log::default();
+        let mut core = Core::default();
+        let id = Rc::new(RefCell::new(None));
+        let hook_log = Rc::clone(&log);
+        let hook_id = Rc::clone(&id);
+        *id.borrow_mut() = Some(core.add_hook(HookStage::PostFrame, move |core| {
+            hook_log.borrow_mut().push("once");
+            assert!(core.remove_hook(hook_id.borrow().unwrap()));
+        }));
+        core.add_hook(HookStage::PostFrame, logging_hook(&log, "after"));
+
+        core.run_hooks(HookStage::PostFrame);
+        core.run_hooks(HookStage::PostFrame);
+        assert_eq!(*log.borrow(), ["once", "after", "after"]);
+    }
+
+    #[test]
+    fn test_hooks_not_reentrant() {
+        let log = Log::default();
+        let mut core = Core::default();
+        let hook_log = Rc::clone(&log);
+        core.add_hook(HookStage::PostDraw, move |core| {
+            hook_log.borrow_mut().push("outer");
+            assert_eq!(core.running_hook_stage(), Some(HookStage::PostDraw));
+            core.run_hooks(HookStage::PostFrame); // Ignored
+            core.run_hooks(HookStage::PostDraw); // Ignored, would recurse
+        });
+        core.add_hook(HookStage::PostFrame, logging_hook(&log, "nested"));
+
+        core.run_hooks(HookStage::PostDraw);
+        assert_eq!(*log.borrow(), ["outer"]);
+        core.run_hooks(HookStage::PostFrame);
+        assert_eq!(*log.borrow(), ["outer", "nested"]);
+    }
+
+    #[test]
+    fn test_hook_added_from_hook() {
+        let log = Log::default();
+        let mut core = Core::default();
+        let hook_log = Rc::clone(&log);
+        core.add_hook(HookStage::PreDraw, move |core| {
+            core.add_hook(HookStage::PreDraw, logging_hook(&hook_log, "added"));
+        });
+
+        core.run_hooks(HookStage::PreDraw);
+        assert!(log.borrow().is_empty(), "first invoked the next time the stage runs");
+        core.run_hooks(HookStage::PreDraw);
+        assert_eq!(*log.borrow(), ["added"]);
+    }
+
+    #[test]
+    fn test_begin_drawing_rejected_in_hook() {
+        let allowed = Rc::new(RefCell::new(None));
+        let mut core = Core::default();
+        assert!(core.can_begin_drawing());
+        let hook_allowed = Rc::clone(&allowed);
+        core.add_hook(HookStage::PreDraw, move |core| {
+            *hook_allowed.borrow_mut() = Some(core.can_begin_drawing());
+        });
+
+        core.run_hooks(HookStage::PreDraw);
+        assert_eq!(*allowed.borrow(), Some(false));
+        assert!(core.can_begin_drawing());
+    }
+}
@@ -151,6 +151,7 @@ pub enum MouseButton {
 
 // Mouse cursor
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub enum MouseCursor {
     /// Default pointer shape
     #[default]
@@ -181,6 +182,7 @@ pub type GamepadID = usize;
 
 /// Gamepad buttons
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum GamepadButton {
     /// Gamepad left DPAD up button
     LeftFaceUp,
@@ -220,6 +222,7 @@ pub enum GamepadButton {
 
 /// Gamepad axis
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum GamepadAxis {
     /// Gamepad left stick X axis
     LeftX,
@@ -304,6 +307,74 @@ impl Keyboard {
     pub const MAX_KEY_PRESSED_QUEUE: usize = MAX_KEY_PRESSED_QUEUE;
     /// Maximum number of characters in the char input queue
     pub const MAX_CHAR_PRESSED_QUEUE: usize = MAX_CHAR_PRESSED_QUEUE;
+
+    /// Register a key down event, returns true if it's the exit key
+    /// NOTE: Key is queued once per press, queue full drops the key press but not the key state
+    pub(crate) fn register_key_down(&mut self, key: KeyboardKey, repeat: bool) -> bool {
+        if repeat {
+            self.key_repeat_in_frame[key as usize] = 1;
+        }
+        if self.current_key_state[key as usize] == 0 {
+            _ = self.key_pressed_queue.try_push(Some(key));
+        }
+        self.current_key_state[key as usize] = 1;
+
+        self.exit_key == Some(key)
+    }
+
+    /// Register a key up event
+    #[inline]
+    pub(crate) fn register_key_up(&mut self, key: KeyboardKey) {
+        self.current_key_state[key as usize] = 0;
+    }
+
+    /// Get key state in a state array, keys out of range are never down
+    #[inline]
+    fn state(states: &[u8; MAX_KEYBOARD_KEYS], key: KeyboardKey) -> bool {
+        states.get(key as usize).is_some_and(|&state| state == 1)
+    }
+
+    /// Check if a key has been pressed once
+    #[must_use]
+    pub fn is_key_pressed(&self, key: KeyboardKey) -> bool {
+        !Self::state(&self.previous_key_state, key) && Self::state(&self.current_key_state, key)
+    }
+
+    /// Check if a key has been pressed again (only on desktop platforms)
+    #[must_use]
+    pub fn is_key_pressed_repeat(&self, key: KeyboardKey) -> bool {
+        Self::state(&self.key_repeat_in_frame, key)
+    }
+
+    /// Check if a key is being pressed
+    #[must_use]
+    pub fn is_key_down(&self, key: KeyboardKey) -> bool {
+        Self::state(&self.current_key_state, key)
+    }
+
+    /// Check if a key has been released once
+    #[must_use]
+    pub fn is_key_released(&self, key: KeyboardKey) -> bool {
+        Self::state(&self.previous_key_state, key) && !Self::state(&self.current_key_state, key)
+    }
+
+    /// Check if a key is NOT being pressed
+    #[must_use]
+    pub fn is_key_up(&self, key: KeyboardKey) -> bool {
+        !Self::state(&self.current_key_state, key)
+    }
+
+    /// Get key pressed, call it multiple times for keys queued, returns `None` when the queue is empty
+    /// NOTE: Keys are returned in the order they were pressed
+    pub fn get_key_pressed(&mut self) -> Option<KeyboardKey> {
+        self.key_pressed_queue.pop_at(0).flatten()
+    }
+
+    /// Get char pressed (unicode), call it multiple times for chars queued, returns `None` when the queue is empty
+    /// NOTE: Chars are returned in the order they were typed
+    pub fn get_char_pressed(&mut self) -> Option<char> {
+        self.char_pressed_queue.pop_at(0)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -337,18 +408,114 @@ pub struct Mouse {
 impl Mouse {
     /// Maximum number of mouse buttons supported
     pub const MAX_BUTTONS: usize = MAX_MOUSE_BUTTONS;
+
+    /// Check if cursor is not visible
+    #[inline]
+    #[must_use]
+    pub fn is_cursor_hidden(&self) -> bool {
+        self.is_cursor_hidden
+    }
+
+    /// Check if cursor is on the screen
+    #[inline]
+    #[must_use]
+    pub fn is_cursor_on_screen(&self) -> bool {
+        self.is_cursor_on_screen
+    }
+
+    /// Get mouse button state in a state array, buttons out of range are never down
+    #[inline]
+    fn state(states: [u8; MAX_MOUSE_BUTTONS], button: MouseButton) -> bool {
+        states.get(button as usize).is_some_and(|&state| state == 1)
+    }
+
+    /// Check if a mouse button has been pressed once
+    #[must_use]
+    pub fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        !Self::state(self.previous_button_state, button) && Self::state(self.current_button_state, button)
+    }
+
+    /// Check if a mouse button is being pressed
+    #[must_use]
+    pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        Self::state(self.current_button_state, button)
+    }
+
+    /// Check if a mouse button has been released once
+    #[must_use]
+    pub fn is_mouse_button_released(&self, button: MouseButton) -> bool {
+        Self::state(self.previous_button_state, button) && !Self::state(self.current_button_state, button)
+    }
+
+    /// Check if a mouse button is NOT being pressed
+    #[must_use]
+    pub fn is_mouse_button_up(&self, button: MouseButton) -> bool {
+        !Self::state(self.current_button_state, button)
+    }
+
+    /// Get mouse position XY, with mouse offset and scale applied
+    pub fn get_mouse_position(&self) -> Position2 {
+        (self.current_position + self.offset)*self.scale
+    }
+
+    /// Get mouse delta between frames
+    pub fn get_mouse_delta(&self) -> Offset2 {
+        self.current_position - self.previous_position
+    }
+
+    /// Set mouse offset, applied to [`Mouse::get_mouse_position`]
+    pub fn set_mouse_offset(&mut self, offset: Offset2) {
+        self.offset = offset;
+    }
+
+    /// Set mouse scaling, applied to [`Mouse::get_mouse_position`] after the offset
+    pub fn set_mouse_scale(&mut self, scale: Vector2) {
+        self.scale = scale;
+    }
+
+    /// Get mouse wheel movement for X or Y, whichever is larger
+    #[must_use]
+    pub fn get_mouse_wheel_move(&self) -> f32 {
+        if self.current_wheel_move.x.abs() > self.current_wheel_move.y.abs() {
+            self.current_wheel_move.x
+        } else {
+            self.current_wheel_move.y
+        }
+    }
+
+    /// Get mouse wheel movement for both X and Y
+    pub fn get_mouse_wheel_move_v(&self) -> Vector2 {
+        self.current_wheel_move
+    }
+
+    /// Register mouse motion
+    /// NOTE: In relative mode (cursor disabled) the reported position does not move,
+    /// deltas are accumulated instead so position differences keep working
+    pub(crate) fn apply_motion(&mut self, position: Vector2, delta: Vector2, relative: bool) {
+        if relative {
+            self.current_position += delta;
+        } else {
+            self.current_position = position;
+        }
+    }
+
+    /// Set mouse position, previous position included so no delta is reported
+    pub(crate) fn reset_position(&mut self, position: Vector2) {
+        self.current_position = position;
+        self.previous_position = position;
+    }
 }
 
 #[derive(Debug, Default)]
 pub struct TouchPoint {
     /// Point identifiers
-    pub(crate) point_id: u32,
+    pub(crate) point_id: u64,
     /// Touch position on screen
     pub(crate) position: Vector2,
     /// Registers current touch state
-    pub(crate) current_touch_state: char,
+    pub(crate) current_touch_state: u8,
     /// Registers previous touch state
-    pub(crate) previous_touch_state: char,
+    pub(crate) previous_touch_state: u8,
 }
 
 #[derive(Debug, Default)]
@@ -359,6 +526,50 @@ pub struct Touch {
 impl Touch {
     /// Maximum number of touch points supported
     pub const MAX: usize = MAX_TOUCH_POINTS;
+
+    /// Get number of touch points
+    /// NOTE: Points released this frame are still counted, until next frame
+    #[must_use]
+    pub fn get_touch_point_count(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Get touch point identifier for given index, `None` if out of range
+    #[must_use]
+    pub fn get_touch_point_id(&self, index: usize) -> Option<u64> {
+        self.items.get(index).map(|point| point.point_id)
+    }
+
+    /// Get touch position XY for a touch point index (relative to screen size), `None` if out of range
+    #[must_use]
+    pub fn get_touch_position(&self, index: usize) -> Option<Position2> {
+        self.items.get(index).map(|point| point.position)
+    }
+
+    /// Register a touch point down, ignored if all touch points are in use
+    pub(crate) fn press(&mut self, point_id: u64, position: Position2) {
+        if let Some(point) = self.items.iter_mut().find(|point| point.point_id == point_id) {
+            point.position = position;
+            point.current_touch_state = 1;
+        } else {
+            _ = self.items.try_push(TouchPoint { point_id, position, current_touch_state: 1, previous_touch_state: 0 });
+        }
+    }
+
+    /// Register a touch point motion
+    pub(crate) fn move_to(&mut self, point_id: u64, position: Position2) {
+        if let Some(point) = self.items.iter_mut().find(|point| point.point_id == point_id) {
+            point.position = position;
+        }
+    }
+
+    /// Register a touch point up, the point is removed on next frame
+    pub(crate) fn release(&mut self, point_id: u64, position: Position2) {
+        if let Some(point) = self.items.iter_mut().find(|point| point.point_id == point_id) {
+            point.position = position;
+            point.current_touch_state = 0;
+        }
+    }
 }
 
 /// Maximum number of bytes in a gamepad name
@@ -389,17 +600,109 @@ impl Gamepads {
     pub const MAX_VIBRATION_TIME: f32 = MAX_GAMEPAD_VIBRATION_TIME;
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Gamepads {
     /// Register last gamepad button pressed
     pub(crate) last_button_pressed: Option<GamepadButton>,
     /// Gamepad array
     pub(crate) items: ArrayVec<Gamepad, MAX_GAMEPADS>,
+    /// Axis movement under this value is reported as rest position
+    pub(crate) axis_deadzone: f32,
+}
+
+impl Default for Gamepads {
+    fn default() -> Self {
+        Self {
+            last_button_pressed: None,
+            items: ArrayVec::new(),
+            axis_deadzone: GAMEPAD_AXIS_DEADZONE,
+        }
+    }
 }
 
 impl Gamepads {
     /// Maximum number of gamepads supported
     pub const MAX: usize = MAX_GAMEPADS;
+
+    /// Get a connected gamepad, `None` if not available or out of range
+    #[inline]
+    fn get(&self, gamepad: GamepadID) -> Option<&Gamepad> {
+        self.items.get(gamepad).filter(|item| item.ready)
+    }
+
+    /// Get gamepad button state in a state array
+    #[inline]
+    fn state(&self, gamepad: GamepadID, button: GamepadButton, previous: bool) -> bool {
+        self.get(gamepad).is_some_and(|item| {
+            let states = if previous { &item.previous_button_state } else { &item.current_button_state };
+            states.get(button as usize).is_some_and(|&state| state == 1)
+        })
+    }
+
+    /// Check if a gamepad is available
+    #[must_use]
+    pub fn is_gamepad_available(&self, gamepad: GamepadID) -> bool {
+        self.get(gamepad).is_some()
+    }
+
+    /// Get gamepad internal name id, `None` if not available
+    #[must_use]
+    pub fn get_gamepad_name(&self, gamepad: GamepadID) -> Option<&str> {
+        self.get(gamepad).map(|item| item.name.as_str())
+    }
+
+    /// Check if a gamepad button has been pressed once
+    #[must_use]
+    pub fn is_gamepad_button_pressed(&self, gamepad: GamepadID, button: GamepadButton) -> bool {
+        !self.state(gamepad, button, true) && self.state(gamepad, button, false)
+    }
+
+    /// Check if a gamepad button is being pressed
+    #[must_use]
+    pub fn is_gamepad_button_down(&self, gamepad: GamepadID, button: GamepadButton) -> bool {
+        self.state(gamepad, button, false)
+    }
+
+    /// Check if a gamepad button has been released once
+    #[must_use]
+    pub fn is_gamepad_button_released(&self, gamepad: GamepadID, button: GamepadButton) -> bool {
+        self.state(gamepad, button, true) && !self.state(gamepad, button, false)
+    }
+
+    /// Check if a gamepad button is NOT being pressed
+    #[must_use]
+    pub fn is_gamepad_button_up(&self, gamepad: GamepadID, button: GamepadButton) -> bool {
+        !self.state(gamepad, button, false)
+    }
+
+    /// Get the last gamepad button pressed
+    #[must_use]
+    pub fn get_gamepad_button_pressed(&self) -> Option<GamepadButton> {
+        self.last_button_pressed
+    }
+
+    /// Get gamepad axis count for a gamepad, 0 if not available
+    #[must_use]
+    pub fn get_gamepad_axis_count(&self, gamepad: GamepadID) -> u32 {
+        self.get(gamepad).map_or(0, |item| item.axis_count)
+    }
+
+    /// Get axis movement value for a gamepad axis
+    /// NOTE: Sticks range [-1..1] with rest position 0, triggers range [-1..1] with rest position -1.
+    /// Movement within the deadzone from rest position is reported as rest position
+    #[must_use]
+    pub fn get_gamepad_axis_movement(&self, gamepad: GamepadID, axis: GamepadAxis) -> f32 {
+        let rest = if matches!(axis, GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger) { -1.0 } else { 0.0 };
+        match self.get(gamepad).and_then(|item| item.axis_state.get(axis as usize)) {
+            Some(&value) if (value - rest).abs() > self.axis_deadzone => value,
+            _ => rest,
+        }
+    }
+
+    /// Set gamepad axis deadzone, default `GAMEPAD_AXIS_DEADZONE`
+    pub fn set_gamepad_axis_deadzone(&mut self, deadzone: f32) {
+        self.axis_deadzone = deadzone.max(0.0);
+    }
 }
 
 #[derive(Debug, Default)]
@@ -408,4 +711,153 @@ pub struct Input {
     pub mouse: Mouse,
     pub touch: Touch,
     pub gamepad: Gamepads,
+    /// Poll input after frame pacing wait instead of at frame start
+    pub(crate) late_polling: bool,
+    /// Previous state already registered for the current frame
+    pub(crate) frame_started: bool,
+}
+
+impl Input {
+    /// Register current state as previous state and reset per-frame state
+    /// NOTE: Done once per frame boundary, polling events again in the same frame
+    /// must not call it, or pressed/released transitions would be lost
+    pub(crate) fn begin_frame(&mut self) {
+        if self.frame_started {
+            return;
+        }
+        self.frame_started = true;
+
+        // Reset keys/chars pressed registered
+        self.keyboard.key_pressed_queue.clear();
+        self.keyboard.char_pressed_queue.clear();
+        // Reset key repeats
+        self.keyboard.key_repeat_in_frame = [0; MAX_KEYBOARD_KEYS];
+        // Register previous keys states
+        self.keyboard.previous_key_state = self.keyboard.current_key_state;
+
+        // Reset last gamepad button registered
+        self.gamepad.last_button_pressed = None;
+        // Register previous gamepad buttons states
+        for gamepad in &mut self.gamepad.items {
+            gamepad.previous_button_state = gamepad.current_button_state;
+        }
+
+        // Register previous mouse states
+        self.mouse.previous_button_state = self.mouse.current_button_state;
+        self.mouse.previous_wheel_move = self.mouse.current_wheel_move;
+        self.mouse.current_wheel_move = Vector2::ZERO;
+        self.mouse.previous_position = self.mouse.current_position;
+
+        // Remove touch points released last frame, register previous touch states
+        self.touch.items.retain(|point| point.current_touch_state == 1);
+        for point in &mut self.touch.items {
+            point.previous_touch_state = point.current_touch_state;
+        }
+    }
+
+    /// Mark the end of the frame, next [`Input::begin_frame`] registers previous state again
+    #[inline]
+    pub(crate) fn end_frame(&mut self) {
+        self.frame_started = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_input_rolls_over_once_per_frame() {
+        let mut input = Input::default();
+        let space = KeyboardKey::Space as usize;
+
+        // Frame 1: key event received by the first poll, a second poll in the same frame keeps the press
+        input.begin_frame();
+        input.keyboard.current_key_state[space] = 1;
+        input.keyboard.key_pressed_queue.push(Some(KeyboardKey::Space));
+        input.mouse.current_wheel_move = Vector2::new(0.0, 1.0);
+        input.begin_frame();
+        assert!(input.keyboard.is_key_pressed(KeyboardKey::Space));
+        assert_eq!(input.mouse.get_mouse_wheel_move(), 1.0);
+        assert_eq!(input.keyboard.get_key_pressed(), Some(KeyboardKey::Space));
+        input.end_frame();
+
+        // Frame 2: held key is no longer pressed, per-frame state is reset
+        input.keyboard.key_pressed_queue.push(Some(KeyboardKey::Space));
+        input.begin_frame();
+        assert!(input.keyboard.is_key_down(KeyboardKey::Space));
+        assert!(!input.keyboard.is_key_pressed(KeyboardKey::Space));
+        assert_eq!(input.mouse.get_mouse_wheel_move(), 0.0);
+        assert_eq!(input.keyboard.get_key_pressed(), None);
+
+        // Release late in the frame (late polling) is seen by the next update, once
+        input.keyboard.current_key_state[space] = 0;
+        input.begin_frame();
+        assert!(input.keyboard.is_key_released(KeyboardKey::Space));
+        input.end_frame();
+        input.begin_frame();
+        assert!(!input.keyboard.is_key_released(KeyboardKey::Space));
+    }
+
+    #[test]
+    fn test_mouse_delta_across_polls() {
+        let mut input = Input::default();
+        input.begin_frame();
+        input.mouse.current_position = Vector2::new(10.0, 5.0);
+        input.mouse.current_button_state[MouseButton::Left as usize] = 1;
+        input.begin_frame();
+        input.mouse.current_position = Vector2::new(12.0, 8.0);
+
+        // Motion from both polls adds up to a single frame delta
+        assert_eq!(input.mouse.get_mouse_delta(), Vector2::new(12.0, 8.0));
+        assert!(input.mouse.is_mouse_button_pressed(MouseButton::Left));
+        input.end_frame();
+
+        input.begin_frame();
+        assert_eq!(input.mouse.get_mouse_delta(), Vector2::ZERO);
+        assert!(!input.mouse.is_mouse_button_pressed(MouseButton::Left));
+        assert!(input.mouse.is_mouse_button_down(MouseButton::Left));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_mouse_polling() {
+        let mut mouse = Mouse { scale: Vector2::new(1.0, 1.0), ..Default::default() };
+        let right = MouseButton::Right as usize;
+        assert!(mouse.is_mouse_button_up(MouseButton::Right));
+
+        // Press, hold, release
+        mouse.current_button_state[right] = 1;
+        assert!(mouse.is_mouse_button_pressed(MouseButton::Right) && mouse.is_mouse_button_down(MouseButton::Right));
+        mouse.previous_button_state[right] = 1;
+        assert!(!mouse.is_mouse_button_pressed(MouseButton::Right) && mouse.is_mouse_button_down(MouseButton::Right));
+        mouse.current_button_state[right] = 0;
+        assert!(mouse.is_mouse_button_released(MouseButton::Right) && mouse.is_mouse_button_up(MouseButton::Right));
+        assert!(!mouse.is_mouse_button_down(MouseButton::Left));
+
+        // Offset is applied before scale, delta is unaffected by both
+        mouse.apply_motion(Vector2::new(100.0, 50.0), Vector2::ZERO, false);
+        assert_eq!(mouse.get_mouse_position(), Vector2::new(100.0, 50.0));
+        mouse.set_mouse_offset(Vector2::new(-20.0, 10.0));
+        mouse.set_mouse_scale(Vector2::new(0.5, 2.0));
+        assert_eq!(mouse.get_mouse_position(), Vector2::new(40.0, 120.0));
+        assert_eq!(mouse.get_mouse_delta(), Vector2::new(100.0, 50.0));
+
+        // Relative mode accumulates deltas, reset reports no delta
+        mouse.reset_position(Vector2::new(10.0, 10.0));
+        assert_eq!(mouse.get_mouse_delta(), Vector2::ZERO);
+        mouse.apply_motion(Vector2::new(10.0, 10.0), Vector2::new(3.0, -1.0), true);
+        mouse.apply_motion(Vector2::new(10.0, 10.0), Vector2::new(2.0, -1.0), true);
+        assert_eq!(mouse.get_mouse_delta(), Vector2::new(5.0, -2.0));
+
+        // Wheel reports the larger axis, vertical on ties
+        mouse.current_wheel_move = Vector2::new(-3.0, 1.0);
+        assert_eq!(mouse.get_mouse_wheel_move(), -3.0);
+        mouse.current_wheel_move = Vector2::new(0.5, -2.0);
+        assert_eq!(mouse.get_mouse_wheel_move(), -2.0);
+        mouse.current_wheel_move = Vector2::new(1.0, -1.0);
+        assert_eq!(mouse.get_mouse_wheel_move(), -1.0);
+        assert_eq!(mouse.get_mouse_wheel_move_v(), Vector2::new(1.0, -1.0));
+    }
 }
@@ -1,12 +1,17 @@
-use std::path::Path;
-use crate::{platforms::rcore_desktop_sdl::Platform, prelude::*, tracelog};
+use std::{fs::OpenOptions, io::{Read, Seek, SeekFrom, Write}, path::{Path, PathBuf}};
+use crate::{config::{STORAGE_DATA_FILE, BACKGROUND_THROTTLE_FPS, FPS_AVERAGE_TIME_SECONDS, FPS_CAPTURE_FRAMES_COUNT}, platforms::rcore_desktop_sdl::{get_time, InitPlatformError, Platform}, prelude::*, rlgl::{FlushRecord, MatrixMode, RLGL}, shapes::ShapesTexture, tracelog};
 #[cfg(feature = "support_gif_recording")]
-use crate::external::msf_gif::MsfGifResult;
+use crate::{config::GIF_RECORD_FRAMERATE, external::msf_gif::{msf_gif_begin, msf_gif_end, msf_gif_frame, MsfGifState}};
+use hooks::Hooks;
 use input::Input;
 use window::Window;
 
 pub mod window;
 pub mod input;
+pub mod automation;
+pub mod random;
+pub mod hooks;
+pub mod files;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Point {
@@ -20,6 +25,7 @@ pub struct Size {
     pub height: u32,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum InputEventType {
     /// param[0]: key
     KeyUp,
@@ -57,6 +63,7 @@ pub enum InputEventType {
     Gesture,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum WindowEventType {
     /// no params
     Close,
@@ -68,6 +75,7 @@ pub enum WindowEventType {
     Resize,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CustomEventType {
     /// no params
     TakeScreenshot,
@@ -76,6 +84,7 @@ pub enum CustomEventType {
 }
 
 /// Automation events type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AutomationEventType {
     Input(InputEventType),
     Window(WindowEventType),
@@ -83,6 +92,7 @@ pub enum AutomationEventType {
 }
 
 /// Automation event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AutomationEvent {
     /// Event frame
     pub(crate) frame: usize,
@@ -98,6 +108,76 @@ pub struct Storage {
     pub(crate) base_path: Option<&'static Path>,
 }
 
+impl Storage {
+    /// Resolve a path relative to the base path, absolute paths are kept
+    pub(crate) fn resolve(&self, path: impl AsRef<Path>) -> PathBuf {
+        let path = path.as_ref();
+        match self.base_path {
+            Some(base_path) if path.is_relative() => base_path.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    /// Save integer value to storage file (to defined position)
+    /// NOTE: Storage positions is directly related to file memory layout (4 bytes each integer),
+    /// the file grows as needed and gaps are zero-filled
+    fn save_value(&self, position: u32, value: i32) -> Result<(), StorageError> {
+        let offset = u64::from(position)*4;
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(self.resolve(STORAGE_DATA_FILE))?;
+        if file.metadata()?.len() < offset + 4 {
+            file.set_len(offset + 4)?;
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(&value.to_le_bytes())?;
+        Ok(())
+    }
+
+    /// Load integer value from storage file (from defined position)
+    fn load_value(&self, position: u32) -> Result<i32, StorageError> {
+        let offset = u64::from(position)*4;
+        let mut file = std::fs::File::open(self.resolve(STORAGE_DATA_FILE))?;
+        if file.metadata()?.len() < offset + 4 {
+            return Err(StorageError::PositionNotFound(position));
+        }
+        let mut bytes = [0; 4];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut bytes)?;
+        Ok(i32::from_le_bytes(bytes))
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum StorageError {
+    Io(std::io::Error),
+    /// Position is beyond the end of the storage file
+    PositionNotFound(u32),
+}
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::PositionNotFound(position) => write!(f, "storage position not found: {position}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::PositionNotFound(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for StorageError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Time {
     /// Current time measure
@@ -116,24 +196,80 @@ pub struct Time {
     pub(crate) base: usize,
     /// Frame counter
     pub(crate) frame_counter: usize,
+    /// Frame time captures for the FPS average
+    pub(crate) fps_history: FpsHistory,
+}
+
+/// Ring buffer of frame time captures, one capture every `FPS_AVERAGE_TIME_SECONDS/FPS_CAPTURE_FRAMES_COUNT` seconds
+#[derive(Debug, Default)]
+pub(crate) struct FpsHistory {
+    /// Index of the last capture
+    index: usize,
+    /// Frame time captures, pre-divided by the number of captures
+    history: [f32; FPS_CAPTURE_FRAMES_COUNT],
+    /// Sum of captures, average frame time
+    average: f32,
+    /// Time of the last capture
+    last: f64,
+}
+
+impl FpsHistory {
+    /// Capture a frame time, if enough time passed since the last capture
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub(crate) fn record(&mut self, now: f64, frame_time: f64) {
+        if now - self.last > FPS_AVERAGE_TIME_SECONDS/FPS_CAPTURE_FRAMES_COUNT as f64 {
+            self.last = now;
+            self.index = (self.index + 1)%FPS_CAPTURE_FRAMES_COUNT;
+            self.average -= self.history[self.index];
+            self.history[self.index] = frame_time as f32/FPS_CAPTURE_FRAMES_COUNT as f32;
+            self.average += self.history[self.index];
+        }
+    }
+
+    /// Get average frames per second, 0 until a frame time is captured
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn fps(&self) -> u32 {
+        if self.average <= 0.0 {
+            0
+        } else {
+            (1.0/self.average).round() as u32
+        }
+    }
 }
 
 /// Core global state context data
 pub struct Core<'a> {
-    pub window: Window<'a>,
+    pub window: Window,
     pub storage: Storage,
     pub input: Input,
     pub time: Time,
+    /// Audio device, sounds are played on its voices
+    pub audio: AudioDevice,
     is_gpu_ready: bool,
+    /// Drawing between `begin_drawing()` and the end of the frame
+    pub(crate) is_drawing: bool,
+    /// rlgl global state
+    pub(crate) rlgl: RLGL,
+    /// User hooks
+    hooks: Hooks<'a>,
+    /// Pseudo-random numbers generator
+    random: RandomGenerator,
+    /// Inset (in texels) applied to source rectangles of texture draws
+    pub(crate) atlas_uv_inset: f32,
+    /// Texture and source rectangle used on shapes drawing
+    pub(crate) shapes_texture: ShapesTexture,
 
     /// Current automation events list, set by user, keep internal pointer
-    current_event_list: Option<&'a mut [AutomationEvent]>,
+    current_event_list: Option<AutomationEventList>,
     /// Recording automation events flag
     automation_event_recording: bool,
 
     /// Screenshots counter
     #[cfg(feature = "support_screen_capture")]
     screenshot_counter: usize,
+    /// Key to take a screenshot, `None` to disable it
+    #[cfg(feature = "support_screen_capture")]
+    screenshot_key: Option<KeyboardKey>,
 
     /// GIF frames counter
     #[cfg(feature = "support_gif_recording")]
@@ -144,6 +280,9 @@ pub struct Core<'a> {
     /// MSGIF context state
     #[cfg(feature = "support_gif_recording")]
     gif_state: MsfGifState,
+    /// GIF file path, written when recording stops
+    #[cfg(feature = "support_gif_recording")]
+    gif_path: PathBuf,
 }
 
 impl Default for Core<'_> {
@@ -153,12 +292,21 @@ impl Default for Core<'_> {
             storage: Default::default(),
             input: Default::default(),
             time: Default::default(),
+            audio: AudioDevice::default(),
             is_gpu_ready: false,
+            is_drawing: false,
+            rlgl: RLGL::default(),
+            hooks: Hooks::default(),
+            random: RandomGenerator::default(),
+            atlas_uv_inset: 0.0,
+            shapes_texture: ShapesTexture::default(),
             current_event_list: None,
             automation_event_recording: false,
 
             #[cfg(feature = "support_screen_capture")]
             screenshot_counter: 0,
+            #[cfg(feature = "support_screen_capture")]
+            screenshot_key: Some(KeyboardKey::F12),
 
             #[cfg(feature = "support_gif_recording")]
             gif_frame_counter: 0,
@@ -166,13 +314,101 @@ impl Default for Core<'_> {
             gif_recording: false,
             #[cfg(feature = "support_gif_recording")]
             gif_state: Default::default(),
+            #[cfg(feature = "support_gif_recording")]
+            gif_path: PathBuf::new(),
         }
     }
 }
 
+impl Drop for Core<'_> {
+    fn drop(&mut self) {
+        self.run_hooks(HookStage::WindowClose);
+    }
+}
+
+/// Window and context options, gathered before initialization
+#[derive(Debug, Clone, Copy)]
+pub struct CoreBuilder<'a> {
+    width: u32,
+    height: u32,
+    title: &'a str,
+    flags: ConfigFlags,
+    min_size: Option<Size>,
+    max_size: Option<Size>,
+    target_fps: Option<u32>,
+}
+
+impl<'a> CoreBuilder<'a> {
+    /// Start building a window of the given size and title
+    #[must_use]
+    pub fn new(width: u32, height: u32, title: &'a str) -> Self {
+        Self {
+            width,
+            height,
+            title,
+            flags: ConfigFlags::empty(),
+            min_size: None,
+            max_size: None,
+            target_fps: None,
+        }
+    }
+
+    /// Set configuration flags applied on window creation
+    #[must_use]
+    pub fn flags(mut self, flags: ConfigFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Set window minimum size (for resizable window)
+    #[must_use]
+    pub fn min_size(mut self, width: u32, height: u32) -> Self {
+        self.min_size = Some(Size { width, height });
+        self
+    }
+
+    /// Set window maximum size (for resizable window)
+    #[must_use]
+    pub fn max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = Some(Size { width, height });
+        self
+    }
+
+    /// Set target FPS (maximum), `None` to not limit frame rate
+    #[must_use]
+    pub fn target_fps(mut self, fps: Option<u32>) -> Self {
+        self.target_fps = fps.filter(|&fps| fps > 0);
+        self
+    }
+
+    /// Try enabling V-Sync on window creation, same as [`ConfigFlags::VsyncHint`]
+    #[must_use]
+    pub fn vsync(mut self, enabled: bool) -> Self {
+        self.flags.set(ConfigFlags::VsyncHint, enabled);
+        self
+    }
+
+    /// Initialize window and OpenGL context
+    ///
+    /// # Errors
+    /// Returns an error if SDL fails to create the window or the OpenGL context
+    pub fn build(self) -> Result<(Core<'a>, Platform), InitPlatformError> {
+        let (core, platform) = Core::init(self);
+        platform.map(|platform| (core, platform))
+    }
+}
+
 impl<'a> Core<'a> {
     /// Initialize window and OpenGL context
+    /// NOTE: Use [`CoreBuilder`] to set more options and handle platform initialization errors
     pub fn new(width: u32, height: u32, title: &'a str) -> Self {
+        Self::init(CoreBuilder::new(width, height, title)).0
+    }
+
+    /// Initialize core state and platform from options
+    fn init(options: CoreBuilder<'a>) -> (Self, Result<Platform, InitPlatformError>) {
+        let CoreBuilder { width, height, title, flags, min_size, max_size, target_fps } = options;
+
         tracelog!(Info, "Initializing raylib {}", crate::RAYLIB_VERSION);
 
         tracelog!(Info, "Platform backend: DESKTOP (SDL)");
@@ -215,10 +451,22 @@ impl<'a> Core<'a> {
         // Initialize window data
         core.window.screen.width = width;
         core.window.screen.height = height;
-        core.window.event_waiting = false;
+        core.window.event_waiting = EventWaiting::Disabled;
         core.window.screen_scale = Matrix::IDENTITY; // No draw scaling required by default
+        core.window.dpi_scale = Vector2::new(1.0, 1.0);
+        core.window.background_throttle = Some(BACKGROUND_THROTTLE_FPS);
+        core.window.flags |= flags;
+        if let Some(size) = min_size {
+            core.window.screen_min = size;
+        }
+        if let Some(size) = max_size {
+            core.window.screen_max = size;
+        }
+        if let Some(fps) = target_fps {
+            core.set_target_fps(fps);
+        }
         if !title.is_empty() {
-            core.window.title = title;
+            title.clone_into(&mut core.window.title);
         }
 
         // Initialize global input state
@@ -238,8 +486,8 @@ impl<'a> Core<'a> {
         // rlglInit(core.window.current_fbo.width, core.window.current_fbo.height);
         // core.is_gpu_ready = true; // Flag to note GPU has been initialized successfully
 
-        // // Setup default viewport
-        // SetupViewport(core.window.current_fbo.width, core.window.current_fbo.height);
+        // Setup default viewport
+        core.setup_viewport(core.window.current_fbo.width, core.window.current_fbo.height);
 
         // if cfg!(feature = "support_module_rtext") {
         //     if cfg!(support_default_font) {
@@ -264,7 +512,7 @@ impl<'a> Core<'a> {
         //         // Set default texture and rectangle to be used for shapes drawing
         //         // NOTE: rlgl default texture is a 1x1 pixel UNCOMPRESSED_R8G8B8A8
         //         let texture = Texture {
-        //             id: rlGetTextureIdDefault(),
+        //             id: core.rlgl.get_texture_id_default(),
         //             width: 1,
         //             height: 1,
         //             mipmap: 1,
@@ -278,11 +526,410 @@ impl<'a> Core<'a> {
         // core.time.frame_counter = 0;
         // core.window.should_close = false;
 
-        // // Initialize random seed
-        // SetRandomSeed(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as u32);
+        // Initialize random seed
+        #[allow(clippy::cast_possible_truncation)]
+        core.set_random_seed(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |time| time.as_secs() as u32));
 
         // TRACELOG!(Info, "SYSTEM: Working Directory: %s", GetWorkingDirectory());
 
-        core
+        (core, platform)
+    }
+
+    /// Check if application should close (exit key pressed or windows close icon clicked)
+    /// NOTE: With event waiting enabled, close status is reset for next frame,
+    /// so a close request is reported once and can be ignored
+    pub fn window_should_close(&mut self) -> bool {
+        if !self.window.ready {
+            return true;
+        }
+        let should_close = self.window.should_close;
+        if self.window.event_waiting != EventWaiting::Disabled {
+            self.window.should_close = false;
+        }
+        should_close
+    }
+
+    /// Set a custom key to exit program, `None` to disable it (default: Escape)
+    pub fn set_exit_key(&mut self, key: Option<KeyboardKey>) {
+        self.input.keyboard.exit_key = key;
+    }
+
+    /// Set a custom key to take a screenshot, `None` to disable it (default: F12)
+    #[cfg(feature = "support_screen_capture")]
+    pub fn set_screenshot_key(&mut self, key: Option<KeyboardKey>) {
+        self.screenshot_key = key;
+    }
+
+    /// Takes a screenshot of current screen (filename extension defines format)
+    /// NOTE: Relative paths are relative to the storage base path (application directory)
+    /// # Errors
+    /// Returns an error if the file name is rejected or the image could not be exported
+    #[cfg(feature = "support_screen_capture")]
+    pub fn take_screenshot(&mut self, filename: &str) -> std::io::Result<()> {
+        // Security check to (partially) avoid malicious code
+        if filename.contains('\'') {
+            tracelog!(Warning, "SYSTEM: Provided fileName could be potentially malicious, avoid ['] character");
+            return Err(std::io::ErrorKind::InvalidInput.into());
+        }
+
+        let Size { width, height } = self.window.render;
+        let (width, height) = (width as usize, height as usize);
+        let image = Image {
+            data: crate::rlgl::rl_read_screen_pixels(self, width, height),
+            width,
+            height,
+            mipmap: 1,
+            format: PixelFormat::UncompressedR8G8B8A8,
+        };
+
+        let path = self.storage.resolve(filename);
+        image.export(&path)?;
+        tracelog!(Info, "SYSTEM: [{}] Screenshot taken successfully", path.display());
+        Ok(())
+    }
+
+    /// Take next numbered screenshot (`screenshot_000.png`, ...)
+    /// NOTE: Used by the screenshot key and automation events, errors are logged
+    #[cfg(feature = "support_screen_capture")]
+    pub(crate) fn take_next_screenshot(&mut self) {
+        _ = self.take_screenshot(&format!("screenshot_{:03}.png", self.screenshot_counter));
+        self.screenshot_counter += 1;
+    }
+
+    /// Check if screenshot key was pressed this frame
+    #[cfg(feature = "support_screen_capture")]
+    pub(crate) fn is_screenshot_key_pressed(&self) -> bool {
+        self.screenshot_key.is_some_and(|key| self.input.keyboard.is_key_pressed(key))
+    }
+
+    /// Start recording screen to an animated GIF file, replacing the current recording
+    /// NOTE: Relative paths are relative to the storage base path (application directory)
+    #[cfg(feature = "support_gif_recording")]
+    pub fn start_gif_recording(&mut self, path: impl AsRef<Path>) {
+        let Size { width, height } = self.window.render;
+        if !msf_gif_begin(&mut self.gif_state, width as usize, height as usize) {
+            tracelog!(Warning, "SYSTEM: Screen size not supported for animated GIF recording");
+            return;
+        }
+        self.gif_path = self.storage.resolve(path);
+        self.gif_frame_counter = 0;
+        self.gif_recording = true;
+
+        tracelog!(Info, "SYSTEM: Start animated GIF recording: {}", self.gif_path.display());
+    }
+
+    /// Stop recording screen and write the animated GIF file
+    /// # Errors
+    /// Returns an error if the file could not be written
+    #[cfg(feature = "support_gif_recording")]
+    pub fn stop_gif_recording(&mut self) -> std::io::Result<()> {
+        if !self.gif_recording {
+            return Ok(());
+        }
+        self.gif_recording = false;
+
+        let result = msf_gif_end(&mut self.gif_state);
+        std::fs::write(&self.gif_path, result.data)
+            .inspect(|()| tracelog!(Info, "SYSTEM: Finish animated GIF recording"))
+            .inspect_err(|_| tracelog!(Warning, "FILEIO: [{}] Failed to save animated GIF", self.gif_path.display()))
+    }
+
+    /// Check if screen is being recorded to an animated GIF
+    #[cfg(feature = "support_gif_recording")]
+    #[must_use]
+    pub fn is_gif_recording(&self) -> bool {
+        self.gif_recording
+    }
+
+    /// Capture current framebuffer into the GIF recording, every `GIF_RECORD_FRAMERATE` frames
+    #[cfg(feature = "support_gif_recording")]
+    pub(crate) fn record_gif_frame(&mut self) {
+        if !self.gif_recording {
+            return;
+        }
+        self.gif_frame_counter += 1;
+
+        // NOTE: We record one gif frame depending on the desired gif framerate
+        if self.gif_frame_counter.is_multiple_of(GIF_RECORD_FRAMERATE) {
+            // Get image data for the current frame (from backbuffer)
+            let Size { width, height } = self.window.render;
+            let screen_data = crate::rlgl::rl_read_screen_pixels(self, width as usize, height as usize);
+            msf_gif_frame(&mut self.gif_state, &screen_data, 10, 16, width as usize*4);
+        }
+
+        /* todo: Display the recording indicator
+        if (((gifFrameCounter/15)%2) == 1) {
+            DrawCircle(30, CORE.Window.screen.height - 20, 10, MAROON); // WARNING: Module required: rshapes
+            DrawText("GIF RECORDING", 50, CORE.Window.screen.height - 25, 10, RED); // WARNING: Module required: rtext
+        }
+        */
+    }
+
+    /// Set the seed for the random number generator
+    pub fn set_random_seed(&mut self, seed: u32) {
+        self.random.set_seed(u64::from(seed));
+    }
+
+    /// Get a random value between min and max (both included)
+    pub fn get_random_value(&mut self, min: i32, max: i32) -> i32 {
+        self.random.value(min, max)
+    }
+
+    /// Load random values sequence, no values repeated
+    /// NOTE: Returns an empty sequence if `count` is greater than the range
+    pub fn load_random_sequence(&mut self, count: u32, min: i32, max: i32) -> Vec<i32> {
+        self.random.sequence(count, min, max)
+    }
+
+    /// Save integer value to storage file (to defined position), returns true on success
+    /// NOTE: Storage positions is directly related to file memory layout (4 bytes each integer)
+    #[must_use]
+    pub fn save_storage_value(&self, position: u32, value: i32) -> bool {
+        self.try_save_storage_value(position, value)
+            .inspect_err(|e| tracelog!(Warning, "FILEIO: [{STORAGE_DATA_FILE}] Failed to save storage value at position {position}: {e}"))
+            .is_ok()
+    }
+
+    /// Save integer value to storage file (to defined position)
+    /// NOTE: The storage file is created, or grown with zero-filled gaps, as needed
+    /// # Errors
+    /// Returns an error if the storage file could not be opened or written
+    pub fn try_save_storage_value(&self, position: u32, value: i32) -> Result<(), StorageError> {
+        self.storage.save_value(position, value)
+    }
+
+    /// Load integer value from storage file (from defined position)
+    /// NOTE: If requested position could not be found, value 0 is returned
+    #[must_use]
+    pub fn load_storage_value(&self, position: u32) -> i32 {
+        self.try_load_storage_value(position)
+            .inspect_err(|e| match e {
+                StorageError::PositionNotFound(_) => tracelog!(Warning, "FILEIO: [{STORAGE_DATA_FILE}] Failed to find storage position: {position}"),
+                StorageError::Io(e) => tracelog!(Warning, "FILEIO: [{STORAGE_DATA_FILE}] Failed to load storage value: {e}"),
+            })
+            .unwrap_or(0)
+    }
+
+    /// Load integer value from storage file (from defined position)
+    /// # Errors
+    /// Returns an error if the storage file could not be read, or does not reach `position`
+    pub fn try_load_storage_value(&self, position: u32) -> Result<i32, StorageError> {
+        self.storage.load_value(position)
+    }
+
+    /// Check if a file has been dropped into window
+    #[must_use]
+    pub fn is_file_dropped(&self) -> bool {
+        !self.window.drop_filepaths.is_empty()
+    }
+
+    /// Load dropped filepaths
+    /// NOTE: Dropped files list is cleared, like `UnloadDroppedFiles()`
+    pub fn load_dropped_files(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.window.drop_filepaths)
+    }
+
+    /// Wait for some time (halt program execution)
+    /// NOTE: Sleeping is not accurate, `support_partialbusy_wait_loop` busy waits the last part of it
+    /// and `support_busy_wait_loop` busy waits all of it (high CPU usage)
+    pub fn wait_time(seconds: f64) {
+        if seconds <= 0.0 {
+            return;
+        }
+
+        #[cfg(any(feature = "support_busy_wait_loop", feature = "support_partialbusy_wait_loop"))]
+        let destination_time = get_time() + seconds;
+
+        #[cfg(not(feature = "support_busy_wait_loop"))]
+        {
+            #[cfg(feature = "support_partialbusy_wait_loop")]
+            let sleep_seconds = seconds - seconds*0.05; // NOTE: We reserve a percentage of the time for busy waiting
+            #[cfg(not(feature = "support_partialbusy_wait_loop"))]
+            let sleep_seconds = seconds;
+
+            std::thread::sleep(std::time::Duration::from_secs_f64(sleep_seconds));
+        }
+
+        #[cfg(any(feature = "support_busy_wait_loop", feature = "support_partialbusy_wait_loop"))]
+        while get_time() < destination_time {
+            std::hint::spin_loop();
+        }
+    }
+
+    /// Set fixed screen size, drawn scaled to fit the window keeping aspect ratio (black bars), `None` to fill the window
+    /// NOTE: Screen size and mouse position are reported in letterbox coordinates
+    pub fn set_letterbox(&mut self, size: Option<Size>) {
+        self.window.letterbox = size;
+        self.update_render_scale();
+    }
+
+    /// Update render scaling and mouse coordinates correction, after resize or DPI change
+    /// NOTE: Mouse offset and scale are overwritten, like upstream does on high DPI resize
+    pub(crate) fn update_render_scale(&mut self) {
+        self.window.update_render_scale();
+        let (offset, scale) = self.window.mouse_transform();
+        self.input.mouse.set_mouse_offset(offset);
+        self.input.mouse.set_mouse_scale(scale);
+        self.setup_viewport(self.window.render.width, self.window.render.height);
+    }
+
+    /// Compute framebuffer size and letterboxing for a fullscreen screen size not matching the display,
+    /// then setup the viewport for it
+    pub(crate) fn setup_framebuffer(&mut self) {
+        self.window.setup_framebuffer();
+        self.setup_viewport(self.window.render.width, self.window.render.height);
+    }
+
+    /// Set viewport for a new render size, projection is reset to an orthographic one matching it
+    /// NOTE: Render size and offset are in framebuffer pixels (high DPI included),
+    /// drawing in screen coordinates is scaled by `screen_scale` on `begin_drawing()`
+    #[allow(clippy::cast_possible_wrap)]
+    pub(crate) fn setup_viewport(&mut self, width: u32, height: u32) {
+        self.window.render = Size { width, height };
+
+        // Set viewport width and height
+        // NOTE: Render offset is the size of the black bars, half of it on each side
+        let offset = self.window.render_offset;
+        let viewport_width = width.saturating_sub(offset.x.unsigned_abs());
+        let viewport_height = height.saturating_sub(offset.y.unsigned_abs());
+        self.rlgl.viewport(offset.x/2, offset.y/2, viewport_width as i32, viewport_height as i32);
+        self.rlgl.set_framebuffer_size(viewport_width as i32, viewport_height as i32);
+
+        self.rlgl.matrix_mode(MatrixMode::Projection); // Switch to projection matrix
+        self.rlgl.load_identity(); // Reset current matrix (projection)
+
+        // Set orthographic projection to current framebuffer size
+        // NOTE: Configured top-left corner as (0, 0)
+        self.rlgl.ortho(0.0, f64::from(viewport_width), f64::from(viewport_height), 0.0, 0.0, 1.0);
+
+        self.rlgl.matrix_mode(MatrixMode::Modelview); // Switch back to modelview matrix
+        self.rlgl.load_identity(); // Reset current matrix (modelview)
+    }
+
+    /// Set target FPS (maximum), 0 disables frame limiting
+    pub fn set_target_fps(&mut self, fps: u32) {
+        self.time.target = if fps < 1 { 0.0 } else { 1.0/f64::from(fps) };
+
+        tracelog!(Info, "TIMER: Target time per frame: {:02.03} milliseconds", self.time.target*1000.0);
+    }
+
+    /// Get current FPS
+    /// NOTE: Averaged over the last `FPS_AVERAGE_TIME_SECONDS`, the instant value fluctuates too much
+    #[must_use]
+    pub fn get_fps(&self) -> u32 {
+        self.time.fps_history.fps()
+    }
+
+    /// Get time in seconds for last frame drawn (delta time)
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub fn get_frame_time(&self) -> Seconds {
+        self.time.frame as Seconds
+    }
+
+    /// Get elapsed time in seconds since the platform was initialized
+    #[must_use]
+    pub fn get_time(&self) -> f64 {
+        get_time()
+    }
+
+    /// Set a tag attached to batch flush records until changed, `None` to clear it
+    /// NOTE: Only used when batch diagnostics are enabled, see [`set_batch_diagnostics`](crate::rlgl::set_batch_diagnostics)
+    pub fn set_draw_tag(&mut self, tag: Option<&'static str>) {
+        self.rlgl.diagnostics.tag = tag;
+    }
+
+    /// Get batch flush records of the current frame (empty if batch diagnostics are disabled)
+    ///
+    /// Finding an accidental texture switch per sprite:
+    /// ```ignore
+    /// set_batch_diagnostics(true);
+    /// core.set_draw_tag(Some("sprites"));
+    /// // ...draw sprites...
+    /// core.set_draw_tag(None);
+    /// for record in core.batch_diagnostics() {
+    ///     if let FlushReason::TextureChange { old_id, new_id } = record.reason {
+    ///         println!("{:?}: texture {old_id} -> {new_id} ({} vertices)", record.tag, record.vertex_count);
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn batch_diagnostics(&self) -> &[FlushRecord] {
+        &self.rlgl.diagnostics.records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_values() {
+        let dir = std::env::temp_dir().join(format!("raylib_storage_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let base_path: &'static Path = Box::leak(dir.clone().into_boxed_path());
+        let data_path = dir.join(STORAGE_DATA_FILE);
+        let _ = std::fs::remove_file(&data_path);
+
+        let mut core = Core::default();
+        core.storage.base_path = Some(base_path);
+        assert!(matches!(core.try_load_storage_value(0), Err(StorageError::Io(_))), "no storage file yet");
+        assert_eq!(core.load_storage_value(0), 0);
+
+        // Writing at position 100 grows the file to hold it, the gap is zero-filled
+        assert!(core.save_storage_value(3, -7));
+        assert_eq!(std::fs::metadata(&data_path).unwrap().len(), 16);
+        core.try_save_storage_value(100, 123_456).unwrap();
+        assert_eq!(std::fs::metadata(&data_path).unwrap().len(), 404);
+        assert_eq!(core.try_load_storage_value(50).unwrap(), 0);
+        assert!(matches!(core.try_load_storage_value(101), Err(StorageError::PositionNotFound(101))));
+        assert_eq!(core.load_storage_value(101), 0);
+
+        // Overwriting doesn't shrink the file
+        assert!(core.save_storage_value(3, i32::MAX));
+        assert_eq!(std::fs::metadata(&data_path).unwrap().len(), 404);
+        drop(core);
+
+        // Values persist for the next run
+        let mut core = Core::default();
+        core.storage.base_path = Some(base_path);
+        assert_eq!(core.load_storage_value(3), i32::MAX);
+        assert_eq!(core.load_storage_value(100), 123_456);
+        assert_eq!(core.load_storage_value(0), 0);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_window_should_close() {
+        let mut core = Core::default();
+        assert!(core.window_should_close(), "window not initialized");
+        core.window.ready = true;
+        core.set_exit_key(Some(KeyboardKey::Escape));
+
+        // Other keys don't close, exit key does until the application stops
+        assert!(!core.input.keyboard.register_key_down(KeyboardKey::Space, false));
+        assert!(!core.window_should_close());
+        assert!(core.input.keyboard.register_key_down(KeyboardKey::Escape, false));
+        core.window.should_close = true;
+        assert!(core.window_should_close());
+        assert!(core.window_should_close(), "reported until the application stops");
+
+        // Custom exit key, or none at all
+        core.input.keyboard.register_key_up(KeyboardKey::Escape);
+        core.set_exit_key(Some(KeyboardKey::Q));
+        assert!(!core.input.keyboard.register_key_down(KeyboardKey::Escape, false));
+        assert!(core.input.keyboard.register_key_down(KeyboardKey::Q, true), "repeats still request closing");
+        core.set_exit_key(None);
+        assert!(!core.input.keyboard.register_key_down(KeyboardKey::Escape, false));
+        assert!(core.input.keyboard.is_key_down(KeyboardKey::Escape));
+
+        // With event waiting, a close request is reported once and can be ignored
+        core.window.event_waiting = EventWaiting::Indefinite;
+        assert!(core.window_should_close());
+        assert!(!core.window_should_close());
+        core.window.should_close = true;
+        core.window.ready = false;
+        assert!(core.window_should_close());
     }
 }
@@ -0,0 +1,112 @@
+//! Pseudo-random numbers generation, port of rprand (`Xoshiro128**` seeded with `SplitMix64`)
+
+use crate::tracelog;
+
+/// Pseudo-random numbers generator state
+/// NOTE: Same seed produces the same sequence as raylib's rprand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RandomGenerator {
+    /// `SplitMix64` seed, used to generate the `Xoshiro128**` state
+    seed: u64,
+    /// `Xoshiro128**` state
+    state: [u32; 4],
+}
+
+impl Default for RandomGenerator {
+    /// Generator with rprand default state
+    fn default() -> Self {
+        Self {
+            seed: 0xAABB_CCDD,
+            state: [0x96ea_83c1, 0x218b_21e5, 0xaa91_febd, 0x9764_14d4],
+        }
+    }
+}
+
+impl RandomGenerator {
+    /// Create generator from seed
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Self::default();
+        rng.set_seed(seed);
+        rng
+    }
+
+    /// Set the seed for the random number generator
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+
+        // To generate the Xoshiro128** state, we use SplitMix64 generator first
+        // We generate 4 pseudo-random 64bit numbers that we combine using their LSB|MSB
+        self.state[0] = (self.splitmix64() & 0xffff_ffff) as u32;
+        self.state[1] = ((self.splitmix64() & 0xffff_ffff_0000_0000) >> 32) as u32;
+        self.state[2] = (self.splitmix64() & 0xffff_ffff) as u32;
+        self.state[3] = ((self.splitmix64() & 0xffff_ffff_0000_0000) >> 32) as u32;
+    }
+
+    /// `SplitMix64` generator, only used to initialize the `Xoshiro128**` state
+    fn splitmix64(&mut self) -> u64 {
+        self.seed = self.seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Get next raw 32 bit value (`Xoshiro128**`)
+    pub fn next_u32(&mut self) -> u32 {
+        let result = (self.state[1].wrapping_mul(5)).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 9;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+
+        self.state[2] ^= t;
+
+        self.state[3] = self.state[3].rotate_left(11);
+
+        result
+    }
+
+    /// Get a random value between min and max, both included
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn value(&mut self, min: i32, max: i32) -> i32 {
+        let (min, max) = if min > max { (max, min) } else { (min, max) };
+        let range = max.abs_diff(min).wrapping_add(1);
+        let value = self.next_u32();
+        let offset = if range == 0 { value } else { value % range };
+        (offset as i32).wrapping_add(min)
+    }
+
+    /// Get a sequence of `count` unique random values between min and max, both included
+    /// NOTE: Returns an empty sequence if the range can not hold `count` unique values
+    pub fn sequence(&mut self, count: u32, min: i32, max: i32) -> Vec<i32> {
+        let (min, max) = if min > max { (max, min) } else { (min, max) };
+        if u64::from(count) > u64::from(max.abs_diff(min)) + 1 {
+            tracelog!(Warning, "RPRAND: Sequence count required is greater than range provided");
+            return Vec::new();
+        }
+
+        let mut sequence = Vec::with_capacity(count as usize);
+        while sequence.len() < count as usize {
+            let value = self.value(min, max);
+            if !sequence.contains(&value) {
+                sequence.push(value);
+            }
+        }
+        sequence
+    }
+
+    /// Get a random float in the range `[0..1)`
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_f32(&mut self) -> f32 {
+        // Use the upper 24 bits, exactly representable in f32
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Get a random float in the range `[min..max)`
+    pub fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
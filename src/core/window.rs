@@ -1,7 +1,7 @@
-use std::{os::raw::c_void, path::Path};
+use std::path::PathBuf;
 use bitflags::bitflags;
 
-use crate::prelude::*;
+use crate::{graphics::GlFrameBufferID, prelude::*, tracelog};
 
 bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -41,154 +41,476 @@ bitflags! {
     }
 }
 
+/// Vertical synchronization mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum VsyncMode {
+    /// Present frames immediately, may cause tearing
+    #[default]
+    Off,
+    /// Wait for vertical blank before presenting frames
+    On,
+    /// Wait for vertical blank, but present late frames immediately (may tear)
+    Adaptive,
+}
+
+/// Event waiting mode at frame end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum EventWaiting {
+    /// Poll events, frames run continuously
+    #[default]
+    Disabled,
+    /// Block until an event arrives
+    Indefinite,
+    /// Wait for events at most the target frame time, frames keep running at target fps
+    TargetFrameTime,
+}
+
 pub type MonitorID = usize;
 
+/// Audio behavior while the window is in background
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[non_exhaustive]
+pub enum BackgroundAudio {
+    /// Keep playing normally
+    #[default]
+    Keep,
+    /// Lower master volume to the given level
+    Duck(Percent),
+    /// Pause audio device
+    Pause,
+}
+
+/// Frame behavior decided by [`background_frame_policy`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FramePolicy {
+    /// Render the frame, update still runs when false
+    pub render: bool,
+    /// Target frame rate override, `None` to keep the configured one
+    pub target_fps: Option<u32>,
+    /// Audio behavior
+    pub audio: BackgroundAudio,
+}
+
+impl FramePolicy {
+    /// Get frame time to pace frames to, given the configured target frame time (0 if unlimited)
+    /// NOTE: Background throttling can only slow frames down, the stricter limit wins
+    #[must_use]
+    pub(crate) fn frame_time(&self, target: f64) -> f64 {
+        self.target_fps
+            .filter(|&fps| fps > 0)
+            .map_or(target, |fps| target.max(1.0 / f64::from(fps)))
+    }
+}
+
+/// Apply a V-Sync mode with `set_interval`, returns the mode actually applied
+/// NOTE: Adaptive mode falls back to regular V-Sync if it can't be set, `VsyncHint` follows the applied mode
+pub(crate) fn apply_vsync<E>(flags: &mut ConfigFlags, mode: VsyncMode, mut set_interval: impl FnMut(VsyncMode) -> Result<(), E>) -> Result<VsyncMode, E> {
+    let applied = match set_interval(mode) {
+        Ok(()) => mode,
+        Err(_) if mode == VsyncMode::Adaptive => {
+            tracelog!(Warning, "DISPLAY: Adaptive V-Sync not supported, falling back to V-Sync");
+            set_interval(VsyncMode::On)?;
+            VsyncMode::On
+        }
+        Err(e) => return Err(e),
+    };
+    flags.set(ConfigFlags::VsyncHint, applied != VsyncMode::Off);
+    Ok(applied)
+}
+
+/// Decide how frames run given window state flags and background settings
+/// NOTE: Without `WindowAlwaysRun`, a minimized or unfocused window is throttled to `throttle_fps`
+/// (no throttling if `None`) and minimized windows skip rendering. With `WindowAlwaysRun` frames
+/// always run normally
+#[must_use]
+pub fn background_frame_policy(flags: ConfigFlags, throttle_fps: Option<u32>, audio: BackgroundAudio) -> FramePolicy {
+    let minimized = flags.contains(ConfigFlags::WindowMinimized);
+    let background = minimized || flags.contains(ConfigFlags::WindowUnfocused);
+
+    if !background || flags.contains(ConfigFlags::WindowAlwaysRun) {
+        FramePolicy { render: true, target_fps: None, audio: BackgroundAudio::Keep }
+    } else {
+        FramePolicy {
+            render: !minimized,
+            target_fps: throttle_fps,
+            audio,
+        }
+    }
+}
+
+/// Monitor video mode
+/// NOTE: Ordered by width, then height, then refresh rate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct VideoMode {
+    /// Mode width in pixels
+    pub width: u32,
+    /// Mode height in pixels
+    pub height: u32,
+    /// Mode refresh rate in Hz, 0 if unspecified
+    pub refresh_rate: u32,
+}
+
+impl VideoMode {
+    #[inline]
+    #[must_use]
+    pub const fn new(width: u32, height: u32, refresh_rate: u32) -> Self {
+        Self { width, height, refresh_rate }
+    }
+
+    /// Check if both modes have the same aspect ratio
+    #[inline]
+    #[must_use]
+    pub fn same_aspect(&self, other: &Self) -> bool {
+        u64::from(self.width) * u64::from(other.height) == u64::from(other.width) * u64::from(self.height)
+    }
+
+    /// Get mode area in pixels
+    #[inline]
+    #[must_use]
+    pub fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+}
+
+/// Get the mode closest to the desired one from a list of supported modes
+/// NOTE: Prefers exact resolution, then same aspect ratio, then nearest area;
+/// among equal resolutions the exact refresh rate is preferred, then the highest one.
+/// A desired refresh rate of 0 always picks the highest available, ties keep the list order
+#[must_use]
+pub fn closest_video_mode(modes: &[VideoMode], desired: VideoMode) -> Option<VideoMode> {
+    modes.iter()
+        .copied()
+        .min_by_key(|mode| (
+            (mode.width, mode.height) != (desired.width, desired.height),
+            !mode.same_aspect(&desired),
+            mode.area().abs_diff(desired.area()),
+            desired.refresh_rate != 0 && mode.refresh_rate != desired.refresh_rate,
+            std::cmp::Reverse(mode.refresh_rate),
+        ))
+}
+
 #[must_use]
 #[derive(Debug, Default)]
-pub struct Window<'a> {
-    /// Window text title const pointer
-    pub title: &'a str,
+pub struct Window {
+    /// Window text title
+    pub(crate) title: String,
     /// Configuration flags (bit based), keeps window state
-    pub flags: ConfigFlags,
+    pub(crate) flags: ConfigFlags,
     /// Check if window has been initialized successfully
-    pub ready: bool,
-    /// Check if fullscreen mode is enabled
-    pub fullscreen: bool,
+    pub(crate) ready: bool,
     /// Check if window set for closing
-    pub should_close: bool,
+    pub(crate) should_close: bool,
     /// Check if window has been resized last frame
-    pub resized_last_frame: bool,
+    pub(crate) resized_last_frame: bool,
     /// Wait for events before ending frame
-    pub event_waiting: bool,
-    /// Using FBO (RenderTexture) for rendering instead of default framebuffer
-    pub using_fbo: bool,
+    pub(crate) event_waiting: EventWaiting,
+    /// FBO (`RenderTexture`) used for rendering instead of default framebuffer
+    pub(crate) render_target: Option<GlFrameBufferID>,
 
     /// Window position (required on fullscreen toggle)
-    pub position: Point,
+    pub(crate) position: Point,
     /// Window previous position (required on borderless windowed toggle)
-    pub previous_position: Point,
+    pub(crate) previous_position: Point,
     /// Display width and height (monitor, device-screen, LCD, ...)
-    pub display: Size,
+    pub(crate) display: Size,
     /// Screen width and height (used render area)
-    pub screen: Size,
+    pub(crate) screen: Size,
     /// Screen previous width and height (required on borderless windowed toggle)
-    pub previous_screen: Size,
+    pub(crate) previous_screen: Size,
+    /// Screen width and height before maximizing (required on restore)
+    pub(crate) restored_screen: Option<Size>,
     /// Current render width and height (depends on active fbo)
-    pub current_fbo: Size,
+    pub(crate) current_fbo: Size,
     /// Framebuffer width and height (render area, including black bars if required)
-    pub render: Size,
+    pub(crate) render: Size,
     /// Offset from render area (must be divided by 2)
-    pub render_offset: Point,
+    pub(crate) render_offset: Point,
     /// Screen minimum width and height (for resizable window)
-    pub screen_min: Size,
+    pub(crate) screen_min: Size,
     /// Screen maximum width and height (for resizable window)
-    pub screen_max: Size,
+    pub(crate) screen_max: Size,
     /// Matrix to scale screen (framebuffer rendering)
-    pub screen_scale: Matrix,
-
-    /// Store dropped files paths pointers (provided by GLFW)
-    pub drop_filepaths: Vec<Box<Path>>,
+    pub(crate) screen_scale: Matrix,
+    /// Framebuffer pixels per window unit (high DPI), 1 without `WindowHighdpi`
+    pub(crate) dpi_scale: Vector2,
+    /// Fixed screen size drawn scaled to fit the window with black bars, `None` to fill the window
+    pub(crate) letterbox: Option<Size>,
+
+    /// Store dropped files paths (provided by SDL)
+    pub(crate) drop_filepaths: Vec<PathBuf>,
+
+    /// Frame rate while in background, `None` to disable throttling
+    pub(crate) background_throttle: Option<u32>,
+    /// Audio behavior while in background
+    pub(crate) background_audio: BackgroundAudio,
+    /// Window opacity last set, `None` if never set (fully opaque)
+    pub(crate) opacity: Option<Percent>,
 }
 
-impl<'a> Window<'a> {
+impl Window {
+    /// Get window text title
+    #[inline]
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get configuration flags, keeps window state
+    #[inline]
+    #[must_use]
+    pub fn flags(&self) -> ConfigFlags {
+        self.flags
+    }
+
+    /// Get current event waiting mode
+    #[inline]
+    #[must_use]
+    pub fn event_waiting(&self) -> EventWaiting {
+        self.event_waiting
+    }
+
+    /// Get display width and height (monitor, device-screen, LCD, ...)
+    #[inline]
+    #[must_use]
+    pub fn display_size(&self) -> Size {
+        self.display
+    }
+
+    /// Get framebuffer pixels per window unit, 1 without `WindowHighdpi`
+    #[inline]
+    pub fn dpi_scale(&self) -> Vector2 {
+        self.dpi_scale
+    }
+
+    /// Get fixed screen size drawn scaled to fit the window, `None` if filling the window
+    #[inline]
+    #[must_use]
+    pub fn letterbox(&self) -> Option<Size> {
+        self.letterbox
+    }
+
+    /// Get frame rate while in background, `None` if throttling is disabled
+    #[inline]
+    #[must_use]
+    pub fn background_throttle(&self) -> Option<u32> {
+        self.background_throttle
+    }
+
+    /// Get audio behavior while in background
+    #[inline]
+    #[must_use]
+    pub fn background_audio(&self) -> BackgroundAudio {
+        self.background_audio
+    }
+
+    /// Get frame behavior for the current window state
+    #[inline]
+    #[must_use]
+    pub fn frame_policy(&self) -> FramePolicy {
+        background_frame_policy(self.flags, self.background_throttle, self.background_audio)
+    }
+
+    /// Compute framebuffer size relative to screen size and display size
+    /// NOTE: `render` and `render_offset` are calculated, `screen_scale` too if downscaling is required
+    #[allow(clippy::cast_precision_loss, clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+    pub(crate) fn setup_framebuffer(&mut self) {
+        let (screen, display) = (self.screen, self.display);
+
+        if screen.width > display.width || screen.height > display.height {
+            tracelog!(Warning, "DISPLAY: Downscaling required: Screen size ({}x{}) is bigger than display size ({}x{})", screen.width, screen.height, display.width, display.height);
+
+            // Downscaling to fit display with border-bars
+            let width_ratio = display.width as f32 / screen.width as f32;
+            let height_ratio = display.height as f32 / screen.height as f32;
+
+            if width_ratio <= height_ratio {
+                self.render.width = display.width;
+                self.render.height = (screen.height as f32 * width_ratio).round() as u32;
+                self.render_offset = Point { x: 0, y: (display.height - self.render.height) as i32 };
+            } else {
+                self.render.width = (screen.width as f32 * height_ratio).round() as u32;
+                self.render.height = display.height;
+                self.render_offset = Point { x: (display.width - self.render.width) as i32, y: 0 };
+            }
+
+            // Screen scaling required
+            let scale_ratio = self.render.width as f32 / screen.width as f32;
+            self.screen_scale = Matrix::scale(scale_ratio, scale_ratio, 1.0);
+
+            // NOTE: We render to full display resolution!
+            // We just need to calculate above parameters for downscale matrix and offsets
+            self.render = display;
+
+            tracelog!(Warning, "DISPLAY: Downscale matrix generated, content will be rendered at ({}x{})", self.render.width, self.render.height);
+        } else if screen.width < display.width || screen.height < display.height {
+            // Required screen size is smaller than display size
+            tracelog!(Info, "DISPLAY: Upscaling required: Screen size ({}x{}) smaller than display size ({}x{})", screen.width, screen.height, display.width, display.height);
+
+            if screen.width == 0 || screen.height == 0 {
+                self.screen = display;
+            }
+
+            // Upscaling to fit display with border-bars
+            let display_ratio = display.width as f32 / display.height as f32;
+            let screen_ratio = self.screen.width as f32 / self.screen.height as f32;
+
+            if display_ratio <= screen_ratio {
+                self.render.width = self.screen.width;
+                self.render.height = (self.screen.width as f32 / display_ratio).round() as u32;
+                self.render_offset = Point { x: 0, y: (self.render.height - self.screen.height) as i32 };
+            } else {
+                self.render.width = (self.screen.height as f32 * display_ratio).round() as u32;
+                self.render.height = self.screen.height;
+                self.render_offset = Point { x: (self.render.width - self.screen.width) as i32, y: 0 };
+            }
+        } else {
+            self.render = screen;
+            self.render_offset = Point { x: 0, y: 0 };
+        }
+
+        self.current_fbo = self.render;
+    }
+
     /// Check if application should close (KEY_ESCAPE pressed or windows close icon clicked)
+    /// NOTE: Doesn't reset close status, see [`Core::window_should_close`]
+    #[inline]
+    #[must_use]
     pub fn should_close(&self) -> bool {
-        todo!()
+        !self.ready || self.should_close
     }
 
     /// Check if window has been initialized successfully
+    #[inline]
+    #[must_use]
     pub fn is_ready(&self) -> bool {
-        todo!()
+        self.ready
     }
 
     /// Check if window is currently fullscreen
+    #[inline]
+    #[must_use]
     pub fn is_fullscreen(&self) -> bool {
-        todo!()
+        self.flags.contains(ConfigFlags::FullscreenMode)
     }
 
     /// Check if window is currently hidden
+    #[inline]
+    #[must_use]
     pub fn is_hidden(&self) -> bool {
-        todo!()
+        self.flags.contains(ConfigFlags::WindowHidden)
     }
 
     /// Check if window is currently minimized
     pub fn is_minimized(&self) -> bool {
-        todo!()
+        self.flags.contains(ConfigFlags::WindowMinimized)
     }
 
     /// Check if window is currently maximized
     pub fn is_maximized(&self) -> bool {
-        todo!()
+        self.flags.contains(ConfigFlags::WindowMaximized)
     }
 
     /// Check if window is currently focused
+    #[inline]
+    #[must_use]
     pub fn is_focused(&self) -> bool {
-        todo!()
+        !self.flags.contains(ConfigFlags::WindowUnfocused)
     }
 
     /// Check if window has been resized last frame
+    #[inline]
+    #[must_use]
     pub fn is_resized(&self) -> bool {
-        todo!()
+        self.resized_last_frame
     }
 
     /// Check if one specific window flag is enabled
+    #[inline]
+    #[must_use]
     pub fn is_state(&self, flag: ConfigFlags) -> bool {
-        todo!()
+        self.flags.contains(flag)
     }
 
     /// Set window configuration state using flags
+    /// NOTE: Only updates the stored state, see [`set_window_state`](crate::set_window_state) to update the platform window
     pub fn set_state(&mut self, flags: ConfigFlags) {
-        todo!()
+        self.flags.insert(flags);
     }
 
     /// Clear window configuration state flags
+    /// NOTE: Only updates the stored state, see [`clear_window_state`](crate::clear_window_state) to update the platform window
     pub fn clear_state(&mut self, flags: ConfigFlags) {
-        todo!()
+        self.flags.remove(flags);
     }
 
-    /// Toggle window state: fullscreen/windowed, resizes monitor to match window resolution
+    /// Toggle window state: fullscreen/windowed
+    /// NOTE: Only updates the stored state, see [`toggle_fullscreen`](crate::toggle_fullscreen) to update the platform window
     pub fn toggle_fullscreen(&mut self) {
-        todo!()
+        self.flags.toggle(ConfigFlags::FullscreenMode);
     }
 
-    /// Toggle window state: borderless windowed, resizes window to match monitor resolution
+    /// Toggle window state: borderless windowed, window position and size are saved on enter
+    /// NOTE: Only updates the stored state, see [`toggle_borderless_windowed`](crate::toggle_borderless_windowed)
+    /// to resize the platform window to match monitor resolution
     pub fn toggle_borderless_windowed(&mut self) {
-        todo!()
+        if self.flags.contains(ConfigFlags::BorderlessWindowedMode) {
+            self.flags.remove(ConfigFlags::BorderlessWindowedMode);
+        } else {
+            self.previous_position = self.position;
+            self.previous_screen = self.screen;
+            self.flags.insert(ConfigFlags::BorderlessWindowedMode);
+        }
     }
 
     /// Set window state: maximized, if resizable
+    /// NOTE: Only updates the stored state, see [`maximize_window`](crate::maximize_window) to update the platform window
     pub fn maximize(&mut self) {
-        todo!()
+        if !self.flags.contains(ConfigFlags::WindowResizable) {
+            return;
+        }
+        if !self.is_maximized() {
+            self.restored_screen = Some(self.screen);
+        }
+        self.flags.insert(ConfigFlags::WindowMaximized);
+        self.flags.remove(ConfigFlags::WindowMinimized);
     }
 
     /// Set window state: minimized, if resizable
+    /// NOTE: Only updates the stored state, see [`minimize_window`](crate::minimize_window) to update the platform window
     pub fn minimize(&mut self) {
-        todo!()
+        if !self.flags.contains(ConfigFlags::WindowResizable) {
+            return;
+        }
+        self.flags.insert(ConfigFlags::WindowMinimized);
     }
 
-    /// Set window state: not minimized/maximized
+    /// Set window state: not minimized/maximized, restores screen size from before maximizing
+    /// NOTE: Only updates the stored state, see [`restore_window`](crate::restore_window) to update the platform window
     pub fn restore(&mut self) {
-        todo!()
+        self.flags.remove(ConfigFlags::WindowMinimized | ConfigFlags::WindowMaximized);
+        if let Some(screen) = self.restored_screen.take() {
+            self.screen = screen;
+        }
     }
 
-    /// Set icon for window (single image, RGBA 32bit)
-    pub fn set_icon(&mut self, image: &Image) {
-        todo!()
-    }
-
-    /// Set icon for window (multiple images, RGBA 32bit)
-    pub fn set_icons(&mut self, images: &[Image]) {
-        todo!()
-    }
-
-    /// Set title for window
-    pub fn set_title(&mut self, title: &str) {
-        todo!()
+    /// Set title for window, returns false if it did not change
+    /// NOTE: Only updates the stored title, see [`set_window_title`](crate::set_window_title) to update the platform window
+    pub fn set_title(&mut self, title: &str) -> bool {
+        if self.title == title {
+            return false;
+        }
+        // Reuse title buffer, setting the title every frame does not reallocate
+        self.title.clear();
+        self.title.push_str(title);
+        true
     }
 
     /// Set window position on screen
+    /// NOTE: Only updates the stored state, see [`set_window_position`](crate::set_window_position) to move the platform window
     pub fn set_position(&mut self, x: i32, y: i32) {
-        todo!()
+        self.position = Point { x, y };
     }
 
     /// Set monitor for the current window
@@ -196,166 +518,262 @@ impl<'a> Window<'a> {
         todo!()
     }
 
-    /// Set window minimum dimensions (for FLAG_WINDOW_RESIZABLE)
-    pub fn set_min_size(&mut self, width: usize, height: usize) {
-        todo!()
+    /// Set window minimum dimensions (for resizable window), 0 for unconstrained
+    /// NOTE: Only updates the stored state, see [`set_window_min_size`](crate::set_window_min_size) to update the platform window
+    pub fn set_min_size(&mut self, width: u32, height: u32) {
+        self.screen_min = Size { width, height };
     }
 
-    /// Set window maximum dimensions (for FLAG_WINDOW_RESIZABLE)
-    pub fn set_max_size(&mut self, width: usize, height: usize) {
-        todo!()
+    /// Set window maximum dimensions (for resizable window), 0 for unconstrained
+    /// NOTE: Only updates the stored state, see [`set_window_max_size`](crate::set_window_max_size) to update the platform window
+    pub fn set_max_size(&mut self, width: u32, height: u32) {
+        self.screen_max = Size { width, height };
     }
 
-    /// Set window dimensions
-    pub fn set_size(&mut self, width: usize, height: usize) {
-        todo!()
+    /// Set window dimensions, reported as a resize
+    /// NOTE: Only updates the stored state, see [`set_window_size`](crate::set_window_size) to resize the platform window
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.resize(width, height);
     }
 
-    /// Set window opacity [0.0f..1.0f]
-    pub fn set_opacity(&mut self, opacity: f32) {
-        todo!()
+    /// Register a window resize, same bookkeeping for user and programmatic resizes
+    pub(crate) fn resize(&mut self, width: u32, height: u32) {
+        self.screen = Size { width, height };
+        self.update_render_scale();
+        self.resized_last_frame = true;
     }
 
-    /// Set window focused
-    pub fn set_focused(&mut self) {
-        todo!()
-    }
+    /// Compute render size, offset and screen scaling from screen size, DPI scale and letterbox
+    /// NOTE: Screen size is logical (window units), render size is in framebuffer pixels
+    #[allow(clippy::cast_precision_loss, clippy::cast_sign_loss, clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub(crate) fn update_render_scale(&mut self) {
+        let dpi = self.highdpi_scale();
+        self.render = Size {
+            width: (self.screen.width as f32*dpi.x).round() as u32,
+            height: (self.screen.height as f32*dpi.y).round() as u32,
+        };
 
-    /// Get native window handle
-    pub fn handle(&self) -> *mut c_void {
-        todo!()
-    }
-
-    /// Get current screen width
-    pub fn screen_width(&self) -> usize {
-        todo!()
-    }
-
-    /// Get current screen height
-    pub fn screen_height(&self) -> usize {
-        todo!()
-    }
-
-    /// Get current render width (it considers HiDPI)
-    pub fn render_width(&self) -> usize {
-        todo!()
-    }
-
-    /// Get current render height (it considers HiDPI)
-    pub fn render_height(&self) -> usize {
-        todo!()
-    }
-
-    /// Get number of connected monitors
-    pub fn monitor_count(&self) -> usize {
-        todo!()
-    }
-
-    /// Get current monitor where window is placed
-    pub fn current_monitor(&self) -> MonitorID {
-        todo!()
-    }
+        if let Some((size, scale)) = self.letterbox_scale() {
+            // Keep aspect ratio, black bars fill the remaining render area
+            self.screen_scale = Matrix::scale(scale, scale, 1.0);
+            self.render_offset = Point {
+                x: self.render.width.saturating_sub((size.width as f32*scale).round() as u32) as i32,
+                y: self.render.height.saturating_sub((size.height as f32*scale).round() as u32) as i32,
+            };
+        } else {
+            self.screen_scale = Matrix::scale(dpi.x, dpi.y, 1.0);
+            self.render_offset = Point { x: 0, y: 0 };
+        }
 
-    /// Get specified monitor position
-    pub fn monitor_position(&self, monitor: MonitorID) -> Position2 {
-        todo!()
+        self.current_fbo = self.render;
     }
 
-    /// Get specified monitor width (current video mode used by monitor)
-    pub fn monitor_width(&self, monitor: MonitorID) -> usize {
-        todo!()
+    /// Get mouse offset and scale mapping window coordinates to screen coordinates
+    /// NOTE: SDL reports mouse positions in window units, already logical without letterbox
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn mouse_transform(&self) -> (Offset2, Vector2) {
+        let dpi = self.highdpi_scale();
+        match self.letterbox_scale() {
+            Some((_, scale)) => (
+                Vector2::new(-(self.render_offset.x as f32)/(2.0*dpi.x), -(self.render_offset.y as f32)/(2.0*dpi.y)),
+                Vector2::new(dpi.x/scale, dpi.y/scale),
+            ),
+            None => (Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0)),
+        }
     }
 
-    /// Get specified monitor height (current video mode used by monitor)
-    pub fn monitor_height(&self, monitor: MonitorID) -> usize {
-        todo!()
+    /// Get DPI scale if `WindowHighdpi` is enabled
+    fn highdpi_scale(&self) -> Vector2 {
+        if self.flags.contains(ConfigFlags::WindowHighdpi) {
+            self.dpi_scale
+        } else {
+            Vector2::new(1.0, 1.0)
+        }
     }
 
-    /// Get specified monitor physical width in millimetres
-    pub fn monitor_physical_width(&self, monitor: MonitorID) -> usize {
-        todo!()
+    /// Get letterbox size and its uniform scale to fit the render area
+    #[allow(clippy::cast_precision_loss)]
+    fn letterbox_scale(&self) -> Option<(Size, f32)> {
+        let size = self.letterbox.filter(|size| size.width > 0 && size.height > 0)?;
+        let scale = (self.render.width as f32/size.width as f32).min(self.render.height as f32/size.height as f32);
+        Some((size, scale)).filter(|&(_, scale)| scale > 0.0)
     }
 
-    /// Get specified monitor physical height in millimetres
-    pub fn monitor_physical_height(&self, monitor: MonitorID) -> usize {
-        todo!()
+    /// Set window opacity [0.0f..1.0f], clamped
+    /// NOTE: Only updates the stored state, see [`set_window_opacity`](crate::set_window_opacity) to update the platform window
+    pub fn set_opacity(&mut self, opacity: Percent) {
+        self.opacity = Some(opacity.clamp(0.0, 1.0));
     }
 
-    /// Get specified monitor refresh rate
-    pub fn monitor_refresh_rate(&self, monitor: MonitorID) -> usize {
-        todo!()
+    /// Get window opacity [0.0f..1.0f], last value set (fully opaque by default)
+    #[inline]
+    #[must_use]
+    pub fn window_opacity(&self) -> Percent {
+        self.opacity.unwrap_or(1.0)
     }
 
-    /// Get window position XY on monitor
-    pub fn window_position(&self) -> Position2 {
-        todo!()
+    /// Set window focused
+    /// NOTE: Only updates the stored state, see [`set_window_focused`](crate::set_window_focused) to focus the platform window
+    pub fn set_focused(&mut self) {
+        self.flags.remove(ConfigFlags::WindowUnfocused);
     }
 
-    /// Get window scale DPI factor
-    pub fn window_scale_dpi(&self) -> Vector2 {
-        todo!()
+    /// Get current screen width (logical, letterbox width if set)
+    #[inline]
+    #[must_use]
+    pub fn screen_width(&self) -> usize {
+        self.letterbox.unwrap_or(self.screen).width as usize
     }
 
-    /// Get the human-readable, UTF-8 encoded name of the specified monitor
-    pub fn monitor_name(&self, monitor: MonitorID) -> &str {
-        todo!()
+    /// Get current screen height (logical, letterbox height if set)
+    #[inline]
+    #[must_use]
+    pub fn screen_height(&self) -> usize {
+        self.letterbox.unwrap_or(self.screen).height as usize
     }
 
-    /// Set clipboard text content
-    pub fn set_clipboard_text(&mut self, text: &str) {
-        todo!()
+    /// Get current render width (it considers `HiDPI`)
+    #[inline]
+    #[must_use]
+    pub fn render_width(&self) -> usize {
+        self.render.width as usize
     }
 
-    /// Get clipboard text content
-    pub fn clipboard_text(&self) -> &str {
-        todo!()
+    /// Get current render height (it considers `HiDPI`)
+    #[inline]
+    #[must_use]
+    pub fn render_height(&self) -> usize {
+        self.render.height as usize
     }
 
-    /// Get clipboard image content
-    pub fn clipboard_image(&self) -> &Image {
-        todo!()
+    /// Get window position XY on monitor, last known position
+    #[inline]
+    #[must_use]
+    pub fn window_position(&self) -> Point {
+        self.position
     }
 
     /// Enable waiting for events on EndDrawing(), no automatic event polling
     pub fn enable_event_waiting(&mut self) {
-        todo!()
+        self.event_waiting = EventWaiting::Indefinite;
     }
 
     /// Disable waiting for events on EndDrawing(), automatic events polling
     pub fn disable_event_waiting(&mut self) {
-        todo!()
-    }
-
-    // Cursor-related functions
-
-    /// Shows cursor
-    pub fn show_cursor(&mut self) {
-        todo!()
-    }
-
-    /// Hides cursor
-    pub fn hide_cursor(&mut self) {
-        todo!()
+        self.event_waiting = EventWaiting::Disabled;
     }
 
-    /// Check if cursor is not visible
-    pub fn is_cursor_hidden(&self) -> bool {
-        todo!()
+    /// Set event waiting mode, [`EventWaiting::TargetFrameTime`] waits indefinitely if no target fps is set
+    pub fn set_event_waiting(&mut self, mode: EventWaiting) {
+        self.event_waiting = mode;
     }
 
-    /// Enables cursor (unlock cursor)
-    pub fn enable_cursor(&mut self) {
-        todo!()
-    }
-
-    /// Disables cursor (lock cursor)
-    pub fn disable_cursor(&mut self) {
-        todo!()
-    }
+}
 
-    /// Check if cursor is on the screen
-    pub fn is_cursor_on_screen(&self) -> bool {
-        todo!()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_background_frame_policy() {
+        const THROTTLE: Option<u32> = Some(10);
+        const AUDIO: BackgroundAudio = BackgroundAudio::Duck(0.25);
+        const FOREGROUND: FramePolicy = FramePolicy { render: true, target_fps: None, audio: BackgroundAudio::Keep };
+        const UNFOCUSED: FramePolicy = FramePolicy { render: true, target_fps: THROTTLE, audio: AUDIO };
+        const MINIMIZED: FramePolicy = FramePolicy { render: false, target_fps: THROTTLE, audio: AUDIO };
+
+        let (minimized, unfocused, always_run) = (ConfigFlags::WindowMinimized, ConfigFlags::WindowUnfocused, ConfigFlags::WindowAlwaysRun);
+        let table = [
+            (ConfigFlags::empty(), FOREGROUND),
+            (unfocused, UNFOCUSED),
+            (minimized, MINIMIZED),
+            (minimized | unfocused, MINIMIZED),
+            (always_run, FOREGROUND),
+            (always_run | unfocused, FOREGROUND),
+            (always_run | minimized, FOREGROUND),
+            (always_run | minimized | unfocused, FOREGROUND),
+        ];
+        for (flags, expected) in table {
+            assert_eq!(background_frame_policy(flags, THROTTLE, AUDIO), expected, "{flags:?}");
+        }
+
+        // Unrelated flags don't matter, throttling can be disabled
+        assert_eq!(background_frame_policy(ConfigFlags::WindowResizable | ConfigFlags::VsyncHint, THROTTLE, AUDIO), FOREGROUND);
+        assert_eq!(background_frame_policy(unfocused, None, AUDIO), FramePolicy { target_fps: None, ..UNFOCUSED });
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_frame_time() {
+        let policy = |target_fps| FramePolicy { render: true, target_fps, audio: BackgroundAudio::Keep };
+
+        // No throttle keeps the configured target, unlimited included
+        assert_eq!(policy(None).frame_time(1.0/60.0), 1.0/60.0);
+        assert_eq!(policy(None).frame_time(0.0), 0.0);
+        assert_eq!(policy(Some(0)).frame_time(1.0/60.0), 1.0/60.0);
+
+        // Stricter limit wins
+        assert_eq!(policy(Some(10)).frame_time(0.0), 0.1);
+        assert_eq!(policy(Some(30)).frame_time(1.0/60.0), 1.0/30.0);
+        assert_eq!(policy(Some(30)).frame_time(0.1), 0.1);
+    }
+
+    #[test]
+    fn test_apply_vsync() {
+        // Driver without adaptive V-Sync support
+        let mut applied = Vec::new();
+        let mut set_interval = |mode| {
+            applied.push(mode);
+            if mode == VsyncMode::Adaptive { Err("unsupported") } else { Ok(()) }
+        };
+
+        let mut flags = ConfigFlags::WindowResizable;
+        assert_eq!(apply_vsync(&mut flags, VsyncMode::Adaptive, &mut set_interval), Ok(VsyncMode::On));
+        assert_eq!(flags, ConfigFlags::WindowResizable | ConfigFlags::VsyncHint);
+        assert_eq!(apply_vsync(&mut flags, VsyncMode::Off, &mut set_interval), Ok(VsyncMode::Off));
+        assert_eq!(flags, ConfigFlags::WindowResizable);
+        assert_eq!(apply_vsync(&mut flags, VsyncMode::On, &mut set_interval), Ok(VsyncMode::On));
+        assert!(flags.contains(ConfigFlags::VsyncHint));
+        assert_eq!(applied, [VsyncMode::Adaptive, VsyncMode::On, VsyncMode::Off, VsyncMode::On]);
+
+        // Supported adaptive mode is kept, failures leave the flags untouched
+        assert_eq!(apply_vsync(&mut flags, VsyncMode::Off, |_| Ok::<_, ()>(())), Ok(VsyncMode::Off));
+        assert_eq!(apply_vsync(&mut flags, VsyncMode::Adaptive, |_| Ok::<_, ()>(())), Ok(VsyncMode::Adaptive));
+        assert!(flags.contains(ConfigFlags::VsyncHint));
+        assert_eq!(apply_vsync(&mut flags, VsyncMode::Off, |_| Err("failed")), Err("failed"));
+        assert_eq!(apply_vsync(&mut flags, VsyncMode::Adaptive, |_| Err("failed")), Err("failed"));
+        assert!(flags.contains(ConfigFlags::VsyncHint));
+    }
+
+    #[test]
+    fn test_closest_video_mode() {
+        const fn mode(width: u32, height: u32, refresh_rate: u32) -> VideoMode {
+            VideoMode::new(width, height, refresh_rate)
+        }
+        let modes = [mode(1280, 720, 60), mode(1920, 1080, 60), mode(1920, 1080, 144), mode(1920, 1080, 75), mode(1280, 1024, 60), mode(1600, 900, 60)];
+
+        // Empty list
+        assert_eq!(closest_video_mode(&[], mode(1920, 1080, 60)), None);
+
+        // Exact match, refresh rate preference among the same resolution
+        assert_eq!(closest_video_mode(&modes, mode(1920, 1080, 75)), Some(mode(1920, 1080, 75)));
+        assert_eq!(closest_video_mode(&modes, mode(1920, 1080, 120)), Some(mode(1920, 1080, 144)));
+        assert_eq!(closest_video_mode(&modes, mode(1920, 1080, 0)), Some(mode(1920, 1080, 144)));
+
+        // Same aspect ratio is preferred over a nearer area, then nearest area
+        assert_eq!(closest_video_mode(&[mode(1280, 1024, 60), mode(1600, 900, 60)], mode(1280, 720, 60)), Some(mode(1600, 900, 60)));
+        assert_eq!(closest_video_mode(&modes, mode(1440, 810, 60)), Some(mode(1280, 720, 60)));
+        assert_eq!(closest_video_mode(&modes, mode(1200, 1000, 0)), Some(mode(1280, 1024, 60)));
+
+        // Resolution is preferred over refresh rate
+        assert_eq!(closest_video_mode(&modes, mode(1600, 900, 144)), Some(mode(1600, 900, 60)));
+
+        // Ties keep the list order
+        let tied = [mode(80, 100, 60), mode(120, 100, 60)];
+        assert_eq!(closest_video_mode(&tied, mode(100, 100, 60)), Some(tied[0]));
+        let reversed = [tied[1], tied[0]];
+        assert_eq!(closest_video_mode(&reversed, mode(100, 100, 60)), Some(tied[1]));
+        let duplicates = [mode(640, 480, 60), mode(640, 480, 60)];
+        assert_eq!(closest_video_mode(&duplicates, mode(800, 600, 0)), Some(duplicates[0]));
     }
-
 }
@@ -1 +1,4 @@
+#[cfg(feature = "support_gif_recording")]
 pub mod msf_gif;
+pub mod sdefl;
+pub mod sinfl;
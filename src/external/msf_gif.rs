@@ -1,41 +1,482 @@
-use std::{collections::LinkedList, os::raw::c_void};
+//! Port of `msf_gif` (Miles Fogle), animated GIF encoder
+//!
+//! Frames are quantized to a per-frame palette (ordered dithering, bit depth lowered until
+//! the frame fits 255 colors), pixels unchanged since the previous frame are encoded as
+//! transparent to improve compression. Palette index 0 is always the transparent color
+//!
+//! NOTE: Only the in-memory API is ported, no file streaming, no BGRA input
 
+/// Bits per channel for each total bit depth (green gets the extra bit first)
+const RDEPTHS: [u32; 17] = [0, 0, 1, 1, 1, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5];
+const GDEPTHS: [u32; 17] = [0, 1, 1, 1, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6];
+const BDEPTHS: [u32; 17] = [0, 0, 0, 1, 1, 1, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5];
+
+/// Ordered dithering 4x4 Bayer matrix, scaled to 16 bit
+const DITHER_KERNEL: [u32; 16] = [
+     0 << 12,  8 << 12,  2 << 12, 10 << 12,
+    12 << 12,  4 << 12, 14 << 12,  6 << 12,
+     3 << 12, 11 << 12,  1 << 12,  9 << 12,
+    15 << 12,  7 << 12, 13 << 12,  5 << 12,
+];
+
+/// Max number of LZW codes (12 bit)
+const LZW_MAX_CODE: u32 = 4095;
+
+/// Encoded GIF file data
+#[derive(Debug, Default)]
 pub struct MsfGifResult {
     pub data: Vec<u8>,
-
-    /// internal use
-    alloc_size: usize,
-    /// internal use
-    context_pointer: *mut c_void,
 }
 
 /// internal use
+#[derive(Debug, Default)]
 struct MsfCookedFrame {
-    pub pixels: Vec<u32>,
-    pub depth: u32,
-    pub count: u32,
-    pub rbits: u32,
-    pub gbits: u32,
-    pub bbits: u32,
+    pixels: Vec<u32>,
+    depth: usize,
+    count: usize,
+    rbits: u32,
+    gbits: u32,
+    bbits: u32,
 }
 
+/// Encoded data chunk, file header or single frame
+#[derive(Debug)]
 pub struct MsfGifBuffer {
     pub data: Vec<u8>,
 }
 
-/// buffer, size, count, stream
-pub type MsfGifFileWriteFunc = dyn FnMut(&[u8], usize, usize, &mut [u8]) -> usize;
-
-pub struct MsfGifState<'a> {
-    pub file_write_func: &'a mut MsfGifFileWriteFunc,
-    pub file_write_data: Vec<u8>,
-    pub previous_frame: MsfCookedFrame,
-    pub current_frame: MsfCookedFrame,
-    pub lzw_mem: Vec<i16>,
-    pub list: LinkedList<MsfGifBuffer>,
-    pub width: u32,
-    pub height: u32,
-    pub custom_allocator_context: *mut c_void,
+/// GIF encoding state, between `msf_gif_begin` and `msf_gif_end`
+#[derive(Debug, Default)]
+pub struct MsfGifState {
+    previous_frame: MsfCookedFrame,
+    lzw_mem: Vec<u16>,
+    list: Vec<MsfGifBuffer>,
+    width: usize,
+    height: usize,
     /// needed for transparency to work correctly (because we reach into the previous frame)
-    pub frames_submitted: usize,
+    frames_submitted: usize,
+}
+
+/// Number of bits required to store a value
+fn msf_bit_log(value: usize) -> u32 {
+    usize::BITS - value.leading_zeros()
+}
+
+/// Quantize RGBA pixels to packed color values of at most `depth` bits
+/// NOTE: Transparent pixels (alpha < 128) use the value after the last color
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+fn msf_cook_frame(raw: &[u8], used: &mut [bool], width: usize, height: usize, pitch: usize, mut depth: usize) -> MsfCookedFrame {
+    let mut cooked = vec![0u32; width*height];
+
+    let count = loop {
+        let (rbits, gbits, bbits) = (RDEPTHS[depth], GDEPTHS[depth], BDEPTHS[depth]);
+        let palette_size = (1usize << (rbits + gbits + bbits)) + 1;
+        used[..palette_size].fill(false);
+
+        // Scale channels so that max value plus max dither offset fits 16 bit
+        let mul = |bits: u32| ((255.0 - ((1u32 << (8 - bits)) - 1) as f32)/255.0*257.0) as u32;
+        let (rmul, gmul, bmul) = (mul(rbits), mul(gbits), mul(bbits));
+
+        let gmask = ((1u32 << gbits) - 1) << rbits;
+        let bmask = ((1u32 << bbits) - 1) << rbits << gbits;
+
+        for y in 0..height {
+            for x in 0..width {
+                let p = &raw[y*pitch + x*4..][..4];
+                let cooked = &mut cooked[y*width + x];
+
+                // Transparent pixel if alpha is low
+                if p[3] < 128 {
+                    *cooked = (palette_size - 1) as u32;
+                    continue;
+                }
+
+                let k = DITHER_KERNEL[(y & 3)*4 + (x & 3)];
+                *cooked =
+                    ((u32::from(p[2])*bmul + (k >> bbits)).min(65535) >> (16 - rbits - gbits - bbits) & bmask) |
+                    ((u32::from(p[1])*gmul + (k >> gbits)).min(65535) >> (16 - rbits - gbits) & gmask) |
+                     (u32::from(p[0])*rmul + (k >> rbits)).min(65535) >> (16 - rbits);
+            }
+        }
+
+        for &value in &cooked {
+            used[value as usize] = true;
+        }
+
+        // Count used colors, transparent is ignored
+        let count = used[..palette_size - 1].iter().filter(|&&used| used).count();
+        if count < 256 || depth == 0 {
+            break count;
+        }
+        depth -= 1;
+    };
+
+    MsfCookedFrame {
+        pixels: cooked,
+        depth,
+        count,
+        rbits: RDEPTHS[depth],
+        gbits: GDEPTHS[depth],
+        bbits: BDEPTHS[depth],
+    }
+}
+
+/// LSB first bit packing of LZW codes
+#[derive(Default)]
+struct MsfBitWriter {
+    data: Vec<u8>,
+    pending: u32,
+    pending_bits: u32,
+}
+
+impl MsfBitWriter {
+    #[allow(clippy::cast_possible_truncation)]
+    fn put_code(&mut self, len: u32, code: u32) {
+        self.pending |= code << self.pending_bits;
+        self.pending_bits += len;
+        while self.pending_bits >= 8 {
+            self.data.push(self.pending as u8);
+            self.pending >>= 8;
+            self.pending_bits -= 8;
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn finish(mut self) -> Vec<u8> {
+        if self.pending_bits > 0 {
+            self.data.push(self.pending as u8);
+        }
+        self.data
+    }
+}
+
+/// Compress palette indices with variable length LZW, as specified by `GIF89a`
+/// NOTE: `lzw` stores the code table as a trie, `stride` children per code (one per palette index)
+#[allow(clippy::cast_possible_truncation)]
+fn msf_lzw_compress(indices: impl IntoIterator<Item = u8>, min_code_size: u32, stride: usize, lzw: &mut Vec<u16>) -> Vec<u8> {
+    let clear_code = 1u32 << min_code_size;
+    lzw.clear();
+    lzw.resize((LZW_MAX_CODE as usize + 1)*stride, 0);
+
+    let mut writer = MsfBitWriter::default();
+    let mut code_size = min_code_size + 1;
+    let mut max_code = clear_code + 1;
+
+    // SPEC: "Encoders should output a Clear code as the first code of each image data stream."
+    writer.put_code(code_size, clear_code);
+
+    let mut current = None;
+    for index in indices {
+        let Some(code) = current else {
+            current = Some(u32::from(index));
+            continue;
+        };
+
+        let slot = code as usize*stride + usize::from(index);
+        if lzw[slot] != 0 {
+            current = Some(u32::from(lzw[slot]));
+            continue;
+        }
+
+        writer.put_code(code_size, code);
+        max_code += 1;
+        lzw[slot] = max_code as u16;
+        if max_code >= 1 << code_size {
+            code_size += 1;
+        }
+        // Table full, start over
+        if max_code == LZW_MAX_CODE {
+            writer.put_code(code_size, clear_code);
+            lzw.fill(0);
+            code_size = min_code_size + 1;
+            max_code = clear_code + 1;
+        }
+        current = Some(u32::from(index));
+    }
+
+    if let Some(code) = current {
+        writer.put_code(code_size, code);
+    }
+    writer.put_code(code_size, clear_code + 1); // End of information
+    writer.finish()
+}
+
+/// Encode a cooked frame: graphic control extension, image descriptor, local color table and image data
+#[allow(clippy::cast_possible_truncation)]
+fn msf_compress_frame(state: &mut MsfGifState, frame: &MsfCookedFrame, used: &[bool], centi_seconds: u16) -> MsfGifBuffer {
+    // Generate palette, translating packed colors to palette indices
+    let tlb_size = (1usize << (frame.rbits + frame.gbits + frame.bbits)) + 1;
+    let mut tlb = vec![0u8; tlb_size];
+    let mut table = [[0u8; 3]; 256];
+    let mut table_idx = 1; // We start counting at 1 because 0 is the transparent color
+    let expand = |value: usize, bits: u32| -> u8 {
+        if bits == 0 {
+            return 0;
+        }
+        // Shift into highest bits, then replicate them into the lowest bits
+        let value = (value << (8 - bits)) as u8;
+        (0..8).step_by(bits as usize).fold(0, |color, shift| color | value >> shift)
+    };
+    for (i, _) in used[..tlb_size - 1].iter().enumerate().filter(|(_, &used)| used) {
+        tlb[i] = table_idx as u8;
+        let r = i & ((1 << frame.rbits) - 1);
+        let g = i >> frame.rbits & ((1 << frame.gbits) - 1);
+        let b = i >> (frame.rbits + frame.gbits);
+        table[table_idx] = [expand(r, frame.rbits), expand(g, frame.gbits), expand(b, frame.bbits)];
+        table_idx += 1;
+    }
+    let has_transparent_pixels = used[tlb_size - 1];
+
+    // SPEC: "Because of some algorithmic constraints however, black & white images which have one color bit
+    //        must be indicated as having a code size of 2."
+    let table_bits = msf_bit_log(table_idx - 1).max(2);
+    let table_size = 1usize << table_bits;
+
+    let previous = &state.previous_frame;
+    let has_same_pal = frame.rbits == previous.rbits && frame.gbits == previous.gbits && frame.bbits == previous.bbits;
+    let frames_compatible = has_same_pal && !has_transparent_pixels && previous.pixels.len() == frame.pixels.len();
+
+    // NOTE: We need to check the frame number because if we reach into the buffer prior to the first frame,
+    // we'll just clobber the file header instead, which is a bug
+    if has_transparent_pixels && state.frames_submitted > 0 {
+        if let Some(last) = state.list.last_mut() {
+            last.data[3] = 0x09; // Set the previous frame's disposal to background, so transparency is possible
+        }
+    }
+
+    let mut data = Vec::with_capacity(32 + table_size*3 + frame.pixels.len()*3/2);
+    data.extend_from_slice(&[0x21, 0xF9, 0x04, 0x05]); // Graphic control extension, transparency enabled
+    data.extend_from_slice(&centi_seconds.to_le_bytes());
+    data.extend_from_slice(&[0, 0]); // Transparent color index, block terminator
+    data.extend_from_slice(&[0x2C, 0, 0, 0, 0]); // Image descriptor at (0, 0)
+    data.extend_from_slice(&(state.width as u16).to_le_bytes());
+    data.extend_from_slice(&(state.height as u16).to_le_bytes());
+    data.push(0x80 | (table_bits - 1) as u8); // Local color table
+
+    // Local color table
+    for color in &table[..table_size] {
+        data.extend_from_slice(color);
+    }
+    data.push(table_bits as u8);
+
+    // Unchanged pixels are left transparent, showing the previous frame
+    let indices = frame.pixels.iter().enumerate().map(|(i, &pixel)| {
+        if frames_compatible && pixel == previous.pixels[i] { 0 } else { tlb[pixel as usize] }
+    });
+    let compressed = msf_lzw_compress(indices, table_bits, table_idx, &mut state.lzw_mem);
+
+    // Image data sub-blocks
+    for block in compressed.chunks(255) {
+        data.push(block.len() as u8);
+        data.extend_from_slice(block);
+    }
+    data.push(0);
+
+    MsfGifBuffer { data }
+}
+
+/// Start encoding a GIF, writes the file header
+/// NOTE: Width and height are limited to 65535 by the format
+pub fn msf_gif_begin(state: &mut MsfGifState, width: usize, height: usize) -> bool {
+    let (Ok(w), Ok(h)) = (u16::try_from(width), u16::try_from(height)) else {
+        return false;
+    };
+    *state = MsfGifState { width, height, ..Default::default() };
+
+    let mut data = Vec::with_capacity(33);
+    data.extend_from_slice(b"GIF89a");
+    data.extend_from_slice(&w.to_le_bytes());
+    data.extend_from_slice(&h.to_le_bytes());
+    data.extend_from_slice(&[0x70, 0, 0]); // No global color table
+    data.extend_from_slice(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\0\0\0"); // Loop forever
+    state.list.push(MsfGifBuffer { data });
+    true
+}
+
+/// Add a frame of RGBA pixels, `pitch_in_bytes` 0 for tightly packed rows
+/// NOTE: `max_bit_depth` (1..=16) limits the palette quality, lower depths produce smaller files
+pub fn msf_gif_frame(state: &mut MsfGifState, pixel_data: &[u8], centi_seconds_per_frame: u16, max_bit_depth: usize, pitch_in_bytes: usize) -> bool {
+    if state.list.is_empty() || state.width == 0 || state.height == 0 {
+        return false;
+    }
+    let max_bit_depth = max_bit_depth.clamp(1, 16);
+    let pitch = if pitch_in_bytes == 0 { state.width*4 } else { pitch_in_bytes };
+    if pitch < state.width*4 || pixel_data.len() < pitch*(state.height - 1) + state.width*4 {
+        return false;
+    }
+
+    // Start from the previous frame depth, raised when it used few colors
+    let depth = max_bit_depth.min(state.previous_frame.depth + 160/state.previous_frame.count.max(1));
+    let mut used = vec![false; (1 << 16) + 1];
+    let frame = msf_cook_frame(pixel_data, &mut used, state.width, state.height, pitch, depth);
+
+    let buffer = msf_compress_frame(state, &frame, &used, centi_seconds_per_frame);
+    state.list.push(buffer);
+
+    state.previous_frame = frame;
+    state.frames_submitted += 1;
+    true
+}
+
+/// Finish encoding, returns the GIF file data (empty if encoding was not started)
+pub fn msf_gif_end(state: &mut MsfGifState) -> MsfGifResult {
+    let state = std::mem::take(state);
+    if state.list.is_empty() {
+        return MsfGifResult::default();
+    }
+
+    let mut data = Vec::with_capacity(state.list.iter().map(|buffer| buffer.data.len()).sum::<usize>() + 1);
+    for buffer in state.list {
+        data.extend_from_slice(&buffer.data);
+    }
+    data.push(0x3B); // Trailing marker
+
+    MsfGifResult { data }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decoded image of a GIF file
+    struct GifFrame {
+        delay: u16,
+        size: (u16, u16),
+        /// RGB color of each pixel, `None` for transparent ones
+        pixels: Vec<Option<[u8; 3]>>,
+    }
+
+    /// Concatenate data sub-blocks starting at `pos`, moves `pos` after the block terminator
+    fn sub_blocks(data: &[u8], pos: &mut usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        while data[*pos] != 0 {
+            let len = usize::from(data[*pos]);
+            out.extend_from_slice(&data[*pos + 1..*pos + 1 + len]);
+            *pos += 1 + len;
+        }
+        *pos += 1;
+        out
+    }
+
+    /// Decode GIF variable code size LZW data into color indices
+    fn lzw_decode(min_code_size: u32, data: &[u8]) -> Vec<u8> {
+        let (clear, end) = (1usize << min_code_size, (1usize << min_code_size) + 1);
+        let reset = || (0..clear).map(|index| vec![index as u8]).chain([Vec::new(), Vec::new()]).collect::<Vec<_>>();
+        let (mut table, mut code_size, mut previous) = (reset(), min_code_size + 1, None::<Vec<u8>>);
+        let (mut out, mut bit) = (Vec::new(), 0);
+        loop {
+            let code = (0..code_size as usize).fold(0, |code, i| code | usize::from(data[(bit + i)/8] >> ((bit + i)%8) & 1) << i);
+            bit += code_size as usize;
+            if code == clear {
+                (table, code_size, previous) = (reset(), min_code_size + 1, None);
+                continue;
+            } else if code == end {
+                return out;
+            }
+
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.as_slice(), &previous[..1]].concat(),
+                (None, None) => panic!("code {code} used before definition"),
+            };
+            out.extend_from_slice(&entry);
+            if let Some(previous) = previous {
+                if table.len() <= LZW_MAX_CODE as usize {
+                    table.push([previous.as_slice(), &entry[..1]].concat());
+                }
+            }
+            if table.len() == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+            previous = Some(entry);
+        }
+    }
+
+    /// Parse a GIF file, checking its header and trailer
+    fn decode(data: &[u8]) -> ((u16, u16), Vec<GifFrame>) {
+        assert_eq!(&data[..6], b"GIF89a");
+        let size = (u16::from_le_bytes([data[6], data[7]]), u16::from_le_bytes([data[8], data[9]]));
+        assert_eq!(data[10] & 0x80, 0, "no global color table");
+
+        let (mut pos, mut delay, mut frames) = (13, 0, Vec::new());
+        loop {
+            match data[pos] {
+                0x21 => {
+                    let label = data[pos + 1];
+                    pos += 2;
+                    let block = sub_blocks(data, &mut pos);
+                    if label == 0xF9 {
+                        delay = u16::from_le_bytes([block[1], block[2]]);
+                    }
+                }
+                0x2C => {
+                    let field = |i: usize| u16::from_le_bytes([data[pos + i], data[pos + i + 1]]);
+                    let frame_size = (field(5), field(7));
+                    let flags = data[pos + 9];
+                    assert_ne!(flags & 0x80, 0, "local color table");
+                    let table_len = 3 << ((flags & 7) + 1);
+                    let table = &data[pos + 10..pos + 10 + table_len];
+                    pos += 10 + table_len;
+                    let min_code_size = u32::from(data[pos]);
+                    pos += 1;
+                    let indices = lzw_decode(min_code_size, &sub_blocks(data, &mut pos));
+                    let pixels = indices.iter().map(|&index| {
+                        let index = usize::from(index);
+                        (index != 0).then(|| [table[index*3], table[index*3 + 1], table[index*3 + 2]])
+                    }).collect();
+                    frames.push(GifFrame { delay, size: frame_size, pixels });
+                }
+                0x3B => {
+                    assert_eq!(pos, data.len() - 1, "trailer ends the file");
+                    return (size, frames);
+                }
+                marker => panic!("unexpected block 0x{marker:02X} at {pos}"),
+            }
+        }
+    }
+
+    /// RGBA frame of 8x6 pixels from a color per pixel
+    fn frame(color: impl Fn(usize, usize) -> [u8; 3]) -> Vec<u8> {
+        (0..6).flat_map(|y| (0..8).map(move |x| (x, y))).flat_map(|(x, y)| {
+            let [r, g, b] = color(x, y);
+            [r, g, b, 255]
+        }).collect()
+    }
+
+    #[test]
+    fn test_gif_golden() {
+        const RED: [u8; 3] = [255, 0, 0];
+        const BLUE: [u8; 3] = [0, 0, 255];
+        const WHITE: [u8; 3] = [255, 255, 255];
+        let checker = |x: usize, y: usize| if (x + y).is_multiple_of(2) { RED } else { BLUE };
+        let stripes = |x: usize, _| if x < 4 { WHITE } else { BLUE };
+        let frames = [frame(checker), frame(checker), frame(stripes)];
+
+        let mut state = MsfGifState::default();
+        assert!(!msf_gif_frame(&mut state, &frames[0], 10, 16, 0), "frame before begin");
+        assert!(msf_gif_begin(&mut state, 8, 6));
+        for pixels in &frames {
+            assert!(msf_gif_frame(&mut state, pixels, 10, 16, 0));
+        }
+        assert!(!msf_gif_frame(&mut state, &frames[0][..8], 10, 16, 0), "short frame data");
+        let gif = msf_gif_end(&mut state).data;
+        assert_eq!(gif.last(), Some(&0x3B));
+
+        let (size, decoded) = decode(&gif);
+        assert_eq!(size, (8, 6));
+        assert_eq!(decoded.len(), 3);
+        assert!(decoded.iter().all(|frame| frame.size == (8, 6) && frame.delay == 10 && frame.pixels.len() == 48));
+
+        // First frame is complete, unchanged pixels of next frames are transparent
+        let expected = |color: &dyn Fn(usize, usize) -> [u8; 3]| (0..48).map(|i| Some(color(i % 8, i / 8))).collect::<Vec<_>>();
+        assert_eq!(decoded[0].pixels, expected(&checker));
+        assert!(decoded[1].pixels.iter().all(Option::is_none));
+        let changed = (0..48).map(|i| (checker(i % 8, i / 8) != stripes(i % 8, i / 8)).then(|| stripes(i % 8, i / 8))).collect::<Vec<_>>();
+        assert_eq!(decoded[2].pixels, changed);
+
+        // Ending without frames gives an empty animation, never started gives nothing
+        assert!(msf_gif_begin(&mut state, 8, 6));
+        assert_eq!(decode(&msf_gif_end(&mut state).data).1.len(), 0);
+        assert!(msf_gif_end(&mut state).data.is_empty());
+    }
 }
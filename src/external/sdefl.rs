@@ -0,0 +1,408 @@
+//! Raw DEFLATE compression (RFC 1951), counterpart of sdefl (Micha Mettke)
+//!
+//! Greedy LZ77 matching over hash chains, every block is written with the cheapest of
+//! dynamic Huffman, fixed Huffman or stored encoding
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+use super::sinfl::{CODE_LENGTH_ORDER, DIST_BASE, DIST_EXTRA, LENGTH_BASE, LENGTH_EXTRA, MAX_CODE_BITS};
+
+/// Sliding window size, max match distance
+const WINDOW_SIZE: usize = 1 << 15;
+const HASH_BITS: u32 = 15;
+/// Max number of hash chain entries checked per position
+const MAX_CHAIN: usize = 64;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+/// Max tokens per block, Huffman tables are rebuilt for each block
+const BLOCK_TOKENS: usize = 1 << 15;
+/// Maximum payload of a stored block
+const MAX_STORED_BLOCK: usize = 0xFFFF;
+/// Maximum bits of a code length code
+const MAX_CODE_LENGTH_BITS: usize = 7;
+const NO_POS: usize = usize::MAX;
+
+/// LZ77 output symbol
+#[derive(Clone, Copy)]
+enum Token {
+    Literal(u8),
+    Match { len: u16, dist: u16 },
+}
+
+impl Token {
+    /// Get literal/length symbol, distance symbol and extra bits values
+    fn symbols(self) -> (usize, Option<usize>, u32, u32) {
+        match self {
+            Self::Literal(byte) => (usize::from(byte), None, 0, 0),
+            Self::Match { len, dist } => {
+                let len_index = LENGTH_BASE.partition_point(|&base| base <= len) - 1;
+                let dist_index = DIST_BASE.partition_point(|&base| base <= dist) - 1;
+                (257 + len_index, Some(dist_index), u32::from(len - LENGTH_BASE[len_index]), u32::from(dist - DIST_BASE[dist_index]))
+            }
+        }
+    }
+}
+
+/// LSB-first bit writer
+#[derive(Default)]
+struct BitWriter {
+    out: Vec<u8>,
+    buffer: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    #[allow(clippy::cast_possible_truncation)]
+    fn bits(&mut self, value: u32, n: u32) {
+        self.buffer |= u64::from(value) << self.count;
+        self.count += n;
+        while self.count >= 8 {
+            self.out.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Pad with zero bits up to the next byte boundary
+    fn align(&mut self) {
+        if self.count > 0 {
+            self.bits(0, 8 - self.count);
+        }
+    }
+}
+
+/// Compute length limited Huffman code lengths from symbol frequencies
+/// NOTE: At least two codes are always generated, so every code is complete
+fn huffman_lengths(freqs: &[u32], limit: usize) -> Vec<u8> {
+    let mut freqs = freqs.to_vec();
+    for index in 0..freqs.len() {
+        if freqs.iter().filter(|&&freq| freq > 0).count() >= 2 {
+            break;
+        }
+        if freqs[index] == 0 {
+            freqs[index] = 1;
+        }
+    }
+
+    loop {
+        // Build tree, leaves are symbols, then internal nodes in creation order
+        let mut heap: BinaryHeap<_> = freqs.iter().enumerate()
+            .filter(|(_, &freq)| freq > 0)
+            .map(|(symbol, &freq)| Reverse((u64::from(freq), symbol)))
+            .collect();
+        let mut children = Vec::new();
+        while heap.len() > 1 {
+            let (Some(Reverse((weight_a, a))), Some(Reverse((weight_b, b)))) = (heap.pop(), heap.pop()) else { break };
+            children.push((a, b));
+            heap.push(Reverse((weight_a + weight_b, freqs.len() + children.len() - 1)));
+        }
+
+        // Depths top-down, root is the last internal node
+        let mut depths = vec![0usize; freqs.len() + children.len()];
+        for (index, &(a, b)) in children.iter().enumerate().rev() {
+            let depth = depths[freqs.len() + index] + 1;
+            depths[a] = depth;
+            depths[b] = depth;
+        }
+
+        let lengths = &depths[..freqs.len()];
+        if lengths.iter().all(|&len| len <= limit) {
+            return lengths.iter().map(|&len| u8::try_from(len).unwrap_or_default()).collect();
+        }
+        // Flatten frequencies until the tree fits
+        for freq in freqs.iter_mut().filter(|freq| **freq > 0) {
+            *freq = (*freq >> 1) | 1;
+        }
+    }
+}
+
+/// Compute canonical codes from code lengths, bit reversed for LSB-first output
+fn canonical_codes(lengths: &[u8]) -> Vec<u32> {
+    let mut counts = [0u32; MAX_CODE_BITS + 1];
+    for &len in lengths {
+        counts[usize::from(len)] += 1;
+    }
+    counts[0] = 0;
+
+    let mut next = [0u32; MAX_CODE_BITS + 1];
+    for len in 1..=MAX_CODE_BITS {
+        next[len] = (next[len - 1] + counts[len - 1]) << 1;
+    }
+
+    lengths.iter().map(|&len| {
+        if len == 0 {
+            return 0;
+        }
+        let code = next[usize::from(len)];
+        next[usize::from(len)] += 1;
+        code.reverse_bits() >> (32 - u32::from(len))
+    }).collect()
+}
+
+/// Run-length encode code lengths with code length symbols 16, 17 and 18
+#[allow(clippy::cast_possible_truncation)]
+fn rle_lengths(lengths: &[u8]) -> Vec<(u8, u32)> {
+    let mut out = Vec::new();
+    let mut index = 0;
+    while index < lengths.len() {
+        let len = lengths[index];
+        let run = lengths[index..].iter().take_while(|&&other| other == len).count();
+        let mut left = run;
+        if len == 0 {
+            while left >= 11 {
+                let n = left.min(138);
+                out.push((18, (n - 11) as u32));
+                left -= n;
+            }
+            if left >= 3 {
+                out.push((17, (left - 3) as u32));
+                left = 0;
+            }
+        } else {
+            out.push((len, 0));
+            left -= 1;
+            while left >= 3 {
+                let n = left.min(6);
+                out.push((16, (n - 3) as u32));
+                left -= n;
+            }
+        }
+        out.extend(std::iter::repeat_n((len, 0), left));
+        index += run;
+    }
+    out
+}
+
+/// Extra bits of a code length symbol
+fn code_length_extra(symbol: u8) -> u32 {
+    match symbol {
+        16 => 2,
+        17 => 3,
+        18 => 7,
+        _ => 0,
+    }
+}
+
+/// Fixed Huffman literal/length and distance code lengths
+fn fixed_lengths() -> ([u8; 288], [u8; 30]) {
+    let mut lengths = [8u8; 288];
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    (lengths, [5; 30])
+}
+
+/// Bits used by tokens with the given code lengths
+fn tokens_cost(tokens: &[Token], lit_lengths: &[u8], dist_lengths: &[u8]) -> usize {
+    tokens.iter().map(|&token| {
+        let (lit, dist, ..) = token.symbols();
+        usize::from(lit_lengths[lit]) + dist.map_or(0, |dist| {
+            usize::from(LENGTH_EXTRA[lit - 257]) + usize::from(dist_lengths[dist]) + usize::from(DIST_EXTRA[dist])
+        })
+    }).sum::<usize>() + usize::from(lit_lengths[256])
+}
+
+/// Write tokens and end of block with the given codes
+fn write_tokens(writer: &mut BitWriter, tokens: &[Token], lit_lengths: &[u8], dist_lengths: &[u8]) {
+    let (lit_codes, dist_codes) = (canonical_codes(lit_lengths), canonical_codes(dist_lengths));
+    for &token in tokens {
+        let (lit, dist, len_extra, dist_extra) = token.symbols();
+        writer.bits(lit_codes[lit], u32::from(lit_lengths[lit]));
+        if let Some(dist) = dist {
+            writer.bits(len_extra, u32::from(LENGTH_EXTRA[lit - 257]));
+            writer.bits(dist_codes[dist], u32::from(dist_lengths[dist]));
+            writer.bits(dist_extra, u32::from(DIST_EXTRA[dist]));
+        }
+    }
+    writer.bits(lit_codes[256], u32::from(lit_lengths[256]));
+}
+
+/// Write a block of tokens (encoding `raw` input data), choosing the smallest encoding
+#[allow(clippy::cast_possible_truncation)]
+fn write_block(writer: &mut BitWriter, tokens: &[Token], raw: &[u8], last: bool) {
+    let mut lit_freqs = [0u32; 286];
+    let mut dist_freqs = [0u32; 30];
+    lit_freqs[256] = 1;
+    for &token in tokens {
+        let (lit, dist, ..) = token.symbols();
+        lit_freqs[lit] += 1;
+        if let Some(dist) = dist {
+            dist_freqs[dist] += 1;
+        }
+    }
+
+    let lit_lengths = huffman_lengths(&lit_freqs, MAX_CODE_BITS);
+    let dist_lengths = huffman_lengths(&dist_freqs, MAX_CODE_BITS);
+    let lit_count = lit_lengths.iter().rposition(|&len| len > 0).map_or(0, |last| last + 1).max(257);
+    let dist_count = dist_lengths.iter().rposition(|&len| len > 0).map_or(0, |last| last + 1).max(1);
+
+    let rle = rle_lengths(&[&lit_lengths[..lit_count], &dist_lengths[..dist_count]].concat());
+    let mut code_freqs = [0u32; 19];
+    for &(symbol, _) in &rle {
+        code_freqs[usize::from(symbol)] += 1;
+    }
+    let code_lengths = huffman_lengths(&code_freqs, MAX_CODE_LENGTH_BITS);
+    let code_count = CODE_LENGTH_ORDER.iter().rposition(|&index| code_lengths[index] > 0).map_or(0, |last| last + 1).max(4);
+
+    let dynamic_cost = 3 + 5 + 5 + 4 + 3*code_count
+        + rle.iter().map(|&(symbol, _)| usize::from(code_lengths[usize::from(symbol)]) + code_length_extra(symbol) as usize).sum::<usize>()
+        + tokens_cost(tokens, &lit_lengths, &dist_lengths);
+    let (fixed_lit_lengths, fixed_dist_lengths) = fixed_lengths();
+    let fixed_cost = 3 + tokens_cost(tokens, &fixed_lit_lengths, &fixed_dist_lengths);
+    let stored_cost = (raw.len().div_ceil(MAX_STORED_BLOCK).max(1))*(3 + 7 + 32) + raw.len()*8;
+
+    if stored_cost < dynamic_cost.min(fixed_cost) {
+        let mut chunks = raw.chunks(MAX_STORED_BLOCK).peekable();
+        if chunks.peek().is_none() {
+            writer.bits(u32::from(last), 3);
+            writer.align();
+            writer.bits(0xFFFF_0000, 32);
+        }
+        while let Some(chunk) = chunks.next() {
+            writer.bits(u32::from(last && chunks.peek().is_none()), 3);
+            writer.align();
+            let len = chunk.len() as u32;
+            writer.bits(len | (!len << 16), 32);
+            writer.out.extend_from_slice(chunk);
+        }
+    } else if fixed_cost <= dynamic_cost {
+        writer.bits(u32::from(last) | (1 << 1), 3);
+        write_tokens(writer, tokens, &fixed_lit_lengths, &fixed_dist_lengths);
+    } else {
+        writer.bits(u32::from(last) | (2 << 1), 3);
+        writer.bits((lit_count - 257) as u32, 5);
+        writer.bits((dist_count - 1) as u32, 5);
+        writer.bits((code_count - 4) as u32, 4);
+        for &index in &CODE_LENGTH_ORDER[..code_count] {
+            writer.bits(u32::from(code_lengths[index]), 3);
+        }
+        let codes = canonical_codes(&code_lengths);
+        for &(symbol, extra) in &rle {
+            writer.bits(codes[usize::from(symbol)], u32::from(code_lengths[usize::from(symbol)]));
+            writer.bits(extra, code_length_extra(symbol));
+        }
+        write_tokens(writer, tokens, &lit_lengths, &dist_lengths);
+    }
+}
+
+/// Positions of previous occurrences of each 3 bytes sequence, most recent first
+struct HashChains {
+    head: Vec<usize>,
+    prev: Vec<usize>,
+}
+
+impl HashChains {
+    fn new() -> Self {
+        Self {
+            head: vec![NO_POS; 1 << HASH_BITS],
+            prev: vec![NO_POS; WINDOW_SIZE],
+        }
+    }
+
+    /// Hash of the 3 bytes starting at `pos`
+    fn hash(data: &[u8], pos: usize) -> usize {
+        let value = u32::from(data[pos]) << 16 | u32::from(data[pos + 1]) << 8 | u32::from(data[pos + 2]);
+        (value.wrapping_mul(0x9E37_79B1) >> (32 - HASH_BITS)) as usize
+    }
+
+    fn insert(&mut self, data: &[u8], pos: usize) {
+        if pos + MIN_MATCH <= data.len() {
+            let hash = Self::hash(data, pos);
+            self.prev[pos % WINDOW_SIZE] = self.head[hash];
+            self.head[hash] = pos;
+        }
+    }
+
+    /// Find longest match for `pos` within the window, returns length and distance
+    fn longest_match(&self, data: &[u8], pos: usize) -> (usize, usize) {
+        let (mut best_len, mut best_dist) = (0, 0);
+        if pos + MIN_MATCH > data.len() {
+            return (best_len, best_dist);
+        }
+
+        let max_len = MAX_MATCH.min(data.len() - pos);
+        let mut candidate = self.head[Self::hash(data, pos)];
+        let mut chain = MAX_CHAIN;
+        // NOTE: Entries within the window are never overwritten, so following the chain is safe
+        while candidate != NO_POS && pos - candidate <= WINDOW_SIZE && chain > 0 {
+            let len = data[candidate..].iter().zip(&data[pos..pos + max_len]).take_while(|(a, b)| a == b).count();
+            if len > best_len {
+                (best_len, best_dist) = (len, pos - candidate);
+                if len == max_len {
+                    break;
+                }
+            }
+            candidate = self.prev[candidate % WINDOW_SIZE];
+            chain -= 1;
+        }
+        (best_len, best_dist)
+    }
+}
+
+/// Compress data as raw DEFLATE stream
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::default();
+    let mut chains = HashChains::new();
+
+    let mut tokens = Vec::with_capacity(BLOCK_TOKENS);
+    let (mut block_start, mut pos) = (0, 0);
+    while pos < data.len() {
+        let (best_len, best_dist) = chains.longest_match(data, pos);
+        if best_len >= MIN_MATCH {
+            tokens.push(Token::Match { len: best_len as u16, dist: best_dist as u16 });
+            for offset in 0..best_len {
+                chains.insert(data, pos + offset);
+            }
+            pos += best_len;
+        } else {
+            tokens.push(Token::Literal(data[pos]));
+            chains.insert(data, pos);
+            pos += 1;
+        }
+
+        if tokens.len() == BLOCK_TOKENS {
+            write_block(&mut writer, &tokens, &data[block_start..pos], pos == data.len());
+            tokens.clear();
+            block_start = pos;
+        }
+    }
+    if !tokens.is_empty() || data.is_empty() {
+        write_block(&mut writer, &tokens, &data[block_start..], true);
+    }
+
+    writer.align();
+    writer.out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::sinfl::{inflate, tests::samples};
+
+    #[test]
+    fn test_deflate_zlib_inflate() {
+        for data in samples() {
+            let compressed = deflate(&data);
+            let decompressed = miniz_oxide::inflate::decompress_to_vec(&compressed).unwrap();
+            assert!(decompressed == data, "{} bytes", data.len());
+        }
+    }
+
+    #[test]
+    fn test_deflate_round_trip() {
+        for data in samples() {
+            let compressed = deflate(&data);
+            assert_eq!(inflate(&compressed, data.len()).unwrap(), (data, compressed.len()));
+        }
+    }
+
+    #[test]
+    fn test_deflate_size() {
+        let [empty, _, text, noise, runs, _] = samples().try_into().unwrap();
+        assert!(deflate(&empty).len() <= 2);
+        assert!(deflate(&text).len() < text.len()/20);
+        assert!(deflate(&runs).len() < runs.len()/500);
+        // Incompressible data falls back to stored blocks, only block headers are added
+        assert!(deflate(&noise).len() <= noise.len() + noise.len()/1000);
+    }
+}
@@ -0,0 +1,366 @@
+//! Raw DEFLATE decompression (RFC 1951), counterpart of sinfl (Micha Mettke)
+//!
+//! Stored, fixed and dynamic Huffman blocks are supported, output size is capped by the caller
+
+use crate::utils::DecompressError;
+
+/// Maximum bits of a DEFLATE Huffman code
+pub(crate) const MAX_CODE_BITS: usize = 15;
+
+/// Base length and extra bits of length symbols 257..285
+pub(crate) const LENGTH_BASE: [u16; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+pub(crate) const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+
+/// Base distance and extra bits of distance symbols 0..29
+pub(crate) const DIST_BASE: [u16; 30] = [1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577];
+pub(crate) const DIST_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+
+/// Order of code length code lengths in dynamic block headers
+pub(crate) const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Maximum back-reference distance, output kept in memory when streaming
+pub(crate) const WINDOW_SIZE: usize = 32768;
+
+/// LSB-first bit reader over DEFLATE data
+struct BitReader<'d> {
+    data: &'d [u8],
+    pos: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl<'d> BitReader<'d> {
+    fn new(data: &'d [u8]) -> Self {
+        Self { data, pos: 0, buffer: 0, count: 0 }
+    }
+
+    fn bits(&mut self, n: u32) -> Result<u32, DecompressError> {
+        while self.count < n {
+            self.buffer |= u32::from(*self.data.get(self.pos).ok_or(DecompressError::InvalidData)?) << self.count;
+            self.pos += 1;
+            self.count += 8;
+        }
+        let value = self.buffer & ((1 << n) - 1);
+        self.buffer >>= n;
+        self.count -= n;
+        Ok(value)
+    }
+
+    /// Discard bits up to the next byte boundary
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+/// Receiver of streamed decompressed data
+type Sink<'s> = &'s mut dyn FnMut(&[u8]);
+
+/// Decompressed output, optionally streamed to a sink keeping only the back-reference window
+struct Output<'s> {
+    data: Vec<u8>,
+    /// Number of bytes already passed to the sink and dropped from `data`
+    flushed: usize,
+    max_size: usize,
+    sink: Option<Sink<'s>>,
+}
+
+impl<'s> Output<'s> {
+    fn new(max_size: usize, sink: Option<Sink<'s>>) -> Self {
+        Self { data: Vec::new(), flushed: 0, max_size, sink }
+    }
+
+    /// Check if `len` more bytes fit in the output
+    fn reserve(&self, len: usize) -> Result<(), DecompressError> {
+        if self.flushed + self.data.len() + len > self.max_size {
+            return Err(DecompressError::TooLarge(self.max_size));
+        }
+        Ok(())
+    }
+
+    /// Pass data out of the window to the sink, once enough data is accumulated
+    fn flush(&mut self, all: bool) {
+        if let Some(sink) = &mut self.sink {
+            let keep = if all { 0 } else { WINDOW_SIZE };
+            if all || self.data.len() >= 2*WINDOW_SIZE {
+                let len = self.data.len() - keep;
+                sink(&self.data[..len]);
+                self.data.drain(..len);
+                self.flushed += len;
+            }
+        }
+    }
+
+    /// Copy a back-reference, byte by byte as the match can overlap the output
+    fn copy(&mut self, dist: usize, len: usize) -> Result<(), DecompressError> {
+        let start = self.data.len().checked_sub(dist).ok_or(DecompressError::InvalidData)?;
+        for i in 0..len {
+            self.data.push(self.data[start + i]);
+        }
+        Ok(())
+    }
+}
+
+/// Canonical Huffman decoding table
+struct Huffman {
+    /// Number of codes of each length
+    counts: [u16; MAX_CODE_BITS + 1],
+    /// Symbols sorted by code
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// Build table from code lengths, fails if lengths are over-subscribed
+    fn new(lengths: &[u8]) -> Result<Self, DecompressError> {
+        let mut counts = [0u16; MAX_CODE_BITS + 1];
+        for &len in lengths {
+            *counts.get_mut(usize::from(len)).ok_or(DecompressError::InvalidData)? += 1;
+        }
+        counts[0] = 0;
+
+        let mut left = 1i32;
+        for &count in &counts[1..] {
+            left = (left << 1) - i32::from(count);
+            if left < 0 {
+                return Err(DecompressError::InvalidData);
+            }
+        }
+
+        let mut offsets = [0u16; MAX_CODE_BITS + 2];
+        for len in 1..=MAX_CODE_BITS {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &len) in (0u16..).zip(lengths) {
+            if len != 0 {
+                let offset = &mut offsets[usize::from(len)];
+                symbols[usize::from(*offset)] = symbol;
+                *offset += 1;
+            }
+        }
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, DecompressError> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for &count in &self.counts[1..] {
+            code |= i32::try_from(reader.bits(1)?).map_err(|_| DecompressError::InvalidData)?;
+            let count = i32::from(count);
+            if code - count < first {
+                return usize::try_from(index + code - first).ok()
+                    .and_then(|index| self.symbols.get(index).copied())
+                    .ok_or(DecompressError::InvalidData);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(DecompressError::InvalidData)
+    }
+}
+
+/// Decode a Huffman compressed block
+#[allow(clippy::cast_possible_truncation)]
+fn inflate_block(reader: &mut BitReader, out: &mut Output, lengths: &Huffman, distances: &Huffman) -> Result<(), DecompressError> {
+    loop {
+        let symbol = usize::from(lengths.decode(reader)?);
+        let len = match symbol {
+            0..=255 => 1,
+            256 => return Ok(()),
+            _ => {
+                let index = symbol - 257;
+                usize::from(*LENGTH_BASE.get(index).ok_or(DecompressError::InvalidData)?) + reader.bits(u32::from(LENGTH_EXTRA[index]))? as usize
+            }
+        };
+        out.reserve(len)?;
+
+        if symbol < 256 {
+            out.data.push(symbol as u8);
+        } else {
+            let index = usize::from(distances.decode(reader)?);
+            let dist = usize::from(*DIST_BASE.get(index).ok_or(DecompressError::InvalidData)?) + reader.bits(u32::from(DIST_EXTRA[index]))? as usize;
+            out.copy(dist, len)?;
+        }
+        out.flush(false);
+    }
+}
+
+/// Read Huffman tables of a dynamic block
+#[allow(clippy::cast_possible_truncation)]
+fn dynamic_tables(reader: &mut BitReader) -> Result<(Huffman, Huffman), DecompressError> {
+    let lit_count = reader.bits(5)? as usize + 257;
+    let dist_count = reader.bits(5)? as usize + 1;
+    let code_count = reader.bits(4)? as usize + 4;
+
+    let mut code_lengths = [0u8; 19];
+    for &index in &CODE_LENGTH_ORDER[..code_count] {
+        code_lengths[index] = reader.bits(3)? as u8;
+    }
+    let codes = Huffman::new(&code_lengths)?;
+
+    let mut lengths = Vec::with_capacity(lit_count + dist_count);
+    while lengths.len() < lit_count + dist_count {
+        let (value, repeat) = match codes.decode(reader)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => (*lengths.last().ok_or(DecompressError::InvalidData)?, 3 + reader.bits(2)?),
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?),
+        };
+        lengths.extend(std::iter::repeat_n(value, repeat as usize));
+    }
+    if lengths.len() != lit_count + dist_count || lengths[256] == 0 {
+        return Err(DecompressError::InvalidData);
+    }
+    Ok((Huffman::new(&lengths[..lit_count])?, Huffman::new(&lengths[lit_count..])?))
+}
+
+/// Decompress raw DEFLATE data, output is limited to `max_size` bytes
+/// NOTE: Returns the decompressed data and the number of bytes of `data` consumed,
+/// trailing data (i.e. zlib checksum) starts at the next byte boundary
+pub(crate) fn inflate(data: &[u8], max_size: usize) -> Result<(Vec<u8>, usize), DecompressError> {
+    let mut out = Output::new(max_size, None);
+    let used = inflate_into(data, &mut out)?;
+    Ok((out.data, used))
+}
+
+/// Decompress raw DEFLATE data progressively, decompressed data is passed to `sink` in pieces
+/// NOTE: Only the back-reference window is kept in memory, returns the decompressed size
+/// and the number of bytes of `data` consumed, like [`inflate`]
+pub(crate) fn inflate_stream(data: &[u8], max_size: usize, sink: &mut dyn FnMut(&[u8])) -> Result<(usize, usize), DecompressError> {
+    let mut out = Output::new(max_size, Some(sink));
+    let used = inflate_into(data, &mut out)?;
+    out.flush(true);
+    Ok((out.flushed, used))
+}
+
+/// Decompress DEFLATE blocks into the output, returns the number of bytes consumed
+fn inflate_into(data: &[u8], out: &mut Output) -> Result<usize, DecompressError> {
+    let mut reader = BitReader::new(data);
+    loop {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align();
+                let header = data.get(reader.pos..reader.pos + 4).ok_or(DecompressError::InvalidData)?;
+                let len = u16::from_le_bytes([header[0], header[1]]);
+                if len != !u16::from_le_bytes([header[2], header[3]]) {
+                    return Err(DecompressError::InvalidData);
+                }
+                out.reserve(usize::from(len))?;
+                let start = reader.pos + 4;
+                out.data.extend_from_slice(data.get(start..start + usize::from(len)).ok_or(DecompressError::InvalidData)?);
+                out.flush(false);
+                reader.pos = start + usize::from(len);
+            }
+            1 => {
+                let mut lengths = [8u8; 288];
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                let lengths = Huffman::new(&lengths)?;
+                let distances = Huffman::new(&[5; 30])?;
+                inflate_block(&mut reader, out, &lengths, &distances)?;
+            }
+            2 => {
+                let (lengths, distances) = dynamic_tables(&mut reader)?;
+                inflate_block(&mut reader, out, &lengths, &distances)?;
+            }
+            _ => return Err(DecompressError::InvalidData),
+        }
+        if last {
+            break;
+        }
+    }
+
+    reader.align();
+    Ok(reader.pos)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Test inputs: empty, tiny, repetitive text, incompressible (several stored blocks),
+    /// long runs (matches spanning the window) and low entropy noise (several Huffman blocks)
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn samples() -> Vec<Vec<u8>> {
+        let mut seed = 0x1234_5678u32;
+        let mut noise = || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (seed >> 24) as u8
+        };
+        vec![
+            Vec::new(),
+            vec![b'a'],
+            b"The quick brown fox jumps over the lazy dog. ".repeat(100),
+            (0..150_000).map(|_| noise()).collect(),
+            [vec![0; 100_000], vec![1; 300], vec![0; 70_000]].concat(),
+            (0..200_000).map(|_| noise() & 3).collect(),
+        ]
+    }
+
+    #[test]
+    fn test_inflate_zlib_streams() {
+        for data in samples() {
+            for level in 0..=10 {
+                let compressed = miniz_oxide::deflate::compress_to_vec(&data, level);
+                let (decompressed, consumed) = inflate(&compressed, data.len()).unwrap();
+                assert!(decompressed == data, "level {level}, {} bytes", data.len());
+                assert_eq!(consumed, compressed.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_inflate_stream() {
+        for data in samples() {
+            let compressed = miniz_oxide::deflate::compress_to_vec(&data, 6);
+            let mut pieces = Vec::new();
+            let (size, consumed) = inflate_stream(&compressed, data.len(), &mut |piece| pieces.push(piece.to_vec())).unwrap();
+            assert_eq!((size, consumed), (data.len(), compressed.len()));
+            assert!(pieces.concat() == data, "{} bytes", data.len());
+            // Only the window (and the block being decoded) is held in memory
+            assert!(pieces.iter().all(|piece| piece.len() <= 2*WINDOW_SIZE + usize::from(u16::MAX)));
+            assert!(data.len() < 4*WINDOW_SIZE || pieces.len() > 1, "{} bytes in {} pieces", data.len(), pieces.len());
+        }
+        assert_eq!(inflate_stream(&[0b111], 64, &mut |_| {}), Err(DecompressError::InvalidData));
+    }
+
+    #[test]
+    fn test_inflate_trailing_data() {
+        // zlib wrapper: 2 byte header, DEFLATE stream, 4 byte checksum after it
+        let data = b"hello hello hello hello";
+        let wrapped = miniz_oxide::deflate::compress_to_vec_zlib(data, 6);
+        let (decompressed, consumed) = inflate(&wrapped[2..], data.len()).unwrap();
+        assert_eq!(decompressed, data);
+        assert_eq!(consumed, wrapped.len() - 6);
+    }
+
+    #[test]
+    fn test_inflate_blocks() {
+        // Fixed Huffman block
+        let fixed = [203, 72, 205, 201, 201, 87, 200, 64, 39, 1];
+        assert_eq!(inflate(&fixed, 64).unwrap(), (b"hello hello hello hello".to_vec(), fixed.len()));
+        // Stored blocks, non final then final
+        let stored = [0, 2, 0, !2, !0, b'h', b'i', 1, 1, 0, !1, !0, b'!'];
+        assert_eq!(inflate(&stored, 64).unwrap(), (b"hi!".to_vec(), stored.len()));
+        assert_eq!(inflate(&[1, 0, 0, !0, !0], 0).unwrap(), (Vec::new(), 5));
+    }
+
+    #[test]
+    fn test_inflate_invalid() {
+        let stored = [1, 2, 0, !2, !0, b'h', b'i'];
+        assert_eq!(inflate(&[], 64), Err(DecompressError::InvalidData));
+        // Reserved block type
+        assert_eq!(inflate(&[0b111], 64), Err(DecompressError::InvalidData));
+        // Stored length not matching its complement, truncated payload
+        assert_eq!(inflate(&[1, 2, 0, 2, !0, b'h', b'i'], 64), Err(DecompressError::InvalidData));
+        assert_eq!(inflate(&stored[..6], 64), Err(DecompressError::InvalidData));
+        // Truncated Huffman data, missing final block
+        let compressed = miniz_oxide::deflate::compress_to_vec(&samples()[2], 6);
+        assert_eq!(inflate(&compressed[..compressed.len()/2], usize::MAX), Err(DecompressError::InvalidData));
+        assert_eq!(inflate(&[0, 0, 0, !0, !0], 64), Err(DecompressError::InvalidData));
+        // Output limit
+        assert_eq!(inflate(&stored, 1), Err(DecompressError::TooLarge(1)));
+        assert_eq!(inflate(&compressed, 100), Err(DecompressError::TooLarge(100)));
+    }
+}
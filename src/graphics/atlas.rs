@@ -0,0 +1,122 @@
+//! Rectangle packing for texture atlases
+
+use crate::prelude::*;
+
+/// Shelf-based rectangle packer for texture atlases
+/// NOTE: Rectangles are placed left to right in rows (shelves), a new shelf is opened
+/// below the current one when a rectangle doesn't fit. Packing rectangles sorted by
+/// decreasing height gives the best results, see [`AtlasPacker::pack_all`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtlasPacker {
+    /// Atlas width in pixels
+    width: u32,
+    /// Atlas height in pixels
+    height: u32,
+    /// Padding added around every packed rectangle
+    padding: u32,
+    /// Current shelf top position
+    shelf_y: u32,
+    /// Current shelf height
+    shelf_height: u32,
+    /// Next free position in current shelf
+    cursor_x: u32,
+}
+
+impl AtlasPacker {
+    /// Create a packer for an atlas of the given size, with padding between rectangles
+    #[must_use]
+    pub const fn new(width: u32, height: u32, padding: u32) -> Self {
+        Self {
+            width,
+            height,
+            padding,
+            shelf_y: 0,
+            shelf_height: 0,
+            cursor_x: 0,
+        }
+    }
+
+    /// Atlas width in pixels
+    #[inline]
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Atlas height in pixels
+    #[inline]
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Find space for a rectangle, returning its position in the atlas (padding excluded)
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn pack(&mut self, width: u32, height: u32) -> Option<Point> {
+        let padded_width = width + 2 * self.padding;
+        let padded_height = height + 2 * self.padding;
+
+        if padded_width > self.width {
+            return None;
+        }
+
+        if self.cursor_x + padded_width > self.width {
+            // Open a new shelf below the current one
+            self.shelf_y += self.shelf_height;
+            self.shelf_height = 0;
+            self.cursor_x = 0;
+        }
+
+        if self.shelf_y + padded_height > self.height {
+            return None;
+        }
+
+        let position = Point {
+            x: (self.cursor_x + self.padding) as i32,
+            y: (self.shelf_y + self.padding) as i32,
+        };
+        self.cursor_x += padded_width;
+        self.shelf_height = self.shelf_height.max(padded_height);
+        Some(position)
+    }
+
+    /// Pack all sizes into the smallest power-of-two atlas that fits them
+    /// NOTE: Returns atlas size and rectangles in the same order as `sizes`, `None` if
+    /// any size is larger than `max_size`
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn pack_all(sizes: &[Size], padding: u32, max_size: u32) -> Option<(Size, Vec<Rectangle>)> {
+        // Sort by decreasing height for tighter shelves
+        let mut order: Vec<usize> = (0..sizes.len()).collect();
+        order.sort_by_key(|&i| (std::cmp::Reverse(sizes[i].height), std::cmp::Reverse(sizes[i].width)));
+
+        let total_area: u64 = sizes.iter()
+            .map(|size| u64::from(size.width + 2 * padding) * u64::from(size.height + 2 * padding))
+            .sum();
+
+        // Start from the smallest square-ish power of two that can hold the total area
+        let mut width: u32 = 1;
+        let mut height: u32 = 1;
+        while u64::from(width) * u64::from(height) < total_area {
+            if width <= height { width *= 2; } else { height *= 2; }
+        }
+
+        while width <= max_size && height <= max_size {
+            let mut packer = Self::new(width, height, padding);
+            let mut recs = vec![Rectangle::default(); sizes.len()];
+            let fits = order.iter().all(|&i| {
+                packer.pack(sizes[i].width, sizes[i].height)
+                    .map(|position| recs[i] = Rectangle::new(position.x as f32, position.y as f32, sizes[i].width as f32, sizes[i].height as f32))
+                    .is_some()
+            });
+
+            if fits {
+                return Some((Size { width, height }, recs));
+            }
+
+            if width <= height { width *= 2; } else { height *= 2; }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,111 @@
+//! Minimal BMP decoder, uncompressed 24 and 32 bit images, with or without file header
+
+use crate::prelude::*;
+
+/// Size of the BITMAPFILEHEADER structure
+const FILE_HEADER_SIZE: usize = 14;
+/// Size of the BITMAPINFOHEADER structure, smallest supported DIB header
+const INFO_HEADER_SIZE: usize = 40;
+
+const BI_RGB: u32 = 0;
+const BI_BITFIELDS: u32 = 3;
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(*data.get(offset..)?.first_chunk::<2>()?))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(*data.get(offset..)?.first_chunk::<4>()?))
+}
+
+/// Extract a channel from a pixel using its bit mask, scaled to 8 bit
+#[allow(clippy::cast_possible_truncation)]
+fn channel(pixel: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let max = mask >> mask.trailing_zeros();
+    let value = (pixel & mask) >> mask.trailing_zeros();
+    (u64::from(value) * 255 / u64::from(max)) as u8
+}
+
+/// Decode BMP file data, or DIB data without file header (as found on clipboards)
+/// NOTE: 24 bit images generate R8G8B8 images, 32 bit images generate R8G8B8A8 images.
+/// Alpha is considered unused (opaque) when every pixel has zero alpha
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn decode(data: &[u8]) -> Option<Image> {
+    let (dib, pixel_offset) = if data.starts_with(b"BM") {
+        let dib = data.get(FILE_HEADER_SIZE..)?;
+        (dib, Some((read_u32(data, 10)? as usize).checked_sub(FILE_HEADER_SIZE)?))
+    } else {
+        (data, None)
+    };
+
+    let header_size = read_u32(dib, 0)? as usize;
+    if header_size < INFO_HEADER_SIZE {
+        return None;
+    }
+    let width = usize::try_from(i32::from_le_bytes(read_u32(dib, 4)?.to_le_bytes())).ok()?;
+    let height = i32::from_le_bytes(read_u32(dib, 8)?.to_le_bytes());
+    let top_down = height < 0;
+    let height = height.unsigned_abs() as usize;
+    let bit_count = read_u16(dib, 14)?;
+    let compression = read_u32(dib, 16)?;
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let (mut masks, masks_size) = match (bit_count, compression) {
+        (24, BI_RGB) => ([0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0], 0),
+        (32, BI_RGB) => ([0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0xFF00_0000], 0),
+        // Masks follow a BITMAPINFOHEADER, or are part of bigger headers
+        (32, BI_BITFIELDS) => ([
+            read_u32(dib, 40)?,
+            read_u32(dib, 44)?,
+            read_u32(dib, 48)?,
+            if header_size > INFO_HEADER_SIZE { read_u32(dib, 52)? } else { 0 },
+        ], if header_size == INFO_HEADER_SIZE { 12 } else { 0 }),
+        _ => return None,
+    };
+
+    let bytes_per_pixel = usize::from(bit_count / 8);
+    let stride = (width.checked_mul(bytes_per_pixel)? + 3) & !3;
+    let pixels = dib.get(pixel_offset.unwrap_or(header_size + masks_size)..)?;
+    if pixels.len() < stride.checked_mul(height)? {
+        return None;
+    }
+
+    let pixel = |x: usize, y: usize| -> u32 {
+        let row = if top_down { y } else { height - 1 - y };
+        let start = row * stride + x * bytes_per_pixel;
+        pixels[start..start + bytes_per_pixel].iter().rev().fold(0, |value, &byte| (value << 8) | u32::from(byte))
+    };
+
+    if bit_count == 32 && masks[3] != 0 && (0..height).all(|y| (0..width).all(|x| pixel(x, y) & masks[3] == 0)) {
+        masks[3] = 0;
+    }
+
+    let (format, channels) = if bit_count == 32 {
+        (PixelFormat::UncompressedR8G8B8A8, 4)
+    } else {
+        (PixelFormat::UncompressedR8G8B8, 3)
+    };
+    let mut out = Vec::with_capacity(width * height * channels);
+    for y in 0..height {
+        for x in 0..width {
+            let value = pixel(x, y);
+            out.extend_from_slice(&[channel(value, masks[0]), channel(value, masks[1]), channel(value, masks[2])]);
+            if channels == 4 {
+                out.push(if masks[3] == 0 { 255 } else { channel(value, masks[3]) });
+            }
+        }
+    }
+
+    Some(Image {
+        data: out,
+        width,
+        height,
+        mipmap: 1,
+        format,
+    })
+}
@@ -1,12 +1,75 @@
 use crate::prelude::*;
 
+/// Camera, defines position/orientation in 2d space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera2D {
+    /// Camera offset (displacement from target)
+    pub offset: Offset2,
+    /// Camera target (rotation and zoom origin)
+    pub target: Position2,
+    /// Camera rotation
+    pub rotation: Degrees,
+    /// Camera zoom (scaling), should be 1.0f by default
+    pub zoom: f32,
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self {
+            offset: Vector2::ZERO,
+            target: Vector2::ZERO,
+            rotation: Degrees::ZERO,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl Camera2D {
+    /// Get camera 2d transform matrix
+    /// NOTE: Camera rotation and zoom are applied around target, then target is moved to offset:
+    /// matrix = translate(-target) * scale(zoom) * rotate(rotation) * translate(offset)
+    pub fn matrix(&self) -> Matrix {
+        // The camera in world-space is set by
+        //   1. Move it to target
+        //   2. Rotate by -rotation and scale by (1/zoom)
+        //      When setting higher scale, it's more intuitive for the world to become bigger (= camera become smaller),
+        //      not for the camera getting bigger, hence the invert. Same deal with rotation
+        //   3. Move it by (-offset);
+        //      Offset defines target transform relative to screen, but since we're effectively "moving" screen (camera)
+        //      we need to do it into opposite direction (inverse transform)
+
+        // Having camera transform in world-space, inverse of it gives the modelview transform
+        // Since (A*B*C)' = C'*B'*A', the modelview is
+        //   1. Move to offset
+        //   2. Rotate and Scale
+        //   3. Move by -target
+        let origin = Matrix::translate(-self.target.x, -self.target.y, 0.0);
+        let rotation = Matrix::rotate(Vector3::new(0.0, 0.0, 1.0), self.rotation.to_radians());
+        let scale = Matrix::scale(self.zoom, self.zoom, 1.0);
+        let translation = Matrix::translate(self.offset.x, self.offset.y, 0.0);
+
+        (origin*(scale*rotation))*translation
+    }
+}
+
+/// Get the screen space position for a 2d camera world space position
+pub fn get_world_to_screen_2d(position: Position2, camera: &Camera2D) -> Position2 {
+    position.transform(&camera.matrix())
+}
+
+/// Get the world space position for a 2d camera screen space position
+/// NOTE: A degenerate camera (zoom 0) can't be inverted, position is returned unchanged
+pub fn get_screen_to_world_2d(position: Position2, camera: &Camera2D) -> Position2 {
+    position.transform(&camera.matrix().invert().unwrap_or(Matrix::IDENTITY))
+}
+
 pub struct Camera3D {
     pub position: Position3,
     /// Camera target it looks-at
     pub target: Position3,
     /// Camera up vector (rotation over its axis)
     pub up: Direction3,
-    /// Camera field-of-view aperture in Y (degrees) in perspective, used as near plane width in orthographic
+    /// Camera field-of-view aperture in Y in perspective, its value is used as near plane height in orthographic
     pub fovy: Degrees,
     pub projection: CameraProjection,
 }
@@ -86,7 +149,6 @@ impl Camera {
     /// Rotates the camera around its up vector
     /// Yaw is "looking left and right"
     /// If rotateAroundTarget is false, the camera rotates around its position
-    /// Note: angle must be provided in radians
     pub fn yaw(&mut self, angle: Radians, rotate_around_target: bool) {
         let target = (self.target - self.position) // view vector
             .rotate_by_axis_angle(self.up(), angle); // Rotate view vector around up axis
@@ -104,7 +166,6 @@ impl Camera {
     ///  - lockView prevents camera overrotation (aka "somersaults")
     ///  - rotateAroundTarget defines if rotation is around target or around its position
     ///  - rotateUp rotates the up direction as well (typically only usefull in CAMERA_FREE)
-    /// NOTE: angle must be provided in radians
     pub fn pitch(&mut self, mut angle: Radians, lock_view: bool, rotate_around_target: bool, rotate_up: bool) {
         // Up direction
         let up = self.up();
@@ -117,12 +178,12 @@ impl Camera {
             // to allow only viewing straight up or down.
 
             // Clamp view up
-            let max_angle_up = up.angle(target_position) - 0.001;
+            let max_angle_up = up.angle(target_position) - Radians(0.001);
 
             // Clamp view down
-            let max_angle_down = -(-up).angle(target_position) + 0.001;
+            let max_angle_down = -(-up).angle(target_position) + Radians(0.001);
 
-            angle = angle.clamp(max_angle_down, max_angle_up)
+            angle = angle.clamp(max_angle_down, max_angle_up);
         }
 
         // Rotation axis
@@ -147,17 +208,27 @@ impl Camera {
 
     // Rotates the camera around its forward vector
     // Roll is "turning your head sideways to the left or right"
-    // Note: angle must be provided in radians
     pub fn roll(&mut self, angle: Radians) {
         todo!()
     }
 
+    /// Get camera transform matrix (view matrix)
     pub fn view_matrix(&self) -> Matrix {
-        todo!()
+        Matrix::look_at(self.position, self.target, self.up)
     }
 
+    /// Get camera projection matrix for a viewport aspect ratio (width/height)
+    /// NOTE: In orthographic projection `fovy` is the near plane height
     pub fn projection_matrix(&self, aspect: f32) -> Matrix {
-        todo!()
+        let (near, far) = (f64::from(Self::CULL_DISTANCE_NEAR), f64::from(Self::CULL_DISTANCE_FAR));
+        match self.projection {
+            CameraProjection::Perspective => Matrix::perspective(f64::from(self.fovy.to_radians().value()), f64::from(aspect), near, far),
+            CameraProjection::Orthographic => {
+                let top = f64::from(self.fovy.value())/2.0;
+                let right = top*f64::from(aspect);
+                Matrix::ortho(-right, right, -top, top, near, far)
+            }
+        }
     }
 
     pub const CULL_DISTANCE_NEAR: f32 =    0.01;
@@ -167,8 +238,124 @@ impl Camera {
     pub const PAN_SPEED:  Ratio<f32, Second> = Ratio(0.2, Second);
 
     /// Camera orbital speed in CAMERA_ORBITAL mode
-    pub const ORBITAL_SPEED:  Ratio<Radians, Second> = Ratio(0.5 , Second);
-    pub const ROTATION_SPEED: Ratio<Radians, Second> = Ratio(0.03, Second);
+    pub const ORBITAL_SPEED:  Ratio<Radians, Second> = Ratio(Radians(0.5 ), Second);
+    pub const ROTATION_SPEED: Ratio<Radians, Second> = Ratio(Radians(0.03), Second);
 
     pub const MOUSE_MOVE_SENSITIVITY: f32 = 0.003;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn assert_near(a: Vector2, b: Vector2) {
+        assert!((a - b).magnitude() < 1.0e-3, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_camera_2d_world_to_screen() {
+        let point = Vector2::new(12.5, -40.0);
+        assert_near(get_world_to_screen_2d(point, &Camera2D::default()), point);
+
+        let camera = Camera2D { offset: Vector2::new(400.0, 300.0), target: Vector2::new(100.0, 100.0), rotation: Degrees::ZERO, zoom: 2.0 };
+        assert_near(get_world_to_screen_2d(Vector2::new(110.0, 95.0), &camera), Vector2::new(420.0, 290.0));
+
+        // Rotation is clockwise on screen (y down), around the target
+        let camera = Camera2D { rotation: Degrees(90.0), ..camera };
+        assert_near(get_world_to_screen_2d(Vector2::new(110.0, 100.0), &camera), Vector2::new(400.0, 320.0));
+
+        // Target is always at offset on screen
+        for rotation in [0.0, 45.0, -170.0] {
+            for zoom in [0.5, 1.0, 3.0] {
+                let camera = Camera2D { rotation: Degrees(rotation), zoom, ..camera };
+                assert_near(get_world_to_screen_2d(camera.target, &camera), camera.offset);
+            }
+        }
+    }
+
+    #[test]
+    fn test_camera_2d_round_trip() {
+        let points = [Vector2::ZERO, Vector2::new(640.0, 360.0), Vector2::new(-1500.0, 20.0), Vector2::new(3.25, -999.0)];
+        let cameras = [
+            Camera2D::default(),
+            Camera2D { offset: Vector2::new(400.0, 300.0), target: Vector2::new(100.0, 100.0), rotation: Degrees(30.0), zoom: 2.0 },
+            Camera2D { offset: Vector2::new(-50.0, 10.0), target: Vector2::new(-800.0, 64.0), rotation: Degrees(-135.0), zoom: 0.25 },
+        ];
+        for camera in cameras {
+            for point in points {
+                let screen = get_world_to_screen_2d(point, &camera);
+                assert_near(get_screen_to_world_2d(screen, &camera), point);
+                let world = get_screen_to_world_2d(point, &camera);
+                assert_near(get_world_to_screen_2d(world, &camera), point);
+            }
+        }
+
+        // Degenerate camera can't be inverted
+        let camera = Camera2D { zoom: 0.0, ..Camera2D::default() };
+        assert_eq!(get_screen_to_world_2d(Vector2::new(5.0, 6.0), &camera), Vector2::new(5.0, 6.0));
+    }
+
+    fn camera_3d() -> Camera3D {
+        Camera3D {
+            position: Vector3::new(0.0, 0.0, 10.0),
+            target: Vector3::ZERO,
+            up: Vector3::UNIT_Y,
+            fovy: Degrees(45.0),
+            projection: CameraProjection::Perspective,
+        }
+    }
+
+    #[track_caller]
+    fn assert_vector3_near(a: Vector3, b: Vector3) {
+        assert!((a - b).magnitude() < 1.0e-3, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_camera_yaw() {
+        // Turning left around the position moves the target to the left (-x)
+        let mut camera = camera_3d();
+        camera.yaw(Radians::FRAC_PI_2, false);
+        assert_vector3_near(camera.position, Vector3::new(0.0, 0.0, 10.0));
+        assert_vector3_near(camera.target, Vector3::new(-10.0, 0.0, 10.0));
+
+        // Orbiting keeps the target and the distance to it
+        let mut camera = camera_3d();
+        camera.yaw(Radians(0.3), true);
+        camera.yaw(Radians(-1.1), true);
+        assert_vector3_near(camera.target, Vector3::ZERO);
+        assert!((camera.position.magnitude() - 10.0).abs() < 1.0e-3);
+        assert_vector3_near(camera.position, Vector3::new(10.0*(-0.8f32).sin(), 0.0, 10.0*(-0.8f32).cos()));
+        assert_vector3_near(camera.up, Vector3::UNIT_Y);
+    }
+
+    #[test]
+    fn test_camera_pitch_lock_view() {
+        // Pitching up past vertical stops just short of it instead of flipping over
+        let mut camera = camera_3d();
+        camera.pitch(Radians::PI, true, false, false);
+        let forward = camera.forward();
+        assert!(forward.y > 0.999 && forward.z < 0.0, "{forward:?}");
+        assert_vector3_near(camera.position, Vector3::new(0.0, 0.0, 10.0));
+        camera.pitch(Radians(0.5), true, false, false);
+        assert_vector3_near(camera.forward(), forward);
+
+        // Same looking down, from straight up
+        camera.pitch(-Radians::TAU, true, false, false);
+        let forward = camera.forward();
+        assert!(forward.y < -0.999 && forward.z < 0.0, "{forward:?}");
+
+        // Orbiting keeps the target, up is untouched unless asked
+        let mut camera = camera_3d();
+        camera.pitch(Radians(-2.0), true, true, false);
+        assert_vector3_near(camera.target, Vector3::ZERO);
+        assert!(camera.position.y > 9.99 && camera.position.z > 0.0, "{:?}", camera.position);
+        assert_vector3_near(camera.up, Vector3::UNIT_Y);
+
+        // Without lock the view somersaults
+        let mut camera = camera_3d();
+        camera.pitch(Radians::PI, false, false, true);
+        assert_vector3_near(camera.forward(), Vector3::UNIT_Z);
+        assert_vector3_near(camera.up, -Vector3::UNIT_Y);
+    }
+}
@@ -0,0 +1,214 @@
+//! Editor overlays: world-space grids, rulers and crosshairs
+
+use crate::prelude::*;
+
+/// Maximum number of grid lines per axis, spacing is coarsened above it
+pub const GRID_MAX_LINES: usize = 256;
+
+/// Window edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Grid lines intersecting the view, computed by [`visible_grid_lines`]
+/// NOTE: Line `i` is at world coordinate `i * spacing`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridLines {
+    /// World distance between consecutive lines
+    pub spacing: Units,
+    /// Every line multiple of `major_every` is a major line
+    pub major_every: i64,
+    /// First and last vertical line indices (world x)
+    pub x: (i64, i64),
+    /// First and last horizontal line indices (world y)
+    pub y: (i64, i64),
+}
+
+impl GridLines {
+    /// Number of lines, both axes
+    #[must_use]
+    pub fn count(&self) -> usize {
+        let count = |(first, last): (i64, i64)| usize::try_from(last - first + 1).unwrap_or(0);
+        count(self.x) + count(self.y)
+    }
+
+    /// Check if line index is a major line
+    #[inline]
+    #[must_use]
+    pub fn is_major(&self, index: i64) -> bool {
+        index.rem_euclid(self.major_every) == 0
+    }
+}
+
+/// Get world space line thickness drawn with a constant width in pixels whatever the camera zoom
+#[inline]
+#[must_use]
+pub fn constant_pixel_thickness(camera: &Camera2D, thickness: Pixels) -> Units {
+    thickness / camera.zoom
+}
+
+/// Compute the world-space grid lines visible on screen
+/// NOTE: `spacing` is the major lines spacing in world units, `subdivisions` minor lines divide it.
+/// When there would be more than [`GRID_MAX_LINES`] lines per axis, minor lines are merged
+/// (spacing multiplied by the subdivision factor) until it fits
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub fn visible_grid_lines(camera: &Camera2D, screen_size: Vector2, spacing: Units, subdivisions: usize) -> Option<GridLines> {
+    if !(spacing > 0.0 && camera.zoom > 0.0 && spacing.is_finite()) {
+        return None;
+    }
+
+    // View bounds in world, rotated views are covered by their bounding box
+    let corners = [
+        Vector2::ZERO,
+        Vector2::new(screen_size.x, 0.0),
+        Vector2::new(0.0, screen_size.y),
+        screen_size,
+    ].map(|corner| get_screen_to_world_2d(corner, camera));
+    let min = corners.iter().fold(Vector2::new(f32::MAX, f32::MAX), |min, c| Vector2::new(min.x.min(c.x), min.y.min(c.y)));
+    let max = corners.iter().fold(Vector2::new(f32::MIN, f32::MIN), |max, c| Vector2::new(max.x.max(c.x), max.y.max(c.y)));
+    if !(min.x.is_finite() && min.y.is_finite() && max.x.is_finite() && max.y.is_finite()) {
+        return None;
+    }
+
+    let factor = subdivisions.max(1) as f64;
+    let mut major_every = i64::try_from(subdivisions.max(1)).unwrap_or(i64::MAX);
+    let mut line_spacing = f64::from(spacing) / factor;
+    let extent = f64::from((max.x - min.x).max(max.y - min.y));
+    while extent / line_spacing >= GRID_MAX_LINES as f64 {
+        // Coarsen: drop minor lines first, then skip major lines by the subdivision factor
+        line_spacing *= factor.max(2.0);
+        major_every = (major_every / factor.max(2.0) as i64).max(1);
+    }
+
+    let range = |min: f32, max: f32| ((f64::from(min) / line_spacing).ceil() as i64, (f64::from(max) / line_spacing).floor() as i64);
+    Some(GridLines {
+        spacing: line_spacing as f32,
+        major_every,
+        x: range(min.x, max.x),
+        y: range(min.y, max.y),
+    })
+}
+
+/// Ruler tick, computed by [`ruler_ticks`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RulerTick {
+    /// Position along the edge in pixels, from the left or top of the screen
+    pub offset: Pixels,
+    /// World coordinate of the tick (x for top/bottom edges, y for left/right edges)
+    pub value: Units,
+    /// Major ticks should be labeled with their value
+    pub major: bool,
+}
+
+/// Compute ruler ticks along a screen edge, where world grid lines cross it
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn ruler_ticks(camera: &Camera2D, screen_size: Vector2, edge: Edge, spacing: Units, subdivisions: usize) -> Vec<RulerTick> {
+    let Some(lines) = visible_grid_lines(camera, screen_size, spacing, subdivisions) else { return Vec::new() };
+
+    let (start, end, length) = match edge {
+        Edge::Top    => (Vector2::ZERO, Vector2::new(screen_size.x, 0.0), screen_size.x),
+        Edge::Bottom => (Vector2::new(0.0, screen_size.y), screen_size, screen_size.x),
+        Edge::Left   => (Vector2::ZERO, Vector2::new(0.0, screen_size.y), screen_size.y),
+        Edge::Right  => (Vector2::new(screen_size.x, 0.0), screen_size, screen_size.y),
+    };
+    let (start, end) = (get_screen_to_world_2d(start, camera), get_screen_to_world_2d(end, camera));
+    let (from, to, range) = match edge {
+        Edge::Top | Edge::Bottom => (start.x, end.x, lines.x),
+        Edge::Left | Edge::Right => (start.y, end.y, lines.y),
+    };
+    if (to - from).abs() <= f32::EPSILON {
+        return Vec::new();
+    }
+
+    (range.0..=range.1)
+        .filter_map(|index| {
+            let value = index as f32 * lines.spacing;
+            let t = (value - from) / (to - from);
+            (0.0..=1.0).contains(&t).then_some(RulerTick {
+                offset: t * length,
+                value,
+                major: lines.is_major(index),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(offset: Vector2, zoom: f32) -> Camera2D {
+        Camera2D { offset, target: Vector2::ZERO, rotation: Degrees::ZERO, zoom }
+    }
+
+    const SCREEN: Vector2 = Vector2::new(100.0, 50.0);
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_visible_grid_lines() {
+        let lines = |offset, zoom, subdivisions| visible_grid_lines(&camera(offset, zoom), SCREEN, 10.0, subdivisions).unwrap();
+        let grid = |spacing, major_every, x, y| GridLines { spacing, major_every, x, y };
+
+        // Lines at world multiples of the spacing, subdivisions add minor lines
+        assert_eq!(lines(Vector2::ZERO, 1.0, 1), grid(10.0, 1, (0, 10), (0, 5)));
+        assert_eq!(lines(Vector2::ZERO, 1.0, 5), grid(2.0, 5, (0, 50), (0, 25)));
+        assert_eq!(lines(Vector2::new(50.0, 25.0), 1.0, 1), grid(10.0, 1, (-5, 5), (-2, 2)));
+        assert!(lines(Vector2::ZERO, 1.0, 5).is_major(-10) && !lines(Vector2::ZERO, 1.0, 5).is_major(3));
+
+        // Zooming in keeps the spacing, zooming out coarsens minor lines first, then major ones
+        assert_eq!(lines(Vector2::ZERO, 2.0, 5), grid(2.0, 5, (0, 25), (0, 12)));
+        assert_eq!(lines(Vector2::ZERO, 0.25, 5), grid(2.0, 5, (0, 200), (0, 100)));
+        assert_eq!(lines(Vector2::ZERO, 0.125, 5), grid(10.0, 1, (0, 80), (0, 40)));
+        assert_eq!(lines(Vector2::ZERO, 1.0/64.0, 5), grid(50.0, 1, (0, 128), (0, 64)));
+        for zoom in [1.0, 0.5, 0.125, 1.0/64.0, 1.0/1024.0] {
+            let lines = lines(Vector2::ZERO, zoom, 4);
+            let max = i64::try_from(GRID_MAX_LINES).unwrap();
+            assert!(lines.x.1 - lines.x.0 < max && lines.y.1 - lines.y.0 < max, "{zoom}");
+        }
+
+        // Rotated views are covered by their bounding box
+        let rotated = Camera2D { rotation: Degrees(90.0), ..camera(Vector2::ZERO, 1.0) };
+        let lines = visible_grid_lines(&rotated, SCREEN, 10.0, 1).unwrap();
+        assert_eq!((lines.x, lines.y), ((0, 5), (-10, 0)));
+        assert_eq!(lines.count(), 6 + 11);
+
+        // Degenerate inputs
+        assert!(visible_grid_lines(&camera(Vector2::ZERO, 0.0), SCREEN, 10.0, 1).is_none());
+        assert!(visible_grid_lines(&camera(Vector2::ZERO, 1.0), SCREEN, 0.0, 1).is_none());
+        assert!(visible_grid_lines(&camera(Vector2::ZERO, 1.0), SCREEN, f32::NAN, 1).is_none());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_ruler_ticks() {
+        let ticks = |zoom, edge| ruler_ticks(&camera(Vector2::ZERO, zoom), SCREEN, edge, 10.0, 2);
+        let pixel_spacing = |ticks: &[RulerTick]| ticks[1].offset - ticks[0].offset;
+
+        // Tick spacing in pixels follows the zoom, ticks stay on screen
+        for (zoom, count, spacing) in [(1.0, 21, 5.0), (2.0, 11, 10.0), (0.5, 41, 2.5)] {
+            let ticks = ticks(zoom, Edge::Top);
+            assert_eq!(ticks.len(), count, "{zoom}");
+            assert!((pixel_spacing(&ticks) - spacing).abs() < 1.0e-4, "{zoom}");
+            assert!(ticks.iter().all(|tick| (0.0..=SCREEN.x).contains(&tick.offset) && (tick.offset - tick.value * zoom).abs() < 1.0e-4));
+        }
+
+        // Zooming out past the line limit coarsens ticks
+        let far = ticks(1.0/64.0, Edge::Top);
+        assert_eq!(far.len(), 161);
+        assert!((pixel_spacing(&far) - 0.625).abs() < 1.0e-4);
+
+        // Major ticks every subdivision, vertical edges read world y
+        let left = ticks(1.0, Edge::Left);
+        assert_eq!(left.len(), 11);
+        assert_eq!(left.iter().map(|tick| tick.major).collect::<Vec<_>>(), (0..11).map(|i| i % 2 == 0).collect::<Vec<_>>());
+        assert_eq!((left[3].value, left[3].major), (15.0, false));
+        assert!((left[3].offset - 15.0).abs() < 1.0e-4);
+        assert_eq!(ticks(1.0, Edge::Bottom), ticks(1.0, Edge::Top));
+    }
+}
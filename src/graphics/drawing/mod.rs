@@ -0,0 +1,400 @@
+use std::ops::{Deref, DerefMut};
+use crate::{prelude::{Camera2D, Camera3D, Color, ConfigFlags, Core, RenderTexture, Size, VrStereoConfig}, rlgl::{FlushReason, MatrixMode}, tracelog};
+
+pub use crate::rlgl::BlendMode;
+
+pub mod grid;
+
+/// Set background color (framebuffer clear color)
+/// NOTE: Clears the active framebuffer, the screen or the render texture in texture mode
+pub fn clear_background(core: &mut Core, color: Color) {
+    core.rlgl.clear_color(color.r, color.g, color.b, color.a); // Set clear color
+    core.rlgl.clear_screen_buffers(); // Clear current framebuffers
+}
+
+/// Begin 2D mode with custom camera (2D)
+/// NOTE: 2D modes do not nest, `end_mode_2d()` resets the modelview to screen scaling
+pub fn begin_mode_2d(core: &mut Core, camera: &Camera2D) {
+    core.rlgl.draw_render_batch(FlushReason::MatrixChange); // Update and draw internal render batch
+
+    core.rlgl.load_identity(); // Reset current matrix (modelview)
+
+    // Apply 2d camera transformation to modelview
+    core.rlgl.mult_matrix(camera.matrix());
+
+    // Apply screen scaling if required
+    core.rlgl.mult_matrix(core.window.screen_scale);
+}
+
+/// End 2D mode with custom camera
+pub fn end_mode_2d(core: &mut Core) {
+    core.rlgl.draw_render_batch(FlushReason::MatrixChange); // Update and draw internal render batch
+
+    core.rlgl.load_identity(); // Reset current matrix (modelview)
+    core.rlgl.mult_matrix(core.window.screen_scale); // Apply screen scaling if required
+}
+
+/// Begin 3D mode with custom camera (3D), depth testing is enabled
+/// NOTE: Projection is pushed and restored by `end_mode_3d()`, 3D modes do not nest
+#[allow(clippy::cast_precision_loss)]
+pub fn begin_mode_3d(core: &mut Core, camera: &Camera3D) {
+    core.rlgl.draw_render_batch(FlushReason::MatrixChange); // Update and draw internal render batch
+
+    core.rlgl.matrix_mode(MatrixMode::Projection); // Switch to projection matrix
+    core.rlgl.push_matrix(); // Save previous matrix, which contains the settings for the 2d ortho projection
+    core.rlgl.load_identity(); // Reset current matrix (projection)
+
+    let fbo = core.window.current_fbo;
+    let aspect = fbo.width as f32/fbo.height as f32;
+    core.rlgl.mult_matrix(camera.projection_matrix(aspect)); // Setup perspective or orthographic projection
+
+    core.rlgl.matrix_mode(MatrixMode::Modelview); // Switch back to modelview matrix
+    core.rlgl.load_identity(); // Reset current matrix (modelview)
+
+    core.rlgl.mult_matrix(camera.view_matrix()); // Multiply modelview matrix by view matrix (camera)
+
+    core.rlgl.set_depth_test(true); // Enable DEPTH_TEST for 3D
+}
+
+/// End 3D mode and returns to default 2D orthographic mode
+pub fn end_mode_3d(core: &mut Core) {
+    core.rlgl.draw_render_batch(FlushReason::MatrixChange); // Update and draw internal render batch
+
+    core.rlgl.matrix_mode(MatrixMode::Projection); // Switch to projection matrix
+    core.rlgl.pop_matrix(); // Restore previous matrix (projection) from matrix stack
+
+    core.rlgl.matrix_mode(MatrixMode::Modelview); // Switch back to modelview matrix
+    core.rlgl.load_identity(); // Reset current matrix (modelview)
+
+    core.rlgl.mult_matrix(core.window.screen_scale); // Apply screen scaling if required
+
+    core.rlgl.set_depth_test(false); // Disable DEPTH_TEST for 2D
+}
+
+/// Begin drawing to render texture
+/// NOTE: Texture modes do not nest, beginning a new one replaces the current target (logged)
+/// NOTE: OpenGL textures origin is bottom-left, draw the render texture with a negative
+/// source height to see it the right way up
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap, clippy::cast_precision_loss)]
+pub fn begin_texture_mode(core: &mut Core, target: &RenderTexture) {
+    if let Some(current) = core.window.render_target {
+        tracelog!(Warning, "FBO: [ID {current}] Texture mode already active, nested texture modes are not supported, replaced by [ID {}]", target.id);
+    }
+
+    core.rlgl.draw_render_batch(FlushReason::FramebufferChange); // Update and draw internal render batch
+
+    core.rlgl.enable_framebuffer(target.id); // Enable render target
+
+    // Set viewport and RLGL internal framebuffer size
+    let (width, height) = (target.texture.width, target.texture.height);
+    core.rlgl.viewport(0, 0, width as i32, height as i32);
+    core.rlgl.set_framebuffer_size(width as i32, height as i32);
+
+    core.rlgl.matrix_mode(MatrixMode::Projection); // Switch to projection matrix
+    core.rlgl.load_identity(); // Reset current matrix (projection)
+
+    // Set orthographic projection to current framebuffer size
+    // NOTE: Configured top-left corner as (0, 0)
+    core.rlgl.ortho(0.0, width as f64, height as f64, 0.0, 0.0, 1.0);
+
+    core.rlgl.matrix_mode(MatrixMode::Modelview); // Switch back to modelview matrix
+    core.rlgl.load_identity(); // Reset current matrix (modelview)
+
+    // Setup current width/height for proper aspect ratio
+    // calculation when using begin_mode_3d()
+    core.window.current_fbo = Size { width: width as u32, height: height as u32 };
+    core.window.render_target = Some(target.id);
+}
+
+/// End drawing to render texture
+/// NOTE: Screen viewport and projection are restored, modelview is reset to screen scaling (like `begin_drawing()`)
+pub fn end_texture_mode(core: &mut Core) {
+    if core.window.render_target.is_none() {
+        tracelog!(Warning, "FBO: Texture mode not active, nothing to end");
+        return;
+    }
+
+    core.rlgl.draw_render_batch(FlushReason::FramebufferChange); // Update and draw internal render batch
+
+    core.rlgl.disable_framebuffer(); // Disable render target (fbo)
+
+    // Set viewport to default framebuffer size
+    core.setup_viewport(core.window.render.width, core.window.render.height);
+
+    // Go back to the modelview state from begin_drawing() since we are back to the default FBO
+    core.rlgl.mult_matrix(core.window.screen_scale); // Apply screen scaling if required
+
+    // Reset current fbo to screen size
+    core.window.current_fbo = core.window.render;
+    core.window.render_target = None;
+}
+
+/// Begin stereo rendering (requires `begin_mode_3d()` and `begin_texture_mode()` for lens distortion)
+/// NOTE: Each eye is drawn on half of the current framebuffer with its own projection and view offset
+pub fn begin_vr_stereo_mode(core: &mut Core, config: &VrStereoConfig) {
+    core.rlgl.set_stereo_render(true);
+
+    // Set stereo render matrices
+    core.rlgl.set_matrix_projection_stereo(config.projection);
+    core.rlgl.set_matrix_view_offset_stereo(config.view_offset);
+}
+
+/// End stereo rendering
+pub fn end_vr_stereo_mode(core: &mut Core) {
+    core.rlgl.set_stereo_render(false);
+}
+
+/// Begin blending mode (alpha, additive, multiplied, subtract, custom)
+/// NOTE: Pending draws are flushed when the mode changes
+pub fn begin_blend_mode(core: &mut Core, mode: BlendMode) {
+    core.rlgl.set_blend_mode(mode);
+}
+
+/// End blending mode (reset to default: alpha blending)
+pub fn end_blend_mode(core: &mut Core) {
+    core.rlgl.set_blend_mode(BlendMode::Alpha);
+}
+
+/// Set blending factors and equation for `BlendMode::Custom` (using OpenGL factors)
+/// NOTE: Factors and equations are defined in [`rlgl::gl`](crate::rlgl::gl), i.e. `GL_SRC_ALPHA`
+pub fn set_blend_factors(core: &mut Core, src_factor: u32, dst_factor: u32, equation: u32) {
+    core.rlgl.set_blend_factors(src_factor, dst_factor, equation);
+}
+
+/// Set separate RGB and alpha blending factors and equations for `BlendMode::CustomSeparate` (using OpenGL factors)
+pub fn set_blend_factors_separate(core: &mut Core, src_rgb: u32, dst_rgb: u32, src_alpha: u32, dst_alpha: u32, equation_rgb: u32, equation_alpha: u32) {
+    core.rlgl.set_blend_factors_separate(src_rgb, dst_rgb, src_alpha, dst_alpha, equation_rgb, equation_alpha);
+}
+
+/// Scissor mode handle, scissor mode ends when dropped
+/// NOTE: Derefs to [`Core`], drawing continues through it
+pub struct ScissorMode<'c, 'a> {
+    core: &'c mut Core<'a>,
+}
+
+impl<'a> Deref for ScissorMode<'_, 'a> {
+    type Target = Core<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.core
+    }
+}
+
+impl DerefMut for ScissorMode<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.core
+    }
+}
+
+impl Drop for ScissorMode<'_, '_> {
+    fn drop(&mut self) {
+        end_scissor_mode(self.core);
+    }
+}
+
+/// Begin scissor mode (define screen area for following drawing), scissor mode ends when the returned handle is dropped
+/// NOTE: Area is defined in screen coordinates (top-left origin), nested scissor modes replace
+/// the area and ending any of them disables scissor test (full viewport)
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap, clippy::cast_precision_loss)]
+pub fn begin_scissor_mode<'c, 'a>(core: &'c mut Core<'a>, x: i32, y: i32, width: i32, height: i32) -> ScissorMode<'c, 'a> {
+    core.rlgl.draw_render_batch(FlushReason::ScissorModeChange); // Update and draw internal render batch
+    core.rlgl.enable_scissor_test();
+
+    // NOTE: OpenGL scissor area origin is bottom-left, framebuffer height is required to flip it
+    let window = &core.window;
+    let fbo_height = if window.render_target.is_some() { window.current_fbo.height } else { window.render.height } as i32;
+    if window.render_target.is_none() && window.flags.contains(ConfigFlags::WindowHighdpi) {
+        let scale = window.dpi_scale;
+        core.rlgl.scissor(
+            (x as f32*scale.x) as i32,
+            (fbo_height as f32 - (y + height) as f32*scale.y) as i32,
+            (width as f32*scale.x) as i32,
+            (height as f32*scale.y) as i32,
+        );
+    } else {
+        core.rlgl.scissor(x, fbo_height - (y + height), width, height);
+    }
+
+    ScissorMode { core }
+}
+
+/// End scissor mode
+fn end_scissor_mode(core: &mut Core) {
+    core.rlgl.draw_render_batch(FlushReason::ScissorModeChange); // Update and draw internal render batch
+    core.rlgl.disable_scissor_test();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::{draw_pixel, load_vr_stereo_config, CameraProjection, Degrees, Magnitude, Matrix, MatrixTransform, Vector2, Vector3, Vector4, VrDeviceInfo}, rlgl::{gl::{GL_DEPTH_TEST, GL_DST_COLOR, GL_FUNC_ADD, GL_FUNC_SUBTRACT, GL_MAX, GL_ONE, GL_ONE_MINUS_SRC_ALPHA, GL_SRC_ALPHA, GL_ZERO}, tests::{recording_rlgl, CallLog, GlCall}}};
+
+    /// Blend state calls recorded since last call
+    fn blend_calls(calls: &CallLog) -> Vec<GlCall> {
+        let blend = calls.borrow().iter()
+            .filter(|call| matches!(call, GlCall::BlendFunc { .. } | GlCall::BlendFuncSeparate(_) | GlCall::BlendEquation(_) | GlCall::BlendEquationSeparate { .. }))
+            .cloned()
+            .collect();
+        calls.borrow_mut().clear();
+        blend
+    }
+
+    #[test]
+    fn test_blend_mode() {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+
+        // Pending draws are flushed with the previous mode before switching
+        draw_pixel(&mut core, Vector2::ZERO, Color::RED);
+        begin_blend_mode(&mut core, BlendMode::Additive);
+        let position = |calls: &CallLog, f: fn(&GlCall) -> bool| calls.borrow().iter().position(f);
+        let draw = position(&calls, |call| matches!(call, GlCall::DrawArrays { .. } | GlCall::DrawElements { .. }));
+        let blend = position(&calls, |call| matches!(call, GlCall::BlendFunc { .. }));
+        assert!(draw.is_some() && draw < blend, "pixel drawn before the switch");
+        assert_eq!(blend_calls(&calls), [GlCall::BlendFunc { src: GL_SRC_ALPHA, dst: GL_ONE }, GlCall::BlendEquation(GL_FUNC_ADD)]);
+
+        // Same mode again is a no-op, nothing is flushed
+        draw_pixel(&mut core, Vector2::ZERO, Color::RED);
+        begin_blend_mode(&mut core, BlendMode::Additive);
+        assert!(calls.borrow().is_empty());
+        end_blend_mode(&mut core);
+        assert_eq!(blend_calls(&calls), [GlCall::BlendFunc { src: GL_SRC_ALPHA, dst: GL_ONE_MINUS_SRC_ALPHA }, GlCall::BlendEquation(GL_FUNC_ADD)]);
+
+        begin_blend_mode(&mut core, BlendMode::Multiplied);
+        assert_eq!(blend_calls(&calls)[0], GlCall::BlendFunc { src: GL_DST_COLOR, dst: GL_ONE_MINUS_SRC_ALPHA });
+        begin_blend_mode(&mut core, BlendMode::SubtractColors);
+        assert_eq!(blend_calls(&calls), [GlCall::BlendFunc { src: GL_ONE, dst: GL_ONE }, GlCall::BlendEquation(GL_FUNC_SUBTRACT)]);
+    }
+
+    #[test]
+    fn test_custom_blend_factors() {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+
+        // Factors are applied when the custom mode begins
+        set_blend_factors(&mut core, GL_ONE, GL_ZERO, GL_MAX);
+        assert!(blend_calls(&calls).is_empty());
+        begin_blend_mode(&mut core, BlendMode::Custom);
+        assert_eq!(blend_calls(&calls), [GlCall::BlendFunc { src: GL_ONE, dst: GL_ZERO }, GlCall::BlendEquation(GL_MAX)]);
+
+        // Changed factors are applied beginning the same custom mode again, unchanged ones aren't
+        begin_blend_mode(&mut core, BlendMode::Custom);
+        assert!(blend_calls(&calls).is_empty());
+        set_blend_factors(&mut core, GL_ONE, GL_ZERO, GL_MAX);
+        begin_blend_mode(&mut core, BlendMode::Custom);
+        assert!(blend_calls(&calls).is_empty());
+        set_blend_factors(&mut core, GL_SRC_ALPHA, GL_ONE, GL_FUNC_ADD);
+        begin_blend_mode(&mut core, BlendMode::Custom);
+        assert_eq!(blend_calls(&calls), [GlCall::BlendFunc { src: GL_SRC_ALPHA, dst: GL_ONE }, GlCall::BlendEquation(GL_FUNC_ADD)]);
+
+        set_blend_factors_separate(&mut core, GL_SRC_ALPHA, GL_ONE_MINUS_SRC_ALPHA, GL_ONE, GL_ONE, GL_FUNC_ADD, GL_MAX);
+        begin_blend_mode(&mut core, BlendMode::CustomSeparate);
+        assert_eq!(blend_calls(&calls), [
+            GlCall::BlendFuncSeparate([GL_SRC_ALPHA, GL_ONE_MINUS_SRC_ALPHA, GL_ONE, GL_ONE]),
+            GlCall::BlendEquationSeparate { rgb: GL_FUNC_ADD, alpha: GL_MAX },
+        ]);
+    }
+
+    /// Depth test toggles recorded since last call
+    fn depth_test_calls(calls: &CallLog) -> Vec<GlCall> {
+        let depth = calls.borrow().iter()
+            .filter(|call| matches!(call, GlCall::Enable(GL_DEPTH_TEST) | GlCall::Disable(GL_DEPTH_TEST)))
+            .cloned()
+            .collect();
+        calls.borrow_mut().clear();
+        depth
+    }
+
+    /// Check a point lands on `expected` x/y normalized device coordinates
+    #[track_caller]
+    fn assert_ndc_xy(mvp: &Matrix, point: Vector3, expected: Vector2) {
+        let clip = Vector4::new(point.x, point.y, point.z, 1.0).transform(mvp);
+        let ndc = Vector2::new(clip.x/clip.w, clip.y/clip.w);
+        assert!((ndc - expected).magnitude() < 1.0e-4, "{point:?}: {ndc:?} != {expected:?}");
+    }
+
+    #[test]
+    fn test_mode_3d() {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+        core.window.current_fbo = Size { width: 800, height: 400 };
+        core.window.screen_scale = Matrix::scale(2.0, 2.0, 1.0);
+        let ortho_2d = Matrix::ortho(0.0, 800.0, 400.0, 0.0, 0.0, 1.0);
+        core.rlgl.matrix_mode(MatrixMode::Projection);
+        core.rlgl.load_identity();
+        core.rlgl.mult_matrix(ortho_2d);
+        core.rlgl.matrix_mode(MatrixMode::Modelview);
+
+        // Camera matrices are loaded, projection uses the current framebuffer aspect ratio
+        let camera = Camera3D {
+            position: Vector3::new(0.0, 0.0, 10.0),
+            target: Vector3::ZERO,
+            up: Vector3::new(0.0, 1.0, 0.0),
+            fovy: Degrees(90.0),
+            projection: CameraProjection::Perspective,
+        };
+        begin_mode_3d(&mut core, &camera);
+        assert_eq!(*core.rlgl.get_matrix_projection(), camera.projection_matrix(2.0));
+        assert_eq!(*core.rlgl.get_matrix_modelview(), camera.view_matrix());
+        assert_eq!(depth_test_calls(&calls), [GlCall::Enable(GL_DEPTH_TEST)]);
+
+        // 90 degrees vertical field of view, twice as wide horizontally: frustum edges at the target distance
+        let mvp = *core.rlgl.get_matrix_modelview() * *core.rlgl.get_matrix_projection();
+        assert_ndc_xy(&mvp, Vector3::ZERO, Vector2::ZERO);
+        assert_ndc_xy(&mvp, Vector3::new(20.0, 10.0, 0.0), Vector2::new(1.0, 1.0));
+        assert_ndc_xy(&mvp, Vector3::new(-10.0, 2.5, 5.0), Vector2::new(-1.0, 0.5));
+
+        // 2d projection and screen scaling are restored
+        end_mode_3d(&mut core);
+        assert_eq!(*core.rlgl.get_matrix_projection(), ortho_2d);
+        assert_eq!(*core.rlgl.get_matrix_modelview(), Matrix::scale(2.0, 2.0, 1.0));
+        assert_eq!(depth_test_calls(&calls), [GlCall::Disable(GL_DEPTH_TEST)]);
+
+        // Orthographic `fovy` is the view height
+        let camera = Camera3D { fovy: Degrees(8.0), projection: CameraProjection::Orthographic, ..camera };
+        begin_mode_3d(&mut core, &camera);
+        let mvp = *core.rlgl.get_matrix_modelview() * *core.rlgl.get_matrix_projection();
+        assert_ndc_xy(&mvp, Vector3::new(8.0, 4.0, 0.0), Vector2::new(1.0, 1.0));
+        assert_ndc_xy(&mvp, Vector3::new(-8.0, -4.0, 5.0), Vector2::new(-1.0, -1.0));
+        end_mode_3d(&mut core);
+        assert_eq!(*core.rlgl.get_matrix_projection(), ortho_2d);
+    }
+
+    #[test]
+    fn test_vr_stereo_mode() {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+        core.rlgl.set_framebuffer_size(800, 400);
+        let config = load_vr_stereo_config(&VrDeviceInfo::default());
+        let (modelview, projection) = (*core.rlgl.get_matrix_modelview(), *core.rlgl.get_matrix_projection());
+        let frame = |core: &mut Core, calls: &CallLog| {
+            calls.borrow_mut().clear();
+            draw_pixel(core, Vector2::ZERO, Color::RED);
+            core.rlgl.draw_render_batch(FlushReason::Explicit);
+            calls.borrow().iter().filter_map(|call| match *call {
+                GlCall::Viewport { x, y, width, height } => Some(GlCall::Viewport { x, y, width, height }),
+                GlCall::UniformMatrix4(mvp) => Some(GlCall::UniformMatrix4(mvp)),
+                _ => None,
+            }).collect::<Vec<_>>()
+        };
+
+        // Batch is drawn once per eye, on each half of the framebuffer, with the eye matrices
+        begin_vr_stereo_mode(&mut core, &config);
+        assert_eq!(frame(&mut core, &calls), [
+            GlCall::Viewport { x: 0, y: 0, width: 400, height: 400 },
+            GlCall::UniformMatrix4(modelview*config.view_offset[0]*config.projection[0]),
+            GlCall::Viewport { x: 400, y: 0, width: 400, height: 400 },
+            GlCall::UniformMatrix4(modelview*config.view_offset[1]*config.projection[1]),
+            GlCall::Viewport { x: 0, y: 0, width: 800, height: 400 },
+        ]);
+
+        // Back to a single draw with the regular matrices
+        end_vr_stereo_mode(&mut core);
+        assert!(!core.rlgl.is_stereo_render_enabled());
+        assert_eq!(frame(&mut core, &calls), [GlCall::UniformMatrix4(modelview*projection)]);
+    }
+}
@@ -1,4 +1,14 @@
-use crate::prelude::*;
+use std::path::Path;
+use crate::{
+    config::{FONT_TTF_DEFAULT_CHARS_PADDING, FONT_TTF_DEFAULT_FIRST_CHAR, FONT_TTF_DEFAULT_LAST_CHAR, FONT_TTF_DEFAULT_SIZE, TEXT_LINE_SPACING},
+    prelude::*,
+    tracelog,
+    utils::{directory_resolver, file_type, load_file_data, FileResolver},
+};
+use super::truetype::TrueType;
+
+/// Maximum atlas size used when repacking font glyphs
+pub const MAX_FONT_ATLAS_SIZE: u32 = 16384;
 
 /// `GlyphInfo`, font characters glyphs info
 pub struct GlyphInfo {
@@ -20,8 +30,894 @@ pub struct Font {
     pub base_size: i32,
     /// Padding around the glyph characters
     pub glyph_padding: i32,
+    /// Distance from baseline to the top of the tallest glyph (at base size)
+    pub ascent: i32,
+    /// Distance from baseline to the bottom of the lowest glyph (at base size, usually negative)
+    pub descent: i32,
+    /// Extra spacing between lines recommended by the font (at base size)
+    pub line_gap: i32,
     /// Texture atlas containing the glyphs
     pub texture: Texture2D,
     // Glyphs info & rectangles in texture for the glyphs
     pub glyphs_recs: Vec<(GlyphInfo, Rectangle)>,
 }
+
+impl Font {
+    /// Load font from file with extended parameters, `font_size` 0 uses `FONT_TTF_DEFAULT_SIZE`
+    /// and no `codepoints` loads the default ASCII set
+    /// NOTE: `BMFont` (`.fnt`) pages are read from the font file directory, size and codepoints are ignored
+    #[must_use]
+    pub fn load(core: &mut Core, path: impl AsRef<Path>, font_size: i32, codepoints: Option<&[char]>) -> Option<Self> {
+        let path = path.as_ref();
+        let (file_type, data) = load_file_data(path)?;
+        if file_type.eq_ignore_ascii_case(".fnt") {
+            let mut resolver = directory_resolver(path.parent().unwrap_or(Path::new("")));
+            Self::load_bmfont_from_memory(core, &data, &mut resolver)
+        } else {
+            Self::load_from_memory(core, &file_type, &data, font_size, codepoints)
+        }
+    }
+
+    /// Load font from memory buffer, `file_type` refers to extension: i.e. `.ttf`
+    /// NOTE: `font_size` 0 uses `FONT_TTF_DEFAULT_SIZE`, no `codepoints` loads the default ASCII set
+    ///
+    /// Loading an embedded font:
+    /// ```ignore
+    /// let font = Font::load_from_memory(&mut core, ".ttf", include_bytes!("../resources/font.ttf"), 20, None);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss, clippy::cast_possible_wrap)]
+    pub fn load_from_memory(core: &mut Core, file_type: &str, data: &[u8], font_size: i32, codepoints: Option<&[char]>) -> Option<Self> {
+        let supported = cfg!(feature = "support_fileformat_ttf")
+            && (file_type.eq_ignore_ascii_case(".ttf") || file_type.eq_ignore_ascii_case(".otf"));
+        if !supported {
+            tracelog!(Warning, "FONT: Data format not supported");
+            return None;
+        }
+        let Some(font_data) = TrueType::parse(data) else {
+            tracelog!(Warning, "FONT: Failed to process TTF font data");
+            return None;
+        };
+
+        let font_size = if font_size > 0 { font_size } else { FONT_TTF_DEFAULT_SIZE };
+        let scale = font_data.scale_for_pixel_height(font_size as f32);
+        let baseline = (f32::from(font_data.ascent) * scale) as i32;
+        let default_codepoints: Vec<char> = (FONT_TTF_DEFAULT_FIRST_CHAR..=FONT_TTF_DEFAULT_LAST_CHAR).collect();
+
+        let glyphs_recs = codepoints.unwrap_or(&default_codepoints).iter().map(|&value| {
+            let index = font_data.glyph_index(value);
+            let advance_x = (f32::from(font_data.advance_width(index)) * scale) as i32;
+            let glyph = match font_data.rasterize(index, scale) {
+                Some(bitmap) => GlyphInfo {
+                    value,
+                    offset_x: bitmap.offset_x,
+                    offset_y: bitmap.offset_y + baseline,
+                    advance_x,
+                    image: Image {
+                        data: bitmap.coverage.iter().flat_map(|&alpha| [255, alpha]).collect(),
+                        width: bitmap.width,
+                        height: bitmap.height,
+                        mipmap: 1,
+                        format: PixelFormat::UncompressedGrayAlpha,
+                    },
+                },
+                // Glyphs without outline (i.e. space) are blank, as wide as they advance
+                None => GlyphInfo {
+                    value,
+                    offset_x: 0,
+                    offset_y: 0,
+                    advance_x,
+                    image: Image::blank(usize::try_from(advance_x).unwrap_or(0), usize::try_from(font_size).unwrap_or(0), PixelFormat::UncompressedGrayAlpha),
+                },
+            };
+            (glyph, Rectangle::default())
+        }).collect();
+
+        let to_pixels = |units: i16| (f32::from(units) * scale).round() as i32;
+        Self::from_glyphs(core, font_size, FONT_TTF_DEFAULT_CHARS_PADDING, glyphs_recs).map(|font| Self {
+            ascent: to_pixels(font_data.ascent),
+            descent: to_pixels(font_data.descent),
+            line_gap: to_pixels(font_data.line_gap),
+            ..font
+        })
+    }
+
+    /// Load `BMFont` (text format) from memory buffer, page images are requested by name to `resolver`
+    ///
+    /// Loading an embedded font:
+    /// ```ignore
+    /// let font = Font::load_bmfont_from_memory(&mut core, include_bytes!("../resources/font.fnt"), &mut |name| match name {
+    ///     "font.png" => Some(include_bytes!("../resources/font.png").into()),
+    ///     _ => None,
+    /// });
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn load_bmfont_from_memory(core: &mut Core, data: &[u8], resolver: &mut FileResolver) -> Option<Self> {
+        if !cfg!(feature = "support_fileformat_fnt") {
+            tracelog!(Warning, "FONT: Data format not supported");
+            return None;
+        }
+        let Ok(text) = std::str::from_utf8(data) else {
+            tracelog!(Warning, "FONT: [BMFont] Failed to process font data");
+            return None;
+        };
+
+        let (mut line_height, mut base) = (0, 0);
+        let mut pages = Vec::new();
+        let mut glyphs_recs = Vec::new();
+        for line in text.lines() {
+            let (tag, values) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+            let values = bmfont_values(values);
+            let value = |key: &str| values.iter().find(|(name, _)| *name == key).and_then(|(_, value)| value.parse::<i32>().ok());
+            match tag {
+                "common" => (line_height, base) = (value("lineHeight")?, value("base")?),
+                "page" => {
+                    let id = usize::try_from(value("id")?).ok()?;
+                    let file = values.iter().find(|(name, _)| *name == "file")?.1;
+                    let Some(page) = resolver(file).and_then(|page_data| Image::load_from_memory(&file_type(Path::new(file)), &page_data)) else {
+                        tracelog!(Warning, "FONT: [BMFont] Failed to load page image: {file}");
+                        return None;
+                    };
+                    // Pages are converted to a common format, so glyphs can share the atlas
+                    let image = Image {
+                        data: page.to_rgba8()?,
+                        width: page.width,
+                        height: page.height,
+                        mipmap: 1,
+                        format: PixelFormat::UncompressedR8G8B8A8,
+                    };
+                    pages.resize_with(pages.len().max(id + 1), || None);
+                    pages[id] = Some(image);
+                }
+                "char" => {
+                    let rec = Rectangle::new(value("x")? as f32, value("y")? as f32, value("width")? as f32, value("height")? as f32);
+                    let page = usize::try_from(value("page").unwrap_or(0)).ok();
+                    let Some(page) = page.and_then(|page| pages.get(page)).and_then(Option::as_ref) else {
+                        tracelog!(Warning, "FONT: [BMFont] Glyph references a missing page");
+                        return None;
+                    };
+                    let glyph = GlyphInfo {
+                        value: char::from_u32(u32::try_from(value("id")?).ok()?)?,
+                        offset_x: value("xoffset")?,
+                        offset_y: value("yoffset")?,
+                        advance_x: value("xadvance")?,
+                        image: page.from_image(rec),
+                    };
+                    glyphs_recs.push((glyph, Rectangle::default()));
+                }
+                _ => {}
+            }
+        }
+
+        // NOTE: Base is the distance from the line top to the baseline, the rest of the line is below it
+        Self::from_glyphs(core, line_height, 0, glyphs_recs)
+            .map(|font| Self { ascent: base, descent: base - line_height, ..font })
+    }
+
+    /// Create font from glyph images, packed into a new atlas texture
+    fn from_glyphs(core: &mut Core, base_size: i32, glyph_padding: i32, glyphs_recs: Vec<(GlyphInfo, Rectangle)>) -> Option<Self> {
+        let format = glyphs_recs.first().map_or(PixelFormat::UncompressedGrayAlpha, |(glyph, _)| glyph.image.format);
+        let mut font = Self {
+            base_size,
+            glyph_padding,
+            ascent: 0,
+            descent: 0,
+            line_gap: 0,
+            texture: Texture { id: 0, width: 0, height: 0, mipmap: 1, format, filter: TextureFilter::Point, unload_sender: None },
+            glyphs_recs,
+        };
+        if font.glyphs_recs.is_empty() || !font.repack_atlas(core) {
+            tracelog!(Warning, "FONT: Failed to load font");
+            return None;
+        }
+        tracelog!(Info, "FONT: Font loaded successfully ({} glyphs)", font.glyphs_recs.len());
+        Some(font)
+    }
+
+    /// Get glyph index position in font for a codepoint (unicode character)
+    #[must_use]
+    pub fn glyph_index(&self, codepoint: char) -> Option<usize> {
+        self.glyphs_recs.iter().position(|(glyph, _)| glyph.value == codepoint)
+    }
+
+    /// Get glyph font info data and rectangle in atlas for a codepoint (unicode character)
+    #[must_use]
+    pub fn glyph(&self, codepoint: char) -> Option<(&GlyphInfo, Rectangle)> {
+        self.glyph_index(codepoint)
+            .map(|index| {
+                let (glyph, rec) = &self.glyphs_recs[index];
+                (glyph, *rec)
+            })
+    }
+
+    /// Get glyph index for a codepoint, falling back to '?' (or the first glyph) when missing
+    #[must_use]
+    pub fn glyph_index_or_fallback(&self, codepoint: char) -> usize {
+        self.glyph_index(codepoint)
+            .or_else(|| self.glyph_index('?'))
+            .unwrap_or(0)
+    }
+
+    /// Get the layout of every glyph drawn for a text, exactly as `draw_text_ex` renders it
+    /// NOTE: Spaces and tabs advance the pen but are not yielded, line breaks reset it to the next line
+    #[allow(clippy::cast_precision_loss)]
+    pub fn layout<'a>(&'a self, text: &'a str, font_size: f32, spacing: f32) -> impl Iterator<Item = GlyphLayout> + 'a {
+        let scale = font_size / self.base_size as f32;
+        let padding = self.glyph_padding as f32;
+        let mut offset = Vector2::ZERO;
+
+        text.char_indices().filter_map(move |(byte_index, ch)| {
+            if ch == '\n' {
+                offset.y += font_size + TEXT_LINE_SPACING;
+                offset.x = 0.0;
+                return None;
+            }
+
+            let (glyph, rec) = self.glyphs_recs.get(self.glyph_index_or_fallback(ch))?;
+            let position = offset;
+            offset.x += if glyph.advance_x == 0 { rec.width } else { glyph.advance_x as f32 } * scale + spacing;
+
+            (ch != ' ' && ch != '\t').then_some(GlyphLayout {
+                ch,
+                byte_index,
+                position: Vector2 {
+                    x: position.x + (glyph.offset_x as f32 - padding) * scale,
+                    y: position.y + (glyph.offset_y as f32 - padding) * scale,
+                },
+                source: Rectangle {
+                    x: rec.x - padding,
+                    y: rec.y - padding,
+                    width: rec.width + 2.0 * padding,
+                    height: rec.height + 2.0 * padding,
+                },
+                scale,
+            })
+        })
+    }
+
+    /// Re-pack all glyph images into the smallest power-of-two atlas that fits, rewriting glyph rectangles
+    /// and replacing the font texture with the new atlas
+    /// NOTE: Must be called outside of drawing (e.g. on a loading screen): text batched
+    /// before the call still references the old rectangles and texture.
+    /// Returns false, leaving the font unchanged, if glyphs do not fit or the atlas can not be uploaded
+    pub fn repack_atlas(&mut self, core: &mut Core) -> bool {
+        debug_assert!(!core.is_drawing, "repack_atlas() must not be called while drawing");
+
+        let format = self.glyphs_recs.first().map_or(PixelFormat::UncompressedGrayscale, |(glyph, _)| glyph.image.format);
+        let sizes: Vec<Size> = self.glyphs_recs.iter()
+            .map(|(glyph, _)| Size { width: glyph.image.width as u32, height: glyph.image.height as u32 })
+            .collect();
+
+        let padding = u32::try_from(self.glyph_padding).unwrap_or(0);
+        let Some((size, recs)) = AtlasPacker::pack_all(&sizes, padding, MAX_FONT_ATLAS_SIZE) else {
+            tracelog!(Warning, "FONT: Failed to repack atlas, glyphs do not fit in {MAX_FONT_ATLAS_SIZE}x{MAX_FONT_ATLAS_SIZE}");
+            return false;
+        };
+
+        let glyphs = self.glyphs_recs.iter().map(|(glyph, _)| &glyph.image).zip(recs.iter().copied());
+        let atlas = Self::compose_atlas(glyphs, size.width as usize, size.height as usize, format);
+        let Ok(mut texture) = Texture::load_from_image(core, &atlas) else {
+            tracelog!(Warning, "FONT: Failed to repack atlas, texture could not be loaded");
+            return false;
+        };
+        if self.texture.filter != texture.filter {
+            set_texture_filter(core, &mut texture, self.texture.filter);
+        }
+
+        for ((_, rec), new_rec) in self.glyphs_recs.iter_mut().zip(recs) {
+            *rec = new_rec;
+        }
+        self.texture = texture;
+
+        tracelog!(Info, "FONT: Atlas repacked successfully ({}x{}, {} glyphs)", size.width, size.height, self.glyphs_recs.len());
+        true
+    }
+
+    /// Generate atlas image from glyph images placed at rectangles
+    #[allow(clippy::cast_sign_loss)]
+    fn compose_atlas<'a>(glyphs: impl IntoIterator<Item = (&'a Image, Rectangle)>, width: usize, height: usize, format: PixelFormat) -> Image {
+        let mut atlas = Image::blank(width, height, format);
+        for (image, rec) in glyphs {
+            atlas.copy_from(image, rec.x as usize, rec.y as usize);
+        }
+        atlas
+    }
+
+    /// Export font atlas image and a CSV sidecar (same path, `.csv` extension) with glyph metrics
+    /// NOTE: Atlas image is rebuilt from glyph images, so it matches the current rectangles
+    /// # Errors
+    /// Returns an error if the image format is not supported or a file could not be written
+    #[cfg(feature = "support_image_export")]
+    pub fn export_atlas(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        use std::fmt::Write;
+
+        let path = path.as_ref();
+        let format = self.glyphs_recs.first().map_or(PixelFormat::UncompressedGrayscale, |(glyph, _)| glyph.image.format);
+        let glyphs = self.glyphs_recs.iter().map(|(glyph, rec)| (&glyph.image, *rec));
+        Self::compose_atlas(glyphs, self.texture.width, self.texture.height, format).export(path)?;
+
+        let mut csv = String::from("codepoint,x,y,width,height,offset_x,offset_y,advance_x\n");
+        for (glyph, rec) in &self.glyphs_recs {
+            let _ = writeln!(csv, "{},{},{},{},{},{},{},{}", u32::from(glyph.value), rec.x, rec.y, rec.width, rec.height, glyph.offset_x, glyph.offset_y, glyph.advance_x);
+        }
+        std::fs::write(path.with_extension("csv"), csv)
+    }
+
+    /// Get glyph horizontal advance, unscaled
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn glyph_advance(glyph: &GlyphInfo, rec: Rectangle) -> f32 {
+        if glyph.advance_x == 0 {
+            rec.width + glyph.offset_x as f32
+        } else {
+            glyph.advance_x as f32
+        }
+    }
+}
+
+/// Split `BMFont` line attributes into `key=value` pairs, quoted values may contain spaces
+fn bmfont_values(line: &str) -> Vec<(&str, &str)> {
+    let mut values = Vec::new();
+    let mut rest = line.trim_start();
+    while let Some((key, value)) = rest.split_once('=') {
+        let (value, remaining) = match value.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => value.split_once(' ').unwrap_or((value, "")),
+        };
+        values.push((key.trim(), value));
+        rest = remaining.trim_start();
+    }
+    values
+}
+
+/// Glyph placement computed by [`Font::layout`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphLayout {
+    /// Character drawn (the requested one, even if a fallback glyph is used)
+    pub ch: char,
+    /// Byte index of the character in the text
+    pub byte_index: usize,
+    /// Destination top-left corner, relative to text position
+    pub position: Position2,
+    /// Source rectangle in font texture, glyph padding included
+    pub source: Rectangle,
+    /// Scale from font base size to drawn size, destination size is `source` size times `scale`
+    pub scale: f32,
+}
+
+impl GlyphLayout {
+    /// Get destination rectangle of the glyph, for text drawn at `position`
+    #[must_use]
+    pub fn dest(&self, position: Position2) -> Rectangle {
+        Rectangle {
+            x: position.x + self.position.x,
+            y: position.y + self.position.y,
+            width: self.source.width * self.scale,
+            height: self.source.height * self.scale,
+        }
+    }
+}
+
+/// Inset applied to glyph source rectangles of a font atlas
+/// NOTE: Only bilinear filtering bleeds neighbor glyphs, point filtered atlases are sampled as they are
+pub(crate) fn glyph_uv_inset(core: &Core, texture: &Texture) -> f32 {
+    if texture.filter == TextureFilter::Bilinear { core.atlas_uv_inset } else { 0.0 }
+}
+
+/// Draw a laid out glyph of a font atlas, text drawn at `position`
+pub(crate) fn draw_glyph(core: &mut Core, texture: &Texture, glyph: &GlyphLayout, position: Position2, tint: Color) {
+    let inset = glyph_uv_inset(core, texture);
+    draw_texture_pro_inset(core, texture, glyph.source, glyph.dest(position), Vector2::ZERO, Degrees::ZERO, tint, inset);
+}
+
+/// Draw text using font and additional parameters
+/// NOTE: Glyphs are placed by [`Font::layout`], missing codepoints use the '?' glyph.
+/// The atlas UV inset is applied when the font texture is bilinear filtered
+pub fn draw_text_ex(core: &mut Core, font: &Font, text: &str, position: Position2, font_size: f32, spacing: f32, tint: Color) {
+    for glyph in font.layout(text, font_size, spacing) {
+        draw_glyph(core, &font.texture, &glyph, position, tint);
+    }
+}
+
+/// Ordered list of fonts, searched front to back for each codepoint
+/// NOTE: Useful for mixing scripts (latin, CJK, emoji...) that no single font covers
+#[derive(Default)]
+pub struct FontStack {
+    /// Fonts in priority order
+    pub fonts: Vec<Font>,
+    /// Vertical spacing between lines of text, in pixels
+    pub line_spacing: f32,
+}
+
+impl FontStack {
+    /// Create a font stack from fonts in priority order
+    #[must_use]
+    pub fn new(fonts: Vec<Font>) -> Self {
+        Self { fonts, line_spacing: TEXT_LINE_SPACING }
+    }
+
+    /// Find the first font containing a codepoint, returning font index, glyph info and atlas rectangle
+    /// NOTE: Falls back to '?' in the first font containing it when no font has the codepoint
+    #[must_use]
+    pub fn glyph_lookup(&self, codepoint: char) -> Option<(usize, &GlyphInfo, Rectangle)> {
+        let find = |codepoint| self.fonts.iter()
+            .enumerate()
+            .find_map(|(index, font)| font.glyph(codepoint).map(|(glyph, rec)| (index, glyph, rec)));
+
+        find(codepoint).or_else(|| find('?'))
+    }
+
+    /// Get scale factor to draw a font of the stack at the requested size
+    /// NOTE: Returns `None` if there is no font at `font_index` or its base size is 0
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn scale(&self, font_index: usize, font_size: f32) -> Option<f32> {
+        self.fonts.get(font_index)
+            .filter(|font| font.base_size != 0)
+            .map(|font| font_size / font.base_size as f32)
+    }
+
+    /// Get the stack ascent at the requested size, the tallest among all fonts
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ascent(&self, font_size: f32) -> f32 {
+        self.fonts.iter()
+            .enumerate()
+            .filter_map(|(index, font)| Some(font.ascent as f32 * self.scale(index, font_size)?))
+            .fold(0.0, f32::max)
+    }
+
+    /// Get vertical offset to apply to a font's glyphs so its baseline matches the stack baseline
+    /// NOTE: Returns `None` if there is no font at `font_index` or its base size is 0
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn baseline_offset(&self, font_index: usize, font_size: f32) -> Option<f32> {
+        let scale = self.scale(font_index, font_size)?;
+        Some(self.ascent(font_size) - self.fonts[font_index].ascent as f32 * scale)
+    }
+
+    /// Get the layout of every glyph drawn for a text, with the index of the font drawing it
+    /// NOTE: Glyphs are shifted so all fonts share the stack baseline, spaces and tabs are not yielded
+    #[allow(clippy::cast_precision_loss)]
+    pub fn layout<'a>(&'a self, text: &'a str, font_size: f32, spacing: f32) -> impl Iterator<Item = (usize, GlyphLayout)> + 'a {
+        let mut offset = Vector2::ZERO;
+
+        text.char_indices().filter_map(move |(byte_index, ch)| {
+            if ch == '\n' {
+                offset.y += font_size + self.line_spacing;
+                offset.x = 0.0;
+                return None;
+            }
+
+            let (index, glyph, rec) = self.glyph_lookup(ch)?;
+            let scale = self.scale(index, font_size)?;
+            let baseline = self.baseline_offset(index, font_size)?;
+            let padding = self.fonts[index].glyph_padding as f32;
+            let position = offset;
+            offset.x += Font::glyph_advance(glyph, rec) * scale + spacing;
+
+            (ch != ' ' && ch != '\t').then_some((index, GlyphLayout {
+                ch,
+                byte_index,
+                position: Vector2 {
+                    x: position.x + (glyph.offset_x as f32 - padding) * scale,
+                    y: position.y + baseline + (glyph.offset_y as f32 - padding) * scale,
+                },
+                source: Rectangle {
+                    x: rec.x - padding,
+                    y: rec.y - padding,
+                    width: rec.width + 2.0 * padding,
+                    height: rec.height + 2.0 * padding,
+                },
+                scale,
+            }))
+        })
+    }
+
+    /// Measure string size, each codepoint using the first font of the stack containing it
+    #[allow(clippy::cast_precision_loss)]
+    pub fn measure_text(&self, text: &str, font_size: f32, spacing: f32) -> Vector2 {
+        let mut text_width: f32 = 0.0;
+        let mut max_text_width: f32 = 0.0;
+        let mut text_height = font_size;
+        let mut line_len: usize = 0;
+        let mut max_line_len: usize = 0;
+
+        for codepoint in text.chars() {
+            if codepoint == '\n' {
+                max_text_width = max_text_width.max(text_width);
+                max_line_len = max_line_len.max(line_len);
+                text_width = 0.0;
+                line_len = 0;
+                text_height += font_size + self.line_spacing;
+            } else if let Some((index, glyph, rec)) = self.glyph_lookup(codepoint) {
+                text_width += Font::glyph_advance(glyph, rec) * self.scale(index, font_size).unwrap_or(0.0);
+                line_len += 1;
+            }
+        }
+
+        max_text_width = max_text_width.max(text_width);
+        max_line_len = max_line_len.max(line_len);
+
+        Vector2 {
+            x: max_text_width + max_line_len.saturating_sub(1) as f32 * spacing,
+            y: text_height,
+        }
+    }
+}
+
+/// Font or font stack used to draw text
+#[derive(Clone, Copy)]
+pub enum FontOrStack<'a> {
+    Font(&'a Font),
+    Stack(&'a FontStack),
+}
+
+impl<'a> From<&'a Font> for FontOrStack<'a> {
+    fn from(value: &'a Font) -> Self {
+        Self::Font(value)
+    }
+}
+
+impl<'a> From<&'a FontStack> for FontOrStack<'a> {
+    fn from(value: &'a FontStack) -> Self {
+        Self::Stack(value)
+    }
+}
+
+impl<'a> FontOrStack<'a> {
+    /// Get font by index in the stack, a single font has index 0
+    #[must_use]
+    pub fn font(self, font_index: usize) -> Option<&'a Font> {
+        match self {
+            Self::Font(font) => (font_index == 0).then_some(font),
+            Self::Stack(stack) => stack.fonts.get(font_index),
+        }
+    }
+
+    /// Find the glyph drawn for a codepoint, returning font index, glyph info and atlas rectangle
+    /// NOTE: Falls back to '?' (or the first glyph of a single font) when the codepoint is missing
+    #[must_use]
+    pub fn glyph_lookup(self, codepoint: char) -> Option<(usize, &'a GlyphInfo, Rectangle)> {
+        match self {
+            Self::Font(font) => font.glyphs_recs.get(font.glyph_index_or_fallback(codepoint)).map(|(glyph, rec)| (0, glyph, *rec)),
+            Self::Stack(stack) => stack.glyph_lookup(codepoint),
+        }
+    }
+
+    /// Get scale factor to draw a font at the requested size
+    /// NOTE: Returns `None` if there is no font at `font_index` or its base size is 0
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn scale(self, font_index: usize, font_size: f32) -> Option<f32> {
+        self.font(font_index)
+            .filter(|font| font.base_size != 0)
+            .map(|font| font_size / font.base_size as f32)
+    }
+
+    /// Get ascent at the requested size, the tallest among all fonts of a stack
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ascent(self, font_size: f32) -> f32 {
+        match self {
+            Self::Font(font) => self.scale(0, font_size).map_or(0.0, |scale| font.ascent as f32 * scale),
+            Self::Stack(stack) => stack.ascent(font_size),
+        }
+    }
+
+    /// Get vertical offset to apply to a font's glyphs so its baseline matches the stack baseline
+    /// NOTE: Returns `None` if there is no font at `font_index` or its base size is 0
+    #[must_use]
+    pub fn baseline_offset(self, font_index: usize, font_size: f32) -> Option<f32> {
+        match self {
+            Self::Font(_) => self.scale(font_index, font_size).map(|_| 0.0),
+            Self::Stack(stack) => stack.baseline_offset(font_index, font_size),
+        }
+    }
+}
+
+/// Draw text using a font stack, each codepoint using the first font containing it
+/// NOTE: Glyphs are placed by [`FontStack::layout`], all fonts sharing the same baseline
+pub fn draw_text_stack(core: &mut Core, stack: &FontStack, text: &str, position: Position2, font_size: f32, spacing: f32, tint: Color) {
+    for (index, glyph) in stack.layout(text, font_size, spacing) {
+        draw_glyph(core, &stack.fonts[index].texture, &glyph, position, tint);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::utils::tests::write_temp_file;
+    use super::{super::truetype, *};
+
+    /// Glyph of the fixture font, 10 pixels high
+    #[allow(clippy::cast_precision_loss)]
+    fn glyph(value: char, x: f32, width: usize, offset_x: i32, advance_x: i32) -> (GlyphInfo, Rectangle) {
+        let info = GlyphInfo { value, offset_x, offset_y: 0, advance_x, image: Image::blank(width, 10, PixelFormat::UncompressedGrayscale) };
+        (info, Rectangle::new(x, 0.0, width as f32, 10.0))
+    }
+
+    /// Font with '?', 'a', 'é' and ' ' glyphs at base size 10
+    pub(crate) fn font() -> Font {
+        Font {
+            base_size: 10,
+            glyph_padding: 0,
+            ascent: 8,
+            descent: -2,
+            line_gap: 0,
+            texture: Texture { id: 1, width: 16, height: 16, mipmap: 1, format: PixelFormat::UncompressedGrayscale, filter: TextureFilter::Point, unload_sender: None },
+            glyphs_recs: vec![
+                glyph('?', 0.0, 4, 0, 5),
+                glyph('a', 4.0, 4, 1, 5),
+                glyph('é', 8.0, 4, 0, 6),
+                glyph(' ', 12.0, 3, 0, 0),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_layout_multi_byte_byte_index() {
+        let font = font();
+        let indices: Vec<(char, usize)> = font.layout("aé€a", 10.0, 0.0).map(|glyph| (glyph.ch, glyph.byte_index)).collect();
+        assert_eq!(indices, [('a', 0), ('é', 1), ('€', 3), ('a', 6)]);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_layout_spacing_and_scale() {
+        let font = font();
+        let glyphs: Vec<GlyphLayout> = font.layout("aa", 20.0, 2.0).collect();
+        assert_eq!(glyphs.len(), 2);
+        assert_eq!(glyphs[0].scale, 2.0);
+        assert_eq!(glyphs[0].position, Vector2::new(2.0, 0.0));
+        // Advance 5 scaled by 2, plus spacing, plus offset 1 scaled by 2
+        assert_eq!(glyphs[1].position, Vector2::new(14.0, 0.0));
+        assert_eq!(glyphs[1].dest(Vector2::new(100.0, 50.0)), Rectangle::new(114.0, 50.0, 8.0, 20.0));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_layout_spaces_advance_without_glyph() {
+        let font = font();
+        let glyphs: Vec<GlyphLayout> = font.layout("a a", 10.0, 1.0).collect();
+        assert_eq!(glyphs.len(), 2);
+        // Space has no advance, its rectangle width is used instead
+        assert_eq!(glyphs[1].position.x, 5.0 + 1.0 + 3.0 + 1.0 + 1.0);
+        assert_eq!(glyphs[1].byte_index, 2);
+    }
+
+    #[test]
+    fn test_layout_line_break() {
+        let font = font();
+        let glyphs: Vec<GlyphLayout> = font.layout("aa\na", 10.0, 0.0).collect();
+        assert_eq!(glyphs.len(), 3);
+        assert_eq!(glyphs[2].position, Vector2::new(1.0, 10.0 + TEXT_LINE_SPACING));
+        assert_eq!(glyphs[2].byte_index, 3);
+    }
+
+    #[test]
+    fn test_layout_fallback_glyph() {
+        let font = font();
+        let glyph = font.layout("b", 10.0, 0.0).next().unwrap();
+        assert_eq!(glyph.ch, 'b');
+        assert_eq!(glyph.source, Rectangle::new(0.0, 0.0, 4.0, 10.0));
+    }
+
+    #[test]
+    fn test_layout_padding() {
+        let mut font = font();
+        font.glyph_padding = 1;
+        let glyph = font.layout("a", 20.0, 0.0).next().unwrap();
+        assert_eq!(glyph.source, Rectangle::new(3.0, -1.0, 6.0, 12.0));
+        assert_eq!(glyph.position, Vector2::new(0.0, -2.0));
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn test_repack_atlas() {
+        let mut core = Core::default();
+        let mut font = font();
+        let before: Vec<Vector2> = font.layout("a?é a", 10.0, 1.0).map(|glyph| glyph.position).collect();
+
+        assert!(font.repack_atlas(&mut core));
+        assert_ne!(font.texture.id, 1);
+        assert!(font.texture.width * font.texture.height <= 16 * 16);
+
+        // Glyphs do not overlap and fit in the new texture
+        for (index, (_, rec)) in font.glyphs_recs.iter().enumerate() {
+            assert!(rec.x_max() <= font.texture.width as f32 && rec.y_max() <= font.texture.height as f32);
+            for (_, other) in &font.glyphs_recs[index + 1..] {
+                assert!(!check_collision_recs(rec, other), "{rec:?} overlaps {other:?}");
+            }
+        }
+
+        // Layout only changes source rectangles
+        let after: Vec<Vector2> = font.layout("a?é a", 10.0, 1.0).map(|glyph| glyph.position).collect();
+        assert_eq!(before, after);
+    }
+
+    /// Glyph value, offsets, advance, image size and data
+    type GlyphData<'a> = (char, i32, i32, i32, usize, usize, &'a [u8]);
+
+    /// Glyph metrics and image data, for comparing loaded fonts
+    fn glyph_data(font: &Font) -> Vec<GlyphData<'_>> {
+        font.glyphs_recs.iter()
+            .map(|(glyph, _)| (glyph.value, glyph.offset_x, glyph.offset_y, glyph.advance_x, glyph.image.width, glyph.image.height, &glyph.image.data[..]))
+            .collect()
+    }
+
+    #[test]
+    fn test_load_ttf() {
+        let mut core = Core::default();
+        let ttf = truetype::tests::ttf(true);
+        let font = Font::load_from_memory(&mut core, ".ttf", &ttf, 10, Some(&['A', 'C', ' ', 'Z'])).unwrap();
+        assert_eq!((font.base_size, font.glyph_padding), (10, FONT_TTF_DEFAULT_CHARS_PADDING));
+        assert_ne!(font.texture.id, 0);
+        assert_eq!(font.texture.format, PixelFormat::UncompressedGrayAlpha);
+        // Ascent 800, descent -200 and line gap 100 font units, scaled to 10 pixels high
+        assert_eq!((font.ascent, font.descent, font.line_gap), (8, -2, 1));
+
+        // 400x600 square at x 100, ascent 800 scaled to 8 pixels above the baseline
+        let (square, rec) = font.glyph('A').unwrap();
+        assert_eq!((square.offset_x, square.offset_y, square.advance_x), (1, 2, 6));
+        assert_eq!((square.image.width, square.image.height, rec.width, rec.height), (4, 6, 4.0, 6.0));
+        assert!(square.image.data.chunks_exact(2).all(|pixel| pixel == [255, 255]));
+        let (composite, _) = font.glyph('C').unwrap();
+        assert_eq!((composite.offset_x, composite.offset_y, composite.advance_x), (3, 1, 8));
+
+        // Glyphs without outline are blank, as wide as their advance
+        let (space, _) = font.glyph(' ').unwrap();
+        assert_eq!((space.advance_x, space.image.width, space.image.height), (2, 2, 10));
+        assert!(space.image.data.iter().all(|&value| value == 0));
+        let (missing, _) = font.glyph('Z').unwrap();
+        assert_eq!(missing.advance_x, 6);
+    }
+
+    #[test]
+    fn test_load_ttf_defaults_and_file_parity() {
+        let mut core = Core::default();
+        let ttf = truetype::tests::ttf(false);
+        let path = write_temp_file("font_parity.ttf", &ttf);
+        let from_file = Font::load(&mut core, &path, 0, None).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let from_memory = Font::load_from_memory(&mut core, ".TTF", &ttf, 0, None).unwrap();
+        assert_eq!(from_memory.base_size, FONT_TTF_DEFAULT_SIZE);
+        assert_eq!((from_file.ascent, from_file.descent, from_file.line_gap), (26, -6, 3));
+        assert_eq!(from_memory.glyphs_recs.len(), 95);
+        assert_eq!(from_memory.glyphs_recs.first().map(|(glyph, _)| glyph.value), Some(' '));
+        assert_eq!(from_memory.glyphs_recs.last().map(|(glyph, _)| glyph.value), Some('~'));
+        assert_eq!(glyph_data(&from_file), glyph_data(&from_memory));
+    }
+
+    #[test]
+    fn test_load_unsupported() {
+        let mut core = Core::default();
+        let ttf = truetype::tests::ttf(false);
+        assert!(Font::load_from_memory(&mut core, ".woff", &ttf, 10, None).is_none());
+        assert!(Font::load_from_memory(&mut core, ".ttf", &ttf[..64], 10, None).is_none());
+        assert!(Font::load_from_memory(&mut core, ".ttf", &ttf, 10, Some(&[])).is_none());
+        assert!(Font::load(&mut core, "missing_file.ttf", 10, None).is_none());
+    }
+
+    /// `BMFont` descriptor with glyphs 'A' and 'B' on one 8x4 page
+    fn bmfont(page: &str) -> String {
+        format!("info face=\"Test Font\" size=4 bold=0\n\
+            common lineHeight=5 base=4 scaleW=8 scaleH=4 pages=1\n\
+            page id=0 file=\"{page}\"\n\
+            chars count=2\n\
+            char id=65 x=0 y=0 width=3 height=4 xoffset=0 yoffset=1 xadvance=4 page=0 chnl=15\n\
+            char id=66   x=3 y=0 width=5 height=4 xoffset=-1 yoffset=0 xadvance=6 page=0 chnl=15\n")
+    }
+
+    /// 8x4 grayscale page, each pixel value is its index
+    fn bmfont_page() -> Image {
+        let mut page = Image::blank(8, 4, PixelFormat::UncompressedGrayscale);
+        for (i, value) in page.data.iter_mut().enumerate() {
+            *value = u8::try_from(i).unwrap();
+        }
+        page
+    }
+
+    #[test]
+    fn test_load_bmfont() {
+        let mut core = Core::default();
+        let png = super::super::png::encode(&bmfont_page()).unwrap();
+        let mut requested = Vec::new();
+        let mut resolver = |name: &str| {
+            requested.push(name.to_string());
+            (name == "test page.png").then(|| MemoryData::from(png.clone()))
+        };
+        let font = Font::load_bmfont_from_memory(&mut core, bmfont("test page.png").as_bytes(), &mut resolver).unwrap();
+        assert_eq!((font.base_size, font.glyph_padding, font.glyphs_recs.len()), (5, 0, 2));
+        assert_eq!((font.ascent, font.descent, font.line_gap), (4, -1, 0));
+        assert_eq!(font.texture.format, PixelFormat::UncompressedR8G8B8A8);
+
+        let (b, rec) = font.glyph('B').unwrap();
+        assert_eq!((b.offset_x, b.offset_y, b.advance_x, rec.width, rec.height), (-1, 0, 6, 5.0, 4.0));
+        // Glyph images are cut from the page, converted to RGBA
+        let expected = bmfont_page().from_image(Rectangle::new(3.0, 0.0, 5.0, 4.0));
+        let expected: Vec<u8> = expected.data.iter().flat_map(|&value| [value, value, value, 255]).collect();
+        assert_eq!(b.image.data, expected);
+
+        assert!(Font::load_bmfont_from_memory(&mut core, bmfont("other.png").as_bytes(), &mut resolver).is_none());
+        assert!(Font::load_bmfont_from_memory(&mut core, b"\xFF\xFE", &mut resolver).is_none());
+        assert_eq!(requested, ["test page.png", "other.png"]);
+    }
+
+    #[test]
+    fn test_load_bmfont_file_parity() {
+        let mut core = Core::default();
+        let png = super::super::png::encode(&bmfont_page()).unwrap();
+        let page_path = write_temp_file("bmfont_page.png", &png);
+        let page_name = page_path.file_name().unwrap().to_string_lossy().into_owned();
+        let fnt = bmfont(&page_name);
+        let path = write_temp_file("bmfont.fnt", fnt.as_bytes());
+        let from_file = Font::load(&mut core, &path, 0, None).unwrap();
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(page_path).unwrap();
+
+        let from_memory = Font::load_bmfont_from_memory(&mut core, fnt.as_bytes(), &mut |_| Some(png.clone().into())).unwrap();
+        assert_eq!(glyph_data(&from_file), glyph_data(&from_memory));
+    }
+
+    /// Stack of the fixture font and a font with only 'b', at base size 20 with a taller ascent
+    pub(crate) fn stack() -> FontStack {
+        let mut large = font();
+        large.base_size = 20;
+        large.ascent = 18;
+        large.glyphs_recs = vec![glyph('b', 0.0, 8, 0, 10)];
+        FontStack::new(vec![font(), large])
+    }
+
+    #[test]
+    fn test_stack_glyph_lookup() {
+        let stack = stack();
+        assert_eq!(stack.glyph_lookup('a').map(|(index, glyph, _)| (index, glyph.value)), Some((0, 'a')));
+        assert_eq!(stack.glyph_lookup('b').map(|(index, glyph, _)| (index, glyph.value)), Some((1, 'b')));
+        assert_eq!(stack.glyph_lookup('c').map(|(index, glyph, _)| (index, glyph.value)), Some((0, '?')));
+        assert!(FontStack::default().glyph_lookup('a').is_none());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_stack_scale_and_baseline() {
+        let mut stack = stack();
+        assert_eq!(stack.scale(0, 20.0), Some(2.0));
+        assert_eq!(stack.scale(1, 20.0), Some(1.0));
+        assert_eq!(stack.scale(2, 20.0), None);
+
+        // Stack ascent is the tallest one: 8*2 for the first font, 18 for the second
+        assert_eq!(stack.ascent(20.0), 18.0);
+        assert_eq!(stack.baseline_offset(0, 20.0), Some(2.0));
+        assert_eq!(stack.baseline_offset(1, 20.0), Some(0.0));
+        assert_eq!(stack.baseline_offset(2, 20.0), None);
+
+        stack.fonts[1].base_size = 0;
+        assert_eq!(stack.scale(1, 20.0), None);
+        assert_eq!(stack.baseline_offset(1, 20.0), None);
+        assert_eq!(stack.ascent(20.0), 16.0);
+    }
+
+    #[test]
+    fn test_stack_layout() {
+        let stack = stack();
+        let glyphs: Vec<(usize, GlyphLayout)> = stack.layout("ab\nb", 20.0, 1.0).collect();
+        assert_eq!(glyphs.len(), 3);
+        assert_eq!(glyphs[0].0, 0);
+        assert_eq!(glyphs[0].1.position, Vector2::new(2.0, 2.0));
+        assert_eq!((glyphs[1].0, glyphs[1].1.byte_index), (1, 1));
+        assert_eq!(glyphs[1].1.position, Vector2::new(11.0, 0.0));
+        assert_eq!(glyphs[1].1.dest(Vector2::ZERO), Rectangle::new(11.0, 0.0, 8.0, 10.0));
+        assert_eq!(glyphs[2].1.position, Vector2::new(0.0, 20.0 + TEXT_LINE_SPACING));
+    }
+
+    #[test]
+    fn test_stack_measure_text() {
+        let stack = stack();
+        // 'a' advances 5*2, 'b' 10*1, plus spacing between them
+        assert_eq!(stack.measure_text("ab", 20.0, 1.0), Vector2::new(21.0, 20.0));
+        assert_eq!(stack.measure_text("ab\nb", 20.0, 1.0), Vector2::new(21.0, 40.0 + TEXT_LINE_SPACING));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{prelude::*, tracelog, utils::load_file_data};
 
 /// Image, pixel data stored in CPU memory (RAM)
 pub struct Image {
@@ -13,3 +13,246 @@ pub struct Image {
     /// Data format
     pub format: PixelFormat,
 }
+
+impl Image {
+    /// Generate image with all pixels set to zero (transparent black)
+    /// NOTE: Only uncompressed formats are supported, compressed formats generate an empty image
+    #[must_use]
+    pub fn blank(width: usize, height: usize, format: PixelFormat) -> Self {
+        Self {
+            data: vec![0; width * height * format.bytes_per_pixel().unwrap_or(0)],
+            width,
+            height,
+            mipmap: 1,
+            format,
+        }
+    }
+
+    /// Load image from file into CPU memory (RAM)
+    #[must_use]
+    pub fn load(path: impl AsRef<std::path::Path>) -> Option<Self> {
+        let (file_type, data) = load_file_data(path.as_ref())?;
+        Self::load_from_memory(&file_type, &data)
+    }
+
+    /// Load image from memory buffer, `file_type` refers to extension: i.e. `.png`
+    /// NOTE: Supported formats depend on enabled `support_fileformat_*` features
+    ///
+    /// Loading an embedded image:
+    /// ```ignore
+    /// let image = Image::load_from_memory(".png", include_bytes!("../resources/sprite.png"));
+    /// ```
+    #[must_use]
+    pub fn load_from_memory(file_type: &str, data: &[u8]) -> Option<Self> {
+        let image = if cfg!(feature = "support_fileformat_png") && file_type.eq_ignore_ascii_case(".png") {
+            super::png::decode(data)
+        } else if cfg!(feature = "support_fileformat_bmp") && file_type.eq_ignore_ascii_case(".bmp") {
+            super::bmp::decode(data)
+        } else {
+            tracelog!(Warning, "IMAGE: Data format not supported");
+            return None;
+        };
+
+        match &image {
+            Some(image) => tracelog!(Info, "IMAGE: Data loaded successfully ({}x{} | {:?} | {} mipmaps)", image.width, image.height, image.format, image.mipmap),
+            None => tracelog!(Warning, "IMAGE: Failed to load image data"),
+        }
+        image
+    }
+
+    /// Load image from clipboard data of the given MIME type (`image/png` or `image/bmp`)
+    #[cfg(feature = "support_clipboard_image")]
+    #[must_use]
+    pub fn from_clipboard_data(mime_type: &str, data: &[u8]) -> Option<Self> {
+        let file_type = match mime_type {
+            "image/png" => ".png",
+            "image/bmp" | "image/x-bmp" => ".bmp",
+            _ => {
+                tracelog!(Debug, "CLIPBOARD: Unsupported image type: {mime_type}");
+                return None;
+            }
+        };
+        Self::load_from_memory(file_type, data)
+    }
+
+    /// Create an image from another image piece, clipped to image bounds
+    /// NOTE: Compressed formats generate an empty image
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn from_image(&self, rec: Rectangle) -> Self {
+        let x = (rec.x.max(0.0) as usize).min(self.width);
+        let y = (rec.y.max(0.0) as usize).min(self.height);
+        let width = (rec.width.max(0.0) as usize).min(self.width - x);
+        let height = (rec.height.max(0.0) as usize).min(self.height - y);
+
+        let mut image = Self::blank(width, height, self.format);
+        if let Some(bpp) = self.format.bytes_per_pixel() {
+            let row_len = width * bpp;
+            for row in 0..height {
+                let src_start = ((y + row) * self.width + x) * bpp;
+                image.data[row * row_len..(row + 1) * row_len].copy_from_slice(&self.data[src_start..src_start + row_len]);
+            }
+        }
+        image
+    }
+
+    /// Get pixel data converted to 8 bit RGBA, `None` for compressed and floating point formats
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_rgba8(&self) -> Option<Vec<u8>> {
+        let bpp = self.format.bytes_per_pixel()?;
+        let pixels = self.data.get(..self.width * self.height * bpp)?.chunks_exact(bpp);
+        // Expand n bit channel to 8 bit
+        let expand = |value: u16, bits: u32| (u32::from(value) * 255 / ((1 << bits) - 1)) as u8;
+
+        let mut out = Vec::with_capacity(self.width * self.height * 4);
+        match self.format {
+            PixelFormat::UncompressedGrayscale => pixels.for_each(|p| out.extend_from_slice(&[p[0], p[0], p[0], 255])),
+            PixelFormat::UncompressedGrayAlpha => pixels.for_each(|p| out.extend_from_slice(&[p[0], p[0], p[0], p[1]])),
+            PixelFormat::UncompressedR8G8B8 => pixels.for_each(|p| out.extend_from_slice(&[p[0], p[1], p[2], 255])),
+            PixelFormat::UncompressedR8G8B8A8 => pixels.for_each(|p| out.extend_from_slice(p)),
+            PixelFormat::UncompressedR5G6B5 => pixels.for_each(|p| {
+                let p = u16::from_le_bytes([p[0], p[1]]);
+                out.extend_from_slice(&[expand(p >> 11, 5), expand((p >> 5) & 0x3F, 6), expand(p & 0x1F, 5), 255]);
+            }),
+            PixelFormat::UncompressedR5G5B5A1 => pixels.for_each(|p| {
+                let p = u16::from_le_bytes([p[0], p[1]]);
+                out.extend_from_slice(&[expand(p >> 11, 5), expand((p >> 6) & 0x1F, 5), expand((p >> 1) & 0x1F, 5), expand(p & 1, 1)]);
+            }),
+            PixelFormat::UncompressedR4G4B4A4 => pixels.for_each(|p| {
+                let p = u16::from_le_bytes([p[0], p[1]]);
+                out.extend_from_slice(&[expand(p >> 12, 4), expand((p >> 8) & 0xF, 4), expand((p >> 4) & 0xF, 4), expand(p & 0xF, 4)]);
+            }),
+            _ => return None,
+        }
+        Some(out)
+    }
+
+    /// Copy source image pixels into this image at position, clipping to bounds
+    /// NOTE: Both images must share the same uncompressed format, no conversion or blending is done
+    pub(crate) fn copy_from(&mut self, src: &Image, x: usize, y: usize) {
+        let Some(bpp) = self.format.bytes_per_pixel() else { return };
+        if src.format != self.format || x >= self.width || y >= self.height {
+            return;
+        }
+
+        let row_len = src.width.min(self.width - x) * bpp;
+        for row in 0..src.height.min(self.height - y) {
+            let src_start = row * src.width * bpp;
+            let dst_start = ((y + row) * self.width + x) * bpp;
+            self.data[dst_start..dst_start + row_len].copy_from_slice(&src.data[src_start..src_start + row_len]);
+        }
+    }
+
+    /// Get image pixels as colors for modification, converting the image to R8G8B8A8
+    /// NOTE: Mipmaps of converted images are dropped, `None` for compressed and floating point formats
+    #[cfg(feature = "support_image_manipulation")]
+    fn colors_mut(&mut self) -> Option<&mut [Color]> {
+        if self.format != PixelFormat::UncompressedR8G8B8A8 {
+            let Some(data) = self.to_rgba8() else {
+                tracelog!(Warning, "IMAGE: Pixel data retrieval not supported for format");
+                return None;
+            };
+            self.data = data;
+            self.format = PixelFormat::UncompressedR8G8B8A8;
+            self.mipmap = 1;
+        }
+        Some(colors_from_rgba8_mut(&mut self.data))
+    }
+
+    /// Modify image color: tint
+    #[cfg(feature = "support_image_manipulation")]
+    pub fn color_tint(&mut self, color: Color) {
+        if let Some(colors) = self.colors_mut() {
+            tint_slice(colors, color);
+        }
+    }
+
+    /// Modify image color: brightness (-255 to 255)
+    #[cfg(feature = "support_image_manipulation")]
+    pub fn color_brightness(&mut self, brightness: i32) {
+        if let Some(colors) = self.colors_mut() {
+            brightness_slice(colors, brightness);
+        }
+    }
+
+    /// Modify image color: contrast (-100 to 100)
+    #[cfg(feature = "support_image_manipulation")]
+    pub fn color_contrast(&mut self, contrast: f32) {
+        if let Some(colors) = self.colors_mut() {
+            contrast_slice(colors, contrast);
+        }
+    }
+
+    /// Export image data to file
+    /// NOTE: File format is selected by extension, only `.png` is supported for now
+    /// # Errors
+    /// Returns an error if the format is not supported or the file could not be written
+    #[cfg(feature = "support_image_export")]
+    pub fn export(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let is_png = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+
+        let data = if cfg!(feature = "support_fileformat_png") && is_png {
+            super::png::encode(self)
+        } else {
+            None
+        };
+
+        let Some(data) = data else {
+            tracelog!(Warning, "IMAGE: Export image format not supported: {}", path.display());
+            return Err(std::io::ErrorKind::Unsupported.into());
+        };
+
+        std::fs::write(path, data)
+            .inspect(|()| tracelog!(Info, "FILEIO: [{}] Image exported successfully", path.display()))
+            .inspect_err(|_| tracelog!(Warning, "FILEIO: [{}] Failed to export image", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::tests::write_temp_file;
+    use super::*;
+
+    #[test]
+    fn test_load_file_parity() {
+        let mut image = Image::blank(3, 2, PixelFormat::UncompressedR8G8B8A8);
+        for (i, byte) in image.data.iter_mut().enumerate() {
+            *byte = u8::try_from(i * 10).unwrap();
+        }
+        let png = super::super::png::encode(&image).unwrap();
+        let path = write_temp_file("image_parity.png", &png);
+        let from_file = Image::load(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let from_memory = Image::load_from_memory(".png", &png).unwrap();
+        for loaded in [&from_file, &from_memory] {
+            assert_eq!((loaded.width, loaded.height, loaded.format), (3, 2, PixelFormat::UncompressedR8G8B8A8));
+            assert_eq!(loaded.data, image.data);
+        }
+        assert!(Image::load_from_memory(".xyz", &png).is_none());
+        assert!(Image::load("missing_file.png").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "support_image_manipulation")]
+    fn test_color_manipulation() {
+        let mut image = Image::blank(2, 1, PixelFormat::UncompressedGrayAlpha);
+        image.data.copy_from_slice(&[100, 255, 200, 128]);
+
+        // Non RGBA images are converted first
+        image.color_brightness(60);
+        assert_eq!((image.format, image.data.as_slice()), (PixelFormat::UncompressedR8G8B8A8, [160, 160, 160, 255, 255, 255, 255, 128].as_slice()));
+
+        image.color_tint(Color::new(255, 0, 255, 255));
+        assert_eq!(image.data, [160, 0, 160, 255, 255, 0, 255, 128]);
+
+        image.color_contrast(-100.0);
+        assert_eq!(image.data, [127, 127, 127, 255, 127, 127, 127, 128]);
+
+        let mut compressed = Image { data: vec![0; 8], width: 4, height: 4, mipmap: 1, format: PixelFormat::CompressedDxt1RGB };
+        compressed.color_tint(Color::RED);
+        assert_eq!((compressed.format, compressed.data), (PixelFormat::CompressedDxt1RGB, vec![0; 8]));
+    }
+}
@@ -7,6 +7,15 @@ pub mod camera;
 pub mod model;
 pub mod shader;
 pub mod drawing;
+pub mod vr;
+pub mod shared;
+pub mod atlas;
+pub mod particles;
+pub mod tiled;
+pub mod rich_text;
+mod png;
+mod bmp;
+mod truetype;
 
-pub(self) type GlFrameBufferID = u32;
-pub(self) type GlTextureID = u32;
+pub(crate) type GlFrameBufferID = u32;
+pub(crate) type GlTextureID = u32;
@@ -0,0 +1,201 @@
+//! Distance-based level of detail selection
+
+use crate::prelude::*;
+
+/// Levels of detail of a model, selected by distance to the camera
+/// NOTE: Generic over the level type, usually a model or a mesh
+#[derive(Debug, Clone)]
+pub struct ModelLod<M> {
+    /// Levels sorted by switch distance, from the most detailed
+    levels: Vec<(Units, M)>,
+    /// Extra distance required to switch back to a more detailed level
+    hysteresis: Units,
+    /// Debug override of the selected level
+    forced: Option<usize>,
+    /// Level selected last time
+    current: usize,
+}
+
+impl<M> ModelLod<M> {
+    /// Create from levels and the distance from which each level is used, sorted by distance
+    /// NOTE: The first level is used below the smallest distance
+    #[must_use]
+    pub fn new(levels: Vec<(Units, M)>) -> Self {
+        let mut levels = levels;
+        levels.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            levels,
+            hysteresis: 0.0,
+            forced: None,
+            current: 0,
+        }
+    }
+
+    /// Set extra distance required to switch back to a more detailed level, prevents popping at boundaries
+    #[must_use]
+    pub fn with_hysteresis(mut self, hysteresis: Units) -> Self {
+        self.hysteresis = hysteresis.max(0.0);
+        self
+    }
+
+    /// Get levels with their switch distance
+    #[inline]
+    #[must_use]
+    pub fn levels(&self) -> &[(Units, M)] {
+        &self.levels
+    }
+
+    /// Force a level whatever the distance, `None` to select by distance again
+    pub fn force_level(&mut self, level: Option<usize>) {
+        self.forced = level.map(|level| level.min(self.levels.len().saturating_sub(1)));
+    }
+
+    /// Get level index selected last time
+    #[inline]
+    #[must_use]
+    pub fn current_level(&self) -> usize {
+        self.current
+    }
+
+    /// Select level index for a distance
+    /// NOTE: Switching to a less detailed level happens at its switch distance,
+    /// switching back requires getting closer than the switch distance minus hysteresis
+    pub fn select(&mut self, distance: Units) -> usize {
+        if let Some(level) = self.forced {
+            self.current = level;
+            return level;
+        }
+
+        let target = self.levels.iter()
+            .rposition(|&(switch_distance, _)| distance >= switch_distance)
+            .unwrap_or(0);
+
+        if target < self.current {
+            // Only move towards detail when below the hysteresis band of every crossed level
+            let mut level = self.current;
+            while level > target && distance < self.levels[level].0 - self.hysteresis {
+                level -= 1;
+            }
+            self.current = level;
+        } else {
+            self.current = target;
+        }
+        self.current
+    }
+
+    /// Select level for an object at position seen by a camera, `None` if there are no levels
+    pub fn select_for_camera(&mut self, camera: &Camera3D, position: Position3) -> Option<&M> {
+        let level = self.select(camera.position.distance(position));
+        self.levels.get(level).map(|(_, model)| model)
+    }
+}
+
+impl ModelLod<Model> {
+    /// Draw the level selected for the camera distance to `position`, like [`draw_model`]
+    pub fn draw(&mut self, core: &mut Core, camera: &Camera3D, position: Position3, scale: f32, tint: Color) {
+        let level = self.select(camera.position.distance(position));
+        if let Some((_, model)) = self.levels.get_mut(level) {
+            draw_model(core, model, position, scale, tint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rlgl::tests::{recording_rlgl, GlCall};
+    use super::*;
+
+    fn three_levels() -> ModelLod<&'static str> {
+        ModelLod::new(vec![(50.0, "low"), (0.0, "high"), (10.0, "medium")])
+    }
+
+    #[test]
+    fn test_lod_select_by_distance() {
+        let mut lod = three_levels();
+        assert_eq!(lod.levels().iter().map(|&(_, level)| level).collect::<Vec<_>>(), ["high", "medium", "low"]);
+        for (distance, expected) in [(0.0, 0), (9.9, 0), (10.0, 1), (49.9, 1), (50.0, 2), (1000.0, 2), (20.0, 1), (-5.0, 0)] {
+            assert_eq!(lod.select(distance), expected, "distance {distance}");
+            assert_eq!(lod.current_level(), expected);
+        }
+
+        // Forced level is clamped and ignores distance
+        lod.force_level(Some(7));
+        assert_eq!(lod.select(0.0), 2);
+        lod.force_level(None);
+        assert_eq!(lod.select(0.0), 0);
+
+        assert!(ModelLod::<()>::new(Vec::new()).select_for_camera(&camera(), Vector3::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_lod_hysteresis() {
+        let mut lod = three_levels().with_hysteresis(2.0);
+        // Less detail as soon as the switch distance is reached
+        assert_eq!(lod.select(10.0), 1);
+        // Back to more detail only below the switch distance minus hysteresis
+        assert_eq!(lod.select(9.0), 1);
+        assert_eq!(lod.select(8.0), 1);
+        assert_eq!(lod.select(7.9), 0);
+        assert_eq!(lod.select(9.0), 0);
+
+        // Getting much closer crosses several levels at once
+        assert_eq!(lod.select(60.0), 2);
+        assert_eq!(lod.select(48.5), 2);
+        assert_eq!(lod.select(47.0), 1);
+        assert_eq!(lod.select(60.0), 2);
+        assert_eq!(lod.select(5.0), 0);
+
+        // Negative hysteresis is ignored
+        let mut lod = three_levels().with_hysteresis(-5.0);
+        assert_eq!(lod.select(10.0), 1);
+        assert_eq!(lod.select(9.9), 0);
+    }
+
+    fn camera() -> Camera3D {
+        Camera3D {
+            position: Vector3::ZERO,
+            target: Vector3::new(0.0, 0.0, -1.0),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            fovy: Degrees(45.0),
+            projection: CameraProjection::Perspective,
+        }
+    }
+
+    /// Model of a single mesh with `triangles` unindexed triangles
+    fn model(triangles: usize) -> Model {
+        let vertices = (0..triangles).flat_map(|_| [Vector3::ZERO, Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0)]).collect();
+        let mut material = Material::default();
+        material[MaterialMapIndex::DIFFUSE].color = Color::new(255, 0, 255, 255);
+        Model {
+            transform: Matrix::IDENTITY,
+            meshes: vec![Mesh::new(vertices, Vec::new(), Vec::new(), Vec::new(), Vec::new())],
+            materials: vec![material],
+            mesh_material: vec![0],
+        }
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_lod_draw() {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+        let mut lod = ModelLod::new(vec![(0.0, model(4)), (10.0, model(2)), (50.0, model(1))]);
+
+        // Selected level is drawn, tinted by its diffuse color
+        let mut draw = |lod: &mut ModelLod<Model>, position| {
+            calls.borrow_mut().clear();
+            lod.draw(&mut core, &camera(), position, 1.0, Color::new(255, 255, 255, 51));
+            calls.borrow().iter().filter_map(|call| match *call {
+                GlCall::DrawArrays { count, .. } => Some(count),
+                _ => None,
+            }).collect::<Vec<_>>()
+        };
+        assert_eq!(draw(&mut lod, Vector3::new(0.0, 0.0, -5.0)), [12]);
+        assert_eq!(draw(&mut lod, Vector3::new(0.0, 20.0, 0.0)), [6]);
+        assert_eq!(draw(&mut lod, Vector3::new(60.0, 0.0, 0.0)), [3]);
+        assert_eq!(lod.current_level(), 2);
+        assert!(lod.levels().iter().all(|(_, model)| model.meshes[0].is_uploaded()));
+        assert!(calls.borrow().contains(&GlCall::Uniform4f([1.0, 0.0, 1.0, 0.2])));
+    }
+}
@@ -0,0 +1,79 @@
+//! Materials, textures and colors applied to model meshes
+
+use crate::{config::MAX_MATERIAL_MAPS, prelude::*};
+
+/// Material map index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaterialMapIndex {
+    /// Albedo material (same as: Diffuse)
+    Albedo,
+    /// Metalness material (same as: Specular)
+    Metalness,
+    /// Normal material
+    Normal,
+    /// Roughness material
+    Roughness,
+    /// Ambient occlusion material
+    Occlusion,
+    /// Emission material
+    Emission,
+    /// Heightmap material
+    Height,
+    /// Cubemap material (NOTE: Uses `GL_TEXTURE_CUBE_MAP`)
+    Cubemap,
+    /// Irradiance material (NOTE: Uses `GL_TEXTURE_CUBE_MAP`)
+    Irradiance,
+    /// Prefilter material (NOTE: Uses `GL_TEXTURE_CUBE_MAP`)
+    Prefilter,
+    /// Brdf material
+    Brdf,
+}
+
+impl MaterialMapIndex {
+    pub const DIFFUSE: Self = Self::Albedo;
+    pub const SPECULAR: Self = Self::Metalness;
+}
+
+/// `MaterialMap`
+#[derive(Debug, Default)]
+pub struct MaterialMap {
+    /// Material map texture, `None` uses the default texture
+    pub texture: Option<Texture2D>,
+    /// Material map color
+    pub color: Color,
+    /// Material map value
+    pub value: f32,
+}
+
+/// Material, includes maps (textures and colors)
+#[derive(Debug)]
+pub struct Material {
+    /// Material maps array (`MAX_MATERIAL_MAPS`)
+    pub maps: [MaterialMap; MAX_MATERIAL_MAPS],
+}
+
+impl Default for Material {
+    /// Default material: white diffuse and specular colors, no textures
+    fn default() -> Self {
+        let mut material = Self { maps: std::array::from_fn(|_| MaterialMap::default()) };
+        material[MaterialMapIndex::DIFFUSE].color = Color::WHITE;
+        material[MaterialMapIndex::SPECULAR].color = Color::WHITE;
+        material
+    }
+}
+
+impl std::ops::Index<MaterialMapIndex> for Material {
+    type Output = MaterialMap;
+
+    #[inline]
+    fn index(&self, index: MaterialMapIndex) -> &Self::Output {
+        &self.maps[index as usize]
+    }
+}
+
+impl std::ops::IndexMut<MaterialMapIndex> for Material {
+    #[inline]
+    fn index_mut(&mut self, index: MaterialMapIndex) -> &mut Self::Output {
+        &mut self.maps[index as usize]
+    }
+}
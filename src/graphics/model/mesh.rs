@@ -0,0 +1,637 @@
+use std::collections::HashMap;
+use crate::{config::{MAX_MESH_VERTEX_BUFFERS, RL_DEFAULT_SHADER_ATTRIB_LOCATION_INDICES}, prelude::*, rlgl::{gl::{AttribType, BufferData, BufferTarget}, FlushReason, RLGL}, tracelog};
+
+/// Layout of the mesh vertex attributes: components, type and normalization
+/// NOTE: Order matches `Mesh::vbo_id`, the default shader locations are used
+const MESH_ATTRIBS: [(i32, AttribType, bool); 4] = [
+    (3, AttribType::Float, false),
+    (2, AttribType::Float, false),
+    (3, AttribType::Float, false),
+    (4, AttribType::UnsignedByte, true),
+];
+
+/// Mesh, vertex data and vao/vbo
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    /// Vertex position (shader-location = 0)
+    pub(crate) vertices: Vec<Vector3>,
+    /// Vertex texture coordinates (UV - 2 components per vertex) (shader-location = 1)
+    pub(crate) texcoords: Vec<Vector2>,
+    /// Vertex normals (shader-location = 2)
+    pub(crate) normals: Vec<Vector3>,
+    /// Vertex colors (shader-location = 3)
+    pub(crate) colors: Vec<Color>,
+    /// Vertex indices (in case vertex data comes indexed), 3 per triangle
+    pub(crate) indices: Vec<u16>,
+
+    /// OpenGL Vertex Array Object id
+    pub(crate) vao_id: u32,
+    /// OpenGL Vertex Buffer Objects id (default vertex data)
+    pub(crate) vbo_id: [u32; MAX_MESH_VERTEX_BUFFERS],
+    /// Vertex data modified since last upload
+    pub(crate) dirty: bool,
+}
+
+impl Mesh {
+    /// Create mesh from vertex data, optional attributes can be left empty
+    /// NOTE: Attributes must be empty or have one element per vertex, `indices` can be empty for non-indexed meshes
+    #[must_use]
+    pub fn new(vertices: Vec<Vector3>, texcoords: Vec<Vector2>, normals: Vec<Vector3>, colors: Vec<Color>, indices: Vec<u16>) -> Self {
+        Self {
+            vertices,
+            texcoords,
+            normals,
+            colors,
+            indices,
+            ..Default::default()
+        }
+    }
+
+    /// Number of triangles
+    #[inline]
+    #[must_use]
+    pub fn triangle_count(&self) -> usize {
+        if self.indices.is_empty() { self.vertices.len() / 3 } else { self.indices.len() / 3 }
+    }
+
+    /// Number of vertices
+    #[inline]
+    #[must_use]
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Check if vertex data was modified since last upload
+    #[inline]
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Clear modified flag, returns true if vertex data must be uploaded again
+    #[inline]
+    pub(crate) fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// Get vertex positions
+    #[inline]
+    pub fn positions(&self) -> &[Vector3] {
+        &self.vertices
+    }
+
+    /// Get vertex positions for modification, marks the mesh dirty
+    #[inline]
+    pub fn positions_mut(&mut self) -> &mut [Vector3] {
+        self.dirty = true;
+        &mut self.vertices
+    }
+
+    /// Get vertex normals, empty if the mesh has no normals
+    #[inline]
+    pub fn normals(&self) -> &[Vector3] {
+        &self.normals
+    }
+
+    /// Get vertex normals for modification, marks the mesh dirty
+    #[inline]
+    pub fn normals_mut(&mut self) -> &mut [Vector3] {
+        self.dirty = true;
+        &mut self.normals
+    }
+
+    /// Get vertex texture coordinates, empty if the mesh has no texcoords
+    #[inline]
+    pub fn texcoords(&self) -> &[Vector2] {
+        &self.texcoords
+    }
+
+    /// Get vertex texture coordinates for modification, marks the mesh dirty
+    #[inline]
+    pub fn texcoords_mut(&mut self) -> &mut [Vector2] {
+        self.dirty = true;
+        &mut self.texcoords
+    }
+
+    /// Get vertex colors, empty if the mesh has no colors
+    #[inline]
+    pub fn colors(&self) -> &[Color] {
+        &self.colors
+    }
+
+    /// Get vertex colors for modification, marks the mesh dirty
+    /// NOTE: Colors are allocated (white) on first access if the mesh has none
+    pub fn colors_mut(&mut self) -> &mut [Color] {
+        if self.colors.len() != self.vertices.len() {
+            self.colors.resize(self.vertices.len(), Color::WHITE);
+        }
+        self.dirty = true;
+        &mut self.colors
+    }
+
+    /// Get vertex indices of every triangle, for indexed and non-indexed meshes
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn triangle_indices(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        self.triangles().into_iter().map(|[a, b, c]| [a as u32, b as u32, c as u32])
+    }
+
+    /// Set every vertex color from its position and normal
+    /// NOTE: Normal is `Vector3::ZERO` if the mesh has no normals
+    pub fn paint_vertex_colors(&mut self, mut paint: impl FnMut(Vector3, Vector3) -> Color) {
+        self.colors_mut();
+        for (i, (color, vertex)) in self.colors.iter_mut().zip(&self.vertices).enumerate() {
+            let normal = self.normals.get(i).copied().unwrap_or(Vector3::ZERO);
+            *color = paint(*vertex, normal);
+        }
+    }
+
+    /// Move every vertex along its normal by the returned distance, normals are recomputed afterwards
+    /// NOTE: Normals are computed first if the mesh has none
+    pub fn displace_along_normals(&mut self, mut displacement: impl FnMut(Vector3) -> f32) {
+        if self.normals.len() != self.vertices.len() {
+            self.recompute_normals();
+        }
+        for (vertex, normal) in self.vertices.iter_mut().zip(&self.normals) {
+            *vertex += *normal * displacement(*vertex);
+        }
+        self.recompute_normals();
+    }
+
+    /// Compute smooth vertex normals, averaging adjacent face normals weighted by face area
+    /// NOTE: Vertices not used by any triangle get a zero normal
+    pub fn recompute_normals(&mut self) {
+        let mut normals = vec![Vector3::ZERO; self.vertices.len()];
+        for [a, b, c] in self.triangles() {
+            // Cross product length is twice the triangle area
+            let face = (self.vertices[b] - self.vertices[a]).cross_product(self.vertices[c] - self.vertices[a]);
+            normals[a] += face;
+            normals[b] += face;
+            normals[c] += face;
+        }
+        for normal in &mut normals {
+            if normal.magnitude_sqr() > f32::EPSILON * f32::EPSILON {
+                *normal = normal.normalize();
+            }
+        }
+        self.normals = normals;
+        self.dirty = true;
+    }
+
+    /// Check if vertex data was uploaded to GPU
+    #[inline]
+    #[must_use]
+    pub fn is_uploaded(&self) -> bool {
+        self.vbo_id[0] != 0
+    }
+
+    /// Get vertex attributes flattened for upload: positions, texcoords, normals and colors
+    fn attribute_data(&self) -> ([Vec<f32>; 3], Vec<u8>) {
+        (
+            [
+                self.vertices.iter().flat_map(Vector3::as_array).collect(),
+                self.texcoords.iter().flat_map(Vector2::as_array).collect(),
+                self.normals.iter().flat_map(Vector3::as_array).collect(),
+            ],
+            self.colors.iter().flat_map(|c| [c.r, c.g, c.b, c.a]).collect(),
+        )
+    }
+
+    /// Upload non-empty vertex attributes, loading missing buffers (recorded into the bound VAO) and updating the others
+    #[allow(clippy::cast_possible_truncation)]
+    fn upload_attributes(&mut self, rlgl: &mut RLGL, dynamic: bool) {
+        let ([vertices, texcoords, normals], colors) = self.attribute_data();
+        let data = [
+            BufferData::F32(&vertices),
+            BufferData::F32(&texcoords),
+            BufferData::F32(&normals),
+            BufferData::U8(&colors),
+        ];
+        for (location, (data, (size, ty, normalized))) in data.into_iter().zip(MESH_ATTRIBS).enumerate() {
+            if self.vbo_id[location] != 0 {
+                rlgl.update_vertex_buffer(self.vbo_id[location], data, 0);
+            } else if location == 0 || data.size() > 0 {
+                // NOTE: Positions are always loaded, they mark the mesh as uploaded
+                self.vbo_id[location] = rlgl.load_vertex_buffer(BufferTarget::Array, data, dynamic);
+                rlgl.set_vertex_attribute(self.vbo_id[location], location as u32, size, ty, normalized);
+            }
+        }
+    }
+
+    /// Bind vertex buffers and setup vertex attributes, required before every draw if VAO is not supported
+    #[allow(clippy::cast_possible_truncation)]
+    fn bind_attributes(&self, rlgl: &mut RLGL) {
+        for (location, (size, ty, normalized)) in MESH_ATTRIBS.into_iter().enumerate() {
+            if self.vbo_id[location] != 0 {
+                rlgl.set_vertex_attribute(self.vbo_id[location], location as u32, size, ty, normalized);
+            }
+        }
+        rlgl.enable_vertex_buffer_element(self.vbo_id[RL_DEFAULT_SHADER_ATTRIB_LOCATION_INDICES]);
+    }
+
+    /// Get vertex indices of every triangle
+    fn triangles(&self) -> Vec<[usize; 3]> {
+        if self.indices.is_empty() {
+            (0..self.vertices.len() / 3).map(|i| [3 * i, 3 * i + 1, 3 * i + 2]).collect()
+        } else {
+            self.indices.chunks_exact(3)
+                .map(|tri| [usize::from(tri[0]), usize::from(tri[1]), usize::from(tri[2])])
+                .collect()
+        }
+    }
+
+    /// Group vertices in grid cells of the given size, returns the cluster of every vertex and the cluster count
+    #[allow(clippy::cast_possible_truncation)]
+    fn cluster_vertices(&self, min: Vector3, cell_size: f32) -> (Vec<usize>, usize) {
+        let mut cells = HashMap::new();
+        let clusters = self.vertices.iter()
+            .map(|vertex| {
+                let cell = (
+                    ((vertex.x - min.x) / cell_size).floor() as i32,
+                    ((vertex.y - min.y) / cell_size).floor() as i32,
+                    ((vertex.z - min.z) / cell_size).floor() as i32,
+                );
+                let next = cells.len();
+                *cells.entry(cell).or_insert(next)
+            })
+            .collect();
+        (clusters, cells.len())
+    }
+
+    /// Generate a simplified mesh with about `target_ratio` of the vertices, using vertex clustering
+    /// NOTE: Vertices in the same grid cell are merged (attributes averaged), collapsed and duplicate
+    /// triangles are removed. Geometric error is bounded by the grid cell diagonal
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn simplify(&self, target_ratio: f32) -> Self {
+        let target = ((self.vertices.len() as f32 * target_ratio.clamp(0.0, 1.0)) as usize).max(3);
+        if self.vertices.len() <= target {
+            // NOTE: Copy is not uploaded, GPU buffers stay owned by this mesh
+            return Self::new(self.vertices.clone(), self.texcoords.clone(), self.normals.clone(), self.colors.clone(), self.indices.clone());
+        }
+
+        let (min, max) = self.vertices.iter().fold(
+            (Vector3::new(f32::MAX, f32::MAX, f32::MAX), Vector3::new(f32::MIN, f32::MIN, f32::MIN)),
+            |(min, max), v| (
+                Vector3::new(min.x.min(v.x), min.y.min(v.y), min.z.min(v.z)),
+                Vector3::new(max.x.max(v.x), max.y.max(v.y), max.z.max(v.z)),
+            ),
+        );
+        let diagonal = (max - min).magnitude().max(f32::EPSILON);
+
+        // Binary search the smallest cell size producing at most `target` clusters
+        let (mut low, mut high) = (0.0, diagonal);
+        let mut best = self.cluster_vertices(min, high);
+        for _ in 0..24 {
+            let cell_size = (low + high) * 0.5;
+            if cell_size <= 0.0 {
+                break;
+            }
+            let clustering = self.cluster_vertices(min, cell_size);
+            if clustering.1 <= target {
+                high = cell_size;
+                best = clustering;
+            } else {
+                low = cell_size;
+            }
+        }
+        let (clusters, cluster_count) = best;
+
+        // Average vertex attributes per cluster
+        let mut counts = vec![0.0f32; cluster_count];
+        let mut vertices = vec![Vector3::ZERO; cluster_count];
+        let mut texcoords = vec![Vector2::ZERO; if self.texcoords.is_empty() { 0 } else { cluster_count }];
+        let mut normals = vec![Vector3::ZERO; if self.normals.is_empty() { 0 } else { cluster_count }];
+        let mut colors = vec![[0.0f32; 4]; if self.colors.is_empty() { 0 } else { cluster_count }];
+        for (i, &cluster) in clusters.iter().enumerate() {
+            counts[cluster] += 1.0;
+            vertices[cluster] += self.vertices[i];
+            if let (Some(sum), Some(texcoord)) = (texcoords.get_mut(cluster), self.texcoords.get(i)) {
+                *sum += *texcoord;
+            }
+            if let (Some(sum), Some(normal)) = (normals.get_mut(cluster), self.normals.get(i)) {
+                *sum += *normal;
+            }
+            if let (Some(sum), Some(color)) = (colors.get_mut(cluster), self.colors.get(i)) {
+                for (channel, value) in sum.iter_mut().zip([color.r, color.g, color.b, color.a]) {
+                    *channel += f32::from(value);
+                }
+            }
+        }
+        for (cluster, &count) in counts.iter().enumerate() {
+            vertices[cluster] /= count;
+            if let Some(texcoord) = texcoords.get_mut(cluster) {
+                *texcoord /= count;
+            }
+            if let Some(normal) = normals.get_mut(cluster) {
+                if normal.magnitude_sqr() > f32::EPSILON {
+                    *normal = normal.normalize();
+                }
+            }
+        }
+        let colors = colors.iter().zip(&counts)
+            .map(|(sum, count)| Color::new(
+                (sum[0] / count) as u8,
+                (sum[1] / count) as u8,
+                (sum[2] / count) as u8,
+                (sum[3] / count) as u8,
+            ))
+            .collect();
+
+        // Remap triangles, removing collapsed and duplicated ones
+        let mut seen = std::collections::HashSet::new();
+        let mut indices = Vec::new();
+        for [a, b, c] in self.triangles() {
+            let tri = [clusters[a], clusters[b], clusters[c]];
+            if tri[0] == tri[1] || tri[1] == tri[2] || tri[0] == tri[2] {
+                continue;
+            }
+            // Same triangle with the same winding, whatever the first vertex
+            let start = (0..3).min_by_key(|&i| tri[i]).unwrap_or(0);
+            let key = [tri[start], tri[(start + 1) % 3], tri[(start + 2) % 3]];
+            if seen.insert(key) {
+                indices.extend(tri);
+            }
+        }
+
+        let mut mesh = Self::new(vertices, texcoords, normals, colors, Vec::new());
+        if cluster_count <= usize::from(u16::MAX) + 1 {
+            mesh.indices = indices.iter().map(|&i| i as u16).collect();
+        } else {
+            // Too many vertices to be indexed, unroll triangles
+            mesh = Self::new(
+                indices.iter().map(|&i| mesh.vertices[i]).collect(),
+                indices.iter().filter_map(|&i| mesh.texcoords.get(i).copied()).collect(),
+                indices.iter().filter_map(|&i| mesh.normals.get(i).copied()).collect(),
+                indices.iter().filter_map(|&i| mesh.colors.get(i).copied()).collect(),
+                Vec::new(),
+            );
+        }
+        mesh
+    }
+}
+
+/// Upload mesh vertex data in GPU and provide VAO/VBO ids
+/// NOTE: Vertex data is kept in CPU memory, `dynamic` buffers are expected to be updated often
+pub fn upload_mesh(core: &mut Core, mesh: &mut Mesh, dynamic: bool) {
+    if mesh.is_uploaded() {
+        // Check if mesh has already been loaded in GPU
+        tracelog!(Warning, "VAO: [ID {}] Trying to re-load an already loaded mesh", mesh.vao_id);
+        return;
+    }
+
+    let rlgl = &mut core.rlgl;
+    mesh.vao_id = rlgl.load_vertex_array();
+    rlgl.enable_vertex_array(mesh.vao_id);
+
+    // NOTE: Vertex attributes must be uploaded considering default locations points
+    mesh.upload_attributes(rlgl, dynamic);
+    if !mesh.indices.is_empty() {
+        mesh.vbo_id[RL_DEFAULT_SHADER_ATTRIB_LOCATION_INDICES] = rlgl.load_vertex_buffer(BufferTarget::ElementArray, BufferData::U16(&mesh.indices), dynamic);
+    }
+    rlgl.disable_vertex_array();
+    mesh.dirty = false;
+
+    if mesh.vao_id == 0 {
+        tracelog!(Info, "VBO: Mesh uploaded successfully to VRAM (GPU)");
+    } else {
+        tracelog!(Info, "VAO: [ID {}] Mesh uploaded successfully to VRAM (GPU)", mesh.vao_id);
+    }
+}
+
+/// Unload mesh vertex data from GPU memory (VRAM)
+/// NOTE: Vertex data is kept in CPU memory, the mesh can be uploaded again
+pub fn unload_mesh(core: &mut Core, mesh: &mut Mesh) {
+    core.rlgl.unload_vertex_array(std::mem::take(&mut mesh.vao_id));
+    for vbo_id in &mut mesh.vbo_id {
+        core.rlgl.unload_vertex_buffer(std::mem::take(vbo_id));
+    }
+}
+
+/// Upload modified mesh vertex data to GPU, nothing is done if the mesh was not modified
+/// NOTE: Buffers are updated in place, attributes added since upload (colors) get a new buffer
+pub fn update_mesh_buffers(core: &mut Core, mesh: &mut Mesh) {
+    if !mesh.is_uploaded() || !mesh.take_dirty() {
+        return;
+    }
+
+    let rlgl = &mut core.rlgl;
+    rlgl.enable_vertex_array(mesh.vao_id);
+    mesh.upload_attributes(rlgl, true);
+    rlgl.disable_vertex_array();
+}
+
+/// Draw a 3d mesh with the current shader and the default texture, `transform` is applied to the mesh
+/// NOTE: The mesh is uploaded on first draw, modified vertex data is uploaded again before drawing
+#[inline]
+pub fn draw_mesh(core: &mut Core, mesh: &mut Mesh, transform: Matrix) {
+    draw_mesh_tinted(core, mesh, transform, Color::WHITE);
+}
+
+/// Draw a 3d mesh like [`draw_mesh`], multiplying its colors by `tint`
+pub(crate) fn draw_mesh_tinted(core: &mut Core, mesh: &mut Mesh, transform: Matrix, tint: Color) {
+    // NOTE: Pending batch draws are drawn first to keep the drawing order
+    core.rlgl.draw_render_batch(FlushReason::MeshDraw);
+
+    if mesh.is_uploaded() {
+        update_mesh_buffers(core, mesh);
+    } else {
+        upload_mesh(core, mesh, false);
+    }
+
+    let rlgl = &mut core.rlgl;
+    if !rlgl.enable_vertex_array(mesh.vao_id) {
+        mesh.bind_attributes(rlgl);
+    }
+    let indexed = !mesh.indices.is_empty();
+    rlgl.draw_vertex_array(if indexed { mesh.indices.len() } else { mesh.vertices.len() }, indexed, transform, tint);
+    rlgl.disable_vertex_array();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+    use crate::rlgl::tests::{recording_rlgl, GlCall, Upload};
+
+    /// Flat grid of `quads`x`quads` quads in the xz plane, indexed, with normals and colors
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn grid_mesh(quads: usize) -> Mesh {
+        let stride = quads + 1;
+        let vertices = (0..stride*stride).map(|i| Vector3::new((i%stride) as f32, 0.0, (i/stride) as f32)).collect();
+        let indices = (0..quads*quads)
+            .flat_map(|quad| {
+                let i = (quad/quads*stride + quad%quads) as u16;
+                let row = stride as u16;
+                [i, i + row, i + 1, i + 1, i + row, i + row + 1]
+            })
+            .collect();
+        Mesh::new(vertices, Vec::new(), vec![Vector3::new(0.0, 1.0, 0.0); stride*stride], vec![Color::RED; stride*stride], indices)
+    }
+
+    /// Unit UV sphere of `rings`x`slices` quads, indexed, with normals
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn sphere_mesh(rings: usize, slices: usize) -> Mesh {
+        let stride = slices + 1;
+        let vertices: Vec<Vector3> = (0..(rings + 1)*stride)
+            .map(|i| {
+                let (theta, phi) = ((i/stride) as f32/rings as f32*PI, (i%stride) as f32/slices as f32*2.0*PI);
+                Vector3::new(theta.sin()*phi.cos(), theta.cos(), theta.sin()*phi.sin())
+            })
+            .collect();
+        let indices = (0..rings*slices)
+            .flat_map(|quad| {
+                let i = (quad/slices*stride + quad%slices) as u16;
+                let row = stride as u16;
+                [i, i + 1, i + row, i + 1, i + row + 1, i + row]
+            })
+            .collect();
+        Mesh::new(vertices.clone(), Vec::new(), vertices, Vec::new(), indices)
+    }
+
+    #[track_caller]
+    fn assert_vector_near(actual: Vector3, expected: Vector3) {
+        assert!((actual - expected).magnitude() < 1e-5, "{actual:?} != {expected:?}");
+    }
+
+    #[test]
+    fn test_paint_sphere_vertex_colors() {
+        let mut mesh = sphere_mesh(8, 16);
+        assert!(mesh.colors().is_empty());
+        mesh.take_dirty();
+
+        // Upper hemisphere white, lower black, from the normal
+        let mut painted = 0;
+        mesh.paint_vertex_colors(|vertex, normal| {
+            assert_vector_near(vertex, normal);
+            painted += 1;
+            if normal.y >= 0.0 { Color::WHITE } else { Color::BLACK }
+        });
+        assert_eq!(painted, mesh.vertex_count());
+        assert!(mesh.is_dirty());
+        for (vertex, color) in mesh.positions().iter().zip(mesh.colors()) {
+            assert_eq!(*color, if vertex.y >= 0.0 { Color::WHITE } else { Color::BLACK }, "{vertex:?}");
+        }
+
+        // Without normals the painter gets a zero normal
+        let mut mesh = Mesh::new(mesh.vertices.clone(), Vec::new(), Vec::new(), Vec::new(), mesh.indices.clone());
+        mesh.paint_vertex_colors(|_, normal| {
+            assert_eq!(normal, Vector3::ZERO);
+            Color::RED
+        });
+        assert_eq!(mesh.colors(), vec![Color::RED; mesh.vertex_count()]);
+    }
+
+    #[test]
+    fn test_recompute_sphere_normals() {
+        let mut mesh = sphere_mesh(16, 32);
+        let expected = mesh.normals().to_vec();
+        mesh.normals_mut().fill(Vector3::ZERO);
+        mesh.recompute_normals();
+
+        // Smooth normals of a sphere point away from its center, seam vertices included
+        // NOTE: Pole rows are skipped, some of their vertices only touch degenerate triangles
+        let stride = 33;
+        let rows = mesh.normals()[stride..mesh.vertex_count() - stride].iter().zip(&expected[stride..]);
+        for (normal, &expected) in rows {
+            assert!((normal.magnitude() - 1.0).abs() < 1e-5);
+            assert!(normal.dot(expected) > 0.99, "{normal:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_displace_plane_recomputes_normals() {
+        let mut mesh = grid_mesh(4);
+        mesh.take_dirty();
+
+        // Uniform displacement moves the plane up, normals keep pointing up
+        mesh.displace_along_normals(|_| 2.0);
+        assert!(mesh.is_dirty());
+        assert!(mesh.positions().iter().all(|vertex| vertex.y == 2.0));
+        for normal in mesh.normals() {
+            assert_vector_near(*normal, Vector3::new(0.0, 1.0, 0.0));
+        }
+
+        // Ramp rising along x tilts every normal back against it
+        let mut mesh = grid_mesh(4);
+        mesh.displace_along_normals(|vertex| vertex.x);
+        assert!(mesh.positions().iter().all(|vertex| vertex.y == vertex.x));
+        for normal in mesh.normals() {
+            assert_vector_near(*normal, Vector3::new(-1.0, 1.0, 0.0).normalize());
+        }
+
+        // Missing normals are computed first, then the vertices moved along them
+        let mut mesh = Mesh::new(grid_mesh(2).vertices, Vec::new(), Vec::new(), Vec::new(), grid_mesh(2).indices);
+        mesh.displace_along_normals(|_| 1.0);
+        assert!(mesh.positions().iter().all(|vertex| vertex.y == 1.0));
+        assert_eq!(mesh.normals().len(), mesh.vertex_count());
+    }
+
+    #[test]
+    fn test_dirty_mesh_uploads_once() {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+        let mut mesh = grid_mesh(2);
+        let uploads = |calls: &[GlCall]| calls.iter().filter(|call| matches!(call, GlCall::BufferSubData(_))).count();
+
+        // First draw uploads with buffer data, nothing is updated while the mesh is clean
+        draw_mesh(&mut core, &mut mesh, Matrix::IDENTITY);
+        assert!(mesh.is_uploaded() && !mesh.is_dirty());
+        calls.borrow_mut().clear();
+        draw_mesh(&mut core, &mut mesh, Matrix::IDENTITY);
+        assert_eq!(uploads(&calls.borrow()), 0);
+
+        // Modified positions are uploaded once, along with the other loaded attributes
+        mesh.positions_mut()[0].y = 1.0;
+        assert!(mesh.is_dirty());
+        draw_mesh(&mut core, &mut mesh, Matrix::IDENTITY);
+        draw_mesh(&mut core, &mut mesh, Matrix::IDENTITY);
+        update_mesh_buffers(&mut core, &mut mesh);
+        let positions: Vec<f32> = mesh.positions().iter().flat_map(Vector3::as_array).collect();
+        let calls = calls.borrow();
+        assert_eq!(calls.iter().filter(|call| **call == GlCall::BufferSubData(Upload::F32(positions.clone()))).count(), 1);
+        assert_eq!(uploads(&calls), 3, "positions, normals and colors");
+        assert!(!mesh.is_dirty());
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn test_simplify_triangle_counts() {
+        let mesh = grid_mesh(16);
+        assert_eq!((mesh.vertex_count(), mesh.triangle_count()), (289, 512));
+
+        let mut previous = mesh.triangle_count();
+        for ratio in [0.5, 0.25, 0.1, 0.02] {
+            let simplified = mesh.simplify(ratio);
+            let target = (289.0*ratio) as usize;
+            assert!(simplified.vertex_count() <= target.max(3), "ratio {ratio}: {} vertices", simplified.vertex_count());
+            assert!(simplified.triangle_count() > 0 && simplified.triangle_count() <= previous, "ratio {ratio}: {} triangles", simplified.triangle_count());
+            assert!(simplified.indices.iter().all(|&i| usize::from(i) < simplified.vertex_count()));
+            // Attributes are averaged per cluster
+            assert_eq!(simplified.normals(), vec![Vector3::new(0.0, 1.0, 0.0); simplified.vertex_count()]);
+            assert_eq!(simplified.colors(), vec![Color::RED; simplified.vertex_count()]);
+            previous = simplified.triangle_count();
+        }
+
+        // Nothing to simplify, same geometry
+        let copy = mesh.simplify(1.0);
+        assert_eq!((copy.positions(), copy.indices.as_slice()), (mesh.positions(), mesh.indices.as_slice()));
+        assert_eq!(mesh.simplify(2.0).triangle_count(), 512);
+    }
+
+    #[test]
+    fn test_simplify_copy_is_not_uploaded() {
+        let mut core = Core::default();
+        let mut mesh = grid_mesh(2);
+        upload_mesh(&mut core, &mut mesh, false);
+        assert!(mesh.is_uploaded());
+
+        // Copy can't share the GPU buffers, they would be unloaded twice
+        let copy = mesh.simplify(1.0);
+        assert!(!copy.is_uploaded());
+        assert_eq!((copy.vao_id, copy.vbo_id), (0, [0; MAX_MESH_VERTEX_BUFFERS]));
+        assert!(!mesh.simplify(0.5).is_uploaded());
+        assert!(mesh.is_uploaded());
+    }
+}
@@ -1,10 +1,229 @@
-use crate::prelude::*;
+use std::path::Path;
+use crate::{prelude::*, tracelog, utils::{file_type, load_file_data}};
+use self::mesh::draw_mesh_tinted;
+use self::obj::MtlMaterial;
 
 pub mod mesh;
 pub mod material;
 pub mod animation;
+pub mod lod;
+mod obj;
 
 pub struct BoundingBox {
     pub min: Position3,
     pub max: Position3,
 }
+
+/// Model, meshes, materials and animation data
+#[derive(Debug)]
+pub struct Model {
+    /// Local transform matrix
+    pub transform: Matrix,
+    /// Meshes array, uploaded to GPU on load
+    pub meshes: Vec<Mesh>,
+    /// Materials array
+    pub materials: Vec<Material>,
+    /// Material index of each mesh
+    pub mesh_material: Vec<usize>,
+}
+
+impl Model {
+    /// Load model from file (meshes and materials)
+    /// NOTE: Material libraries and textures are read from the model file directory
+    #[must_use]
+    pub fn load(core: &mut Core, path: impl AsRef<Path>) -> Option<Self> {
+        let path = path.as_ref();
+        let (file_type, data) = load_file_data(path)?;
+        let mut resolver = directory_resolver(path.parent().unwrap_or(Path::new("")));
+        Self::load_from_memory(core, &file_type, &data, &mut resolver)
+    }
+
+    /// Load model from memory buffer, `file_type` refers to extension: i.e. `.obj`
+    /// NOTE: Material libraries and textures are requested by name to `resolver`,
+    /// meshes use a default material if theirs can not be found
+    ///
+    /// Loading an embedded model:
+    /// ```ignore
+    /// let model = Model::load_from_memory(&mut core, ".obj", include_bytes!("../resources/house.obj"), &mut |name| match name {
+    ///     "house.mtl" => Some(include_bytes!("../resources/house.mtl").into()),
+    ///     "house_diffuse.png" => Some(include_bytes!("../resources/house_diffuse.png").into()),
+    ///     _ => None,
+    /// });
+    /// ```
+    #[must_use]
+    pub fn load_from_memory(core: &mut Core, file_type: &str, data: &[u8], resolver: &mut FileResolver) -> Option<Self> {
+        if !(cfg!(feature = "support_fileformat_obj") && file_type.eq_ignore_ascii_case(".obj")) {
+            tracelog!(Warning, "MODEL: Data format not supported");
+            return None;
+        }
+        let Some(obj) = std::str::from_utf8(data).ok().and_then(obj::parse_obj) else {
+            tracelog!(Warning, "MODEL: [OBJ] Failed to process model data");
+            return None;
+        };
+
+        let mut mtl_materials = Vec::new();
+        if cfg!(feature = "support_fileformat_mtl") {
+            for lib in &obj.material_libs {
+                match resolver(lib).as_deref().map(std::str::from_utf8) {
+                    Some(Ok(text)) => mtl_materials.extend(obj::parse_mtl(text)),
+                    _ => tracelog!(Warning, "MODEL: [OBJ] Failed to load material library: {lib}"),
+                }
+            }
+        }
+
+        let mut materials: Vec<Material> = mtl_materials.iter().map(|material| Self::load_mtl_material(core, material, resolver)).collect();
+        let mut default_material = None;
+        let mut model = Self { transform: Matrix::IDENTITY, meshes: Vec::new(), materials: Vec::new(), mesh_material: Vec::new() };
+        for obj_mesh in obj.meshes {
+            let index = obj_mesh.material.as_ref()
+                .and_then(|name| mtl_materials.iter().position(|material| material.name == *name))
+                .unwrap_or_else(|| *default_material.get_or_insert_with(|| {
+                    materials.push(Material::default());
+                    materials.len() - 1
+                }));
+
+            let mut mesh = Mesh::new(obj_mesh.vertices, obj_mesh.texcoords, obj_mesh.normals, Vec::new(), Vec::new());
+            upload_mesh(core, &mut mesh, false);
+            model.meshes.push(mesh);
+            model.mesh_material.push(index);
+        }
+        if materials.is_empty() {
+            materials.push(Material::default());
+        }
+        model.materials = materials;
+
+        tracelog!(Info, "MODEL: Model loaded successfully ({} meshes, {} materials)", model.meshes.len(), model.materials.len());
+        Some(model)
+    }
+
+    /// Create material from MTL data, textures are loaded through `resolver`
+    fn load_mtl_material(core: &mut Core, mtl: &MtlMaterial, resolver: &mut FileResolver) -> Material {
+        let mut load_texture = |name: &Option<String>| {
+            let name = name.as_deref()?;
+            let texture = resolver(name)
+                .and_then(|data| Image::load_from_memory(&file_type(Path::new(name)), &data))
+                .and_then(|image| Texture::load_from_image(core, &image).ok());
+            if texture.is_none() {
+                tracelog!(Warning, "MODEL: [OBJ] Failed to load material texture: {name}");
+            }
+            texture
+        };
+
+        let mut material = Material::default();
+        material[MaterialMapIndex::DIFFUSE] = MaterialMap { texture: load_texture(&mtl.diffuse_map), color: mtl.diffuse, value: 0.0 };
+        material[MaterialMapIndex::SPECULAR] = MaterialMap { texture: load_texture(&mtl.specular_map), color: mtl.specular, value: mtl.shininess };
+        material[MaterialMapIndex::Emission] = MaterialMap { texture: load_texture(&mtl.emission_map), color: mtl.emission, value: 0.0 };
+        material[MaterialMapIndex::Normal].texture = load_texture(&mtl.normal_map);
+        material
+    }
+}
+
+/// Draw a model (with its meshes and material colors) at `position`, scaled uniformly
+/// NOTE: Model transform is applied first, each mesh is tinted with its diffuse color times `tint`
+pub fn draw_model(core: &mut Core, model: &mut Model, position: Position3, scale: f32, tint: Color) {
+    let transform = model.transform*Matrix::scale(scale, scale, scale)*Matrix::translate(position.x, position.y, position.z);
+    for (mesh, &material) in model.meshes.iter_mut().zip(&model.mesh_material) {
+        let diffuse = model.materials.get(material).map_or(Color::WHITE, |material| material[MaterialMapIndex::DIFFUSE].color);
+        draw_mesh_tinted(core, mesh, transform, diffuse.tint(tint));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::tests::write_temp_file;
+    use super::*;
+
+    /// OBJ with a textured quad using material `red`, and a triangle using a missing material
+    fn obj(mtl: &str) -> String {
+        format!("mtllib {mtl}\nv 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvt 0 0\nvt 1 1\nusemtl red\nf 1/1 2/1 3/2 4/2\nusemtl missing\nf 1 2 3\n")
+    }
+
+    fn mtl(texture: &str) -> String {
+        format!("newmtl red\nKd 1 0 0\nNs 10\nmap_Kd {texture}\nnewmtl unused\n")
+    }
+
+    fn texture_png() -> Vec<u8> {
+        super::super::png::encode(&Image::blank(2, 3, PixelFormat::UncompressedR8G8B8A8)).unwrap()
+    }
+
+    /// Mesh vertex data and material colors, textures and values, for comparing loaded models
+    #[allow(clippy::type_complexity)]
+    fn model_data(model: &Model) -> (Vec<(&[Vector3], &[Vector2], usize)>, Vec<Vec<(Color, Option<(usize, usize)>, u32)>>) {
+        let meshes = model.meshes.iter().zip(&model.mesh_material).map(|(mesh, &material)| (mesh.positions(), mesh.texcoords(), material)).collect();
+        let materials = model.materials.iter()
+            .map(|material| material.maps.iter().map(|map| (map.color, map.texture.as_ref().map(|texture| (texture.width, texture.height)), map.value.to_bits())).collect())
+            .collect();
+        (meshes, materials)
+    }
+
+    #[test]
+    fn test_load_obj() {
+        let mut core = Core::default();
+        let png = texture_png();
+        let mut requested = Vec::new();
+        let mut resolver = |name: &str| {
+            requested.push(name.to_string());
+            match name {
+                "scene.mtl" => Some(MemoryData::from(mtl("red.png").into_bytes())),
+                "red.png" => Some(MemoryData::from(png.clone())),
+                _ => None,
+            }
+        };
+        let model = Model::load_from_memory(&mut core, ".obj", obj("scene.mtl").as_bytes(), &mut resolver).unwrap();
+        assert_eq!(requested, ["scene.mtl", "red.png"]);
+        assert_eq!(model.transform, Matrix::IDENTITY);
+
+        // Quad is triangulated, texcoords are flipped
+        assert_eq!(model.meshes.len(), 2);
+        assert_eq!((model.meshes[0].vertex_count(), model.meshes[1].vertex_count()), (6, 3));
+        assert_eq!(model.meshes[0].texcoords()[..3], [Vector2::new(0.0, 1.0), Vector2::new(0.0, 1.0), Vector2::new(1.0, 0.0)]);
+        assert!(model.meshes.iter().all(Mesh::is_uploaded));
+
+        // Materials from the library, plus a default one for the missing material
+        assert_eq!(model.mesh_material, [0, 2]);
+        assert_eq!(model.materials.len(), 3);
+        let red = &model.materials[0][MaterialMapIndex::DIFFUSE];
+        assert_eq!((red.color, red.texture.as_ref().map(|texture| (texture.width, texture.height))), (rgb(255, 0, 0), Some((2, 3))));
+        assert_eq!(model.materials[0][MaterialMapIndex::SPECULAR].value.to_bits(), 10.0f32.to_bits());
+        let default = &model.materials[2][MaterialMapIndex::DIFFUSE];
+        assert_eq!((default.color, default.texture.is_none()), (Color::WHITE, true));
+    }
+
+    #[test]
+    fn test_load_obj_file_parity() {
+        let mut core = Core::default();
+        let png = texture_png();
+        let texture_path = write_temp_file("model_texture.png", &png);
+        let texture_name = texture_path.file_name().unwrap().to_string_lossy().into_owned();
+        let mtl = mtl(&texture_name);
+        let mtl_path = write_temp_file("model.mtl", mtl.as_bytes());
+        let mtl_name = mtl_path.file_name().unwrap().to_string_lossy().into_owned();
+        let obj = obj(&mtl_name);
+        let path = write_temp_file("model.obj", obj.as_bytes());
+        let from_file = Model::load(&mut core, &path);
+        for path in [path, mtl_path, texture_path] {
+            std::fs::remove_file(path).unwrap();
+        }
+
+        let mut resolver = |name: &str| {
+            if name == mtl_name { Some(MemoryData::from(mtl.clone().into_bytes())) } else { Some(MemoryData::from(png.clone())) }
+        };
+        let from_memory = Model::load_from_memory(&mut core, ".obj", obj.as_bytes(), &mut resolver).unwrap();
+        assert_eq!(model_data(&from_file.unwrap()), model_data(&from_memory));
+    }
+
+    #[test]
+    fn test_load_obj_without_materials() {
+        let mut core = Core::default();
+        let model = Model::load_from_memory(&mut core, ".OBJ", b"v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n", &mut |_| None).unwrap();
+        assert_eq!((model.meshes.len(), model.materials.len(), model.mesh_material.as_slice()), (1, 1, [0].as_slice()));
+
+        // Missing library only loses materials
+        let model = Model::load_from_memory(&mut core, ".obj", obj("missing.mtl").as_bytes(), &mut |_| None).unwrap();
+        assert_eq!((model.materials.len(), model.mesh_material.as_slice()), (1, [0, 0].as_slice()));
+
+        assert!(Model::load_from_memory(&mut core, ".gltf", b"", &mut |_| None).is_none());
+        assert!(Model::load_from_memory(&mut core, ".obj", b"f 1 2 3\n", &mut |_| None).is_none());
+        assert!(Model::load(&mut core, "missing_file.obj").is_none());
+    }
+}
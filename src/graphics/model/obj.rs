@@ -0,0 +1,245 @@
+//! Minimal OBJ and MTL parsers: triangulated faces grouped by material, material colors and texture names
+
+use crate::prelude::*;
+
+/// OBJ mesh data, faces using the same material
+#[derive(Debug, Default)]
+pub(crate) struct ObjMesh {
+    /// Material name set by `usemtl`, `None` before the first one
+    pub(crate) material: Option<String>,
+    pub(crate) vertices: Vec<Vector3>,
+    /// Texture coordinates, empty if no face corner references one
+    pub(crate) texcoords: Vec<Vector2>,
+    /// Normals, empty if no face corner references one
+    pub(crate) normals: Vec<Vector3>,
+}
+
+/// OBJ file data
+#[derive(Debug, Default)]
+pub(crate) struct ObjData {
+    /// Material library file names (`mtllib`)
+    pub(crate) material_libs: Vec<String>,
+    pub(crate) meshes: Vec<ObjMesh>,
+}
+
+/// Face corner attribute indices, resolved to zero-based
+type Corner = (usize, Option<usize>, Option<usize>);
+
+/// Resolve an OBJ index: one-based, or negative relative to the end of the list
+fn resolve_index(value: &str, count: usize) -> Option<usize> {
+    let index = value.parse::<isize>().ok()?;
+    let resolved = if index < 0 { count.checked_sub(index.unsigned_abs())? } else { index.unsigned_abs().checked_sub(1)? };
+    (resolved < count).then_some(resolved)
+}
+
+/// Parse numbers after a keyword, missing components are zero
+fn parse_floats<const N: usize>(values: &str) -> Option<[f32; N]> {
+    let mut numbers = [0.0; N];
+    for (number, value) in numbers.iter_mut().zip(values.split_whitespace()) {
+        *number = value.parse().ok()?;
+    }
+    Some(numbers)
+}
+
+/// Build mesh from the corners of faces sharing a material, `None` if there are no faces
+fn finish_mesh(material: Option<String>, corners: &mut Vec<Corner>, positions: &[Vector3], texcoords: &[Vector2], normals: &[Vector3]) -> Option<ObjMesh> {
+    if corners.is_empty() {
+        return None;
+    }
+    let has_texcoords = corners.iter().any(|corner| corner.1.is_some());
+    let has_normals = corners.iter().any(|corner| corner.2.is_some());
+    let mesh = ObjMesh {
+        material,
+        vertices: corners.iter().map(|corner| positions[corner.0]).collect(),
+        texcoords: if has_texcoords { corners.iter().map(|corner| corner.1.map_or(Vector2::ZERO, |i| texcoords[i])).collect() } else { Vec::new() },
+        normals: if has_normals { corners.iter().map(|corner| corner.2.map_or(Vector3::ZERO, |i| normals[i])).collect() } else { Vec::new() },
+    };
+    corners.clear();
+    Some(mesh)
+}
+
+/// Parse OBJ text, faces are triangulated as fans and split into one mesh per material
+/// NOTE: Texture coordinates are flipped vertically, to match the image origin
+pub(crate) fn parse_obj(text: &str) -> Option<ObjData> {
+    let mut positions = Vec::new();
+    let mut texcoords = Vec::new();
+    let mut normals = Vec::new();
+    let mut data = ObjData::default();
+    // Corners of the current material faces
+    let mut material = None;
+    let mut corners: Vec<Corner> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let (keyword, values) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let values = values.trim();
+        match keyword {
+            "v" => {
+                let [x, y, z] = parse_floats(values)?;
+                positions.push(Vector3::new(x, y, z));
+            }
+            "vt" => {
+                let [u, v] = parse_floats(values)?;
+                texcoords.push(Vector2::new(u, 1.0 - v));
+            }
+            "vn" => {
+                let [x, y, z] = parse_floats(values)?;
+                normals.push(Vector3::new(x, y, z));
+            }
+            "f" => {
+                let face: Vec<Corner> = values.split_whitespace().map(|corner| {
+                    let mut indices = corner.split('/');
+                    let position = resolve_index(indices.next()?, positions.len())?;
+                    let texcoord = match indices.next() {
+                        Some("") | None => None,
+                        Some(index) => Some(resolve_index(index, texcoords.len())?),
+                    };
+                    let normal = match indices.next() {
+                        Some("") | None => None,
+                        Some(index) => Some(resolve_index(index, normals.len())?),
+                    };
+                    Some((position, texcoord, normal))
+                }).collect::<Option<_>>()?;
+                for i in 1..face.len().saturating_sub(1) {
+                    corners.extend([face[0], face[i], face[i + 1]]);
+                }
+            }
+            "usemtl" => {
+                data.meshes.extend(finish_mesh(material.take(), &mut corners, &positions, &texcoords, &normals));
+                material = Some(values.to_string());
+            }
+            "mtllib" => data.material_libs.push(values.to_string()),
+            _ => {}
+        }
+    }
+    data.meshes.extend(finish_mesh(material, &mut corners, &positions, &texcoords, &normals));
+    Some(data)
+}
+
+/// MTL material data
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MtlMaterial {
+    pub(crate) name: String,
+    /// Diffuse color (`Kd`)
+    pub(crate) diffuse: Color,
+    /// Specular color (`Ks`)
+    pub(crate) specular: Color,
+    /// Emission color (`Ke`)
+    pub(crate) emission: Color,
+    /// Specular exponent (`Ns`)
+    pub(crate) shininess: f32,
+    /// Texture file names (`map_Kd`, `map_Ks`, `map_Ke`, `bump`)
+    pub(crate) diffuse_map: Option<String>,
+    pub(crate) specular_map: Option<String>,
+    pub(crate) emission_map: Option<String>,
+    pub(crate) normal_map: Option<String>,
+}
+
+impl MtlMaterial {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            diffuse: Color::WHITE,
+            specular: Color::WHITE,
+            emission: Color::BLACK,
+            shininess: 0.0,
+            diffuse_map: None,
+            specular_map: None,
+            emission_map: None,
+            normal_map: None,
+        }
+    }
+}
+
+/// Parse MTL text, lines before the first `newmtl` are ignored
+/// NOTE: Texture options (i.e. `-bm 1.0`) are skipped, the last value is used as file name
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn parse_mtl(text: &str) -> Vec<MtlMaterial> {
+    let mut materials: Vec<MtlMaterial> = Vec::new();
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let (keyword, values) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let values = values.trim();
+        if keyword == "newmtl" {
+            materials.push(MtlMaterial::new(values));
+            continue;
+        }
+        let Some(material) = materials.last_mut() else { continue };
+
+        let color = || parse_floats(values).map(|[r, g, b]: [f32; 3]| {
+            let to_byte = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+            rgb(to_byte(r), to_byte(g), to_byte(b))
+        });
+        let map = || values.split_whitespace().last().map(str::to_string);
+        match keyword {
+            "Kd" => material.diffuse = color().unwrap_or(material.diffuse),
+            "Ks" => material.specular = color().unwrap_or(material.specular),
+            "Ke" => material.emission = color().unwrap_or(material.emission),
+            "Ns" => material.shininess = values.parse().unwrap_or(material.shininess),
+            "map_Kd" => material.diffuse_map = map(),
+            "map_Ks" => material.specular_map = map(),
+            "map_Ke" => material.emission_map = map(),
+            "bump" | "map_bump" | "map_Bump" | "norm" => material.normal_map = map(),
+            _ => {}
+        }
+    }
+    materials
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_obj_faces() {
+        let obj = "\
+            # Quad and triangle\n\
+            v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n\
+            vt 0 0\nvt 1 0.25\n\
+            vn 0 0 1\n\
+            f 1/1/1 2/2/1 3/2/1 4/1/1\n\
+            f -4//-1 -3//-1 -2//-1 # relative indices\n";
+        let data = parse_obj(obj).unwrap();
+        assert_eq!(data.meshes.len(), 1);
+        let mesh = &data.meshes[0];
+        assert_eq!(mesh.material, None);
+        // Fan triangulation of the quad, then the triangle
+        let expected = [0, 1, 2, 0, 2, 3, 0, 1, 2].map(|i| [Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 0.0), Vector3::new(0.0, 1.0, 0.0)][i]);
+        assert_eq!(mesh.vertices, expected);
+        // Texcoords are flipped, corners without one get zero
+        assert_eq!(mesh.texcoords[..3], [Vector2::new(0.0, 1.0), Vector2::new(1.0, 0.75), Vector2::new(1.0, 0.75)]);
+        assert_eq!(mesh.texcoords[6..], [Vector2::ZERO; 3]);
+        assert_eq!(mesh.normals, [Vector3::new(0.0, 0.0, 1.0); 9]);
+    }
+
+    #[test]
+    fn test_parse_obj_materials() {
+        let obj = "mtllib scene materials.mtl\nv 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\nusemtl red\nusemtl blue\nf 3 2 1\nusemtl red\nf 1 2 3\n";
+        let data = parse_obj(obj).unwrap();
+        assert_eq!(data.material_libs, ["scene materials.mtl"]);
+        let materials: Vec<Option<&str>> = data.meshes.iter().map(|mesh| mesh.material.as_deref()).collect();
+        assert_eq!(materials, [None, Some("blue"), Some("red")]);
+        assert!(data.meshes.iter().all(|mesh| mesh.vertices.len() == 3 && mesh.texcoords.is_empty() && mesh.normals.is_empty()));
+    }
+
+    #[test]
+    fn test_parse_obj_invalid() {
+        assert!(parse_obj("v 0 0 0\nf 1 2 3\n").is_none());
+        assert!(parse_obj("v 0 0 0\nf 0 1 1\n").is_none());
+        assert!(parse_obj("v 0 zero 0\n").is_none());
+        assert!(parse_obj("v 0 0 0\nf 1/2 1 1\n").is_none());
+        assert!(parse_obj("").unwrap().meshes.is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_parse_mtl() {
+        let mtl = "Kd 0 0 0\nnewmtl red\nKd 1 0 0\nKs 0.5 0.5 0.5\nNs 32\nmap_Kd -bm 1.0 red.png\nbump red_normal.png\n\nnewmtl plain\nKe 0 0 1\n";
+        let materials = parse_mtl(mtl);
+        assert_eq!(materials.len(), 2);
+        let red = &materials[0];
+        assert_eq!((red.name.as_str(), red.diffuse, red.specular, red.shininess), ("red", rgb(255, 0, 0), rgb(128, 128, 128), 32.0));
+        assert_eq!((red.diffuse_map.as_deref(), red.normal_map.as_deref()), (Some("red.png"), Some("red_normal.png")));
+        assert_eq!(materials[1], MtlMaterial { emission: rgb(0, 0, 255), ..MtlMaterial::new("plain") });
+    }
+}
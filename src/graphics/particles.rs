@@ -0,0 +1,453 @@
+//! CPU simulated particle emitters
+
+use crate::{prelude::*, shapes::draw_shapes_quads};
+
+/// Particle emitter configuration
+#[derive(Debug, Clone)]
+pub struct EmitterConfig {
+    /// Emitter position, new particles spawn here
+    pub position: Position2,
+    /// Particles emitted per second
+    pub rate: f32,
+    /// Particles emitted at once when the emitter is created
+    pub burst: usize,
+    /// Maximum number of alive particles, pool size
+    /// NOTE: Only read by [`ParticleEmitter::new`], the pool is never reallocated
+    pub max_particles: usize,
+    /// Particle lifetime range (min, max)
+    pub lifetime: (Seconds, Seconds),
+    /// Initial velocity cone center direction
+    pub direction: Radians,
+    /// Initial velocity cone half-angle
+    pub spread: Radians,
+    /// Initial speed range (min, max), in pixels per second
+    pub speed: (f32, f32),
+    /// Initial angular velocity range (min, max), in radians per second
+    pub angular_velocity: (f32, f32),
+    /// Constant acceleration, in pixels per second squared
+    pub gravity: Vector2,
+    /// Velocity reduction factor per second
+    pub linear_damping: f32,
+    /// Angular velocity reduction factor per second
+    pub angular_damping: f32,
+    /// Particle size at birth, in pixels
+    pub size_start: Pixels,
+    /// Particle size at death, in pixels
+    pub size_end: Pixels,
+    /// Easing applied to size over life, maps life `[0..1]` to amount `[0..1]`
+    pub size_easing: fn(Percent) -> Percent,
+    /// Particle color over life
+    pub color: Gradient,
+    /// Particle texture, `None` to draw with the shapes texture
+    pub texture: Option<SharedTexture>,
+    /// Texture source rectangle
+    pub source: Rectangle,
+    /// Draw oldest particles first (below younger ones), pool order otherwise
+    pub sort_by_age: bool,
+    /// Random generator seed, same seed produces the same simulation
+    pub seed: u64,
+}
+
+impl Default for EmitterConfig {
+    fn default() -> Self {
+        Self {
+            position: Vector2::ZERO,
+            rate: 10.0,
+            burst: 0,
+            max_particles: 1000,
+            lifetime: (1.0, 1.0),
+            direction: -Radians::FRAC_PI_2,
+            spread: Radians::ZERO,
+            speed: (0.0, 0.0),
+            angular_velocity: (0.0, 0.0),
+            gravity: Vector2::ZERO,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            size_start: 1.0,
+            size_end: 1.0,
+            size_easing: |t| t,
+            color: Gradient::linear(Color::WHITE, Color::WHITE),
+            texture: None,
+            source: Rectangle::new(0.0, 0.0, 1.0, 1.0),
+            sort_by_age: false,
+            seed: 0,
+        }
+    }
+}
+
+/// Simulated particle
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Particle {
+    pub position: Position2,
+    pub velocity: Vector2,
+    pub rotation: Radians,
+    pub angular_velocity: f32,
+    /// Time since birth
+    pub age: Seconds,
+    /// Time of death, relative to birth
+    pub lifetime: Seconds,
+}
+
+impl Particle {
+    /// Get life progress, `0` at birth to `1` at death
+    #[inline]
+    #[must_use]
+    pub fn life(&self) -> Percent {
+        if self.lifetime > 0.0 { (self.age / self.lifetime).min(1.0) } else { 1.0 }
+    }
+}
+
+/// Particle ready to be drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticleInstance {
+    pub position: Position2,
+    pub size: Pixels,
+    pub rotation: Radians,
+    pub color: Color,
+}
+
+impl ParticleInstance {
+    /// Get sprite centered on the particle position
+    #[must_use]
+    pub fn sprite(&self) -> Sprite {
+        Sprite {
+            dest: Rectangle::new(self.position.x, self.position.y, self.size, self.size),
+            origin: Vector2::new(self.size*0.5, self.size*0.5),
+            rotation: self.rotation.to_degrees(),
+            tint: self.color,
+        }
+    }
+}
+
+/// Particle emitter, simulates particles in a fixed-capacity pool
+/// NOTE: No allocation happens after creation, dead particles are swap-removed
+#[derive(Debug, Clone)]
+pub struct ParticleEmitter {
+    pub config: EmitterConfig,
+    particles: Vec<Particle>,
+    /// Pool size, fixed at creation
+    capacity: usize,
+    rng: RandomGenerator,
+    /// Fractional particles pending emission
+    emit_accumulator: f32,
+}
+
+impl ParticleEmitter {
+    /// Create emitter, emitting the configured burst
+    #[must_use]
+    pub fn new(config: EmitterConfig) -> Self {
+        let mut emitter = Self {
+            particles: Vec::with_capacity(config.max_particles),
+            capacity: config.max_particles,
+            rng: RandomGenerator::new(config.seed),
+            emit_accumulator: 0.0,
+            config,
+        };
+        emitter.emit(emitter.config.burst);
+        emitter
+    }
+
+    /// Get alive particles, in pool order
+    #[inline]
+    #[must_use]
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// Get pool size, maximum number of alive particles
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Emit particles immediately, limited by pool capacity
+    pub fn emit(&mut self, count: usize) {
+        let count = count.min(self.capacity.saturating_sub(self.particles.len()));
+        for _ in 0..count {
+            let config = &self.config;
+            let angle = config.direction + Radians(self.rng.range_f32(-config.spread.value(), config.spread.value()));
+            let speed = self.rng.range_f32(config.speed.0, config.speed.1);
+            let particle = Particle {
+                position: config.position,
+                velocity: Vector2::new(angle.cos(), angle.sin()) * speed,
+                rotation: Radians::ZERO,
+                angular_velocity: self.rng.range_f32(config.angular_velocity.0, config.angular_velocity.1),
+                age: 0.0,
+                lifetime: self.rng.range_f32(config.lifetime.0, config.lifetime.1),
+            };
+            self.particles.push(particle);
+        }
+    }
+
+    /// Simulate particles and emit new ones
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+    pub fn update(&mut self, dt: Seconds) {
+        let config = &self.config;
+        let linear_damping = 1.0 / (1.0 + config.linear_damping * dt);
+        let angular_damping = 1.0 / (1.0 + config.angular_damping * dt);
+
+        let mut i = 0;
+        while i < self.particles.len() {
+            let particle = &mut self.particles[i];
+            particle.age += dt;
+            if particle.age >= particle.lifetime {
+                self.particles.swap_remove(i);
+                continue;
+            }
+
+            particle.velocity += config.gravity * dt;
+            particle.velocity *= linear_damping;
+            particle.position += particle.velocity * dt;
+            particle.angular_velocity *= angular_damping;
+            particle.rotation += Radians(particle.angular_velocity * dt);
+            i += 1;
+        }
+
+        self.emit_accumulator += config.rate * dt;
+        let count = self.emit_accumulator.floor();
+        self.emit_accumulator -= count;
+        self.emit(count as usize);
+    }
+
+    /// Get particle size at its current age
+    #[must_use]
+    pub fn size_of(&self, particle: &Particle) -> Pixels {
+        let amount = (self.config.size_easing)(particle.life());
+        self.config.size_start.lerp_to(self.config.size_end, amount)
+    }
+
+    /// Get particle color at its current age
+    pub fn color_of(&self, particle: &Particle) -> Color {
+        self.config.color.sample(particle.life())
+    }
+
+    /// Get particles ready to be drawn, in pool order
+    pub fn instances(&self) -> impl Iterator<Item = ParticleInstance> + '_ {
+        self.particles.iter().map(|particle| ParticleInstance {
+            position: particle.position,
+            size: self.size_of(particle),
+            rotation: particle.rotation,
+            color: self.color_of(particle),
+        })
+    }
+
+    /// Get particles ready to be drawn, oldest first (drawn below younger ones)
+    #[must_use]
+    pub fn instances_by_age(&self) -> Vec<ParticleInstance> {
+        let mut order: Vec<&Particle> = self.particles.iter().collect();
+        order.sort_by(|a, b| b.age.total_cmp(&a.age));
+        order.into_iter()
+            .map(|particle| ParticleInstance {
+                position: particle.position,
+                size: self.size_of(particle),
+                rotation: particle.rotation,
+                color: self.color_of(particle),
+            })
+            .collect()
+    }
+
+    /// Draw particles with the configured texture and source, in the configured order
+    /// NOTE: Particles are drawn as plain quads with the shapes texture if no texture is set
+    pub fn draw(&self, core: &mut Core) {
+        if self.config.sort_by_age {
+            self.draw_instances(core, self.instances_by_age());
+        } else {
+            self.draw_instances(core, self.instances());
+        }
+    }
+
+    fn draw_instances(&self, core: &mut Core, instances: impl IntoIterator<Item = ParticleInstance>) {
+        let sprites = instances.into_iter().map(|instance| instance.sprite());
+        match &self.config.texture {
+            Some(texture) => draw_sprites(core, texture, self.config.source, sprites),
+            None => draw_shapes_quads(core, sprites.map(|sprite| (sprite.corners(), [sprite.tint; 4]))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rlgl::{FlushReason, tests::{draw_calls, recording_rlgl, GlCall, Upload}};
+
+    /// Emitter without randomness, every particle gets the given lifetime
+    fn fixed_emitter(rate: f32, burst: usize, lifetime: Seconds) -> ParticleEmitter {
+        ParticleEmitter::new(EmitterConfig { rate, burst, lifetime: (lifetime, lifetime), ..EmitterConfig::default() })
+    }
+
+    #[test]
+    fn test_particle_count_over_time() {
+        let mut emitter = fixed_emitter(4.0, 3, 10.0);
+        assert_eq!(emitter.particles().len(), 3);
+        for step in 1..=8 {
+            emitter.update(0.25);
+            assert_eq!(emitter.particles().len(), 3 + step);
+        }
+
+        // Fractional emissions accumulate over updates
+        let mut emitter = fixed_emitter(3.0, 0, 10.0);
+        let counts: Vec<usize> = (0..6).map(|_| { emitter.update(0.25); emitter.particles().len() }).collect();
+        assert_eq!(counts, [0, 1, 2, 3, 3, 4]);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_particle_lifetime_expiry() {
+        let mut emitter = fixed_emitter(0.0, 5, 1.0);
+        emitter.update(0.5);
+        assert_eq!(emitter.particles().len(), 5);
+        assert!(emitter.particles().iter().all(|particle| particle.life() == 0.5));
+        emitter.update(0.5);
+        assert!(emitter.particles().is_empty());
+
+        // Dead particles are removed before new ones are emitted
+        let mut emitter = fixed_emitter(2.0, 0, 0.5);
+        emitter.update(0.5);
+        emitter.update(0.5);
+        assert_eq!(emitter.particles().len(), 1);
+        assert_eq!(emitter.particles()[0].age, 0.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_particle_motion() {
+        let mut emitter = ParticleEmitter::new(EmitterConfig {
+            rate: 0.0,
+            burst: 1,
+            lifetime: (10.0, 10.0),
+            position: Vector2::new(1.0, 2.0),
+            direction: Radians::ZERO,
+            speed: (10.0, 10.0),
+            angular_velocity: (2.0, 2.0),
+            gravity: Vector2::new(0.0, 4.0),
+            ..EmitterConfig::default()
+        });
+        emitter.update(0.5);
+        let particle = emitter.particles()[0];
+        // Velocity is updated before position (semi-implicit Euler)
+        assert_eq!(particle.velocity, Vector2::new(10.0, 2.0));
+        assert_eq!(particle.position, Vector2::new(6.0, 3.0));
+        assert_eq!(particle.rotation, Radians(1.0));
+
+        // Damping divides velocity by (1 + damping*dt)
+        emitter.config.gravity = Vector2::ZERO;
+        emitter.config.linear_damping = 2.0;
+        emitter.config.angular_damping = 2.0;
+        emitter.update(0.5);
+        let particle = emitter.particles()[0];
+        assert_eq!(particle.velocity, Vector2::new(5.0, 1.0));
+        assert_eq!(particle.angular_velocity, 1.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_particle_over_life_curves() {
+        let mut emitter = ParticleEmitter::new(EmitterConfig {
+            rate: 0.0,
+            burst: 1,
+            lifetime: (2.0, 2.0),
+            size_start: 2.0,
+            size_end: 10.0,
+            size_easing: |t| t*t,
+            color: Gradient::linear(Color::new(0, 0, 0, 255), Color::new(200, 100, 0, 55)),
+            ..EmitterConfig::default()
+        });
+        let sample = |emitter: &ParticleEmitter| {
+            let instance = emitter.instances().next().unwrap();
+            (instance.size, instance.color)
+        };
+        assert_eq!(sample(&emitter), (2.0, Color::new(0, 0, 0, 255)));
+        emitter.update(1.0);
+        assert_eq!(sample(&emitter), (4.0, Color::new(100, 50, 0, 155)));
+        emitter.update(0.5);
+        assert_eq!(sample(&emitter), (6.5, Color::new(150, 75, 0, 105)));
+    }
+
+    #[test]
+    fn test_particle_pool_capacity() {
+        let mut emitter = ParticleEmitter::new(EmitterConfig { rate: 1000.0, burst: 10, max_particles: 4, ..EmitterConfig::default() });
+        assert_eq!((emitter.particles().len(), emitter.capacity()), (4, 4));
+
+        // Pool size is fixed at creation, changing the config doesn't underflow or grow the pool
+        let pool = emitter.particles().as_ptr();
+        emitter.config.max_particles = 2;
+        emitter.emit(5);
+        emitter.config.max_particles = 100;
+        emitter.emit(5);
+        assert_eq!(emitter.particles().len(), 4);
+
+        // Dead particles free their slots for new ones, without reallocating
+        for _ in 0..100 {
+            emitter.update(0.3);
+            assert!(emitter.particles().len() <= 4);
+        }
+        assert_eq!(emitter.particles().len(), 4);
+        assert_eq!(emitter.particles().as_ptr(), pool);
+    }
+
+    #[test]
+    fn test_particle_determinism() {
+        let config = EmitterConfig {
+            rate: 50.0,
+            burst: 10,
+            lifetime: (0.5, 2.0),
+            spread: Radians(1.0),
+            speed: (10.0, 50.0),
+            angular_velocity: (-1.0, 1.0),
+            seed: 42,
+            ..EmitterConfig::default()
+        };
+        let simulate = |config: &EmitterConfig| {
+            let mut emitter = ParticleEmitter::new(config.clone());
+            for _ in 0..60 {
+                emitter.update(1.0/60.0);
+            }
+            emitter.particles().to_vec()
+        };
+        let particles = simulate(&config);
+        assert!(!particles.is_empty());
+        assert_eq!(particles, simulate(&config));
+        assert_ne!(particles, simulate(&EmitterConfig { seed: 7, ..config }));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_particle_draw() {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+        let texture = Texture::load_from_image(&mut core, &Image::blank(4, 4, PixelFormat::UncompressedR8G8B8A8)).unwrap().into_shared();
+        let mut emitter = ParticleEmitter::new(EmitterConfig {
+            rate: 0.0,
+            burst: 3,
+            size_start: 2.0,
+            size_end: 2.0,
+            texture: Some(texture.clone()),
+            ..EmitterConfig::default()
+        });
+        emitter.update(0.5);
+
+        // All particles in a single draw with the configured texture
+        calls.borrow_mut().clear();
+        emitter.draw(&mut core);
+        core.rlgl.draw_render_batch(FlushReason::Explicit);
+        assert_eq!(draw_calls(&calls), [GlCall::BindTexture(texture.id), GlCall::DrawElements { count: 18, offset: 0 }, GlCall::BindTexture(0)]);
+
+        // Quads are centered on the particle position
+        let positions = calls.borrow().iter().find_map(|call| match call {
+            GlCall::BufferSubData(Upload::F32(data)) => Some(data.clone()),
+            _ => None,
+        }).unwrap();
+        let corners: Vec<[f32; 2]> = positions[..12].chunks_exact(3).map(|v| [v[0], v[1]]).collect();
+        assert_eq!(corners, [[-1.0, -1.0], [-1.0, 1.0], [1.0, 1.0], [1.0, -1.0]]);
+
+        // Without texture particles are plain quads
+        calls.borrow_mut().clear();
+        emitter.config.texture = None;
+        emitter.config.sort_by_age = true;
+        emitter.draw(&mut core);
+        core.rlgl.draw_render_batch(FlushReason::Explicit);
+        assert!(matches!(draw_calls(&calls)[..], [GlCall::BindTexture(id), GlCall::DrawElements { count: 18, .. }, GlCall::BindTexture(0)] if id != texture.id));
+    }
+}
@@ -1,5 +1,7 @@
 /// Pixel formats
 /// NOTE: Support depends on OpenGL version and platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum PixelFormat {
     /** 8 bit per pixel (no alpha)         */ UncompressedGrayscale = 1,
     /** 8*2 bpp (2 channels)               */ UncompressedGrayAlpha,
@@ -27,11 +29,87 @@ pub enum PixelFormat {
     /** 2 bpp                              */ CompressedAstc8x8RGBA,
 }
 
+impl PixelFormat {
+    /// Get bytes per pixel for uncompressed formats, `None` for block-compressed formats
+    #[must_use]
+    pub const fn bytes_per_pixel(self) -> Option<usize> {
+        match self {
+            Self::UncompressedGrayscale => Some(1),
+            Self::UncompressedGrayAlpha
+            | Self::UncompressedR5G6B5
+            | Self::UncompressedR5G5B5A1
+            | Self::UncompressedR4G4B4A4
+            | Self::UncompressedR16 => Some(2),
+            Self::UncompressedR8G8B8 => Some(3),
+            Self::UncompressedR8G8B8A8
+            | Self::UncompressedR32 => Some(4),
+            Self::UncompressedR16G16B16 => Some(6),
+            Self::UncompressedR16G16B16A16 => Some(8),
+            Self::UncompressedR32G32A32 => Some(12),
+            Self::UncompressedR32G32A32A32 => Some(16),
+            _ => None,
+        }
+    }
+
+    /// Get bits per pixel, block-compressed formats included
+    #[must_use]
+    pub const fn bits_per_pixel(self) -> usize {
+        match self {
+            Self::CompressedDxt1RGB
+            | Self::CompressedDxt1RGBA
+            | Self::CompressedEtc1RGB
+            | Self::CompressedEtc2RGB
+            | Self::CompressedPvrtRGB
+            | Self::CompressedPvrtRGBA => 4,
+            Self::CompressedDxt3RGBA
+            | Self::CompressedDxt5RGBA
+            | Self::CompressedEtc2EacRGBA
+            | Self::CompressedAstc4x4RGBA => 8,
+            Self::CompressedAstc8x8RGBA => 2,
+            _ => match self.bytes_per_pixel() {
+                Some(bytes) => bytes*8,
+                None => 0,
+            },
+        }
+    }
+
+    /// Check if format is block-compressed
+    #[must_use]
+    pub const fn is_compressed(self) -> bool {
+        self.bytes_per_pixel().is_none()
+    }
+
+    /// Get pixel data size in bytes for certain format
+    /// NOTE: Size can be requested for Image or Texture data
+    #[must_use]
+    pub const fn data_size(self, width: usize, height: usize) -> usize {
+        // Most compressed formats works on 4x4 blocks,
+        // if texture is smaller, minimum dataSize is 8 or 16
+        if width < 4 && height < 4 {
+            match self {
+                Self::CompressedDxt1RGB | Self::CompressedDxt1RGBA => return 8,
+                Self::CompressedDxt3RGBA
+                | Self::CompressedDxt5RGBA
+                | Self::CompressedEtc1RGB
+                | Self::CompressedEtc2RGB
+                | Self::CompressedEtc2EacRGBA
+                | Self::CompressedPvrtRGB
+                | Self::CompressedPvrtRGBA
+                | Self::CompressedAstc4x4RGBA => return 16,
+                _ => {}
+            }
+        }
+        width*height*self.bits_per_pixel()/8
+    }
+}
+
 // Texture parameters: filter mode
 // NOTE 1: Filtering considers mipmaps if available in the texture
 // NOTE 2: Filter is accordingly set for minification and magnification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
 pub enum TextureFilter {
-    /** No filter, just pixel approximation       */ Point,
+    /** No filter, just pixel approximation       */ #[default] Point,
     /** Linear filtering                          */ Bilinear,
     /** Trilinear filtering (linear with mipmaps) */ Trilinear,
     /** Anisotropic filtering 4x                  */ Anisotropic4x,
@@ -48,6 +126,7 @@ pub enum TextureWrap {
 }
 
 // Cubemap layouts
+#[non_exhaustive]
 pub enum CubemapLayout {
     /** Automatically detect layout type                    */ AutoDetect,
     /** Layout is defined by a vertical line with faces     */ LineVertical,
@@ -0,0 +1,394 @@
+//! Minimal PNG encoder, 8 bit per channel, no filtering
+//!
+//! Minimal PNG decoder, non-interlaced images of any color type, converted to 8 bit per channel
+
+use crate::{external::{sdefl, sinfl}, prelude::*, utils::crc32_update};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// CRC-32 over several chunks, as if they were contiguous
+fn crc32(chunks: &[&[u8]]) -> u32 {
+    !chunks.iter().fold(!0, |crc, chunk| crc32_update(crc, chunk))
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    adler32_update(1, data)
+}
+
+/// Update Adler-32 checksum with more data
+fn adler32_update(adler: u32, data: &[u8]) -> u32 {
+    let (mut a, mut b) = (adler & 0xFFFF, adler >> 16);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+fn write_chunk(out: &mut Vec<u8>, ty: [u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(&ty);
+    out.extend_from_slice(data);
+    out.extend_from_slice(&crc32(&[&ty, data]).to_be_bytes());
+}
+
+/// Wrap data in a zlib stream
+fn zlib_deflate(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x9C];
+    out.extend_from_slice(&sdefl::deflate(data));
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// Encode image as PNG file data
+/// NOTE: Only 8 bit per channel formats are supported: grayscale, gray-alpha, R8G8B8 and R8G8B8A8
+pub(crate) fn encode(image: &Image) -> Option<Vec<u8>> {
+    let (color_type, channels) = match image.format {
+        PixelFormat::UncompressedGrayscale => (0, 1),
+        PixelFormat::UncompressedGrayAlpha => (4, 2),
+        PixelFormat::UncompressedR8G8B8 => (2, 3),
+        PixelFormat::UncompressedR8G8B8A8 => (6, 4),
+        _ => return None,
+    };
+    let stride = image.width * channels;
+    if image.data.len() < stride * image.height {
+        return None;
+    }
+
+    // Every scanline is prefixed by its filter type (0: None)
+    let mut raw = Vec::with_capacity((stride + 1) * image.height);
+    for row in image.data.chunks_exact(stride.max(1)).take(image.height) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&u32::try_from(image.width).ok()?.to_be_bytes());
+    header.extend_from_slice(&u32::try_from(image.height).ok()?.to_be_bytes());
+    header.extend_from_slice(&[8, color_type, 0, 0, 0]);
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&PNG_SIGNATURE);
+    write_chunk(&mut out, *b"IHDR", &header);
+    write_chunk(&mut out, *b"IDAT", &zlib_deflate(&raw));
+    write_chunk(&mut out, *b"IEND", &[]);
+    Some(out)
+}
+
+/// Paeth predictor
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = i16::from(a) + i16::from(b) - i16::from(c);
+    let (pa, pb, pc) = ((p - i16::from(a)).abs(), (p - i16::from(b)).abs(), (p - i16::from(c)).abs());
+    if pa <= pb && pa <= pc { a } else if pb <= pc { b } else { c }
+}
+
+/// Reverse a scanline filter, `previous` is all zeros for the first row
+fn unfilter_row(filter: u8, line: &[u8], previous: &[u8], current: &mut [u8], bpp: usize) -> Option<()> {
+    for i in 0..current.len() {
+        let a = if i >= bpp { current[i - bpp] } else { 0 };
+        let b = previous[i];
+        let c = if i >= bpp { previous[i - bpp] } else { 0 };
+        current[i] = line[i].wrapping_add(match filter {
+            0 => 0,
+            1 => a,
+            2 => b,
+            3 => a.midpoint(b),
+            4 => paeth(a, b, c),
+            _ => return None,
+        });
+    }
+    Some(())
+}
+
+/// PNG file decoder, chunks are parsed up front and image data is decoded on demand
+/// NOTE: Interlaced images are not supported, 16 bit channels are reduced to 8 bit,
+/// paletted images are expanded to R8G8B8 (or R8G8B8A8 with transparency),
+/// transparent color keys of grayscale and RGB images are ignored
+pub(crate) struct PngDecoder<'d> {
+    /// Image width in pixels
+    pub(crate) width: usize,
+    /// Image height in pixels
+    pub(crate) height: usize,
+    /// Decoded pixel format, 8 bit per channel
+    pub(crate) format: PixelFormat,
+    depth: usize,
+    color_type: u8,
+    channels: usize,
+    palette: &'d [u8],
+    transparency: &'d [u8],
+    /// Image data chunks, a single zlib stream once concatenated
+    compressed: Vec<&'d [u8]>,
+}
+
+impl<'d> PngDecoder<'d> {
+    /// Parse PNG file data, checking chunks CRC and header values
+    pub(crate) fn new(data: &'d [u8]) -> Option<Self> {
+        let mut rest = data.strip_prefix(&PNG_SIGNATURE)?;
+        let mut header = None;
+        let mut palette: &[u8] = &[];
+        let mut transparency: &[u8] = &[];
+        let mut compressed = Vec::new();
+
+        while let Some((len, after)) = rest.split_first_chunk::<4>() {
+            let len = u32::from_be_bytes(*len) as usize;
+            let ty = after.get(..4)?;
+            let chunk = after.get(4..4 + len)?;
+            let crc = after.get(4 + len..8 + len)?;
+            if u32::from_be_bytes([crc[0], crc[1], crc[2], crc[3]]) != crc32(&[ty, chunk]) {
+                return None;
+            }
+            rest = &after[8 + len..];
+
+            match ty {
+                b"IHDR" => header = Some(chunk),
+                b"PLTE" => palette = chunk,
+                b"tRNS" => transparency = chunk,
+                b"IDAT" => compressed.push(chunk),
+                b"IEND" => break,
+                _ => {}
+            }
+        }
+
+        let header = header.filter(|header| header.len() == 13)?;
+        let width = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let height = u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        let (depth, color_type, interlace) = (usize::from(header[8]), header[9], header[12]);
+        if width == 0 || height == 0 || interlace != 0 {
+            return None;
+        }
+        let channels = match (color_type, depth) {
+            (0, 1 | 2 | 4 | 8 | 16) | (3, 1 | 2 | 4 | 8) => 1,
+            (2, 8 | 16) => 3,
+            (4, 8 | 16) => 2,
+            (6, 8 | 16) => 4,
+            _ => return None,
+        };
+        let format = match (color_type, transparency.is_empty()) {
+            (0, _) => PixelFormat::UncompressedGrayscale,
+            (2, _) | (3, true) => PixelFormat::UncompressedR8G8B8,
+            (4, _) => PixelFormat::UncompressedGrayAlpha,
+            _ => PixelFormat::UncompressedR8G8B8A8,
+        };
+
+        Some(Self { width, height, format, depth, color_type, channels, palette, transparency, compressed })
+    }
+
+    /// Reduce the samples of an unfiltered row to 8 bit, expanding palette indices
+    #[allow(clippy::cast_possible_truncation)]
+    fn expand_row(&self, row: &[u8], out: &mut Vec<u8>) -> Option<()> {
+        let depth = self.depth;
+        let max = (1u16 << depth.min(8)) - 1;
+        let sample = |i: usize| -> u8 {
+            match depth {
+                16 => row[2 * i],
+                8 => row[i],
+                _ => {
+                    let bit = i * depth;
+                    let value = (row[bit / 8] >> (8 - depth - bit % 8)) & max as u8;
+                    if self.color_type == 3 { value } else { (u16::from(value) * 255 / max) as u8 }
+                }
+            }
+        };
+
+        out.clear();
+        if self.color_type == 3 {
+            for x in 0..self.width {
+                let index = usize::from(sample(x));
+                out.extend_from_slice(self.palette.get(3 * index..3 * index + 3)?);
+                if self.format == PixelFormat::UncompressedR8G8B8A8 {
+                    out.push(self.transparency.get(index).copied().unwrap_or(255));
+                }
+            }
+        } else {
+            out.extend((0..self.width * self.channels).map(sample));
+        }
+        Some(())
+    }
+
+    /// Decode image rows top to bottom, 8 bit per channel in the decoder `format`
+    /// NOTE: Decompressed data is streamed, only a couple of scanlines are kept in memory.
+    /// Rows are passed as they are decoded, the zlib checksum is checked at the end
+    pub(crate) fn decode_rows(&self, mut on_row: impl FnMut(&[u8])) -> Option<()> {
+        let data = self.compressed.concat();
+        let (&[cmf, flg], stream) = data.split_first_chunk::<2>()?;
+        if cmf & 0x0F != 8 || (u16::from(cmf) << 8 | u16::from(flg)) % 31 != 0 || flg & 0x20 != 0 {
+            return None;
+        }
+
+        let stride = self.width.checked_mul(self.channels * self.depth)?.div_ceil(8);
+        let bpp = (self.channels * self.depth / 8).max(1);
+        let (mut line, mut previous, mut current) = (Vec::with_capacity(stride + 1), vec![0u8; stride], vec![0u8; stride]);
+        let mut row = Vec::new();
+        let (mut y, mut valid, mut adler) = (0, true, 1);
+
+        let (_, used) = sinfl::inflate_stream(stream, usize::MAX, &mut |mut piece| {
+            adler = adler32_update(adler, piece);
+            while valid && y < self.height && !piece.is_empty() {
+                let len = (stride + 1 - line.len()).min(piece.len());
+                line.extend_from_slice(&piece[..len]);
+                piece = &piece[len..];
+                if line.len() == stride + 1 {
+                    valid = unfilter_row(line[0], &line[1..], &previous, &mut current, bpp).is_some()
+                        && self.expand_row(&current, &mut row).is_some();
+                    if valid {
+                        on_row(&row);
+                    }
+                    std::mem::swap(&mut previous, &mut current);
+                    line.clear();
+                    y += 1;
+                }
+            }
+        }).ok()?;
+
+        let checksum = stream.get(used..used + 4)?;
+        (valid && y == self.height && u32::from_be_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]) == adler).then_some(())
+    }
+}
+
+/// Decode PNG file data into an 8 bit per channel image, see [`PngDecoder`]
+pub(crate) fn decode(data: &[u8]) -> Option<Image> {
+    let decoder = PngDecoder::new(data)?;
+    let mut out = Vec::with_capacity(decoder.width.checked_mul(decoder.height)?.checked_mul(decoder.format.bytes_per_pixel()?)?);
+    decoder.decode_rows(|row| out.extend_from_slice(row))?;
+
+    Some(Image {
+        data: out,
+        width: decoder.width,
+        height: decoder.height,
+        mipmap: 1,
+        format: decoder.format,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build PNG file data from raw (filtered) scanlines
+    fn png_file(width: u32, height: u32, depth: u8, color_type: u8, chunks: &[([u8; 4], &[u8])], raw: &[u8]) -> Vec<u8> {
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&width.to_be_bytes());
+        header.extend_from_slice(&height.to_be_bytes());
+        header.extend_from_slice(&[depth, color_type, 0, 0, 0]);
+
+        let mut out = PNG_SIGNATURE.to_vec();
+        write_chunk(&mut out, *b"IHDR", &header);
+        for &(ty, data) in chunks {
+            write_chunk(&mut out, ty, data);
+        }
+        // Image data split in two chunks
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(raw, 6);
+        let (first, second) = compressed.split_at(compressed.len()/2);
+        write_chunk(&mut out, *b"IDAT", first);
+        write_chunk(&mut out, *b"IDAT", second);
+        write_chunk(&mut out, *b"IEND", &[]);
+        out
+    }
+
+    /// Filter a scanline with the given filter type, inverse of `unfilter_row`
+    fn filter_row(filter: u8, row: &[u8], previous: &[u8], bpp: usize) -> Vec<u8> {
+        let mut line = vec![filter];
+        for i in 0..row.len() {
+            let a = if i >= bpp { row[i - bpp] } else { 0 };
+            let b = previous[i];
+            let c = if i >= bpp { previous[i - bpp] } else { 0 };
+            line.push(row[i].wrapping_sub(match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => a.midpoint(b),
+                _ => paeth(a, b, c),
+            }));
+        }
+        line
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn gradient(width: usize, height: usize, channels: usize) -> Vec<u8> {
+        (0..width*height*channels).map(|i| (i*7 + i/(width*channels)*31) as u8).collect()
+    }
+
+    #[test]
+    fn test_png_round_trip() {
+        for (format, channels) in [
+            (PixelFormat::UncompressedGrayscale, 1),
+            (PixelFormat::UncompressedGrayAlpha, 2),
+            (PixelFormat::UncompressedR8G8B8, 3),
+            (PixelFormat::UncompressedR8G8B8A8, 4),
+        ] {
+            let image = Image { data: gradient(13, 7, channels), width: 13, height: 7, mipmap: 1, format };
+            let decoded = decode(&encode(&image).unwrap()).unwrap();
+            assert_eq!((decoded.width, decoded.height, decoded.format), (13, 7, format));
+            assert_eq!(decoded.data, image.data, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn test_png_filters() {
+        let (width, height) = (9, 10);
+        let pixels = gradient(width, height, 3);
+        let stride = width*3;
+        let mut raw = Vec::new();
+        for (y, row) in pixels.chunks_exact(stride).enumerate() {
+            let previous = if y == 0 { vec![0; stride] } else { pixels[(y - 1)*stride..y*stride].to_vec() };
+            #[allow(clippy::cast_possible_truncation)]
+            raw.extend(filter_row((y%5) as u8, row, &previous, 3));
+        }
+
+        let file = png_file(9, 10, 8, 2, &[], &raw);
+        let image = decode(&file).unwrap();
+        assert_eq!(image.format, PixelFormat::UncompressedR8G8B8);
+        assert_eq!(image.data, pixels);
+
+        // Rows are streamed top to bottom
+        let decoder = PngDecoder::new(&file).unwrap();
+        let mut rows = Vec::new();
+        decoder.decode_rows(|row| rows.push(row.to_vec())).unwrap();
+        assert_eq!(rows.len(), height);
+        assert_eq!(rows.concat(), pixels);
+
+        // Unknown filter type
+        raw[0] = 5;
+        assert!(decode(&png_file(9, 10, 8, 2, &[], &raw)).is_none());
+        // Missing rows
+        assert!(decode(&png_file(9, 11, 8, 2, &[], &raw)).is_none());
+    }
+
+    #[test]
+    fn test_png_palette() {
+        // 2 bit indices, 5 pixels per row: 0 1 2 3 0
+        let raw = [0, 0b0001_1011, 0b0000_0000, 0, 0b1110_0100, 0b1100_0000];
+        let palette = [10, 11, 12, 20, 21, 22, 30, 31, 32, 40, 41, 42];
+        let image = decode(&png_file(5, 2, 2, 3, &[(*b"PLTE", &palette)], &raw)).unwrap();
+        assert_eq!(image.format, PixelFormat::UncompressedR8G8B8);
+        assert_eq!(image.data[..15], [10, 11, 12, 20, 21, 22, 30, 31, 32, 40, 41, 42, 10, 11, 12]);
+        assert_eq!(image.data[15..30], [40, 41, 42, 30, 31, 32, 20, 21, 22, 10, 11, 12, 40, 41, 42]);
+
+        let image = decode(&png_file(5, 2, 2, 3, &[(*b"PLTE", &palette), (*b"tRNS", &[0, 128])], &raw)).unwrap();
+        assert_eq!(image.format, PixelFormat::UncompressedR8G8B8A8);
+        assert_eq!(image.data[..8], [10, 11, 12, 0, 20, 21, 22, 128]);
+        assert_eq!(image.data[8..12], [30, 31, 32, 255]);
+    }
+
+    #[test]
+    fn test_png_invalid() {
+        let file = encode(&Image { data: gradient(4, 4, 4), width: 4, height: 4, mipmap: 1, format: PixelFormat::UncompressedR8G8B8A8 }).unwrap();
+        assert!(decode(&file[1..]).is_none());
+        // Corrupted chunk CRC
+        let mut corrupted = file.clone();
+        corrupted[20] ^= 1;
+        assert!(PngDecoder::new(&corrupted).is_none());
+        // Corrupted checksum of the zlib stream, found after rows were decoded
+        let idat = file.windows(4).position(|ty| ty == b"IDAT").unwrap();
+        let len = u32::from_be_bytes(file[idat - 4..idat].try_into().unwrap()) as usize;
+        let mut corrupted = file.clone();
+        corrupted[idat + 4 + len - 1] ^= 1;
+        let crc = crc32(&[b"IDAT", &corrupted[idat + 4..idat + 4 + len]]);
+        corrupted[idat + 4 + len..idat + 8 + len].copy_from_slice(&crc.to_be_bytes());
+        assert!(decode(&corrupted).is_none());
+    }
+}
@@ -1,15 +1,116 @@
-use crate::prelude::*;
+use std::sync::mpsc::Sender;
+use crate::{prelude::*, rlgl::{FramebufferAttachTextureType, FramebufferAttachType, GlObject}, tracelog};
 use super::GlFrameBufferID;
 
 /// `RenderTexture`, fbo for texture rendering
+/// NOTE: Framebuffer and attachments are unloaded on drop
+#[derive(Debug)]
 pub struct RenderTexture {
     /// OpenGL framebuffer object id
     pub id: GlFrameBufferID,
     /// Color buffer attachment texture
     pub texture: Texture,
-    /// Depth buffer attachment texture
+    /// Depth buffer attachment texture (renderbuffer, it can not be sampled)
     pub depth: Texture,
+    /// Release GPU objects on drop
+    unload_sender: Sender<GlObject>,
 }
 
 /// `RenderTexture2D`, same as `RenderTexture`
 pub type RenderTexture2D = RenderTexture;
+
+/// Render texture loading error
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RenderTextureError {
+    /// Framebuffer object can not be created
+    CreateFailed,
+    /// Color texture can not be loaded
+    Texture(TextureError),
+    /// Framebuffer is not complete, status is logged
+    Incomplete,
+}
+
+impl std::fmt::Display for RenderTextureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreateFailed => f.write_str("framebuffer object can not be created"),
+            Self::Texture(e) => e.fmt(f),
+            Self::Incomplete => f.write_str("framebuffer is not complete"),
+        }
+    }
+}
+
+impl std::error::Error for RenderTextureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Texture(e) => Some(e),
+            Self::CreateFailed | Self::Incomplete => None,
+        }
+    }
+}
+
+impl From<TextureError> for RenderTextureError {
+    fn from(value: TextureError) -> Self {
+        Self::Texture(value)
+    }
+}
+
+impl RenderTexture {
+    /// Load texture for rendering (framebuffer)
+    /// NOTE: Render texture is RGBA color texture with a depth renderbuffer
+    /// # Errors
+    /// Returns an error if the framebuffer or its color texture can not be created, or the framebuffer is not complete
+    pub fn load(core: &mut Core, width: usize, height: usize) -> Result<Self, RenderTextureError> {
+        let rlgl = &mut core.rlgl;
+        let id = rlgl.load_framebuffer(); // Load an empty framebuffer
+        if id == 0 {
+            tracelog!(Warning, "FBO: Framebuffer object can not be created");
+            return Err(RenderTextureError::CreateFailed);
+        }
+
+        // Create color texture (default to RGBA)
+        let texture_id = match rlgl.load_texture(None, width, height, PixelFormat::UncompressedR8G8B8A8, 1) {
+            Ok(texture_id) => texture_id,
+            Err(e) => {
+                rlgl.unload_framebuffer(id);
+                return Err(e.into());
+            }
+        };
+
+        // Create depth renderbuffer
+        let depth_id = rlgl.load_texture_depth(width, height);
+
+        // Attach color texture and depth renderbuffer to FBO
+        rlgl.framebuffer_attach(id, texture_id, FramebufferAttachType::ColorChannel0, FramebufferAttachTextureType::Texture2D, 0);
+        rlgl.framebuffer_attach(id, depth_id, FramebufferAttachType::Depth, FramebufferAttachTextureType::Renderbuffer, 0);
+
+        let target = Self {
+            id,
+            texture: Texture { id: texture_id, width, height, mipmap: 1, format: PixelFormat::UncompressedR8G8B8A8, filter: TextureFilter::Point, unload_sender: Some(rlgl.unload_sender()) },
+            // NOTE: Depth renderbuffer has no pixel format, 32 bit single channel is the closest one
+            // Renderbuffer is not a texture, it is unloaded by the render texture
+            depth: Texture { id: depth_id, width, height, mipmap: 1, format: PixelFormat::UncompressedR32, filter: TextureFilter::Point, unload_sender: None },
+            unload_sender: rlgl.unload_sender(),
+        };
+
+        // Check if fbo is complete with attachments (valid)
+        if !rlgl.framebuffer_complete(id) {
+            return Err(RenderTextureError::Incomplete);
+        }
+        tracelog!(Info, "FBO: [ID {id}] Framebuffer object created successfully");
+        Ok(target)
+    }
+}
+
+impl Drop for RenderTexture {
+    fn drop(&mut self) {
+        // NOTE: Objects are unloaded on the next batch draw, nothing to do if rlgl is gone
+        // Color texture is unloaded by its own drop
+        for object in [GlObject::Framebuffer(self.id), GlObject::Renderbuffer(self.depth.id)] {
+            if self.unload_sender.send(object).is_err() {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,389 @@
+//! Rich text markup: inline color, alpha and size changes
+//!
+//! Supported markup:
+//! - `[color=#RRGGBB]...[/color]` or `[color=#RRGGBBAA]...[/color]`: text color, replaces the default tint
+//! - `[alpha=0.5]...[/alpha]`: text opacity `[0..1]`, multiplies the color alpha
+//! - `[size=1.5]...[/size]`: text size relative to the requested font size
+//! - `[[`: literal `[`
+//!
+//! Tags can be nested, a closing tag restores the previous value of its attribute.
+//! Unknown tags and closing tags without an opening one are kept as literal text,
+//! tags left open are closed at the end of the text
+
+use crate::{config::TEXT_LINE_SPACING, graphics::font::draw_glyph, prelude::*};
+
+/// Text with uniform style, produced by [`parse_rich_text`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRun {
+    /// Run text, markup removed
+    pub text: String,
+    /// Text color, `None` to use the default tint
+    pub color: Option<Color>,
+    /// Text opacity, multiplies the color alpha
+    pub alpha: f32,
+    /// Text size, relative to the requested font size
+    pub size: f32,
+}
+
+impl TextRun {
+    /// Get run color, applying alpha to color or default tint
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn tint(&self, default_tint: Color) -> Color {
+        let color = self.color.unwrap_or(default_tint);
+        Color {
+            a: (f32::from(color.a) * self.alpha.clamp(0.0, 1.0)) as u8,
+            ..color
+        }
+    }
+
+    #[allow(clippy::float_cmp)]
+    fn has_style_of(&self, other: &Self) -> bool {
+        self.color == other.color && self.alpha == other.alpha && self.size == other.size
+    }
+}
+
+/// Glyph placement computed by [`layout_rich_text`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RichGlyph {
+    /// Index of the run the glyph belongs to
+    pub run: usize,
+    /// Index of the font drawing the glyph in the stack, 0 for a single font
+    pub font: usize,
+    /// Glyph layout, `byte_index` is relative to the run text
+    pub glyph: GlyphLayout,
+    /// Glyph color
+    pub tint: Color,
+}
+
+/// Parse hex color `#RRGGBB` or `#RRGGBBAA`
+fn parse_hex_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| hex.get(i..i + 2).and_then(|c| u8::from_str_radix(c, 16).ok());
+    Some(Color {
+        r: channel(0)?,
+        g: channel(2)?,
+        b: channel(4)?,
+        a: if hex.len() == 8 { channel(6)? } else { 255 },
+    })
+}
+
+/// Current style stacks, one per attribute
+#[derive(Default)]
+struct StyleStack {
+    color: Vec<Color>,
+    alpha: Vec<f32>,
+    size: Vec<f32>,
+}
+
+impl StyleStack {
+    fn run(&self) -> TextRun {
+        TextRun {
+            text: String::new(),
+            color: self.color.last().copied(),
+            alpha: self.alpha.last().copied().unwrap_or(1.0),
+            size: self.size.last().copied().unwrap_or(1.0),
+        }
+    }
+
+    /// Apply tag, returns false if the tag is not valid
+    fn apply(&mut self, tag: &str) -> bool {
+        match tag {
+            "/color" => self.color.pop().is_some(),
+            "/alpha" => self.alpha.pop().is_some(),
+            "/size" => self.size.pop().is_some(),
+            _ => match tag.split_once('=') {
+                Some(("color", value)) => parse_hex_color(value)
+                    .map(|color| self.color.push(color))
+                    .is_some(),
+                Some(("alpha", value)) => value.parse::<f32>().ok()
+                    .filter(|alpha| alpha.is_finite())
+                    .map(|alpha| self.alpha.push(alpha.clamp(0.0, 1.0)))
+                    .is_some(),
+                Some(("size", value)) => value.parse::<f32>().ok()
+                    .filter(|size| size.is_finite() && *size > 0.0)
+                    .map(|size| self.size.push(size))
+                    .is_some(),
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Parse rich text markup into runs of uniform style, empty runs are omitted
+#[must_use]
+pub fn parse_rich_text(text: &str) -> Vec<TextRun> {
+    let mut runs = Vec::new();
+    let mut styles = StyleStack::default();
+    let mut current = styles.run();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('[') {
+        current.text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("[[") {
+            current.text.push('[');
+            rest = after;
+            continue;
+        }
+
+        let Some(end) = rest.find(']') else { break };
+        let tag = &rest[1..end];
+        if tag.contains('[') {
+            // Not a tag, the next bracket may start one
+            current.text.push('[');
+            rest = &rest[1..];
+            continue;
+        }
+        if styles.apply(tag) {
+            let next = styles.run();
+            if !next.has_style_of(&current) {
+                let previous = std::mem::replace(&mut current, next);
+                if !previous.text.is_empty() {
+                    runs.push(previous);
+                }
+            }
+        } else {
+            // Unknown tag, keep it as text
+            current.text.push_str(&rest[..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+    current.text.push_str(rest);
+
+    if !current.text.is_empty() {
+        runs.push(current);
+    }
+
+    // Merge runs whose style ended up identical (e.g. `[size=1][/size]`)
+    runs.dedup_by(|next, previous| {
+        let merge = previous.has_style_of(next);
+        if merge {
+            previous.text.push_str(&next.text);
+        }
+        merge
+    });
+    runs
+}
+
+/// Layout runs glyph by glyph, returns glyphs and text size
+/// NOTE: Glyphs of different sizes share the line baseline, line height is the biggest size in the line
+#[allow(clippy::cast_precision_loss)]
+fn layout_runs(font: FontOrStack, runs: &[TextRun], font_size: f32, spacing: f32, default_tint: Color) -> (Vec<RichGlyph>, Vector2) {
+    let ascent = font.ascent(1.0);
+    let ascent = if ascent > 0.0 { ascent } else { 1.0 };
+
+    // Split text in lines of (run, byte index, char), with the biggest size in the line
+    let mut lines: Vec<(Vec<_>, f32)> = Vec::new();
+    let mut line: Vec<(usize, usize, char)> = Vec::new();
+    let mut line_size = 0.0f32;
+    for (run_index, run) in runs.iter().enumerate() {
+        for (byte_index, ch) in run.text.char_indices() {
+            if ch == '\n' {
+                lines.push((std::mem::take(&mut line), line_size));
+                line_size = 0.0;
+            } else {
+                line_size = line_size.max(font_size * run.size);
+                line.push((run_index, byte_index, ch));
+            }
+        }
+    }
+    lines.push((line, line_size));
+
+    let mut glyphs = Vec::new();
+    let mut size = Vector2::ZERO;
+    let mut line_top = 0.0;
+    for (line, line_size) in &lines {
+        let line_size = if *line_size > 0.0 { *line_size } else { font_size };
+        let mut pen_x = 0.0;
+
+        for &(run_index, byte_index, ch) in line {
+            let run = &runs[run_index];
+            let glyph_size = font_size * run.size;
+            let Some((font_index, glyph, rec)) = font.glyph_lookup(ch) else { continue };
+            let (Some(scale), Some(baseline)) = (font.scale(font_index, glyph_size), font.baseline_offset(font_index, glyph_size)) else { continue };
+            let padding = font.font(font_index).map_or(0.0, |font| font.glyph_padding as f32);
+
+            if ch != ' ' && ch != '\t' {
+                let top = line_top + (line_size - glyph_size) * ascent + baseline;
+                glyphs.push(RichGlyph {
+                    run: run_index,
+                    font: font_index,
+                    glyph: GlyphLayout {
+                        ch,
+                        byte_index,
+                        position: Vector2 {
+                            x: pen_x + (glyph.offset_x as f32 - padding) * scale,
+                            y: top + (glyph.offset_y as f32 - padding) * scale,
+                        },
+                        source: Rectangle {
+                            x: rec.x - padding,
+                            y: rec.y - padding,
+                            width: rec.width + 2.0 * padding,
+                            height: rec.height + 2.0 * padding,
+                        },
+                        scale,
+                    },
+                    tint: run.tint(default_tint),
+                });
+            }
+
+            pen_x += if glyph.advance_x == 0 { rec.width } else { glyph.advance_x as f32 } * scale + spacing;
+        }
+
+        if !line.is_empty() {
+            size.x = size.x.max(pen_x - spacing);
+        }
+        size.y = line_top + line_size;
+        line_top += line_size + TEXT_LINE_SPACING;
+    }
+
+    (glyphs, size)
+}
+
+/// Get the layout of every glyph drawn for rich text runs
+/// NOTE: Same placement rules as [`Font::layout`], glyphs of different sizes share the line baseline
+#[must_use]
+pub fn layout_rich_text<'a>(font: impl Into<FontOrStack<'a>>, runs: &[TextRun], font_size: f32, spacing: f32, default_tint: Color) -> Vec<RichGlyph> {
+    layout_runs(font.into(), runs, font_size, spacing, default_tint).0
+}
+
+/// Measure rich text size, markup removed, matching [`layout_rich_text`] placement
+pub fn measure_text_rich<'a>(font: impl Into<FontOrStack<'a>>, text: &str, font_size: f32, spacing: f32) -> Vector2 {
+    layout_runs(font.into(), &parse_rich_text(text), font_size, spacing, Color::WHITE).1
+}
+
+/// Draw rich text markup, see the [module documentation](self) for supported tags
+/// NOTE: Glyphs are placed by [`layout_rich_text`], untagged text uses `default_tint`
+pub fn draw_text_rich<'a>(core: &mut Core, font: impl Into<FontOrStack<'a>>, text: &str, position: Position2, font_size: f32, spacing: f32, default_tint: Color) {
+    let font = font.into();
+    for RichGlyph { font: font_index, glyph, tint, .. } in layout_runs(font, &parse_rich_text(text), font_size, spacing, default_tint).0 {
+        if let Some(font) = font.font(font_index) {
+            draw_glyph(core, &font.texture, &glyph, position, tint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::font::tests::{font, stack};
+
+    /// Run of text with style
+    fn run(text: &str, color: Option<Color>, alpha: f32, size: f32) -> TextRun {
+        TextRun { text: text.to_string(), color, alpha, size }
+    }
+
+    /// Run of text with default style
+    fn plain(text: &str) -> TextRun {
+        run(text, None, 1.0, 1.0)
+    }
+
+    #[test]
+    fn test_parse_plain() {
+        assert_eq!(parse_rich_text("hello"), [plain("hello")]);
+        assert!(parse_rich_text("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_nested_tags() {
+        let red = Some(Color::new(255, 0, 0, 255));
+        assert_eq!(parse_rich_text("a[color=#FF0000]b[alpha=0.5]c[/alpha]d[/color]e"), [
+            plain("a"),
+            run("b", red, 1.0, 1.0),
+            run("c", red, 0.5, 1.0),
+            run("d", red, 1.0, 1.0),
+            plain("e"),
+        ]);
+        assert_eq!(parse_rich_text("[color=#00FF0080][size=2]x"), [run("x", Some(Color::new(0, 255, 0, 128)), 1.0, 2.0)]);
+    }
+
+    #[test]
+    fn test_parse_literal_text() {
+        assert_eq!(parse_rich_text("[[x]"), [plain("[x]")]);
+        assert_eq!(parse_rich_text("[foo]x[/color]"), [plain("[foo]x[/color]")]);
+        assert_eq!(parse_rich_text("[color=#GG0000]x"), [plain("[color=#GG0000]x")]);
+        assert_eq!(parse_rich_text("[size=0]x[size=inf]"), [plain("[size=0]x[size=inf]")]);
+        assert_eq!(parse_rich_text("[a[size=2]b"), [plain("[a"), run("b", None, 1.0, 2.0)]);
+        assert_eq!(parse_rich_text("x[size=2"), [plain("x[size=2")]);
+    }
+
+    #[test]
+    fn test_parse_merges_identical_styles() {
+        assert_eq!(parse_rich_text("a[size=2][/size]b"), [plain("ab")]);
+        assert_eq!(parse_rich_text("a[size=1]b[/size]c"), [plain("abc")]);
+        assert_eq!(parse_rich_text("[alpha=2]a"), [run("a", None, 1.0, 1.0)]);
+    }
+
+    #[test]
+    fn test_run_tint() {
+        assert_eq!(run("", None, 0.5, 1.0).tint(Color::WHITE), Color::new(255, 255, 255, 127));
+        assert_eq!(run("", Some(Color::RED), 1.0, 1.0).tint(Color::WHITE), Color::RED);
+    }
+
+    #[test]
+    fn test_layout_matches_font_layout() {
+        let font = font();
+        let text = "aé a\n?a";
+        let rich: Vec<GlyphLayout> = layout_rich_text(&font, &parse_rich_text(text), 20.0, 1.0, Color::WHITE)
+            .into_iter()
+            .map(|glyph| glyph.glyph)
+            .collect();
+        let plain: Vec<GlyphLayout> = font.layout(text, 20.0, 1.0).collect();
+        assert_eq!(rich, plain);
+    }
+
+    #[test]
+    fn test_layout_matches_stack_layout() {
+        let stack = stack();
+        let text = "ab\nba";
+        let rich: Vec<(usize, GlyphLayout)> = layout_rich_text(&stack, &parse_rich_text(text), 20.0, 1.0, Color::WHITE)
+            .into_iter()
+            .map(|glyph| (glyph.font, glyph.glyph))
+            .collect();
+        let plain: Vec<(usize, GlyphLayout)> = stack.layout(text, 20.0, 1.0).collect();
+        assert_eq!(rich, plain);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_layout_mixed_sizes_share_baseline() {
+        let font = font();
+        let glyphs = layout_rich_text(&font, &parse_rich_text("a[size=2]a"), 10.0, 0.0, Color::WHITE);
+        assert_eq!(glyphs.len(), 2);
+        // Baseline is ascent (8 at base size) below glyph top
+        assert_eq!(glyphs[0].glyph.position.y + 8.0, 16.0);
+        assert_eq!(glyphs[1].glyph.position.y + 8.0 * glyphs[1].glyph.scale, 16.0);
+        assert_eq!(measure_text_rich(&font, "a[size=2]a", 10.0, 0.0), Vector2::new(15.0, 20.0));
+    }
+
+    #[test]
+    fn test_layout_line_break_size() {
+        let font = font();
+        // Line break in a bigger run does not make the line taller
+        let text = "a[size=2]\n[/size]a";
+        let glyphs = layout_rich_text(&font, &parse_rich_text(text), 10.0, 0.0, Color::WHITE);
+        assert_eq!(glyphs[1].glyph.position, Vector2::new(1.0, 10.0 + TEXT_LINE_SPACING));
+        assert_eq!(measure_text_rich(&font, text, 10.0, 0.0), Vector2::new(5.0, 20.0 + TEXT_LINE_SPACING));
+    }
+
+    #[test]
+    fn test_layout_zero_base_size() {
+        let mut font = font();
+        font.base_size = 0;
+        assert!(layout_rich_text(&font, &parse_rich_text("aa"), 10.0, 0.0, Color::WHITE).is_empty());
+        assert_eq!(measure_text_rich(&font, "aa", 10.0, 0.0), Vector2::new(0.0, 10.0));
+    }
+
+    #[test]
+    fn test_layout_tints() {
+        let font = font();
+        let glyphs = layout_rich_text(&font, &parse_rich_text("a[color=#FF0000]a[alpha=0]a"), 10.0, 0.0, Color::WHITE);
+        let tints: Vec<Color> = glyphs.iter().map(|glyph| glyph.tint).collect();
+        assert_eq!(tints, [Color::WHITE, Color::new(255, 0, 0, 255), Color::new(255, 0, 0, 0)]);
+        assert_eq!(glyphs.iter().map(|glyph| glyph.run).collect::<Vec<_>>(), [0, 1, 2]);
+    }
+}
@@ -1,3 +1,406 @@
+use std::{collections::HashMap, path::Path, sync::mpsc::Sender};
+use crate::{config::{RL_DEFAULT_SHADER_ATTRIB_NAME_COLOR, RL_DEFAULT_SHADER_ATTRIB_NAME_NORMAL, RL_DEFAULT_SHADER_ATTRIB_NAME_POSITION, RL_DEFAULT_SHADER_ATTRIB_NAME_TANGENT, RL_DEFAULT_SHADER_ATTRIB_NAME_TEXCOORD, RL_DEFAULT_SHADER_ATTRIB_NAME_TEXCOORD2, RL_DEFAULT_SHADER_SAMPLER2D_NAME_TEXTURE0, RL_DEFAULT_SHADER_SAMPLER2D_NAME_TEXTURE1, RL_DEFAULT_SHADER_SAMPLER2D_NAME_TEXTURE2, RL_DEFAULT_SHADER_UNIFORM_NAME_COLOR, RL_DEFAULT_SHADER_UNIFORM_NAME_MODEL, RL_DEFAULT_SHADER_UNIFORM_NAME_MVP, RL_DEFAULT_SHADER_UNIFORM_NAME_NORMAL, RL_DEFAULT_SHADER_UNIFORM_NAME_PROJECTION, RL_DEFAULT_SHADER_UNIFORM_NAME_VIEW, RL_MAX_SHADER_LOCATIONS}, prelude::*, rlgl::{FlushReason, GlObject}, tracelog};
+use super::GlTextureID;
+
+/// Shader
+/// NOTE: Shader program is unloaded on drop, unless it is the default shader
+#[derive(Debug, Default)]
 pub struct Shader {
+    /// Shader program id
+    pub id: u32,
+    /// Shader locations array (`RL_MAX_SHADER_LOCATIONS`)
+    pub locs: Vec<i32>,
+    /// Last uniform values set, `None` if uniform dirty-checking is disabled
+    uniform_cache: Option<HashMap<i32, ShaderUniformData<'static>>>,
+    /// Release GPU program on drop, `None` for the default shader
+    unload_sender: Option<Sender<GlObject>>,
+}
+
+/// Shader location index, position in `Shader::locs`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(usize)]
+#[non_exhaustive]
+pub enum ShaderLocationIndex {
+    /** Shader location: vertex attribute: position                  */ VertexPosition = 0,
+    /** Shader location: vertex attribute: texcoord01                */ VertexTexcoord01,
+    /** Shader location: vertex attribute: texcoord02                */ VertexTexcoord02,
+    /** Shader location: vertex attribute: normal                    */ VertexNormal,
+    /** Shader location: vertex attribute: tangent                   */ VertexTangent,
+    /** Shader location: vertex attribute: color                     */ VertexColor,
+    /** Shader location: matrix uniform: model-view-projection       */ MatrixMvp,
+    /** Shader location: matrix uniform: view (camera transform)     */ MatrixView,
+    /** Shader location: matrix uniform: projection                  */ MatrixProjection,
+    /** Shader location: matrix uniform: model (transform)           */ MatrixModel,
+    /** Shader location: matrix uniform: normal                      */ MatrixNormal,
+    /** Shader location: vector uniform: view                        */ VectorView,
+    /** Shader location: vector uniform: diffuse color               */ ColorDiffuse,
+    /** Shader location: vector uniform: specular color              */ ColorSpecular,
+    /** Shader location: vector uniform: ambient color               */ ColorAmbient,
+    /** Shader location: sampler2d texture: albedo (same as: diffuse) */ MapAlbedo,
+    /** Shader location: sampler2d texture: metalness (same as: specular) */ MapMetalness,
+    /** Shader location: sampler2d texture: normal                   */ MapNormal,
+    /** Shader location: sampler2d texture: roughness                */ MapRoughness,
+    /** Shader location: sampler2d texture: occlusion                */ MapOcclusion,
+    /** Shader location: sampler2d texture: emission                 */ MapEmission,
+    /** Shader location: sampler2d texture: height                   */ MapHeight,
+    /** Shader location: samplerCube texture: cubemap                */ MapCubemap,
+    /** Shader location: samplerCube texture: irradiance             */ MapIrradiance,
+    /** Shader location: samplerCube texture: prefilter              */ MapPrefilter,
+    /** Shader location: sampler2d texture: brdf                     */ MapBrdf,
+    /** Shader location: vertex attribute: boneIds                   */ VertexBoneIds,
+    /** Shader location: vertex attribute: boneWeights               */ VertexBoneWeights,
+    /** Shader location: array of matrices uniform: boneMatrices     */ BoneMatrices,
+}
+
+impl ShaderLocationIndex {
+    /// Shader location: sampler2d texture: diffuse (same as: albedo)
+    pub const MAP_DIFFUSE: Self = Self::MapAlbedo;
+    /// Shader location: sampler2d texture: specular (same as: metalness)
+    pub const MAP_SPECULAR: Self = Self::MapMetalness;
+}
+
+/// Shader uniform data, as uploaded to the GPU
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum ShaderUniformData<'a> {
+    Float(f32),
+    Vec2([f32; 2]),
+    Vec3([f32; 3]),
+    Vec4([f32; 4]),
+    Int(i32),
+    IVec2([i32; 2]),
+    IVec3([i32; 3]),
+    IVec4([i32; 4]),
+    /// Array of float vectors, `components` (1 to 4) floats per element
+    FloatArray { components: usize, values: &'a [f32] },
+    /// Array of int vectors, `components` (1 to 4) ints per element
+    IntArray { components: usize, values: &'a [i32] },
+    /// 4x4 matrix
+    Matrix(&'a Matrix),
+    /// 2D texture sampler, texture id
+    Sampler2D(GlTextureID),
+}
+
+impl ShaderUniformData<'_> {
+    /// Get data to be kept for dirty-checking, `None` for arrays, matrices and samplers (always uploaded)
+    /// NOTE: Sampler texture units are released on every batch flush, they must be set again
+    fn cached(self) -> Option<ShaderUniformData<'static>> {
+        match self {
+            Self::Float(x) => Some(ShaderUniformData::Float(x)),
+            Self::Vec2(v) => Some(ShaderUniformData::Vec2(v)),
+            Self::Vec3(v) => Some(ShaderUniformData::Vec3(v)),
+            Self::Vec4(v) => Some(ShaderUniformData::Vec4(v)),
+            Self::Int(x) => Some(ShaderUniformData::Int(x)),
+            Self::IVec2(v) => Some(ShaderUniformData::IVec2(v)),
+            Self::IVec3(v) => Some(ShaderUniformData::IVec3(v)),
+            Self::IVec4(v) => Some(ShaderUniformData::IVec4(v)),
+            Self::FloatArray { .. } | Self::IntArray { .. } | Self::Matrix(_) | Self::Sampler2D(_) => None,
+        }
+    }
+}
+
+/// Value that can be set to a shader uniform
+pub trait ShaderUniformValue {
+    /// Get uniform data to be uploaded
+    fn uniform_data(&self) -> ShaderUniformData<'_>;
+}
+
+impl ShaderUniformValue for f32 {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::Float(*self) }
+}
+
+impl ShaderUniformValue for i32 {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::Int(*self) }
+}
+
+impl ShaderUniformValue for Vector2 {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::Vec2([self.x, self.y]) }
+}
+
+impl ShaderUniformValue for Vector3 {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::Vec3([self.x, self.y, self.z]) }
+}
+
+impl ShaderUniformValue for Vector4 {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::Vec4([self.x, self.y, self.z, self.w]) }
+}
+
+impl ShaderUniformValue for [f32; 2] {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::Vec2(*self) }
+}
+
+impl ShaderUniformValue for [f32; 3] {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::Vec3(*self) }
+}
+
+impl ShaderUniformValue for [f32; 4] {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::Vec4(*self) }
+}
+
+impl ShaderUniformValue for [i32; 2] {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::IVec2(*self) }
+}
+
+impl ShaderUniformValue for [i32; 3] {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::IVec3(*self) }
+}
+
+impl ShaderUniformValue for [i32; 4] {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::IVec4(*self) }
+}
+
+impl ShaderUniformValue for [f32] {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::FloatArray { components: 1, values: self } }
+}
+
+impl<const N: usize> ShaderUniformValue for [[f32; N]] {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::FloatArray { components: N, values: self.as_flattened() } }
+}
+
+impl ShaderUniformValue for [i32] {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::IntArray { components: 1, values: self } }
+}
+
+impl<const N: usize> ShaderUniformValue for [[i32; N]] {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::IntArray { components: N, values: self.as_flattened() } }
+}
+
+impl ShaderUniformValue for Matrix {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::Matrix(self) }
+}
+
+impl ShaderUniformValue for Texture {
+    fn uniform_data(&self) -> ShaderUniformData<'_> { ShaderUniformData::Sampler2D(self.id) }
+}
+
+impl Shader {
+    /// Load shader from files and bind default locations
+    /// NOTE: `None` (or unreadable) files use the default shader code for that stage,
+    /// shader failing to compile or link falls back to the default shader (logged)
+    pub fn load(core: &mut Core, vs_path: Option<&Path>, fs_path: Option<&Path>) -> Self {
+        let read = |path: &Path| std::fs::read_to_string(path)
+            .inspect_err(|e| tracelog!(Warning, "FILEIO: [{}] Failed to read text file: {e}", path.display()))
+            .ok();
+        let vs_code = vs_path.and_then(read);
+        let fs_code = fs_path.and_then(read);
+        Self::load_from_memory(core, vs_code.as_deref(), fs_code.as_deref())
+    }
+
+    /// Load shader from code strings and bind default locations
+    /// NOTE: `None` code uses the default shader code for that stage,
+    /// shader failing to compile or link falls back to the default shader (logged)
+    ///
+    /// Loading an embedded shader:
+    /// ```ignore
+    /// let shader = Shader::load_from_memory(&mut core, None, Some(include_str!("../resources/grayscale.fs")));
+    /// ```
+    pub fn load_from_memory(core: &mut Core, vs_code: Option<&str>, fs_code: Option<&str>) -> Self {
+        let rlgl = &mut core.rlgl;
+        let id = match rlgl.load_shader_code(vs_code, fs_code) {
+            Ok(id) => id,
+            Err(e) => {
+                tracelog!(Warning, "SHADER: Failed to load custom shader code, using default shader: {e}");
+                return Self::default_shader(core);
+            }
+        };
+
+        // NOTE: All locations must be reseted to -1 (no location)
+        let mut locs = vec![-1; RL_MAX_SHADER_LOCATIONS];
+
+        // Get handles to GLSL input attribute locations
+        for (index, name) in [
+            (ShaderLocationIndex::VertexPosition, RL_DEFAULT_SHADER_ATTRIB_NAME_POSITION),
+            (ShaderLocationIndex::VertexTexcoord01, RL_DEFAULT_SHADER_ATTRIB_NAME_TEXCOORD),
+            (ShaderLocationIndex::VertexTexcoord02, RL_DEFAULT_SHADER_ATTRIB_NAME_TEXCOORD2),
+            (ShaderLocationIndex::VertexNormal, RL_DEFAULT_SHADER_ATTRIB_NAME_NORMAL),
+            (ShaderLocationIndex::VertexTangent, RL_DEFAULT_SHADER_ATTRIB_NAME_TANGENT),
+            (ShaderLocationIndex::VertexColor, RL_DEFAULT_SHADER_ATTRIB_NAME_COLOR),
+        ] {
+            locs[index as usize] = rlgl.get_location_attrib(id, name);
+        }
+
+        // Get handles to GLSL uniform locations (vertex shader)
+        // Get handles to GLSL uniform locations (fragment shader)
+        for (index, name) in [
+            (ShaderLocationIndex::MatrixMvp, RL_DEFAULT_SHADER_UNIFORM_NAME_MVP),
+            (ShaderLocationIndex::MatrixView, RL_DEFAULT_SHADER_UNIFORM_NAME_VIEW),
+            (ShaderLocationIndex::MatrixProjection, RL_DEFAULT_SHADER_UNIFORM_NAME_PROJECTION),
+            (ShaderLocationIndex::MatrixModel, RL_DEFAULT_SHADER_UNIFORM_NAME_MODEL),
+            (ShaderLocationIndex::MatrixNormal, RL_DEFAULT_SHADER_UNIFORM_NAME_NORMAL),
+            (ShaderLocationIndex::ColorDiffuse, RL_DEFAULT_SHADER_UNIFORM_NAME_COLOR),
+            (ShaderLocationIndex::MAP_DIFFUSE, RL_DEFAULT_SHADER_SAMPLER2D_NAME_TEXTURE0), // SHADER_LOC_MAP_ALBEDO
+            (ShaderLocationIndex::MAP_SPECULAR, RL_DEFAULT_SHADER_SAMPLER2D_NAME_TEXTURE1), // SHADER_LOC_MAP_METALNESS
+            (ShaderLocationIndex::MapNormal, RL_DEFAULT_SHADER_SAMPLER2D_NAME_TEXTURE2),
+        ] {
+            locs[index as usize] = rlgl.get_location_uniform(id, name);
+        }
+
+        Self {
+            id,
+            locs,
+            uniform_cache: None,
+            unload_sender: Some(rlgl.unload_sender()),
+        }
+    }
+
+    /// Get default shader (not unloaded on drop)
+    #[must_use]
+    pub fn default_shader(core: &Core) -> Self {
+        Self {
+            id: core.rlgl.get_shader_id_default(),
+            locs: core.rlgl.get_shader_locs_default().to_vec(),
+            uniform_cache: None,
+            unload_sender: None,
+        }
+    }
+
+    /// Check if a shader is valid (loaded on GPU)
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.id > 0 && !self.locs.is_empty()
+    }
+
+    /// Get shader uniform location, -1 if not found (logged)
+    pub fn get_location(&self, core: &mut Core, uniform_name: &str) -> i32 {
+        let location = core.rlgl.get_location_uniform(self.id, uniform_name);
+        if location == -1 {
+            tracelog!(Debug, "SHADER: [ID {}] Failed to find shader uniform: {uniform_name}", self.id);
+        }
+        location
+    }
+
+    /// Get shader attribute location, -1 if not found (logged)
+    pub fn get_location_attrib(&self, core: &mut Core, attrib_name: &str) -> i32 {
+        let location = core.rlgl.get_location_attrib(self.id, attrib_name);
+        if location == -1 {
+            tracelog!(Debug, "SHADER: [ID {}] Failed to find shader attribute: {attrib_name}", self.id);
+        }
+        location
+    }
+
+    /// Set shader uniform value, negative locations are ignored
+    /// NOTE: Pending draws are flushed first, they keep the previous value.
+    /// Textures are bound to a free texture unit until the next batch flush, they must be set every frame
+    pub fn set_value<T: ShaderUniformValue + ?Sized>(&mut self, core: &mut Core, location: i32, value: &T) {
+        if location < 0 {
+            return;
+        }
+        let data = value.uniform_data();
+        if !self.uniform_changed(location, data) {
+            return;
+        }
+        core.rlgl.draw_render_batch(FlushReason::UniformChange);
+        core.rlgl.set_uniform(self.id, location, data);
+    }
+
+    /// Enable/disable uniform dirty-checking: setting a uniform to its current value
+    /// is skipped and doesn't flush the render batch
+    /// NOTE: Only useful for uniforms changed between draws, costs a lookup per uniform set.
+    /// Arrays, matrices and samplers are always uploaded
+    pub fn set_uniform_dirty_check(&mut self, enabled: bool) {
+        self.uniform_cache = enabled.then(HashMap::new);
+    }
+
+    /// Check if uniform dirty-checking is enabled
+    #[must_use]
+    pub fn is_uniform_dirty_check_enabled(&self) -> bool {
+        self.uniform_cache.is_some()
+    }
+
+    /// Register a uniform value, returns false if the upload can be skipped (same value as last time)
+    pub(crate) fn uniform_changed(&mut self, location: i32, value: ShaderUniformData) -> bool {
+        match (&mut self.uniform_cache, value.cached()) {
+            (Some(cache), Some(value)) => cache.insert(location, value) != Some(value),
+            _ => true,
+        }
+    }
+}
+
+impl Drop for Shader {
+    fn drop(&mut self) {
+        // NOTE: Program is unloaded on the next batch draw, nothing to do if rlgl is gone
+        if let Some(sender) = &self.unload_sender {
+            _ = sender.send(GlObject::Program(self.id));
+        }
+    }
+}
+
+/// Begin custom shader drawing
+/// NOTE: The render batch is only flushed when a draw is emitted with a different shader than
+/// the previous one, consecutive draws with the same shader are batched together
+pub fn begin_shader_mode(core: &mut Core, shader: impl AsRef<Shader>) {
+    core.rlgl.set_shader(shader.as_ref().id);
+}
+
+/// End custom shader drawing (use default shader)
+pub fn end_shader_mode(core: &mut Core) {
+    core.rlgl.set_shader(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rlgl::{DrawMode, tests::{draw_calls, recording_rlgl, CallLog, GlCall}};
+    use super::*;
+
+    /// Float uniform uploads recorded so far
+    fn uploads(calls: &CallLog) -> usize {
+        calls.borrow().iter().filter(|call| matches!(call, GlCall::UniformFv { .. })).count()
+    }
+
+    /// Batch flushes recorded so far, each one ends unbinding its last texture
+    fn flushes(calls: &CallLog) -> usize {
+        draw_calls(calls).iter().filter(|call| **call == GlCall::BindTexture(0)).count()
+    }
+
+    fn quad(core: &mut Core) {
+        let rlgl = &mut core.rlgl;
+        rlgl.begin(DrawMode::Quads);
+        for (x, y) in [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)] {
+            rlgl.vertex2f(x, y);
+        }
+        rlgl.end();
+    }
+
+    #[test]
+    fn test_uniform_dirty_check() {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+        let mut shader = Shader::load_from_memory(&mut core, None, None);
+        assert!(!shader.is_uniform_dirty_check_enabled());
+        shader.set_uniform_dirty_check(true);
+        begin_shader_mode(&mut core, &shader);
+
+        // First value is uploaded, flushing pending draws
+        quad(&mut core);
+        shader.set_value(&mut core, 3, &1.0_f32);
+        assert_eq!((uploads(&calls), flushes(&calls)), (1, 1));
+        assert!(calls.borrow().contains(&GlCall::UniformFv { location: 3, values: vec![1.0] }));
+
+        // Same value is skipped and keeps the batch, other locations are tracked apart
+        quad(&mut core);
+        shader.set_value(&mut core, 3, &1.0_f32);
+        assert_eq!((uploads(&calls), flushes(&calls)), (1, 1));
+        shader.set_value(&mut core, 4, &1.0_f32);
+        assert_eq!((uploads(&calls), flushes(&calls)), (2, 2));
+        quad(&mut core);
+        shader.set_value(&mut core, 3, &[1.0_f32, 0.0]);
+        assert_eq!((uploads(&calls), flushes(&calls)), (3, 3));
+
+        // Matrices are always uploaded (nothing left to flush), negative locations never
+        let matrices = |calls: &CallLog| calls.borrow().iter().filter(|call| matches!(call, GlCall::UniformMatrix4(_))).count();
+        let before = matrices(&calls);
+        shader.set_value(&mut core, 5, &Matrix::IDENTITY);
+        shader.set_value(&mut core, 5, &Matrix::IDENTITY);
+        assert_eq!(matrices(&calls) - before, 2);
+        shader.set_value(&mut core, -1, &2.0_f32);
+        assert_eq!(uploads(&calls), 3);
 
+        // Without dirty-checking every set is uploaded
+        shader.set_uniform_dirty_check(false);
+        shader.set_value(&mut core, 3, &1.0_f32);
+        shader.set_value(&mut core, 3, &1.0_f32);
+        assert_eq!(uploads(&calls), 5);
+        end_shader_mode(&mut core);
+    }
 }
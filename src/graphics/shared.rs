@@ -0,0 +1,254 @@
+use std::{ops::Deref, sync::{Arc, Weak}};
+use crate::prelude::*;
+
+/// Shared, reference-counted handle to a GPU resource
+/// NOTE: Resource is released when the last strong handle is dropped
+#[derive(Debug)]
+pub struct Shared<T>(Arc<T>);
+
+/// Weak handle to a shared resource, does not keep it alive
+#[derive(Debug)]
+pub struct WeakShared<T>(Weak<T>);
+
+pub type SharedTexture = Shared<Texture>;
+pub type SharedFont = Shared<Font>;
+pub type SharedShader = Shared<Shader>;
+
+pub type WeakTexture = WeakShared<Texture>;
+pub type WeakFont = WeakShared<Font>;
+pub type WeakShader = WeakShared<Shader>;
+
+impl<T> Shared<T> {
+    /// Wrap resource in a shared handle
+    #[must_use]
+    pub fn new(resource: T) -> Self {
+        Self(Arc::new(resource))
+    }
+
+    /// Create a weak handle to the resource
+    #[must_use]
+    pub fn downgrade(this: &Self) -> WeakShared<T> {
+        WeakShared(Arc::downgrade(&this.0))
+    }
+
+    /// Get number of strong handles to the resource
+    #[must_use]
+    pub fn strong_count(this: &Self) -> usize {
+        Arc::strong_count(&this.0)
+    }
+
+    /// Check if both handles point to the same resource
+    #[must_use]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+
+    /// Take back ownership of the resource, if this is the only strong handle
+    /// # Errors
+    /// Returns the handle unchanged if other strong handles exist
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        Arc::try_unwrap(this.0).map_err(Self)
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for Shared<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> From<T> for Shared<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> WeakShared<T> {
+    /// Get a strong handle, if the resource is still alive
+    #[must_use]
+    pub fn upgrade(&self) -> Option<Shared<T>> {
+        self.0.upgrade().map(Shared)
+    }
+}
+
+impl<T> Clone for WeakShared<T> {
+    fn clone(&self) -> Self {
+        Self(Weak::clone(&self.0))
+    }
+}
+
+impl<T> Default for WeakShared<T> {
+    /// Create a weak handle that never upgrades
+    fn default() -> Self {
+        Self(Weak::new())
+    }
+}
+
+impl Texture {
+    /// Convert texture into a shared handle
+    #[must_use]
+    pub fn into_shared(self) -> SharedTexture {
+        Shared::new(self)
+    }
+}
+
+impl AsRef<Texture> for Texture {
+    fn as_ref(&self) -> &Texture {
+        self
+    }
+}
+
+impl Font {
+    /// Convert font into a shared handle
+    #[must_use]
+    pub fn into_shared(self) -> SharedFont {
+        Shared::new(self)
+    }
+}
+
+impl AsRef<Font> for Font {
+    fn as_ref(&self) -> &Font {
+        self
+    }
+}
+
+impl Shader {
+    /// Convert shader into a shared handle
+    #[must_use]
+    pub fn into_shared(self) -> SharedShader {
+        Shared::new(self)
+    }
+}
+
+impl AsRef<Shader> for Shader {
+    fn as_ref(&self) -> &Shader {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::rlgl::{FlushReason, tests::{draw_calls, recording_rlgl, CallLog, GlCall}};
+    use super::*;
+
+    fn recording_core() -> (Core<'static>, CallLog) {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+        (core, calls)
+    }
+
+    fn texture(core: &mut Core) -> Texture {
+        let image = Image { data: vec![255; 4*4*4], width: 4, height: 4, mipmap: 1, format: PixelFormat::UncompressedR8G8B8A8 };
+        Texture::load_from_image(core, &image).unwrap()
+    }
+
+    fn deleted_textures(calls: &CallLog) -> Vec<u32> {
+        calls.borrow().iter().filter_map(|call| match call {
+            GlCall::DeleteTexture(id) => Some(*id),
+            _ => None,
+        }).collect()
+    }
+
+    #[test]
+    fn test_last_drop_releases_once() {
+        let (mut core, calls) = recording_core();
+        let shared = texture(&mut core).into_shared();
+        let id = shared.id;
+        let (a, b) = (shared.clone(), shared.clone());
+        assert_eq!(Shared::strong_count(&shared), 3);
+
+        // Dropping some handles keeps the texture
+        drop(a);
+        drop(shared);
+        core.rlgl.draw_render_batch(FlushReason::Explicit);
+        assert!(deleted_textures(&calls).is_empty());
+
+        // Last handle releases it on the next batch draw, only once
+        drop(b);
+        core.rlgl.draw_render_batch(FlushReason::Explicit);
+        core.rlgl.draw_render_batch(FlushReason::Explicit);
+        assert_eq!(deleted_textures(&calls), [id]);
+
+        // Unwrapped resources are released by their owner
+        let shared = texture(&mut core).into_shared();
+        let other = shared.clone();
+        let shared = Shared::try_unwrap(shared).unwrap_err();
+        drop(other);
+        let owned = Shared::try_unwrap(shared).unwrap();
+        let id2 = owned.id;
+        core.rlgl.draw_render_batch(FlushReason::Explicit);
+        assert_eq!(deleted_textures(&calls), [id]);
+        drop(owned);
+        core.rlgl.draw_render_batch(FlushReason::Explicit);
+        assert_eq!(deleted_textures(&calls), [id, id2]);
+    }
+
+    #[test]
+    fn test_draw_accepts_both_handles() {
+        let (mut core, calls) = recording_core();
+        let owned = texture(&mut core);
+        let shared = texture(&mut core).into_shared();
+        let dest = Rectangle { x: 0.0, y: 0.0, width: 4.0, height: 4.0 };
+        let source = Rectangle { x: 0.0, y: 0.0, width: 4.0, height: 4.0 };
+        calls.borrow_mut().clear();
+
+        draw_texture_pro(&mut core, &owned, source, dest, Vector2::ZERO, Degrees::ZERO, Color::WHITE);
+        draw_texture_pro(&mut core, &shared, source, dest, Vector2::ZERO, Degrees::ZERO, Color::WHITE);
+        draw_texture_pro(&mut core, shared.clone(), source, dest, Vector2::ZERO, Degrees::ZERO, Color::WHITE);
+        core.rlgl.draw_render_batch(FlushReason::Explicit);
+        let bound: Vec<_> = draw_calls(&calls).into_iter().filter(|call| matches!(call, GlCall::BindTexture(_))).collect();
+        assert_eq!(bound, [GlCall::BindTexture(owned.id), GlCall::BindTexture(shared.id), GlCall::BindTexture(0)]);
+
+        // Shaders and fonts work through either handle as well
+        let shader = Shader::load_from_memory(&mut core, None, None).into_shared();
+        begin_shader_mode(&mut core, &shader);
+        end_shader_mode(&mut core);
+        let font = crate::graphics::font::tests::font().into_shared();
+        draw_text_ex(&mut core, &font, "a", Vector2::ZERO, 10.0, 1.0, Color::WHITE);
+        draw_text_ex(&mut core, &crate::graphics::font::tests::font(), "a", Vector2::ZERO, 10.0, 1.0, Color::WHITE);
+    }
+
+    #[test]
+    fn test_weak_cache_reloads() {
+        let (mut core, _calls) = recording_core();
+        let mut cache: HashMap<&str, WeakTexture> = HashMap::new();
+        let mut load = |core: &mut Core, name| {
+            if let Some(texture) = cache.get(name).and_then(WeakShared::upgrade) {
+                return (texture, false);
+            }
+            let texture = texture(core).into_shared();
+            cache.insert(name, Shared::downgrade(&texture));
+            (texture, true)
+        };
+
+        // Cached while a strong handle lives
+        let (first, loaded) = load(&mut core, "atlas");
+        assert!(loaded);
+        let (again, loaded) = load(&mut core, "atlas");
+        assert!(!loaded && Shared::ptr_eq(&first, &again));
+
+        // Reloaded once every strong handle is gone
+        let id = first.id;
+        drop((first, again));
+        let (reloaded, loaded) = load(&mut core, "atlas");
+        assert!(loaded);
+        assert_ne!(reloaded.id, id);
+        assert!(WeakTexture::default().upgrade().is_none());
+    }
+}
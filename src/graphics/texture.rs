@@ -1,7 +1,10 @@
-use crate::prelude::*;
+use std::sync::mpsc::Sender;
+use crate::{prelude::*, rlgl::{DrawMode, GlObject}, tracelog};
 use super::GlTextureID;
 
 /// Texture, tex data stored in GPU memory (VRAM)
+/// NOTE: Texture is unloaded on drop
+#[derive(Debug)]
 pub struct Texture {
     /// OpenGL texture id
     pub id: GlTextureID,
@@ -13,7 +16,319 @@ pub struct Texture {
     pub mipmap: usize,
     // Data format
     pub format: PixelFormat,
+    /// Filter used on sampling, see [`set_texture_filter`]
+    pub(crate) filter: TextureFilter,
+    /// Release GPU texture on drop, `None` if the texture is owned by another object
+    pub(crate) unload_sender: Option<Sender<GlObject>>,
 }
 
 pub type Texture2D = Texture;
 pub type TextureCubemap = Texture;
+
+/// Texture loading error
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TextureError {
+    /// Pixel format not supported by the GPU (compressed format extension missing)
+    UnsupportedFormat(PixelFormat),
+    /// Provided pixel data is smaller than required by size, format and mipmaps
+    DataTooSmall { expected: usize, found: usize },
+    /// OpenGL failed to create the texture
+    LoadFailed,
+}
+
+impl std::fmt::Display for TextureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedFormat(format) => write!(f, "texture format not supported: {format:?}"),
+            Self::DataTooSmall { expected, found } => write!(f, "texture data too small: expected {expected} bytes, found {found}"),
+            Self::LoadFailed => f.write_str("failed to load texture"),
+        }
+    }
+}
+
+impl std::error::Error for TextureError {}
+
+impl Drop for Texture {
+    fn drop(&mut self) {
+        // NOTE: Texture is unloaded on the next batch draw, nothing to do if rlgl is gone
+        if let Some(sender) = &self.unload_sender {
+            _ = sender.send(GlObject::Texture(self.id));
+        }
+    }
+}
+
+/// Set the inset (in texels) applied to source rectangles of texture draws, 0 by default
+/// NOTE: Insetting by half a texel prevents bilinear filtering from sampling neighbor sprites in atlases
+pub fn set_atlas_uv_inset(core: &mut Core, texels: f32) {
+    core.atlas_uv_inset = texels.max(0.0);
+}
+
+/// Get the inset (in texels) applied to source rectangles of texture draws
+#[inline]
+#[must_use]
+pub fn get_atlas_uv_inset(core: &Core) -> f32 {
+    core.atlas_uv_inset
+}
+
+/// Texture coordinates of a source rectangle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourceUv {
+    /// Coordinates sampled at the destination top-left corner
+    pub top_left: Vector2,
+    /// Coordinates sampled at the destination bottom-right corner
+    pub bottom_right: Vector2,
+}
+
+impl SourceUv {
+    /// Compute texture coordinates of a source rectangle (in pixels) inset by `inset` texels on every side
+    /// NOTE: Negative source width/height flip the texture. Insets larger than half
+    /// the source size are clamped, sampling the source center instead of inverting
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(source: Rectangle, texture_width: usize, texture_height: usize, inset: f32) -> Self {
+        let (width, height) = (texture_width as f32, texture_height as f32);
+        let inset_x = inset.max(0.0).min(source.width.abs() * 0.5);
+        let inset_y = inset.max(0.0).min(source.height.abs() * 0.5);
+
+        // Source region always starts at source position, sign only flips it
+        let (x0, x1) = (source.x, source.x + source.width.abs());
+        let (y0, y1) = (source.y, source.y + source.height.abs());
+        let (mut left, mut right) = ((x0 + inset_x) / width, (x1 - inset_x) / width);
+        let (mut top, mut bottom) = ((y0 + inset_y) / height, (y1 - inset_y) / height);
+
+        if source.width < 0.0 {
+            std::mem::swap(&mut left, &mut right);
+        }
+        if source.height < 0.0 {
+            std::mem::swap(&mut top, &mut bottom);
+        }
+
+        Self {
+            top_left: Vector2::new(left, top),
+            bottom_right: Vector2::new(right, bottom),
+        }
+    }
+}
+
+impl Texture {
+    /// Load texture from image data
+    /// NOTE: Image mipmaps are uploaded as they are, none are generated
+    /// # Errors
+    /// Returns an error if the image is empty, or the GPU can not load its data
+    pub fn load_from_image(core: &mut Core, image: &Image) -> Result<Self, TextureError> {
+        if image.width == 0 || image.height == 0 || image.data.is_empty() {
+            tracelog!(Warning, "IMAGE: Data is not valid to load texture");
+            return Err(TextureError::LoadFailed);
+        }
+
+        let rlgl = &mut core.rlgl;
+        let id = rlgl.load_texture(Some(&image.data), image.width, image.height, image.format, image.mipmap)?;
+        Ok(Self {
+            id,
+            width: image.width,
+            height: image.height,
+            mipmap: image.mipmap.max(1),
+            format: image.format,
+            filter: if image.mipmap > 1 { TextureFilter::Trilinear } else { TextureFilter::Point },
+            unload_sender: Some(rlgl.unload_sender()),
+        })
+    }
+
+    /// Compute texture coordinates of a source rectangle with an explicit inset (in texels)
+    #[must_use]
+    pub fn source_uv_inset(&self, source: Rectangle, inset: f32) -> SourceUv {
+        SourceUv::new(source, self.width, self.height, inset)
+    }
+
+    /// Get filter used on sampling
+    #[inline]
+    #[must_use]
+    pub fn filter(&self) -> TextureFilter {
+        self.filter
+    }
+}
+
+/// Set texture scaling filter mode
+/// NOTE: Trilinear and anisotropic filters require mipmaps, bilinear is used otherwise
+pub fn set_texture_filter(core: &mut Core, texture: &mut Texture, filter: TextureFilter) {
+    texture.filter = core.rlgl.set_texture_filter(texture.id, texture.mipmap, filter);
+}
+
+/// Update GPU texture with new data
+/// NOTE: pixels data must match texture.format
+pub fn update_texture(core: &mut Core, texture: &Texture, pixels: &[u8]) {
+    core.rlgl.update_texture(texture.id, 0, 0, texture.width, texture.height, texture.format, pixels);
+}
+
+/// Update GPU texture rectangle with new data
+/// NOTE: pixels data must match texture.format, the rectangle is truncated to whole pixels
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn update_texture_rec(core: &mut Core, texture: &Texture, rec: Rectangle, pixels: &[u8]) {
+    core.rlgl.update_texture(texture.id, rec.x as usize, rec.y as usize, rec.width as usize, rec.height as usize, texture.format, pixels);
+}
+
+/// Textured quad drawn by [`draw_sprites`], parameters as in [`draw_texture_pro`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprite {
+    /// Destination rectangle, rotation happens around its position
+    pub dest: Rectangle,
+    /// Origin relative to destination rectangle position
+    pub origin: Offset2,
+    pub rotation: Degrees,
+    pub tint: Color,
+}
+
+impl Sprite {
+    /// Compute quad corners (top-left, bottom-left, bottom-right, top-right)
+    pub fn corners(&self) -> [Vector2; 4] {
+        let Self { dest, origin, rotation, .. } = *self;
+        if rotation == Degrees::ZERO {
+            Rectangle { x: dest.x - origin.x, y: dest.y - origin.y, ..dest }.corners()
+        } else {
+            let (sin, cos) = rotation.to_radians().sin_cos();
+            let local = Rectangle { x: -origin.x, y: -origin.y, ..dest }.corners();
+            local.map(|Vector2 { x, y }| Vector2::new(dest.x + x*cos - y*sin, dest.y + x*sin + y*cos))
+        }
+    }
+}
+
+/// Draw a part of a texture (defined by a rectangle) with 'pro' parameters
+/// NOTE: origin is relative to destination rectangle size, the atlas UV inset is applied to source
+pub fn draw_texture_pro(core: &mut Core, texture: impl AsRef<Texture>, source: Rectangle, dest: Rectangle, origin: Offset2, rotation: Degrees, tint: Color) {
+    let inset = core.atlas_uv_inset;
+    draw_texture_pro_inset(core, texture, source, dest, origin, rotation, tint, inset);
+}
+
+/// Draw a part of a texture (defined by a rectangle) with 'pro' parameters and an explicit source inset (in texels)
+#[allow(clippy::too_many_arguments)]
+pub fn draw_texture_pro_inset(core: &mut Core, texture: impl AsRef<Texture>, source: Rectangle, dest: Rectangle, origin: Offset2, rotation: Degrees, tint: Color, inset: f32) {
+    draw_sprites_inset(core, texture, source, inset, [Sprite { dest, origin, rotation, tint }]);
+}
+
+/// Draw the same part of a texture (defined by a rectangle) once per sprite, in a single draw mode
+/// NOTE: Sprites are batched together, the texture is only bound once. The atlas UV inset is applied to source
+pub fn draw_sprites(core: &mut Core, texture: impl AsRef<Texture>, source: Rectangle, sprites: impl IntoIterator<Item = Sprite>) {
+    let inset = core.atlas_uv_inset;
+    draw_sprites_inset(core, texture, source, inset, sprites);
+}
+
+/// Draw the same part of a texture once per sprite with an explicit source inset (in texels)
+pub fn draw_sprites_inset(core: &mut Core, texture: impl AsRef<Texture>, source: Rectangle, inset: f32, sprites: impl IntoIterator<Item = Sprite>) {
+    let texture = texture.as_ref();
+    if texture.id == 0 {
+        return;
+    }
+
+    let SourceUv { top_left: tl, bottom_right: br } = texture.source_uv_inset(source, inset);
+    let texcoords = [[tl.x, tl.y], [tl.x, br.y], [br.x, br.y], [br.x, tl.y]];
+
+    let rlgl = &mut core.rlgl;
+    rlgl.set_texture(texture.id);
+    rlgl.begin(DrawMode::Quads);
+        rlgl.normal3f(0.0, 0.0, 1.0);
+        for sprite in sprites {
+            let tint = sprite.tint;
+            rlgl.color4ub(tint.r, tint.g, tint.b, tint.a);
+            for (corner, [u, v]) in sprite.corners().into_iter().zip(texcoords) {
+                rlgl.tex_coord2f(u, v);
+                rlgl.vertex2f(corner.x, corner.y);
+            }
+        }
+    rlgl.end();
+    rlgl.set_texture(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rlgl::{FlushReason, tests::{recording_rlgl, CallLog, GlCall, Upload}};
+    use super::*;
+
+    #[track_caller]
+    fn assert_uv(uv: SourceUv, [left, top, right, bottom]: [f32; 4]) {
+        let expected = SourceUv { top_left: Vector2::new(left, top), bottom_right: Vector2::new(right, bottom) };
+        assert_eq!(uv, expected);
+    }
+
+    fn atlas(core: &mut Core) -> Texture {
+        let image = Image { data: vec![255; 64*64*4], width: 64, height: 64, mipmap: 1, format: PixelFormat::UncompressedR8G8B8A8 };
+        Texture::load_from_image(core, &image).unwrap()
+    }
+
+    /// Texture coordinates uploaded by the draws of `draw`
+    fn texcoords(core: &mut Core, calls: &CallLog, draw: impl FnOnce(&mut Core)) -> Vec<f32> {
+        calls.borrow_mut().clear();
+        draw(core);
+        core.rlgl.draw_render_batch(FlushReason::Explicit);
+        calls.borrow().iter().filter_map(|call| match call {
+            GlCall::BufferSubData(Upload::F32(data)) => Some(data.clone()),
+            _ => None,
+        }).nth(1).unwrap()
+    }
+
+    #[test]
+    fn test_source_uv_inset() {
+        let rec = |x, y, width, height| Rectangle { x, y, width, height };
+
+        // No inset keeps the plain source rectangle
+        assert_uv(SourceUv::new(rec(16.0, 8.0, 16.0, 32.0), 64, 64, 0.0), [0.25, 0.125, 0.5, 0.625]);
+        assert_uv(SourceUv::new(rec(16.0, 8.0, 16.0, 32.0), 64, 64, -1.0), [0.25, 0.125, 0.5, 0.625]);
+
+        // Edge and interior rectangles are inset on every side, per texture dimension
+        assert_uv(SourceUv::new(rec(0.0, 0.0, 8.0, 8.0), 64, 32, 0.5), [0.5/64.0, 0.5/32.0, 7.5/64.0, 7.5/32.0]);
+        assert_uv(SourceUv::new(rec(16.0, 16.0, 16.0, 16.0), 64, 64, 0.5), [16.5/64.0, 16.5/64.0, 31.5/64.0, 31.5/64.0]);
+        assert_uv(SourceUv::new(rec(56.0, 24.0, 8.0, 8.0), 64, 32, 0.5), [56.5/64.0, 24.5/32.0, 63.5/64.0, 31.5/32.0]);
+
+        // Flipped sources swap the inset coordinates
+        assert_uv(SourceUv::new(rec(16.0, 16.0, -16.0, 16.0), 64, 64, 0.5), [31.5/64.0, 16.5/64.0, 16.5/64.0, 31.5/64.0]);
+        assert_uv(SourceUv::new(rec(16.0, 16.0, 16.0, -16.0), 64, 64, 0.5), [16.5/64.0, 31.5/64.0, 31.5/64.0, 16.5/64.0]);
+
+        // Sources smaller than twice the inset sample their center instead of inverting
+        assert_uv(SourceUv::new(rec(4.0, 4.0, 0.5, 2.0), 64, 64, 0.5), [4.25/64.0, 4.5/64.0, 4.25/64.0, 5.5/64.0]);
+        assert_uv(SourceUv::new(rec(4.0, 4.0, -0.5, 0.5), 64, 64, 1.0), [4.25/64.0, 4.25/64.0, 4.25/64.0, 4.25/64.0]);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_draw_atlas_uv_inset() {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+        let texture = atlas(&mut core);
+        let source = Rectangle { x: 16.0, y: 0.0, width: 16.0, height: 32.0 };
+        let dest = Rectangle { x: 0.0, y: 0.0, width: 16.0, height: 32.0 };
+
+        let quad = |[left, top, right, bottom]: [f32; 4]| vec![left, top, left, bottom, right, bottom, right, top];
+
+        // Global inset applies to every draw, explicit inset overrides it
+        assert_eq!(get_atlas_uv_inset(&core), 0.0);
+        assert_eq!(texcoords(&mut core, &calls, |core| draw_texture_pro(core, &texture, source, dest, Vector2::ZERO, Degrees::ZERO, Color::WHITE)), quad([0.25, 0.0, 0.5, 0.5]));
+        set_atlas_uv_inset(&mut core, 0.5);
+        assert_eq!(get_atlas_uv_inset(&core), 0.5);
+        assert_eq!(texcoords(&mut core, &calls, |core| draw_texture_pro(core, &texture, source, dest, Vector2::ZERO, Degrees::ZERO, Color::WHITE)), quad([16.5/64.0, 0.5/64.0, 31.5/64.0, 31.5/64.0]));
+        assert_eq!(texcoords(&mut core, &calls, |core| draw_texture_pro_inset(core, &texture, source, dest, Vector2::ZERO, Degrees::ZERO, Color::WHITE, 0.0)), quad([0.25, 0.0, 0.5, 0.5]));
+
+        let sprite = Sprite { dest, origin: Vector2::ZERO, rotation: Degrees::ZERO, tint: Color::WHITE };
+        let both = quad([17.0/64.0, 1.0/64.0, 31.0/64.0, 31.0/64.0]).repeat(2);
+        assert_eq!(texcoords(&mut core, &calls, |core| draw_sprites_inset(core, &texture, source, 1.0, [sprite, sprite])), both);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_glyph_uv_inset() {
+        let mut core = Core::default();
+        let mut texture = atlas(&mut core);
+        set_atlas_uv_inset(&mut core, 0.5);
+
+        // Point filtered atlases don't bleed, only bilinear ones are inset
+        assert_eq!(texture.filter(), TextureFilter::Point);
+        assert_eq!(crate::graphics::font::glyph_uv_inset(&core, &texture), 0.0);
+        set_texture_filter(&mut core, &mut texture, TextureFilter::Bilinear);
+        assert_eq!(texture.filter(), TextureFilter::Bilinear);
+        assert_eq!(crate::graphics::font::glyph_uv_inset(&core, &texture), 0.5);
+
+        // Trilinear filtering needs mipmaps
+        set_texture_filter(&mut core, &mut texture, TextureFilter::Trilinear);
+        assert_eq!(texture.filter(), TextureFilter::Bilinear);
+    }
+}
@@ -0,0 +1,442 @@
+//! Tiled textures, images larger than the maximum texture size split into GPU-sized tiles
+
+use crate::{prelude::*, tracelog, utils::load_file_data};
+use super::png::PngDecoder;
+
+/// Grid of square tiles covering an image, tiles on the right and bottom edges may be smaller
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileGrid {
+    /// Full image width in pixels
+    width: u32,
+    /// Full image height in pixels
+    height: u32,
+    /// Tile width and height in pixels
+    tile_size: u32,
+}
+
+/// Single tile draw computed by [`TileGrid::draw_calls`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileDraw {
+    /// Tile index, row-major
+    pub tile: usize,
+    /// Source rectangle, relative to the tile
+    pub source: Rectangle,
+    /// Destination rectangle
+    pub dest: Rectangle,
+}
+
+impl TileGrid {
+    /// Create grid for an image, `tile_size` should not exceed the device maximum texture size
+    #[must_use]
+    pub const fn new(width: u32, height: u32, tile_size: u32) -> Self {
+        Self {
+            width,
+            height,
+            tile_size: if tile_size == 0 { 1 } else { tile_size },
+        }
+    }
+
+    /// Full image width in pixels
+    #[inline]
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Full image height in pixels
+    #[inline]
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Tile width and height in pixels
+    #[inline]
+    #[must_use]
+    pub const fn tile_size(&self) -> u32 {
+        self.tile_size
+    }
+
+    /// Number of tile columns
+    #[inline]
+    #[must_use]
+    pub const fn columns(&self) -> u32 {
+        self.width.div_ceil(self.tile_size)
+    }
+
+    /// Number of tile rows
+    #[inline]
+    #[must_use]
+    pub const fn rows(&self) -> u32 {
+        self.height.div_ceil(self.tile_size)
+    }
+
+    /// Total number of tiles
+    #[inline]
+    #[must_use]
+    pub const fn tile_count(&self) -> usize {
+        self.columns() as usize * self.rows() as usize
+    }
+
+    /// Get tile rectangle in image pixels, `None` if index is out of the grid
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn tile_rect(&self, index: usize) -> Option<Rectangle> {
+        if index >= self.tile_count() {
+            return None;
+        }
+        let column = (index % self.columns() as usize) as u32;
+        let row = (index / self.columns() as usize) as u32;
+        let x = column * self.tile_size;
+        let y = row * self.tile_size;
+        Some(Rectangle::new(
+            x as f32,
+            y as f32,
+            self.tile_size.min(self.width - x) as f32,
+            self.tile_size.min(self.height - y) as f32,
+        ))
+    }
+
+    /// Compute the per-tile draws equivalent to drawing `source` (in image pixels) into `dest`
+    /// NOTE: Source is clipped to the image, flipping (negative source size) is not supported.
+    /// Tile edges are mapped through the same function, so adjacent draws share edges up to float rounding
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn draw_calls(&self, source: Rectangle, dest: Rectangle) -> Vec<TileDraw> {
+        if source.width <= 0.0 || source.height <= 0.0 {
+            return Vec::new();
+        }
+
+        let scale_x = dest.width / source.width;
+        let scale_y = dest.height / source.height;
+        let map_x = |x: f32| dest.x + (x - source.x) * scale_x;
+        let map_y = |y: f32| dest.y + (y - source.y) * scale_y;
+
+        // Clip source to image bounds
+        let x0 = source.x.max(0.0);
+        let y0 = source.y.max(0.0);
+        let x1 = source.x_max().min(self.width as f32);
+        let y1 = source.y_max().min(self.height as f32);
+        if x0 >= x1 || y0 >= y1 {
+            return Vec::new();
+        }
+
+        let tile_size = self.tile_size as f32;
+        let (first_column, last_column) = ((x0 / tile_size) as u32, ((x1 / tile_size).ceil() as u32).min(self.columns()));
+        let (first_row, last_row) = ((y0 / tile_size) as u32, ((y1 / tile_size).ceil() as u32).min(self.rows()));
+
+        let mut draws = Vec::with_capacity(((last_column - first_column) * (last_row - first_row)) as usize);
+        for row in first_row..last_row {
+            let tile_y = row as f32 * tile_size;
+            let top = y0.max(tile_y);
+            let bottom = y1.min(tile_y + tile_size);
+            if top >= bottom {
+                continue;
+            }
+
+            for column in first_column..last_column {
+                let tile_x = column as f32 * tile_size;
+                let left = x0.max(tile_x);
+                let right = x1.min(tile_x + tile_size);
+                if left >= right {
+                    continue;
+                }
+
+                draws.push(TileDraw {
+                    tile: row as usize * self.columns() as usize + column as usize,
+                    source: Rectangle::new(left - tile_x, top - tile_y, right - left, bottom - top),
+                    dest: Rectangle::new(map_x(left), map_y(top), map_x(right) - map_x(left), map_y(bottom) - map_y(top)),
+                });
+            }
+        }
+        draws
+    }
+
+    /// Split image in tile images, row-major
+    /// NOTE: Image size must match the grid size, `None` otherwise
+    #[must_use]
+    pub fn split_image(&self, image: &Image) -> Option<Vec<Image>> {
+        if image.width != self.width as usize || image.height != self.height as usize {
+            return None;
+        }
+        (0..self.tile_count())
+            .map(|index| self.tile_rect(index).map(|rec| image.from_image(rec)))
+            .collect()
+    }
+}
+
+/// Texture made of several tiles, used for images larger than the maximum texture size
+/// NOTE: Tiles are sampled independently, use point filtering or expect
+/// slightly visible seams with bilinear filtering
+#[derive(Debug)]
+pub struct TiledTexture {
+    grid: TileGrid,
+    /// Tile textures, row-major
+    tiles: Vec<Texture>,
+}
+
+impl TiledTexture {
+    /// Create tiled texture from already uploaded tiles, `None` if tile count doesn't match the grid
+    #[must_use]
+    pub fn from_tiles(grid: TileGrid, tiles: Vec<Texture>) -> Option<Self> {
+        (tiles.len() == grid.tile_count()).then_some(Self { grid, tiles })
+    }
+
+    /// Load tiled texture from file, tiles are uploaded to GPU as the image is decoded
+    /// NOTE: PNG files are decoded progressively, one row of tiles at a time, other formats are fully loaded first.
+    /// Tile size is reduced to the maximum texture size supported by the GPU
+    #[must_use]
+    pub fn load(core: &mut Core, path: impl AsRef<std::path::Path>, tile_size: u32) -> Option<Self> {
+        let (file_type, data) = load_file_data(path.as_ref())?;
+        let max_texture_size = core.rlgl.get_ext_supported().max_texture_size;
+        let tile_size = if max_texture_size > 0 { tile_size.min(max_texture_size) } else { tile_size };
+
+        let tiled = if cfg!(feature = "support_fileformat_png") && file_type.eq_ignore_ascii_case(".png") {
+            Self::load_png(core, &data, tile_size)
+        } else {
+            Self::load_from_image(core, &Image::load_from_memory(&file_type, &data)?, tile_size)
+        };
+
+        match &tiled {
+            Some(tiled) => tracelog!(Info, "TEXTURE: Tiled texture loaded successfully ({}x{} | {} tiles of {}x{})", tiled.width(), tiled.height(), tiled.tiles.len(), tile_size, tile_size),
+            None => tracelog!(Warning, "TEXTURE: Failed to load tiled texture"),
+        }
+        tiled
+    }
+
+    /// Load tiled texture from image (RAM), a texture is uploaded for each tile
+    #[must_use]
+    pub fn load_from_image(core: &mut Core, image: &Image, tile_size: u32) -> Option<Self> {
+        let grid = TileGrid::new(u32::try_from(image.width).ok()?, u32::try_from(image.height).ok()?, tile_size);
+        let tiles = grid.split_image(image)?
+            .iter()
+            .map(|tile| Texture::load_from_image(core, tile).ok())
+            .collect::<Option<Vec<_>>>()?;
+        Self::from_tiles(grid, tiles)
+    }
+
+    /// Decode PNG file data row by row, uploading each row of tiles once complete
+    /// NOTE: Only one row of tiles is kept in memory
+    #[allow(clippy::cast_precision_loss)]
+    fn load_png(core: &mut Core, data: &[u8], tile_size: u32) -> Option<Self> {
+        let decoder = PngDecoder::new(data)?;
+        let grid = TileGrid::new(u32::try_from(decoder.width).ok()?, u32::try_from(decoder.height).ok()?, tile_size);
+        let band_height = grid.tile_size as usize;
+        let mut band = Image {
+            data: Vec::with_capacity(decoder.width * band_height * decoder.format.bytes_per_pixel()?),
+            width: decoder.width,
+            height: 0,
+            mipmap: 1,
+            format: decoder.format,
+        };
+        let mut tiles = Vec::with_capacity(grid.tile_count());
+        let (mut rows, mut uploaded) = (0, true);
+
+        decoder.decode_rows(|row| {
+            band.data.extend_from_slice(row);
+            band.height += 1;
+            rows += 1;
+            if band.height == band_height || rows == decoder.height {
+                for column in 0..grid.columns() {
+                    let x = (column * grid.tile_size) as f32;
+                    let tile = band.from_image(Rectangle::new(x, 0.0, grid.tile_size as f32, band.height as f32));
+                    match Texture::load_from_image(core, &tile) {
+                        Ok(texture) => tiles.push(texture),
+                        Err(_) => uploaded = false,
+                    }
+                }
+                band.data.clear();
+                band.height = 0;
+            }
+        })?;
+
+        uploaded.then(|| Self::from_tiles(grid, tiles)).flatten()
+    }
+
+    /// Tile layout
+    #[inline]
+    #[must_use]
+    pub const fn grid(&self) -> &TileGrid {
+        &self.grid
+    }
+
+    /// Tile textures, row-major
+    #[inline]
+    #[must_use]
+    pub fn tiles(&self) -> &[Texture] {
+        &self.tiles
+    }
+
+    /// Full texture width in pixels
+    #[inline]
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.grid.width
+    }
+
+    /// Full texture height in pixels
+    #[inline]
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.grid.height
+    }
+
+    /// Compute the tile textures and rectangles to draw `source` into `dest`
+    pub fn draw_calls(&self, source: Rectangle, dest: Rectangle) -> impl Iterator<Item = (&Texture, TileDraw)> {
+        self.grid.draw_calls(source, dest)
+            .into_iter()
+            .map(|draw| (&self.tiles[draw.tile], draw))
+    }
+
+    /// Draw a part of the tiled texture defined by a rectangle (in image pixels), scaled into `dest`
+    /// NOTE: Like [`draw_texture_pro`] without origin and rotation, a draw is issued per visible tile
+    pub fn draw(&self, core: &mut Core, source: Rectangle, dest: Rectangle, tint: Color) {
+        for (texture, draw) in self.draw_calls(source, dest) {
+            draw_texture_pro(core, texture, draw.source, draw.dest, Vector2::ZERO, Degrees(0.0), tint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn numbered_image(width: usize, height: usize) -> Image {
+        Image {
+            data: (0..width*height).flat_map(|i| [(i%width) as u8, (i/width) as u8, 7, 255]).collect(),
+            width,
+            height,
+            mipmap: 1,
+            format: PixelFormat::UncompressedR8G8B8A8,
+        }
+    }
+
+    /// Edges closer than float rounding of the mapped coordinates, no visible gap or overlap
+    #[track_caller]
+    fn assert_edge(a: f32, b: f32) {
+        assert!((a - b).abs() <= a.abs().max(b.abs()) * f32::EPSILON * 2.0, "edge {a} != {b}");
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_tile_grid_layout() {
+        let grid = TileGrid::new(100, 70, 32);
+        assert_eq!((grid.columns(), grid.rows(), grid.tile_count()), (4, 3, 12));
+        assert_eq!(grid.tile_rect(0), Some(Rectangle::new(0.0, 0.0, 32.0, 32.0)));
+        // Right and bottom edge tiles are smaller
+        assert_eq!(grid.tile_rect(3), Some(Rectangle::new(96.0, 0.0, 4.0, 32.0)));
+        assert_eq!(grid.tile_rect(11), Some(Rectangle::new(96.0, 64.0, 4.0, 6.0)));
+        assert_eq!(grid.tile_rect(12), None);
+        // Exact multiple of the tile size, zero tile size
+        assert_eq!(TileGrid::new(64, 64, 32).tile_count(), 4);
+        assert_eq!(TileGrid::new(3, 2, 0).tile_count(), 6);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_tile_grid_draw_calls_seamless() {
+        let grid = TileGrid::new(100, 70, 32);
+        let source = Rectangle::new(10.5, 20.0, 80.0, 45.0);
+        let dest = Rectangle::new(-3.0, 7.25, 173.0, 91.0);
+        let draws = grid.draw_calls(source, dest);
+        assert_eq!(draws.iter().map(|draw| draw.tile).collect::<Vec<_>>(), [0, 1, 2, 4, 5, 6, 8, 9, 10]);
+
+        for draw in &draws {
+            // Source stays inside its tile
+            let tile = grid.tile_rect(draw.tile).unwrap();
+            assert!(draw.source.x >= 0.0 && draw.source.y >= 0.0, "{draw:?}");
+            assert!(draw.source.x_max() <= tile.width && draw.source.y_max() <= tile.height, "{draw:?}");
+
+            // Adjacent tiles share destination edges
+            let right = draws.iter().find(|other| other.tile == draw.tile + 1 && other.dest.y == draw.dest.y);
+            if let Some(right) = right {
+                assert_edge(draw.dest.x + draw.dest.width, right.dest.x);
+                assert_eq!(draw.dest.height, right.dest.height);
+            }
+            let below = draws.iter().find(|other| other.tile == draw.tile + 4);
+            if let Some(below) = below {
+                assert_edge(draw.dest.y + draw.dest.height, below.dest.y);
+                assert_eq!(draw.dest.width, below.dest.width);
+            }
+        }
+
+        // Draws cover the destination exactly
+        assert_eq!((draws[0].dest.x, draws[0].dest.y), (dest.x, dest.y));
+        let last = draws.last().unwrap();
+        assert_edge(last.dest.x + last.dest.width, dest.x + dest.width);
+        assert_edge(last.dest.y + last.dest.height, dest.y + dest.height);
+        let source_area: f32 = draws.iter().map(|draw| draw.source.width * draw.source.height).sum();
+        assert_eq!(source_area, source.width * source.height);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_tile_grid_draw_calls_boundaries() {
+        let grid = TileGrid::new(100, 70, 32);
+        // Source starting on a tile edge doesn't touch the previous tile
+        let draws = grid.draw_calls(Rectangle::new(32.0, 0.0, 32.0, 32.0), Rectangle::new(0.0, 0.0, 32.0, 32.0));
+        assert_eq!(draws, [TileDraw { tile: 1, source: Rectangle::new(0.0, 0.0, 32.0, 32.0), dest: Rectangle::new(0.0, 0.0, 32.0, 32.0) }]);
+
+        // Source is clipped to the image, destination keeps the source mapping
+        let draws = grid.draw_calls(Rectangle::new(-10.0, 60.0, 20.0, 20.0), Rectangle::new(0.0, 0.0, 40.0, 40.0));
+        assert_eq!(draws, [
+            TileDraw { tile: 4, source: Rectangle::new(0.0, 28.0, 10.0, 4.0), dest: Rectangle::new(20.0, 0.0, 20.0, 8.0) },
+            TileDraw { tile: 8, source: Rectangle::new(0.0, 0.0, 10.0, 6.0), dest: Rectangle::new(20.0, 8.0, 20.0, 12.0) },
+        ]);
+
+        // Outside, empty and flipped sources draw nothing
+        assert!(grid.draw_calls(Rectangle::new(100.0, 0.0, 10.0, 10.0), Rectangle::new(0.0, 0.0, 10.0, 10.0)).is_empty());
+        assert!(grid.draw_calls(Rectangle::new(0.0, 0.0, 0.0, 10.0), Rectangle::new(0.0, 0.0, 10.0, 10.0)).is_empty());
+        assert!(grid.draw_calls(Rectangle::new(0.0, 0.0, -10.0, 10.0), Rectangle::new(0.0, 0.0, 10.0, 10.0)).is_empty());
+
+        // Whole image, one draw per tile
+        let draws = grid.draw_calls(Rectangle::new(0.0, 0.0, 100.0, 70.0), Rectangle::new(0.0, 0.0, 100.0, 70.0));
+        assert_eq!(draws.len(), grid.tile_count());
+        assert!(draws.iter().all(|draw| Some(draw.dest) == grid.tile_rect(draw.tile)));
+    }
+
+    #[test]
+    fn test_tile_grid_split_image() {
+        let image = numbered_image(10, 7);
+        let grid = TileGrid::new(10, 7, 4);
+        let tiles = grid.split_image(&image).unwrap();
+        assert_eq!(tiles.len(), 6);
+        assert_eq!(tiles.iter().map(|tile| (tile.width, tile.height)).collect::<Vec<_>>(), [(4, 4), (4, 4), (2, 4), (4, 3), (4, 3), (2, 3)]);
+
+        // Every tile pixel comes from its position in the image
+        for (index, tile) in tiles.iter().enumerate() {
+            let rec = grid.tile_rect(index).unwrap();
+            for (i, pixel) in tile.data.chunks_exact(4).enumerate() {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let (x, y) = ((rec.x as usize + i%tile.width) as u8, (rec.y as usize + i/tile.width) as u8);
+                assert_eq!(pixel, [x, y, 7, 255], "tile {index}");
+            }
+        }
+
+        assert!(TileGrid::new(10, 8, 4).split_image(&image).is_none());
+    }
+
+    #[test]
+    fn test_tiled_texture_load() {
+        let mut core = Core::default();
+        let image = numbered_image(70, 45);
+        let path = std::env::temp_dir().join(format!("tiled_texture_{}.png", std::process::id()));
+        std::fs::write(&path, crate::graphics::png::encode(&image).unwrap()).unwrap();
+        let tiled = TiledTexture::load(&mut core, &path, 32);
+        std::fs::remove_file(&path).unwrap();
+
+        let tiled = tiled.unwrap();
+        assert_eq!((tiled.width(), tiled.height(), tiled.grid().tile_size()), (70, 45, 32));
+        let expected = TileGrid::new(70, 45, 32).split_image(&image).unwrap();
+        assert_eq!(tiled.tiles().len(), expected.len());
+        for (texture, tile) in tiled.tiles().iter().zip(&expected) {
+            assert_eq!((texture.width, texture.height, texture.format), (tile.width, tile.height, PixelFormat::UncompressedR8G8B8A8));
+        }
+        // Streamed and whole image loading agree
+        let whole = TiledTexture::load_from_image(&mut core, &image, 32).unwrap();
+        assert_eq!(whole.grid(), tiled.grid());
+
+        assert!(TiledTexture::load(&mut core, "missing.png", 32).is_none());
+    }
+}
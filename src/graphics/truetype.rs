@@ -0,0 +1,586 @@
+//! Minimal TrueType decoder: cmap (formats 4 and 12), horizontal metrics and quadratic glyph outlines
+//! (simple and composite), rasterized with non-zero winding and anti-aliasing
+
+use crate::prelude::*;
+
+/// Vertical samples per pixel row, horizontal coverage is computed exactly
+const SUBSAMPLES: usize = 4;
+/// Maximum nesting of composite glyphs
+const MAX_COMPONENT_DEPTH: usize = 8;
+
+fn read_u8(data: &[u8], offset: usize) -> Option<u8> {
+    data.get(offset).copied()
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(*data.get(offset..)?.first_chunk::<2>()?))
+}
+
+fn read_i16(data: &[u8], offset: usize) -> Option<i16> {
+    Some(i16::from_be_bytes(*data.get(offset..)?.first_chunk::<2>()?))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(*data.get(offset..)?.first_chunk::<4>()?))
+}
+
+/// Read a 2.14 fixed point number
+fn read_f2dot14(data: &[u8], offset: usize) -> Option<f32> {
+    read_i16(data, offset).map(|value| f32::from(value) / 16384.0)
+}
+
+/// Outline point in font units, y pointing up
+#[derive(Debug, Clone, Copy)]
+struct OutlinePoint {
+    x: f32,
+    y: f32,
+    on_curve: bool,
+}
+
+/// Glyph coverage bitmap, one byte per pixel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GlyphBitmap {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    /// Horizontal offset from the pen position to the bitmap left side, in pixels
+    pub(crate) offset_x: i32,
+    /// Vertical offset from the baseline to the bitmap top side, in pixels (y pointing down)
+    pub(crate) offset_y: i32,
+    pub(crate) coverage: Vec<u8>,
+}
+
+/// TrueType font data, tables are read on demand
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TrueType<'a> {
+    data: &'a [u8],
+    /// Offset of the selected cmap subtable
+    cmap: usize,
+    loca: usize,
+    glyf: usize,
+    hmtx: usize,
+    num_glyphs: u16,
+    num_h_metrics: u16,
+    /// `loca` uses 32 bit offsets
+    long_loca: bool,
+    /// Distance from baseline to the top of the font, in font units
+    pub(crate) ascent: i16,
+    /// Distance from baseline to the bottom of the font, in font units (usually negative)
+    pub(crate) descent: i16,
+    /// Recommended gap between lines, in font units
+    pub(crate) line_gap: i16,
+}
+
+impl<'a> TrueType<'a> {
+    /// Parse font tables, returns `None` if a required table is missing or the data is not a TrueType font
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let version = read_u32(data, 0)?;
+        if version != 0x0001_0000 && version != u32::from_be_bytes(*b"true") {
+            return None;
+        }
+
+        let num_tables = usize::from(read_u16(data, 4)?);
+        let find_table = |tag: &[u8; 4]| (0..num_tables)
+            .map(|index| 12 + 16*index)
+            .find(|&record| data.get(record..record + 4) == Some(tag))
+            .and_then(|record| read_u32(data, record + 8))
+            .map(|offset| offset as usize);
+
+        let head = find_table(b"head")?;
+        let hhea = find_table(b"hhea")?;
+        let cmap = find_table(b"cmap")?;
+
+        // Prefer full unicode subtables over BMP ones
+        let num_subtables = usize::from(read_u16(data, cmap + 2)?);
+        let mut subtable = None;
+        for record in (0..num_subtables).map(|index| cmap + 4 + 8*index) {
+            let platform = read_u16(data, record)?;
+            let encoding = read_u16(data, record + 2)?;
+            let offset = cmap + read_u32(data, record + 4)? as usize;
+            match (platform, encoding) {
+                (3, 10) | (0, 4 | 6) => subtable = Some(offset),
+                (3, 1) | (0, _) => subtable = subtable.or(Some(offset)),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            data,
+            cmap: subtable?,
+            loca: find_table(b"loca")?,
+            glyf: find_table(b"glyf")?,
+            hmtx: find_table(b"hmtx")?,
+            num_glyphs: read_u16(data, find_table(b"maxp")? + 4)?,
+            num_h_metrics: read_u16(data, hhea + 34)?,
+            long_loca: read_i16(data, head + 50)? != 0,
+            ascent: read_i16(data, hhea + 4)?,
+            descent: read_i16(data, hhea + 6)?,
+            line_gap: read_i16(data, hhea + 8)?,
+        })
+    }
+
+    /// Get scale factor from font units to pixels, so that ascent to descent spans `height` pixels
+    pub(crate) fn scale_for_pixel_height(&self, height: f32) -> f32 {
+        let font_height = f32::from(self.ascent) - f32::from(self.descent);
+        if font_height > 0.0 { height / font_height } else { 0.0 }
+    }
+
+    /// Get glyph index for a codepoint, 0 (missing glyph) if the font does not contain it
+    pub(crate) fn glyph_index(&self, codepoint: char) -> u16 {
+        self.find_glyph_index(u32::from(codepoint)).unwrap_or(0)
+    }
+
+    fn find_glyph_index(&self, codepoint: u32) -> Option<u16> {
+        let (data, table) = (self.data, self.cmap);
+        match read_u16(data, table)? {
+            4 => {
+                let codepoint = u16::try_from(codepoint).ok()?;
+                let segments = usize::from(read_u16(data, table + 6)? / 2);
+                let end_codes = table + 14;
+                let start_codes = end_codes + 2*segments + 2;
+                let deltas = start_codes + 2*segments;
+                let range_offsets = deltas + 2*segments;
+
+                let segment = (0..segments).find(|&i| read_u16(data, end_codes + 2*i).is_some_and(|end| codepoint <= end))?;
+                let start = read_u16(data, start_codes + 2*segment)?;
+                if codepoint < start {
+                    return None;
+                }
+                let delta = read_u16(data, deltas + 2*segment)?;
+                let range_offset = usize::from(read_u16(data, range_offsets + 2*segment)?);
+                if range_offset == 0 {
+                    return Some(codepoint.wrapping_add(delta));
+                }
+                let glyph_offset = range_offsets + 2*segment + range_offset + 2*usize::from(codepoint - start);
+                let glyph = read_u16(data, glyph_offset)?;
+                (glyph != 0).then(|| glyph.wrapping_add(delta))
+            }
+            12 => {
+                let groups = read_u32(data, table + 12)? as usize;
+                (0..groups).map(|i| table + 16 + 12*i).find_map(|group| {
+                    let start = read_u32(data, group)?;
+                    let end = read_u32(data, group + 4)?;
+                    (start..=end).contains(&codepoint)
+                        .then(|| read_u32(data, group + 8))
+                        .flatten()
+                        .and_then(|glyph| u16::try_from(glyph + (codepoint - start)).ok())
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Get glyph advance width, in font units
+    pub(crate) fn advance_width(&self, glyph: u16) -> u16 {
+        let metric = glyph.min(self.num_h_metrics.saturating_sub(1));
+        read_u16(self.data, self.hmtx + 4*usize::from(metric)).unwrap_or(0)
+    }
+
+    /// Get glyph data range in the `glyf` table, `None` for glyphs without outline
+    fn glyph_range(&self, glyph: u16) -> Option<(usize, usize)> {
+        if glyph >= self.num_glyphs {
+            return None;
+        }
+        let index = usize::from(glyph);
+        let (start, end) = if self.long_loca {
+            (read_u32(self.data, self.loca + 4*index)? as usize, read_u32(self.data, self.loca + 4*index + 4)? as usize)
+        } else {
+            (usize::from(read_u16(self.data, self.loca + 2*index)?) * 2, usize::from(read_u16(self.data, self.loca + 2*index + 2)?) * 2)
+        };
+        (start < end).then_some((self.glyf + start, self.glyf + end))
+    }
+
+    /// Get glyph contours, composite glyphs are flattened into their components
+    #[allow(clippy::cast_precision_loss)]
+    fn glyph_contours(&self, glyph: u16, depth: usize, contours: &mut Vec<Vec<OutlinePoint>>) -> Option<()> {
+        let Some((start, _)) = self.glyph_range(glyph) else { return Some(()) };
+        let data = self.data;
+        let num_contours = read_i16(data, start)?;
+
+        if num_contours >= 0 {
+            let num_contours = num_contours.unsigned_abs() as usize;
+            let end_points = start + 10;
+            let num_points = if num_contours == 0 { 0 } else { usize::from(read_u16(data, end_points + 2*(num_contours - 1))?) + 1 };
+            let instructions_length = usize::from(read_u16(data, end_points + 2*num_contours)?);
+            let mut offset = end_points + 2*num_contours + 2 + instructions_length;
+
+            let mut flags = Vec::with_capacity(num_points);
+            while flags.len() < num_points {
+                let flag = read_u8(data, offset)?;
+                offset += 1;
+                let repeat = if flag & 0x08 != 0 {
+                    offset += 1;
+                    read_u8(data, offset - 1)?
+                } else {
+                    0
+                };
+                flags.extend(std::iter::repeat_n(flag, usize::from(repeat) + 1));
+            }
+            flags.truncate(num_points);
+
+            // Coordinates are deltas, either a byte with a sign flag, a repeated value or a 16 bit value
+            let mut read_coords = |short_flag: u8, same_flag: u8| -> Option<Vec<f32>> {
+                let mut value = 0i32;
+                flags.iter().map(|&flag| {
+                    if flag & short_flag != 0 {
+                        let delta = i32::from(read_u8(data, offset)?);
+                        offset += 1;
+                        value += if flag & same_flag != 0 { delta } else { -delta };
+                    } else if flag & same_flag == 0 {
+                        value += i32::from(read_i16(data, offset)?);
+                        offset += 2;
+                    }
+                    Some(value as f32)
+                }).collect()
+            };
+            let xs = read_coords(0x02, 0x10)?;
+            let ys = read_coords(0x04, 0x20)?;
+
+            let mut first = 0;
+            for contour in 0..num_contours {
+                let last = usize::from(read_u16(data, end_points + 2*contour)?);
+                if last < first || last >= num_points {
+                    return None;
+                }
+                contours.push((first..=last).map(|i| OutlinePoint { x: xs[i], y: ys[i], on_curve: flags[i] & 0x01 != 0 }).collect());
+                first = last + 1;
+            }
+        } else if depth < MAX_COMPONENT_DEPTH {
+            let mut offset = start + 10;
+            loop {
+                let flags = read_u16(data, offset)?;
+                let component = read_u16(data, offset + 2)?;
+                offset += 4;
+
+                // NOTE: Components positioned by matching points are not supported, they are not moved
+                let (dx, dy) = if flags & 0x0001 != 0 {
+                    offset += 4;
+                    (f32::from(read_i16(data, offset - 4)?), f32::from(read_i16(data, offset - 2)?))
+                } else {
+                    offset += 2;
+                    (f32::from(read_u8(data, offset - 2)?.cast_signed()), f32::from(read_u8(data, offset - 1)?.cast_signed()))
+                };
+                let (dx, dy) = if flags & 0x0002 != 0 { (dx, dy) } else { (0.0, 0.0) };
+
+                let [xx, xy, yx, yy] = if flags & 0x0008 != 0 {
+                    offset += 2;
+                    let scale = read_f2dot14(data, offset - 2)?;
+                    [scale, 0.0, 0.0, scale]
+                } else if flags & 0x0040 != 0 {
+                    offset += 4;
+                    [read_f2dot14(data, offset - 4)?, 0.0, 0.0, read_f2dot14(data, offset - 2)?]
+                } else if flags & 0x0080 != 0 {
+                    offset += 8;
+                    [read_f2dot14(data, offset - 8)?, read_f2dot14(data, offset - 6)?, read_f2dot14(data, offset - 4)?, read_f2dot14(data, offset - 2)?]
+                } else {
+                    [1.0, 0.0, 0.0, 1.0]
+                };
+
+                let first = contours.len();
+                self.glyph_contours(component, depth + 1, contours)?;
+                for point in contours[first..].iter_mut().flatten() {
+                    let (x, y) = (point.x, point.y);
+                    point.x = xx*x + yx*y + dx;
+                    point.y = xy*x + yy*y + dy;
+                }
+
+                if flags & 0x0020 == 0 {
+                    break;
+                }
+            }
+        }
+        Some(())
+    }
+
+    /// Rasterize a glyph at a scale, `None` for glyphs without outline (i.e. space) or invalid glyph data
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+    pub(crate) fn rasterize(&self, glyph: u16, scale: f32) -> Option<GlyphBitmap> {
+        let mut contours = Vec::new();
+        self.glyph_contours(glyph, 0, &mut contours)?;
+
+        // Flatten curves in pixel space, y pointing down
+        let polylines: Vec<Vec<Vector2>> = contours.iter()
+            .filter(|contour| !contour.is_empty())
+            .map(|contour| flatten_contour(contour, scale))
+            .collect();
+
+        let (min, max) = polylines.iter().flatten().fold(
+            (Vector2::new(f32::MAX, f32::MAX), Vector2::new(f32::MIN, f32::MIN)),
+            |(min, max), point| (Vector2::new(min.x.min(point.x), min.y.min(point.y)), Vector2::new(max.x.max(point.x), max.y.max(point.y))),
+        );
+        if min.x >= max.x || min.y >= max.y {
+            return None;
+        }
+
+        let (x0, y0) = (min.x.floor(), min.y.floor());
+        let width = (max.x.ceil() - x0) as usize;
+        let height = (max.y.ceil() - y0) as usize;
+        let mut coverage = vec![0.0f32; width*height];
+
+        let edges: Vec<(Vector2, Vector2)> = polylines.iter()
+            .flat_map(|polyline| polyline.windows(2).map(|pair| (pair[0] - Vector2::new(x0, y0), pair[1] - Vector2::new(x0, y0))))
+            .filter(|(a, b)| (b.y - a.y).abs() > f32::EPSILON)
+            .collect();
+
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        for sample_row in 0..height*SUBSAMPLES {
+            let y = (sample_row as f32 + 0.5) / SUBSAMPLES as f32;
+            crossings.clear();
+            for (a, b) in &edges {
+                let (top, bottom) = if a.y < b.y { (a, b) } else { (b, a) };
+                if y >= top.y && y < bottom.y {
+                    let x = a.x + (y - a.y)*(b.x - a.x)/(b.y - a.y);
+                    crossings.push((x, if b.y > a.y { 1 } else { -1 }));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            // Fill spans with non-zero winding, partially covered pixels at span ends
+            let row = &mut coverage[(sample_row / SUBSAMPLES)*width..][..width];
+            let mut winding = 0;
+            let mut span_start = 0.0;
+            for &(x, direction) in &crossings {
+                let was_inside = winding != 0;
+                winding += direction;
+                if !was_inside && winding != 0 {
+                    span_start = x;
+                } else if was_inside && winding == 0 {
+                    let (start, end) = (span_start.clamp(0.0, width as f32), x.clamp(0.0, width as f32));
+                    let pixels = row.iter_mut().enumerate().take(end.ceil() as usize).skip(start as usize);
+                    for (pixel, value) in pixels {
+                        let overlap = end.min(pixel as f32 + 1.0) - start.max(pixel as f32);
+                        *value += overlap.max(0.0) / SUBSAMPLES as f32;
+                    }
+                }
+            }
+        }
+
+        Some(GlyphBitmap {
+            width,
+            height,
+            offset_x: x0 as i32,
+            offset_y: y0 as i32,
+            coverage: coverage.into_iter().map(|value| (value.clamp(0.0, 1.0)*255.0).round() as u8).collect(),
+        })
+    }
+}
+
+/// Flatten a contour of on/off-curve points into a closed polyline, scaled to pixels with y pointing down
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+fn flatten_contour(points: &[OutlinePoint], scale: f32) -> Vec<Vector2> {
+    let to_pixels = |point: &OutlinePoint| Vector2::new(point.x*scale, -point.y*scale);
+    let count = points.len();
+
+    // Start on an on-curve point, or on the implied one between the first two off-curve points
+    let (start, first) = match points.iter().position(|point| point.on_curve) {
+        Some(index) => (to_pixels(&points[index]), index + 1),
+        None => (to_pixels(&points[0]).lerp_to(to_pixels(&points[1 % count]), 0.5), 1),
+    };
+
+    let mut polyline = vec![start];
+    let quad_to = |polyline: &mut Vec<Vector2>, control: Vector2, end: Vector2| {
+        let from = *polyline.last().unwrap_or(&start);
+        // Subdivide according to the curve deviation from a straight line
+        let deviation = (from - control*2.0 + end).magnitude();
+        let steps = (deviation.sqrt()*2.0).ceil().clamp(1.0, 16.0) as usize;
+        for step in 1..=steps {
+            let t = step as f32 / steps as f32;
+            polyline.push(from.lerp_to(control, t).lerp_to(control.lerp_to(end, t), t));
+        }
+    };
+
+    let mut control = None;
+    for offset in 0..count {
+        let point = &points[(first + offset) % count];
+        let position = to_pixels(point);
+        control = match (point.on_curve, control) {
+            (true, None) => {
+                polyline.push(position);
+                None
+            }
+            (true, Some(control)) => {
+                quad_to(&mut polyline, control, position);
+                None
+            }
+            (false, None) => Some(position),
+            (false, Some(control)) => {
+                quad_to(&mut polyline, control, control.lerp_to(position, 0.5));
+                Some(position)
+            }
+        };
+    }
+    if let Some(control) = control {
+        quad_to(&mut polyline, control, start);
+    }
+    polyline
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Simple glyph from contours of `(x, y, on_curve)` points, coordinates stored as 16 bit deltas
+    fn simple_glyph(contours: &[&[(i16, i16, bool)]]) -> Vec<u8> {
+        let points: Vec<(i16, i16, bool)> = contours.iter().flat_map(|contour| contour.iter().copied()).collect();
+        let mut glyph = Vec::new();
+        glyph.extend(i16::try_from(contours.len()).unwrap().to_be_bytes());
+        glyph.extend([0; 8]); // Bounding box is not read
+        let mut end = 0;
+        for contour in contours {
+            end += contour.len();
+            glyph.extend(u16::try_from(end - 1).unwrap().to_be_bytes());
+        }
+        glyph.extend(0u16.to_be_bytes());
+        glyph.extend(points.iter().map(|&(_, _, on_curve)| u8::from(on_curve)));
+        for axis in [0, 1] {
+            let mut previous = 0;
+            for &(x, y, _) in &points {
+                let value = if axis == 0 { x } else { y };
+                glyph.extend((value - previous).to_be_bytes());
+                previous = value;
+            }
+        }
+        glyph
+    }
+
+    fn table_u16(values: &[u16]) -> Vec<u8> {
+        values.iter().flat_map(|value| value.to_be_bytes()).collect()
+    }
+
+    /// TrueType font with ascent 800, descent -200, line gap 100 and glyphs:
+    /// 'A' 400x600 square at x 100, 'B' quadratic arch 600 wide and 350 high,
+    /// 'C' composite of 'A' moved by (200, 100) and ' ' without outline.
+    /// The cmap has a format 4 subtable, plus a preferred format 12 one if `format_12`
+    pub(crate) fn ttf(format_12: bool) -> Vec<u8> {
+        let glyphs = [
+            Vec::new(),
+            simple_glyph(&[&[(100, 0, true), (100, 600, true), (500, 600, true), (500, 0, true)]]),
+            simple_glyph(&[&[(0, 0, true), (300, 700, false), (600, 0, true)]]),
+            [table_u16(&[0xFFFF, 0, 0, 0, 0, 0x0003, 1]), 200i16.to_be_bytes().to_vec(), 100i16.to_be_bytes().to_vec()].concat(),
+            Vec::new(),
+        ];
+
+        let mut glyf = Vec::new();
+        let mut loca = Vec::new();
+        for glyph in &glyphs {
+            loca.push(u16::try_from(glyf.len() / 2).unwrap());
+            glyf.extend(glyph);
+            glyf.resize(glyf.len().next_multiple_of(2), 0);
+        }
+        loca.push(u16::try_from(glyf.len() / 2).unwrap());
+
+        let mut head = vec![0; 54];
+        head[50..52].copy_from_slice(&0u16.to_be_bytes()); // Short loca offsets
+        let mut hhea = vec![0; 36];
+        hhea[4..10].copy_from_slice(&table_u16(&[800, (-200i16).cast_unsigned(), 100]));
+        hhea[34..36].copy_from_slice(&5u16.to_be_bytes());
+        let maxp = table_u16(&[0, 0x5000, 5]);
+        let hmtx = table_u16(&[600, 0, 600, 100, 700, 0, 800, 300, 250, 0]);
+
+        // Segments: ' ' -> 4, 'A'..='C' -> 1..=3, end marker
+        let subtable_4 = table_u16(&[
+            4, 40, 0, 6, 4, 1, 2,
+            32, 67, 0xFFFF, 0,
+            32, 65, 0xFFFF,
+            4u16.wrapping_sub(32), 1u16.wrapping_sub(65), 1,
+            0, 0, 0,
+        ]);
+        let subtable_12 = [
+            table_u16(&[12, 0, 0, 40, 0, 0, 0, 2]),
+            table_u16(&[0, 32, 0, 32, 0, 4]),
+            table_u16(&[0, 65, 0, 67, 0, 1]),
+        ].concat();
+        let mut cmap = table_u16(&[0, if format_12 { 2 } else { 1 }, 3, 1, 0, 20, 3, 10, 0, 20 + 40]);
+        if !format_12 {
+            cmap.truncate(12);
+            cmap[10..12].copy_from_slice(&12u16.to_be_bytes());
+        }
+        cmap.extend(subtable_4);
+        if format_12 {
+            cmap.extend(subtable_12);
+        }
+
+        let tables: [(&[u8; 4], Vec<u8>); 8] = [
+            (b"cmap", cmap), (b"glyf", glyf), (b"head", head), (b"hhea", hhea),
+            (b"hmtx", hmtx), (b"loca", table_u16(&loca)), (b"maxp", maxp), (b"name", Vec::new()),
+        ];
+        let mut font = [0x0001_0000u32.to_be_bytes().to_vec(), table_u16(&[8, 0, 0, 0])].concat();
+        let mut offset = 12 + 16*tables.len();
+        for (tag, table) in &tables {
+            font.extend(*tag);
+            font.extend([0; 4]);
+            font.extend(u32::try_from(offset).unwrap().to_be_bytes());
+            font.extend(u32::try_from(table.len()).unwrap().to_be_bytes());
+            offset += table.len().next_multiple_of(4);
+        }
+        for (_, table) in tables {
+            font.extend(table);
+            font.resize(font.len().next_multiple_of(4), 0);
+        }
+        font
+    }
+
+    #[test]
+    fn test_parse_and_cmap() {
+        for format_12 in [false, true] {
+            let data = ttf(format_12);
+            let font = TrueType::parse(&data).unwrap();
+            assert_eq!((font.ascent, font.descent, font.line_gap), (800, -200, 100));
+            assert_eq!(font.cmap, if format_12 { 12 + 16*8 + 20 + 40 } else { 12 + 16*8 + 12 });
+            let indices: Vec<u16> = [' ', 'A', 'B', 'C', 'D', '@', '\u{1F600}'].map(|ch| font.glyph_index(ch)).to_vec();
+            assert_eq!(indices, [4, 1, 2, 3, 0, 0, 0]);
+            assert_eq!(font.advance_width(3), 800);
+            // Glyphs past the metrics use the last advance
+            assert_eq!(font.advance_width(9), 250);
+        }
+        assert!(TrueType::parse(b"OTTO\0\0\0\0").is_none());
+        assert!(TrueType::parse(&ttf(false)[..100]).is_none());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_rasterize_square_and_composite() {
+        let data = ttf(false);
+        let font = TrueType::parse(&data).unwrap();
+        let scale = font.scale_for_pixel_height(10.0);
+        assert_eq!(scale, 0.01);
+
+        // Pixel aligned outlines are fully covered, y points down from the baseline
+        let square = font.rasterize(1, scale).unwrap();
+        assert_eq!((square.width, square.height, square.offset_x, square.offset_y), (4, 6, 1, -6));
+        assert!(square.coverage.iter().all(|&value| value == 255));
+
+        let composite = font.rasterize(3, scale).unwrap();
+        assert_eq!((composite.width, composite.height, composite.offset_x, composite.offset_y), (4, 6, 3, -7));
+        assert_eq!(composite.coverage, square.coverage);
+
+        let half = font.rasterize(1, scale * 0.5).unwrap();
+        assert_eq!((half.width, half.height, half.offset_x, half.offset_y), (3, 3, 0, -3));
+        assert_eq!(half.coverage, [128, 255, 128, 128, 255, 128, 128, 255, 128]);
+    }
+
+    #[test]
+    fn test_rasterize_curve() {
+        let data = ttf(false);
+        let font = TrueType::parse(&data).unwrap();
+        let arch = font.rasterize(2, 0.01).unwrap();
+        assert_eq!((arch.width, arch.height, arch.offset_x, arch.offset_y), (6, 4, 0, -4));
+        let pixel = |x: usize, y: usize| arch.coverage[y*arch.width + x];
+        // Top corners are outside the curve, bottom center is inside, edges are anti-aliased
+        assert_eq!((pixel(0, 0), pixel(5, 0)), (0, 0));
+        assert_eq!((pixel(2, 3), pixel(3, 3)), (255, 255));
+        assert!(arch.coverage.iter().any(|&value| value > 0 && value < 255));
+        // Symmetric outline, symmetric coverage
+        for row in arch.coverage.chunks_exact(arch.width) {
+            assert!(row.iter().eq(row.iter().rev()), "{row:?}");
+        }
+    }
+
+    #[test]
+    fn test_rasterize_empty_glyphs() {
+        let data = ttf(false);
+        let font = TrueType::parse(&data).unwrap();
+        assert!(font.rasterize(0, 0.01).is_none());
+        assert!(font.rasterize(4, 0.01).is_none());
+        assert!(font.rasterize(100, 0.01).is_none());
+    }
+}
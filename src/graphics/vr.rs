@@ -0,0 +1,153 @@
+use crate::{prelude::*, config::{RL_CULL_DISTANCE_NEAR, RL_CULL_DISTANCE_FAR}};
+
+/// Head-Mounted-Display device parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VrDeviceInfo {
+    /// Horizontal resolution in pixels
+    pub h_resolution: u32,
+    /// Vertical resolution in pixels
+    pub v_resolution: u32,
+    /// Horizontal size in meters
+    pub h_screen_size: f32,
+    /// Vertical size in meters
+    pub v_screen_size: f32,
+    /// Distance between eye and display in meters
+    pub eye_to_screen_distance: f32,
+    /// Lens separation distance in meters
+    pub lens_separation_distance: f32,
+    /// IPD (distance between pupils) in meters
+    pub interpupillary_distance: f32,
+    /// Lens distortion constant parameters
+    pub lens_distortion_values: [f32; 4],
+    /// Chromatic aberration correction parameters
+    pub chroma_ab_correction: [f32; 4],
+}
+
+impl Default for VrDeviceInfo {
+    /// Oculus Rift CV1 parameters, as used by raylib VR examples
+    fn default() -> Self {
+        Self {
+            h_resolution: 2160,
+            v_resolution: 1200,
+            h_screen_size: 0.133_793,
+            v_screen_size: 0.0669,
+            eye_to_screen_distance: 0.041,
+            lens_separation_distance: 0.07,
+            interpupillary_distance: 0.07,
+            lens_distortion_values: [1.0, 0.22, 0.24, 0.0],
+            chroma_ab_correction: [0.996, -0.004, 1.014, 0.0],
+        }
+    }
+}
+
+/// VR stereo rendering configuration for simulator
+/// NOTE: Index 0 of eye arrays is the left eye
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VrStereoConfig {
+    /// VR projection matrices (per eye)
+    pub projection: [Matrix; 2],
+    /// VR view offset matrices (per eye)
+    pub view_offset: [Matrix; 2],
+    /// VR left lens center
+    pub left_lens_center: [f32; 2],
+    /// VR right lens center
+    pub right_lens_center: [f32; 2],
+    /// VR left screen center
+    pub left_screen_center: [f32; 2],
+    /// VR right screen center
+    pub right_screen_center: [f32; 2],
+    /// VR distortion scale
+    pub scale: [f32; 2],
+    /// VR distortion scale in
+    pub scale_in: [f32; 2],
+}
+
+/// Load VR stereo config for VR simulator device parameters
+/// NOTE: Lens values are meant for a distortion shader, matrices are used by `begin_vr_stereo_mode()`
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn load_vr_stereo_config(device: &VrDeviceInfo) -> VrStereoConfig {
+    // Compute aspect ratio
+    let aspect = (device.h_resolution as f32*0.5)/device.v_resolution as f32;
+
+    // Compute lens parameters
+    let lens_shift = (device.h_screen_size*0.25 - device.lens_separation_distance*0.5)/device.h_screen_size;
+
+    // Compute distortion scale parameters
+    // NOTE: To get lens max radius, lensShift must be normalized to [-1..1]
+    let lens_radius = (-1.0 - 4.0*lens_shift).abs();
+    let lens_radius_sq = lens_radius*lens_radius;
+    let [k0, k1, k2, k3] = device.lens_distortion_values;
+    let distortion_scale = k0 + k1*lens_radius_sq + k2*lens_radius_sq*lens_radius_sq + k3*lens_radius_sq*lens_radius_sq*lens_radius_sq;
+
+    let norm_screen_width = 0.5;
+    let norm_screen_height = 1.0;
+
+    // Fovy is normally computed with: 2*atan2f(device.vScreenSize, 2*device.eyeToScreenDistance)
+    // ...but with lens distortion it is increased (see Oculus SDK Documentation)
+    let fovy = 2.0*(device.v_screen_size*0.5*distortion_scale).atan2(device.eye_to_screen_distance);
+
+    // Compute camera projection matrices
+    let proj_offset = 4.0*lens_shift; // Scaled to projection space coordinates [-1..1]
+    let proj = Matrix::perspective(f64::from(fovy), f64::from(aspect), f64::from(RL_CULL_DISTANCE_NEAR), f64::from(RL_CULL_DISTANCE_FAR));
+
+    VrStereoConfig {
+        projection: [
+            proj*Matrix::translate(proj_offset, 0.0, 0.0),
+            proj*Matrix::translate(-proj_offset, 0.0, 0.0),
+        ],
+        // Compute camera transformation matrices
+        // NOTE: Camera movement might seem more natural if we model the head
+        // Our axis of rotation is the base of our head, so we might want to add
+        // some y (base of head to eye level) and -z (center of head to eye protrusion) to the camera positions
+        view_offset: [
+            Matrix::translate(device.interpupillary_distance*0.5, 0.075, 0.045),
+            Matrix::translate(-device.interpupillary_distance*0.5, 0.075, 0.045),
+        ],
+        left_lens_center: [0.25 + lens_shift, 0.5],
+        right_lens_center: [0.75 - lens_shift, 0.5],
+        left_screen_center: [0.25, 0.5],
+        right_screen_center: [0.75, 0.5],
+        scale: [norm_screen_width*0.5/distortion_scale, norm_screen_height*0.5*aspect/distortion_scale],
+        scale_in: [2.0/norm_screen_width, 2.0/norm_screen_height/aspect],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn assert_near(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1.0e-4, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_load_vr_stereo_config() {
+        // Oculus Rift CV1: lens shift -0.0116, distortion scale 1.3985, fovy 97.5 degrees, eye aspect 0.9
+        let config = load_vr_stereo_config(&VrDeviceInfo::default());
+        let (lens_shift, distortion_scale, aspect) = (-0.011_598, 1.398_528, 0.9);
+        let fovy = 97.535_51_f32.to_radians();
+
+        assert_near(config.left_lens_center[0], 0.25 + lens_shift);
+        assert_near(config.right_lens_center[0], 0.75 - lens_shift);
+        assert_eq!((config.left_lens_center[1], config.right_lens_center[1]), (0.5, 0.5));
+        assert_eq!((config.left_screen_center, config.right_screen_center), ([0.25, 0.5], [0.75, 0.5]));
+        assert_near(config.scale[0], 0.25/distortion_scale);
+        assert_near(config.scale[1], 0.5*aspect/distortion_scale);
+        assert_near(config.scale_in[0], 4.0);
+        assert_near(config.scale_in[1], 2.0/aspect);
+
+        // Eyes share the projection, shifted horizontally in opposite directions
+        for projection in config.projection {
+            assert_near(projection.0[1][1], 1.0/(fovy*0.5).tan());
+            assert_near(projection.0[0][0], projection.0[1][1]/aspect);
+        }
+        let shift = config.projection[0] * Matrix::translate(-8.0*lens_shift, 0.0, 0.0);
+        assert!(<[f32; 16]>::from(shift).iter().zip(<[f32; 16]>::from(config.projection[1])).all(|(a, b)| (a - b).abs() < 1.0e-5));
+
+        // Eyes are half the interpupillary distance away from the head center
+        assert_eq!(config.view_offset[0], Matrix::translate(0.035, 0.075, 0.045));
+        assert_eq!(config.view_offset[1], Matrix::translate(-0.035, 0.075, 0.045));
+    }
+}
@@ -116,47 +116,140 @@ pub use platforms::rcore_desktop_sdl::*;
 pub mod prelude {
     pub use super::{
         core::{
-            *,
-            window::*,
-            input::*,
+            Point, Size, InputEventType, WindowEventType, CustomEventType, AutomationEventType,
+            AutomationEvent, Storage, StorageError, Time, Core, CoreBuilder,
+            window::{
+                VsyncMode, EventWaiting, MonitorID, BackgroundAudio, FramePolicy, background_frame_policy,
+                VideoMode, closest_video_mode, Window, ConfigFlags,
+            },
+            input::{
+                KeyboardKey, MouseButton, MouseCursor, GamepadID, GamepadButton, GamepadAxis, Keyboard, Mouse,
+                TouchPoint, Touch, MAX_GAMEPAD_NAME_LEN, Gamepad, Gamepads, Input, Gesture,
+            },
+            automation::{AutomationEventList, AutomationEventError},
+            random::RandomGenerator,
+            hooks::{HookStage, HookId},
+            files::{
+                DIRECTORY_FILTER_TAG, file_exists, directory_exists, is_path_file, is_file_extension,
+                get_file_length, get_file_extension, get_file_name, get_file_name_without_ext,
+                get_directory_path, get_prev_directory_path, get_working_directory, change_directory,
+                get_file_mod_time, try_get_file_mod_time, load_directory_files, load_directory_files_ex,
+                try_load_directory_files_ex,
+            },
+        },
+        utils::{
+            MemoryData, FileResolver, directory_resolver, DecompressError, Base64Error, encode_data_base64,
+            decode_data_base64, compute_crc32, compute_md5, compute_sha1, log_type_level, set_log_type_level,
+            set_trace_log_fn, clear_trace_log_fn,
+        },
+        color::{
+            Color, rgb, rgba, hsv, ColorblindKind, Palette, colors_from_rgba8, colors_from_rgba8_mut,
+            colors_as_rgba8, colors_to_rgba8, write_colors_rgba8, tint_slice, lerp_slices, premultiply_slice,
+            brightness_slice, contrast_slice, Gradient,
         },
-        utils::*,
-        color::*,
         math::{
-            *,
-            indicators::*,
-            matrix::*,
-            quaternion::*,
-            ray::*,
-            transform::*,
-            vector::*,
+            Wrap, Angle, Magnitude, Distance, Lerp, LerpTo, NormalizeBetween, Remap, NearEq, FloatExt,
+            indicators::{
+                Ratio, Angular, Radians, Radian, Degrees, Degree, Percent, Seconds, Second, Pixels, Pixel,
+                Units, Unit, Normalized, Position2, Offset2, Direction2, Position3, Offset3, Direction3,
+                Position4, Offset4, Direction4,
+            },
+            matrix::Matrix,
+            quaternion::Quaternion,
+            ray::{
+                Ray, RayCollision, get_ray_collision_sphere, get_ray_collision_box,
+                get_ray_collision_triangle, get_ray_collision_quad, get_ray_collision_ground,
+            },
+            transform::Transform,
+            vector::{DotProduct, MatrixTransform, Normalize, Vector, Vector2, Vector3, Vector4},
+            smoothing::{Smooth, exp_decay, smooth_damp, exp_decay_angle, smooth_damp_angle},
+            easing::{Ease, ease},
         },
         graphics::{
-            *,
             model::{
-                *,
-                animation::*,
-                material::*,
-                mesh::*,
+                BoundingBox, Model, draw_model,
+                material::{MaterialMapIndex, MaterialMap, Material},
+                mesh::{Mesh, upload_mesh, unload_mesh, update_mesh_buffers, draw_mesh},
+                lod::ModelLod,
             },
             drawing::{
-                *,
+                BlendMode, clear_background, begin_mode_2d, end_mode_2d, begin_mode_3d, end_mode_3d,
+                begin_texture_mode, end_texture_mode, begin_vr_stereo_mode, end_vr_stereo_mode,
+                begin_blend_mode, end_blend_mode, set_blend_factors, set_blend_factors_separate, ScissorMode,
+                begin_scissor_mode,
+                grid::{
+                    GRID_MAX_LINES, Edge, GridLines, constant_pixel_thickness, visible_grid_lines, RulerTick,
+                    ruler_ticks,
+                },
+            },
+            camera::{
+                Camera2D, get_world_to_screen_2d, get_screen_to_world_2d, Camera3D, Camera, CameraProjection,
+                CameraMode,
+            },
+            font::{
+                MAX_FONT_ATLAS_SIZE, GlyphInfo, Font, GlyphLayout, draw_text_ex, FontStack, FontOrStack,
+                draw_text_stack,
+            },
+            image::Image,
+            pixel_format::{PixelFormat, TextureFilter, TextureWrap, CubemapLayout},
+            render_texture::{RenderTexture, RenderTexture2D, RenderTextureError},
+            shader::{
+                Shader, ShaderLocationIndex, ShaderUniformData, ShaderUniformValue, begin_shader_mode,
+                end_shader_mode,
+            },
+            shared::{Shared, WeakShared, SharedTexture, SharedFont, SharedShader, WeakTexture, WeakFont, WeakShader},
+            atlas::AtlasPacker,
+            particles::{EmitterConfig, Particle, ParticleInstance, ParticleEmitter},
+            tiled::{TileGrid, TileDraw, TiledTexture},
+            rich_text::{TextRun, RichGlyph, parse_rich_text, layout_rich_text, measure_text_rich, draw_text_rich},
+            texture::{
+                Texture, Texture2D, TextureCubemap, TextureError, set_atlas_uv_inset, get_atlas_uv_inset,
+                SourceUv, set_texture_filter, update_texture, update_texture_rec, Sprite, draw_texture_pro,
+                draw_texture_pro_inset, draw_sprites, draw_sprites_inset,
             },
-            camera::*,
-            font::*,
-            image::*,
-            pixel_format::*,
-            render_texture::*,
-            shader::*,
-            texture::*,
+            vr::{VrDeviceInfo, VrStereoConfig, load_vr_stereo_config},
         },
         shapes::{
-            *,
-            circle::*,
-            rectangle::*,
-            triangle::*,
+            Shape, set_shapes_texture, get_shapes_texture_rectangle,
+            circle::{
+                Circle, draw_circle_v, draw_circle_sector, draw_circle_sector_lines, draw_circle_gradient,
+                draw_circle_lines_v, draw_ellipse, draw_ellipse_lines, draw_ring, draw_ring_lines,
+            },
+            rectangle::{
+                Rectangle, draw_rectangle_rec, draw_rectangle_pro, draw_rectangle_gradient_v,
+                draw_rectangle_gradient_h, draw_rectangle_gradient_ex, draw_rectangle_lines_ex,
+                draw_rectangle_rounded, draw_rectangle_rounded_lines, draw_rectangle_rounded_lines_ex,
+            },
+            triangle::{
+                Triangle2D, Triangle3D, Triangle, draw_triangle, draw_triangle_lines, draw_triangle_fan,
+                draw_triangle_strip, draw_poly, draw_poly_lines, draw_poly_lines_ex,
+            },
+            sweep::{SweepHit, sweep_aabb, sweep_aabb_multi, slide_response, bounce_response},
+            line::{draw_pixel, draw_line, draw_line_ex, draw_line_strip, draw_line_bezier},
+            spline::{
+                get_spline_point_linear, get_spline_point_basis, get_spline_point_catmull_rom,
+                get_spline_point_bezier_quad, get_spline_point_bezier_cubic, draw_spline_linear,
+                draw_spline_basis, draw_spline_catmull_rom, draw_spline_bezier_quadratic,
+                draw_spline_bezier_cubic, draw_spline_segment_linear, draw_spline_segment_basis,
+                draw_spline_segment_catmull_rom, draw_spline_segment_bezier_quadratic,
+                draw_spline_segment_bezier_cubic,
+            },
+            collision::{
+                check_collision_recs, check_collision_circles, check_collision_circle_rec,
+                check_collision_circle_line, check_collision_point_rec, check_collision_point_circle,
+                check_collision_point_triangle, check_collision_point_poly, check_collision_lines,
+                check_collision_point_line, get_collision_rec,
+            },
+        },
+        audio::{
+            device::{AUDIO_DEVICE_DEFAULT_SAMPLE_RATE, AudioBackend, NullAudio, AudioDevice},
+            voice::{VoiceHandle, PlayParams, StealPolicy, VoicePool},
+            wave::{Wave, Sound},
+            music::Music,
         },
     };
+    #[cfg(feature = "support_compression_api")]
+    pub use super::utils::{compress_data, decompress_data};
 }
 
 /// Trace log level
@@ -196,6 +289,7 @@ impl TryFrom<u8> for TraceLogType {
 /// Trace log level
 /// NOTE: Organized by priority level
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[non_exhaustive]
 pub enum TraceLogLevel {
     /// Display all logs
     All,
@@ -269,3 +363,156 @@ impl PartialOrd<TraceLogLevel> for TraceLogType {
         (*self as u8).partial_cmp(&(*other as u8))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, fmt::Write, fs, path::{Path, PathBuf}};
+
+    /// Public API snapshot, the public surface must only change on purpose
+    /// NOTE: Source level listing of `pub` items in public modules, regenerate with `UPDATE_PUBLIC_API=1 cargo test public_api`
+    const PUBLIC_API_SNAPSHOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/public-api.txt");
+
+    /// Source file of a module declared in `file`
+    fn module_file(file: &Path, name: &str) -> Option<PathBuf> {
+        let dir = match file.file_name()?.to_str()? {
+            "lib.rs" | "mod.rs" => file.parent()?.to_path_buf(),
+            _ => file.with_extension(""),
+        };
+        [dir.join(format!("{name}.rs")), dir.join(name).join("mod.rs")].into_iter().find(|path| path.is_file())
+    }
+
+    /// Collapse a multi-line signature into a single line without the item body
+    fn signature(lines: &[&str]) -> String {
+        let mut signature = lines.join(" ").split_whitespace().collect::<Vec<_>>().join(" ")
+            .replace("( ", "(").replace(", )", ")").replace(" )", ")")
+            .replace("{ ", "{").replace(", }", "}").replace(" }", "}");
+        if !signature.starts_with("pub use ") {
+            let is_value = signature.starts_with("pub const ") && !signature.starts_with("pub const fn ") || signature.starts_with("pub static ");
+            for end in [" where", " {", if is_value { " =" } else { ";" }] {
+                if let Some(index) = signature.find(end) {
+                    signature.truncate(index);
+                }
+            }
+        }
+        signature.trim_end_matches([';', ',']).to_owned()
+    }
+
+    /// Flatten a `use` tree into one path per imported name
+    fn use_paths(prefix: &str, tree: &str, paths: &mut Vec<String>) {
+        let tree = tree.trim().trim_end_matches(',');
+        let Some(open) = tree.find('{') else {
+            if !tree.is_empty() {
+                paths.push(format!("{prefix}{tree}"));
+            }
+            return;
+        };
+        let prefix = format!("{prefix}{}", &tree[..open]);
+        let (mut depth, mut start) = (0, open + 1);
+        for (index, ch) in tree.char_indices().skip(open + 1) {
+            match ch {
+                '{' => depth += 1,
+                '}' if depth == 0 => {
+                    use_paths(&prefix, &tree[start..index], paths);
+                    break;
+                }
+                '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    use_paths(&prefix, &tree[start..index], paths);
+                    start = index + 1;
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Public item signatures of a source file, unit tests excluded, and the modules it exposes
+    fn scan(source: &str) -> (Vec<String>, Vec<Vec<String>>) {
+        let (mut items, mut modules) = (Vec::new(), Vec::new());
+        let mut non_exhaustive = false;
+        let mut lines = source.lines().map(str::trim);
+        while let Some(line) = lines.next() {
+            if line.ends_with("mod tests {") {
+                break;
+            } else if line == "#[non_exhaustive]" {
+                non_exhaustive = true;
+                continue;
+            } else if !line.starts_with("pub ") {
+                non_exhaustive &= line.starts_with("#[") || line.starts_with("//");
+                continue;
+            }
+
+            // Gather the whole signature
+            let mut item = vec![line];
+            let is_use = line.starts_with("pub use ");
+            while !(item[item.len() - 1].ends_with(';') || !is_use && item[item.len() - 1].ends_with(['{', '}', ','])) {
+                let Some(line) = lines.next() else { break };
+                item.push(line);
+            }
+            let item = signature(&item);
+
+            if let Some(name) = item.strip_prefix("pub mod ").filter(|name| !name.contains('{')) {
+                modules.push(vec![name.to_owned()]);
+            } else if let Some(path) = item.strip_prefix("pub use ").and_then(|path| path.strip_suffix("::*")).filter(|path| !path.contains(['{', ' '])) {
+                modules.push(path.trim_start_matches("crate::").trim_start_matches("self::").split("::").map(str::to_owned).collect());
+            }
+            if let Some(tree) = item.strip_prefix("pub use ").filter(|tree| tree.contains('{')) {
+                let mut paths = Vec::new();
+                use_paths("", tree, &mut paths);
+                items.extend(paths.into_iter().map(|path| format!("pub use {path}")));
+                continue;
+            }
+            items.push(if std::mem::take(&mut non_exhaustive) { format!("#[non_exhaustive] {item}") } else { item });
+        }
+        (items, modules)
+    }
+
+    /// Public API listing, one item per line prefixed by its source file
+    fn public_api() -> String {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut files = BTreeMap::new();
+        let mut pending = vec![src.join("lib.rs")];
+        while let Some(file) = pending.pop() {
+            if files.contains_key(&file) {
+                continue;
+            }
+            let (items, modules) = scan(&fs::read_to_string(&file).unwrap());
+            for path in modules {
+                let mut module = Some(file.clone());
+                for name in &path {
+                    module = module.and_then(|module| module_file(&module, name));
+                }
+                pending.extend(module);
+            }
+            files.insert(file, items);
+        }
+
+        let mut listing = String::new();
+        for (file, items) in files {
+            let file = file.strip_prefix(&src).unwrap().to_string_lossy().replace('\\', "/");
+            for item in items {
+                writeln!(listing, "{file}: {item}").unwrap();
+            }
+        }
+        listing
+    }
+
+    #[test]
+    fn test_public_api() {
+        let current = public_api();
+        if std::env::var_os("UPDATE_PUBLIC_API").is_some() {
+            fs::write(PUBLIC_API_SNAPSHOT, &current).unwrap();
+            return;
+        }
+
+        let snapshot = fs::read_to_string(PUBLIC_API_SNAPSHOT).unwrap_or_default().replace("\r\n", "\n");
+        if current != snapshot {
+            let added: Vec<_> = current.lines().filter(|line| !snapshot.lines().any(|old| old == *line)).collect();
+            let removed: Vec<_> = snapshot.lines().filter(|line| !current.lines().any(|new| new == *line)).collect();
+            panic!(
+                "public API changed, review and regenerate public-api.txt with `UPDATE_PUBLIC_API=1 cargo test public_api`\nadded:\n  {}\nremoved:\n  {}",
+                added.join("\n  "),
+                removed.join("\n  "),
+            );
+        }
+    }
+}
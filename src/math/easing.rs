@@ -0,0 +1,476 @@
+//! Robert Penner easing equations, port of raylib `reasings.h`
+//!
+//! Every function takes the current time `t`, the `start` value, the total `change`
+//! of the value and the `duration` of the animation, `t` ranging `[0..duration]`
+
+use std::f32::consts::PI;
+use crate::prelude::*;
+
+/// Linear easing
+#[inline]
+#[must_use]
+pub fn linear(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    change*t/duration + start
+}
+
+/// Sine easing in
+#[inline]
+#[must_use]
+pub fn sine_in(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    -change*(t/duration*(PI/2.0)).cos() + change + start
+}
+
+/// Sine easing out
+#[inline]
+#[must_use]
+pub fn sine_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    change*(t/duration*(PI/2.0)).sin() + start
+}
+
+/// Sine easing in-out
+#[inline]
+#[must_use]
+pub fn sine_in_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    -change/2.0*((PI*t/duration).cos() - 1.0) + start
+}
+
+/// Quadratic easing in
+#[inline]
+#[must_use]
+pub fn quad_in(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/duration;
+    change*t*t + start
+}
+
+/// Quadratic easing out
+#[inline]
+#[must_use]
+pub fn quad_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/duration;
+    -change*t*(t - 2.0) + start
+}
+
+/// Quadratic easing in-out
+#[inline]
+#[must_use]
+pub fn quad_in_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/(duration/2.0);
+    if t < 1.0 {
+        change/2.0*(t*t) + start
+    } else {
+        -change/2.0*((t - 1.0)*(t - 3.0) - 1.0) + start
+    }
+}
+
+/// Cubic easing in
+#[inline]
+#[must_use]
+pub fn cubic_in(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/duration;
+    change*t*t*t + start
+}
+
+/// Cubic easing out
+#[inline]
+#[must_use]
+pub fn cubic_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/duration - 1.0;
+    change*(t*t*t + 1.0) + start
+}
+
+/// Cubic easing in-out
+#[inline]
+#[must_use]
+pub fn cubic_in_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/(duration/2.0);
+    if t < 1.0 {
+        change/2.0*t*t*t + start
+    } else {
+        let t = t - 2.0;
+        change/2.0*(t*t*t + 2.0) + start
+    }
+}
+
+/// Quartic easing in
+#[inline]
+#[must_use]
+pub fn quart_in(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/duration;
+    change*t*t*t*t + start
+}
+
+/// Quartic easing out
+#[inline]
+#[must_use]
+pub fn quart_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/duration - 1.0;
+    -change*(t*t*t*t - 1.0) + start
+}
+
+/// Quartic easing in-out
+#[inline]
+#[must_use]
+pub fn quart_in_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/(duration/2.0);
+    if t < 1.0 {
+        change/2.0*t*t*t*t + start
+    } else {
+        let t = t - 2.0;
+        -change/2.0*(t*t*t*t - 2.0) + start
+    }
+}
+
+/// Exponential easing in
+#[inline]
+#[must_use]
+#[allow(clippy::float_cmp)]
+pub fn expo_in(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    if t == 0.0 {
+        start
+    } else {
+        change*2.0_f32.powf(10.0*(t/duration - 1.0)) + start
+    }
+}
+
+/// Exponential easing out
+#[inline]
+#[must_use]
+#[allow(clippy::float_cmp)]
+pub fn expo_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    if t == duration {
+        start + change
+    } else {
+        change*(-(2.0_f32.powf(-10.0*t/duration)) + 1.0) + start
+    }
+}
+
+/// Exponential easing in-out
+#[inline]
+#[must_use]
+#[allow(clippy::float_cmp)]
+pub fn expo_in_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    if t == 0.0 {
+        return start;
+    }
+    if t == duration {
+        return start + change;
+    }
+
+    let t = t/(duration/2.0);
+    if t < 1.0 {
+        change/2.0*2.0_f32.powf(10.0*(t - 1.0)) + start
+    } else {
+        change/2.0*(-(2.0_f32.powf(-10.0*(t - 1.0))) + 2.0) + start
+    }
+}
+
+/// Circular easing in
+#[inline]
+#[must_use]
+pub fn circ_in(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/duration;
+    -change*((1.0 - t*t).sqrt() - 1.0) + start
+}
+
+/// Circular easing out
+#[inline]
+#[must_use]
+pub fn circ_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/duration - 1.0;
+    change*(1.0 - t*t).sqrt() + start
+}
+
+/// Circular easing in-out
+#[inline]
+#[must_use]
+pub fn circ_in_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/(duration/2.0);
+    if t < 1.0 {
+        -change/2.0*((1.0 - t*t).sqrt() - 1.0) + start
+    } else {
+        let t = t - 2.0;
+        change/2.0*((1.0 - t*t).sqrt() + 1.0) + start
+    }
+}
+
+/// Back easing overshoot amount (10%)
+const BACK_OVERSHOOT: f32 = 1.70158;
+
+/// Back easing in
+#[inline]
+#[must_use]
+pub fn back_in(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let overshoot = BACK_OVERSHOOT;
+    let t = t/duration;
+    change*t*t*((overshoot + 1.0)*t - overshoot) + start
+}
+
+/// Back easing out
+#[inline]
+#[must_use]
+pub fn back_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let overshoot = BACK_OVERSHOOT;
+    let t = t/duration - 1.0;
+    change*(t*t*((overshoot + 1.0)*t + overshoot) + 1.0) + start
+}
+
+/// Back easing in-out
+#[inline]
+#[must_use]
+pub fn back_in_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let overshoot = BACK_OVERSHOOT*1.525;
+    let t = t/(duration/2.0);
+    if t < 1.0 {
+        change/2.0*(t*t*((overshoot + 1.0)*t - overshoot)) + start
+    } else {
+        let t = t - 2.0;
+        change/2.0*(t*t*((overshoot + 1.0)*t + overshoot) + 2.0) + start
+    }
+}
+
+/// Bounce easing out
+#[inline]
+#[must_use]
+pub fn bounce_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    let t = t/duration;
+    if t < 1.0/2.75 {
+        change*(7.5625*t*t) + start
+    } else if t < 2.0/2.75 {
+        let t = t - 1.5/2.75;
+        change*(7.5625*t*t + 0.75) + start
+    } else if t < 2.5/2.75 {
+        let t = t - 2.25/2.75;
+        change*(7.5625*t*t + 0.9375) + start
+    } else {
+        let t = t - 2.625/2.75;
+        change*(7.5625*t*t + 0.984_375) + start
+    }
+}
+
+/// Bounce easing in
+#[inline]
+#[must_use]
+pub fn bounce_in(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    change - bounce_out(duration - t, 0.0, change, duration) + start
+}
+
+/// Bounce easing in-out
+#[inline]
+#[must_use]
+pub fn bounce_in_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    if t < duration/2.0 {
+        bounce_in(t*2.0, 0.0, change, duration)*0.5 + start
+    } else {
+        bounce_out(t*2.0 - duration, 0.0, change, duration)*0.5 + change*0.5 + start
+    }
+}
+
+/// Elastic easing in
+#[inline]
+#[must_use]
+#[allow(clippy::float_cmp)]
+pub fn elastic_in(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    if t == 0.0 {
+        return start;
+    }
+    let t = t/duration;
+    if t == 1.0 {
+        return start + change;
+    }
+
+    let period = duration*0.3;
+    let shift = period/4.0;
+    let t = t - 1.0;
+    let amplitude = change*2.0_f32.powf(10.0*t);
+    -(amplitude*((t*duration - shift)*(2.0*PI)/period).sin()) + start
+}
+
+/// Elastic easing out
+#[inline]
+#[must_use]
+#[allow(clippy::float_cmp)]
+pub fn elastic_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    if t == 0.0 {
+        return start;
+    }
+    let t = t/duration;
+    if t == 1.0 {
+        return start + change;
+    }
+
+    let period = duration*0.3;
+    let shift = period/4.0;
+    change*2.0_f32.powf(-10.0*t)*((t*duration - shift)*(2.0*PI)/period).sin() + change + start
+}
+
+/// Elastic easing in-out
+#[inline]
+#[must_use]
+#[allow(clippy::float_cmp)]
+pub fn elastic_in_out(t: Seconds, start: f32, change: f32, duration: Seconds) -> f32 {
+    if t == 0.0 {
+        return start;
+    }
+    let t = t/(duration/2.0);
+    if t == 2.0 {
+        return start + change;
+    }
+
+    let period = duration*(0.3*1.5);
+    let shift = period/4.0;
+    if t < 1.0 {
+        let t = t - 1.0;
+        let amplitude = change*2.0_f32.powf(10.0*t);
+        -0.5*(amplitude*((t*duration - shift)*(2.0*PI)/period).sin()) + start
+    } else {
+        let t = t - 1.0;
+        let amplitude = change*2.0_f32.powf(-10.0*t);
+        amplitude*((t*duration - shift)*(2.0*PI)/period).sin()*0.5 + change + start
+    }
+}
+
+/// Easing function kinds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Ease {
+    #[default]
+    Linear,
+    SineIn,
+    SineOut,
+    SineInOut,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+}
+
+impl Ease {
+    /// Get the easing function of this kind
+    #[must_use]
+    pub const fn function(self) -> fn(Seconds, f32, f32, Seconds) -> f32 {
+        match self {
+            Self::Linear       => linear,
+            Self::SineIn       => sine_in,
+            Self::SineOut      => sine_out,
+            Self::SineInOut    => sine_in_out,
+            Self::QuadIn       => quad_in,
+            Self::QuadOut      => quad_out,
+            Self::QuadInOut    => quad_in_out,
+            Self::CubicIn      => cubic_in,
+            Self::CubicOut     => cubic_out,
+            Self::CubicInOut   => cubic_in_out,
+            Self::QuartIn      => quart_in,
+            Self::QuartOut     => quart_out,
+            Self::QuartInOut   => quart_in_out,
+            Self::ExpoIn       => expo_in,
+            Self::ExpoOut      => expo_out,
+            Self::ExpoInOut    => expo_in_out,
+            Self::CircIn       => circ_in,
+            Self::CircOut      => circ_out,
+            Self::CircInOut    => circ_in_out,
+            Self::BackIn       => back_in,
+            Self::BackOut      => back_out,
+            Self::BackInOut    => back_in_out,
+            Self::BounceIn     => bounce_in,
+            Self::BounceOut    => bounce_out,
+            Self::BounceInOut  => bounce_in_out,
+            Self::ElasticIn    => elastic_in,
+            Self::ElasticOut   => elastic_out,
+            Self::ElasticInOut => elastic_in_out,
+        }
+    }
+}
+
+/// Ease normalized time `t` `[0..1]` into an interpolation amount
+/// NOTE: Use the result with [`LerpTo::lerp_to`], back and elastic easings leave the `[0..1]` range
+#[inline]
+#[must_use]
+pub fn ease(kind: Ease, t: Percent) -> Percent {
+    (kind.function())(t, 0.0, 1.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every easing kind with its values at a quarter, half and three quarters of the duration,
+    /// for start 10, change 20 and duration 2 (computed with the C reference)
+    const REFERENCE: [(Ease, [f32; 3]); 28] = [
+            (Ease::Linear, [15.0, 20.0, 25.0]),
+            (Ease::SineIn, [11.52241, 15.85786, 22.34633]),
+            (Ease::SineOut, [17.65367, 24.14214, 28.47759]),
+            (Ease::SineInOut, [12.92893, 20.0, 27.07107]),
+            (Ease::QuadIn, [11.25, 15.0, 21.25]),
+            (Ease::QuadOut, [18.75, 25.0, 28.75]),
+            (Ease::QuadInOut, [12.5, 20.0, 27.5]),
+            (Ease::CubicIn, [10.3125, 12.5, 18.4375]),
+            (Ease::CubicOut, [21.5625, 27.5, 29.6875]),
+            (Ease::CubicInOut, [11.25, 20.0, 28.75]),
+            (Ease::QuartIn, [10.07812, 11.25, 16.32812]),
+            (Ease::QuartOut, [23.67188, 28.75, 29.92188]),
+            (Ease::QuartInOut, [10.625, 20.0, 29.375]),
+            (Ease::ExpoIn, [10.11049, 10.625, 13.53553]),
+            (Ease::ExpoOut, [26.46447, 29.375, 29.88951]),
+            (Ease::ExpoInOut, [10.3125, 20.0, 29.6875]),
+            (Ease::CircIn, [10.63508, 12.67949, 16.77124]),
+            (Ease::CircOut, [23.22876, 27.32051, 29.36492]),
+            (Ease::CircInOut, [11.33975, 20.0, 28.66025]),
+            (Ease::BackIn, [8.71727, 8.24605, 13.65181]),
+            (Ease::BackOut, [26.34819, 31.75395, 31.28273]),
+            (Ease::BackInOut, [8.00636, 20.0, 31.99364]),
+            (Ease::BounceIn, [10.54688, 14.6875, 20.54688]),
+            (Ease::BounceOut, [19.45312, 25.3125, 29.45312]),
+            (Ease::BounceInOut, [12.34375, 20.0, 27.65625]),
+            (Ease::ElasticIn, [9.88951, 9.6875, 11.76777]),
+            (Ease::ElasticOut, [28.23223, 30.3125, 30.11049]),
+            (Ease::ElasticInOut, [10.23939, 20.0, 29.76061]),
+    ];
+
+    #[test]
+    fn test_easing_endpoints() {
+        for (kind, _) in REFERENCE {
+            let function = kind.function();
+            assert!((function(0.0, 10.0, 20.0, 2.0) - 10.0).abs() < 1.0e-4, "{kind:?}");
+            assert!((function(2.0, 10.0, 20.0, 2.0) - 30.0).abs() < 1.0e-4, "{kind:?}");
+            assert!(ease(kind, 0.0).abs() < 1.0e-5, "{kind:?}");
+            assert!((ease(kind, 1.0) - 1.0).abs() < 1.0e-5, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_easing_reference() {
+        for (kind, expected) in REFERENCE {
+            for (t, expected) in [0.5, 1.0, 1.5].into_iter().zip(expected) {
+                let value = (kind.function())(t, 10.0, 20.0, 2.0);
+                assert!((value - expected).abs() < 1.0e-4, "{kind:?} at {t}: {value} != {expected}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ease_lerp() {
+        assert!((ease(Ease::QuadIn, 0.5) - 0.25).abs() < 1.0e-6);
+        assert!((10.0.lerp_to(30.0, ease(Ease::BounceOut, 0.25)) - 19.453_125).abs() < 1.0e-4);
+        // Back easing overshoots the target range
+        assert!(ease(Ease::BackIn, 0.25) < 0.0 && ease(Ease::BackOut, 0.75) > 1.0);
+    }
+}
@@ -14,14 +14,269 @@ impl<T: std::ops::Div<U, Output = T>, U> Ratio<T, U> {
     }
 }
 
-/// Indicates the value is expected as an angle in radians `[0..2pi]`
-pub type Radians = f32;
+/// Implement arithmetic on an angle unit, a scalar multiple of the same angle keeps its unit
+macro_rules! impl_angle_ops {
+    ($Angle:ident) => {
+        impl $Angle {
+            /// Get the raw angle value
+            #[inline]
+            #[must_use]
+            pub const fn value(self) -> f32 {
+                self.0
+            }
+
+            /// Get the absolute angle
+            #[inline]
+            #[must_use]
+            pub fn abs(self) -> Self {
+                Self(self.0.abs())
+            }
+
+            /// Get the smaller angle
+            #[inline]
+            #[must_use]
+            pub fn min(self, other: Self) -> Self {
+                Self(self.0.min(other.0))
+            }
+
+            /// Get the larger angle
+            #[inline]
+            #[must_use]
+            pub fn max(self, other: Self) -> Self {
+                Self(self.0.max(other.0))
+            }
+
+            /// Clamp angle between min and max
+            #[inline]
+            #[must_use]
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                Self(self.0.max(min.0).min(max.0))
+            }
+        }
+
+        impl std::ops::Neg for $Angle {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                Self(-self.0)
+            }
+        }
+
+        impl std::ops::Add for $Angle {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self::Output {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl std::ops::AddAssign for $Angle {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl std::ops::Sub for $Angle {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl std::ops::SubAssign for $Angle {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl std::ops::Mul<f32> for $Angle {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: f32) -> Self::Output {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl std::ops::Mul<$Angle> for f32 {
+            type Output = $Angle;
+
+            #[inline]
+            fn mul(self, rhs: $Angle) -> Self::Output {
+                $Angle(self * rhs.0)
+            }
+        }
+
+        impl std::ops::Div<f32> for $Angle {
+            type Output = Self;
+
+            #[inline]
+            fn div(self, rhs: f32) -> Self::Output {
+                Self(self.0 / rhs)
+            }
+        }
+
+        /// Ratio between two angles
+        impl std::ops::Div for $Angle {
+            type Output = f32;
+
+            #[inline]
+            fn div(self, rhs: Self) -> Self::Output {
+                self.0 / rhs.0
+            }
+        }
+
+        impl std::ops::Rem for $Angle {
+            type Output = Self;
+
+            #[inline]
+            fn rem(self, rhs: Self) -> Self::Output {
+                Self(self.0 % rhs.0)
+            }
+        }
+
+        impl NearEq for $Angle {
+            #[inline]
+            fn near_eq(self, other: Self) -> bool {
+                self.0.near_eq(other.0)
+            }
+        }
+
+        impl Wrap for $Angle {
+            #[inline]
+            fn wrap(self, min: Self, max: Self) -> Self {
+                Self(self.0.wrap(min.0, max.0))
+            }
+        }
+    };
+}
+
+/// Angle units
+pub trait Angular: Copy + Wrap + std::ops::Neg<Output = Self> + std::ops::Div<f32, Output = Self> {
+    /// Angle of no rotation
+    const ZERO: Self;
+    /// Angle of a full turn
+    const FULL_TURN: Self;
+
+    /// Wrap angle into `[0..FULL_TURN)`
+    #[must_use]
+    fn normalized(self) -> Self {
+        self.wrap(Self::ZERO, Self::FULL_TURN)
+    }
+
+    /// Wrap angle into `[-FULL_TURN/2..FULL_TURN/2)`
+    #[must_use]
+    fn signed_normalized(self) -> Self {
+        let half_turn = Self::FULL_TURN/2.0;
+        self.wrap(-half_turn, half_turn)
+    }
+}
+
+/// Angle in radians
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Radians(pub f32);
+
+impl_angle_ops!(Radians);
+
+impl Angular for Radians {
+    const ZERO: Self = Self(0.0);
+    const FULL_TURN: Self = Self::TAU;
+}
+
+impl Radians {
+    pub const FRAC_PI_2: Self = Self(std::f32::consts::FRAC_PI_2);
+    pub const PI: Self = Self(std::f32::consts::PI);
+    pub const TAU: Self = Self(std::f32::consts::TAU);
+
+    /// Convert angle to degrees
+    #[inline]
+    #[must_use]
+    pub fn to_degrees(self) -> Degrees {
+        Degrees(self.0.to_degrees())
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn sin(self) -> f32 {
+        self.0.sin()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn cos(self) -> f32 {
+        self.0.cos()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn tan(self) -> f32 {
+        self.0.tan()
+    }
+
+    /// Get sine and cosine of the angle
+    #[inline]
+    #[must_use]
+    pub fn sin_cos(self) -> (f32, f32) {
+        self.0.sin_cos()
+    }
+
+    /// Get the angle of a cosine, in range `[0..pi]`
+    #[inline]
+    #[must_use]
+    pub fn acos(cos: f32) -> Self {
+        Self(cos.acos())
+    }
+
+    /// Get the angle of a point relative to the positive x axis, in range `[-pi..pi]`
+    #[inline]
+    #[must_use]
+    pub fn atan2(y: f32, x: f32) -> Self {
+        Self(y.atan2(x))
+    }
+}
+
+impl From<Degrees> for Radians {
+    #[inline]
+    fn from(value: Degrees) -> Self {
+        value.to_radians()
+    }
+}
 
 /// Indicates the ratio is x units per radian
 pub struct Radian;
 
-/// Indicates the value is expected as an angle in degrees `[0..360]`
-pub type Degrees = f32;
+/// Angle in degrees
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Degrees(pub f32);
+
+impl_angle_ops!(Degrees);
+
+impl Angular for Degrees {
+    const ZERO: Self = Self(0.0);
+    const FULL_TURN: Self = Self(360.0);
+}
+
+impl Degrees {
+    /// Convert angle to radians
+    #[inline]
+    #[must_use]
+    pub fn to_radians(self) -> Radians {
+        Radians(self.0.to_radians())
+    }
+}
+
+impl From<Radians> for Degrees {
+    #[inline]
+    fn from(value: Radians) -> Self {
+        value.to_degrees()
+    }
+}
 
 /// Indicates the ratio is x units per degree
 pub struct Degree;
@@ -70,3 +325,56 @@ pub type Position4 = Vector4;
 pub type Offset4 = Vector4;
 /// Indicates the vector is expected to be a normalized direction in 4D space
 pub type Direction4 = Normalized<Vector4>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degrees_normalized() {
+        for (angle, normalized, signed) in [
+            (0.0, 0.0, 0.0),
+            (90.0, 90.0, 90.0),
+            (180.0, 180.0, -180.0),
+            (-180.0, 180.0, -180.0),
+            (270.0, 270.0, -90.0),
+            (360.0, 0.0, 0.0),
+            (-360.0, 0.0, 0.0),
+            (-90.0, 270.0, -90.0),
+            (3690.0, 90.0, 90.0),
+            (-3690.0, 270.0, -90.0),
+            (3780.0, 180.0, -180.0),
+        ] {
+            assert_eq!(Degrees(angle).normalized(), Degrees(normalized), "{angle}");
+            assert_eq!(Degrees(angle).signed_normalized(), Degrees(signed), "{angle}");
+        }
+    }
+
+    #[test]
+    fn test_radians_normalized() {
+        assert_eq!(Radians::TAU.normalized(), Radians::ZERO);
+        assert_eq!((-Radians::TAU).normalized(), Radians::ZERO);
+        assert_eq!(Radians::PI.signed_normalized(), -Radians::PI);
+        assert_eq!((-Radians::PI).signed_normalized(), -Radians::PI);
+        assert!(((Radians::TAU*10.0 + Radians(1.0)).normalized() - Radians(1.0)).0.abs() < 1.0e-5);
+        assert!(((-Radians::TAU*10.0 - Radians(1.0)).signed_normalized() + Radians(1.0)).0.abs() < 1.0e-5);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_yaw_accumulation() {
+        // Ten full turns in 10 degree steps land exactly on zero
+        let mut yaw = Degrees::ZERO;
+        for _ in 0..360 {
+            yaw = (yaw + Degrees(10.0)).normalized();
+            assert!((0.0..360.0).contains(&yaw.0));
+        }
+        assert_eq!(yaw, Degrees::ZERO);
+
+        let mut yaw = Radians::ZERO;
+        for _ in 0..10_000 {
+            yaw = (yaw - Radians(0.1)).signed_normalized();
+            assert!(yaw >= -Radians::PI && yaw < Radians::PI);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
 use crate::prelude::*;
 
 /// Matrix, 4x4 components, column major, OpenGL style, right-handed
@@ -11,7 +11,7 @@ use crate::prelude::*;
 /// m2 == [2][0], m6 == [2][1], m10 == [2][2], m14 == [2][3],
 /// m3 == [3][0], m7 == [3][1], m11 == [3][2], m15 == [3][3],
 /// ```
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 #[must_use]
 pub struct Matrix(pub [[f32; 4]; 4]);
 
@@ -23,22 +23,73 @@ impl Matrix {
         [0.0, 0.0, 0.0, 1.0],
     ]);
 
+    /// Get row `i`
+    /// NOTE: Panics if `i >= 4`
+    #[inline]
+    pub const fn row(&self, i: usize) -> Vector4 {
+        let row = self.0[i];
+        Vector4::new(row[0], row[1], row[2], row[3])
+    }
+
+    /// Get column `i`
+    /// NOTE: Panics if `i >= 4`
+    #[inline]
+    pub const fn col(&self, i: usize) -> Vector4 {
+        Vector4::new(self.0[0][i], self.0[1][i], self.0[2][i], self.0[3][i])
+    }
+
+    /// Get array of columns; each column is an array of rows
+    /// NOTE: Same memory layout as OpenGL matrices (column major)
+    #[inline]
     #[must_use]
-    pub fn det(self) -> f32 {
+    pub const fn to_cols_array_2d(&self) -> [[f32; 4]; 4] {
+        let [c0, c1, c2, c3] = [self.col(0), self.col(1), self.col(2), self.col(3)];
+        [
+            [c0.x, c0.y, c0.z, c0.w],
+            [c1.x, c1.y, c1.z, c1.w],
+            [c2.x, c2.y, c2.z, c2.w],
+            [c3.x, c3.y, c3.z, c3.w],
+        ]
+    }
+
+    /// Matrix values and the 2x2 minors of its top and bottom row pairs,
+    /// shared by determinant and inverse computation
+    fn minors(self) -> ([f32; 16], [f32; 12]) {
         // Cache the matrix values (speed optimization)
+        let values = <[f32; 16]>::from(self);
         let [
             a00, a01, a02, a03,
             a10, a11, a12, a13,
             a20, a21, a22, a23,
             a30, a31, a32, a33,
-        ] = <[f32; 16]>::from(self);
+        ] = values;
+
+        let minors = [
+            a00 * a11 - a01 * a10,
+            a00 * a12 - a02 * a10,
+            a00 * a13 - a03 * a10,
+            a01 * a12 - a02 * a11,
+            a01 * a13 - a03 * a11,
+            a02 * a13 - a03 * a12,
+            a20 * a31 - a21 * a30,
+            a20 * a32 - a22 * a30,
+            a20 * a33 - a23 * a30,
+            a21 * a32 - a22 * a31,
+            a21 * a33 - a23 * a31,
+            a22 * a33 - a23 * a32,
+        ];
+
+        (values, minors)
+    }
 
-        (a30 * a21 * a12 * a03) - (a20 * a31 * a12 * a03) - (a30 * a11 * a22 * a03) + (a10 * a31 * a22 * a03) +
-        (a20 * a11 * a32 * a03) - (a10 * a21 * a32 * a03) - (a30 * a21 * a02 * a13) + (a20 * a31 * a02 * a13) +
-        (a30 * a01 * a22 * a13) - (a00 * a31 * a22 * a13) - (a20 * a01 * a32 * a13) + (a00 * a21 * a32 * a13) +
-        (a30 * a11 * a02 * a23) - (a10 * a31 * a02 * a23) - (a30 * a01 * a12 * a23) + (a00 * a31 * a12 * a23) +
-        (a10 * a01 * a32 * a23) - (a00 * a11 * a32 * a23) - (a20 * a11 * a02 * a33) + (a10 * a21 * a02 * a33) +
-        (a20 * a01 * a12 * a33) - (a00 * a21 * a12 * a33) - (a10 * a01 * a22 * a33) + (a00 * a11 * a22 * a33)
+    /// Determinant from the 2x2 minors (Laplace expansion)
+    const fn det_from_minors([b00, b01, b02, b03, b04, b05, b06, b07, b08, b09, b10, b11]: [f32; 12]) -> f32 {
+        b00 * b11 - b01 * b10 + b02 * b09 + b03 * b08 - b04 * b07 + b05 * b06
+    }
+
+    #[must_use]
+    pub fn det(self) -> f32 {
+        Self::det_from_minors(self.minors().1)
     }
 
     #[inline]
@@ -57,31 +108,26 @@ impl Matrix {
         ])
     }
 
-    pub fn invert(self) -> Self {
-        // Cache the matrix values (speed optimization)
+    /// Invert provided matrix
+    /// NOTE: Returns `None` if the matrix is singular (determinant near zero)
+    #[must_use]
+    pub fn invert(self) -> Option<Self> {
+        let (values, minors) = self.minors();
+        let det = Self::det_from_minors(minors);
+        if det.near_eq(0.0) {
+            return None;
+        }
+
         let [
             a00, a01, a02, a03,
             a10, a11, a12, a13,
             a20, a21, a22, a23,
             a30, a31, a32, a33,
-        ] = <[f32; 16]>::from(self);
-
-        let b00 = a00 * a11 - a01 * a10;
-        let b01 = a00 * a12 - a02 * a10;
-        let b02 = a00 * a13 - a03 * a10;
-        let b03 = a01 * a12 - a02 * a11;
-        let b04 = a01 * a13 - a03 * a11;
-        let b05 = a02 * a13 - a03 * a12;
-        let b06 = a20 * a31 - a21 * a30;
-        let b07 = a20 * a32 - a22 * a30;
-        let b08 = a20 * a33 - a23 * a30;
-        let b09 = a21 * a32 - a22 * a31;
-        let b10 = a21 * a33 - a23 * a31;
-        let b11 = a22 * a33 - a23 * a32;
-
-        let inv_det = 1.0 / (b00 * b11 - b01 * b10 + b02 * b09 + b03 * b08 - b04 * b07 + b05 * b06);
+        ] = values;
+        let [b00, b01, b02, b03, b04, b05, b06, b07, b08, b09, b10, b11] = minors;
+        let inv_det = 1.0 / det;
 
-        Self([
+        Some(Self([
             [
                 ( a11 * b11 - a12 * b10 + a13 * b09) * inv_det,
                 (-a10 * b11 + a12 * b08 - a13 * b07) * inv_det,
@@ -103,7 +149,7 @@ impl Matrix {
                 (-a20 * b04 + a21 * b02 - a23 * b00) * inv_det,
                 ( a20 * b03 - a21 * b01 + a22 * b00) * inv_det,
             ],
-        ])
+        ]))
     }
 
     /// Get translation matrix
@@ -249,8 +295,9 @@ impl Matrix {
         ])
     }
 
+    /// Get camera look-at matrix (view matrix)
     pub fn look_at(eye: Vector3, target: Vector3, up: Vector3) -> Self {
-        let vz = eye - target;
+        let vz = (eye - target).normalize();
         let vx = up.cross_product(vz).normalize();
         let vy = vz.cross_product(vx);
 
@@ -263,14 +310,15 @@ impl Matrix {
     }
 
     /// Returns: (translation, rotation, scale)
+    /// NOTE: Expects an affine transform (last row 0, 0, 0, 1)
     pub fn decompose(self) -> (Vector3, Quaternion, Vector3) {
         let translation = Vector3 {
-            x: self.0[3][0],
-            y: self.0[3][1],
-            z: self.0[3][2],
+            x: self.0[0][3],
+            y: self.0[1][3],
+            z: self.0[2][3],
         };
 
-        // Extract upper-left for determinant computation
+        // Extract upper-left for scale computation
         let a = self.0[0][0];
         let b = self.0[1][0];
         let c = self.0[2][0];
@@ -282,10 +330,8 @@ impl Matrix {
         let i = self.0[2][2];
 
         // Extract scale
-        let det =
-            a * (e * i - f * h) +
-            b * (f * g - d * i) +
-            c * (d * h - e * g);
+        // NOTE: For affine transforms the determinant equals the upper-left 3x3 one
+        let det = self.det();
 
         let scale = det.signum() * Vector3::new(
             Vector3::new(a, b, c).magnitude(),
@@ -295,17 +341,17 @@ impl Matrix {
 
         let rotation = if !det.near_eq(0.0) {
             // Remove scale from the matrix if it is not close to zero
-            let mut clone = self.clone();
-            clone.0[0][0] /= scale.x;
-            clone.0[1][0] /= scale.x;
-            clone.0[2][0] /= scale.x;
-            clone.0[0][1] /= scale.y;
-            clone.0[1][1] /= scale.y;
-            clone.0[2][1] /= scale.y;
-            clone.0[0][2] /= scale.z;
-            clone.0[1][2] /= scale.z;
-            clone.0[2][2] /= scale.z;
-            clone.into()
+            let mut unscaled = self;
+            unscaled.0[0][0] /= scale.x;
+            unscaled.0[1][0] /= scale.x;
+            unscaled.0[2][0] /= scale.x;
+            unscaled.0[0][1] /= scale.y;
+            unscaled.0[1][1] /= scale.y;
+            unscaled.0[2][1] /= scale.y;
+            unscaled.0[0][2] /= scale.z;
+            unscaled.0[1][2] /= scale.z;
+            unscaled.0[2][2] /= scale.z;
+            unscaled.into()
         } else {
             // Set to identity if close to zero
             Quaternion::IDENTITY
@@ -387,33 +433,86 @@ impl Mul for Matrix {
     type Output = Self;
 
     /// NOTE: When multiplying matrices... the order matters!
+    /// `left * right` applies `left` first, same as raymath `MatrixMultiply(left, right)`
     fn mul(self, rhs: Self) -> Self::Output {
         Self([
             [
-                self.0[0][0] * rhs.0[0][0] + self.0[1][0] * rhs.0[0][1] + self.0[2][0] * rhs.0[0][2] + self.0[3][0] * rhs.0[0][3],
-                self.0[0][0] * rhs.0[1][0] + self.0[1][0] * rhs.0[1][1] + self.0[2][0] * rhs.0[1][2] + self.0[3][0] * rhs.0[1][3],
-                self.0[0][0] * rhs.0[2][0] + self.0[1][0] * rhs.0[2][1] + self.0[2][0] * rhs.0[2][2] + self.0[3][0] * rhs.0[2][3],
-                self.0[0][0] * rhs.0[3][0] + self.0[1][0] * rhs.0[3][1] + self.0[2][0] * rhs.0[3][2] + self.0[3][0] * rhs.0[3][3],
+                rhs.0[0][0] * self.0[0][0] + rhs.0[0][1] * self.0[1][0] + rhs.0[0][2] * self.0[2][0] + rhs.0[0][3] * self.0[3][0],
+                rhs.0[0][0] * self.0[0][1] + rhs.0[0][1] * self.0[1][1] + rhs.0[0][2] * self.0[2][1] + rhs.0[0][3] * self.0[3][1],
+                rhs.0[0][0] * self.0[0][2] + rhs.0[0][1] * self.0[1][2] + rhs.0[0][2] * self.0[2][2] + rhs.0[0][3] * self.0[3][2],
+                rhs.0[0][0] * self.0[0][3] + rhs.0[0][1] * self.0[1][3] + rhs.0[0][2] * self.0[2][3] + rhs.0[0][3] * self.0[3][3],
             ], [
-                self.0[0][1] * rhs.0[0][0] + self.0[1][1] * rhs.0[0][1] + self.0[2][1] * rhs.0[0][2] + self.0[3][1] * rhs.0[0][3],
-                self.0[0][1] * rhs.0[1][0] + self.0[1][1] * rhs.0[1][1] + self.0[2][1] * rhs.0[1][2] + self.0[3][1] * rhs.0[1][3],
-                self.0[0][1] * rhs.0[2][0] + self.0[1][1] * rhs.0[2][1] + self.0[2][1] * rhs.0[2][2] + self.0[3][1] * rhs.0[2][3],
-                self.0[0][1] * rhs.0[3][0] + self.0[1][1] * rhs.0[3][1] + self.0[2][1] * rhs.0[3][2] + self.0[3][1] * rhs.0[3][3],
+                rhs.0[1][0] * self.0[0][0] + rhs.0[1][1] * self.0[1][0] + rhs.0[1][2] * self.0[2][0] + rhs.0[1][3] * self.0[3][0],
+                rhs.0[1][0] * self.0[0][1] + rhs.0[1][1] * self.0[1][1] + rhs.0[1][2] * self.0[2][1] + rhs.0[1][3] * self.0[3][1],
+                rhs.0[1][0] * self.0[0][2] + rhs.0[1][1] * self.0[1][2] + rhs.0[1][2] * self.0[2][2] + rhs.0[1][3] * self.0[3][2],
+                rhs.0[1][0] * self.0[0][3] + rhs.0[1][1] * self.0[1][3] + rhs.0[1][2] * self.0[2][3] + rhs.0[1][3] * self.0[3][3],
             ], [
-                self.0[0][2] * rhs.0[0][0] + self.0[1][2] * rhs.0[0][1] + self.0[2][2] * rhs.0[0][2] + self.0[3][2] * rhs.0[0][3],
-                self.0[0][2] * rhs.0[1][0] + self.0[1][2] * rhs.0[1][1] + self.0[2][2] * rhs.0[1][2] + self.0[3][2] * rhs.0[1][3],
-                self.0[0][2] * rhs.0[2][0] + self.0[1][2] * rhs.0[2][1] + self.0[2][2] * rhs.0[2][2] + self.0[3][2] * rhs.0[2][3],
-                self.0[0][2] * rhs.0[3][0] + self.0[1][2] * rhs.0[3][1] + self.0[2][2] * rhs.0[3][2] + self.0[3][2] * rhs.0[3][3],
+                rhs.0[2][0] * self.0[0][0] + rhs.0[2][1] * self.0[1][0] + rhs.0[2][2] * self.0[2][0] + rhs.0[2][3] * self.0[3][0],
+                rhs.0[2][0] * self.0[0][1] + rhs.0[2][1] * self.0[1][1] + rhs.0[2][2] * self.0[2][1] + rhs.0[2][3] * self.0[3][1],
+                rhs.0[2][0] * self.0[0][2] + rhs.0[2][1] * self.0[1][2] + rhs.0[2][2] * self.0[2][2] + rhs.0[2][3] * self.0[3][2],
+                rhs.0[2][0] * self.0[0][3] + rhs.0[2][1] * self.0[1][3] + rhs.0[2][2] * self.0[2][3] + rhs.0[2][3] * self.0[3][3],
             ], [
-                self.0[0][3] * rhs.0[0][0] + self.0[1][3] * rhs.0[0][1] + self.0[2][3] * rhs.0[0][2] + self.0[3][3] * rhs.0[0][3],
-                self.0[0][3] * rhs.0[1][0] + self.0[1][3] * rhs.0[1][1] + self.0[2][3] * rhs.0[1][2] + self.0[3][3] * rhs.0[1][3],
-                self.0[0][3] * rhs.0[2][0] + self.0[1][3] * rhs.0[2][1] + self.0[2][3] * rhs.0[2][2] + self.0[3][3] * rhs.0[2][3],
-                self.0[0][3] * rhs.0[3][0] + self.0[1][3] * rhs.0[3][1] + self.0[2][3] * rhs.0[3][2] + self.0[3][3] * rhs.0[3][3],
+                rhs.0[3][0] * self.0[0][0] + rhs.0[3][1] * self.0[1][0] + rhs.0[3][2] * self.0[2][0] + rhs.0[3][3] * self.0[3][0],
+                rhs.0[3][0] * self.0[0][1] + rhs.0[3][1] * self.0[1][1] + rhs.0[3][2] * self.0[2][1] + rhs.0[3][3] * self.0[3][1],
+                rhs.0[3][0] * self.0[0][2] + rhs.0[3][1] * self.0[1][2] + rhs.0[3][2] * self.0[2][2] + rhs.0[3][3] * self.0[3][2],
+                rhs.0[3][0] * self.0[0][3] + rhs.0[3][1] * self.0[1][3] + rhs.0[3][2] * self.0[2][3] + rhs.0[3][3] * self.0[3][3],
             ],
         ])
     }
 }
 
+/// Forward borrowed operands and compound assignment to the owned operator impl
+macro_rules! impl_matrix_ref_ops {
+    ($($Op:ident::$op:ident, $OpAssign:ident::$op_assign:ident);+ $(;)?) => {$(
+        impl $Op<&Matrix> for Matrix {
+            type Output = Matrix;
+
+            #[inline]
+            fn $op(self, rhs: &Matrix) -> Self::Output {
+                self.$op(*rhs)
+            }
+        }
+
+        impl $Op<Matrix> for &Matrix {
+            type Output = Matrix;
+
+            #[inline]
+            fn $op(self, rhs: Matrix) -> Self::Output {
+                (*self).$op(rhs)
+            }
+        }
+
+        impl $Op<&Matrix> for &Matrix {
+            type Output = Matrix;
+
+            #[inline]
+            fn $op(self, rhs: &Matrix) -> Self::Output {
+                (*self).$op(*rhs)
+            }
+        }
+
+        impl $OpAssign for Matrix {
+            #[inline]
+            fn $op_assign(&mut self, rhs: Self) {
+                *self = (*self).$op(rhs);
+            }
+        }
+
+        impl $OpAssign<&Matrix> for Matrix {
+            #[inline]
+            fn $op_assign(&mut self, rhs: &Self) {
+                *self = (*self).$op(*rhs);
+            }
+        }
+    )+};
+}
+
+impl_matrix_ref_ops! {
+    Add::add, AddAssign::add_assign;
+    Sub::sub, SubAssign::sub_assign;
+    Mul::mul, MulAssign::mul_assign;
+}
+
 impl From<Matrix> for [f32; 16] {
     #[inline]
     fn from(Matrix(rows): Matrix) -> Self {
@@ -440,3 +539,183 @@ impl From<Matrix> for [f32; 16] {
         ]
     }
 }
+
+impl From<[f32; 16]> for Matrix {
+    /// NOTE: Array is read column major, the inverse of `From<Matrix> for [f32; 16]`
+    #[inline]
+    fn from(m: [f32; 16]) -> Self {
+        Self([
+            [m[0], m[4], m[ 8], m[12]],
+            [m[1], m[5], m[ 9], m[13]],
+            [m[2], m[6], m[10], m[14]],
+            [m[3], m[7], m[11], m[15]],
+        ])
+    }
+}
+
+impl Index<usize> for Matrix {
+    type Output = [f32; 4];
+
+    /// Get row `index`
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl IndexMut<usize> for Matrix {
+    /// Get row `index`
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn counting() -> [f32; 16] {
+        std::array::from_fn(|i| i as f32 + 0.5)
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_matrix_array_round_trip() {
+        let values = counting();
+        let mat = Matrix::from(values);
+        assert_eq!(<[f32; 16]>::from(mat), values);
+        assert_eq!(Matrix::from(<[f32; 16]>::from(mat)), mat);
+
+        // Column major, OpenGL style: m12, m13, m14 are the translation
+        let translate = <[f32; 16]>::from(Matrix::translate(1.0, 2.0, 3.0));
+        assert_eq!(translate[12..15], [1.0, 2.0, 3.0]);
+        assert_eq!(mat.to_cols_array_2d().concat(), values);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_matrix_accessors() {
+        let mut mat = Matrix::from(counting());
+        assert_eq!(mat[1], [1.5, 5.5, 9.5, 13.5]);
+        assert_eq!(mat.row(1), Vector4::new(1.5, 5.5, 9.5, 13.5));
+        assert_eq!(mat.col(3), Vector4::new(12.5, 13.5, 14.5, 15.5));
+        assert_eq!(mat[2][3], mat.0[2][3]);
+
+        mat[0][3] = -1.0;
+        assert_eq!(<[f32; 16]>::from(mat)[12], -1.0);
+        assert_eq!(mat.transpose().row(3), mat.col(3));
+    }
+
+    #[track_caller]
+    fn assert_near(a: Matrix, b: Matrix) {
+        let close = <[f32; 16]>::from(a).iter().zip(<[f32; 16]>::from(b)).all(|(x, y)| (x - y).abs() < 1.0e-5);
+        assert!(close, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_matrix_invert() {
+        assert_eq!(Matrix::IDENTITY.invert(), Some(Matrix::IDENTITY));
+        assert_near(Matrix::translate(1.0, -2.0, 3.0).invert().unwrap(), Matrix::translate(-1.0, 2.0, -3.0));
+        assert_near(Matrix::scale(2.0, 4.0, -0.5).invert().unwrap(), Matrix::scale(0.5, 0.25, -2.0));
+        assert_near(Matrix::rotate_y(Radians(0.75)).invert().unwrap(), Matrix::rotate_y(Radians(-0.75)));
+        assert_eq!(Matrix::scale(2.0, 3.0, 4.0).det(), 24.0);
+
+        let transforms = [
+            Matrix::translate(1.0, 2.0, 3.0) * Matrix::rotate_xyz(Radians(0.3), Radians(-1.2), Radians(2.0)) * Matrix::scale(2.0, 0.5, 3.0),
+            Matrix::rotate(Vector3::new(1.0, 1.0, 0.0), Radians(1.0)) * Matrix::translate(-5.0, 0.0, 7.0),
+            Matrix::perspective(1.0, 16.0/9.0, 0.1, 100.0),
+            Matrix::look_at(Vector3::new(3.0, 4.0, 5.0), Vector3::ZERO, Vector3::new(0.0, 1.0, 0.0)),
+        ];
+        for mat in transforms {
+            let inverse = mat.invert().unwrap();
+            assert_near(mat * inverse, Matrix::IDENTITY);
+            assert_near(inverse * mat, Matrix::IDENTITY);
+            assert_near(inverse.invert().unwrap(), mat);
+        }
+    }
+
+    #[test]
+    fn test_matrix_mul_order() {
+        let point = Vector3::new(1.0, 0.0, 0.0);
+        // Translate first, then scale
+        assert_eq!(point.transform(&(Matrix::translate(1.0, 0.0, 0.0) * Matrix::scale(2.0, 3.0, 4.0))), Vector3::new(4.0, 0.0, 0.0));
+        assert_eq!(point.transform(&(Matrix::scale(2.0, 3.0, 4.0) * Matrix::translate(1.0, 0.0, 0.0))), Vector3::new(3.0, 0.0, 0.0));
+        let product = Matrix::translate(1.0, 2.0, 3.0) * Matrix::scale(2.0, 3.0, 4.0);
+        assert_eq!(product, Matrix([
+            [2.0, 0.0, 0.0,  2.0],
+            [0.0, 3.0, 0.0,  6.0],
+            [0.0, 0.0, 4.0, 12.0],
+            [0.0, 0.0, 0.0,  1.0],
+        ]));
+    }
+
+    #[test]
+    fn test_matrix_invert_singular() {
+        assert_eq!(Matrix::default().invert(), None);
+        assert_eq!(Matrix::scale(1.0, 0.0, 1.0).invert(), None);
+        assert_eq!((Matrix::translate(1.0, 2.0, 3.0) * Matrix::scale(2.0, 2.0, 0.0)).invert(), None);
+        // Linearly dependent rows
+        let mut mat = Matrix::from(counting());
+        mat[3] = mat[0].map(|x| x*2.0);
+        assert_eq!(mat.invert(), None);
+    }
+
+    #[test]
+    fn test_matrix_decompose_round_trip() {
+        let translation = Vector3::new(1.0, -2.0, 3.0);
+        let rotation = Quaternion::from_euler(Radians(0.3), Radians(-1.2), Radians(2.0));
+        let scale = Vector3::new(2.0, 0.5, 3.0);
+        // Scale first, then rotate, then translate
+        let mat = Matrix::scale(scale.x, scale.y, scale.z) * Matrix::from(rotation) * Matrix::translate(translation.x, translation.y, translation.z);
+
+        let (t, r, s) = mat.decompose();
+        assert!(t.distance(translation) < 1.0e-5, "{t:?} != {translation:?}");
+        assert!(s.distance(scale) < 1.0e-5, "{s:?} != {scale:?}");
+        // Both quaternion signs are the same rotation
+        let same = |a: Quaternion, b: Quaternion| [a.x - b.x, a.y - b.y, a.z - b.z, a.w - b.w].iter().all(|d| d.abs() < 1.0e-5);
+        assert!(same(r, rotation) || same(r, -rotation), "{r:?} != {rotation:?}");
+        assert_near(Matrix::scale(s.x, s.y, s.z) * Matrix::from(r) * Matrix::translate(t.x, t.y, t.z), mat);
+
+        let (t, r, s) = Matrix::translate(4.0, 5.0, 6.0).decompose();
+        assert_eq!(t, Vector3::new(4.0, 5.0, 6.0));
+        assert_eq!(r, Quaternion::IDENTITY);
+        assert_eq!(s, Vector3::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_matrix_operator_chaining() {
+        let translate = Matrix::translate(1.0, 2.0, 3.0);
+        let scale = Matrix::scale(2.0, 3.0, 4.0);
+        let rotate = Matrix::rotate_z(Radians(0.5));
+        let expected = translate * scale * rotate;
+
+        // Owned and borrowed operands mix freely, operands stay usable (Copy)
+        assert_eq!(&translate * &scale * &rotate, expected);
+        assert_eq!(translate * &scale * rotate, expected);
+        assert_eq!(&translate * scale * &rotate, expected);
+        assert_eq!(&(translate * scale) * rotate, expected);
+        assert_eq!(translate * (&scale * &rotate), expected);
+        assert_eq!(&translate + &scale - &rotate, translate + scale - rotate);
+        assert_eq!(translate - &translate, Matrix::default());
+
+        // Compound assignment matches the binary operators, `*=` multiplies on the right
+        let mut mat = translate;
+        mat *= scale;
+        mat *= &rotate;
+        assert_eq!(mat, expected);
+        mat += translate;
+        mat -= &translate;
+        assert_eq!(mat, expected + translate - translate);
+
+        // Transforms borrow the matrix, one matrix transforms many vectors
+        let points = [Vector3::ZERO, Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0)];
+        let transformed = points.map(|point| point.transform(&mat));
+        assert_eq!(transformed[0], Vector3::new(1.0, 2.0, 3.0).transform(&(scale * rotate)));
+        assert_eq!(Vector2::ZERO.transform(&translate), Vector2::new(1.0, 2.0));
+        assert_eq!(Vector4::new(0.0, 0.0, 0.0, 1.0).transform(&translate), Vector4::new(1.0, 2.0, 3.0, 1.0));
+    }
+}
@@ -1,13 +1,58 @@
 use crate::prelude::*;
 
+/// Implement `as_array()` and conversions from/to arrays and tuples for a vector-like struct of `f32` components
+macro_rules! impl_component_conversions {
+    ($Vector:ident { $($field:ident),+ }, $N:literal, $Tuple:ty) => {
+        impl $Vector {
+            /// Get components as an array
+            #[inline]
+            #[must_use]
+            pub const fn as_array(&self) -> [f32; $N] {
+                [$(self.$field),+]
+            }
+        }
+
+        impl From<[f32; $N]> for $Vector {
+            #[inline]
+            fn from([$($field),+]: [f32; $N]) -> Self {
+                Self { $($field),+ }
+            }
+        }
+
+        impl From<$Vector> for [f32; $N] {
+            #[inline]
+            fn from(value: $Vector) -> Self {
+                value.as_array()
+            }
+        }
+
+        impl From<$Tuple> for $Vector {
+            #[inline]
+            fn from(($($field),+): $Tuple) -> Self {
+                Self { $($field),+ }
+            }
+        }
+
+        impl From<$Vector> for $Tuple {
+            #[inline]
+            fn from($Vector { $($field),+ }: $Vector) -> Self {
+                ($($field),+)
+            }
+        }
+    };
+}
+
 pub mod vector;
 pub mod quaternion;
 pub mod matrix;
 pub mod transform;
 pub mod ray;
 pub mod indicators;
+pub mod smoothing;
+pub mod easing;
 
 pub trait Wrap {
+    /// Wrap input value from min to max, in range `[min..max)`
     #[must_use]
     fn wrap(self, min: Self, max: Self) -> Self;
 }
@@ -15,10 +60,19 @@ pub trait Wrap {
 impl Wrap for f32 {
     #[inline]
     fn wrap(self, min: Self, max: Self) -> Self {
-        self - (max - min) * self.normalize_between(min, max).floor()
+        let range = max - min;
+        let offset = (self - min).rem_euclid(range);
+        // NOTE: Tiny negative offsets round up to the whole range
+        if offset >= range { min } else { min + offset }
     }
 }
 
+pub trait Angle {
+    /// Calculate angle between two vectors
+    #[must_use]
+    fn angle(self, other: Self) -> Radians;
+}
+
 pub trait Magnitude {
     #[must_use]
     fn magnitude(self) -> f32;
@@ -134,3 +188,195 @@ impl NearEq for f64 {
         (self - other).abs() <= Self::EPSILON * self.abs().max(other.abs()).max(1.0)
     }
 }
+
+/// Scalar helpers commonly used by gameplay code
+pub trait FloatExt {
+    /// Clamp value into `[0..1]`
+    #[must_use]
+    fn clamp01(self) -> Self;
+
+    /// Normalize input value within input range, clamped into `[0..1]`
+    /// NOTE: An empty range (`start == end`) steps from 0 to 1 at `start`
+    #[must_use]
+    fn inverse_lerp(self, start: Self, end: Self) -> Self;
+
+    /// Smooth Hermite interpolation from 0 at `edge0` to 1 at `edge1`
+    /// NOTE: An empty range (`edge0 == edge1`) steps from 0 to 1 at `edge0`
+    #[must_use]
+    fn smoothstep(self, edge0: Self, edge1: Self) -> Self;
+
+    /// Move towards target by at most `max_delta`, never overshooting
+    /// NOTE: A negative `max_delta` moves away from the target
+    #[must_use]
+    fn move_towards(self, target: Self, max_delta: Self) -> Self;
+
+    /// Loop value in range `[0..length)`
+    /// NOTE: Returns 0 if `length` is not positive
+    #[must_use]
+    fn repeat(self, length: Self) -> Self;
+
+    /// Bounce value back and forth in range `[0..length]`
+    /// NOTE: Returns 0 if `length` is not positive
+    #[must_use]
+    fn ping_pong(self, length: Self) -> Self;
+
+    /// Move towards target by a fraction of the remaining distance, independent of frame rate
+    /// NOTE: Same as [`smoothing::exp_decay`], `decay` is the decay per second (higher is faster)
+    #[must_use]
+    fn approach_exponential(self, target: Self, decay: Self, dt: Seconds) -> Self;
+}
+
+impl FloatExt for f32 {
+    #[inline]
+    fn clamp01(self) -> Self {
+        self.clamp(0.0, 1.0)
+    }
+
+    #[inline]
+    #[allow(clippy::float_cmp)]
+    fn inverse_lerp(self, start: Self, end: Self) -> Self {
+        if start == end {
+            if self < start { 0.0 } else { 1.0 }
+        } else {
+            self.normalize_between(start, end).clamp01()
+        }
+    }
+
+    #[inline]
+    fn smoothstep(self, edge0: Self, edge1: Self) -> Self {
+        let t = self.inverse_lerp(edge0, edge1);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    #[inline]
+    fn move_towards(self, target: Self, max_delta: Self) -> Self {
+        let delta = target - self;
+        if max_delta >= 0.0 && delta.abs() <= max_delta {
+            target
+        } else {
+            self + delta.signum() * max_delta
+        }
+    }
+
+    #[inline]
+    fn repeat(self, length: Self) -> Self {
+        if length > 0.0 { self.wrap(0.0, length) } else { 0.0 }
+    }
+
+    #[inline]
+    fn ping_pong(self, length: Self) -> Self {
+        if length > 0.0 {
+            length - (self.repeat(length * 2.0) - length).abs()
+        } else {
+            0.0
+        }
+    }
+
+    #[inline]
+    fn approach_exponential(self, target: Self, decay: Self, dt: Seconds) -> Self {
+        smoothing::exp_decay(self, target, decay, dt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_wrap_f32() {
+        // Max is excluded, min included
+        assert_eq!(0.0.wrap(0.0, 360.0), 0.0);
+        assert_eq!(360.0.wrap(0.0, 360.0), 0.0);
+        assert_eq!(359.5.wrap(0.0, 360.0), 359.5);
+        assert_eq!(1.0.wrap(-1.0, 1.0), -1.0);
+        assert_eq!((-1.0).wrap(-1.0, 1.0), -1.0);
+
+        // Negative inputs
+        assert_eq!((-0.5).wrap(0.0, 360.0), 359.5);
+        assert_eq!((-360.0).wrap(0.0, 360.0), 0.0);
+        assert_eq!((-1.5).wrap(-1.0, 1.0), 0.5);
+
+        // Large magnitudes: 10 full turns
+        assert_eq!(3600.0.wrap(0.0, 360.0), 0.0);
+        assert_eq!(3610.0.wrap(0.0, 360.0), 10.0);
+        assert_eq!((-3610.0).wrap(0.0, 360.0), 350.0);
+
+        // Tiny negative values round to max, which wraps to min
+        assert_eq!((-1.0e-8).wrap(0.0, 360.0), 0.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_lerp_remap_normalize() {
+        // Endpoints are exact, amounts outside [0..1] extrapolate
+        assert_eq!(0.0.lerp(2.0, 6.0), 2.0);
+        assert_eq!(1.0.lerp(2.0, 6.0), 6.0);
+        assert_eq!(1.5.lerp(2.0, 6.0), 8.0);
+        assert_eq!((-0.5).lerp(2.0, 6.0), 0.0);
+        assert_eq!(0.5.lerp(-1.0, -1.0), -1.0);
+
+        assert_eq!(4.0.normalize_between(2.0, 6.0), 0.5);
+        assert_eq!(4.0.normalize_between(6.0, 2.0), 0.5);
+        assert_eq!(8.0.normalize_between(2.0, 6.0), 1.5);
+        assert!(4.0.normalize_between(2.0, 2.0).is_infinite(), "empty range is not handled, see inverse_lerp");
+
+        // Reversed ranges flip, values outside the input range are not clamped
+        assert_eq!(5.0.remap(0.0, 10.0, 100.0, 200.0), 150.0);
+        assert_eq!(5.0.remap(0.0, 10.0, 200.0, 100.0), 150.0);
+        assert_eq!(2.0.remap(0.0, 10.0, 200.0, 100.0), 180.0);
+        assert_eq!(20.0.remap(0.0, 10.0, 0.0, 1.0), 2.0);
+        assert_eq!((-10.0).remap(10.0, 0.0, 0.0, 1.0), 2.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_float_ext() {
+        assert_eq!((-0.5).clamp01(), 0.0);
+        assert_eq!(0.25.clamp01(), 0.25);
+        assert_eq!(7.0.clamp01(), 1.0);
+
+        // Clamped, reversed and empty ranges
+        assert_eq!(4.0.inverse_lerp(2.0, 6.0), 0.5);
+        assert_eq!(9.0.inverse_lerp(2.0, 6.0), 1.0);
+        assert_eq!(5.0.inverse_lerp(6.0, 2.0), 0.25);
+        assert_eq!(1.0.inverse_lerp(2.0, 2.0), 0.0);
+        assert_eq!(2.0.inverse_lerp(2.0, 2.0), 1.0);
+
+        assert_eq!(0.0.smoothstep(1.0, 3.0), 0.0);
+        assert_eq!(2.0.smoothstep(1.0, 3.0), 0.5);
+        assert_eq!(1.5.smoothstep(1.0, 3.0), 0.156_25);
+        assert_eq!(9.0.smoothstep(1.0, 3.0), 1.0);
+        assert_eq!(1.0.smoothstep(1.0, 1.0), 1.0);
+
+        // Never overshoots, negative delta moves away
+        assert_eq!(0.0.move_towards(10.0, 3.0), 3.0);
+        assert_eq!(9.0.move_towards(10.0, 3.0), 10.0);
+        assert_eq!(0.0.move_towards(-10.0, 3.0), -3.0);
+        assert_eq!(0.0.move_towards(10.0, -3.0), -3.0);
+        assert_eq!(10.0.move_towards(10.0, 0.0), 10.0);
+
+        assert_eq!(7.5.repeat(2.5), 0.0);
+        assert_eq!(8.0.repeat(2.5), 0.5);
+        assert_eq!((-0.5).repeat(2.5), 2.0);
+        assert_eq!(3.0.repeat(0.0), 0.0);
+        assert_eq!(3.0.repeat(-1.0), 0.0);
+
+        // Up from 0 to length, back down to 0
+        assert_eq!(0.0.ping_pong(2.0), 0.0);
+        assert_eq!(1.5.ping_pong(2.0), 1.5);
+        assert_eq!(2.0.ping_pong(2.0), 2.0);
+        assert_eq!(3.0.ping_pong(2.0), 1.0);
+        assert_eq!(4.0.ping_pong(2.0), 0.0);
+        assert_eq!((-1.0).ping_pong(2.0), 1.0);
+        assert_eq!(3.0.ping_pong(0.0), 0.0);
+
+        // Frame rate independent: two half steps reach the same value as one full step
+        let full = 0.0.approach_exponential(10.0, 4.0, 0.5);
+        let half = 0.0.approach_exponential(10.0, 4.0, 0.25).approach_exponential(10.0, 4.0, 0.25);
+        assert!((full - half).abs() < 1e-5, "{full} != {half}");
+        assert!((full - 10.0 * (1.0 - (-2.0f32).exp())).abs() < 1e-5);
+        assert_eq!(3.0.approach_exponential(10.0, 4.0, 0.0), 3.0);
+        assert_eq!(10.0.approach_exponential(10.0, 4.0, 0.5), 10.0);
+    }
+}
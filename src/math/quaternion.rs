@@ -24,6 +24,8 @@ impl From<Quaternion> for Vector4 {
     }
 }
 
+impl_component_conversions!(Quaternion { x, y, z, w }, 4, (f32, f32, f32, f32));
+
 impl Vector for Quaternion {}
 
 impl Quaternion {
@@ -46,6 +48,18 @@ impl Quaternion {
         Vector3::new(self.x, self.y, self.z)
     }
 
+    /// Get the conjugate, negating the vector part
+    /// NOTE: Equals the inverse for unit quaternions, without normalizing
+    #[inline]
+    pub const fn conjugate(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w:  self.w,
+        }
+    }
+
     #[inline]
     pub fn invert(self) -> Self {
         let inv_magnitude = 1.0 / self.magnitude();
@@ -59,8 +73,15 @@ impl Quaternion {
 }
 
 impl Angle for Quaternion {
+    /// Calculate angle of the rotation between two orientations, in range [0..PI]
+    /// NOTE: `q` and `-q` represent the same orientation, zero quaternions have an angle of 0
     fn angle(self, other: Self) -> Radians {
-        todo!()
+        let magnitudes = self.magnitude() * other.magnitude();
+        if magnitudes == 0.0 {
+            Radians::ZERO
+        } else {
+            2.0 * Radians::acos((self.dot(other).abs() / magnitudes).min(1.0))
+        }
     }
 }
 
@@ -130,7 +151,7 @@ impl Mul for Quaternion {
             x: self.x * rhs.w + self.w * rhs.x + self.y * rhs.z - self.z * rhs.y,
             y: self.y * rhs.w + self.w * rhs.y + self.z * rhs.x - self.x * rhs.z,
             z: self.z * rhs.w + self.w * rhs.z + self.x * rhs.y - self.y * rhs.x,
-            w: self.w * rhs.w - self.w * rhs.x - self.w * rhs.y - self.z * rhs.z,
+            w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
         }
     }
 }
@@ -398,7 +419,7 @@ impl Quaternion {
             self = self.normalize();
         }
 
-        let res_angle = self.w.acos() * 2.0;
+        let res_angle = Radians::acos(self.w) * 2.0;
         let den = (1.0 - self.w * self.w).sqrt();
 
         let res_axis = if den > f32::EPSILON {
@@ -433,23 +454,74 @@ impl Quaternion {
         // Roll (x-axis rotation)
         let x0 =       2.0 * (self.w * self.x + self.y * self.z);
         let x1 = 1.0 - 2.0 * (self.x * self.x + self.y * self.y);
-        let roll = x0.atan2(x1);
+        let roll = Radians::atan2(x0, x1);
 
         // Pitch (y-axis rotation)
         let y0 = 2.0 * (self.w * self.y - self.z * self.x).clamp(-1.0, 1.0);
-        let pitch = y0.asin();
+        let pitch = Radians(y0.asin());
 
         // Yaw (z-axis rotation)
         let z0 =       2.0 * (self.w * self.z + self.x * self.y);
         let z1 = 1.0 - 2.0 * (self.y * self.y + self.z * self.z);
-        let yaw = z0.atan2(z1);
+        let yaw = Radians::atan2(z0, z1);
 
         (roll, pitch, yaw)
     }
+
+    /// Convert matrix rotation part into quaternion, removing scale and renormalizing the result
+    pub fn from_rotation_matrix_normalized(mut mat: Matrix) -> Normalized<Self> {
+        // Normalize basis vectors (columns) to remove scale
+        for col in 0..3 {
+            let length = Vector3::new(mat.0[0][col], mat.0[1][col], mat.0[2][col]).magnitude();
+            if length > f32::EPSILON {
+                for row in 0..3 {
+                    mat.0[row][col] /= length;
+                }
+            }
+        }
+
+        Self::from(mat).normalize()
+    }
+
+    /// Calculate orientation looking along `forward` with the given `up`
+    /// NOTE: Camera convention, the local -Z axis is rotated onto `forward` and +Y towards `up`.
+    /// If `forward` is parallel to `up`, an alternate up is used
+    pub fn look_rotation(forward: Direction3, up: Direction3) -> Normalized<Self> {
+        let vz = -forward.normalize();
+        let mut vx = up.cross_product(vz);
+        if vx.magnitude_sqr() < f32::EPSILON {
+            let alternate_up = if vz.x.abs() < 0.9 { Vector3::UNIT_X } else { Vector3::UNIT_Z };
+            vx = alternate_up.cross_product(vz);
+        }
+        let vx = vx.normalize();
+        let vy = vz.cross_product(vx);
+
+        Self::from(Matrix([
+            [vx.x, vy.x, vz.x, 0.0],
+            [vx.y, vy.y, vz.y, 0.0],
+            [vx.z, vy.z, vz.z, 0.0],
+            [ 0.0,  0.0,  0.0, 1.0],
+        ])).normalize()
+    }
+
+    /// Rotate towards target by at most `max_angle`, never overshooting
+    /// NOTE: Both quaternions must be unit length
+    pub fn rotate_towards(self, target: Self, max_angle: Radians) -> Self {
+        let angle = 2.0 * Radians::acos(self.dot(target).abs().min(1.0));
+        if angle <= max_angle || angle.value() <= f32::EPSILON {
+            target
+        } else if max_angle <= Radians::ZERO {
+            self
+        } else {
+            self.slerp_to(target, max_angle / angle)
+        }
+    }
 }
 
 impl From<Matrix> for Quaternion {
     /// Convert matrix into quaternion
+    /// NOTE: Matrix rotation part must be orthonormal (no scale), the result is not unit length otherwise.
+    /// Use [`Quaternion::from_rotation_matrix_normalized`] for matrices with scale or numeric drift
     fn from(mat: Matrix) -> Self {
         let (biggest_index, four_biggest_squared_minus_1) = [
             mat.0[0][0] + mat.0[1][1] + mat.0[2][2],
@@ -461,7 +533,7 @@ impl From<Matrix> for Quaternion {
             .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
             .unwrap();
 
-        let biggest_val = (four_biggest_squared_minus_1 + 1.0) * 0.5;
+        let biggest_val = (four_biggest_squared_minus_1 + 1.0).sqrt() * 0.5;
         let mult = 0.25 / biggest_val;
 
         match biggest_index {
@@ -543,7 +615,7 @@ impl DotProduct for Quaternion {
 
 impl MatrixTransform for Quaternion {
     #[inline]
-    fn transform(self, mat: Matrix) -> Self {
+    fn transform(self, mat: &Matrix) -> Self {
         Self {
             x: mat.0[0][0] * self.x + mat.0[0][1] * self.y + mat.0[0][2] * self.z + mat.0[0][3] * self.w,
             y: mat.0[1][0] * self.x + mat.0[1][1] * self.y + mat.0[1][2] * self.z + mat.0[1][3] * self.w,
@@ -552,3 +624,161 @@ impl MatrixTransform for Quaternion {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn assert_near(a: Quaternion, b: Quaternion) {
+        assert!(a.near_eq(b), "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_quaternion_mul() {
+        // Hamilton product: i*j = k, j*i = -k, i*i = -1
+        let [i, j, k] = [Quaternion::new(1.0, 0.0, 0.0, 0.0), Quaternion::new(0.0, 1.0, 0.0, 0.0), Quaternion::new(0.0, 0.0, 1.0, 0.0)];
+        assert_eq!(i*j, k);
+        assert_eq!(j*i, -k);
+        assert_eq!(i*i, Quaternion::new(0.0, 0.0, 0.0, -1.0));
+        assert_eq!(Quaternion::new(1.0, 2.0, 3.0, 4.0)*Quaternion::new(5.0, 6.0, 7.0, 8.0), Quaternion::new(24.0, 48.0, 48.0, -6.0));
+
+        let q = Quaternion::new(0.5, -1.5, 2.0, 3.0);
+        assert_eq!(q*Quaternion::IDENTITY, q);
+        assert_eq!(Quaternion::IDENTITY*q, q);
+    }
+
+    #[test]
+    fn test_quaternion_conjugate() {
+        let q = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 3.0), Radians(1.25));
+        assert_near(q*q.conjugate(), Quaternion::IDENTITY);
+        assert_near(q.conjugate()*q, Quaternion::IDENTITY);
+        assert_near(q.conjugate(), q.invert());
+        // Conjugate doesn't normalize, unlike invert
+        let q = Quaternion::new(0.0, 0.0, 0.0, 2.0);
+        assert_eq!((q.conjugate(), q.invert()), (q, Quaternion::IDENTITY));
+    }
+
+    #[test]
+    fn test_quaternion_compose() {
+        let axis = Vector3::new(0.0, 1.0, 1.0);
+        let q1 = Quaternion::from_axis_angle(axis, Radians(0.5));
+        let q2 = Quaternion::from_axis_angle(axis, Radians(0.75));
+        assert_near(q1*q2, Quaternion::from_axis_angle(axis, Radians(1.25)));
+
+        // Composition rotates by the right hand side first
+        let x_then_z = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), Radians::FRAC_PI_2)*Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), Radians::FRAC_PI_2);
+        let v = Vector3::new(0.0, 0.0, 1.0).rotate_by_quaternion(x_then_z);
+        assert!((v - Vector3::new(1.0, 0.0, 0.0)).magnitude() < 1.0e-6, "{v:?}");
+    }
+
+    #[test]
+    fn test_quaternion_matrix_round_trip() {
+        let rotations = [
+            Quaternion::IDENTITY,
+            Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), Radians(0.5)),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), Radians(3.0)),
+            Quaternion::from_axis_angle(Vector3::new(1.0, -2.0, 0.5), Radians(2.0)),
+            Quaternion::from_euler(Radians(0.1), Radians(-2.5), Radians(1.0)),
+        ];
+        for q in rotations {
+            assert_near(Quaternion::from(Matrix::from(q)), q);
+        }
+    }
+
+    /// Looser than [`assert_near`], for results of chained trigonometry
+    #[track_caller]
+    fn assert_close(a: Quaternion, b: Quaternion) {
+        assert!((a - b).magnitude() < 1.0e-5 || (a + b).magnitude() < 1.0e-5, "{a:?} != {b:?}");
+    }
+
+    #[track_caller]
+    fn assert_vector_near(a: Vector3, b: Vector3) {
+        assert!((a - b).magnitude() < 1.0e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_from_rotation_matrix_normalized() {
+        let q = Quaternion::from_axis_angle(Vector3::new(1.0, -2.0, 0.5), Radians(2.0));
+
+        // Non-uniform scale is removed, plain conversion is not unit length then
+        let scaled = Matrix::scale(2.0, 0.5, 3.0) * Matrix::from(q);
+        assert!((Quaternion::from(scaled).magnitude() - 1.0).abs() > 0.1);
+        let normalized = Quaternion::from_rotation_matrix_normalized(scaled);
+        assert!((normalized.magnitude() - 1.0).abs() < 1.0e-6);
+        assert_near(normalized, q);
+
+        // Numeric drift is renormalized
+        let mut drifted = Matrix::from(q);
+        drifted[0][1] += 1.0e-3;
+        drifted[2][0] -= 1.0e-3;
+        let normalized = Quaternion::from_rotation_matrix_normalized(drifted);
+        assert!((normalized.magnitude() - 1.0).abs() < 1.0e-6);
+        assert!(normalized.angle(q).value() < 1.0e-2);
+
+        // Translation is ignored, degenerate columns are kept as is
+        assert_near(Quaternion::from_rotation_matrix_normalized(Matrix::from(q) * Matrix::translate(5.0, 6.0, 7.0)), q);
+        assert!(Quaternion::from_rotation_matrix_normalized(Matrix::scale(0.0, 1.0, 1.0)).magnitude().is_finite());
+    }
+
+    #[test]
+    fn test_look_rotation() {
+        let up = Vector3::new(0.0, 1.0, 0.0);
+        assert_near(Quaternion::look_rotation(Vector3::new(0.0, 0.0, -1.0), up), Quaternion::IDENTITY);
+
+        // Local -Z is rotated onto forward, local +Y stays in the plane of up
+        for forward in [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 1.0), Vector3::new(1.0, 2.0, -3.0), Vector3::new(-4.0, -1.0, 0.5)] {
+            let q = Quaternion::look_rotation(forward, up);
+            assert!((q.magnitude() - 1.0).abs() < 1.0e-6);
+            assert_vector_near(Vector3::new(0.0, 0.0, -1.0).rotate_by_quaternion(q), forward.normalize());
+            let local_up = Vector3::new(0.0, 1.0, 0.0).rotate_by_quaternion(q);
+            assert!(local_up.y > 0.0 && local_up.dot(forward).abs() < 1.0e-5, "{forward:?}: {local_up:?}");
+            assert!(Vector3::new(1.0, 0.0, 0.0).rotate_by_quaternion(q).y.abs() < 1.0e-5, "no roll");
+        }
+
+        // Forward parallel to up falls back to an alternate up
+        for forward in [up, -up] {
+            let q = Quaternion::look_rotation(forward, up);
+            assert!((q.magnitude() - 1.0).abs() < 1.0e-6);
+            assert_vector_near(Vector3::new(0.0, 0.0, -1.0).rotate_by_quaternion(q), forward);
+        }
+    }
+
+    #[test]
+    fn test_rotate_towards() {
+        let axis = Vector3::new(0.0, 1.0, 0.0);
+        let from = Quaternion::IDENTITY;
+        let target = Quaternion::from_axis_angle(axis, Radians(1.0));
+
+        // Steps by max_angle, then snaps to target without overshooting
+        let step = from.rotate_towards(target, Radians(0.25));
+        assert_close(step, Quaternion::from_axis_angle(axis, Radians(0.25)));
+        assert_close(step.rotate_towards(target, Radians(0.5)), Quaternion::from_axis_angle(axis, Radians(0.75)));
+        assert_eq!(from.rotate_towards(target, Radians(1.5)), target);
+        assert_eq!(from.rotate_towards(target, Radians(1.0)), target);
+
+        // No step, or already there
+        assert_eq!(from.rotate_towards(target, Radians(0.0)), from);
+        assert_eq!(from.rotate_towards(target, Radians(-1.0)), from);
+        assert_eq!(target.rotate_towards(target, Radians(0.0)), target);
+
+        // Takes the short path when the target has the opposite sign
+        assert_close(from.rotate_towards(-target, Radians(0.25)), Quaternion::from_axis_angle(axis, Radians(0.25)));
+    }
+
+    #[test]
+    fn test_quaternion_angle() {
+        let axis = Vector3::new(1.0, -2.0, 0.5);
+        let [a, b] = [0.3, 1.7].map(|angle| Quaternion::from_axis_angle(axis, Radians(angle)));
+        assert!((a.angle(b).0 - 1.4).abs() < 1.0e-4, "{:?}", a.angle(b));
+        assert!((b.angle(a).0 - 1.4).abs() < 1.0e-4);
+        assert!((Quaternion::IDENTITY.angle(Quaternion::from_axis_angle(Vector3::UNIT_Y, Radians::PI)).0 - std::f32::consts::PI).abs() < 1.0e-4);
+
+        // Same orientation for both signs and any magnitude, never past a half turn
+        assert!(a.angle(-a).0.abs() < 1.0e-3);
+        assert!((a.angle(b*3.0).0 - 1.4).abs() < 1.0e-4);
+        let long_way = Quaternion::from_axis_angle(axis, Radians(0.3 + 5.0));
+        assert!((a.angle(long_way).0 - (std::f32::consts::TAU - 5.0)).abs() < 1.0e-3, "{:?}", a.angle(long_way));
+        assert_eq!(a.angle(Quaternion::new(0.0, 0.0, 0.0, 0.0)), Radians::ZERO);
+    }
+}
@@ -1,13 +1,295 @@
 use crate::prelude::*;
 
+/// Ray for raycasting
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Ray {
     pub position: Vector3,
     pub direction: Normalized<Vector3>,
 }
 
+/// Ray hit information
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct RayCollision {
     pub is_hit: bool,
     pub distance: Units,
     pub point: Vector3,
     pub normal: Normalized<Vector3>,
 }
+
+impl RayCollision {
+    /// No collision
+    pub const MISS: Self = Self {
+        is_hit: false,
+        distance: 0.0,
+        point: Vector3::ZERO,
+        normal: Vector3::ZERO,
+    };
+}
+
+impl Ray {
+    /// Get the point at `distance` along the ray
+    #[inline]
+    pub fn point_at(&self, distance: Units) -> Position3 {
+        self.position + self.direction*distance
+    }
+}
+
+/// Get collision info between ray and sphere
+/// NOTE: Rays starting inside the sphere hit its far side, spheres behind the ray are not hit
+#[must_use]
+pub fn get_ray_collision_sphere(ray: &Ray, center: Position3, radius: Units) -> RayCollision {
+    let ray_sphere_pos = center - ray.position;
+    let vector = ray_sphere_pos.dot(ray.direction);
+    let distance = ray_sphere_pos.magnitude();
+    let d = radius*radius - (distance*distance - vector*vector);
+    if d < 0.0 {
+        return RayCollision::MISS;
+    }
+
+    // Check if ray origin is inside the sphere to calculate the correct collision point
+    let (distance, outwards) = if distance < radius {
+        (vector + d.sqrt(), false)
+    } else {
+        (vector - d.sqrt(), true)
+    };
+    if distance < 0.0 {
+        return RayCollision::MISS;
+    }
+
+    let point = ray.point_at(distance);
+    let normal = (point - center).normalize();
+    RayCollision {
+        is_hit: true,
+        distance,
+        point,
+        normal: if outwards { normal } else { -normal },
+    }
+}
+
+/// Get collision info between ray and box
+/// NOTE: Rays starting inside the box hit its far side, the normal pointing inwards
+#[must_use]
+pub fn get_ray_collision_box(ray: &Ray, bbox: &BoundingBox) -> RayCollision {
+    let Ray { position, mut direction } = *ray;
+
+    // Check if ray origin is inside the box
+    let inside_box =
+        position.x > bbox.min.x && position.x < bbox.max.x &&
+        position.y > bbox.min.y && position.y < bbox.max.y &&
+        position.z > bbox.min.z && position.z < bbox.max.z;
+
+    if inside_box {
+        direction = -direction;
+    }
+
+    // Slab distances, NaN (ray on a slab plane) is ignored by min/max
+    let inv_dir = Vector3::new(1.0/direction.x, 1.0/direction.y, 1.0/direction.z);
+    let near = (bbox.min - position)*inv_dir;
+    let far = (bbox.max - position)*inv_dir;
+    let t_min = near.x.min(far.x).max(near.y.min(far.y)).max(near.z.min(far.z));
+    let t_max = near.x.max(far.x).min(near.y.max(far.y)).min(near.z.max(far.z));
+
+    if t_max < 0.0 || t_min > t_max {
+        return RayCollision::MISS;
+    }
+
+    let point = position + direction*t_min;
+
+    // Get vector center point->hit point, scaled to unit cube
+    // NOTE: We use an additional .01 to fix numerical errors
+    let center = bbox.min.lerp_to(bbox.max, 0.5);
+    let normal = (point - center)*2.01/(bbox.max - bbox.min);
+
+    // The relevant elements of the vector are now slightly larger than 1.0 (or smaller than -1.0)
+    // and the others are somewhere between -1.0 and 1.0, truncating is exactly our wanted normal
+    let normal = Vector3::new(normal.x.trunc(), normal.y.trunc(), normal.z.trunc()).normalize();
+
+    if inside_box {
+        RayCollision { is_hit: true, distance: -t_min, point, normal: -normal }
+    } else {
+        RayCollision { is_hit: true, distance: t_min, point, normal }
+    }
+}
+
+/// Get collision info between ray and triangle
+/// NOTE: Based on Möller-Trumbore intersection algorithm, both sides of the triangle are hit,
+/// the normal always faces the ray
+#[must_use]
+pub fn get_ray_collision_triangle(ray: &Ray, triangle: &Triangle3D) -> RayCollision {
+    const EPSILON: f32 = 0.000_001;
+
+    let [p1, p2, p3] = triangle.points;
+
+    // Find vectors for two edges sharing V1
+    let edge1 = p2 - p1;
+    let edge2 = p3 - p1;
+
+    // Begin calculating determinant - also used to calculate u parameter
+    let p = ray.direction.cross_product(edge2);
+
+    // If determinant is near zero, ray lies in plane of triangle or ray is parallel to plane of triangle
+    let det = edge1.dot(p);
+    if det.abs() < EPSILON {
+        return RayCollision::MISS;
+    }
+    let inv_det = 1.0/det;
+
+    // Calculate distance from V1 to ray origin
+    let tv = ray.position - p1;
+
+    // Calculate u parameter and test bound
+    let u = tv.dot(p)*inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return RayCollision::MISS;
+    }
+
+    // Prepare to test v parameter
+    let q = tv.cross_product(edge1);
+
+    // Calculate v parameter and test bound
+    let v = ray.direction.dot(q)*inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return RayCollision::MISS;
+    }
+
+    let distance = edge2.dot(q)*inv_det;
+    if distance <= EPSILON {
+        return RayCollision::MISS;
+    }
+
+    let normal = edge1.cross_product(edge2).normalize();
+    RayCollision {
+        is_hit: true,
+        distance,
+        point: ray.point_at(distance),
+        normal: if normal.dot(ray.direction) > 0.0 { -normal } else { normal },
+    }
+}
+
+/// Get collision info between ray and quad
+/// NOTE: The quad is split in triangles (p1, p2, p4) and (p2, p3, p4)
+#[must_use]
+pub fn get_ray_collision_quad(ray: &Ray, p1: Position3, p2: Position3, p3: Position3, p4: Position3) -> RayCollision {
+    let collision = get_ray_collision_triangle(ray, &Triangle3D { points: [p1, p2, p4] });
+    if collision.is_hit {
+        collision
+    } else {
+        get_ray_collision_triangle(ray, &Triangle3D { points: [p2, p3, p4] })
+    }
+}
+
+/// Get collision info between ray and the horizontal ground plane at `ground_height`
+/// NOTE: Rays parallel to the ground never hit, the normal always faces the ray
+#[must_use]
+pub fn get_ray_collision_ground(ray: &Ray, ground_height: Units) -> RayCollision {
+    const EPSILON: f32 = 0.000_001;
+
+    if ray.direction.y.abs() < EPSILON {
+        return RayCollision::MISS;
+    }
+
+    let distance = (ground_height - ray.position.y)/ray.direction.y;
+    if distance < 0.0 {
+        return RayCollision::MISS;
+    }
+
+    RayCollision {
+        is_hit: true,
+        distance,
+        point: ray.point_at(distance),
+        normal: Vector3::new(0.0, -ray.direction.y.signum(), 0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vec3([x, y, z]: [f32; 3]) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    fn ray(position: [f32; 3], direction: [f32; 3]) -> Ray {
+        Ray { position: vec3(position), direction: vec3(direction).normalize() }
+    }
+
+    #[track_caller]
+    fn assert_hit(collision: RayCollision, distance: Units, point: [f32; 3], normal: [f32; 3]) {
+        assert!(collision.is_hit, "{collision:?}");
+        assert!((collision.distance - distance).abs() < 1.0e-5, "{collision:?}");
+        assert!((collision.point - vec3(point)).magnitude() < 1.0e-5, "{collision:?}");
+        assert!((collision.normal - vec3(normal)).magnitude() < 1.0e-5, "{collision:?}");
+    }
+
+    #[test]
+    fn test_ray_sphere() {
+        let center = Vector3::new(0.0, 0.0, 5.0);
+        assert_hit(get_ray_collision_sphere(&ray([0.0, 0.0, 0.0], [0.0, 0.0, 1.0]), center, 1.0), 4.0, [0.0, 0.0, 4.0], [0.0, 0.0, -1.0]);
+        assert_hit(get_ray_collision_sphere(&ray([0.0, 3.0, 5.0], [0.0, -1.0, 0.0]), center, 1.0), 2.0, [0.0, 1.0, 5.0], [0.0, 1.0, 0.0]);
+        // Inside: far side is hit, normal facing the ray
+        assert_hit(get_ray_collision_sphere(&ray([0.0, 0.0, 5.0], [0.0, 0.0, 1.0]), center, 1.0), 1.0, [0.0, 0.0, 6.0], [0.0, 0.0, -1.0]);
+        assert_hit(get_ray_collision_sphere(&ray([0.0, 0.0, 5.5], [0.0, 0.0, 1.0]), center, 1.0), 0.5, [0.0, 0.0, 6.0], [0.0, 0.0, -1.0]);
+        // Behind the origin and passing beside
+        assert_eq!(get_ray_collision_sphere(&ray([0.0, 0.0, 0.0], [0.0, 0.0, -1.0]), center, 1.0), RayCollision::MISS);
+        assert_eq!(get_ray_collision_sphere(&ray([2.0, 0.0, 0.0], [0.0, 0.0, 1.0]), center, 1.0), RayCollision::MISS);
+    }
+
+    #[test]
+    fn test_ray_box() {
+        let bbox = BoundingBox { min: Vector3::new(1.0, -1.0, -1.0), max: Vector3::new(3.0, 1.0, 1.0) };
+        assert_hit(get_ray_collision_box(&ray([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]), &bbox), 1.0, [1.0, 0.0, 0.0], [-1.0, 0.0, 0.0]);
+        assert_hit(get_ray_collision_box(&ray([2.0, 0.5, 4.0], [0.0, 0.0, -1.0]), &bbox), 3.0, [2.0, 0.5, 1.0], [0.0, 0.0, 1.0]);
+        assert_hit(get_ray_collision_box(&ray([0.0, 0.0, 0.0], [4.0, 0.0, 3.0]), &bbox), 1.25, [1.0, 0.0, 0.75], [-1.0, 0.0, 0.0]);
+        assert_eq!(get_ray_collision_box(&ray([0.0, 0.0, 0.0], [3.0, 0.0, 4.0]), &bbox), RayCollision::MISS);
+        // Inside: far side is hit, normal flipped to face the ray
+        assert_hit(get_ray_collision_box(&ray([2.0, 0.0, 0.0], [1.0, 0.0, 0.0]), &bbox), 1.0, [3.0, 0.0, 0.0], [-1.0, 0.0, 0.0]);
+        assert_hit(get_ray_collision_box(&ray([2.0, 0.0, 0.0], [0.0, -1.0, 0.0]), &bbox), 1.0, [2.0, -1.0, 0.0], [0.0, 1.0, 0.0]);
+        // Behind the origin and passing beside, parallel to a slab
+        assert_eq!(get_ray_collision_box(&ray([0.0, 0.0, 0.0], [-1.0, 0.0, 0.0]), &bbox), RayCollision::MISS);
+        assert_eq!(get_ray_collision_box(&ray([0.0, 2.0, 0.0], [1.0, 0.0, 0.0]), &bbox), RayCollision::MISS);
+    }
+
+    #[test]
+    fn test_ray_triangle() {
+        let triangle = Triangle3D { points: [vec3([0.0, 0.0, 5.0]), vec3([2.0, 0.0, 5.0]), vec3([0.0, 2.0, 5.0])] };
+        assert_hit(get_ray_collision_triangle(&ray([0.5, 0.5, 0.0], [0.0, 0.0, 1.0]), &triangle), 5.0, [0.5, 0.5, 5.0], [0.0, 0.0, -1.0]);
+        // Back side is hit too, normal facing the ray
+        assert_hit(get_ray_collision_triangle(&ray([0.5, 0.5, 10.0], [0.0, 0.0, -1.0]), &triangle), 5.0, [0.5, 0.5, 5.0], [0.0, 0.0, 1.0]);
+        // Edges and vertices are inclusive
+        assert_hit(get_ray_collision_triangle(&ray([1.0, 1.0, 0.0], [0.0, 0.0, 1.0]), &triangle), 5.0, [1.0, 1.0, 5.0], [0.0, 0.0, -1.0]);
+        assert_hit(get_ray_collision_triangle(&ray([0.0, 0.0, 0.0], [0.0, 0.0, 1.0]), &triangle), 5.0, [0.0, 0.0, 5.0], [0.0, 0.0, -1.0]);
+        assert_hit(get_ray_collision_triangle(&ray([1.0, 0.0, 0.0], [0.0, 0.0, 1.0]), &triangle), 5.0, [1.0, 0.0, 5.0], [0.0, 0.0, -1.0]);
+        // Just outside the edges
+        for position in [[1.01, 1.0, 0.0], [-0.01, 1.0, 0.0], [1.0, -0.01, 0.0]] {
+            assert_eq!(get_ray_collision_triangle(&ray(position, [0.0, 0.0, 1.0]), &triangle), RayCollision::MISS, "{position:?}");
+        }
+        // Parallel to the plane, in the plane, and behind the origin
+        assert_eq!(get_ray_collision_triangle(&ray([0.5, 0.5, 4.0], [1.0, 0.0, 0.0]), &triangle), RayCollision::MISS);
+        assert_eq!(get_ray_collision_triangle(&ray([-1.0, 0.5, 5.0], [1.0, 0.0, 0.0]), &triangle), RayCollision::MISS);
+        assert_eq!(get_ray_collision_triangle(&ray([0.5, 0.5, 10.0], [0.0, 0.0, 1.0]), &triangle), RayCollision::MISS);
+    }
+
+    #[test]
+    fn test_ray_quad() {
+        let [p1, p2, p3, p4] = [[0.0, 0.0, 5.0], [2.0, 0.0, 5.0], [2.0, 2.0, 5.0], [0.0, 2.0, 5.0]].map(vec3);
+        // Both triangles, the diagonal seam and the far corner
+        for [x, y] in [[0.5, 0.5], [1.5, 1.5], [1.0, 1.0], [2.0, 2.0]] {
+            assert_hit(get_ray_collision_quad(&ray([x, y, 0.0], [0.0, 0.0, 1.0]), p1, p2, p3, p4), 5.0, [x, y, 5.0], [0.0, 0.0, -1.0]);
+        }
+        assert_hit(get_ray_collision_quad(&ray([1.5, 1.5, 6.0], [0.0, 0.0, -1.0]), p1, p2, p3, p4), 1.0, [1.5, 1.5, 5.0], [0.0, 0.0, 1.0]);
+        assert_eq!(get_ray_collision_quad(&ray([2.1, 1.0, 0.0], [0.0, 0.0, 1.0]), p1, p2, p3, p4), RayCollision::MISS);
+        assert_eq!(get_ray_collision_quad(&ray([1.0, 1.0, 0.0], [0.0, 1.0, 0.0]), p1, p2, p3, p4), RayCollision::MISS);
+        assert_eq!(get_ray_collision_quad(&ray([1.0, 1.0, 0.0], [0.0, 0.0, -1.0]), p1, p2, p3, p4), RayCollision::MISS);
+    }
+
+    #[test]
+    fn test_ray_ground() {
+        assert_hit(get_ray_collision_ground(&ray([0.0, 10.0, 0.0], [0.0, -1.0, 0.0]), 0.0), 10.0, [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        assert_hit(get_ray_collision_ground(&ray([0.0, 10.0, 0.0], [0.6, -0.8, 0.0]), 2.0), 10.0, [6.0, 2.0, 0.0], [0.0, 1.0, 0.0]);
+        // From below, normal facing the ray
+        assert_hit(get_ray_collision_ground(&ray([0.0, -1.0, 0.0], [0.0, 1.0, 0.0]), 0.0), 1.0, [0.0, 0.0, 0.0], [0.0, -1.0, 0.0]);
+        // Parallel and pointing away
+        assert_eq!(get_ray_collision_ground(&ray([0.0, 10.0, 0.0], [1.0, 0.0, 0.0]), 0.0), RayCollision::MISS);
+        assert_eq!(get_ray_collision_ground(&ray([0.0, 10.0, 0.0], [0.0, 1.0, 0.0]), 0.0), RayCollision::MISS);
+    }
+}
@@ -0,0 +1,162 @@
+//! Frame-rate independent smoothing
+
+use std::ops::{Add, Mul, Sub};
+use crate::prelude::*;
+
+/// Values that can be smoothed: scalars and vectors
+pub trait Smooth:
+    Copy +
+    Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self> +
+    DotProduct + Magnitude
+{}
+
+impl Smooth for f32 {}
+impl Smooth for Vector2 {}
+impl Smooth for Vector3 {}
+
+/// Move towards target by a fraction of the remaining distance, independent of frame rate
+/// NOTE: Replaces `value += (target - value) * factor`, `rate` is the decay per second (higher is faster)
+#[inline]
+#[must_use]
+pub fn exp_decay<T: Smooth>(current: T, target: T, rate: f32, dt: Seconds) -> T {
+    current + (target - current) * (1.0 - (-rate * dt).exp())
+}
+
+/// Move towards target with a critically damped spring, never overshooting
+/// NOTE: `velocity` must be kept between calls, `smooth_time` is approximately the time to
+/// reach the target, speed is limited to `max_speed` (use `f32::INFINITY` for no limit)
+#[must_use]
+pub fn smooth_damp<T: Smooth>(current: T, target: T, velocity: &mut T, smooth_time: Seconds, max_speed: f32, dt: Seconds) -> T {
+    let smooth_time = smooth_time.max(0.0001);
+    let omega = 2.0 / smooth_time;
+
+    // Approximation of exp(-omega*dt)
+    let x = omega * dt;
+    let exp = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+
+    // Clamp maximum speed
+    let mut change = current - target;
+    let max_change = max_speed * smooth_time;
+    let change_sqr = change.magnitude_sqr();
+    if change_sqr > max_change * max_change {
+        change = change * (max_change / change_sqr.sqrt());
+    }
+    let clamped_target = current - change;
+
+    let temp = (*velocity + change * omega) * dt;
+    *velocity = (*velocity - temp * omega) * exp;
+    let mut output = clamped_target + (change + temp) * exp;
+
+    // Prevent overshooting
+    if (target - current).dot(output - target) > 0.0 {
+        output = target;
+        *velocity = *velocity * 0.0;
+    }
+
+    output
+}
+
+/// Get target angle equivalent closest to current, so interpolation takes the short way around
+#[inline]
+#[must_use]
+fn nearest_angle(current: Radians, target: Radians) -> Radians {
+    current + (target - current).signed_normalized()
+}
+
+/// [`exp_decay`] for angles, taking the shortest path
+#[inline]
+#[must_use]
+pub fn exp_decay_angle(current: Radians, target: Radians, rate: f32, dt: Seconds) -> Radians {
+    Radians(exp_decay(current.value(), nearest_angle(current, target).value(), rate, dt))
+}
+
+/// [`smooth_damp`] for angles, taking the shortest path
+/// NOTE: Result is not wrapped, it may leave the `[-PI..PI]` range, `velocity` is in radians per second
+#[inline]
+#[must_use]
+pub fn smooth_damp_angle(current: Radians, target: Radians, velocity: &mut f32, smooth_time: Seconds, max_speed: f32, dt: Seconds) -> Radians {
+    Radians(smooth_damp(current.value(), nearest_angle(current, target).value(), velocity, smooth_time, max_speed, dt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `frames` steps of `dt`
+    fn run(frames: usize, dt: Seconds, mut value: f32, mut step: impl FnMut(f32, Seconds) -> f32) -> f32 {
+        for _ in 0..frames {
+            value = step(value, dt);
+        }
+        value
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_exp_decay_frame_rate_independent() {
+        // One second at 30, 60 and 144 fps ends at the same value
+        let step = |value, dt| exp_decay(value, 10.0, 3.0, dt);
+        let expected = 10.0 * (1.0 - (-3.0f32).exp());
+        for fps in [30, 60, 144] {
+            #[allow(clippy::cast_precision_loss)]
+            let value = run(fps, 1.0 / fps as f32, 0.0, step);
+            assert!((value - expected).abs() < 1.0e-3, "{fps} fps: {value} != {expected}");
+        }
+
+        assert_eq!(exp_decay(4.0, 10.0, 3.0, 0.0), 4.0);
+        assert_eq!(exp_decay(10.0, 10.0, 3.0, 0.5), 10.0);
+        assert!((exp_decay(0.0, 10.0, 1000.0, 1.0) - 10.0).abs() < 1.0e-6, "fast decay reaches target");
+        let v = exp_decay(Vector2::ZERO, Vector2::new(2.0, -4.0), 3.0, 0.25);
+        assert!((v - Vector2::new(2.0, -4.0) * (1.0 - (-0.75f32).exp())).magnitude() < 1.0e-6);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_smooth_damp() {
+        // Approaches monotonically without overshooting, similar end state at any frame rate
+        let mut ends = Vec::new();
+        for fps in [30, 60, 144] {
+            let mut velocity = 0.0;
+            let mut value = 0.0;
+            #[allow(clippy::cast_precision_loss)]
+            let dt = 1.0 / fps as f32;
+            for _ in 0..fps {
+                let next = smooth_damp(value, 10.0, &mut velocity, 0.3, f32::INFINITY, dt);
+                assert!(next >= value && next <= 10.0, "{fps} fps: {value} -> {next}");
+                value = next;
+            }
+            ends.push(value);
+        }
+        assert!(ends.iter().all(|end| (end - 10.0).abs() < 0.1), "{ends:?}");
+
+        // Speed is limited
+        let mut velocity = 0.0;
+        let value = run(10, 0.1, 0.0, |value, dt| smooth_damp(value, 100.0, &mut velocity, 0.1, 5.0, dt));
+        assert!(value <= 5.0 + 1.0e-3, "{value}");
+
+        // Overshoot snaps to target and stops
+        let mut velocity = 50.0;
+        assert_eq!(smooth_damp(9.9, 10.0, &mut velocity, 0.3, f32::INFINITY, 0.1), 10.0);
+        assert_eq!(velocity, 0.0);
+
+        let mut velocity = Vector3::ZERO;
+        let target = Vector3::new(1.0, 2.0, 3.0);
+        let mut value = Vector3::ZERO;
+        for _ in 0..120 {
+            value = smooth_damp(value, target, &mut velocity, 0.2, f32::INFINITY, 1.0 / 60.0);
+        }
+        assert!((value - target).magnitude() < 1.0e-3, "{value:?}");
+    }
+
+    #[test]
+    fn test_angle_smoothing_short_path() {
+        // From 170 to -170 degrees goes through 180, not through 0
+        let current = Radians::from(Degrees(170.0));
+        let target = Radians::from(Degrees(-170.0));
+        let next = exp_decay_angle(current, target, 100.0, 1.0);
+        assert!((next.value() - Radians::from(Degrees(190.0)).value()).abs() < 1.0e-4, "{next:?}");
+
+        let mut velocity = 0.0;
+        let next = smooth_damp_angle(current, target, &mut velocity, 0.1, f32::INFINITY, 0.01);
+        assert!(next > current && velocity > 0.0, "{next:?}, {velocity}");
+    }
+}
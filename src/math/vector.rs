@@ -6,9 +6,16 @@ pub trait DotProduct {
     fn dot(self, rhs: Self) -> f32;
 }
 
+impl DotProduct for f32 {
+    #[inline]
+    fn dot(self, rhs: Self) -> f32 {
+        self * rhs
+    }
+}
+
 pub trait MatrixTransform {
     #[must_use]
-    fn transform(self, mat: Matrix) -> Self;
+    fn transform(self, mat: &Matrix) -> Self;
 }
 
 /// Vector normalization
@@ -17,11 +24,6 @@ pub trait Normalize {
     fn normalize(self) -> Normalized<Self>;
 }
 
-pub trait Angle {
-    #[must_use]
-    fn angle(self, other: Self) -> Radians;
-}
-
 // Helper for providing vector-wide implementations
 pub trait Vector:
     Sized + Copy +
@@ -87,11 +89,143 @@ impl Vector2 {
     pub const fn new(x: f32, y: f32) -> Self {
         Self { x, y }
     }
+
+    /// Calculate angle defined by a two vectors line
+    /// NOTE: Angle is negated, so it increases counter-clockwise on screen (y-down)
+    #[must_use]
+    pub fn line_angle(start: Self, end: Self) -> Radians {
+        -Radians::atan2(end.y - start.y, end.x - start.x)
+    }
+
+    /// Rotate vector by angle
+    pub fn rotate(self, angle: Radians) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+        }
+    }
+
+    /// Calculate reflected vector to normal
+    pub fn reflect(self, normal: Self) -> Self {
+        let dot_product = self.dot(normal);
+        self - normal * (2.0 * dot_product)
+    }
+
+    /// Get min value for each pair of components
+    #[inline]
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+        }
+    }
+
+    /// Get max value for each pair of components
+    #[inline]
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+        }
+    }
+
+    /// Clamp the components of the vector between min and max values specified by the given vectors
+    #[inline]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        self.max(min).min(max)
+    }
+
+    /// Clamp the magnitude of the vector between two min and max values
+    pub fn clamp_magnitude(self, min: f32, max: f32) -> Self {
+        let length_sqr = self.magnitude_sqr();
+        if length_sqr > 0.0 {
+            let length = length_sqr.sqrt();
+            let scale = if length < min {
+                min / length
+            } else if length > max {
+                max / length
+            } else {
+                1.0
+            };
+            self * scale
+        } else {
+            self
+        }
+    }
+
+    /// Move vector towards target, never overshooting it
+    /// NOTE: Negative `max_distance` moves away from target
+    pub fn move_towards(self, target: Self, max_distance: f32) -> Self {
+        let delta = target - self;
+        let dist_sqr = target.distance_sqr(self);
+
+        if dist_sqr == 0.0 || (max_distance >= 0.0 && dist_sqr <= max_distance*max_distance) {
+            target
+        } else {
+            let dist = dist_sqr.sqrt();
+
+            self + max_distance * delta / dist
+        }
+    }
+
+    /// Invert the given vector
+    #[inline]
+    pub fn invert(self) -> Self {
+        Self {
+            x: 1.0 / self.x,
+            y: 1.0 / self.y,
+        }
+    }
+
+    /// Compute the direction of a refracted ray
+    /// `self`: normalized direction of the incoming ray
+    /// `normal`: normalized normal vector of the interface of two optical media
+    /// `ratio`: ratio of the refractive index of the medium from where the ray comes
+    ///          to the refractive index of the medium on the other side of the surface
+    /// NOTE: Total internal reflection returns a zero vector
+    pub fn refract(self, normal: Self, ratio: f32) -> Self {
+        let dot_product = self.dot(normal);
+        let d = 1.0 - ratio * ratio * (1.0 - dot_product * dot_product);
+
+        if d >= 0.0 {
+            let d = d.sqrt();
+            self * ratio - normal * (ratio * dot_product + d)
+        } else {
+            Self::ZERO
+        }
+    }
+}
+
+impl_component_conversions!(Vector2 { x, y }, 2, (f32, f32));
+
+impl From<Point> for Vector2 {
+    /// NOTE: Coordinates beyond 2^24 lose precision
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    fn from(Point { x, y }: Point) -> Self {
+        Self::new(x as f32, y as f32)
+    }
+}
+
+impl From<Vector2> for Point {
+    /// NOTE: Coordinates are rounded to the nearest integer (half away from zero),
+    /// out of range values saturate and NaN becomes 0
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(Vector2 { x, y }: Vector2) -> Self {
+        Self { x: x.round() as i32, y: y.round() as i32 }
+    }
 }
 
 impl Angle for Vector2 {
+    /// Calculate angle between two vectors
+    /// NOTE: Angle is calculated from origin point (0, 0), positive when `other` is counter-clockwise
+    /// from `self` in y-up coordinates (clockwise on screen, y-down)
     fn angle(self, other: Self) -> Radians {
-        todo!()
+        let det = self.x * other.y - self.y * other.x;
+        let dot = self.dot(other);
+        Radians::atan2(det, dot)
     }
 }
 
@@ -338,7 +472,7 @@ impl DotProduct for Vector2 {
 
 impl MatrixTransform for Vector2 {
     #[inline]
-    fn transform(self, mat: Matrix) -> Self {
+    fn transform(self, mat: &Matrix) -> Self {
         Self {
             x: mat.0[0][0] * self.x + mat.0[0][1] * self.y + mat.0[0][2] * 0.0 + mat.0[0][3],
             y: mat.0[1][0] * self.x + mat.0[1][1] * self.y + mat.0[1][2] * 0.0 + mat.0[1][3],
@@ -372,6 +506,24 @@ impl Vector3 {
         Self { x, y, z }
     }
 
+    /// Extend a 2d vector with a z component
+    #[inline]
+    pub const fn from_vec2(Vector2 { x, y }: Vector2, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Get x and y components, dropping z
+    #[inline]
+    pub const fn xy(self) -> Vector2 {
+        Vector2::new(self.x, self.y)
+    }
+
+    /// Get x and z components (ground plane), dropping y
+    #[inline]
+    pub const fn xz(self) -> Vector2 {
+        Vector2::new(self.x, self.z)
+    }
+
     #[inline]
     pub fn cross_product(self, other: Self) -> Self {
         Self {
@@ -381,6 +533,64 @@ impl Vector3 {
         }
     }
 
+    /// Get a vector perpendicular to the given one, crossing it with its smallest cardinal axis
+    pub fn perpendicular(self) -> Self {
+        let mut min = self.x.abs();
+        let mut cardinal_axis = Self::UNIT_X;
+
+        if self.y.abs() < min {
+            min = self.y.abs();
+            cardinal_axis = Self::UNIT_Y;
+        }
+
+        if self.z.abs() < min {
+            cardinal_axis = Self::UNIT_Z;
+        }
+
+        self.cross_product(cardinal_axis)
+    }
+
+    /// Calculate the projection of the vector on to `onto`
+    pub fn project(self, onto: Self) -> Self {
+        onto * (self.dot(onto) / onto.dot(onto))
+    }
+
+    /// Calculate the rejection of the vector from `from`, the component perpendicular to it
+    pub fn reject(self, from: Self) -> Self {
+        self - self.project(from)
+    }
+
+    /// Orthonormalize provided vectors
+    /// Makes vectors normalized and orthogonal to each other
+    /// Gram-Schmidt function implementation
+    /// NOTE: Zero length vectors are left unscaled instead of producing NaN
+    pub fn ortho_normalize(v1: &mut Self, v2: &mut Self) {
+        let unit = |v: Self| match v.magnitude() {
+            0.0 => v,
+            length => v / length,
+        };
+
+        // Vector3Normalize(*v1);
+        *v1 = unit(*v1);
+
+        // Vector3CrossProduct(*v1, *v2)
+        let vn1 = unit(v1.cross_product(*v2));
+
+        // Vector3CrossProduct(vn1, *v1)
+        *v2 = vn1.cross_product(*v1);
+    }
+
+    /// Transform a vector by quaternion rotation
+    pub fn rotate_by_quaternion(self, q: Quaternion) -> Self {
+        let Self { x, y, z } = self;
+        Self {
+            x: x*(q.x*q.x + q.w*q.w - q.y*q.y - q.z*q.z) + y*(2.0*q.x*q.y - 2.0*q.w*q.z) + z*(2.0*q.x*q.z + 2.0*q.w*q.y),
+            y: x*(2.0*q.w*q.z + 2.0*q.x*q.y) + y*(q.w*q.w - q.x*q.x + q.y*q.y - q.z*q.z) + z*(-2.0*q.w*q.x + 2.0*q.y*q.z),
+            z: x*(-2.0*q.w*q.y + 2.0*q.x*q.z) + y*(2.0*q.w*q.x + 2.0*q.y*q.z) + z*(q.w*q.w - q.x*q.x - q.y*q.y + q.z*q.z),
+        }
+    }
+
+    /// Rotates a vector around an axis
     pub fn rotate_by_axis_angle(self, axis: Self, angle: Radians) -> Self {
         // Using Euler-Rodrigues Formula
         // Ref.: https://en.wikipedia.org/w/index.php?title=Euler%E2%80%93Rodrigues_formula
@@ -404,17 +614,110 @@ impl Vector3 {
             self + max_distance * delta / dist
         }
     }
-}
 
-impl From<Vector3> for [f32; 3] {
-    fn from(Vector3 { x, y, z }: Vector3) -> Self {
-        [x, y, z]
+    /// Get min value for each pair of components
+    #[inline]
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z),
+        }
+    }
+
+    /// Get max value for each pair of components
+    #[inline]
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z),
+        }
+    }
+
+    /// Clamp the components of the vector between min and max values specified by the given vectors
+    #[inline]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        self.max(min).min(max)
+    }
+
+    /// Clamp the magnitude of the vector between two values
+    pub fn clamp_magnitude(self, min: f32, max: f32) -> Self {
+        let length_sqr = self.magnitude_sqr();
+        if length_sqr > 0.0 {
+            let length = length_sqr.sqrt();
+            let scale = if length < min {
+                min / length
+            } else if length > max {
+                max / length
+            } else {
+                1.0
+            };
+            self * scale
+        } else {
+            self
+        }
+    }
+
+    /// Compute barycenter coordinates (u, v, w) for point `p` with respect to triangle (a, b, c)
+    /// NOTE: Assumes `p` is on the plane of the triangle
+    pub fn barycenter(p: Self, a: Self, b: Self, c: Self) -> Self {
+        let v0 = b - a;
+        let v1 = c - a;
+        let v2 = p - a;
+        let d00 = v0.dot(v0);
+        let d01 = v0.dot(v1);
+        let d11 = v1.dot(v1);
+        let d20 = v2.dot(v0);
+        let d21 = v2.dot(v1);
+
+        let denom = d00*d11 - d01*d01;
+
+        let weight_b = (d11*d20 - d01*d21) / denom;
+        let weight_c = (d00*d21 - d01*d20) / denom;
+        Self::new(1.0 - (weight_c + weight_b), weight_b, weight_c)
+    }
+
+    /// Calculate cubic hermite interpolation between two vectors and their tangents
+    /// as described in the GLTF 2.0 specification: <https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#interpolation-cubic>
+    pub fn cubic_hermite(self, tangent1: Self, other: Self, tangent2: Self, amount: Percent) -> Self {
+        let amount_pow2 = amount*amount;
+        let amount_pow3 = amount*amount*amount;
+
+        self * (2.0*amount_pow3 - 3.0*amount_pow2 + 1.0)
+            + tangent1 * (amount_pow3 - 2.0*amount_pow2 + amount)
+            + other * (-2.0*amount_pow3 + 3.0*amount_pow2)
+            + tangent2 * (amount_pow3 - amount_pow2)
+    }
+
+    /// Compute the direction of a refracted ray
+    /// `self`: normalized direction of the incoming ray
+    /// `normal`: normalized normal vector of the interface of two optical media
+    /// `ratio`: ratio of the refractive index of the medium from where the ray comes
+    ///          to the refractive index of the medium on the other side of the surface
+    /// NOTE: Total internal reflection returns a zero vector
+    pub fn refract(self, normal: Self, ratio: f32) -> Self {
+        let dot_product = self.dot(normal);
+        let d = 1.0 - ratio * ratio * (1.0 - dot_product * dot_product);
+
+        if d >= 0.0 {
+            let d = d.sqrt();
+            self * ratio - normal * (ratio * dot_product + d)
+        } else {
+            Self::ZERO
+        }
     }
 }
 
+impl_component_conversions!(Vector3 { x, y, z }, 3, (f32, f32, f32));
+
 impl Angle for Vector3 {
+    /// Calculate angle between two vectors, always positive
     fn angle(self, other: Self) -> Radians {
-        todo!()
+        let cross = self.cross_product(other);
+        let len = cross.magnitude();
+        let dot = self.dot(other);
+        Radians::atan2(len, dot)
     }
 }
 
@@ -677,7 +980,7 @@ impl DotProduct for Vector3 {
 
 impl MatrixTransform for Vector3 {
     #[inline]
-    fn transform(self, mat: Matrix) -> Self {
+    fn transform(self, mat: &Matrix) -> Self {
         Self {
             x: mat.0[0][0] * self.x + mat.0[0][1] * self.y + mat.0[0][2] * self.z + mat.0[0][3],
             y: mat.0[1][0] * self.x + mat.0[1][1] * self.y + mat.0[1][2] * self.z + mat.0[1][3],
@@ -713,11 +1016,38 @@ impl Vector4 {
     pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
         Self { x, y, z, w }
     }
+
+    /// Extend a 3d vector with a w component
+    #[inline]
+    pub const fn from_vec3(Vector3 { x, y, z }: Vector3, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Get x and y components, dropping z and w
+    #[inline]
+    pub const fn xy(self) -> Vector2 {
+        Vector2::new(self.x, self.y)
+    }
+
+    /// Get x, y and z components, dropping w
+    #[inline]
+    pub const fn xyz(self) -> Vector3 {
+        Vector3::new(self.x, self.y, self.z)
+    }
 }
 
+impl_component_conversions!(Vector4 { x, y, z, w }, 4, (f32, f32, f32, f32));
+
 impl Angle for Vector4 {
+    /// Calculate angle between two vectors, always positive
+    /// NOTE: Zero vectors have no direction, their angle is 0
     fn angle(self, other: Self) -> Radians {
-        todo!()
+        let magnitudes = self.magnitude() * other.magnitude();
+        if magnitudes == 0.0 {
+            Radians::ZERO
+        } else {
+            Radians::acos((self.dot(other) / magnitudes).clamp(-1.0, 1.0))
+        }
     }
 }
 
@@ -996,7 +1326,7 @@ impl DotProduct for Vector4 {
 
 impl MatrixTransform for Vector4 {
     #[inline]
-    fn transform(self, mat: Matrix) -> Self {
+    fn transform(self, mat: &Matrix) -> Self {
         Self {
             x: mat.0[0][0] * self.x + mat.0[0][1] * self.y + mat.0[0][2] * self.z + mat.0[0][3] * self.w,
             y: mat.0[1][0] * self.x + mat.0[1][1] * self.y + mat.0[1][2] * self.z + mat.0[1][3] * self.w,
@@ -1005,3 +1335,190 @@ impl MatrixTransform for Vector4 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+    use super::*;
+
+    #[track_caller]
+    fn assert_vector2_near(actual: Vector2, expected: Vector2) {
+        assert!((actual - expected).magnitude() < 1.0e-5, "{actual:?} != {expected:?}");
+    }
+
+    #[track_caller]
+    fn assert_angle_near(actual: Radians, expected: f32) {
+        assert!((actual.0 - expected).abs() < 1.0e-5, "{actual:?} != {expected}");
+    }
+
+    #[test]
+    fn test_vector2_angles() {
+        // Positive from x towards y, same as raymath Vector2Angle()
+        assert_angle_near(Vector2::UNIT_X.angle(Vector2::UNIT_Y), FRAC_PI_2);
+        assert_angle_near(Vector2::UNIT_Y.angle(Vector2::UNIT_X), -FRAC_PI_2);
+        assert_angle_near(Vector2::new(2.0, 0.0).angle(Vector2::new(1.0, 1.0)), FRAC_PI_4);
+        assert_angle_near(Vector2::UNIT_X.angle(-Vector2::UNIT_X), PI);
+        assert_angle_near(Vector2::new(3.0, 4.0).angle(Vector2::new(6.0, 8.0)), 0.0);
+
+        // Negated, raymath 5.5 Vector2LineAngle()
+        assert_angle_near(Vector2::line_angle(Vector2::ZERO, Vector2::new(1.0, 1.0)), -FRAC_PI_4);
+        assert_angle_near(Vector2::line_angle(Vector2::new(1.0, 1.0), Vector2::new(1.0, 0.0)), FRAC_PI_2);
+        assert_angle_near(Vector2::line_angle(Vector2::new(5.0, 2.0), Vector2::new(9.0, 2.0)), 0.0);
+
+        // Rotating by the angle between vectors aligns them
+        assert_vector2_near(Vector2::UNIT_X.rotate(Radians(FRAC_PI_2)), Vector2::UNIT_Y);
+        assert_vector2_near(Vector2::new(1.0, 2.0).rotate(Radians(PI)), Vector2::new(-1.0, -2.0));
+        let (a, b) = (Vector2::new(3.0, -1.0), Vector2::new(-2.0, 5.0));
+        assert_vector2_near(a.rotate(a.angle(b)).normalize(), b.normalize());
+        assert!((a.rotate(Radians(1.0)).magnitude() - a.magnitude()).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_vector2_reflect_refract() {
+        assert_vector2_near(Vector2::new(1.0, -1.0).reflect(Vector2::UNIT_Y), Vector2::new(1.0, 1.0));
+        assert_vector2_near(Vector2::new(-2.0, 3.0).reflect(Vector2::UNIT_X), Vector2::new(2.0, 3.0));
+
+        // Snell's law: sin(refracted) = ratio*sin(incident)
+        let incident = Vector2::new(0.5, -(0.75f32).sqrt());
+        assert_vector2_near(incident.refract(Vector2::UNIT_Y, 1.0), incident);
+        let refracted = incident.refract(Vector2::UNIT_Y, 1.0/1.5);
+        assert_vector2_near(refracted, Vector2::new(0.5/1.5, -(1.0 - (0.5f32/1.5).powi(2)).sqrt()));
+        // Total internal reflection
+        let grazing = Vector2::new(0.75f32.sqrt(), -0.5);
+        assert_eq!(grazing.refract(Vector2::UNIT_Y, 1.5), Vector2::ZERO);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_vector2_clamping() {
+        let (low, high) = (Vector2::new(1.0, -2.0), Vector2::new(-3.0, 4.0));
+        assert_eq!(low.min(high), Vector2::new(-3.0, -2.0));
+        assert_eq!(low.max(high), Vector2::new(1.0, 4.0));
+        assert_eq!(Vector2::new(5.0, -5.0).clamp(Vector2::ZERO, Vector2::ONE), Vector2::new(1.0, 0.0));
+        assert_eq!(Vector2::new(0.5, 0.25).clamp(Vector2::ZERO, Vector2::ONE), Vector2::new(0.5, 0.25));
+
+        // Direction is kept, zero vectors can't be scaled
+        let v = Vector2::new(3.0, 4.0);
+        assert_vector2_near(v.clamp_magnitude(1.0, 2.0), Vector2::new(1.2, 1.6));
+        assert_vector2_near(v.clamp_magnitude(10.0, 20.0), Vector2::new(6.0, 8.0));
+        assert_eq!(v.clamp_magnitude(1.0, 10.0), v);
+        assert_eq!(Vector2::ZERO.clamp_magnitude(1.0, 2.0), Vector2::ZERO);
+
+        // Never overshoots, negative distance moves away
+        assert_vector2_near(Vector2::ZERO.move_towards(v, 1.0), Vector2::new(0.6, 0.8));
+        assert_eq!(Vector2::ZERO.move_towards(v, 10.0), v);
+        assert_vector2_near(Vector2::ZERO.move_towards(v, -1.0), Vector2::new(-0.6, -0.8));
+        assert_eq!(v.move_towards(v, -1.0), v);
+
+        assert_eq!(Vector2::new(2.0, -4.0).invert(), Vector2::new(0.5, -0.25));
+    }
+
+    #[track_caller]
+    fn assert_vector3_near(actual: Vector3, expected: Vector3) {
+        assert!((actual - expected).magnitude() < 1.0e-5, "{actual:?} != {expected:?}");
+    }
+
+    #[test]
+    fn test_vector3_rotation() {
+        // Right-handed: x turns into y around z
+        assert_vector3_near(Vector3::UNIT_X.rotate_by_axis_angle(Vector3::UNIT_Z, Radians(FRAC_PI_2)), Vector3::UNIT_Y);
+        assert_vector3_near(Vector3::UNIT_Y.rotate_by_axis_angle(Vector3::UNIT_X, Radians(FRAC_PI_2)), Vector3::UNIT_Z);
+        assert_vector3_near(Vector3::UNIT_Z.rotate_by_axis_angle(Vector3::new(0.0, 5.0, 0.0), Radians(FRAC_PI_2)), Vector3::UNIT_X);
+        // Vectors along the axis don't move
+        assert_vector3_near(Vector3::new(0.0, 0.0, 3.0).rotate_by_axis_angle(Vector3::UNIT_Z, Radians(1.0)), Vector3::new(0.0, 0.0, 3.0));
+
+        // Euler-Rodrigues matches the quaternion rotation
+        let v = Vector3::new(1.0, -2.0, 0.5);
+        for (axis, angle) in [(Vector3::new(1.0, 2.0, 3.0), 0.7), (Vector3::new(-4.0, 0.0, 1.0), -2.5), (Vector3::UNIT_Y, PI)] {
+            let rotated = v.rotate_by_axis_angle(axis, Radians(angle));
+            assert_vector3_near(rotated, v.rotate_by_quaternion(Quaternion::from_axis_angle(axis, Radians(angle))));
+            assert!((rotated.magnitude() - v.magnitude()).abs() < 1.0e-5);
+        }
+        assert_vector3_near(v.rotate_by_quaternion(Quaternion::IDENTITY), v);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_vector3_projection() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        // Crossed with the smallest cardinal axis, x here
+        assert_eq!(v.perpendicular(), Vector3::new(0.0, 3.0, -2.0));
+        for v in [v, Vector3::new(5.0, -0.5, 2.0), Vector3::new(-1.0, 4.0, 0.25), Vector3::UNIT_Y] {
+            let perpendicular = v.perpendicular();
+            assert!(perpendicular.magnitude() > 0.0 && v.dot(perpendicular).abs() < 1.0e-5, "{v:?} {perpendicular:?}");
+        }
+
+        // Projection and rejection add back up to the vector
+        assert_vector3_near(v.project(Vector3::new(0.0, 2.0, 0.0)), Vector3::new(0.0, 2.0, 0.0));
+        assert_vector3_near(v.reject(Vector3::new(0.0, 2.0, 0.0)), Vector3::new(1.0, 0.0, 3.0));
+        let onto = Vector3::new(-1.0, 1.0, 2.0);
+        assert_vector3_near(v.project(onto) + v.reject(onto), v);
+        assert!(v.reject(onto).dot(onto).abs() < 1.0e-5);
+
+        // Gram-Schmidt keeps the first direction
+        let (mut v1, mut v2) = (Vector3::new(2.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 0.0));
+        Vector3::ortho_normalize(&mut v1, &mut v2);
+        assert_vector3_near(v1, Vector3::UNIT_X);
+        assert_vector3_near(v2, Vector3::UNIT_Y);
+        let (mut v1, mut v2) = (v, onto);
+        Vector3::ortho_normalize(&mut v1, &mut v2);
+        assert_vector3_near(v1, v.normalize());
+        assert!((v2.magnitude() - 1.0).abs() < 1.0e-5 && v1.dot(v2).abs() < 1.0e-5);
+        assert!(v2.dot(onto) > 0.0, "same side as the original second vector");
+
+        // Weights of each triangle corner
+        let [a, b, c] = [Vector3::new(0.0, 0.0, 1.0), Vector3::new(4.0, 0.0, 1.0), Vector3::new(0.0, 2.0, 3.0)];
+        assert_vector3_near(Vector3::barycenter(a*0.2 + b*0.3 + c*0.5, a, b, c), Vector3::new(0.2, 0.3, 0.5));
+        assert_vector3_near(Vector3::barycenter(b, a, b, c), Vector3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_vector3_interpolation() {
+        let (start, end) = (Vector3::new(1.0, 2.0, 3.0), Vector3::new(-3.0, 0.0, 5.0));
+        let (tangent1, tangent2) = (Vector3::new(0.0, 4.0, 0.0), Vector3::new(1.0, 0.0, -1.0));
+        let hermite = |t| start.cubic_hermite(tangent1, end, tangent2, t);
+        assert_vector3_near(hermite(0.0), start);
+        assert_vector3_near(hermite(1.0), end);
+        // Slopes at both ends are the tangents
+        let h = 1.0e-3;
+        assert!(((hermite(h) - hermite(0.0))/h - tangent1).magnitude() < 0.05);
+        assert!(((hermite(1.0) - hermite(1.0 - h))/h - tangent2).magnitude() < 0.05);
+        // Zero tangents along a line stay on it
+        let midpoint = start.cubic_hermite(Vector3::ZERO, end, Vector3::ZERO, 0.5);
+        assert_vector3_near(midpoint, (start + end)*0.5);
+
+        let v = Vector3::new(0.0, 3.0, 4.0);
+        assert_vector3_near(Vector3::ZERO.move_towards(v, 1.0), Vector3::new(0.0, 0.6, 0.8));
+        assert_eq!(Vector3::ZERO.move_towards(v, 6.0), v);
+        assert_vector3_near(v.clamp_magnitude(0.0, 2.5), Vector3::new(0.0, 1.5, 2.0));
+        assert_eq!(Vector3::ZERO.clamp_magnitude(1.0, 2.0), Vector3::ZERO);
+        assert_eq!(Vector3::new(-1.0, 0.5, 9.0).clamp(Vector3::ZERO, Vector3::ONE), Vector3::new(0.0, 0.5, 1.0));
+        assert_eq!(start.min(end), Vector3::new(-3.0, 0.0, 3.0));
+        assert_eq!(start.max(end), Vector3::new(1.0, 2.0, 5.0));
+
+        // Snell's law in the plane of incidence, total internal reflection is zero
+        let incident = Vector3::new(0.5, -(0.75f32).sqrt(), 0.0);
+        let refracted = incident.refract(Vector3::UNIT_Y, 1.0/1.5);
+        assert_vector3_near(refracted, Vector3::new(0.5/1.5, -(1.0 - (0.5f32/1.5).powi(2)).sqrt(), 0.0));
+        assert_eq!(Vector3::new(0.75f32.sqrt(), -0.5, 0.0).refract(Vector3::UNIT_Y, 1.5), Vector3::ZERO);
+    }
+
+    #[test]
+    fn test_vector3_vector4_angles() {
+        // Unsigned, in [0..pi], regardless of order or magnitude
+        let (a, b) = (Vector3::new(1.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 0.0));
+        assert_angle_near(a.angle(b), FRAC_PI_4);
+        assert_angle_near(b.angle(a), FRAC_PI_4);
+        assert_angle_near((a*7.0).angle(b*0.1), FRAC_PI_4);
+        assert_angle_near(Vector3::UNIT_Y.angle(Vector3::new(0.0, -2.0, 0.0)), PI);
+        assert_angle_near(Vector3::UNIT_Y.angle(Vector3::new(0.0, 3.0, 0.0)), 0.0);
+        assert_angle_near(Vector3::UNIT_Y.angle(Vector3::new(0.0, 0.001, -1.0)), FRAC_PI_2 - 0.001);
+
+        assert_angle_near(Vector4::new(1.0, 0.0, 0.0, 0.0).angle(Vector4::new(0.0, 0.0, 0.0, 5.0)), FRAC_PI_2);
+        assert_angle_near(Vector4::new(1.0, 1.0, 1.0, 1.0).angle(Vector4::new(-2.0, -2.0, -2.0, -2.0)), PI);
+        // NOTE: acos is steep near 0, parallel vectors are only close to it
+        assert!(Vector4::new(1.0, 2.0, 3.0, 4.0).angle(Vector4::new(1.0, 2.0, 3.0, 4.0)*3.0).0 < 1.0e-3);
+        assert_eq!(Vector4::ZERO.angle(Vector4::new(1.0, 0.0, 0.0, 0.0)), Radians::ZERO);
+    }
+}
@@ -48,9 +48,9 @@
 *
 **********************************************************************************************/
 
-use std::num::TryFromIntError;
-use sdl3::{gamepad::Gamepad as SdlGamepad, mouse::{Cursor as SdlCursor, SystemCursor}, video::{GLContext, Window as SdlWindow, WindowBuildError}, Error as SdlError, IntegerOrSdlError, Sdl, VideoSubsystem};
-use crate::{config::MAX_GAMEPADS, prelude::{ConfigFlags, Core, GamepadAxis, Image, KeyboardKey, Vector2}, tracelog};
+use std::{ffi::NulError, num::TryFromIntError, ops::{Deref, DerefMut}, path::{Path, PathBuf}};
+use sdl3::{event::{Event, WindowEvent}, gamepad::{AddMappingError, Axis as SdlAxis, Button as SdlButton, Gamepad as SdlGamepad}, joystick::JoystickInstance, mouse::{Cursor as SdlCursor, MouseButton as SdlMouseButton, SystemCursor}, pixels::PixelMasks, surface::Surface, video::{Display, DisplayMode, GLContext, SwapInterval, Window as SdlWindow, WindowBuildError, WindowPos}, Error as SdlError, EventPump, GamepadSubsystem, IntegerOrSdlError, JoystickSubsystem, Sdl, VideoSubsystem};
+use crate::{config::{MAX_FILEPATH_CAPACITY, MAX_GAMEPADS, MAX_GAMEPAD_VIBRATION_TIME}, core::window::apply_vsync, rlgl::FlushReason, prelude::{closest_video_mode, AutomationEvent, AutomationEventType, BackgroundAudio, CustomEventType, InputEventType, WindowEventType, ConfigFlags, Core, EventWaiting, Gamepad, GamepadAxis, GamepadButton, GamepadID, HookStage, Image, KeyboardKey, MonitorID, MouseButton, MouseCursor, Percent, MAX_GAMEPAD_NAME_LEN, Point, Position2, Seconds, Size, Vector2, VideoMode, VsyncMode}, tracelog};
 
 /// Size of the clipboard buffer used on GetClipboardText()
 pub const MAX_CLIPBOARD_BUFFER_LENGTH: usize = 1024;
@@ -63,11 +63,21 @@ pub struct Platform {
     window: SdlWindow,
     gl_context: GLContext,
 
+    event_pump: EventPump,
+    gamepad_subsystem: GamepadSubsystem,
+    joystick_subsystem: JoystickSubsystem,
     gamepad: [Option<SdlGamepad>; MAX_GAMEPADS],
-    cursor: Option<SdlCursor>,
+    /// System cursors, created on first use
+    cursors: [Option<SdlCursor>; CURSORS_LUT.len()],
     cursor_relative: bool,
+    /// Opacity not supported warning already logged
+    opacity_warned: bool,
 }
 
+/// Touch point identifier of the left mouse button, simulated as a touch point
+#[cfg(feature = "support_mouse_gestures")]
+const MOUSE_TOUCH_POINT_ID: u64 = u64::MAX;
+
 pub const SCANCODE_MAPPED_NUM: usize = 232;
 static MAP_SCANCODE_TO_KEY: [Option<KeyboardKey>; SCANCODE_MAPPED_NUM] = [
     None,                             // SDL_SCANCODE_UNKNOWN
@@ -210,6 +220,7 @@ static CURSORS_LUT: [SystemCursor; 11] = [
 ];
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum InitPlatformError {
     WindowBuildError(WindowBuildError),
     SdlError(SdlError),
@@ -260,6 +271,85 @@ impl From<IntegerOrSdlError> for InitPlatformError {
     }
 }
 
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum VideoModeError {
+    /// Monitor index is not connected
+    InvalidMonitor(MonitorID),
+    /// Video mode is not supported by the monitor
+    InvalidMode(VideoMode),
+    SdlError(SdlError),
+}
+
+impl std::fmt::Display for VideoModeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidMonitor(monitor) => write!(f, "monitor {monitor} not found"),
+            Self::InvalidMode(mode) => write!(f, "video mode {}x{} @ {}Hz not supported", mode.width, mode.height, mode.refresh_rate),
+            Self::SdlError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for VideoModeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidMonitor(_) | Self::InvalidMode(_) => None,
+            Self::SdlError(e) => Some(e),
+        }
+    }
+}
+
+impl From<SdlError> for VideoModeError {
+    fn from(value: SdlError) -> Self {
+        Self::SdlError(value)
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GamepadError {
+    /// Gamepad is not connected
+    NotAvailable(GamepadID),
+    /// Mapping rejected by SDL, line numbers start at 1
+    InvalidMapping { line: usize, error: AddMappingError },
+    IntegerOrSdlError(IntegerOrSdlError),
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for GamepadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAvailable(gamepad) => write!(f, "gamepad {gamepad} not available"),
+            Self::InvalidMapping { line, error } => write!(f, "invalid gamepad mapping at line {line}: {error}"),
+            Self::IntegerOrSdlError(e) => e.fmt(f),
+            Self::Io(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GamepadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NotAvailable(_) => None,
+            Self::InvalidMapping { error, .. } => Some(error),
+            Self::IntegerOrSdlError(e) => Some(e),
+            Self::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<IntegerOrSdlError> for GamepadError {
+    fn from(value: IntegerOrSdlError) -> Self {
+        Self::IntegerOrSdlError(value)
+    }
+}
+impl From<std::io::Error> for GamepadError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
 impl Platform {
     /// Initialize platform: graphics, inputs and more
     pub(crate) fn init(core: &mut Core) -> Result<Self, InitPlatformError> {
@@ -280,7 +370,6 @@ impl Platform {
             .map_err(|e| WindowBuildError::SdlError(e))?;
 
         if core.window.flags.contains(ConfigFlags::FullscreenMode) {
-            core.window.fullscreen = true;
             flags |= SDL_WINDOW_FULLSCREEN as u32;
         }
 
@@ -335,10 +424,9 @@ impl Platform {
         core.window.display.width = display_mode.w.try_into()?;
         core.window.display.height = display_mode.h.try_into()?;
 
-        core.window.render.width = core.window.screen.width;
-        core.window.render.height = core.window.screen.height;
-        core.window.current_fbo.width = core.window.render.width;
-        core.window.current_fbo.height = core.window.render.height;
+        // Render at framebuffer pixels size, drawing in screen (logical) coordinates scaled by DPI
+        core.window.dpi_scale = window_scale_dpi(&window);
+        core.update_render_scale();
 
         tracelog!(Info, "DISPLAY: Device initialized successfully");
         tracelog!(Info, "    > Display size: {} x {}", core.window.display.width, core.window.display.height);
@@ -349,50 +437,36 @@ impl Platform {
         video_subsystem.gl_set_swap_interval(if core.window.flags.contains(ConfigFlags::VsyncHint) { 1 } else { 0 })?;
 
         // Load OpenGL extensions
-        // NOTE: GL procedures address loader is required to load extensions
-        /* todo: rlLoadExtensions(SDL_GL_GetProcAddress); */
+        // NOTE: GL procedures are loaded by the backend passed to rlgl::set_gl_api() (using get_gl_proc_address()),
+        // extensions and capabilities are detected then
 
         // Initialize input events system
         //----------------------------------------------------------------------------
         // Initialize gamepads
 
-        let gamepad: [Option<SdlGamepad>; MAX_GAMEPADS] = {
-            let gamepad_subsystem = sdl_context.gamepad()?;
-            let joystick_subsystem = sdl_context.joystick()?;
-            let joysticks_instances = joystick_subsystem.joysticks()?;
-            let mut joystick_iter = joysticks_instances.into_iter()
-                .map(|joystick_instance| {
-                    let id = joystick_instance.id;
-                    let gamepad_joystick = gamepad_subsystem.open(id).and_then(|gamepad| joystick_subsystem.open(joystick_instance).map(|joystick| (gamepad, joystick)));
-                    match gamepad_joystick {
-                        Ok((gamepad, joystick)) => {
-                            let core_gamepad = &mut core.input.gamepad.items[id as usize];
-                            core_gamepad.ready = true;
-                            core_gamepad.axis_count = joystick.num_axes();
-                            core_gamepad.axis_state[GamepadAxis::LeftTrigger as usize] = -1.0;
-                            core_gamepad.axis_state[GamepadAxis::RightTrigger as usize] = -1.0;
-                            core_gamepad.name = gamepad.name().as_str().try_into()
-                                .expect(concat!("gamepad name should not exceed ", stringify!(MAX_GAMEPAD_NAME_LEN), " characters"));
-
-                            Some(gamepad)
-                        }
-                        Err(e) => {
-                            tracelog!(Warning, "PLATFORM: Unable to open game controller [ERROR: {e}]");
-                            None
-                        }
-                    }
-                })
-                .take(gamepad_subsystem.num_gamepads()? as usize);
-
-            std::array::from_fn(|_| joystick_iter.next().flatten())
-        };
+        let gamepad_subsystem = sdl_context.gamepad()?;
+        let joystick_subsystem = sdl_context.joystick()?;
+        // NOTE: Gamepad slots are fixed, disconnected slots are kept as not ready
+        core.input.gamepad.items = std::iter::repeat_with(Gamepad::default).take(MAX_GAMEPADS).collect();
+        let mut gamepad: [Option<SdlGamepad>; MAX_GAMEPADS] = std::array::from_fn(|_| None);
+        let joystick_instances = joystick_subsystem.joysticks()?.into_iter()
+            .filter(|joystick_instance| gamepad_subsystem.is_game_controller(joystick_instance.id))
+            .take(MAX_GAMEPADS);
+        for (slot, joystick_instance) in joystick_instances.enumerate() {
+            gamepad[slot] = open_gamepad(&gamepad_subsystem, &joystick_subsystem, joystick_instance, &mut core.input.gamepad.items[slot]);
+        }
+
+        // Enable text input events, required for GetCharPressed()
+        video_subsystem.text_input().start(&window);
+
+        let event_pump = sdl_context.event_pump()?;
 
         // Disable mouse events being interpreted as touch events
         // NOTE: This is wanted because there are SDL_FINGER* events available which provide unique data
         //       Due to the way PollInputEvents() and rgestures.h are currently implemented, setting this won't break SUPPORT_MOUSE_GESTURES
         sdl3::hint::set(sdl3::hint::names::TOUCH_MOUSE_EVENTS, "0");
 
-        /* todo: SDL_EventState(SDL_DROPFILE, SDL_ENABLE); */
+        // NOTE: SDL_EVENT_DROP_FILE is enabled by default in SDL3, no need to enable it
         //----------------------------------------------------------------------------
 
         // Initialize timing system
@@ -417,9 +491,13 @@ impl Platform {
             video_subsystem,
             window,
             gl_context,
+            event_pump,
+            gamepad_subsystem,
+            joystick_subsystem,
             gamepad,
-            cursor: None,
+            cursors: std::array::from_fn(|_| None),
             cursor_relative: false,
+            opacity_warned: false,
         })
     }
 }
@@ -429,143 +507,1216 @@ impl Drop for Platform {
     fn drop(&mut self) {}
 }
 
-/// Check if application should close
-pub fn window_should_close(core: &Core) -> bool {
-    !core.window.ready || core.window.should_close
+/// Set late input polling: when enabled, `end_drawing()` waits for frame pacing first and then polls input,
+/// so the next frame update sees the freshest input state (also with event waiting: wait, then drain events)
+/// NOTE: Pressed/released states still reflect exactly one frame boundary in both modes
+pub fn set_late_input_polling(core: &mut Core, enabled: bool) {
+    core.input.late_polling = enabled;
+}
+
+/// Check if late input polling is enabled
+#[must_use]
+pub fn is_late_input_polling(core: &Core) -> bool {
+    core.input.late_polling
 }
 
-/// Toggle fullscreen mode
+/// Set frame rate while the window is minimized or unfocused, `None` to disable throttling
+/// NOTE: Ignored when `WindowAlwaysRun` flag is set
+pub fn set_background_throttle(core: &mut Core, fps: Option<u32>) {
+    core.window.background_throttle = fps;
+}
+
+/// Set audio behavior while the window is minimized or unfocused
+/// NOTE: Ignored when `WindowAlwaysRun` flag is set
+pub fn set_background_audio(core: &mut Core, audio: BackgroundAudio) {
+    core.window.background_audio = audio;
+}
+
+/// Toggle fullscreen mode, monitor video mode is changed to match window resolution
+/// NOTE: Uses the monitor the window is currently on, the closest supported mode is selected
+/// and desktop resolution is used if the monitor reports no modes. Desktop mode is restored on exit
+/// # Errors
+/// Returns an error if the current monitor can not be queried or SDL fails to switch
 pub fn toggle_fullscreen(core: &mut Core, platform: &mut Platform) -> Result<(), SdlError> {
-    let new_value = !core.window.flags.contains(ConfigFlags::FullscreenMode);
-    platform.window.set_fullscreen(new_value).inspect_err(|_| tracelog!(Warning, "SDL: Failed to find selected monitor"))?;
-    core.window.flags.set(ConfigFlags::FullscreenMode, new_value);
-    core.window.fullscreen = new_value;
+    let display = platform.window.get_display().inspect_err(|_| tracelog!(Warning, "SDL: Failed to find selected monitor"))?;
+
+    if core.window.is_fullscreen() {
+        platform.window.set_fullscreen(false)?;
+        platform.window.set_display_mode(None)?;
+
+        let desktop = to_video_mode(&display.get_mode()?);
+        core.window.display = Size { width: desktop.width, height: desktop.height };
+    } else {
+        let Size { width, height } = core.window.screen;
+        let modes = display.get_fullscreen_modes()?;
+        let closest = closest_video_mode(&modes.iter().map(to_video_mode).collect::<Vec<_>>(), VideoMode::new(width, height, 0));
+        let display_mode = closest.and_then(|closest| modes.into_iter().find(|mode| to_video_mode(mode) == closest));
+
+        match closest {
+            Some(mode) if (mode.width, mode.height) == (width, height) => {}
+            Some(mode) => tracelog!(Warning, "DISPLAY: No video mode matches screen size ({width}x{height}), using closest: {}x{}", mode.width, mode.height),
+            None => tracelog!(Warning, "DISPLAY: No video mode matches screen size ({width}x{height}), using desktop resolution"),
+        }
+
+        let mode = to_video_mode(&match display_mode {
+            Some(mode) => mode,
+            None => display.get_mode()?,
+        });
+        platform.window.set_display_mode(display_mode)?;
+        platform.window.set_fullscreen(true)?;
+        core.window.display = Size { width: mode.width, height: mode.height };
+    }
+
+    core.window.toggle_fullscreen();
     Ok(())
 }
 
-/// Toggle borderless windowed mode
+/// Toggle borderless windowed mode, window covers the monitor it is currently on
+/// NOTE: Window position and size are restored on exit, borderless windowed is not fullscreen
+/// # Errors
+/// Returns an error if fullscreen can not be left or the current monitor can not be queried
 pub fn toggle_borderless_windowed(core: &mut Core, platform: &mut Platform) -> Result<(), SdlError> {
-    let new_value = !core.window.flags.contains(ConfigFlags::BorderlessWindowedMode);
-    platform.window.set_fullscreen(new_value).inspect_err(|_| tracelog!(Warning, "SDL: Failed to find selected monitor"))?;
-    platform.window.set_bordered(new_value).inspect_err(|_| tracelog!(Warning, "SDL: Failed to find selected monitor"))?;
-    core.window.flags.set(ConfigFlags::BorderlessWindowedMode, new_value);
-    core.window.fullscreen = new_value;
+    // Leave fullscreen before attempting to set borderless windowed mode
+    if core.window.is_fullscreen() {
+        toggle_fullscreen(core, platform)?;
+    }
+
+    if core.window.is_state(ConfigFlags::BorderlessWindowedMode) {
+        let (position, screen) = (core.window.previous_position, core.window.previous_screen);
+        core.window.toggle_borderless_windowed();
+
+        // Re-apply border, unless the window was created undecorated
+        if !platform.window.set_bordered(!core.window.is_state(ConfigFlags::WindowUndecorated)) {
+            tracelog!(Warning, "SDL: Failed to restore window decoration");
+        }
+        set_window_size(core, platform, screen.width, screen.height);
+        set_window_position(core, platform, position.x, position.y);
+    } else {
+        let bounds = platform.window.get_display()
+            .and_then(|display| display.get_bounds())
+            .inspect_err(|_| tracelog!(Warning, "SDL: Failed to find selected monitor"))?;
+
+        // Save current geometry, read from the platform window in case events are pending
+        let (x, y) = platform.window.position();
+        core.window.set_position(x, y);
+        core.window.toggle_borderless_windowed();
+
+        if !platform.window.set_bordered(false) {
+            tracelog!(Warning, "SDL: Failed to remove window decoration");
+        }
+        set_window_position(core, platform, bounds.x(), bounds.y());
+        set_window_size(core, platform, bounds.width(), bounds.height());
+    }
     Ok(())
 }
 
-pub fn maximize_window() {
-    todo!()
+/// Set vertical synchronization mode
+/// NOTE: Adaptive mode falls back to regular V-Sync if not supported by the driver.
+/// When a target FPS is also set, the stricter of both limits wins: V-Sync blocks
+/// on buffer swap and frame pacing only waits for whatever time is left over
+///
+/// # Errors
+/// Returns an error if SDL fails to set the swap interval
+pub fn set_vsync(core: &mut Core, platform: &mut Platform, mode: VsyncMode) -> Result<(), SdlError> {
+    let video = &platform.video_subsystem;
+    apply_vsync(&mut core.window.flags, mode, |mode| video.gl_set_swap_interval(match mode {
+        VsyncMode::Off => SwapInterval::Immediate,
+        VsyncMode::On => SwapInterval::VSync,
+        VsyncMode::Adaptive => SwapInterval::LateSwapTearing,
+    }))
+    .inspect_err(|_| tracelog!(Warning, "DISPLAY: Failed to set V-Sync mode"))?;
+    Ok(())
 }
-pub fn minimize_window() {
-    todo!()
+
+/// Get vertical synchronization mode, as reported by the driver
+#[must_use]
+pub fn get_vsync(platform: &Platform) -> VsyncMode {
+    match platform.video_subsystem.gl_get_swap_interval() {
+        Ok(SwapInterval::Immediate) => VsyncMode::Off,
+        Ok(SwapInterval::VSync) => VsyncMode::On,
+        Ok(SwapInterval::LateSwapTearing) => VsyncMode::Adaptive,
+        Err(e) => {
+            tracelog!(Warning, "DISPLAY: Failed to get V-Sync mode [ERROR: {e}]");
+            VsyncMode::Off
+        }
+    }
 }
-pub fn restore_window() {
-    todo!()
+
+/// Get SDL display for a monitor index
+fn get_display(platform: &Platform, monitor: MonitorID) -> Result<Display, VideoModeError> {
+    platform.video_subsystem.displays()?
+        .get(monitor)
+        .copied()
+        .ok_or(VideoModeError::InvalidMonitor(monitor))
 }
 
-pub fn set_window_state(flags: ConfigFlags) {
-    todo!()
+/// Convert SDL display mode, rounding refresh rate to the nearest Hz
+#[allow(clippy::cast_sign_loss)]
+fn to_video_mode(mode: &DisplayMode) -> VideoMode {
+    VideoMode {
+        width: mode.w.max(0) as u32,
+        height: mode.h.max(0) as u32,
+        refresh_rate: mode.refresh_rate.round().max(0.0) as u32,
+    }
 }
-pub fn clear_window_state(flags: ConfigFlags) {
-    todo!()
+
+/// Get fullscreen video modes supported by a monitor, deduplicated and sorted
+/// # Errors
+/// Returns an error if the monitor is not connected or SDL fails to query its modes
+pub fn get_monitor_video_modes(platform: &Platform, monitor: MonitorID) -> Result<Vec<VideoMode>, VideoModeError> {
+    let mut modes: Vec<VideoMode> = get_display(platform, monitor)?
+        .get_fullscreen_modes()?
+        .iter()
+        .map(to_video_mode)
+        .collect();
+    modes.sort_unstable();
+    modes.dedup();
+    Ok(modes)
 }
 
-pub fn set_window_icon(image: &Image) {
-    todo!()
+/// Get the monitor video mode closest to the desired one, see [`closest_video_mode`]
+/// # Errors
+/// Returns an error if the monitor is not connected or SDL fails to query its modes
+pub fn get_closest_video_mode(platform: &Platform, monitor: MonitorID, desired: VideoMode) -> Result<Option<VideoMode>, VideoModeError> {
+    Ok(closest_video_mode(&get_monitor_video_modes(platform, monitor)?, desired))
 }
-pub fn set_window_icons(images: &[Image]) {
-    todo!()
+
+/// Switch window to exclusive fullscreen on a monitor, using a specific video mode
+/// NOTE: Mode must be one of the modes returned by [`get_monitor_video_modes`], SDL is never left to guess
+/// # Errors
+/// Returns an error if the monitor is not connected, the mode is not supported or SDL fails to switch
+pub fn set_fullscreen_mode(core: &mut Core, platform: &mut Platform, monitor: MonitorID, mode: VideoMode) -> Result<(), VideoModeError> {
+    let display = get_display(platform, monitor)?;
+    let display_mode = display.get_fullscreen_modes()?
+        .into_iter()
+        .find(|display_mode| to_video_mode(display_mode) == mode)
+        .ok_or_else(|| {
+            tracelog!(Warning, "DISPLAY: Video mode {}x{} @ {}Hz not supported by monitor {monitor}", mode.width, mode.height, mode.refresh_rate);
+            VideoModeError::InvalidMode(mode)
+        })?;
+
+    platform.window.set_display_mode(display_mode)?;
+    platform.window.set_fullscreen(true)?;
+
+    core.window.flags.insert(ConfigFlags::FullscreenMode);
+    core.window.display = Size { width: mode.width, height: mode.height };
+    core.setup_framebuffer();
+
+    tracelog!(Info, "DISPLAY: Fullscreen mode set: {}x{} @ {}Hz", mode.width, mode.height, mode.refresh_rate);
+    Ok(())
 }
-pub fn set_window_title(title: &str) {
-    todo!()
+
+/// Set window state: maximized, if resizable
+pub fn maximize_window(core: &mut Core, platform: &mut Platform) {
+    if !core.window.flags.contains(ConfigFlags::WindowResizable) {
+        return;
+    }
+    if platform.window.maximize() {
+        core.window.maximize();
+    } else {
+        tracelog!(Warning, "SDL: Failed to maximize window");
+    }
 }
-pub fn set_window_position(x: u32, y: u32) {
-    todo!()
+
+/// Set window state: minimized, if resizable
+pub fn minimize_window(core: &mut Core, platform: &mut Platform) {
+    if !core.window.flags.contains(ConfigFlags::WindowResizable) {
+        return;
+    }
+    if platform.window.minimize() {
+        core.window.minimize();
+    } else {
+        tracelog!(Warning, "SDL: Failed to minimize window");
+    }
+}
+
+/// Set window state: not minimized/maximized
+pub fn restore_window(core: &mut Core, platform: &mut Platform) {
+    if platform.window.restore() {
+        core.window.restore();
+    } else {
+        tracelog!(Warning, "SDL: Failed to restore window");
+    }
+}
+
+/// Window flags that can only be configured before window initialization
+const INIT_ONLY_FLAGS: ConfigFlags = ConfigFlags::WindowTransparent
+    .union(ConfigFlags::WindowHighdpi)
+    .union(ConfigFlags::MSAA4xHint)
+    .union(ConfigFlags::InterlacedHint);
+
+/// Window flags that SDL bindings can not change on a live window
+const UNSUPPORTED_RUNTIME_FLAGS: ConfigFlags = ConfigFlags::WindowResizable
+    .union(ConfigFlags::WindowTopmost)
+    .union(ConfigFlags::WindowMousePassthrough);
+
+/// Warn about window flags that can not be changed at runtime
+fn warn_runtime_flags(function: &str, flags: ConfigFlags) {
+    for (name, _) in (flags & INIT_ONLY_FLAGS).iter_names() {
+        tracelog!(Warning, "WINDOW: {function}() - {name} can only be configured before window initialization");
+    }
+    for (name, _) in (flags & UNSUPPORTED_RUNTIME_FLAGS).iter_names() {
+        tracelog!(Warning, "WINDOW: {function}() - {name} is not supported at runtime on SDL platform");
+    }
+}
+
+/// Set window configuration state using flags
+/// NOTE: Flags that can not change at runtime are ignored with a warning,
+/// so [`Window::is_state`](crate::prelude::Window::is_state) keeps reflecting the platform window
+pub fn set_window_state(core: &mut Core, platform: &mut Platform, flags: ConfigFlags) {
+    if flags.contains(ConfigFlags::VsyncHint) {
+        _ = set_vsync(core, platform, VsyncMode::On);
+    }
+    if flags.contains(ConfigFlags::FullscreenMode) && !core.window.is_state(ConfigFlags::FullscreenMode) {
+        _ = toggle_fullscreen(core, platform);
+    }
+    if flags.contains(ConfigFlags::WindowUndecorated) {
+        if platform.window.set_bordered(false) {
+            core.window.set_state(ConfigFlags::WindowUndecorated);
+        } else {
+            tracelog!(Warning, "SDL: Failed to set window undecorated");
+        }
+    }
+    if flags.contains(ConfigFlags::WindowHidden) {
+        if platform.window.hide() {
+            core.window.set_state(ConfigFlags::WindowHidden);
+        } else {
+            tracelog!(Warning, "SDL: Failed to hide window");
+        }
+    }
+    if flags.contains(ConfigFlags::WindowMinimized) {
+        minimize_window(core, platform);
+    }
+    if flags.contains(ConfigFlags::WindowMaximized) {
+        maximize_window(core, platform);
+    }
+    if flags.contains(ConfigFlags::WindowUnfocused) {
+        tracelog!(Warning, "WINDOW: set_window_state() - WindowUnfocused is not supported on SDL platform");
+    }
+    if flags.contains(ConfigFlags::WindowAlwaysRun) {
+        core.window.set_state(ConfigFlags::WindowAlwaysRun);
+    }
+    if flags.contains(ConfigFlags::BorderlessWindowedMode) && !core.window.is_state(ConfigFlags::BorderlessWindowedMode) {
+        _ = toggle_borderless_windowed(core, platform);
+    }
+    warn_runtime_flags("set_window_state", flags);
+}
+
+/// Clear window configuration state flags
+/// NOTE: Flags that can not change at runtime are ignored with a warning,
+/// so [`Window::is_state`](crate::prelude::Window::is_state) keeps reflecting the platform window
+pub fn clear_window_state(core: &mut Core, platform: &mut Platform, flags: ConfigFlags) {
+    if flags.contains(ConfigFlags::VsyncHint) {
+        _ = set_vsync(core, platform, VsyncMode::Off);
+    }
+    if flags.contains(ConfigFlags::FullscreenMode) && core.window.is_state(ConfigFlags::FullscreenMode) {
+        _ = toggle_fullscreen(core, platform);
+    }
+    if flags.contains(ConfigFlags::WindowUndecorated) {
+        if platform.window.set_bordered(true) {
+            core.window.clear_state(ConfigFlags::WindowUndecorated);
+        } else {
+            tracelog!(Warning, "SDL: Failed to set window decorated");
+        }
+    }
+    if flags.contains(ConfigFlags::WindowHidden) {
+        if platform.window.show() {
+            core.window.clear_state(ConfigFlags::WindowHidden);
+        } else {
+            tracelog!(Warning, "SDL: Failed to show window");
+        }
+    }
+    if flags.intersects(ConfigFlags::WindowMinimized | ConfigFlags::WindowMaximized) {
+        restore_window(core, platform);
+    }
+    if flags.contains(ConfigFlags::WindowUnfocused) {
+        set_window_focused(core, platform);
+    }
+    if flags.contains(ConfigFlags::WindowAlwaysRun) {
+        core.window.clear_state(ConfigFlags::WindowAlwaysRun);
+    }
+    if flags.contains(ConfigFlags::BorderlessWindowedMode) && core.window.is_state(ConfigFlags::BorderlessWindowedMode) {
+        _ = toggle_borderless_windowed(core, platform);
+    }
+    warn_runtime_flags("clear_window_state", flags);
+}
+
+/// Set icon for window (single image, converted to RGBA 32bit)
+/// NOTE: Compressed and floating point formats are not supported
+pub fn set_window_icon(platform: &mut Platform, image: &Image) {
+    let Some(mut pixels) = image.to_rgba8() else {
+        tracelog!(Warning, "WINDOW: Icon image format not supported: {:?}", image.format);
+        return;
+    };
+    let (Ok(width), Ok(height)) = (u32::try_from(image.width), u32::try_from(image.height)) else {
+        tracelog!(Warning, "WINDOW: Icon image too large");
+        return;
+    };
+
+    // Byte order R, G, B, A whatever the platform endianness
+    let masks = PixelMasks {
+        bpp: 32,
+        rmask: u32::from_ne_bytes([0xFF, 0, 0, 0]),
+        gmask: u32::from_ne_bytes([0, 0xFF, 0, 0]),
+        bmask: u32::from_ne_bytes([0, 0, 0xFF, 0]),
+        amask: u32::from_ne_bytes([0, 0, 0, 0xFF]),
+    };
+    match Surface::from_data_pixelmasks(&mut pixels, width, height, width * 4, &masks) {
+        Ok(surface) => if !platform.window.set_icon(surface) {
+            tracelog!(Warning, "SDL: Failed to set window icon");
+        },
+        Err(e) => tracelog!(Warning, "SDL: Failed to create window icon surface [ERROR: {e}]"),
+    };
+}
+
+/// Set icon for window (multiple images, converted to RGBA 32bit)
+/// NOTE: SDL accepts a single icon, the biggest image is used
+pub fn set_window_icons(platform: &mut Platform, images: &[Image]) {
+    let Some(image) = images.iter().max_by_key(|image| image.width * image.height) else {
+        tracelog!(Warning, "WINDOW: No icon images provided");
+        return;
+    };
+    set_window_icon(platform, image);
+}
+/// Set title for window
+/// NOTE: An empty title leaves the platform window title untouched
+///
+/// # Errors
+/// Returns an error if the title contains a nul character
+pub fn set_window_title(core: &mut Core, platform: &mut Platform, title: &str) -> Result<(), NulError> {
+    if core.window.title == title {
+        return Ok(());
+    }
+    if !title.is_empty() {
+        platform.window.set_title(title)?;
+    }
+    core.window.set_title(title);
+    Ok(())
+}
+/// Set window position on screen
+/// NOTE: Position can be negative on multi-monitor setups
+pub fn set_window_position(core: &mut Core, platform: &mut Platform, x: i32, y: i32) {
+    if platform.window.set_position(WindowPos::Positioned(x), WindowPos::Positioned(y)) {
+        core.window.set_position(x, y);
+    } else {
+        tracelog!(Warning, "SDL: Failed to set window position");
+    }
 }
 pub fn set_window_monitor(monitor: sdl3::sys::video::SDL_DisplayID) {
     todo!()
 }
-pub fn set_window_min_size(width: u32, height: u32) {
-    todo!()
+/// Set window minimum dimensions (for resizable window), 0 for unconstrained
+pub fn set_window_min_size(core: &mut Core, platform: &mut Platform, width: u32, height: u32) {
+    match platform.window.set_minimum_size(width, height) {
+        Ok(()) => core.window.set_min_size(width, height),
+        Err(e) => tracelog!(Warning, "SDL: Failed to set window minimum size [ERROR: {e}]"),
+    }
 }
-pub fn set_window_max_size(width: u32, height: u32) {
-    todo!()
+
+/// Set window maximum dimensions (for resizable window), 0 for unconstrained
+pub fn set_window_max_size(core: &mut Core, platform: &mut Platform, width: u32, height: u32) {
+    match platform.window.set_maximum_size(width, height) {
+        Ok(()) => core.window.set_max_size(width, height),
+        Err(e) => tracelog!(Warning, "SDL: Failed to set window maximum size [ERROR: {e}]"),
+    }
 }
-pub fn set_window_size(width: u32, height: u32) {
-    todo!()
+
+/// Set window dimensions, [`Window::is_resized`](crate::prelude::Window::is_resized) reports it like a user resize
+pub fn set_window_size(core: &mut Core, platform: &mut Platform, width: u32, height: u32) {
+    match platform.window.set_size(width, height) {
+        Ok(()) => {
+            core.window.set_size(width, height);
+            update_dpi_scale(core, platform);
+        }
+        Err(e) => tracelog!(Warning, "SDL: Failed to set window size [ERROR: {e}]"),
+    }
 }
-pub fn set_window_opacity(opacity: f32) {
-    todo!()
+/// Set window opacity [0.0f..1.0f], clamped
+/// NOTE: Value is stored even if the platform does not support opacity, see [`Window::window_opacity`](crate::prelude::Window::window_opacity)
+pub fn set_window_opacity(core: &mut Core, platform: &mut Platform, opacity: Percent) {
+    core.window.set_opacity(opacity);
+    if let Err(e) = platform.window.set_opacity(core.window.window_opacity()) {
+        if !platform.opacity_warned {
+            tracelog!(Warning, "SDL: Window opacity not supported [ERROR: {e}]");
+            platform.opacity_warned = true;
+        }
+    }
 }
-pub fn set_window_focused() {
-    todo!()
+
+/// Set window focused, raising it above other windows
+pub fn set_window_focused(core: &mut Core, platform: &mut Platform) {
+    if platform.window.raise() {
+        core.window.set_focused();
+    } else {
+        tracelog!(Warning, "SDL: Failed to focus window");
+    }
 }
-pub fn get_window_handle() -> *mut std::ffi::c_void {
-    todo!()
+/// Get native window handle, the `SDL_Window` pointer on SDL platform
+/// NOTE: OS handles (HWND, X11 Window, Wayland surface...) are available through the SDL window properties
+#[must_use]
+pub fn get_window_handle(platform: &Platform) -> *mut std::ffi::c_void {
+    platform.window.raw().cast()
 }
-pub fn get_window_position() -> Vector2 {
-    todo!()
+
+/// Get window position XY on monitor
+#[must_use]
+pub fn get_window_position(platform: &Platform) -> Point {
+    let (x, y) = platform.window.position();
+    Point { x, y }
 }
-pub fn get_window_scale_dpi() -> Vector2 {
-    todo!()
+
+/// Get window scale DPI factor, ratio between framebuffer pixels and window size
+pub fn get_window_scale_dpi(platform: &Platform) -> Vector2 {
+    window_scale_dpi(&platform.window)
 }
 
-pub fn get_monitor_count() -> usize {
-    todo!()
+#[allow(clippy::cast_precision_loss)]
+fn window_scale_dpi(window: &SdlWindow) -> Vector2 {
+    let (width, height) = window.size();
+    let (pixel_width, pixel_height) = window.size_in_pixels();
+    if width == 0 || height == 0 {
+        return Vector2::new(1.0, 1.0);
+    }
+    Vector2::new(pixel_width as f32 / width as f32, pixel_height as f32 / height as f32)
 }
-pub fn get_current_monitor() -> sdl3::sys::video::SDL_DisplayID {
-    todo!()
+
+/// Update window DPI scale and render scaling from the platform window
+fn update_dpi_scale(core: &mut Core, platform: &Platform) {
+    core.window.dpi_scale = window_scale_dpi(&platform.window);
+    core.update_render_scale();
 }
-pub fn get_monitor_width(monitor: sdl3::sys::video::SDL_DisplayID) -> u32 {
-    todo!()
+
+/// Query a monitor, logging a warning and returning the default value on failure
+fn query_monitor<T: Default>(platform: &Platform, monitor: MonitorID, query: impl FnOnce(&Display) -> Result<T, SdlError>) -> T {
+    get_display(platform, monitor)
+        .and_then(|display| Ok(query(&display)?))
+        .unwrap_or_else(|e| {
+            tracelog!(Warning, "DISPLAY: Failed to query monitor {monitor} [ERROR: {e}]");
+            T::default()
+        })
 }
-pub fn get_monitor_height(monitor: sdl3::sys::video::SDL_DisplayID) -> u32 {
-    todo!()
+
+/// Get number of connected monitors
+#[must_use]
+pub fn get_monitor_count(platform: &Platform) -> usize {
+    platform.video_subsystem.displays().map_or_else(
+        |e| {
+            tracelog!(Warning, "DISPLAY: Failed to get monitor count [ERROR: {e}]");
+            0
+        },
+        |displays| displays.len(),
+    )
 }
-pub fn get_monitor_physical_width(monitor: sdl3::sys::video::SDL_DisplayID) -> u32 {
-    todo!()
+
+/// Get current monitor where window is placed
+/// NOTE: Monitor containing the window center, or closest to it
+#[must_use]
+pub fn get_current_monitor(platform: &Platform) -> MonitorID {
+    let Ok(displays) = platform.video_subsystem.displays() else {
+        tracelog!(Warning, "DISPLAY: Failed to find monitors");
+        return 0;
+    };
+    let (x, y) = platform.window.position();
+    let (width, height) = platform.window.size();
+    let center = (
+        i64::from(x) + i64::from(width / 2),
+        i64::from(y) + i64::from(height / 2),
+    );
+
+    displays.iter()
+        .enumerate()
+        .filter_map(|(monitor, display)| display.get_bounds().ok().map(|bounds| (monitor, bounds)))
+        .min_by_key(|(_, bounds)| {
+            // Distance from window center to monitor bounds, zero if inside
+            let left = i64::from(bounds.x());
+            let top = i64::from(bounds.y());
+            let dx = (left - center.0).max(center.0 - (left + i64::from(bounds.width()) - 1)).max(0);
+            let dy = (top - center.1).max(center.1 - (top + i64::from(bounds.height()) - 1)).max(0);
+            dx * dx + dy * dy
+        })
+        .map_or(0, |(monitor, _)| monitor)
 }
-pub fn get_monitor_physical_height(monitor: sdl3::sys::video::SDL_DisplayID) -> u32 {
-    todo!()
+
+/// Get specified monitor width (current video mode used by monitor)
+#[must_use]
+#[allow(clippy::cast_sign_loss)]
+pub fn get_monitor_width(platform: &Platform, monitor: MonitorID) -> u32 {
+    query_monitor(platform, monitor, |display| Ok(display.get_mode()?.w.max(0) as u32))
 }
-pub fn get_monitor_refresh_rate(monitor: sdl3::sys::video::SDL_DisplayID) -> u32 {
-    todo!()
+
+/// Get specified monitor height (current video mode used by monitor)
+#[must_use]
+#[allow(clippy::cast_sign_loss)]
+pub fn get_monitor_height(platform: &Platform, monitor: MonitorID) -> u32 {
+    query_monitor(platform, monitor, |display| Ok(display.get_mode()?.h.max(0) as u32))
 }
-pub fn get_monitor_position(monitor: sdl3::sys::video::SDL_DisplayID) -> Vector2 {
-    todo!()
+
+/// Estimate monitor physical size in millimetres from its pixel size and content scale
+/// NOTE: SDL3 does not report physical sizes, 96 DPI is assumed at scale 1.0
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+fn physical_size_mm(pixels: i32, content_scale: f32) -> u32 {
+    const MM_PER_INCH: f32 = 25.4;
+    (pixels.max(0) as f32 / (96.0 * content_scale.max(f32::EPSILON)) * MM_PER_INCH).round() as u32
 }
-pub fn get_monitor_name(monitor: sdl3::sys::video::SDL_DisplayID) -> String {
-    todo!()
+
+/// Get specified monitor physical width in millimetres (estimated)
+#[must_use]
+pub fn get_monitor_physical_width(platform: &Platform, monitor: MonitorID) -> u32 {
+    query_monitor(platform, monitor, |display| Ok(physical_size_mm(display.get_mode()?.w, display.get_content_scale()?)))
 }
 
-pub fn set_clipboard_text(text: &'static str) {
-    todo!()
+/// Get specified monitor physical height in millimetres (estimated)
+#[must_use]
+pub fn get_monitor_physical_height(platform: &Platform, monitor: MonitorID) -> u32 {
+    query_monitor(platform, monitor, |display| Ok(physical_size_mm(display.get_mode()?.h, display.get_content_scale()?)))
 }
-pub fn get_clipboard_text() -> String {
-    todo!()
+
+/// Get specified monitor refresh rate
+#[must_use]
+pub fn get_monitor_refresh_rate(platform: &Platform, monitor: MonitorID) -> u32 {
+    query_monitor(platform, monitor, |display| Ok(to_video_mode(&display.get_mode()?).refresh_rate))
 }
 
-pub fn show_cursor() {
-    todo!()
+/// Get specified monitor position
+#[allow(clippy::cast_precision_loss)]
+pub fn get_monitor_position(platform: &Platform, monitor: MonitorID) -> Vector2 {
+    query_monitor(platform, monitor, |display| {
+        let bounds = display.get_bounds()?;
+        Ok(Vector2::new(bounds.x() as f32, bounds.y() as f32))
+    })
 }
-pub fn hide_cursor() {
-    todo!()
+
+/// Get the human-readable, UTF-8 encoded name of the specified monitor
+pub fn get_monitor_name(platform: &Platform, monitor: MonitorID) -> String {
+    query_monitor(platform, monitor, Display::get_name)
 }
-pub fn enable_cursor() {
-    todo!()
+
+/// Set clipboard text content
+/// NOTE: Text is cut at the first nul character, like a C string
+pub fn set_clipboard_text(platform: &Platform, text: &str) {
+    let text = text.split('\0').next().unwrap_or_default();
+    if let Err(e) = platform.video_subsystem.clipboard().set_clipboard_text(text) {
+        tracelog!(Warning, "SDL: Failed to set clipboard text [ERROR: {e}]");
+    }
 }
-pub fn disable_cursor() {
-    todo!()
+
+/// Get OpenGL function address, to load an OpenGL backend for [`set_gl_api`](crate::rlgl::set_gl_api)
+/// NOTE: Requires the OpenGL context created on window initialization, `None` if not found
+#[must_use]
+pub fn get_gl_proc_address(platform: &Platform, name: &str) -> Option<unsafe extern "C" fn()> {
+    platform.video_subsystem.gl_get_proc_address(name)
+}
+
+/// Get clipboard text content, truncated to `MAX_CLIPBOARD_BUFFER_LENGTH` bytes
+/// NOTE: SDL converts clipboard text to UTF-8, empty if the clipboard has no text
+#[must_use]
+pub fn get_clipboard_text(platform: &Platform) -> String {
+    let clipboard = platform.video_subsystem.clipboard();
+    if !clipboard.has_clipboard_text() {
+        return String::new();
+    }
+
+    let mut text = clipboard.clipboard_text().unwrap_or_else(|e| {
+        tracelog!(Warning, "SDL: Failed to get clipboard text [ERROR: {e}]");
+        String::new()
+    });
+    if text.len() > MAX_CLIPBOARD_BUFFER_LENGTH {
+        // Cut on a char boundary, never in the middle of a UTF-8 sequence
+        let end = (0..=MAX_CLIPBOARD_BUFFER_LENGTH).rev()
+            .find(|&i| text.is_char_boundary(i))
+            .unwrap_or(0);
+        tracelog!(Warning, "SDL: Clipboard text truncated from {} to {end} bytes", text.len());
+        text.truncate(end);
+    }
+    text
+}
+
+/// Get clipboard image content, `None` if the clipboard has no image
+/// NOTE: SDL bindings only expose clipboard text for now, image payloads (decoded with
+/// [`Image::from_clipboard_data`]) can not be read and `None` is always returned
+#[cfg(feature = "support_clipboard_image")]
+#[must_use]
+pub fn get_clipboard_image(_platform: &Platform) -> Option<Image> {
+    tracelog!(Debug, "SDL: Clipboard image data not available");
+    None
+}
+
+/// Shows cursor
+pub fn show_cursor(core: &mut Core, platform: &Platform) {
+    platform.sdl_context.mouse().show_cursor(true);
+    core.input.mouse.is_cursor_hidden = false;
+}
+
+/// Hides cursor
+pub fn hide_cursor(core: &mut Core, platform: &Platform) {
+    platform.sdl_context.mouse().show_cursor(false);
+    core.input.mouse.is_cursor_hidden = true;
+}
+
+/// Set mouse cursor
+/// NOTE: Cursors are created once and cached, switching cursors every frame does not allocate
+pub fn set_mouse_cursor(core: &mut Core, platform: &mut Platform, cursor: MouseCursor) {
+    let index = cursor as usize;
+    let Some(slot) = platform.cursors.get_mut(index) else { return };
+    if slot.is_none() {
+        match SdlCursor::from_system(CURSORS_LUT[index]) {
+            Ok(system_cursor) => *slot = Some(system_cursor),
+            Err(e) => {
+                tracelog!(Warning, "SDL: Failed to create mouse cursor {cursor:?} [ERROR: {e}]");
+                return;
+            }
+        }
+    }
+    if let Some(system_cursor) = slot {
+        system_cursor.set();
+    }
+    core.input.mouse.cursor = cursor;
+}
+
+/// Get current mouse cursor
+#[inline]
+#[must_use]
+pub fn get_mouse_cursor(core: &Core) -> MouseCursor {
+    core.input.mouse.cursor
+}
+
+/// Enables cursor (unlock cursor), cursor is moved to the screen center
+pub fn enable_cursor(core: &mut Core, platform: &mut Platform) {
+    let mouse = platform.sdl_context.mouse();
+    if platform.cursor_relative {
+        mouse.set_relative_mouse_mode(&platform.window, false);
+        platform.cursor_relative = false;
+
+        #[allow(clippy::cast_precision_loss)]
+        let center = Vector2::new(core.window.screen.width as f32 / 2.0, core.window.screen.height as f32 / 2.0);
+        mouse.warp_mouse_in_window(&platform.window, center.x, center.y);
+        core.input.mouse.reset_position(center);
+    }
+    mouse.show_cursor(true);
+    core.input.mouse.is_cursor_hidden = false;
+}
+
+/// Disables cursor (lock cursor), mouse motion is reported as relative deltas
+/// NOTE: Useful for first person cameras, calling it again while disabled does nothing
+pub fn disable_cursor(core: &mut Core, platform: &mut Platform) {
+    if platform.cursor_relative {
+        return;
+    }
+    platform.sdl_context.mouse().set_relative_mouse_mode(&platform.window, true);
+    platform.cursor_relative = true;
+    core.input.mouse.is_cursor_hidden = true;
+}
+
+/// Set mouse position XY, moving the platform cursor too
+/// NOTE: Position is in window coordinates, mouse offset and scale are not applied
+pub fn set_mouse_position(core: &mut Core, platform: &mut Platform, position: Position2) {
+    platform.sdl_context.mouse().warp_mouse_in_window(&platform.window, position.x, position.y);
+    core.input.mouse.reset_position(position);
+}
+
+/// Set gamepad vibration for both motors [0.0f..1.0f], duration in seconds
+/// NOTE: Duration is clamped to `MAX_GAMEPAD_VIBRATION_TIME`, zero duration stops vibration
+///
+/// # Errors
+/// Returns an error if the gamepad is not available or does not support rumble
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn set_gamepad_vibration(core: &Core, platform: &mut Platform, gamepad: GamepadID, left_motor: Percent, right_motor: Percent, duration: Seconds) -> Result<(), GamepadError> {
+    let sdl_gamepad = platform.gamepad.get_mut(gamepad)
+        .and_then(Option::as_mut)
+        .filter(|_| core.input.gamepad.is_gamepad_available(gamepad))
+        .ok_or(GamepadError::NotAvailable(gamepad))?;
+
+    let (low, high, duration_ms) = if duration > 0.0 {
+        (
+            (left_motor.clamp(0.0, 1.0)*65535.0) as u16,
+            (right_motor.clamp(0.0, 1.0)*65535.0) as u16,
+            (duration.min(MAX_GAMEPAD_VIBRATION_TIME)*1000.0) as u32,
+        )
+    } else {
+        (0, 0, 0)
+    };
+
+    sdl_gamepad.set_rumble(low, high, duration_ms)
+        .inspect_err(|e| tracelog!(Warning, "GAMEPAD: [{gamepad}] Vibration not supported [ERROR: {e}]"))?;
+    Ok(())
+}
+
+/// Set internal gamepad mappings (`SDL_GameControllerDB` text format), returns the number of mappings added or updated
+/// NOTE: Empty lines, comments (#) and mappings for other platforms are skipped.
+/// Gamepads connected after the call use the new mappings
+///
+/// # Errors
+/// Returns an error on the first mapping rejected by SDL, mappings before it are kept
+pub fn set_gamepad_mappings(platform: &mut Platform, mappings: &str) -> Result<usize, GamepadError> {
+    let current_platform = format!("platform:{},", sdl3::get_platform());
+    let mut count = 0;
+    for (index, line) in mappings.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.contains("platform:") && !line.contains(&current_platform) {
+            continue;
+        }
+        platform.gamepad_subsystem.add_mapping(line)
+            .map_err(|error| GamepadError::InvalidMapping { line: index + 1, error })
+            .inspect_err(|e| tracelog!(Warning, "GAMEPAD: Failed to set mappings [ERROR: {e}]"))?;
+        count += 1;
+    }
+    tracelog!(Info, "GAMEPAD: {count} mappings set");
+    Ok(count)
+}
+
+/// Load internal gamepad mappings from a file (`SDL_GameControllerDB` text format, i.e. `gamecontrollerdb.txt`)
+///
+/// # Errors
+/// Returns an error if the file can not be read or a mapping is rejected by SDL, see [`set_gamepad_mappings`]
+pub fn load_gamepad_mappings_from_file(platform: &mut Platform, path: impl AsRef<Path>) -> Result<usize, GamepadError> {
+    let mappings = std::fs::read_to_string(path)?;
+    set_gamepad_mappings(platform, &mappings)
+}
+
+/// Register all input events
+/// NOTE: Previous input state is registered once per frame, with event waiting enabled
+/// it blocks until at least one event is received (or the target frame time passes, with timeout)
+pub fn poll_input_events(core: &mut Core, platform: &mut Platform) {
+    if !core.input.frame_started {
+        // Reset window resize flag, set again by resize events
+        core.window.resized_last_frame = false;
+    }
+    core.input.begin_frame();
+
+    // NOTE: Blocking until an event arrives keeps CPU usage near zero while idle
+    let mut next = match core.window.event_waiting {
+        EventWaiting::Disabled => platform.event_pump.poll_event(),
+        EventWaiting::TargetFrameTime if core.time.target > 0.0 => {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let timeout = (core.time.target*1000.0).ceil() as u32;
+            platform.event_pump.wait_event_timeout(timeout)
+        }
+        EventWaiting::Indefinite | EventWaiting::TargetFrameTime => Some(platform.event_pump.wait_event()),
+    };
+    while let Some(event) = next {
+        handle_event(core, platform, event);
+        next = platform.event_pump.poll_event();
+    }
+
+    core.run_hooks(HookStage::PreUpdate);
+}
+
+/// Register a single SDL event into core input and window state
+#[allow(clippy::too_many_lines)]
+fn handle_event(core: &mut Core, platform: &mut Platform, event: Event) {
+    match event {
+        Event::Quit { .. } => core.window.should_close = true,
+
+        // Dropped files are accumulated until loaded, one event per file
+        Event::DropFile { filename, .. } => {
+            if core.window.drop_filepaths.len() < MAX_FILEPATH_CAPACITY {
+                core.window.drop_filepaths.push(PathBuf::from(filename));
+            } else {
+                tracelog!(Warning, "FILEIO: Maximum drag and drop files at once is limited to {MAX_FILEPATH_CAPACITY} files!");
+            }
+        }
+
+        // Window events are also polled (minimized, maximized, close...)
+        Event::Window { win_event, .. } => match win_event {
+            WindowEvent::Resized(width, height) => {
+                if let (Ok(width), Ok(height)) = (u32::try_from(width), u32::try_from(height)) {
+                    // NOTE: Render size, viewport and projection are updated with the DPI scale
+                    core.window.resize(width, height);
+                    update_dpi_scale(core, platform);
+                }
+            }
+            WindowEvent::Moved(x, y) => core.window.set_position(x, y),
+            // Window moved to a monitor with a different pixel density
+            WindowEvent::PixelSizeChanged(..) => update_dpi_scale(core, platform),
+            WindowEvent::Minimized => core.window.flags.insert(ConfigFlags::WindowMinimized),
+            WindowEvent::Maximized => {
+                core.window.flags.insert(ConfigFlags::WindowMaximized);
+                core.window.flags.remove(ConfigFlags::WindowMinimized);
+            }
+            WindowEvent::Restored => core.window.flags.remove(ConfigFlags::WindowMinimized | ConfigFlags::WindowMaximized),
+            WindowEvent::FocusGained => core.window.flags.remove(ConfigFlags::WindowUnfocused),
+            WindowEvent::FocusLost => core.window.flags.insert(ConfigFlags::WindowUnfocused),
+            WindowEvent::MouseEnter => core.input.mouse.is_cursor_on_screen = true,
+            WindowEvent::MouseLeave => core.input.mouse.is_cursor_on_screen = false,
+            WindowEvent::CloseRequested => core.window.should_close = true,
+            _ => {}
+        },
+
+        // Keyboard events
+        Event::KeyDown { scancode: Some(scancode), repeat, .. } => {
+            let Some(key) = convert_scancode_to_key(scancode) else { return };
+            if core.input.keyboard.register_key_down(key, repeat) {
+                core.window.should_close = true;
+            }
+        }
+        Event::KeyUp { scancode: Some(scancode), .. } => {
+            if let Some(key) = convert_scancode_to_key(scancode) {
+                core.input.keyboard.register_key_up(key);
+            }
+        }
+        Event::TextInput { text, .. } => {
+            for ch in text.chars() {
+                if core.input.keyboard.char_pressed_queue.try_push(ch).is_err() {
+                    break;
+                }
+            }
+        }
+
+        // Check mouse events
+        Event::MouseButtonDown { mouse_btn, .. } => {
+            if let Some(button) = convert_mouse_button(mouse_btn) {
+                core.input.mouse.current_button_state[button as usize] = 1;
+            }
+            // Left button simulates a touch point, for touch code testing on desktop
+            #[cfg(feature = "support_mouse_gestures")]
+            if mouse_btn == SdlMouseButton::Left {
+                core.input.touch.press(MOUSE_TOUCH_POINT_ID, core.input.mouse.current_position);
+            }
+        }
+        Event::MouseButtonUp { mouse_btn, .. } => {
+            if let Some(button) = convert_mouse_button(mouse_btn) {
+                core.input.mouse.current_button_state[button as usize] = 0;
+            }
+            #[cfg(feature = "support_mouse_gestures")]
+            if mouse_btn == SdlMouseButton::Left {
+                core.input.touch.release(MOUSE_TOUCH_POINT_ID, core.input.mouse.current_position);
+            }
+        }
+        Event::MouseWheel { x, y, .. } => core.input.mouse.current_wheel_move += Vector2::new(x, y),
+        Event::MouseMotion { x, y, xrel, yrel, .. } => {
+            core.input.mouse.apply_motion(Vector2::new(x, y), Vector2::new(xrel, yrel), platform.cursor_relative);
+            #[cfg(feature = "support_mouse_gestures")]
+            core.input.touch.move_to(MOUSE_TOUCH_POINT_ID, core.input.mouse.current_position);
+        }
+
+        // Check touch events
+        // NOTE: SDL reports normalized coordinates, converted to screen pixels
+        Event::FingerDown { finger_id, x, y, .. } => {
+            let position = touch_to_screen(core, x, y);
+            core.input.touch.press(finger_id, position);
+        }
+        Event::FingerUp { finger_id, x, y, .. } => {
+            let position = touch_to_screen(core, x, y);
+            core.input.touch.release(finger_id, position);
+        }
+        Event::FingerMotion { finger_id, x, y, .. } => {
+            let position = touch_to_screen(core, x, y);
+            core.input.touch.move_to(finger_id, position);
+        }
+
+        // Check gamepad events
+        Event::ControllerDeviceAdded { which, .. } => gamepad_added(core, platform, which),
+        Event::ControllerDeviceRemoved { which, .. } => {
+            if let Some(slot) = gamepad_slot(platform, which) {
+                platform.gamepad[slot] = None;
+                core.input.gamepad.items[slot] = Gamepad::default();
+                tracelog!(Info, "GAMEPAD: Disconnected (slot {slot})");
+            }
+        }
+        Event::ControllerButtonDown { which, button, .. } => {
+            if let (Some(slot), Some(button)) = (gamepad_slot(platform, which), convert_gamepad_button(button)) {
+                core.input.gamepad.items[slot].current_button_state[button as usize] = 1;
+                core.input.gamepad.last_button_pressed = Some(button);
+            }
+        }
+        Event::ControllerButtonUp { which, button, .. } => {
+            if let (Some(slot), Some(button)) = (gamepad_slot(platform, which), convert_gamepad_button(button)) {
+                core.input.gamepad.items[slot].current_button_state[button as usize] = 0;
+                if core.input.gamepad.last_button_pressed == Some(button) {
+                    core.input.gamepad.last_button_pressed = None;
+                }
+            }
+        }
+        Event::ControllerAxisMotion { which, axis, value, .. } => {
+            let Some(slot) = gamepad_slot(platform, which) else { return };
+            let gamepad = &mut core.input.gamepad.items[slot];
+            let mut value = f32::from(value) / 32767.0;
+            match axis {
+                SdlAxis::LeftX => gamepad.axis_state[GamepadAxis::LeftX as usize] = value.clamp(-1.0, 1.0),
+                SdlAxis::LeftY => gamepad.axis_state[GamepadAxis::LeftY as usize] = value.clamp(-1.0, 1.0),
+                SdlAxis::RightX => gamepad.axis_state[GamepadAxis::RightX as usize] = value.clamp(-1.0, 1.0),
+                SdlAxis::RightY => gamepad.axis_state[GamepadAxis::RightY as usize] = value.clamp(-1.0, 1.0),
+                // NOTE: SDL triggers range [0..1], raylib triggers range [-1..1]
+                // Triggers are also registered as buttons, pressed past 0.1
+                SdlAxis::TriggerLeft => {
+                    value = value * 2.0 - 1.0;
+                    gamepad.axis_state[GamepadAxis::LeftTrigger as usize] = value.clamp(-1.0, 1.0);
+                    gamepad.current_button_state[GamepadButton::LeftTrigger2 as usize] = u8::from(value > 0.1);
+                }
+                SdlAxis::TriggerRight => {
+                    value = value * 2.0 - 1.0;
+                    gamepad.axis_state[GamepadAxis::RightTrigger as usize] = value.clamp(-1.0, 1.0);
+                    gamepad.current_button_state[GamepadButton::RightTrigger2 as usize] = u8::from(value > 0.1);
+                }
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Convert normalized touch coordinates to screen pixels
+#[allow(clippy::cast_precision_loss)]
+fn touch_to_screen(core: &Core, x: f32, y: f32) -> Position2 {
+    Vector2::new(x*core.window.screen.width as f32, y*core.window.screen.height as f32)
+}
+
+/// Open a gamepad and register it into a core gamepad slot
+fn open_gamepad(
+    gamepad_subsystem: &GamepadSubsystem,
+    joystick_subsystem: &JoystickSubsystem,
+    joystick_instance: JoystickInstance,
+    core_gamepad: &mut Gamepad,
+) -> Option<SdlGamepad> {
+    let id = joystick_instance.id;
+    let gamepad_joystick = gamepad_subsystem.open(id).and_then(|gamepad| joystick_subsystem.open(joystick_instance).map(|joystick| (gamepad, joystick)));
+    match gamepad_joystick {
+        Ok((gamepad, joystick)) => {
+            *core_gamepad = Gamepad::default();
+            core_gamepad.ready = true;
+            core_gamepad.axis_count = joystick.num_axes();
+            core_gamepad.axis_state[GamepadAxis::LeftTrigger as usize] = -1.0;
+            core_gamepad.axis_state[GamepadAxis::RightTrigger as usize] = -1.0;
+
+            // Name is cut to MAX_GAMEPAD_NAME_LEN bytes, on a char boundary
+            let name = gamepad.name();
+            let mut len = name.len().min(MAX_GAMEPAD_NAME_LEN);
+            while !name.is_char_boundary(len) {
+                len -= 1;
+            }
+            core_gamepad.name = name[..len].try_into().unwrap_or_default();
+
+            tracelog!(Info, "GAMEPAD: Connected: {}", core_gamepad.name);
+            Some(gamepad)
+        }
+        Err(e) => {
+            tracelog!(Warning, "PLATFORM: Unable to open game controller [ERROR: {e}]");
+            None
+        }
+    }
+}
+
+/// Register a connected gamepad into the first free slot
+fn gamepad_added(core: &mut Core, platform: &mut Platform, instance_id: u32) {
+    // NOTE: Gamepads connected at startup are also reported, those are already open
+    if gamepad_slot(platform, instance_id).is_some() {
+        return;
+    }
+    let Some(slot) = platform.gamepad.iter().position(Option::is_none) else {
+        tracelog!(Warning, "GAMEPAD: Maximum number of gamepads ({MAX_GAMEPADS}) already connected");
+        return;
+    };
+    let joystick_instance = platform.joystick_subsystem.joysticks().ok()
+        .and_then(|instances| instances.into_iter().find(|instance| instance.id == instance_id));
+    if let Some(joystick_instance) = joystick_instance {
+        platform.gamepad[slot] = open_gamepad(&platform.gamepad_subsystem, &platform.joystick_subsystem, joystick_instance, &mut core.input.gamepad.items[slot]);
+    }
+}
+
+/// Get the slot of an open gamepad from its SDL instance id
+fn gamepad_slot(platform: &Platform, instance_id: u32) -> Option<usize> {
+    platform.gamepad.iter().position(|gamepad| gamepad.as_ref().is_some_and(|gamepad| gamepad.instance_id() == instance_id))
+}
+
+/// Drawing guard returned by [`begin_drawing`], drawing ends when it is dropped
+/// NOTE: Dereferences to [`Core`], so it can be passed to drawing functions
+#[must_use = "drawing ends as soon as the handle is dropped"]
+pub struct DrawHandle<'c, 'a> {
+    core: &'c mut Core<'a>,
+    platform: &'c mut Platform,
+    /// Drawing actually began, a refused `begin_drawing()` does not end drawing on drop
+    active: bool,
+}
+
+impl<'a> Deref for DrawHandle<'_, 'a> {
+    type Target = Core<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.core
+    }
+}
+
+impl DerefMut for DrawHandle<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.core
+    }
+}
+
+impl Drop for DrawHandle<'_, '_> {
+    fn drop(&mut self) {
+        if self.active {
+            end_drawing(self.core, self.platform);
+        }
+    }
+}
+
+/// Setup canvas (framebuffer) to start drawing, drawing ends when the returned handle is dropped
+/// NOTE: Not allowed from within hooks, the returned handle does nothing then
+pub fn begin_drawing<'c, 'a>(core: &'c mut Core<'a>, platform: &'c mut Platform) -> DrawHandle<'c, 'a> {
+    if !core.can_begin_drawing() {
+        return DrawHandle { core, platform, active: false };
+    }
+
+    // WARNING: Previously to begin_drawing() other render textures drawing could happen,
+    // consequently the measure for update vs draw is not accurate (only the total frame time is accurate)
+    core.time.current = get_time(); // Number of elapsed seconds since SDL_Init()
+    core.time.update = core.time.current - core.time.previous;
+    core.time.previous = core.time.current;
+
+    core.rlgl.load_identity(); // Reset current matrix (modelview)
+    core.rlgl.mult_matrix(core.window.screen_scale); // Apply screen scaling
+    core.rlgl.diagnostics.clear();
+
+    core.run_hooks(HookStage::PreDraw);
+    core.is_drawing = true;
+
+    DrawHandle { core, platform, active: true }
+}
+
+/// End canvas drawing and swap buffers (double buffering)
+/// NOTE: Input is polled before frame pacing wait, or after it with late input polling
+fn end_drawing(core: &mut Core, platform: &mut Platform) {
+    core.is_drawing = false;
+    let policy = core.window.frame_policy();
+
+    // Duck or pause audio while in background, restored once the window is back in foreground
+    core.audio.set_background(policy.audio);
+
+    core.rlgl.draw_render_batch(FlushReason::EndOfFrame); // Update and draw internal render batch
+
+    core.run_hooks(HookStage::PostDraw);
+
+    // Screenshot reads the back buffer, before it is swapped
+    #[cfg(feature = "support_screen_capture")]
+    if policy.render && core.is_screenshot_key_pressed() {
+        core.take_next_screenshot();
+    }
+
+    #[cfg(feature = "support_gif_recording")]
+    if policy.render {
+        core.record_gif_frame();
+    }
+
+    if policy.render {
+        platform.window.gl_swap_window(); // Copy back buffer to front buffer (screen)
+    }
+
+    // Frame time control system
+    core.time.current = get_time();
+    core.time.draw = core.time.current - core.time.previous;
+    core.time.previous = core.time.current;
+
+    core.time.frame = core.time.update + core.time.draw;
+
+    #[cfg(feature = "support_automation_events")]
+    core.record_automation_event(); // Record input state of this frame, before it rolls over
+
+    core.input.end_frame();
+    if !core.input.late_polling {
+        poll_input_events(core, platform); // Poll user events (before next frame update)
+    }
+
+    // Wait for some milliseconds...
+    // NOTE: Background throttling can only slow frames down, never speed them up
+    let target = policy.frame_time(core.time.target);
+    if core.time.frame < target {
+        Core::wait_time(target - core.time.frame);
+
+        core.time.current = get_time();
+        let waited = core.time.current - core.time.previous;
+        core.time.previous = core.time.current;
+
+        core.time.frame += waited; // Total frame time: update + draw + wait
+    }
+
+    if core.input.late_polling {
+        poll_input_events(core, platform); // Poll user events (just before next frame update)
+    }
+
+    core.time.frame_counter += 1;
+    core.time.fps_history.record(core.time.current, core.time.frame);
+
+    core.run_hooks(HookStage::PostFrame);
+}
+
+/// Play a recorded automation event
+/// NOTE: Events are not played while recording
+pub fn play_automation_event(core: &mut Core, platform: &mut Platform, event: &AutomationEvent) {
+    if core.is_automation_event_recording() {
+        return;
+    }
+
+    let [p0, p1, ..] = event.params();
+    match event.event_type() {
+        AutomationEventType::Input(InputEventType::KeyDown) => {
+            // Queue key press like a key down event would, only if it was up
+            let keyboard = &mut core.input.keyboard;
+            let key = MAP_SCANCODE_TO_KEY.iter().flatten().copied().find(|&key| key as i32 == p0);
+            if let Some(key) = key {
+                if keyboard.previous_key_state[key as usize] == 0 && keyboard.current_key_state[key as usize] == 0 {
+                    _ = keyboard.key_pressed_queue.try_push(Some(key));
+                }
+            }
+        }
+        AutomationEventType::Window(WindowEventType::Maximize) => maximize_window(core, platform),
+        AutomationEventType::Window(WindowEventType::Minimize) => minimize_window(core, platform),
+        AutomationEventType::Window(WindowEventType::Resize) => {
+            if let (Ok(width), Ok(height)) = (u32::try_from(p0), u32::try_from(p1)) {
+                set_window_size(core, platform, width, height);
+            }
+        }
+        #[cfg(feature = "support_screen_capture")]
+        AutomationEventType::Custom(CustomEventType::TakeScreenshot) => core.take_next_screenshot(),
+        _ => {}
+    }
+    core.play_input_automation_event(event);
 }
 
 /// Get elapsed time measure in seconds
-fn get_time() -> f64 {
-    let ms = sdl3::timer::ticks(); // Elapsed time in milliseconds since SDL_Init()
-    let time = ms as f64/1000.0;
-    time
+/// NOTE: High resolution performance counter, milliseconds are not enough for frame pacing
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn get_time() -> f64 {
+    sdl3::timer::performance_counter() as f64/sdl3::timer::performance_frequency() as f64
 }
 
 /// Scancode to keycode mapping
 fn convert_scancode_to_key(sdl_scancode: sdl3::keyboard::Scancode) -> Option<KeyboardKey> {
     sdl_scancode.to_i32().try_into().ok().and_then(|code: usize| MAP_SCANCODE_TO_KEY.get(code).copied().flatten())
 }
+
+/// Mouse button mapping, SDL reports right and middle buttons swapped relative to raylib
+fn convert_mouse_button(sdl_button: SdlMouseButton) -> Option<MouseButton> {
+    match sdl_button {
+        SdlMouseButton::Left => Some(MouseButton::Left),
+        SdlMouseButton::Right => Some(MouseButton::Right),
+        SdlMouseButton::Middle => Some(MouseButton::Middle),
+        SdlMouseButton::X1 => Some(MouseButton::Side),
+        SdlMouseButton::X2 => Some(MouseButton::Extra),
+        SdlMouseButton::Unknown => None,
+    }
+}
+
+/// Gamepad button mapping
+fn convert_gamepad_button(sdl_button: SdlButton) -> Option<GamepadButton> {
+    match sdl_button {
+        SdlButton::South => Some(GamepadButton::RightFaceDown),
+        SdlButton::East => Some(GamepadButton::RightFaceRight),
+        SdlButton::West => Some(GamepadButton::RightFaceLeft),
+        SdlButton::North => Some(GamepadButton::RightFaceUp),
+        SdlButton::LeftShoulder => Some(GamepadButton::LeftTrigger1),
+        SdlButton::RightShoulder => Some(GamepadButton::RightTrigger1),
+        SdlButton::Back => Some(GamepadButton::MiddleLeft),
+        SdlButton::Guide => Some(GamepadButton::Middle),
+        SdlButton::Start => Some(GamepadButton::MiddleRight),
+        SdlButton::DPadUp => Some(GamepadButton::LeftFaceUp),
+        SdlButton::DPadRight => Some(GamepadButton::LeftFaceRight),
+        SdlButton::DPadDown => Some(GamepadButton::LeftFaceDown),
+        SdlButton::DPadLeft => Some(GamepadButton::LeftFaceLeft),
+        SdlButton::LeftStick => Some(GamepadButton::LeftThumb),
+        SdlButton::RightStick => Some(GamepadButton::RightThumb),
+        _ => None,
+    }
+}
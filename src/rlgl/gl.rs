@@ -0,0 +1,453 @@
+//! Safe OpenGL boundary used by rlgl
+//!
+//! The crate forbids unsafe code, so raw OpenGL calls can not be issued from here:
+//! rlgl talks to the GPU through [`GlApi`], implemented outside the crate on top of
+//! any OpenGL loader (using [`get_gl_proc_address`](crate::platforms::rcore_desktop_sdl::get_gl_proc_address)).
+//! Until a backend is provided, [`HeadlessGl`] is used and nothing is rendered
+
+use std::fmt::Debug;
+use crate::{prelude::Matrix, rlgl::{DrawMode, GlVersion, ShaderType}};
+
+// Context information (equivalent to OpenGL defines)
+pub const GL_VENDOR: u32 = 0x1F00;
+pub const GL_RENDERER: u32 = 0x1F01;
+pub const GL_VERSION: u32 = 0x1F02;
+pub const GL_SHADING_LANGUAGE_VERSION: u32 = 0x8B8C;
+pub const GL_MAX_TEXTURE_SIZE: u32 = 0x0D33;
+pub const GL_MAX_TEXTURE_MAX_ANISOTROPY_EXT: u32 = 0x84FF;
+
+// Texture parameters (equivalent to OpenGL defines)
+pub const GL_TEXTURE_WRAP_S: u32 = 0x2802;
+pub const GL_TEXTURE_WRAP_T: u32 = 0x2803;
+pub const GL_TEXTURE_MAG_FILTER: u32 = 0x2800;
+pub const GL_TEXTURE_MIN_FILTER: u32 = 0x2801;
+pub const GL_TEXTURE_SWIZZLE_RGBA: u32 = 0x8E46;
+pub const GL_TEXTURE_MAX_ANISOTROPY_EXT: u32 = 0x84FE;
+
+pub const GL_NEAREST: i32 = 0x2600;
+pub const GL_LINEAR: i32 = 0x2601;
+pub const GL_NEAREST_MIPMAP_NEAREST: i32 = 0x2700;
+pub const GL_LINEAR_MIPMAP_NEAREST: i32 = 0x2701;
+pub const GL_LINEAR_MIPMAP_LINEAR: i32 = 0x2703;
+pub const GL_REPEAT: i32 = 0x2901;
+
+// Pixel data formats and types (equivalent to OpenGL defines)
+pub const GL_RED: u32 = 0x1903;
+pub const GL_GREEN: u32 = 0x1904;
+pub const GL_RG: u32 = 0x8227;
+pub const GL_RGB: u32 = 0x1907;
+pub const GL_RGBA: u32 = 0x1908;
+pub const GL_ONE: u32 = 1;
+
+pub const GL_UNSIGNED_BYTE: u32 = 0x1401;
+pub const GL_FLOAT: u32 = 0x1406;
+pub const GL_HALF_FLOAT: u32 = 0x140B;
+pub const GL_UNSIGNED_SHORT_5_6_5: u32 = 0x8363;
+pub const GL_UNSIGNED_SHORT_5_5_5_1: u32 = 0x8034;
+pub const GL_UNSIGNED_SHORT_4_4_4_4: u32 = 0x8033;
+
+// Texture internal formats (equivalent to OpenGL defines)
+pub const GL_R8: u32 = 0x8229;
+pub const GL_RG8: u32 = 0x822B;
+pub const GL_RGB565: u32 = 0x8D62;
+pub const GL_RGB8: u32 = 0x8051;
+pub const GL_RGB5_A1: u32 = 0x8057;
+pub const GL_RGBA4: u32 = 0x8056;
+pub const GL_RGBA8: u32 = 0x8058;
+pub const GL_R32F: u32 = 0x822E;
+pub const GL_RGB32F: u32 = 0x8815;
+pub const GL_RGBA32F: u32 = 0x8814;
+pub const GL_R16F: u32 = 0x822D;
+pub const GL_RGB16F: u32 = 0x881B;
+pub const GL_RGBA16F: u32 = 0x881A;
+
+// Compressed texture internal formats (equivalent to OpenGL extensions defines)
+pub const GL_COMPRESSED_RGB_S3TC_DXT1_EXT: u32 = 0x83F0;
+pub const GL_COMPRESSED_RGBA_S3TC_DXT1_EXT: u32 = 0x83F1;
+pub const GL_COMPRESSED_RGBA_S3TC_DXT3_EXT: u32 = 0x83F2;
+pub const GL_COMPRESSED_RGBA_S3TC_DXT5_EXT: u32 = 0x83F3;
+pub const GL_ETC1_RGB8_OES: u32 = 0x8D64;
+pub const GL_COMPRESSED_RGB8_ETC2: u32 = 0x9274;
+pub const GL_COMPRESSED_RGBA8_ETC2_EAC: u32 = 0x9278;
+pub const GL_COMPRESSED_RGB_PVRTC_4BPPV1_IMG: u32 = 0x8C00;
+pub const GL_COMPRESSED_RGBA_PVRTC_4BPPV1_IMG: u32 = 0x8C02;
+pub const GL_COMPRESSED_RGBA_ASTC_4X4_KHR: u32 = 0x93B0;
+pub const GL_COMPRESSED_RGBA_ASTC_8X8_KHR: u32 = 0x93B7;
+
+// Framebuffer attachment points and targets (equivalent to OpenGL defines)
+pub const GL_COLOR_ATTACHMENT0: u32 = 0x8CE0;
+pub const GL_DEPTH_ATTACHMENT: u32 = 0x8D00;
+pub const GL_STENCIL_ATTACHMENT: u32 = 0x8D20;
+pub const GL_TEXTURE_2D: u32 = 0x0DE1;
+pub const GL_TEXTURE_CUBE_MAP_POSITIVE_X: u32 = 0x8515;
+pub const GL_DEPTH_COMPONENT24: u32 = 0x81A6;
+
+// Framebuffer status (equivalent to OpenGL defines)
+pub const GL_FRAMEBUFFER_COMPLETE: u32 = 0x8CD5;
+pub const GL_FRAMEBUFFER_INCOMPLETE_ATTACHMENT: u32 = 0x8CD6;
+pub const GL_FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT: u32 = 0x8CD7;
+pub const GL_FRAMEBUFFER_INCOMPLETE_DIMENSIONS: u32 = 0x8CD9;
+pub const GL_FRAMEBUFFER_UNSUPPORTED: u32 = 0x8CDD;
+
+// Buffer bits (equivalent to OpenGL defines)
+pub const GL_DEPTH_BUFFER_BIT: u32 = 0x0000_0100;
+pub const GL_STENCIL_BUFFER_BIT: u32 = 0x0000_0400;
+pub const GL_COLOR_BUFFER_BIT: u32 = 0x0000_4000;
+
+// Capabilities (equivalent to OpenGL defines)
+pub const GL_BLEND: u32 = 0x0BE2;
+pub const GL_SCISSOR_TEST: u32 = 0x0C11;
+pub const GL_DEPTH_TEST: u32 = 0x0B71;
+pub const GL_CULL_FACE: u32 = 0x0B44;
+pub const GL_LINE_SMOOTH: u32 = 0x0B20;
+
+// Depth functions, faces and polygon modes (equivalent to OpenGL defines)
+pub const GL_LEQUAL: u32 = 0x0203;
+pub const GL_FRONT: u32 = 0x0404;
+pub const GL_BACK: u32 = 0x0405;
+pub const GL_FRONT_AND_BACK: u32 = 0x0408;
+pub const GL_CCW: u32 = 0x0901;
+pub const GL_LINE: u32 = 0x1B01;
+pub const GL_FILL: u32 = 0x1B02;
+
+// Blending factors (equivalent to OpenGL defines)
+pub const GL_ZERO: u32 = 0;
+pub const GL_SRC_COLOR: u32 = 0x0300;
+pub const GL_ONE_MINUS_SRC_COLOR: u32 = 0x0301;
+pub const GL_SRC_ALPHA: u32 = 0x0302;
+pub const GL_ONE_MINUS_SRC_ALPHA: u32 = 0x0303;
+pub const GL_DST_ALPHA: u32 = 0x0304;
+pub const GL_ONE_MINUS_DST_ALPHA: u32 = 0x0305;
+pub const GL_DST_COLOR: u32 = 0x0306;
+pub const GL_ONE_MINUS_DST_COLOR: u32 = 0x0307;
+pub const GL_SRC_ALPHA_SATURATE: u32 = 0x0308;
+pub const GL_CONSTANT_COLOR: u32 = 0x8001;
+pub const GL_ONE_MINUS_CONSTANT_COLOR: u32 = 0x8002;
+pub const GL_CONSTANT_ALPHA: u32 = 0x8003;
+pub const GL_ONE_MINUS_CONSTANT_ALPHA: u32 = 0x8004;
+
+// Blending functions/equations (equivalent to OpenGL defines)
+pub const GL_FUNC_ADD: u32 = 0x8006;
+pub const GL_MIN: u32 = 0x8007;
+pub const GL_MAX: u32 = 0x8008;
+pub const GL_FUNC_SUBTRACT: u32 = 0x800A;
+pub const GL_FUNC_REVERSE_SUBTRACT: u32 = 0x800B;
+
+/// Buffer binding target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum BufferTarget {
+    /// `GL_ARRAY_BUFFER`
+    Array = 0x8892,
+    /// `GL_ELEMENT_ARRAY_BUFFER`
+    ElementArray = 0x8893,
+}
+
+/// Buffer data usage hint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum BufferUsage {
+    /// `GL_STATIC_DRAW`
+    StaticDraw = 0x88E4,
+    /// `GL_DYNAMIC_DRAW`
+    DynamicDraw = 0x88E8,
+}
+
+/// Vertex attribute component type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum AttribType {
+    /// `GL_UNSIGNED_BYTE`
+    UnsignedByte = 0x1401,
+    /// `GL_UNSIGNED_SHORT`
+    UnsignedShort = 0x1403,
+    /// `GL_UNSIGNED_INT`
+    UnsignedInt = 0x1405,
+    /// `GL_FLOAT`
+    Float = 0x1406,
+}
+
+/// Buffer data to upload, typed to keep the upload safe
+#[derive(Debug, Clone, Copy)]
+pub enum BufferData<'a> {
+    F32(&'a [f32]),
+    U8(&'a [u8]),
+    U16(&'a [u16]),
+    U32(&'a [u32]),
+}
+
+impl BufferData<'_> {
+    /// Size of the data in bytes
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        match self {
+            Self::F32(data) => size_of_val(*data),
+            Self::U8(data) => size_of_val(*data),
+            Self::U16(data) => size_of_val(*data),
+            Self::U32(data) => size_of_val(*data),
+        }
+    }
+}
+
+/// OpenGL functions required by rlgl
+/// NOTE: Offsets and sizes are in bytes, ids follow OpenGL conventions (0 is no object)
+#[allow(clippy::too_many_arguments)]
+pub trait GlApi: Debug {
+    /// OpenGL version of the context, selects shaders code
+    fn version(&self) -> GlVersion;
+
+    /// `glEnable`
+    fn enable(&mut self, cap: u32);
+    /// `glDisable`
+    fn disable(&mut self, cap: u32);
+    /// `glClearColor`, normalized components
+    fn clear_color(&mut self, r: f32, g: f32, b: f32, a: f32);
+    /// `glClear`
+    fn clear(&mut self, mask: u32);
+    /// `glViewport`, window coordinates (bottom-left origin)
+    fn viewport(&mut self, x: i32, y: i32, width: i32, height: i32);
+    /// `glScissor`, window coordinates (bottom-left origin)
+    fn scissor(&mut self, x: i32, y: i32, width: i32, height: i32);
+    /// `glDepthFunc`
+    fn depth_func(&mut self, func: u32);
+    /// `glDepthMask`
+    fn depth_mask(&mut self, enabled: bool);
+    /// `glCullFace`
+    fn cull_face(&mut self, face: u32);
+    /// `glFrontFace`
+    fn front_face(&mut self, mode: u32);
+    /// `glPolygonMode`, not available on OpenGL ES
+    fn polygon_mode(&mut self, face: u32, mode: u32);
+    /// `glBlendFunc`
+    fn blend_func(&mut self, src_factor: u32, dst_factor: u32);
+    /// `glBlendFuncSeparate`
+    fn blend_func_separate(&mut self, src_rgb: u32, dst_rgb: u32, src_alpha: u32, dst_alpha: u32);
+    /// `glBlendEquation`
+    fn blend_equation(&mut self, equation: u32);
+    /// `glBlendEquationSeparate`
+    fn blend_equation_separate(&mut self, equation_rgb: u32, equation_alpha: u32);
+
+    /// `glGenVertexArrays`, `None` if vertex array objects are not supported
+    fn gen_vertex_array(&mut self) -> Option<u32>;
+    /// `glBindVertexArray`
+    fn bind_vertex_array(&mut self, id: u32);
+    /// `glDeleteVertexArrays`
+    fn delete_vertex_array(&mut self, id: u32);
+
+    /// `glGenBuffers`
+    fn gen_buffer(&mut self) -> u32;
+    /// `glBindBuffer`
+    fn bind_buffer(&mut self, target: BufferTarget, id: u32);
+    /// `glBufferData`
+    fn buffer_data(&mut self, target: BufferTarget, data: BufferData<'_>, usage: BufferUsage);
+    /// `glBufferSubData`
+    fn buffer_sub_data(&mut self, target: BufferTarget, offset: usize, data: BufferData<'_>);
+    /// `glDeleteBuffers`
+    fn delete_buffer(&mut self, id: u32);
+
+    /// `glEnableVertexAttribArray`
+    fn enable_vertex_attrib_array(&mut self, index: u32);
+    /// `glDisableVertexAttribArray`
+    fn disable_vertex_attrib_array(&mut self, index: u32);
+    /// `glVertexAttribPointer`, pointer is an offset into the bound array buffer
+    fn vertex_attrib_pointer(&mut self, index: u32, size: i32, ty: AttribType, normalized: bool, stride: usize, offset: usize);
+
+    /// `glCreateShader`, 0 on failure
+    fn create_shader(&mut self, kind: ShaderType) -> u32;
+    /// `glShaderSource` (single source string)
+    fn shader_source(&mut self, shader: u32, source: &str);
+    /// `glCompileShader`
+    fn compile_shader(&mut self, shader: u32);
+    /// `glGetShaderiv(GL_COMPILE_STATUS)`
+    fn get_shader_compile_status(&mut self, shader: u32) -> bool;
+    /// `glGetShaderInfoLog`
+    fn get_shader_info_log(&mut self, shader: u32) -> String;
+    /// `glDeleteShader`
+    fn delete_shader(&mut self, shader: u32);
+
+    /// `glCreateProgram`, 0 on failure
+    fn create_program(&mut self) -> u32;
+    /// `glAttachShader`
+    fn attach_shader(&mut self, program: u32, shader: u32);
+    /// `glDetachShader`
+    fn detach_shader(&mut self, program: u32, shader: u32);
+    /// `glBindAttribLocation`
+    fn bind_attrib_location(&mut self, program: u32, index: u32, name: &str);
+    /// `glLinkProgram`
+    fn link_program(&mut self, program: u32);
+    /// `glGetProgramiv(GL_LINK_STATUS)`
+    fn get_program_link_status(&mut self, program: u32) -> bool;
+    /// `glGetProgramInfoLog`
+    fn get_program_info_log(&mut self, program: u32) -> String;
+    /// `glDeleteProgram`
+    fn delete_program(&mut self, program: u32);
+    /// `glGetAttribLocation`, -1 if not found
+    fn get_attrib_location(&mut self, program: u32, name: &str) -> i32;
+
+    /// `glUseProgram`
+    fn use_program(&mut self, id: u32);
+    /// `glGetUniformLocation`, -1 if not found
+    fn get_uniform_location(&mut self, program: u32, name: &str) -> i32;
+    /// `glUniformMatrix4fv` (single matrix, not transposed)
+    fn uniform_matrix4(&mut self, location: i32, mat: &Matrix);
+    /// `glUniform4f`
+    fn uniform4f(&mut self, location: i32, value: [f32; 4]);
+    /// `glUniform1i`
+    fn uniform1i(&mut self, location: i32, value: i32);
+    /// `glUniform{1,2,3,4}fv`, components per element (1 to 4), element count is `values.len()/components`
+    fn uniform_fv(&mut self, location: i32, components: usize, values: &[f32]);
+    /// `glUniform{1,2,3,4}iv`, components per element (1 to 4), element count is `values.len()/components`
+    fn uniform_iv(&mut self, location: i32, components: usize, values: &[i32]);
+
+    /// `glActiveTexture`, unit index (0 for `GL_TEXTURE0`)
+    fn active_texture(&mut self, unit: u32);
+    /// `glGenTextures`
+    fn gen_texture(&mut self) -> u32;
+    /// `glBindTexture` (`GL_TEXTURE_2D`)
+    fn bind_texture(&mut self, id: u32);
+    /// `glDeleteTextures`
+    fn delete_texture(&mut self, id: u32);
+    /// `glPixelStorei(GL_UNPACK_ALIGNMENT, ..)`
+    fn pixel_store_unpack_alignment(&mut self, alignment: i32);
+    /// `glTexImage2D` (`GL_TEXTURE_2D`), no data just allocates texture storage
+    fn tex_image_2d(&mut self, level: usize, internal_format: u32, width: usize, height: usize, format: u32, ty: u32, data: Option<&[u8]>);
+    /// `glCompressedTexImage2D` (`GL_TEXTURE_2D`), `size` of the image data in bytes
+    fn compressed_tex_image_2d(&mut self, level: usize, internal_format: u32, width: usize, height: usize, size: usize, data: Option<&[u8]>);
+    /// `glTexSubImage2D` (`GL_TEXTURE_2D`)
+    fn tex_sub_image_2d(&mut self, level: usize, offset_x: usize, offset_y: usize, width: usize, height: usize, format: u32, ty: u32, data: &[u8]);
+    /// `glTexParameteri` (`GL_TEXTURE_2D`)
+    fn tex_parameteri(&mut self, pname: u32, param: i32);
+    /// `glTexParameteriv` (`GL_TEXTURE_2D`)
+    fn tex_parameteriv(&mut self, pname: u32, params: &[i32]);
+
+    /// Check if an OpenGL extension is supported (`GL_EXTENSIONS`)
+    fn is_extension_supported(&mut self, name: &str) -> bool;
+    /// `glGetString`, empty if not available
+    fn get_string(&mut self, name: u32) -> String;
+    /// `glGetIntegerv`, single value
+    fn get_integer(&mut self, pname: u32) -> i32;
+    /// `glGetFloatv`, single value
+    fn get_float(&mut self, pname: u32) -> f32;
+
+    /// `glReadPixels` of the bound framebuffer (back buffer by default), rows are stored bottom to top
+    fn read_pixels(&mut self, x: i32, y: i32, width: usize, height: usize, format: u32, ty: u32, data: &mut [u8]);
+    /// `glGenFramebuffers`
+    fn gen_framebuffer(&mut self) -> u32;
+    /// `glBindFramebuffer(GL_FRAMEBUFFER, ..)`
+    fn bind_framebuffer(&mut self, id: u32);
+    /// `glFramebufferTexture2D(GL_FRAMEBUFFER, ..)`
+    fn framebuffer_texture_2d(&mut self, attachment: u32, tex_target: u32, texture: u32, level: usize);
+    /// `glFramebufferRenderbuffer(GL_FRAMEBUFFER, .., GL_RENDERBUFFER, ..)`
+    fn framebuffer_renderbuffer(&mut self, attachment: u32, renderbuffer: u32);
+    /// `glCheckFramebufferStatus(GL_FRAMEBUFFER)`, status of the bound framebuffer
+    fn check_framebuffer_status(&mut self) -> u32;
+    /// `glDeleteFramebuffers`
+    fn delete_framebuffer(&mut self, id: u32);
+    /// `glGenRenderbuffers`
+    fn gen_renderbuffer(&mut self) -> u32;
+    /// `glBindRenderbuffer(GL_RENDERBUFFER, ..)`
+    fn bind_renderbuffer(&mut self, id: u32);
+    /// `glRenderbufferStorage(GL_RENDERBUFFER, ..)`
+    fn renderbuffer_storage(&mut self, internal_format: u32, width: usize, height: usize);
+    /// `glDeleteRenderbuffers`
+    fn delete_renderbuffer(&mut self, id: u32);
+
+    /// `glDrawArrays`
+    fn draw_arrays(&mut self, mode: DrawMode, first: usize, count: usize);
+    /// `glDrawElements`, offset in bytes into the bound element array buffer
+    fn draw_elements(&mut self, mode: DrawMode, count: usize, ty: AttribType, offset: usize);
+}
+
+/// No-op OpenGL backend, objects are just numbered
+/// NOTE: Used until a real backend is provided, i.e. headless runs
+#[derive(Debug, Default)]
+pub struct HeadlessGl {
+    next_id: u32,
+}
+
+impl HeadlessGl {
+    fn next_id(&mut self) -> u32 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+impl GlApi for HeadlessGl {
+    fn version(&self) -> GlVersion { GlVersion::Gl33 }
+    fn enable(&mut self, _cap: u32) {}
+    fn disable(&mut self, _cap: u32) {}
+    fn clear_color(&mut self, _r: f32, _g: f32, _b: f32, _a: f32) {}
+    fn clear(&mut self, _mask: u32) {}
+    fn viewport(&mut self, _x: i32, _y: i32, _width: i32, _height: i32) {}
+    fn scissor(&mut self, _x: i32, _y: i32, _width: i32, _height: i32) {}
+    fn depth_func(&mut self, _func: u32) {}
+    fn depth_mask(&mut self, _enabled: bool) {}
+    fn cull_face(&mut self, _face: u32) {}
+    fn front_face(&mut self, _mode: u32) {}
+    fn polygon_mode(&mut self, _face: u32, _mode: u32) {}
+    fn blend_func(&mut self, _src_factor: u32, _dst_factor: u32) {}
+    fn blend_func_separate(&mut self, _src_rgb: u32, _dst_rgb: u32, _src_alpha: u32, _dst_alpha: u32) {}
+    fn blend_equation(&mut self, _equation: u32) {}
+    fn blend_equation_separate(&mut self, _equation_rgb: u32, _equation_alpha: u32) {}
+    fn gen_vertex_array(&mut self) -> Option<u32> { Some(self.next_id()) }
+    fn bind_vertex_array(&mut self, _id: u32) {}
+    fn delete_vertex_array(&mut self, _id: u32) {}
+    fn gen_buffer(&mut self) -> u32 { self.next_id() }
+    fn bind_buffer(&mut self, _target: BufferTarget, _id: u32) {}
+    fn buffer_data(&mut self, _target: BufferTarget, _data: BufferData<'_>, _usage: BufferUsage) {}
+    fn buffer_sub_data(&mut self, _target: BufferTarget, _offset: usize, _data: BufferData<'_>) {}
+    fn delete_buffer(&mut self, _id: u32) {}
+    fn enable_vertex_attrib_array(&mut self, _index: u32) {}
+    fn disable_vertex_attrib_array(&mut self, _index: u32) {}
+    fn vertex_attrib_pointer(&mut self, _index: u32, _size: i32, _ty: AttribType, _normalized: bool, _stride: usize, _offset: usize) {}
+    fn create_shader(&mut self, _kind: ShaderType) -> u32 { self.next_id() }
+    fn shader_source(&mut self, _shader: u32, _source: &str) {}
+    fn compile_shader(&mut self, _shader: u32) {}
+    fn get_shader_compile_status(&mut self, _shader: u32) -> bool { true }
+    fn get_shader_info_log(&mut self, _shader: u32) -> String { String::new() }
+    fn delete_shader(&mut self, _shader: u32) {}
+    fn create_program(&mut self) -> u32 { self.next_id() }
+    fn attach_shader(&mut self, _program: u32, _shader: u32) {}
+    fn detach_shader(&mut self, _program: u32, _shader: u32) {}
+    fn bind_attrib_location(&mut self, _program: u32, _index: u32, _name: &str) {}
+    fn link_program(&mut self, _program: u32) {}
+    fn get_program_link_status(&mut self, _program: u32) -> bool { true }
+    fn get_program_info_log(&mut self, _program: u32) -> String { String::new() }
+    fn delete_program(&mut self, _program: u32) {}
+    fn get_attrib_location(&mut self, _program: u32, _name: &str) -> i32 { -1 }
+    fn use_program(&mut self, _id: u32) {}
+    fn get_uniform_location(&mut self, _program: u32, _name: &str) -> i32 { -1 }
+    fn uniform_matrix4(&mut self, _location: i32, _mat: &Matrix) {}
+    fn uniform4f(&mut self, _location: i32, _value: [f32; 4]) {}
+    fn uniform1i(&mut self, _location: i32, _value: i32) {}
+    fn uniform_fv(&mut self, _location: i32, _components: usize, _values: &[f32]) {}
+    fn uniform_iv(&mut self, _location: i32, _components: usize, _values: &[i32]) {}
+    fn active_texture(&mut self, _unit: u32) {}
+    fn gen_texture(&mut self) -> u32 { self.next_id() }
+    fn bind_texture(&mut self, _id: u32) {}
+    fn delete_texture(&mut self, _id: u32) {}
+    fn pixel_store_unpack_alignment(&mut self, _alignment: i32) {}
+    fn tex_image_2d(&mut self, _level: usize, _internal_format: u32, _width: usize, _height: usize, _format: u32, _ty: u32, _data: Option<&[u8]>) {}
+    fn compressed_tex_image_2d(&mut self, _level: usize, _internal_format: u32, _width: usize, _height: usize, _size: usize, _data: Option<&[u8]>) {}
+    fn tex_sub_image_2d(&mut self, _level: usize, _offset_x: usize, _offset_y: usize, _width: usize, _height: usize, _format: u32, _ty: u32, _data: &[u8]) {}
+    fn tex_parameteri(&mut self, _pname: u32, _param: i32) {}
+    fn tex_parameteriv(&mut self, _pname: u32, _params: &[i32]) {}
+    fn is_extension_supported(&mut self, _name: &str) -> bool { false }
+    fn get_string(&mut self, _name: u32) -> String { String::new() }
+    fn get_integer(&mut self, _pname: u32) -> i32 { 0 }
+    fn get_float(&mut self, _pname: u32) -> f32 { 0.0 }
+    fn read_pixels(&mut self, _x: i32, _y: i32, _width: usize, _height: usize, _format: u32, _ty: u32, _data: &mut [u8]) {}
+    fn gen_framebuffer(&mut self) -> u32 { self.next_id() }
+    fn bind_framebuffer(&mut self, _id: u32) {}
+    fn framebuffer_texture_2d(&mut self, _attachment: u32, _tex_target: u32, _texture: u32, _level: usize) {}
+    fn framebuffer_renderbuffer(&mut self, _attachment: u32, _renderbuffer: u32) {}
+    fn check_framebuffer_status(&mut self) -> u32 { GL_FRAMEBUFFER_COMPLETE }
+    fn delete_framebuffer(&mut self, _id: u32) {}
+    fn gen_renderbuffer(&mut self) -> u32 { self.next_id() }
+    fn bind_renderbuffer(&mut self, _id: u32) {}
+    fn renderbuffer_storage(&mut self, _internal_format: u32, _width: usize, _height: usize) {}
+    fn delete_renderbuffer(&mut self, _id: u32) {}
+    fn draw_arrays(&mut self, _mode: DrawMode, _first: usize, _count: usize) {}
+    fn draw_elements(&mut self, _mode: DrawMode, _count: usize, _ty: AttribType, _offset: usize) {}
+}
@@ -0,0 +1,3333 @@
+use std::{collections::HashMap, sync::{atomic::{AtomicBool, Ordering}, mpsc::{self, Receiver, Sender}}};
+use arrayvec::ArrayVec;
+use crate::{config::{RL_DEFAULT_BATCH_BUFFERS, RL_DEFAULT_BATCH_MAX_TEXTURE_UNITS, RL_DEFAULT_BATCH_BUFFER_ELEMENTS, RL_DEFAULT_BATCH_DRAWCALLS, RL_DEFAULT_SHADER_ATTRIB_LOCATION_COLOR, RL_DEFAULT_SHADER_ATTRIB_LOCATION_NORMAL, RL_DEFAULT_SHADER_ATTRIB_LOCATION_POSITION, RL_DEFAULT_SHADER_ATTRIB_LOCATION_TANGENT, RL_DEFAULT_SHADER_ATTRIB_LOCATION_TEXCOORD, RL_DEFAULT_SHADER_ATTRIB_LOCATION_TEXCOORD2, RL_DEFAULT_SHADER_ATTRIB_NAME_COLOR, RL_DEFAULT_SHADER_ATTRIB_NAME_NORMAL, RL_DEFAULT_SHADER_ATTRIB_NAME_POSITION, RL_DEFAULT_SHADER_ATTRIB_NAME_TANGENT, RL_DEFAULT_SHADER_ATTRIB_NAME_TEXCOORD, RL_DEFAULT_SHADER_ATTRIB_NAME_TEXCOORD2, RL_DEFAULT_SHADER_SAMPLER2D_NAME_TEXTURE0, RL_DEFAULT_SHADER_UNIFORM_NAME_COLOR, RL_DEFAULT_SHADER_UNIFORM_NAME_MVP, RL_MAX_MATRIX_STACK_SIZE, RL_MAX_SHADER_LOCATIONS}, graphics::{GlFrameBufferID, GlTextureID}, prelude::{Color, Core, Degrees, Matrix, PixelFormat, ShaderLocationIndex, ShaderUniformData, TextureError, TextureFilter, Vector3}, tracelog};
+use gl::{AttribType, BufferData, BufferTarget, BufferUsage, GlApi, HeadlessGl};
+
+pub mod gl;
+
+pub const RLGL_VERSION: &'static str = "5.0";
+
+/// Maximum number of flush records kept per frame when batch diagnostics are enabled
+pub const RL_MAX_FLUSH_RECORDS: usize = 1024;
+
+/// Batch diagnostics enabled flag, checked before recording any flush
+static BATCH_DIAGNOSTICS: AtomicBool = AtomicBool::new(false);
+
+/// Enable/disable recording the reason of every render batch flush
+/// NOTE: When disabled, recording costs a single atomic load per flush
+pub fn set_batch_diagnostics(enabled: bool) {
+    BATCH_DIAGNOSTICS.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if batch diagnostics are enabled
+#[must_use]
+pub fn is_batch_diagnostics_enabled() -> bool {
+    BATCH_DIAGNOSTICS.load(Ordering::Relaxed)
+}
+
+/// Reason for a render batch flush (draw call submission)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FlushReason {
+    /// Texture changed between draws
+    TextureChange { old_id: u32, new_id: u32 },
+    /// Shader changed between draws
+    ShaderChange { old_id: u32, new_id: u32 },
+    /// Shader uniform value changed
+    UniformChange,
+    /// Blend mode changed
+    BlendModeChange,
+    /// Scissor mode began or ended
+    ScissorModeChange,
+    /// Render target changed (texture mode began or ended)
+    FramebufferChange,
+    /// Screen buffers cleared with draws pending
+    ScreenClear,
+    /// Depth test, depth mask, face culling, wire mode or smooth lines toggled
+    RenderStateChange,
+    /// Drawing mode (LINES, TRIANGLES, QUADS) changed and the draw calls buffer is full
+    DrawModeChange,
+    /// Matrix stack pushed/popped or current matrix changed
+    MatrixChange,
+    /// Vertex buffer ran out of space
+    BufferOverflow,
+    /// Flush requested explicitly by user code
+    Explicit,
+    /// Frame ended
+    EndOfFrame,
+    /// Mesh drawn outside of the batch
+    MeshDraw,
+}
+
+/// Batch flush diagnostic record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlushRecord {
+    /// Why the batch was flushed
+    pub reason: FlushReason,
+    /// Number of vertices submitted by the flush
+    pub vertex_count: usize,
+    /// Draw tag active when the flush happened, see [`Core::set_draw_tag`](crate::prelude::Core::set_draw_tag)
+    pub tag: Option<&'static str>,
+}
+
+/// Per-frame batch flush records
+#[derive(Debug, Default)]
+pub(crate) struct BatchDiagnostics {
+    /// Flush records of the current frame, up to `RL_MAX_FLUSH_RECORDS`
+    pub(crate) records: Vec<FlushRecord>,
+    /// Ambient tag for draws, set by user
+    pub(crate) tag: Option<&'static str>,
+}
+
+impl BatchDiagnostics {
+    /// Register a batch flush, if diagnostics are enabled
+    #[inline]
+    pub(crate) fn record(&mut self, reason: FlushReason, vertex_count: usize) {
+        if is_batch_diagnostics_enabled() && self.records.len() < RL_MAX_FLUSH_RECORDS {
+            self.records.push(FlushRecord { reason, vertex_count, tag: self.tag });
+        }
+    }
+
+    /// Clear records at the start of a new frame
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.records.clear();
+    }
+}
+
+/// Primitive assembly draw modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u32)]
+pub enum DrawMode {
+    /// `GL_LINES`
+    Lines = 0x0001,
+    /// `GL_TRIANGLES`
+    Triangles = 0x0004,
+    /// `GL_QUADS`
+    #[default]
+    Quads = 0x0007,
+}
+
+/// Vertex index type, OpenGL ES 2.0 only supports 16 bit indices
+#[cfg(not(feature = "graphics_api_opengl_es2"))]
+pub(crate) type VertexIndex = u32;
+#[cfg(feature = "graphics_api_opengl_es2")]
+pub(crate) type VertexIndex = u16;
+
+/// Vertex index type, as passed to glDrawElements
+#[cfg(not(feature = "graphics_api_opengl_es2"))]
+const VERTEX_INDEX_TYPE: AttribType = AttribType::UnsignedInt;
+#[cfg(feature = "graphics_api_opengl_es2")]
+const VERTEX_INDEX_TYPE: AttribType = AttribType::UnsignedShort;
+
+/// Vertex indices as buffer data
+#[cfg(not(feature = "graphics_api_opengl_es2"))]
+fn index_data(indices: &[VertexIndex]) -> BufferData<'_> {
+    BufferData::U32(indices)
+}
+#[cfg(feature = "graphics_api_opengl_es2")]
+fn index_data(indices: &[VertexIndex]) -> BufferData<'_> {
+    BufferData::U16(indices)
+}
+
+/// Vertex attributes of the vertex buffers: shader location, components, type and normalization
+/// NOTE: Order matches `VertexBuffer::vbo_id`, the default shader locations are used
+#[allow(clippy::cast_possible_truncation)]
+const VERTEX_ATTRIBS: [(u32, i32, AttribType, bool); 4] = [
+    (RL_DEFAULT_SHADER_ATTRIB_LOCATION_POSITION as u32, 3, AttribType::Float, false),
+    (RL_DEFAULT_SHADER_ATTRIB_LOCATION_TEXCOORD as u32, 2, AttribType::Float, false),
+    (RL_DEFAULT_SHADER_ATTRIB_LOCATION_NORMAL as u32, 3, AttribType::Float, false),
+    (RL_DEFAULT_SHADER_ATTRIB_LOCATION_COLOR as u32, 4, AttribType::UnsignedByte, true),
+];
+
+/// Dynamic vertex buffers (position + texcoords + colors + indices arrays)
+#[derive(Debug)]
+pub(crate) struct VertexBuffer {
+    /// Number of elements in the buffer (QUADS)
+    element_count: usize,
+
+    /// Vertex position (XYZ - 3 components per vertex) (shader-location = 0)
+    vertices: Vec<f32>,
+    /// Vertex texture coordinates (UV - 2 components per vertex) (shader-location = 1)
+    texcoords: Vec<f32>,
+    /// Vertex normal (XYZ - 3 components per vertex) (shader-location = 2)
+    normals: Vec<f32>,
+    /// Vertex colors (RGBA - 4 components per vertex) (shader-location = 3)
+    colors: Vec<u8>,
+
+    /// Vertex indices (in case vertex data comes indexed) (6 indices per quad)
+    indices: Vec<VertexIndex>,
+
+    /// OpenGL Vertex Array Object id
+    vao_id: u32,
+    /// OpenGL Vertex Buffer Objects id (5 types of vertex data)
+    vbo_id: [u32; 5],
+}
+
+impl VertexBuffer {
+    /// Allocate vertex buffer for a number of quads, indices are initialized for quads drawing
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(element_count: usize) -> Self {
+        let vertex_count = element_count*4;
+        Self {
+            element_count,
+            vertices: vec![0.0; vertex_count*3],
+            texcoords: vec![0.0; vertex_count*2],
+            normals: vec![0.0; vertex_count*3],
+            colors: vec![0; vertex_count*4],
+            // Indices can be initialized right now, quads are drawn as two triangles
+            indices: (0..element_count)
+                .flat_map(|k| {
+                    let i = (k*4) as VertexIndex;
+                    [i, i + 1, i + 2, i, i + 2, i + 3]
+                })
+                .collect(),
+            vao_id: 0,
+            vbo_id: [0; 5],
+        }
+    }
+
+    /// Maximum number of vertices in the buffer
+    #[inline]
+    fn vertex_capacity(&self) -> usize {
+        self.element_count*4
+    }
+
+    /// Upload vertex buffer data into GPU and setup vertex attributes
+    /// NOTE: Vertex data is dynamic, indices never change
+    fn load(&mut self, gl: &mut dyn GlApi) {
+        // Initialize Quads VAO (if supported)
+        self.vao_id = gl.gen_vertex_array().unwrap_or(0);
+        if self.vao_id != 0 {
+            gl.bind_vertex_array(self.vao_id);
+        }
+
+        let data = [
+            BufferData::F32(&self.vertices),
+            BufferData::F32(&self.texcoords),
+            BufferData::F32(&self.normals),
+            BufferData::U8(&self.colors),
+        ];
+        for (vbo_id, data) in self.vbo_id.iter_mut().zip(data) {
+            *vbo_id = gl.gen_buffer();
+            gl.bind_buffer(BufferTarget::Array, *vbo_id);
+            gl.buffer_data(BufferTarget::Array, data, BufferUsage::DynamicDraw);
+        }
+
+        // Fill index buffer
+        self.vbo_id[4] = gl.gen_buffer();
+        gl.bind_buffer(BufferTarget::ElementArray, self.vbo_id[4]);
+        gl.buffer_data(BufferTarget::ElementArray, index_data(&self.indices), BufferUsage::StaticDraw);
+
+        self.bind_attribs(gl);
+
+        // Unbind the current VAO
+        if self.vao_id != 0 {
+            gl.bind_vertex_array(0);
+        }
+    }
+
+    /// Bind vertex buffers and setup vertex attributes
+    /// NOTE: Recorded into the VAO when supported, required before every draw otherwise
+    fn bind_attribs(&self, gl: &mut dyn GlApi) {
+        for (&vbo_id, (location, size, ty, normalized)) in self.vbo_id.iter().zip(VERTEX_ATTRIBS) {
+            gl.bind_buffer(BufferTarget::Array, vbo_id);
+            gl.vertex_attrib_pointer(location, size, ty, normalized, 0, 0);
+            gl.enable_vertex_attrib_array(location);
+        }
+        gl.bind_buffer(BufferTarget::ElementArray, self.vbo_id[4]);
+    }
+
+    /// Update the used portion of vertex buffer data
+    fn update(&self, gl: &mut dyn GlApi, vertex_count: usize) {
+        let data = [
+            BufferData::F32(&self.vertices[..vertex_count*3]),
+            BufferData::F32(&self.texcoords[..vertex_count*2]),
+            BufferData::F32(&self.normals[..vertex_count*3]),
+            BufferData::U8(&self.colors[..vertex_count*4]),
+        ];
+        for (&vbo_id, data) in self.vbo_id.iter().zip(data) {
+            gl.bind_buffer(BufferTarget::Array, vbo_id);
+            gl.buffer_sub_data(BufferTarget::Array, 0, data);
+        }
+    }
+
+    /// Unload vertex buffer GPU data (VAO/VBOs)
+    fn unload(&mut self, gl: &mut dyn GlApi) {
+        if self.vao_id != 0 {
+            gl.bind_vertex_array(self.vao_id);
+            for (location, ..) in VERTEX_ATTRIBS {
+                gl.disable_vertex_attrib_array(location);
+            }
+            gl.bind_vertex_array(0);
+        }
+
+        // Delete VBOs from GPU (VRAM)
+        for vbo_id in &mut self.vbo_id {
+            gl.delete_buffer(std::mem::take(vbo_id));
+        }
+
+        // Delete VAOs from GPU (VRAM)
+        if self.vao_id != 0 {
+            gl.delete_vertex_array(std::mem::take(&mut self.vao_id));
+        }
+    }
+}
+
+/// Draw call type
+/// NOTE: Texture and shader changes register a new draw, other state-change-related elements are not
+/// used at this moment (vaoId, matrices), raylib just forces a batch draw call if any
+/// of those state-change happens (this is done in core module)
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DrawCall {
+    /// Drawing mode: LINES, TRIANGLES, QUADS
+    mode: DrawMode,
+    /// Number of vertex of the draw
+    vertex_count: usize,
+    /// Number of vertex required for index alignment (LINES, TRIANGLES)
+    vertex_alignment: usize,
+    /// Texture id to be used on the draw -> Use to create new draw call if changes
+    texture_id: u32,
+    /// Shader id to be used on the draw -> Use to create new draw call if changes
+    shader_id: u32,
+}
+
+impl DrawCall {
+    /// Number of vertices required to keep the next draw aligned to quads indexing
+    /// NOTE: Lines and triangles are drawn with glDrawArrays, but the vertex counter
+    /// must stay a multiple of 4 for the quads index buffer to remain valid
+    fn alignment(&self) -> usize {
+        match self.mode {
+            DrawMode::Lines if self.vertex_count < 4 => self.vertex_count,
+            DrawMode::Lines => self.vertex_count%4,
+            DrawMode::Triangles if self.vertex_count < 4 => 1,
+            DrawMode::Triangles => 4 - self.vertex_count%4,
+            DrawMode::Quads => 0,
+        }
+    }
+
+    /// Check if a draw with `texture_id` and `shader_id` must start a new draw call, returns the reason if so
+    /// NOTE: Draw calls are keyed by texture and shader, changing any of them starts a new draw call
+    fn break_reason(&self, texture_id: u32, shader_id: u32) -> Option<FlushReason> {
+        if self.shader_id != shader_id {
+            Some(FlushReason::ShaderChange { old_id: self.shader_id, new_id: shader_id })
+        } else if self.texture_id != texture_id {
+            Some(FlushReason::TextureChange { old_id: self.texture_id, new_id: texture_id })
+        } else {
+            None
+        }
+    }
+}
+
+/// rlRenderBatch type
+#[derive(Debug)]
+pub(crate) struct RenderBatch {
+    /// Number of vertex buffers (multi-buffering support)
+    buffer_count: usize,
+    /// Current buffer tracking in case of multi-buffering
+    current_buffer: usize,
+    /// Dynamic buffer(s) for vertex data
+    vertex_buffer: Vec<VertexBuffer>,
+
+    /// Draw calls array, depends on textureId
+    draws: Vec<DrawCall>,
+    /// Draw calls counter
+    draw_counter: usize,
+    /// Current depth value for next draw
+    current_depth: f32,
+}
+
+impl RenderBatch {
+    /// Allocate render batch CPU buffers
+    /// NOTE: Draw calls are initialized for quads drawing with the provided texture
+    pub(crate) fn new(buffer_count: usize, buffer_elements: usize, texture_id: u32) -> Self {
+        Self {
+            buffer_count,
+            current_buffer: 0,
+            vertex_buffer: (0..buffer_count).map(|_| VertexBuffer::new(buffer_elements)).collect(),
+            draws: vec![DrawCall { mode: DrawMode::Quads, vertex_count: 0, vertex_alignment: 0, texture_id, shader_id: 0 }; RL_DEFAULT_BATCH_DRAWCALLS],
+            draw_counter: 1, // Reset draws counter
+            current_depth: -1.0, // Reset depth value
+        }
+    }
+
+    /// Get current draw call
+    #[inline]
+    fn draw(&self) -> &DrawCall {
+        &self.draws[self.draw_counter - 1]
+    }
+
+    /// Get current draw call
+    #[inline]
+    fn draw_mut(&mut self) -> &mut DrawCall {
+        &mut self.draws[self.draw_counter - 1]
+    }
+
+    /// Load render batch: CPU buffers allocation and GPU buffers creation
+    pub(crate) fn load(gl: &mut dyn GlApi, buffer_count: usize, buffer_elements: usize, texture_id: u32) -> Self {
+        let mut batch = Self::new(buffer_count, buffer_elements, texture_id);
+        for buffer in &mut batch.vertex_buffer {
+            buffer.load(gl);
+        }
+        gl.bind_buffer(BufferTarget::Array, 0);
+        gl.bind_buffer(BufferTarget::ElementArray, 0);
+        batch
+    }
+
+    /// Unload render batch GPU buffers
+    /// NOTE: CPU buffers are freed on drop
+    pub(crate) fn unload(&mut self, gl: &mut dyn GlApi) {
+        // Unbind everything
+        gl.bind_buffer(BufferTarget::Array, 0);
+        gl.bind_buffer(BufferTarget::ElementArray, 0);
+
+        for buffer in &mut self.vertex_buffer {
+            buffer.unload(gl);
+        }
+    }
+
+    /// Get current vertex buffer
+    #[inline]
+    fn buffer(&self) -> &VertexBuffer {
+        &self.vertex_buffer[self.current_buffer]
+    }
+}
+
+/// OpenGL capabilities and extensions support
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ExtSupported {
+    /// OpenGL version of the context
+    pub version: GlVersion,
+    /// VAO support (OpenGL ES2 could not support VAO extension) (`GL_ARB_vertex_array_object`)
+    pub vao: bool,
+    /// Instancing supported (`GL_ANGLE_instanced_arrays`, `GL_EXT_draw_instanced` + `GL_EXT_instanced_arrays`)
+    pub instancing: bool,
+    /// NPOT textures full support (`GL_ARB_texture_non_power_of_two`, `GL_OES_texture_npot`)
+    pub tex_npot: bool,
+    /// Depth textures supported (`GL_ARB_depth_texture`, `GL_OES_depth_texture`)
+    pub tex_depth: bool,
+    /// Float textures support (32 bit per channel) (`GL_OES_texture_float`)
+    pub tex_float32: bool,
+    /// Half float textures support (16 bit per channel) (`GL_OES_texture_half_float`)
+    pub tex_float16: bool,
+    /// DXT texture compression support (`GL_EXT_texture_compression_s3tc`)
+    pub tex_comp_dxt: bool,
+    /// ETC1 texture compression support (`GL_OES_compressed_ETC1_RGB8_texture`)
+    pub tex_comp_etc1: bool,
+    /// ETC2/EAC texture compression support (`GL_ARB_ES3_compatibility`)
+    pub tex_comp_etc2: bool,
+    /// PVR texture compression support (`GL_IMG_texture_compression_pvrtc`)
+    pub tex_comp_pvrt: bool,
+    /// ASTC texture compression support (`GL_KHR_texture_compression_astc_hdr`, `GL_KHR_texture_compression_astc_ldr`)
+    pub tex_comp_astc: bool,
+    /// Clamp mirror wrap mode supported (`GL_EXT_texture_mirror_clamp`)
+    pub tex_mirror_clamp: bool,
+    /// Anisotropic texture filtering support (`GL_EXT_texture_filter_anisotropic`)
+    pub tex_anisotropic_filter: bool,
+    /// Compute shaders support (`GL_ARB_compute_shader`)
+    pub compute_shader: bool,
+    /// Shader storage buffer object support (`GL_ARB_shader_storage_buffer_object`)
+    pub ssbo: bool,
+    /// Maximum anisotropy level supported (minimum is 2.0)
+    pub max_anisotropy_level: f32,
+    /// Maximum bits for depth component
+    pub max_depth_bits: u32,
+    /// Maximum texture width and height supported
+    pub max_texture_size: u32,
+}
+
+impl ExtSupported {
+    /// Check OpenGL version, capabilities and supported extensions
+    /// NOTE: Core features of OpenGL 2.1+/ES 3.0 are assumed, OpenGL ES 2.0 requires extensions
+    fn load(gl: &mut dyn GlApi) -> Self {
+        let version = gl.version();
+
+        tracelog!(Info, "GL: OpenGL device information:");
+        tracelog!(Info, "    > Vendor:   {}", gl.get_string(gl::GL_VENDOR));
+        tracelog!(Info, "    > Renderer: {}", gl.get_string(gl::GL_RENDERER));
+        tracelog!(Info, "    > Version:  {}", gl.get_string(gl::GL_VERSION));
+        tracelog!(Info, "    > GLSL:     {}", gl.get_string(gl::GL_SHADING_LANGUAGE_VERSION));
+
+        let mut supported = |names: &[&str]| names.iter().any(|name| gl.is_extension_supported(name));
+        let es2 = version == GlVersion::GlES2_0;
+        let core = !es2 && version != GlVersion::Gl11;
+        let mut ext = Self {
+            version,
+            vao: core || supported(&["GL_OES_vertex_array_object"]),
+            instancing: core || supported(&["GL_ANGLE_instanced_arrays"]) || (supported(&["GL_EXT_draw_instanced"]) && supported(&["GL_EXT_instanced_arrays"])),
+            tex_npot: core || supported(&["GL_OES_texture_npot", "GL_ARB_texture_non_power_of_two"]),
+            tex_depth: core || supported(&["GL_OES_depth_texture", "GL_WEBGL_depth_texture"]),
+            // NOTE: Float textures are core since OpenGL 3.0, extensions are required on OpenGL ES 2.0
+            tex_float32: core || supported(&["GL_OES_texture_float"]),
+            tex_float16: core || supported(&["GL_OES_texture_half_float"]),
+            tex_comp_dxt: supported(&["GL_EXT_texture_compression_s3tc", "GL_WEBGL_compressed_texture_s3tc", "GL_WEBKIT_WEBGL_compressed_texture_s3tc"]),
+            tex_comp_etc1: supported(&["GL_OES_compressed_ETC1_RGB8_texture", "GL_WEBGL_compressed_texture_etc1"]),
+            tex_comp_etc2: version == GlVersion::GlES3_0 || supported(&["GL_ARB_ES3_compatibility"]),
+            tex_comp_pvrt: supported(&["GL_IMG_texture_compression_pvrtc"]),
+            tex_comp_astc: supported(&["GL_KHR_texture_compression_astc_hdr", "GL_KHR_texture_compression_astc_ldr"]),
+            tex_mirror_clamp: supported(&["GL_EXT_texture_mirror_clamp"]),
+            tex_anisotropic_filter: supported(&["GL_EXT_texture_filter_anisotropic"]),
+            compute_shader: version == GlVersion::Gl43,
+            ssbo: version == GlVersion::Gl43,
+            max_anisotropy_level: 0.0,
+            max_depth_bits: if !es2 { 32 } else if supported(&["GL_OES_depth24"]) { 24 } else { 16 },
+            max_texture_size: u32::try_from(gl.get_integer(gl::GL_MAX_TEXTURE_SIZE)).unwrap_or(0),
+        };
+        if ext.tex_anisotropic_filter {
+            ext.max_anisotropy_level = gl.get_float(gl::GL_MAX_TEXTURE_MAX_ANISOTROPY_EXT);
+        }
+
+        tracelog!(Info, "GL: Supported extensions ({version:?}):");
+        if ext.vao { tracelog!(Info, "    > VAO: supported"); } else { tracelog!(Warning, "    > VAO: not supported, vertex attributes bound on every draw"); }
+        if ext.instancing { tracelog!(Info, "    > Instancing: supported"); }
+        if ext.tex_npot { tracelog!(Info, "    > NPOT textures: full support"); } else { tracelog!(Warning, "    > NPOT textures: limited support (no-mipmaps, no-repeat)"); }
+        if ext.tex_depth { tracelog!(Info, "    > Depth textures: supported ({} bits)", ext.max_depth_bits); }
+        if ext.tex_float32 { tracelog!(Info, "    > Float textures: supported"); }
+        if ext.tex_float16 { tracelog!(Info, "    > Half float textures: supported"); }
+        if ext.tex_comp_dxt { tracelog!(Info, "    > DXT compressed textures: supported"); }
+        if ext.tex_comp_etc1 { tracelog!(Info, "    > ETC1 compressed textures: supported"); }
+        if ext.tex_comp_etc2 { tracelog!(Info, "    > ETC2/EAC compressed textures: supported"); }
+        if ext.tex_comp_pvrt { tracelog!(Info, "    > PVRT compressed textures: supported"); }
+        if ext.tex_comp_astc { tracelog!(Info, "    > ASTC compressed textures: supported"); }
+        if ext.tex_anisotropic_filter { tracelog!(Info, "    > Anisotropic textures filtering: supported (max: {:.0}X)", ext.max_anisotropy_level); }
+        if ext.tex_mirror_clamp { tracelog!(Info, "    > Mirror clamp wrap texture mode: supported"); }
+        if ext.compute_shader { tracelog!(Info, "    > Compute shaders: supported"); }
+        if ext.ssbo { tracelog!(Info, "    > Shader storage buffer objects: supported"); }
+        tracelog!(Info, "    > Max texture size: {}", ext.max_texture_size);
+        ext
+    }
+
+    /// Get OpenGL internal formats and data type from raylib `PixelFormat`
+    /// NOTE: Compressed formats have no format and type (0), `None` if not supported by the GPU
+    fn gl_texture_formats(self, format: PixelFormat) -> Option<(u32, u32, u32)> {
+        use gl::*;
+        match format {
+            PixelFormat::UncompressedGrayscale => Some((GL_R8, GL_RED, GL_UNSIGNED_BYTE)),
+            PixelFormat::UncompressedGrayAlpha => Some((GL_RG8, GL_RG, GL_UNSIGNED_BYTE)),
+            PixelFormat::UncompressedR5G6B5 => Some((GL_RGB565, GL_RGB, GL_UNSIGNED_SHORT_5_6_5)),
+            PixelFormat::UncompressedR8G8B8 => Some((GL_RGB8, GL_RGB, GL_UNSIGNED_BYTE)),
+            PixelFormat::UncompressedR5G5B5A1 => Some((GL_RGB5_A1, GL_RGBA, GL_UNSIGNED_SHORT_5_5_5_1)),
+            PixelFormat::UncompressedR4G4B4A4 => Some((GL_RGBA4, GL_RGBA, GL_UNSIGNED_SHORT_4_4_4_4)),
+            PixelFormat::UncompressedR8G8B8A8 => Some((GL_RGBA8, GL_RGBA, GL_UNSIGNED_BYTE)),
+            PixelFormat::UncompressedR32 if self.tex_float32 => Some((GL_R32F, GL_RED, GL_FLOAT)),
+            PixelFormat::UncompressedR32G32A32 if self.tex_float32 => Some((GL_RGB32F, GL_RGB, GL_FLOAT)),
+            PixelFormat::UncompressedR32G32A32A32 if self.tex_float32 => Some((GL_RGBA32F, GL_RGBA, GL_FLOAT)),
+            PixelFormat::UncompressedR16 if self.tex_float16 => Some((GL_R16F, GL_RED, GL_HALF_FLOAT)),
+            PixelFormat::UncompressedR16G16B16 if self.tex_float16 => Some((GL_RGB16F, GL_RGB, GL_HALF_FLOAT)),
+            PixelFormat::UncompressedR16G16B16A16 if self.tex_float16 => Some((GL_RGBA16F, GL_RGBA, GL_HALF_FLOAT)),
+            PixelFormat::CompressedDxt1RGB if self.tex_comp_dxt => Some((GL_COMPRESSED_RGB_S3TC_DXT1_EXT, 0, 0)),
+            PixelFormat::CompressedDxt1RGBA if self.tex_comp_dxt => Some((GL_COMPRESSED_RGBA_S3TC_DXT1_EXT, 0, 0)),
+            PixelFormat::CompressedDxt3RGBA if self.tex_comp_dxt => Some((GL_COMPRESSED_RGBA_S3TC_DXT3_EXT, 0, 0)),
+            PixelFormat::CompressedDxt5RGBA if self.tex_comp_dxt => Some((GL_COMPRESSED_RGBA_S3TC_DXT5_EXT, 0, 0)),
+            PixelFormat::CompressedEtc1RGB if self.tex_comp_etc1 => Some((GL_ETC1_RGB8_OES, 0, 0)),
+            PixelFormat::CompressedEtc2RGB if self.tex_comp_etc2 => Some((GL_COMPRESSED_RGB8_ETC2, 0, 0)),
+            PixelFormat::CompressedEtc2EacRGBA if self.tex_comp_etc2 => Some((GL_COMPRESSED_RGBA8_ETC2_EAC, 0, 0)),
+            PixelFormat::CompressedPvrtRGB if self.tex_comp_pvrt => Some((GL_COMPRESSED_RGB_PVRTC_4BPPV1_IMG, 0, 0)),
+            PixelFormat::CompressedPvrtRGBA if self.tex_comp_pvrt => Some((GL_COMPRESSED_RGBA_PVRTC_4BPPV1_IMG, 0, 0)),
+            PixelFormat::CompressedAstc4x4RGBA if self.tex_comp_astc => Some((GL_COMPRESSED_RGBA_ASTC_4X4_KHR, 0, 0)),
+            PixelFormat::CompressedAstc8x8RGBA if self.tex_comp_astc => Some((GL_COMPRESSED_RGBA_ASTC_8X8_KHR, 0, 0)),
+            _ => None,
+        }
+    }
+}
+
+/// Swizzle masks of single channel formats, sampled as gray
+#[allow(clippy::cast_possible_wrap)]
+const GRAYSCALE_SWIZZLE: [i32; 4] = [gl::GL_RED as i32, gl::GL_RED as i32, gl::GL_RED as i32, gl::GL_ONE as i32];
+#[allow(clippy::cast_possible_wrap)]
+const GRAY_ALPHA_SWIZZLE: [i32; 4] = [gl::GL_RED as i32, gl::GL_RED as i32, gl::GL_RED as i32, gl::GL_GREEN as i32];
+
+/// Load texture data into GPU, mipmaps data must be consecutive (size reduced by half every level)
+fn load_texture(gl: &mut dyn GlApi, extensions: ExtSupported, data: Option<&[u8]>, width: usize, height: usize, format: PixelFormat, mipmap_count: usize) -> Result<GlTextureID, TextureError> {
+    gl.bind_texture(0); // Free any old binding
+
+    // Check texture format support by the GPU
+    let Some((internal_format, gl_format, gl_type)) = extensions.gl_texture_formats(format) else {
+        tracelog!(Warning, "GL: {format:?} texture format not supported");
+        return Err(TextureError::UnsupportedFormat(format));
+    };
+
+    let mipmap_count = mipmap_count.max(1);
+    let mip_levels = || std::iter::successors(Some((width, height)), |&(w, h)| Some(((w/2).max(1), (h/2).max(1)))).take(mipmap_count);
+    if let Some(data) = data {
+        let expected = mip_levels().map(|(w, h)| format.data_size(w, h)).sum();
+        if data.len() < expected {
+            tracelog!(Warning, "TEXTURE: Failed to load texture, data size ({}) smaller than required ({expected})", data.len());
+            return Err(TextureError::DataTooSmall { expected, found: data.len() });
+        }
+    }
+
+    gl.pixel_store_unpack_alignment(1);
+
+    let id = gl.gen_texture(); // Generate texture id
+    if id == 0 {
+        tracelog!(Warning, "TEXTURE: Failed to load texture");
+        return Err(TextureError::LoadFailed);
+    }
+    gl.bind_texture(id);
+
+    // Load the different mipmap levels
+    let mut mip_offset = 0;
+    for (level, (mip_width, mip_height)) in mip_levels().enumerate() {
+        let mip_size = format.data_size(mip_width, mip_height);
+        let mip_data = data.map(|data| &data[mip_offset..mip_offset + mip_size]);
+
+        tracelog!(Debug, "TEXTURE: Load mipmap level {level} ({mip_width} x {mip_height}), size: {mip_size}, offset: {mip_offset}");
+
+        if format.is_compressed() {
+            gl.compressed_tex_image_2d(level, internal_format, mip_width, mip_height, mip_size, mip_data);
+        } else {
+            gl.tex_image_2d(level, internal_format, mip_width, mip_height, gl_format, gl_type, mip_data);
+        }
+
+        match format {
+            PixelFormat::UncompressedGrayscale => gl.tex_parameteriv(gl::GL_TEXTURE_SWIZZLE_RGBA, &GRAYSCALE_SWIZZLE),
+            PixelFormat::UncompressedGrayAlpha => gl.tex_parameteriv(gl::GL_TEXTURE_SWIZZLE_RGBA, &GRAY_ALPHA_SWIZZLE),
+            _ => {}
+        }
+
+        mip_offset += mip_size;
+    }
+
+    // Texture parameters configuration
+    // NOTE: glTexParameteri does NOT affect texture uploading, just the way it's used
+    gl.tex_parameteri(gl::GL_TEXTURE_WRAP_S, gl::GL_REPEAT); // Set texture to repeat on x-axis
+    gl.tex_parameteri(gl::GL_TEXTURE_WRAP_T, gl::GL_REPEAT); // Set texture to repeat on y-axis
+
+    if mipmap_count > 1 {
+        // Activate Trilinear filtering if mipmaps are available
+        gl.tex_parameteri(gl::GL_TEXTURE_MAG_FILTER, gl::GL_LINEAR);
+        gl.tex_parameteri(gl::GL_TEXTURE_MIN_FILTER, gl::GL_LINEAR_MIPMAP_LINEAR);
+    } else {
+        // Magnification and minification filters
+        gl.tex_parameteri(gl::GL_TEXTURE_MAG_FILTER, gl::GL_NEAREST); // Alternative: GL_LINEAR
+        gl.tex_parameteri(gl::GL_TEXTURE_MIN_FILTER, gl::GL_NEAREST); // Alternative: GL_LINEAR
+    }
+
+    // At this point we have the texture loaded in GPU and texture parameters configured
+    // NOTE: If mipmaps were not in data, they are not generated automatically
+    gl.bind_texture(0); // Unbind current texture
+
+    tracelog!(Info, "TEXTURE: [ID {id}] Texture loaded successfully ({width}x{height} | {format:?} | {mipmap_count} mipmaps)");
+    Ok(id)
+}
+
+/// Framebuffer attachment type
+/// NOTE: By default up to 8 color channels defined, but it can be more
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum FramebufferAttachType {
+    /** Framebuffer attachment type: color 0 */ ColorChannel0 = 0,
+    /** Framebuffer attachment type: color 1 */ ColorChannel1 = 1,
+    /** Framebuffer attachment type: color 2 */ ColorChannel2 = 2,
+    /** Framebuffer attachment type: color 3 */ ColorChannel3 = 3,
+    /** Framebuffer attachment type: color 4 */ ColorChannel4 = 4,
+    /** Framebuffer attachment type: color 5 */ ColorChannel5 = 5,
+    /** Framebuffer attachment type: color 6 */ ColorChannel6 = 6,
+    /** Framebuffer attachment type: color 7 */ ColorChannel7 = 7,
+    /** Framebuffer attachment type: depth   */ Depth = 100,
+    /** Framebuffer attachment type: stencil */ Stencil = 200,
+}
+
+/// Framebuffer texture attachment type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum FramebufferAttachTextureType {
+    /** Framebuffer texture attachment type: cubemap, +X side */ CubemapPositiveX = 0,
+    /** Framebuffer texture attachment type: cubemap, -X side */ CubemapNegativeX = 1,
+    /** Framebuffer texture attachment type: cubemap, +Y side */ CubemapPositiveY = 2,
+    /** Framebuffer texture attachment type: cubemap, -Y side */ CubemapNegativeY = 3,
+    /** Framebuffer texture attachment type: cubemap, +Z side */ CubemapPositiveZ = 4,
+    /** Framebuffer texture attachment type: cubemap, -Z side */ CubemapNegativeZ = 5,
+    /** Framebuffer texture attachment type: texture2d        */ Texture2D = 100,
+    /** Framebuffer texture attachment type: renderbuffer     */ Renderbuffer = 200,
+}
+
+/// GPU object released by its owner drop, unloaded on the next batch draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GlObject {
+    Texture(GlTextureID),
+    Renderbuffer(GlTextureID),
+    Framebuffer(GlFrameBufferID),
+    Program(u32),
+}
+
+/// Batch draw uniform locations of a shader
+#[derive(Debug, Clone, Copy)]
+struct BatchLocations {
+    /// Model-View-Projection matrix
+    mvp: i32,
+    /// Diffuse color
+    color_diffuse: i32,
+    /// Texture unit 0 sampler
+    texture0: i32,
+}
+
+/// OpenGL version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GlVersion {
+    /// OpenGL 1.1
+    Gl11,
+    /// OpenGL 2.1 (GLSL 120)
+    Gl21,
+    /// OpenGL 3.3 (GLSL 330)
+    Gl33,
+    /// OpenGL 4.3 (using GLSL 330)
+    Gl43,
+    /// OpenGL ES 2.0 (GLSL 100)
+    GlES2_0,
+    /// OpenGL ES 3.0 (GLSL 300 es)
+    GlES3_0,
+}
+
+/// Shader type (equivalent to OpenGL)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ShaderType {
+    /// `GL_FRAGMENT_SHADER`
+    Fragment = 0x8B30,
+    /// `GL_VERTEX_SHADER`
+    Vertex = 0x8B31,
+    /// `GL_COMPUTE_SHADER`
+    Compute = 0x91B9,
+}
+
+impl std::fmt::Display for ShaderType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Fragment => "fragment",
+            Self::Vertex => "vertex",
+            Self::Compute => "compute",
+        })
+    }
+}
+
+/// Shader compilation/linking error
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ShaderCompileError {
+    /// Shaders not supported by the OpenGL version
+    NotSupported(GlVersion),
+    /// Shader object could not be created
+    CreateFailed(ShaderType),
+    /// Shader failed to compile, with the compiler info log
+    Compile { kind: ShaderType, log: String },
+    /// Program object could not be created
+    CreateProgramFailed,
+    /// Program failed to link, with the linker info log
+    Link { log: String },
+}
+
+impl std::fmt::Display for ShaderCompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotSupported(version) => write!(f, "shaders not supported by {version:?}"),
+            Self::CreateFailed(kind) => write!(f, "failed to create {kind} shader"),
+            Self::Compile { kind, log } => write!(f, "failed to compile {kind} shader: {log}"),
+            Self::CreateProgramFailed => f.write_str("failed to create shader program"),
+            Self::Link { log } => write!(f, "failed to link shader program: {log}"),
+        }
+    }
+}
+
+impl std::error::Error for ShaderCompileError {}
+
+/// Default vertex shader, GLSL 120 (OpenGL 2.1)
+const DEFAULT_VERTEX_SHADER_120: &str = "#version 120
+attribute vec3 vertexPosition;
+attribute vec2 vertexTexCoord;
+attribute vec4 vertexColor;
+varying vec2 fragTexCoord;
+varying vec4 fragColor;
+uniform mat4 mvp;
+void main()
+{
+    fragTexCoord = vertexTexCoord;
+    fragColor = vertexColor;
+    gl_Position = mvp*vec4(vertexPosition, 1.0);
+}
+";
+/// Default fragment shader, GLSL 120 (OpenGL 2.1)
+const DEFAULT_FRAGMENT_SHADER_120: &str = "#version 120
+varying vec2 fragTexCoord;
+varying vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+void main()
+{
+    vec4 texelColor = texture2D(texture0, fragTexCoord);
+    gl_FragColor = texelColor*colDiffuse*fragColor;
+}
+";
+
+/// Default vertex shader, GLSL 330 (OpenGL 3.3)
+const DEFAULT_VERTEX_SHADER_330: &str = "#version 330
+in vec3 vertexPosition;
+in vec2 vertexTexCoord;
+in vec4 vertexColor;
+out vec2 fragTexCoord;
+out vec4 fragColor;
+uniform mat4 mvp;
+void main()
+{
+    fragTexCoord = vertexTexCoord;
+    fragColor = vertexColor;
+    gl_Position = mvp*vec4(vertexPosition, 1.0);
+}
+";
+/// Default fragment shader, GLSL 330 (OpenGL 3.3)
+const DEFAULT_FRAGMENT_SHADER_330: &str = "#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+out vec4 finalColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+void main()
+{
+    vec4 texelColor = texture(texture0, fragTexCoord);
+    finalColor = texelColor*colDiffuse*fragColor;
+}
+";
+
+/// Default vertex shader, GLSL 100 (OpenGL ES 2.0)
+const DEFAULT_VERTEX_SHADER_100: &str = "#version 100
+precision mediump float;
+attribute vec3 vertexPosition;
+attribute vec2 vertexTexCoord;
+attribute vec4 vertexColor;
+varying vec2 fragTexCoord;
+varying vec4 fragColor;
+uniform mat4 mvp;
+void main()
+{
+    fragTexCoord = vertexTexCoord;
+    fragColor = vertexColor;
+    gl_Position = mvp*vec4(vertexPosition, 1.0);
+}
+";
+/// Default fragment shader, GLSL 100 (OpenGL ES 2.0)
+const DEFAULT_FRAGMENT_SHADER_100: &str = "#version 100
+precision mediump float;
+varying vec2 fragTexCoord;
+varying vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+void main()
+{
+    vec4 texelColor = texture2D(texture0, fragTexCoord);
+    gl_FragColor = texelColor*colDiffuse*fragColor;
+}
+";
+
+/// Default vertex shader, GLSL 300 es (OpenGL ES 3.0)
+const DEFAULT_VERTEX_SHADER_300_ES: &str = "#version 300 es
+precision mediump float;
+in vec3 vertexPosition;
+in vec2 vertexTexCoord;
+in vec4 vertexColor;
+out vec2 fragTexCoord;
+out vec4 fragColor;
+uniform mat4 mvp;
+void main()
+{
+    fragTexCoord = vertexTexCoord;
+    fragColor = vertexColor;
+    gl_Position = mvp*vec4(vertexPosition, 1.0);
+}
+";
+/// Default fragment shader, GLSL 300 es (OpenGL ES 3.0)
+const DEFAULT_FRAGMENT_SHADER_300_ES: &str = "#version 300 es
+precision mediump float;
+in vec2 fragTexCoord;
+in vec4 fragColor;
+out vec4 finalColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+void main()
+{
+    vec4 texelColor = texture(texture0, fragTexCoord);
+    finalColor = texelColor*colDiffuse*fragColor;
+}
+";
+
+/// Get default shader code (vertex, fragment) for an OpenGL version, `None` if shaders are not supported
+/// NOTE: Default shader attributes and uniforms names match `RL_DEFAULT_SHADER_*` names
+fn default_shader_code(version: GlVersion) -> Option<(&'static str, &'static str)> {
+    match version {
+        GlVersion::Gl11 => None,
+        GlVersion::Gl21 => Some((DEFAULT_VERTEX_SHADER_120, DEFAULT_FRAGMENT_SHADER_120)),
+        GlVersion::Gl33 | GlVersion::Gl43 => Some((DEFAULT_VERTEX_SHADER_330, DEFAULT_FRAGMENT_SHADER_330)),
+        GlVersion::GlES2_0 => Some((DEFAULT_VERTEX_SHADER_100, DEFAULT_FRAGMENT_SHADER_100)),
+        GlVersion::GlES3_0 => Some((DEFAULT_VERTEX_SHADER_300_ES, DEFAULT_FRAGMENT_SHADER_300_ES)),
+    }
+}
+
+/// Compile custom shader and return shader id
+fn compile_shader(gl: &mut dyn GlApi, source: &str, kind: ShaderType) -> Result<u32, ShaderCompileError> {
+    let id = gl.create_shader(kind);
+    if id == 0 {
+        tracelog!(Warning, "SHADER: Failed to create {kind} shader");
+        return Err(ShaderCompileError::CreateFailed(kind));
+    }
+    gl.shader_source(id, source);
+    gl.compile_shader(id);
+
+    if !gl.get_shader_compile_status(id) {
+        tracelog!(Warning, "SHADER: [ID {id}] Failed to compile {kind} shader code");
+        let log = gl.get_shader_info_log(id);
+        if !log.is_empty() {
+            tracelog!(Warning, "SHADER: [ID {id}] Compile error: {log}");
+        }
+        gl.delete_shader(id);
+        return Err(ShaderCompileError::Compile { kind, log });
+    }
+
+    tracelog!(Info, "SHADER: [ID {id}] {} shader compiled successfully", match kind {
+        ShaderType::Fragment => "Fragment",
+        ShaderType::Vertex => "Vertex",
+        ShaderType::Compute => "Compute",
+    });
+    Ok(id)
+}
+
+/// Load custom shader program
+/// NOTE: Default attributes are bound to their default locations before linking
+#[allow(clippy::cast_possible_truncation)]
+fn load_shader_program(gl: &mut dyn GlApi, vs_id: u32, fs_id: u32) -> Result<u32, ShaderCompileError> {
+    let program = gl.create_program();
+    if program == 0 {
+        tracelog!(Warning, "SHADER: Failed to create shader program");
+        return Err(ShaderCompileError::CreateProgramFailed);
+    }
+    gl.attach_shader(program, vs_id);
+    gl.attach_shader(program, fs_id);
+
+    // NOTE: Default attribute shader locations must be binded before linking
+    for (location, name) in [
+        (RL_DEFAULT_SHADER_ATTRIB_LOCATION_POSITION, RL_DEFAULT_SHADER_ATTRIB_NAME_POSITION),
+        (RL_DEFAULT_SHADER_ATTRIB_LOCATION_TEXCOORD, RL_DEFAULT_SHADER_ATTRIB_NAME_TEXCOORD),
+        (RL_DEFAULT_SHADER_ATTRIB_LOCATION_NORMAL, RL_DEFAULT_SHADER_ATTRIB_NAME_NORMAL),
+        (RL_DEFAULT_SHADER_ATTRIB_LOCATION_COLOR, RL_DEFAULT_SHADER_ATTRIB_NAME_COLOR),
+        (RL_DEFAULT_SHADER_ATTRIB_LOCATION_TANGENT, RL_DEFAULT_SHADER_ATTRIB_NAME_TANGENT),
+        (RL_DEFAULT_SHADER_ATTRIB_LOCATION_TEXCOORD2, RL_DEFAULT_SHADER_ATTRIB_NAME_TEXCOORD2),
+    ] {
+        gl.bind_attrib_location(program, location as u32, name);
+    }
+
+    // NOTE: If some attrib name is no found on the shader, it locations becomes -1
+    gl.link_program(program);
+
+    if !gl.get_program_link_status(program) {
+        tracelog!(Warning, "SHADER: [ID {program}] Failed to link shader program");
+        let log = gl.get_program_info_log(program);
+        if !log.is_empty() {
+            tracelog!(Warning, "SHADER: [ID {program}] Link error: {log}");
+        }
+        gl.delete_program(program);
+        return Err(ShaderCompileError::Link { log });
+    }
+
+    tracelog!(Info, "SHADER: [ID {program}] Program shader loaded successfully");
+    Ok(program)
+}
+
+/// Load shader program from vertex and fragment shaders code
+/// NOTE: Shaders are deleted once linked, only the program is kept
+fn load_shader_code(gl: &mut dyn GlApi, vs_code: &str, fs_code: &str) -> Result<u32, ShaderCompileError> {
+    let vs_id = compile_shader(gl, vs_code, ShaderType::Vertex)?;
+    let fs_id = compile_shader(gl, fs_code, ShaderType::Fragment).inspect_err(|_| gl.delete_shader(vs_id))?;
+    let program = load_shader_program(gl, vs_id, fs_id);
+
+    // Shaders are not required anymore once linked (or failed to)
+    if let Ok(id) = program {
+        gl.detach_shader(id, vs_id);
+        gl.detach_shader(id, fs_id);
+    }
+    gl.delete_shader(vs_id);
+    gl.delete_shader(fs_id);
+    program
+}
+
+/// Load default shader (just vertex positioning and texture coloring), returns shader id and locations
+/// NOTE: This shader program is used for internal buffers
+fn load_shader_default(gl: &mut dyn GlApi) -> Result<(u32, Vec<i32>), ShaderCompileError> {
+    let version = gl.version();
+    let Some((vs_code, fs_code)) = default_shader_code(version) else {
+        return Err(ShaderCompileError::NotSupported(version));
+    };
+    let id = load_shader_code(gl, vs_code, fs_code)?;
+
+    // NOTE: All locations must be reseted to -1 (no location)
+    let mut locs = vec![-1; RL_MAX_SHADER_LOCATIONS];
+
+    // Set default shader locations: attributes locations
+    locs[ShaderLocationIndex::VertexPosition as usize] = gl.get_attrib_location(id, RL_DEFAULT_SHADER_ATTRIB_NAME_POSITION);
+    locs[ShaderLocationIndex::VertexTexcoord01 as usize] = gl.get_attrib_location(id, RL_DEFAULT_SHADER_ATTRIB_NAME_TEXCOORD);
+    locs[ShaderLocationIndex::VertexColor as usize] = gl.get_attrib_location(id, RL_DEFAULT_SHADER_ATTRIB_NAME_COLOR);
+
+    // Set default shader locations: uniform locations
+    locs[ShaderLocationIndex::MatrixMvp as usize] = gl.get_uniform_location(id, RL_DEFAULT_SHADER_UNIFORM_NAME_MVP);
+    locs[ShaderLocationIndex::ColorDiffuse as usize] = gl.get_uniform_location(id, RL_DEFAULT_SHADER_UNIFORM_NAME_COLOR);
+    locs[ShaderLocationIndex::MAP_DIFFUSE as usize] = gl.get_uniform_location(id, RL_DEFAULT_SHADER_SAMPLER2D_NAME_TEXTURE0);
+
+    Ok((id, locs))
+}
+
+/// Color blending modes (pre-defined)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum BlendMode {
+    /// Blend textures considering alpha (default)
+    #[default]
+    Alpha,
+    /// Blend textures adding colors
+    Additive,
+    /// Blend textures multiplying colors
+    Multiplied,
+    /// Blend textures adding colors (alternative)
+    AddColors,
+    /// Blend textures subtracting colors (alternative)
+    SubtractColors,
+    /// Blend premultiplied textures considering alpha
+    AlphaPremultiply,
+    /// Blend textures using custom src/dst factors (use `set_blend_factors()`)
+    Custom,
+    /// Blend textures using custom rgb/alpha separate src/dst factors (use `set_blend_factors_separate()`)
+    CustomSeparate,
+}
+
+/// Face culling mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CullMode {
+    /// Cull front faces
+    Front,
+    /// Cull back faces (default)
+    #[default]
+    Back,
+}
+
+/// Matrix modes (equivalent to OpenGL)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u32)]
+pub enum MatrixMode {
+    /// `GL_MODELVIEW`
+    #[default]
+    Modelview = 0x1700,
+    /// `GL_PROJECTION`
+    Projection = 0x1701,
+    /// `GL_TEXTURE`
+    Texture = 0x1702,
+}
+
+/// Matrix modified by matrix operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum CurrentMatrix {
+    #[default]
+    Modelview,
+    Projection,
+    Transform,
+}
+
+#[derive(Debug)]
+pub(crate) struct RLGL {
+    /// OpenGL backend
+    gl: Box<dyn GlApi>,
+    /// Supported OpenGL capabilities and extensions
+    extensions: ExtSupported,
+    /// Batch draw uniform locations, by shader id
+    batch_locations: HashMap<u32, BatchLocations>,
+    /// Batch flush diagnostics
+    pub(crate) diagnostics: BatchDiagnostics,
+    /// Shader id used by next draws, 0 for default shader
+    pub(crate) current_shader_id: u32,
+    /// Current matrix mode
+    matrix_mode: MatrixMode,
+    /// Matrix modified by matrix operations
+    current_matrix: CurrentMatrix,
+    /// Default modelview matrix
+    modelview: Matrix,
+    /// Default projection matrix
+    projection: Matrix,
+    /// Transform matrix to be used with rlTranslate, rlRotate, rlScale
+    transform: Matrix,
+    /// Require transform matrix application to current draw-call vertex (if required)
+    transform_required: bool,
+    /// Matrix stack for push/pop
+    stack: ArrayVec<Matrix, RL_MAX_MATRIX_STACK_SIZE>,
+
+    /// Default internal render batch
+    pub(crate) batch: RenderBatch,
+    /// Current active render batch vertex counter (generic, used for all batches)
+    vertex_counter: usize,
+    /// Current active texture coordinate (added on glVertex*())
+    texcoord: [f32; 2],
+    /// Current active normal (added on glVertex*())
+    normal: [f32; 3],
+    /// Current active color (added on glVertex*())
+    color: [u8; 4],
+    /// Default texture used on shapes/poly drawing (required by shader)
+    default_texture_id: u32,
+    /// Default shader program id, supports vertex color and diffuse texture
+    default_shader_id: u32,
+    /// Default shader locations pointer to be used on rendering
+    default_shader_locs: Vec<i32>,
+    /// Current framebuffer width and height, set with the viewport
+    framebuffer_size: (i32, i32),
+    /// Stereo rendering flag
+    stereo_render: bool,
+    /// VR stereo rendering eyes projection matrices
+    projection_stereo: [Matrix; 2],
+    /// VR stereo rendering eyes view offset matrices
+    view_offset_stereo: [Matrix; 2],
+    /// Textures bound to texture units 1.. by shader samplers, 0 for free units
+    active_texture_ids: [u32; RL_DEFAULT_BATCH_MAX_TEXTURE_UNITS],
+
+    /// Blending mode active
+    blend_mode: BlendMode,
+    /// Blending source factor
+    blend_src_factor: u32,
+    /// Blending destination factor
+    blend_dst_factor: u32,
+    /// Blending equation
+    blend_equation: u32,
+    /// Blending source RGB factor
+    blend_src_factor_rgb: u32,
+    /// Blending destination RGB factor
+    blend_dst_factor_rgb: u32,
+    /// Blending source alpha factor
+    blend_src_factor_alpha: u32,
+    /// Blending destination alpha factor
+    blend_dst_factor_alpha: u32,
+    /// Blending equation for RGB
+    blend_equation_rgb: u32,
+    /// Blending equation for alpha
+    blend_equation_alpha: u32,
+    /// Custom blending factors changed since the custom mode was set
+    custom_blend_mode_modified: bool,
+
+    /// Depth testing enabled
+    depth_test: bool,
+    /// Depth buffer writing enabled
+    depth_mask: bool,
+    /// Face culling enabled
+    backface_culling: bool,
+    /// Faces culled when face culling is enabled
+    cull_mode: CullMode,
+    /// Polygons drawn as lines
+    wire_mode: bool,
+    /// Line antialiasing enabled
+    smooth_lines: bool,
+
+    /// GPU objects released on drop, handed to their owners
+    unload_sender: Sender<GlObject>,
+    /// GPU objects pending to be unloaded
+    unload_receiver: Receiver<GlObject>,
+}
+
+impl Default for RLGL {
+    fn default() -> Self {
+        Self::new(Box::new(HeadlessGl::default()))
+    }
+}
+
+impl RLGL {
+    /// Initialize rlgl: OpenGL extensions, default buffers and textures
+    fn new(mut gl: Box<dyn GlApi>) -> Self {
+        let extensions = ExtSupported::load(&mut *gl);
+
+        // Init default white texture
+        let pixels = [255u8; 4]; // 1 pixel RGBA (4 bytes)
+        let default_texture_id = load_texture(&mut *gl, extensions, Some(&pixels), 1, 1, PixelFormat::UncompressedR8G8B8A8, 1)
+            .inspect(|id| tracelog!(Info, "TEXTURE: [ID {id}] Default texture loaded successfully"))
+            .inspect_err(|_| tracelog!(Warning, "TEXTURE: Failed to load default texture"))
+            .unwrap_or(0);
+
+        // Init default Shader (customized for GL 3.3 and ES2)
+        let (default_shader_id, default_shader_locs) = load_shader_default(&mut *gl)
+            .inspect(|(id, _)| tracelog!(Info, "SHADER: [ID {id}] Default shader loaded successfully"))
+            .inspect_err(|_| tracelog!(Warning, "SHADER: Failed to load default shader"))
+            .unwrap_or_else(|_| (0, vec![-1; RL_MAX_SHADER_LOCATIONS]));
+
+        // Init default vertex arrays buffers
+        let batch = RenderBatch::load(&mut *gl, RL_DEFAULT_BATCH_BUFFERS, RL_DEFAULT_BATCH_BUFFER_ELEMENTS, default_texture_id);
+        let (unload_sender, unload_receiver) = mpsc::channel();
+
+        // Init state: Blending mode
+        gl.blend_func(gl::GL_SRC_ALPHA, gl::GL_ONE_MINUS_SRC_ALPHA); // Color blending function (how colors are mixed)
+        gl.enable(gl::GL_BLEND); // Enable color blending (required to work with transparencies)
+
+        // Init state: Depth test
+        gl.depth_func(gl::GL_LEQUAL); // Type of depth testing to apply
+        gl.disable(gl::GL_DEPTH_TEST); // Disable depth testing for 2D (only used for 3D)
+
+        // Init state: Culling
+        // NOTE: All shapes/models triangles are drawn CCW
+        gl.cull_face(gl::GL_BACK); // Cull the back face (default)
+        gl.front_face(gl::GL_CCW); // Front face are defined counter clockwise (default)
+        gl.enable(gl::GL_CULL_FACE); // Enable backface culling
+
+        Self {
+            gl,
+            extensions,
+            batch_locations: HashMap::new(),
+            diagnostics: BatchDiagnostics::default(),
+            current_shader_id: 0,
+            matrix_mode: MatrixMode::Modelview,
+            current_matrix: CurrentMatrix::Modelview,
+            modelview: Matrix::IDENTITY,
+            projection: Matrix::IDENTITY,
+            transform: Matrix::IDENTITY,
+            transform_required: false,
+            stack: ArrayVec::new(),
+            batch,
+            vertex_counter: 0,
+            texcoord: [0.0; 2],
+            normal: [0.0, 0.0, 1.0],
+            color: [255; 4],
+            default_texture_id,
+            default_shader_id,
+            default_shader_locs,
+            framebuffer_size: (0, 0),
+            stereo_render: false,
+            projection_stereo: [Matrix::IDENTITY; 2],
+            view_offset_stereo: [Matrix::IDENTITY; 2],
+            active_texture_ids: [0; RL_DEFAULT_BATCH_MAX_TEXTURE_UNITS],
+            blend_mode: BlendMode::Alpha,
+            blend_src_factor: gl::GL_SRC_ALPHA,
+            blend_dst_factor: gl::GL_ONE_MINUS_SRC_ALPHA,
+            blend_equation: gl::GL_FUNC_ADD,
+            blend_src_factor_rgb: gl::GL_SRC_ALPHA,
+            blend_dst_factor_rgb: gl::GL_ONE_MINUS_SRC_ALPHA,
+            blend_src_factor_alpha: gl::GL_ONE,
+            blend_dst_factor_alpha: gl::GL_ONE_MINUS_SRC_ALPHA,
+            blend_equation_rgb: gl::GL_FUNC_ADD,
+            blend_equation_alpha: gl::GL_FUNC_ADD,
+            custom_blend_mode_modified: false,
+            depth_test: false,
+            depth_mask: true,
+            backface_culling: true,
+            cull_mode: CullMode::Back,
+            wire_mode: false,
+            smooth_lines: false,
+            unload_sender,
+            unload_receiver,
+        }
+    }
+}
+
+impl Drop for RLGL {
+    fn drop(&mut self) {
+        self.unload_pending();
+        self.batch.unload(&mut *self.gl);
+
+        // Unload default texture
+        self.gl.delete_texture(self.default_texture_id);
+        tracelog!(Info, "TEXTURE: [ID {}] Default texture unloaded successfully", self.default_texture_id);
+
+        // Unload default shader
+        self.gl.use_program(0);
+        self.gl.delete_program(self.default_shader_id);
+        tracelog!(Info, "SHADER: [ID {}] Default shader unloaded successfully", self.default_shader_id);
+    }
+}
+
+impl RLGL {
+    /// Replace the OpenGL backend, rlgl is initialized again with it
+    /// NOTE: Default data of the previous backend is unloaded, pending batch data is discarded
+    pub(crate) fn set_gl(&mut self, gl: Box<dyn GlApi>) {
+        *self = Self::new(gl);
+    }
+
+    /// Load texture data into GPU, mipmaps data must be consecutive (size reduced by half every level)
+    /// NOTE: `None` data allocates the texture without initializing it
+    pub(crate) fn load_texture(&mut self, data: Option<&[u8]>, width: usize, height: usize, format: PixelFormat, mipmap_count: usize) -> Result<GlTextureID, TextureError> {
+        load_texture(&mut *self.gl, self.extensions, data, width, height, format, mipmap_count)
+    }
+
+    /// Update texture with new data on GPU
+    /// NOTE: Compressed formats can not be updated
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn update_texture(&mut self, id: GlTextureID, offset_x: usize, offset_y: usize, width: usize, height: usize, format: PixelFormat, data: &[u8]) {
+        if data.len() < format.data_size(width, height) {
+            tracelog!(Warning, "TEXTURE: [ID {id}] Failed to update, data size ({}) smaller than required ({})", data.len(), format.data_size(width, height));
+            return;
+        }
+
+        self.gl.bind_texture(id);
+        match self.get_gl_texture_formats(format) {
+            Some((_, gl_format, gl_type)) if !format.is_compressed() => {
+                self.gl.tex_sub_image_2d(0, offset_x, offset_y, width, height, gl_format, gl_type, data);
+            }
+            _ => tracelog!(Warning, "TEXTURE: [ID {id}] Failed to update for current texture format ({format:?})"),
+        }
+    }
+
+    /// Set texture filter for minification and magnification, returns the filter actually applied
+    /// NOTE: Trilinear falls back to bilinear without mipmaps, anisotropy is clamped to the supported level
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn set_texture_filter(&mut self, id: GlTextureID, mipmaps: usize, filter: TextureFilter) -> TextureFilter {
+        let (min, mag, filter) = match filter {
+            TextureFilter::Point if mipmaps > 1 => (gl::GL_NEAREST_MIPMAP_NEAREST, gl::GL_NEAREST, filter),
+            TextureFilter::Point => (gl::GL_NEAREST, gl::GL_NEAREST, filter),
+            TextureFilter::Bilinear if mipmaps > 1 => (gl::GL_LINEAR_MIPMAP_NEAREST, gl::GL_LINEAR, filter),
+            TextureFilter::Trilinear | TextureFilter::Anisotropic4x | TextureFilter::Anisotropic8x | TextureFilter::Anisotropic16x if mipmaps > 1 => {
+                (gl::GL_LINEAR_MIPMAP_LINEAR, gl::GL_LINEAR, filter)
+            }
+            TextureFilter::Trilinear => {
+                tracelog!(Warning, "TEXTURE: [ID {id}] No mipmaps available for TRILINEAR texture filtering");
+                (gl::GL_LINEAR, gl::GL_LINEAR, TextureFilter::Bilinear)
+            }
+            _ => (gl::GL_LINEAR, gl::GL_LINEAR, filter),
+        };
+
+        self.gl.bind_texture(id);
+        self.gl.tex_parameteri(gl::GL_TEXTURE_MIN_FILTER, min);
+        self.gl.tex_parameteri(gl::GL_TEXTURE_MAG_FILTER, mag);
+
+        let anisotropy: f32 = match filter {
+            TextureFilter::Anisotropic4x => 4.0,
+            TextureFilter::Anisotropic8x => 8.0,
+            TextureFilter::Anisotropic16x => 16.0,
+            _ => 0.0,
+        };
+        if anisotropy > 0.0 {
+            if self.extensions.tex_anisotropic_filter {
+                let level = anisotropy.min(self.extensions.max_anisotropy_level);
+                if level < anisotropy {
+                    tracelog!(Warning, "GL: Maximum anisotropic filter level supported is {level:.0}X");
+                }
+                self.gl.tex_parameteri(gl::GL_TEXTURE_MAX_ANISOTROPY_EXT, level as i32);
+            } else {
+                tracelog!(Warning, "GL: Anisotropic filtering not supported");
+            }
+        }
+        self.gl.bind_texture(0);
+        filter
+    }
+
+    /// Unload texture from GPU memory
+    #[inline]
+    pub(crate) fn unload_texture(&mut self, id: GlTextureID) {
+        self.gl.delete_texture(id);
+    }
+
+    /// Get OpenGL internal formats and data type from raylib `PixelFormat`, `None` if not supported
+    #[inline]
+    pub(crate) fn get_gl_texture_formats(&self, format: PixelFormat) -> Option<(u32, u32, u32)> {
+        self.extensions.gl_texture_formats(format)
+    }
+
+    /// Get OpenGL capabilities and extensions support
+    #[inline]
+    pub(crate) fn get_ext_supported(&self) -> ExtSupported {
+        self.extensions
+    }
+
+    /// Get default texture id
+    #[inline]
+    pub(crate) fn get_texture_id_default(&self) -> GlTextureID {
+        self.default_texture_id
+    }
+
+    /// Load depth texture as a renderbuffer, it can not be sampled
+    pub(crate) fn load_texture_depth(&mut self, width: usize, height: usize) -> GlTextureID {
+        let id = self.gl.gen_renderbuffer();
+        self.gl.bind_renderbuffer(id);
+        self.gl.renderbuffer_storage(gl::GL_DEPTH_COMPONENT24, width, height);
+        self.gl.bind_renderbuffer(0);
+
+        if id == 0 {
+            tracelog!(Warning, "TEXTURE: Failed to load depth renderbuffer");
+        } else {
+            tracelog!(Info, "TEXTURE: [ID {id}] Depth renderbuffer loaded successfully (24 bits)");
+        }
+        id
+    }
+
+    /// Load an empty framebuffer, 0 on failure
+    pub(crate) fn load_framebuffer(&mut self) -> GlFrameBufferID {
+        let id = self.gl.gen_framebuffer(); // Create the framebuffer object
+        self.gl.bind_framebuffer(0); // Unbind any framebuffer
+        id
+    }
+
+    /// Enable rendering to texture (fbo)
+    #[inline]
+    pub(crate) fn enable_framebuffer(&mut self, id: GlFrameBufferID) {
+        self.gl.bind_framebuffer(id);
+    }
+
+    /// Disable rendering to texture
+    #[inline]
+    pub(crate) fn disable_framebuffer(&mut self) {
+        self.gl.bind_framebuffer(0);
+    }
+
+    /// Attach texture or renderbuffer to an fbo
+    /// NOTE: Previous attachment of the same type is replaced, not unloaded
+    pub(crate) fn framebuffer_attach(&mut self, fbo_id: GlFrameBufferID, tex_id: GlTextureID, attach_type: FramebufferAttachType, tex_type: FramebufferAttachTextureType, mip_level: usize) {
+        let attachment = match attach_type {
+            FramebufferAttachType::Depth => gl::GL_DEPTH_ATTACHMENT,
+            FramebufferAttachType::Stencil => gl::GL_STENCIL_ATTACHMENT,
+            channel => gl::GL_COLOR_ATTACHMENT0 + channel as u32,
+        };
+
+        self.gl.bind_framebuffer(fbo_id);
+        match tex_type {
+            FramebufferAttachTextureType::Texture2D => self.gl.framebuffer_texture_2d(attachment, gl::GL_TEXTURE_2D, tex_id, mip_level),
+            FramebufferAttachTextureType::Renderbuffer => self.gl.framebuffer_renderbuffer(attachment, tex_id),
+            side => self.gl.framebuffer_texture_2d(attachment, gl::GL_TEXTURE_CUBE_MAP_POSITIVE_X + side as u32, tex_id, mip_level),
+        }
+        self.gl.bind_framebuffer(0);
+    }
+
+    /// Verify render texture is complete
+    /// NOTE: Incomplete status is logged
+    pub(crate) fn framebuffer_complete(&mut self, id: GlFrameBufferID) -> bool {
+        self.gl.bind_framebuffer(id);
+        let status = self.gl.check_framebuffer_status();
+        self.gl.bind_framebuffer(0);
+
+        match status {
+            gl::GL_FRAMEBUFFER_COMPLETE => return true,
+            gl::GL_FRAMEBUFFER_UNSUPPORTED => tracelog!(Warning, "FBO: [ID {id}] Framebuffer is unsupported"),
+            gl::GL_FRAMEBUFFER_INCOMPLETE_ATTACHMENT => tracelog!(Warning, "FBO: [ID {id}] Framebuffer has incomplete attachment"),
+            gl::GL_FRAMEBUFFER_INCOMPLETE_DIMENSIONS => tracelog!(Warning, "FBO: [ID {id}] Framebuffer has incomplete dimensions"),
+            gl::GL_FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT => tracelog!(Warning, "FBO: [ID {id}] Framebuffer has a missing attachment"),
+            _ => tracelog!(Warning, "FBO: [ID {id}] Framebuffer is incomplete (status: {status:#x})"),
+        }
+        false
+    }
+
+    /// Unload framebuffer from GPU memory
+    /// NOTE: Attachments are not unloaded, they are owned by the render texture
+    pub(crate) fn unload_framebuffer(&mut self, id: GlFrameBufferID) {
+        self.gl.bind_framebuffer(0);
+        self.gl.delete_framebuffer(id);
+        tracelog!(Info, "FBO: [ID {id}] Unloaded framebuffer from VRAM (GPU)");
+    }
+
+    /// Load vertex array (vao), 0 if not supported
+    #[inline]
+    pub(crate) fn load_vertex_array(&mut self) -> u32 {
+        self.gl.gen_vertex_array().unwrap_or(0)
+    }
+
+    /// Enable vertex array (VAO), returns false if the VAO is not valid (not supported)
+    pub(crate) fn enable_vertex_array(&mut self, vao_id: u32) -> bool {
+        if vao_id == 0 {
+            return false;
+        }
+        self.gl.bind_vertex_array(vao_id);
+        true
+    }
+
+    /// Disable vertex array (VAO)
+    #[inline]
+    pub(crate) fn disable_vertex_array(&mut self) {
+        self.gl.bind_vertex_array(0);
+    }
+
+    /// Load a vertex buffer object, left bound to `target`
+    pub(crate) fn load_vertex_buffer(&mut self, target: BufferTarget, data: BufferData<'_>, dynamic: bool) -> u32 {
+        let id = self.gl.gen_buffer();
+        self.gl.bind_buffer(target, id);
+        self.gl.buffer_data(target, data, if dynamic { BufferUsage::DynamicDraw } else { BufferUsage::StaticDraw });
+        id
+    }
+
+    /// Update vertex buffer object data on GPU buffer
+    /// NOTE: Data must fit in the buffer allocated on load
+    pub(crate) fn update_vertex_buffer(&mut self, vbo_id: u32, data: BufferData<'_>, offset: usize) {
+        self.gl.bind_buffer(BufferTarget::Array, vbo_id);
+        self.gl.buffer_sub_data(BufferTarget::Array, offset, data);
+    }
+
+    /// Enable vertex buffer element (VBO element), used when VAO is not supported
+    #[inline]
+    pub(crate) fn enable_vertex_buffer_element(&mut self, vbo_id: u32) {
+        self.gl.bind_buffer(BufferTarget::ElementArray, vbo_id);
+    }
+
+    /// Bind vertex buffer and set vertex attribute data layout, the attribute is enabled
+    /// NOTE: Recorded into the currently bound VAO, if any
+    pub(crate) fn set_vertex_attribute(&mut self, vbo_id: u32, index: u32, size: i32, ty: AttribType, normalized: bool) {
+        self.gl.bind_buffer(BufferTarget::Array, vbo_id);
+        self.gl.vertex_attrib_pointer(index, size, ty, normalized, 0, 0);
+        self.gl.enable_vertex_attrib_array(index);
+    }
+
+    /// Unload vertex array (vao)
+    pub(crate) fn unload_vertex_array(&mut self, vao_id: u32) {
+        if vao_id != 0 {
+            self.gl.bind_vertex_array(0);
+            self.gl.delete_vertex_array(vao_id);
+            tracelog!(Info, "VAO: [ID {vao_id}] Unloaded vertex array data from VRAM (GPU)");
+        }
+    }
+
+    /// Unload vertex buffer object
+    #[inline]
+    pub(crate) fn unload_vertex_buffer(&mut self, vbo_id: u32) {
+        if vbo_id != 0 {
+            self.gl.delete_buffer(vbo_id);
+        }
+    }
+
+    /// Clear color buffer with color
+    pub(crate) fn clear_color(&mut self, r: u8, g: u8, b: u8, a: u8) {
+        // Color values clamp to 0.0f(0) and 1.0f(255)
+        let normalize = |value: u8| f32::from(value)/255.0;
+        self.gl.clear_color(normalize(r), normalize(g), normalize(b), normalize(a));
+    }
+
+    /// Clear used screen buffers (color and depth)
+    /// NOTE: Pending draws are drawn first (and cleared), an empty batch is not flushed
+    pub(crate) fn clear_screen_buffers(&mut self) {
+        if self.vertex_counter > 0 {
+            self.draw_render_batch(FlushReason::ScreenClear);
+        }
+        self.gl.clear(gl::GL_COLOR_BUFFER_BIT | gl::GL_DEPTH_BUFFER_BIT); // Clear used buffers: Color and Depth (Depth is used for 3D)
+    }
+
+    /// Read screen pixel data (color buffer), RGBA 8 bit, top to bottom
+    /// NOTE: OpenGL reads bottom to top, rows are flipped and alpha is forced to 255
+    /// to avoid a transparent screenshot if the framebuffer has an alpha channel
+    pub(crate) fn read_screen_pixels(&mut self, width: usize, height: usize) -> Vec<u8> {
+        let stride = width*4;
+        let mut screen_data = vec![0u8; stride*height];
+        self.gl.read_pixels(0, 0, width, height, gl::GL_RGBA, gl::GL_UNSIGNED_BYTE, &mut screen_data);
+
+        // Flip image vertically!
+        let mut image_data = Vec::with_capacity(screen_data.len());
+        for row in screen_data.chunks_exact(stride).rev() {
+            image_data.extend_from_slice(row);
+        }
+        // Set alpha component value to 255 (no trasparent image retrieval)
+        for alpha in image_data.iter_mut().skip(3).step_by(4) {
+            *alpha = 255;
+        }
+        image_data
+    }
+
+    /// Set current framebuffer size, required by stereo rendering viewports
+    #[inline]
+    pub(crate) fn set_framebuffer_size(&mut self, width: i32, height: i32) {
+        self.framebuffer_size = (width, height);
+    }
+
+    /// Enable/disable stereo rendering
+    /// NOTE: Pending draws are flushed first
+    pub(crate) fn set_stereo_render(&mut self, enabled: bool) {
+        if self.stereo_render != enabled {
+            self.draw_render_batch(FlushReason::RenderStateChange);
+            self.stereo_render = enabled;
+        }
+    }
+
+    /// Check if stereo render is enabled
+    #[inline]
+    pub(crate) fn is_stereo_render_enabled(&self) -> bool {
+        self.stereo_render
+    }
+
+    /// Set eyes projection matrices for stereo rendering, index 0 is drawn on the left half
+    #[inline]
+    pub(crate) fn set_matrix_projection_stereo(&mut self, projections: [Matrix; 2]) {
+        self.projection_stereo = projections;
+    }
+
+    /// Set eyes view offsets matrices for stereo rendering, index 0 is drawn on the left half
+    #[inline]
+    pub(crate) fn set_matrix_view_offset_stereo(&mut self, view_offsets: [Matrix; 2]) {
+        self.view_offset_stereo = view_offsets;
+    }
+
+    /// Set the viewport area, framebuffer coordinates (bottom-left origin)
+    #[inline]
+    pub(crate) fn viewport(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        self.gl.viewport(x, y, width, height);
+    }
+
+    /// Enable scissor test
+    #[inline]
+    pub(crate) fn enable_scissor_test(&mut self) {
+        self.gl.enable(gl::GL_SCISSOR_TEST);
+    }
+
+    /// Disable scissor test
+    #[inline]
+    pub(crate) fn disable_scissor_test(&mut self) {
+        self.gl.disable(gl::GL_SCISSOR_TEST);
+    }
+
+    /// Scissor test area, framebuffer coordinates (bottom-left origin)
+    #[inline]
+    pub(crate) fn scissor(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        self.gl.scissor(x, y, width, height);
+    }
+
+    /// Toggle a render state flag, the render batch is drawn before changing GL state
+    /// NOTE: Returns false (and does nothing) if the state is already set
+    fn toggle_state(&mut self, state: fn(&mut Self) -> &mut bool, enabled: bool) -> bool {
+        if *state(self) == enabled {
+            return false;
+        }
+        self.draw_render_batch(FlushReason::RenderStateChange);
+        *state(self) = enabled;
+        true
+    }
+
+    /// Enable/disable depth test
+    pub(crate) fn set_depth_test(&mut self, enabled: bool) {
+        if self.toggle_state(|rlgl| &mut rlgl.depth_test, enabled) {
+            if enabled { self.gl.enable(gl::GL_DEPTH_TEST) } else { self.gl.disable(gl::GL_DEPTH_TEST) }
+        }
+    }
+
+    /// Enable/disable depth write
+    pub(crate) fn set_depth_mask(&mut self, enabled: bool) {
+        if self.toggle_state(|rlgl| &mut rlgl.depth_mask, enabled) {
+            self.gl.depth_mask(enabled);
+        }
+    }
+
+    /// Enable/disable backface culling
+    pub(crate) fn set_backface_culling(&mut self, enabled: bool) {
+        if self.toggle_state(|rlgl| &mut rlgl.backface_culling, enabled) {
+            if enabled { self.gl.enable(gl::GL_CULL_FACE) } else { self.gl.disable(gl::GL_CULL_FACE) }
+        }
+    }
+
+    /// Set face culling mode
+    pub(crate) fn set_cull_face(&mut self, mode: CullMode) {
+        if self.cull_mode == mode {
+            return;
+        }
+        self.draw_render_batch(FlushReason::RenderStateChange);
+        self.cull_mode = mode;
+        self.gl.cull_face(match mode {
+            CullMode::Front => gl::GL_FRONT,
+            CullMode::Back => gl::GL_BACK,
+        });
+    }
+
+    /// Enable/disable wire mode (polygons drawn as lines)
+    /// NOTE: Not supported on OpenGL ES, state is tracked but nothing is changed
+    pub(crate) fn set_wire_mode(&mut self, enabled: bool) {
+        if self.toggle_state(|rlgl| &mut rlgl.wire_mode, enabled) {
+            #[cfg(not(feature = "graphics_api_opengl_es2"))]
+            self.gl.polygon_mode(gl::GL_FRONT_AND_BACK, if enabled { gl::GL_LINE } else { gl::GL_FILL });
+        }
+    }
+
+    /// Enable/disable line aliasing
+    /// NOTE: Not supported on OpenGL ES, state is tracked but nothing is changed
+    pub(crate) fn set_smooth_lines(&mut self, enabled: bool) {
+        if self.toggle_state(|rlgl| &mut rlgl.smooth_lines, enabled) {
+            #[cfg(not(feature = "graphics_api_opengl_es2"))]
+            if enabled { self.gl.enable(gl::GL_LINE_SMOOTH) } else { self.gl.disable(gl::GL_LINE_SMOOTH) }
+        }
+    }
+
+    /// Set blending mode
+    /// NOTE: Render batch is drawn before switching, pending draws keep the previous mode
+    pub(crate) fn set_blend_mode(&mut self, mode: BlendMode) {
+        let custom = matches!(mode, BlendMode::Custom | BlendMode::CustomSeparate);
+        if self.blend_mode == mode && !(custom && self.custom_blend_mode_modified) {
+            return;
+        }
+
+        self.draw_render_batch(FlushReason::BlendModeChange);
+
+        let gl = &mut self.gl;
+        match mode {
+            BlendMode::Alpha => { gl.blend_func(gl::GL_SRC_ALPHA, gl::GL_ONE_MINUS_SRC_ALPHA); gl.blend_equation(gl::GL_FUNC_ADD); }
+            BlendMode::Additive => { gl.blend_func(gl::GL_SRC_ALPHA, gl::GL_ONE); gl.blend_equation(gl::GL_FUNC_ADD); }
+            BlendMode::Multiplied => { gl.blend_func(gl::GL_DST_COLOR, gl::GL_ONE_MINUS_SRC_ALPHA); gl.blend_equation(gl::GL_FUNC_ADD); }
+            BlendMode::AddColors => { gl.blend_func(gl::GL_ONE, gl::GL_ONE); gl.blend_equation(gl::GL_FUNC_ADD); }
+            BlendMode::SubtractColors => { gl.blend_func(gl::GL_ONE, gl::GL_ONE); gl.blend_equation(gl::GL_FUNC_SUBTRACT); }
+            BlendMode::AlphaPremultiply => { gl.blend_func(gl::GL_ONE, gl::GL_ONE_MINUS_SRC_ALPHA); gl.blend_equation(gl::GL_FUNC_ADD); }
+            BlendMode::Custom => {
+                // NOTE: Using GL blend src/dst factors and GL equation configured with rlSetBlendFactors()
+                gl.blend_func(self.blend_src_factor, self.blend_dst_factor);
+                gl.blend_equation(self.blend_equation);
+            }
+            BlendMode::CustomSeparate => {
+                // NOTE: Using GL blend src/dst factors and GL equation configured with rlSetBlendFactorsSeparate()
+                gl.blend_func_separate(self.blend_src_factor_rgb, self.blend_dst_factor_rgb, self.blend_src_factor_alpha, self.blend_dst_factor_alpha);
+                gl.blend_equation_separate(self.blend_equation_rgb, self.blend_equation_alpha);
+            }
+        }
+
+        self.blend_mode = mode;
+        self.custom_blend_mode_modified = false;
+    }
+
+    /// Set blending mode factor and equation (using OpenGL factors)
+    /// NOTE: Applied the next time `BlendMode::Custom` is set
+    pub(crate) fn set_blend_factors(&mut self, src_factor: u32, dst_factor: u32, equation: u32) {
+        if (self.blend_src_factor, self.blend_dst_factor, self.blend_equation) != (src_factor, dst_factor, equation) {
+            self.blend_src_factor = src_factor;
+            self.blend_dst_factor = dst_factor;
+            self.blend_equation = equation;
+            self.custom_blend_mode_modified = true;
+        }
+    }
+
+    /// Set blending mode factors and equations separately (using OpenGL factors)
+    /// NOTE: Applied the next time `BlendMode::CustomSeparate` is set
+    pub(crate) fn set_blend_factors_separate(&mut self, src_rgb: u32, dst_rgb: u32, src_alpha: u32, dst_alpha: u32, equation_rgb: u32, equation_alpha: u32) {
+        let current = (
+            self.blend_src_factor_rgb, self.blend_dst_factor_rgb,
+            self.blend_src_factor_alpha, self.blend_dst_factor_alpha,
+            self.blend_equation_rgb, self.blend_equation_alpha,
+        );
+        if current != (src_rgb, dst_rgb, src_alpha, dst_alpha, equation_rgb, equation_alpha) {
+            self.blend_src_factor_rgb = src_rgb;
+            self.blend_dst_factor_rgb = dst_rgb;
+            self.blend_src_factor_alpha = src_alpha;
+            self.blend_dst_factor_alpha = dst_alpha;
+            self.blend_equation_rgb = equation_rgb;
+            self.blend_equation_alpha = equation_alpha;
+            self.custom_blend_mode_modified = true;
+        }
+    }
+
+    /// Get a sender to release GPU objects on drop
+    #[inline]
+    pub(crate) fn unload_sender(&self) -> Sender<GlObject> {
+        self.unload_sender.clone()
+    }
+
+    /// Unload GPU objects released since the last batch draw
+    fn unload_pending(&mut self) {
+        while let Ok(object) = self.unload_receiver.try_recv() {
+            match object {
+                GlObject::Texture(id) => self.unload_texture(id),
+                GlObject::Renderbuffer(id) => self.gl.delete_renderbuffer(id),
+                GlObject::Framebuffer(id) => self.unload_framebuffer(id),
+                GlObject::Program(id) => self.unload_shader_program(id),
+            }
+        }
+    }
+
+    /// Compile custom shader and return shader id
+    /// NOTE: Compile errors are logged with the compiler info log
+    #[inline]
+    pub(crate) fn compile_shader(&mut self, source: &str, kind: ShaderType) -> Result<u32, ShaderCompileError> {
+        compile_shader(&mut *self.gl, source, kind)
+    }
+
+    /// Load custom shader program from compiled vertex and fragment shaders
+    /// NOTE: Link errors are logged with the linker info log
+    #[inline]
+    pub(crate) fn load_shader_program(&mut self, vs_id: u32, fs_id: u32) -> Result<u32, ShaderCompileError> {
+        load_shader_program(&mut *self.gl, vs_id, fs_id)
+    }
+
+    /// Load shader program from code, `None` stages use the default shader code
+    /// NOTE: Compile and link errors are logged
+    pub(crate) fn load_shader_code(&mut self, vs_code: Option<&str>, fs_code: Option<&str>) -> Result<u32, ShaderCompileError> {
+        let version = self.gl.version();
+        let Some(default_code) = default_shader_code(version) else {
+            return Err(ShaderCompileError::NotSupported(version));
+        };
+        load_shader_code(&mut *self.gl, vs_code.unwrap_or(default_code.0), fs_code.unwrap_or(default_code.1))
+    }
+
+    /// Unload shader program
+    /// NOTE: Default shader is never unloaded from here
+    pub(crate) fn unload_shader_program(&mut self, id: u32) {
+        if id != 0 && id != self.default_shader_id {
+            self.gl.delete_program(id);
+            tracelog!(Info, "SHADER: [ID {id}] Unloaded shader program data from VRAM (GPU)");
+        }
+    }
+
+    /// Get shader uniform location, -1 if not found
+    #[inline]
+    pub(crate) fn get_location_uniform(&mut self, shader_id: u32, name: &str) -> i32 {
+        self.gl.get_uniform_location(shader_id, name)
+    }
+
+    /// Get shader attribute location, -1 if not found
+    #[inline]
+    pub(crate) fn get_location_attrib(&mut self, shader_id: u32, name: &str) -> i32 {
+        self.gl.get_attrib_location(shader_id, name)
+    }
+
+    /// Set shader uniform value, samplers are bound to the texture units of next draws
+    /// NOTE: Doesn't flush, pending draws using the shader see the new value
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+    pub(crate) fn set_uniform(&mut self, shader_id: u32, location: i32, value: ShaderUniformData) {
+        self.gl.use_program(shader_id);
+        match value {
+            ShaderUniformData::Float(x) => self.gl.uniform_fv(location, 1, &[x]),
+            ShaderUniformData::Vec2(v) => self.gl.uniform_fv(location, 2, &v),
+            ShaderUniformData::Vec3(v) => self.gl.uniform_fv(location, 3, &v),
+            ShaderUniformData::Vec4(v) => self.gl.uniform_fv(location, 4, &v),
+            ShaderUniformData::Int(x) => self.gl.uniform_iv(location, 1, &[x]),
+            ShaderUniformData::IVec2(v) => self.gl.uniform_iv(location, 2, &v),
+            ShaderUniformData::IVec3(v) => self.gl.uniform_iv(location, 3, &v),
+            ShaderUniformData::IVec4(v) => self.gl.uniform_iv(location, 4, &v),
+            ShaderUniformData::FloatArray { components, values } => self.gl.uniform_fv(location, components, values),
+            ShaderUniformData::IntArray { components, values } => self.gl.uniform_iv(location, components, values),
+            ShaderUniformData::Matrix(mat) => self.gl.uniform_matrix4(location, mat),
+            ShaderUniformData::Sampler2D(texture_id) => {
+                // Check if texture is already active, otherwise register it in the first free texture unit
+                let unit = self.active_texture_ids.iter().position(|&id| id == texture_id)
+                    .or_else(|| self.active_texture_ids.iter().position(|&id| id == 0));
+                match unit {
+                    Some(unit) => {
+                        // NOTE: Texture unit 0 is reserved for the draw texture
+                        self.gl.uniform1i(location, 1 + unit as i32);
+                        self.active_texture_ids[unit] = texture_id;
+                    }
+                    None => tracelog!(Warning, "SHADER: [ID {shader_id}] Failed to set sampler texture, all {RL_DEFAULT_BATCH_MAX_TEXTURE_UNITS} texture units in use"),
+                }
+            }
+        }
+        self.gl.use_program(0);
+    }
+
+    /// Get default shader id
+    #[inline]
+    pub(crate) fn get_shader_id_default(&self) -> u32 {
+        self.default_shader_id
+    }
+
+    /// Get default shader locations
+    #[inline]
+    pub(crate) fn get_shader_locs_default(&self) -> &[i32] {
+        &self.default_shader_locs
+    }
+
+    /// Set shader used by next draws
+    /// NOTE: Doesn't flush, the batch starts a new draw call when the next draw uses a different shader
+    #[inline]
+    pub(crate) fn set_shader(&mut self, id: u32) {
+        self.current_shader_id = id;
+    }
+
+    /// Get matrix modified by matrix operations
+    fn current_matrix_mut(&mut self) -> &mut Matrix {
+        match self.current_matrix {
+            CurrentMatrix::Modelview => &mut self.modelview,
+            CurrentMatrix::Projection => &mut self.projection,
+            CurrentMatrix::Transform => &mut self.transform,
+        }
+    }
+
+    /// Choose the current matrix to be transformed
+    /// NOTE: Texture mode keeps the current matrix, like OpenGL 1.1 emulation does upstream
+    pub(crate) fn matrix_mode(&mut self, mode: MatrixMode) {
+        match mode {
+            MatrixMode::Projection => self.current_matrix = CurrentMatrix::Projection,
+            MatrixMode::Modelview => self.current_matrix = CurrentMatrix::Modelview,
+            MatrixMode::Texture => {} // Not supported
+        }
+        self.matrix_mode = mode;
+    }
+
+    /// Push the current matrix into the stack
+    /// NOTE: Stack overflow is logged and the push is ignored
+    pub(crate) fn push_matrix(&mut self) {
+        if self.stack.is_full() {
+            tracelog!(Error, "RLGL: Matrix stack overflow (RL_MAX_MATRIX_STACK_SIZE)");
+            return;
+        }
+
+        if self.matrix_mode == MatrixMode::Modelview {
+            self.transform_required = true;
+            self.current_matrix = CurrentMatrix::Transform;
+        }
+
+        let mat = *self.current_matrix_mut();
+        self.stack.push(mat);
+    }
+
+    /// Pop latest inserted matrix from the stack
+    pub(crate) fn pop_matrix(&mut self) {
+        if let Some(mat) = self.stack.pop() {
+            *self.current_matrix_mut() = mat;
+        }
+
+        if self.stack.is_empty() && self.matrix_mode == MatrixMode::Modelview {
+            self.current_matrix = CurrentMatrix::Modelview;
+            self.transform_required = false;
+        }
+    }
+
+    /// Reset current matrix to identity matrix
+    #[inline]
+    pub(crate) fn load_identity(&mut self) {
+        *self.current_matrix_mut() = Matrix::IDENTITY;
+    }
+
+    /// Multiply the current matrix by a translation matrix
+    pub(crate) fn translatef(&mut self, x: f32, y: f32, z: f32) {
+        self.mult_matrix(Matrix::translate(x, y, z));
+    }
+
+    /// Multiply the current matrix by a rotation matrix
+    /// NOTE: The provided axis is normalized, angle must be provided in degrees
+    pub(crate) fn rotatef(&mut self, angle: Degrees, x: f32, y: f32, z: f32) {
+        self.mult_matrix(Matrix::rotate(Vector3::new(x, y, z), angle.to_radians()));
+    }
+
+    /// Multiply the current matrix by a scaling matrix
+    pub(crate) fn scalef(&mut self, x: f32, y: f32, z: f32) {
+        self.mult_matrix(Matrix::scale(x, y, z));
+    }
+
+    /// Multiply the current matrix by another matrix
+    #[inline]
+    pub(crate) fn mult_matrix(&mut self, mat: Matrix) {
+        let current = self.current_matrix_mut();
+        *current = mat * std::mem::take(current);
+    }
+
+    /// Multiply the current matrix by an orthographic matrix generated by parameters
+    pub(crate) fn ortho(&mut self, left: f64, right: f64, bottom: f64, top: f64, znear: f64, zfar: f64) {
+        let current = self.current_matrix_mut();
+        *current = std::mem::take(current) * Matrix::ortho(left, right, bottom, top, znear, zfar);
+    }
+
+    /// Multiply the current matrix by a perspective matrix generated by parameters
+    pub(crate) fn frustum(&mut self, left: f64, right: f64, bottom: f64, top: f64, znear: f64, zfar: f64) {
+        let current = self.current_matrix_mut();
+        *current = std::mem::take(current) * Matrix::frustrum(left, right, bottom, top, znear, zfar);
+    }
+
+    /// Get internal modelview matrix
+    #[inline]
+    pub(crate) fn get_matrix_modelview(&self) -> &Matrix {
+        &self.modelview
+    }
+
+    /// Get internal projection matrix
+    #[inline]
+    pub(crate) fn get_matrix_projection(&self) -> &Matrix {
+        &self.projection
+    }
+
+    /// Get internal accumulated transform matrix
+    #[inline]
+    pub(crate) fn get_matrix_transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    /// Check if transform matrix must be applied to submitted vertices (matrix stack in use)
+    #[inline]
+    pub(crate) fn is_transform_required(&self) -> bool {
+        self.transform_required
+    }
+
+    /// Finish the current draw call, aligning the vertex counter for the next one
+    /// NOTE: Draw call is reused if empty, the batch is flushed if out of vertices or draw calls
+    fn next_draw_call(&mut self, reason: FlushReason) {
+        if self.batch.draw().vertex_count > 0 {
+            // Make sure current draw vertex count is aligned a multiple of 4,
+            // that way, following QUADS drawing will keep aligned with index processing
+            let alignment = self.batch.draw().alignment();
+            self.batch.draw_mut().vertex_alignment = alignment;
+
+            if !self.check_render_batch_limit(alignment) {
+                self.vertex_counter += alignment;
+                self.batch.draw_counter += 1;
+            }
+        }
+
+        if self.batch.draw_counter >= RL_DEFAULT_BATCH_DRAWCALLS {
+            self.draw_render_batch(reason);
+        }
+    }
+
+    /// Initialize drawing mode (how to organize vertex)
+    pub(crate) fn begin(&mut self, mode: DrawMode) {
+        // Draw mode can be RL_LINES, RL_TRIANGLES and RL_QUADS
+        // NOTE: In all three cases, vertex are accumulated over default internal vertex buffer
+        let draw = *self.batch.draw();
+        let reason = if draw.mode == mode {
+            draw.break_reason(draw.texture_id, self.current_shader_id)
+        } else {
+            Some(FlushReason::DrawModeChange)
+        };
+
+        if let Some(reason) = reason {
+            self.next_draw_call(reason);
+
+            // NOTE: Texture is reset to default on mode change, kept on shader change
+            let texture_id = if draw.mode == mode { draw.texture_id } else { self.default_texture_id };
+            let shader_id = self.current_shader_id;
+            let draw = self.batch.draw_mut();
+            draw.mode = mode;
+            draw.vertex_count = 0;
+            draw.texture_id = texture_id;
+            draw.shader_id = shader_id;
+        }
+    }
+
+    /// Finish vertex providing
+    pub(crate) fn end(&mut self) {
+        // NOTE: Depth increment is dependant on rlOrtho(): z-near and z-far values,
+        // as well as depth buffer bit-depth (16bit or 24bit or 32bit)
+        // Correct increment formula would be: depthInc = (zfar - znear)/pow(2, bits)
+        self.batch.current_depth += 1.0/20000.0;
+    }
+
+    /// Define one vertex (position)
+    /// NOTE: Vertex position data is the basic information required for drawing
+    pub(crate) fn vertex3f(&mut self, x: f32, y: f32, z: f32) {
+        let (mut tx, mut ty, mut tz) = (x, y, z);
+
+        // Transform provided vector if required
+        if self.is_transform_required() {
+            let m = &self.transform.0;
+            tx = m[0][0]*x + m[0][1]*y + m[0][2]*z + m[0][3];
+            ty = m[1][0]*x + m[1][1]*y + m[1][2]*z + m[1][3];
+            tz = m[2][0]*x + m[2][1]*y + m[2][2]*z + m[2][3];
+        }
+
+        // WARNING: We can't break primitives when launching a new batch
+        // RL_LINES comes in pairs, RL_TRIANGLES come in groups of 3 vertices and RL_QUADS come in groups of 4 vertices
+        // We must check current draw.mode when a new vertex is required and finish the batch only if the draw.mode draw.vertexCount is %2, %3 or %4
+        if self.vertex_counter > self.batch.buffer().vertex_capacity().saturating_sub(4) {
+            let draw = self.batch.draw();
+            match draw.mode {
+                // Reached the maximum number of vertices for RL_LINES drawing
+                // Launch a draw call but keep current state for next vertices comming
+                // NOTE: We add +1 vertex to the check for security
+                DrawMode::Lines if draw.vertex_count.is_multiple_of(2) => { self.check_render_batch_limit(2 + 1); }
+                DrawMode::Triangles if draw.vertex_count.is_multiple_of(3) => { self.check_render_batch_limit(3 + 1); }
+                DrawMode::Quads if draw.vertex_count.is_multiple_of(4) => { self.check_render_batch_limit(4 + 1); }
+                _ => {}
+            }
+        }
+
+        let counter = self.vertex_counter;
+        let buffer = &mut self.batch.vertex_buffer[self.batch.current_buffer];
+        if counter >= buffer.vertex_capacity() {
+            // NOTE: Primitive larger than the whole buffer, it can not be batched
+            tracelog!(Error, "RLGL: Render batch vertex buffer overflow, vertex discarded");
+            return;
+        }
+
+        // Add vertices
+        buffer.vertices[3*counter..3*counter + 3].copy_from_slice(&[tx, ty, tz]);
+        // Add current texcoord
+        buffer.texcoords[2*counter..2*counter + 2].copy_from_slice(&self.texcoord);
+        // Add current normal
+        buffer.normals[3*counter..3*counter + 3].copy_from_slice(&self.normal);
+        // Add current color
+        buffer.colors[4*counter..4*counter + 4].copy_from_slice(&self.color);
+
+        self.vertex_counter += 1;
+        self.batch.draw_mut().vertex_count += 1;
+    }
+
+    /// Define one vertex (position)
+    #[inline]
+    pub(crate) fn vertex2f(&mut self, x: f32, y: f32) {
+        self.vertex3f(x, y, self.batch.current_depth);
+    }
+
+    /// Define one vertex (texture coordinate)
+    /// NOTE: Texture coordinates are limited to QUADS only
+    #[inline]
+    pub(crate) fn tex_coord2f(&mut self, x: f32, y: f32) {
+        self.texcoord = [x, y];
+    }
+
+    /// Define one vertex (normal)
+    /// NOTE: Normals limited to TRIANGLES only?
+    pub(crate) fn normal3f(&mut self, x: f32, y: f32, z: f32) {
+        let (mut nx, mut ny, mut nz) = (x, y, z);
+        if self.is_transform_required() {
+            let m = &self.transform.0;
+            nx = m[0][0]*x + m[0][1]*y + m[0][2]*z;
+            ny = m[1][0]*x + m[1][1]*y + m[1][2]*z;
+            nz = m[2][0]*x + m[2][1]*y + m[2][2]*z;
+        }
+        let length = (nx*nx + ny*ny + nz*nz).sqrt();
+        if length != 0.0 {
+            let inv_length = 1.0/length;
+            nx *= inv_length;
+            ny *= inv_length;
+            nz *= inv_length;
+        }
+        self.normal = [nx, ny, nz];
+    }
+
+    /// Define one vertex (color)
+    #[inline]
+    pub(crate) fn color4ub(&mut self, r: u8, g: u8, b: u8, a: u8) {
+        self.color = [r, g, b, a];
+    }
+
+    /// Define one vertex (color)
+    /// NOTE: Color components are expected in range [0.0f..1.0f]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn color4f(&mut self, r: f32, g: f32, b: f32, a: f32) {
+        self.color4ub((r*255.0) as u8, (g*255.0) as u8, (b*255.0) as u8, (a*255.0) as u8);
+    }
+
+    /// Define one vertex (color)
+    /// NOTE: Color components are expected in range [0.0f..1.0f]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn color3f(&mut self, r: f32, g: f32, b: f32) {
+        self.color4ub((r*255.0) as u8, (g*255.0) as u8, (b*255.0) as u8, 255);
+    }
+
+    /// Set current texture to use, 0 for the default texture
+    /// NOTE: A new draw call is started if texture (or shader) changes
+    pub(crate) fn set_texture(&mut self, id: u32) {
+        if id == 0 {
+            // NOTE: If quads batch limit is reached, we force a draw call and next batch starts
+            if self.vertex_counter >= self.batch.buffer().vertex_capacity() {
+                self.draw_render_batch(FlushReason::BufferOverflow);
+            }
+            return;
+        }
+
+        let draw = self.batch.draw();
+        if let Some(reason) = draw.break_reason(id, self.current_shader_id) {
+            self.next_draw_call(reason);
+
+            let shader_id = self.current_shader_id;
+            let draw = self.batch.draw_mut();
+            draw.texture_id = id;
+            draw.shader_id = shader_id;
+            draw.vertex_count = 0;
+        }
+    }
+
+    /// Check internal buffer overflow for a given number of vertex and force a render batch draw call if required
+    /// NOTE: Current draw mode and texture are kept for the next batch
+    pub(crate) fn check_render_batch_limit(&mut self, vertex_count: usize) -> bool {
+        let overflow = self.vertex_counter + vertex_count >= self.batch.buffer().vertex_capacity();
+        if overflow {
+            // Store current primitive drawing mode and texture id
+            let DrawCall { mode, texture_id, shader_id, .. } = *self.batch.draw();
+
+            self.draw_render_batch(FlushReason::BufferOverflow);
+
+            // Restore state of last batch so we can continue adding vertices
+            let draw = self.batch.draw_mut();
+            draw.mode = mode;
+            draw.texture_id = texture_id;
+            draw.shader_id = shader_id;
+        }
+        overflow
+    }
+
+    /// Get batch draw uniform locations of a shader program, cached
+    fn batch_locations(&mut self, program: u32) -> BatchLocations {
+        let gl = &mut self.gl;
+        *self.batch_locations.entry(program).or_insert_with(|| BatchLocations {
+            mvp: gl.get_uniform_location(program, RL_DEFAULT_SHADER_UNIFORM_NAME_MVP),
+            color_diffuse: gl.get_uniform_location(program, RL_DEFAULT_SHADER_UNIFORM_NAME_COLOR),
+            texture0: gl.get_uniform_location(program, RL_DEFAULT_SHADER_SAMPLER2D_NAME_TEXTURE0),
+        })
+    }
+
+    /// Bind a shader program and set batch draw uniforms
+    fn use_batch_program(&mut self, program: u32, mvp: &Matrix, color_diffuse: [f32; 4]) {
+        let locs = self.batch_locations(program);
+        self.gl.use_program(program);
+        // Create modelview-projection matrix and upload to shader
+        self.gl.uniform_matrix4(locs.mvp, mvp);
+        self.gl.uniform4f(locs.color_diffuse, color_diffuse);
+        // WARNING: Additional samplers are expected to be bound by the shader user
+        self.gl.uniform1i(locs.texture0, 0); // Active default sampler2D: texture0
+    }
+
+    /// Draw triangles from the bound vertex data with the current shader and the default texture, tinted by `color`
+    /// NOTE: `transform` is applied before the current matrices, indices are read from the bound element buffer if `indexed`
+    pub(crate) fn draw_vertex_array(&mut self, count: usize, indexed: bool, transform: Matrix, color: Color) {
+        let model = if self.is_transform_required() { transform*self.transform } else { transform };
+        let mvp = model*self.modelview*self.projection;
+
+        // NOTE: Shader id 0 stands for the default shader
+        let shader_id = if self.current_shader_id == 0 { self.default_shader_id } else { self.current_shader_id };
+        self.use_batch_program(shader_id, &mvp, [color.r, color.g, color.b, color.a].map(|channel| f32::from(channel)/255.0));
+        self.gl.active_texture(0);
+        self.gl.bind_texture(self.default_texture_id);
+
+        if indexed {
+            self.gl.draw_elements(DrawMode::Triangles, count, AttribType::UnsignedShort, 0);
+        } else {
+            self.gl.draw_arrays(DrawMode::Triangles, 0, count);
+        }
+
+        self.gl.bind_texture(0); // Unbind textures
+        self.gl.use_program(0); // Unbind shader program
+    }
+
+    /// Draw render batch data (update buffers, draw, reset)
+    /// NOTE: Draw calls with a different shader than the previous one rebind the program
+    pub(crate) fn draw_render_batch(&mut self, reason: FlushReason) {
+        self.diagnostics.record(reason, self.vertex_counter);
+
+        // Update batch vertex buffers
+        // NOTE: If there is not vertex data, buffers doesn't need to be updated (vertexCount > 0)
+        let buffer = &self.batch.vertex_buffer[self.batch.current_buffer];
+        if self.vertex_counter > 0 {
+            if buffer.vao_id != 0 {
+                self.gl.bind_vertex_array(buffer.vao_id);
+            }
+            buffer.update(&mut *self.gl, self.vertex_counter);
+            if buffer.vao_id != 0 {
+                self.gl.bind_vertex_array(0);
+            }
+        }
+
+        // Draw batch vertex buffers
+        if self.vertex_counter > 0 {
+            let (vao_id, draw_counter) = (buffer.vao_id, self.batch.draw_counter);
+
+            if vao_id != 0 {
+                self.gl.bind_vertex_array(vao_id);
+            } else {
+                buffer.bind_attribs(&mut *self.gl);
+            }
+
+            // Activate additional sampler textures
+            // NOTE: Those textures are common for all draw calls of the batch
+            for (unit, &texture_id) in (1..).zip(&self.active_texture_ids) {
+                if texture_id > 0 {
+                    self.gl.active_texture(unit);
+                    self.gl.bind_texture(texture_id);
+                }
+            }
+            self.gl.active_texture(0);
+
+            // NOTE: Stereo rendering draws the batch once per eye, each eye on half of the framebuffer
+            let eye_count = if self.stereo_render { 2 } else { 1 };
+            for eye in 0..eye_count {
+                let mvp = if self.stereo_render {
+                    // Setup current eye viewport (half screen width)
+                    let (width, height) = self.framebuffer_size;
+                    self.gl.viewport(if eye == 0 { 0 } else { width/2 }, 0, width/2, height);
+
+                    // Set current eye view offset to modelview matrix and projection matrix
+                    (self.modelview*self.view_offset_stereo[eye])*self.projection_stereo[eye]
+                } else {
+                    self.modelview*self.projection
+                };
+
+                let mut program = None;
+                let mut vertex_offset = 0;
+                for i in 0..draw_counter {
+                    let draw = self.batch.draws[i];
+                    // NOTE: Shader id 0 stands for the default shader
+                    let shader_id = if draw.shader_id == 0 { self.default_shader_id } else { draw.shader_id };
+                    if program != Some(shader_id) {
+                        self.use_batch_program(shader_id, &mvp, [1.0; 4]);
+                        program = Some(shader_id);
+                    }
+
+                    // Bind current draw call texture, activated as GL_TEXTURE0 and bound to sampler2D texture0 by default
+                    self.gl.bind_texture(draw.texture_id);
+
+                    if matches!(draw.mode, DrawMode::Lines | DrawMode::Triangles) {
+                        self.gl.draw_arrays(draw.mode, vertex_offset, draw.vertex_count);
+                    } else {
+                        // We need to define the number of indices to be processed: elementCount*6
+                        // NOTE: The final parameter tells the GPU the offset in bytes from the
+                        // start of the index buffer to the location of the first index to process
+                        self.gl.draw_elements(DrawMode::Triangles, draw.vertex_count/4*6, VERTEX_INDEX_TYPE, vertex_offset/4*6*size_of::<VertexIndex>());
+                    }
+
+                    vertex_offset += draw.vertex_count + draw.vertex_alignment;
+                }
+            }
+
+            if vao_id != 0 {
+                self.gl.bind_vertex_array(0); // Unbind VAO
+            } else {
+                self.gl.bind_buffer(BufferTarget::Array, 0);
+                self.gl.bind_buffer(BufferTarget::ElementArray, 0);
+            }
+            self.gl.bind_texture(0); // Unbind textures
+            self.gl.use_program(0); // Unbind shader program
+
+            // Restore viewport to default measures
+            if self.stereo_render {
+                let (width, height) = self.framebuffer_size;
+                self.gl.viewport(0, 0, width, height);
+            }
+        }
+
+        // Reset active texture units for next batch
+        self.active_texture_ids = [0; RL_DEFAULT_BATCH_MAX_TEXTURE_UNITS];
+
+        // Reset batch buffers
+        self.vertex_counter = 0;
+        self.batch.current_depth = -1.0;
+
+        // Reset draws array
+        let default_texture_id = self.default_texture_id;
+        for draw in &mut self.batch.draws {
+            draw.mode = DrawMode::Quads;
+            draw.vertex_count = 0;
+            draw.vertex_alignment = 0;
+            draw.texture_id = default_texture_id;
+            draw.shader_id = 0;
+        }
+
+        // Reset draws counter to one draw for the batch
+        self.batch.draw_counter = 1;
+
+        // Change to next buffer in the list (in case of multi-buffering)
+        self.batch.current_buffer = (self.batch.current_buffer + 1)%self.batch.buffer_count;
+
+        // NOTE: Released objects are not used by any pending draw anymore
+        self.unload_pending();
+    }
+}
+
+/// Set the OpenGL backend used for rendering, replacing the default no-op one
+/// NOTE: rlgl is initialized again, textures loaded with the previous backend are not valid anymore
+/// NOTE: OpenGL version, capabilities and extensions are detected on the new backend
+/// NOTE: Must be called after the window (and OpenGL context) is created,
+/// see [`gl`] for the reasons of this boundary
+pub fn set_gl_api(core: &mut Core, gl: impl GlApi + 'static) {
+    core.rlgl.set_gl(Box::new(gl));
+    core.setup_viewport(core.window.render.width, core.window.render.height); // Projection was reset with rlgl
+    tracelog!(Info, "RLGL: OpenGL backend set, default data loaded");
+}
+
+/// Get current OpenGL version
+#[inline]
+#[must_use]
+pub fn rl_get_version(core: &Core) -> GlVersion {
+    core.rlgl.get_ext_supported().version
+}
+
+/// Get OpenGL capabilities and extensions support, detected when the backend was set
+#[inline]
+#[must_use]
+pub fn rl_get_ext_supported(core: &Core) -> ExtSupported {
+    core.rlgl.get_ext_supported()
+}
+
+/// Get maximum anisotropy level supported, 0 if anisotropic filtering is not supported
+#[inline]
+#[must_use]
+pub fn rl_get_texture_max_anisotropy(core: &Core) -> f32 {
+    core.rlgl.get_ext_supported().max_anisotropy_level
+}
+
+/// Enable stereo rendering
+/// NOTE: The batch is drawn once per eye, see `rl_set_matrix_projection_stereo()`
+#[inline]
+pub fn rl_enable_stereo_render(core: &mut Core) {
+    core.rlgl.set_stereo_render(true);
+}
+
+/// Disable stereo rendering
+#[inline]
+pub fn rl_disable_stereo_render(core: &mut Core) {
+    core.rlgl.set_stereo_render(false);
+}
+
+/// Check if stereo render is enabled
+#[inline]
+#[must_use]
+pub fn rl_is_stereo_render_enabled(core: &Core) -> bool {
+    core.rlgl.is_stereo_render_enabled()
+}
+
+/// Set eyes projection matrices for stereo rendering, index 0 is drawn on the left half of the framebuffer
+#[inline]
+pub fn rl_set_matrix_projection_stereo(core: &mut Core, projections: [Matrix; 2]) {
+    core.rlgl.set_matrix_projection_stereo(projections);
+}
+
+/// Set eyes view offsets matrices for stereo rendering, index 0 is drawn on the left half of the framebuffer
+#[inline]
+pub fn rl_set_matrix_view_offset_stereo(core: &mut Core, view_offsets: [Matrix; 2]) {
+    core.rlgl.set_matrix_view_offset_stereo(view_offsets);
+}
+
+/// Set clear color, used by `rl_clear_screen_buffers()`
+#[inline]
+pub fn rl_clear_color(core: &mut Core, r: u8, g: u8, b: u8, a: u8) {
+    core.rlgl.clear_color(r, g, b, a);
+}
+
+/// Clear used screen buffers (color and depth) of the active framebuffer
+#[inline]
+pub fn rl_clear_screen_buffers(core: &mut Core) {
+    core.rlgl.clear_screen_buffers();
+}
+
+/// Set the viewport area, framebuffer coordinates (bottom-left origin)
+/// NOTE: Projection matrix is not changed
+#[inline]
+pub fn rl_viewport(core: &mut Core, x: i32, y: i32, width: i32, height: i32) {
+    core.rlgl.viewport(x, y, width, height);
+}
+
+/// Enable depth test
+/// NOTE: Pending draws are flushed first, already queued 2D geometry is not depth tested
+#[inline]
+pub fn rl_enable_depth_test(core: &mut Core) {
+    core.rlgl.set_depth_test(true);
+}
+
+/// Disable depth test
+#[inline]
+pub fn rl_disable_depth_test(core: &mut Core) {
+    core.rlgl.set_depth_test(false);
+}
+
+/// Enable depth write
+#[inline]
+pub fn rl_enable_depth_mask(core: &mut Core) {
+    core.rlgl.set_depth_mask(true);
+}
+
+/// Disable depth write
+#[inline]
+pub fn rl_disable_depth_mask(core: &mut Core) {
+    core.rlgl.set_depth_mask(false);
+}
+
+/// Enable backface culling
+#[inline]
+pub fn rl_enable_backface_culling(core: &mut Core) {
+    core.rlgl.set_backface_culling(true);
+}
+
+/// Disable backface culling
+#[inline]
+pub fn rl_disable_backface_culling(core: &mut Core) {
+    core.rlgl.set_backface_culling(false);
+}
+
+/// Set face culling mode
+#[inline]
+pub fn rl_set_cull_face(core: &mut Core, mode: CullMode) {
+    core.rlgl.set_cull_face(mode);
+}
+
+/// Enable wire mode
+/// NOTE: Not supported on OpenGL ES
+#[inline]
+pub fn rl_enable_wire_mode(core: &mut Core) {
+    core.rlgl.set_wire_mode(true);
+}
+
+/// Disable wire mode
+#[inline]
+pub fn rl_disable_wire_mode(core: &mut Core) {
+    core.rlgl.set_wire_mode(false);
+}
+
+/// Enable line aliasing
+/// NOTE: Not supported on OpenGL ES
+#[inline]
+pub fn rl_enable_smooth_lines(core: &mut Core) {
+    core.rlgl.set_smooth_lines(true);
+}
+
+/// Disable line aliasing
+#[inline]
+pub fn rl_disable_smooth_lines(core: &mut Core) {
+    core.rlgl.set_smooth_lines(false);
+}
+
+/// Choose the current matrix to be transformed
+#[inline]
+pub fn rl_matrix_mode(core: &mut Core, mode: MatrixMode) {
+    core.rlgl.matrix_mode(mode);
+}
+
+/// Push the current matrix to stack
+/// NOTE: Stack overflow is logged and the push is ignored
+#[inline]
+pub fn rl_push_matrix(core: &mut Core) {
+    core.rlgl.push_matrix();
+}
+
+/// Pop latest inserted matrix from stack
+#[inline]
+pub fn rl_pop_matrix(core: &mut Core) {
+    core.rlgl.pop_matrix();
+}
+
+/// Reset current matrix to identity matrix
+#[inline]
+pub fn rl_load_identity(core: &mut Core) {
+    core.rlgl.load_identity();
+}
+
+/// Multiply the current matrix by a translation matrix
+#[inline]
+pub fn rl_translatef(core: &mut Core, x: f32, y: f32, z: f32) {
+    core.rlgl.translatef(x, y, z);
+}
+
+/// Multiply the current matrix by a rotation matrix
+/// NOTE: The provided axis is normalized
+#[inline]
+pub fn rl_rotatef(core: &mut Core, angle: Degrees, x: f32, y: f32, z: f32) {
+    core.rlgl.rotatef(angle, x, y, z);
+}
+
+/// Multiply the current matrix by a scaling matrix
+#[inline]
+pub fn rl_scalef(core: &mut Core, x: f32, y: f32, z: f32) {
+    core.rlgl.scalef(x, y, z);
+}
+
+/// Multiply the current matrix by another matrix
+#[inline]
+pub fn rl_mult_matrixf(core: &mut Core, mat: Matrix) {
+    core.rlgl.mult_matrix(mat);
+}
+
+/// Multiply the current matrix by a perspective matrix generated by parameters
+#[inline]
+pub fn rl_frustum(core: &mut Core, left: f64, right: f64, bottom: f64, top: f64, znear: f64, zfar: f64) {
+    core.rlgl.frustum(left, right, bottom, top, znear, zfar);
+}
+
+/// Multiply the current matrix by an orthographic matrix generated by parameters
+#[inline]
+pub fn rl_ortho(core: &mut Core, left: f64, right: f64, bottom: f64, top: f64, znear: f64, zfar: f64) {
+    core.rlgl.ortho(left, right, bottom, top, znear, zfar);
+}
+
+/// Get internal modelview matrix
+#[inline]
+pub fn rl_get_matrix_modelview(core: &Core) -> Matrix {
+    *core.rlgl.get_matrix_modelview()
+}
+
+/// Get internal projection matrix
+#[inline]
+pub fn rl_get_matrix_projection(core: &Core) -> Matrix {
+    *core.rlgl.get_matrix_projection()
+}
+
+/// Get internal accumulated transform matrix
+#[inline]
+pub fn rl_get_matrix_transform(core: &Core) -> Matrix {
+    *core.rlgl.get_matrix_transform()
+}
+
+/// Initialize drawing mode (how to organize vertex)
+#[inline]
+pub fn rl_begin(core: &mut Core, mode: DrawMode) {
+    core.rlgl.begin(mode);
+}
+
+/// Finish vertex providing
+#[inline]
+pub fn rl_end(core: &mut Core) {
+    core.rlgl.end();
+}
+
+/// Define one vertex (position) - 2 float
+#[inline]
+pub fn rl_vertex2f(core: &mut Core, x: f32, y: f32) {
+    core.rlgl.vertex2f(x, y);
+}
+
+/// Define one vertex (position) - 3 float
+#[inline]
+pub fn rl_vertex3f(core: &mut Core, x: f32, y: f32, z: f32) {
+    core.rlgl.vertex3f(x, y, z);
+}
+
+/// Define one vertex (texture coordinate) - 2 float
+#[inline]
+pub fn rl_tex_coord2f(core: &mut Core, x: f32, y: f32) {
+    core.rlgl.tex_coord2f(x, y);
+}
+
+/// Define one vertex (normal) - 3 float
+#[inline]
+pub fn rl_normal3f(core: &mut Core, x: f32, y: f32, z: f32) {
+    core.rlgl.normal3f(x, y, z);
+}
+
+/// Define one vertex (color) - 4 byte
+#[inline]
+pub fn rl_color4ub(core: &mut Core, r: u8, g: u8, b: u8, a: u8) {
+    core.rlgl.color4ub(r, g, b, a);
+}
+
+/// Define one vertex (color) - 4 float
+/// NOTE: Color components are expected in range [0.0f..1.0f]
+#[inline]
+pub fn rl_color4f(core: &mut Core, r: f32, g: f32, b: f32, a: f32) {
+    core.rlgl.color4f(r, g, b, a);
+}
+
+/// Define one vertex (color) - 3 float
+/// NOTE: Color components are expected in range [0.0f..1.0f]
+#[inline]
+pub fn rl_color3f(core: &mut Core, r: f32, g: f32, b: f32) {
+    core.rlgl.color3f(r, g, b);
+}
+
+/// Set current texture to use, 0 for the default texture
+#[inline]
+pub fn rl_set_texture(core: &mut Core, id: u32) {
+    core.rlgl.set_texture(id);
+}
+
+/// Compile custom shader and return shader id
+/// # Errors
+/// Returns an error with the compiler info log if the shader fails to compile
+#[inline]
+pub fn rl_compile_shader(core: &mut Core, source: &str, kind: ShaderType) -> Result<u32, ShaderCompileError> {
+    core.rlgl.compile_shader(source, kind)
+}
+
+/// Load custom shader program from compiled vertex and fragment shaders
+/// NOTE: Shaders are not deleted once linked, they can be reused by other programs
+/// # Errors
+/// Returns an error with the linker info log if the program fails to link
+#[inline]
+pub fn rl_load_shader_program(core: &mut Core, vs_id: u32, fs_id: u32) -> Result<u32, ShaderCompileError> {
+    core.rlgl.load_shader_program(vs_id, fs_id)
+}
+
+/// Read screen pixel data (color buffer), RGBA 8 bit, top to bottom
+#[inline]
+pub(crate) fn rl_read_screen_pixels(core: &mut Core, width: usize, height: usize) -> Vec<u8> {
+    core.rlgl.read_screen_pixels(width, height)
+}
+
+// impl RLGL {
+//     // Initialize rlgl: OpenGL extensions, default buffers/shaders/textures, OpenGL states
+//     pub fn init(width: u32, height: u32) {
+//         // Enable OpenGL debug context if required
+//         if cfg!(rlgl_enable_opengl_debug_context) && cfg!(graphics_api_opengl_43) {
+//             if !glDebugMessageCallback.is_none() && !glDebugMessageControl.is_none() {
+//                 glDebugMessageCallback(rlDebugMessageCallback, 0);
+//                 // glDebugMessageControl(GL_DEBUG_SOURCE_API, GL_DEBUG_TYPE_ERROR, GL_DEBUG_SEVERITY_HIGH, 0, 0, GL_TRUE);
+
+//                 // Debug context options:
+//                 //  - GL_DEBUG_OUTPUT - Faster version but not useful for breakpoints
+//                 //  - GL_DEBUG_OUTPUT_SYNCHRONUS - Callback is in sync with errors, so a breakpoint can be placed on the callback in order to get a stacktrace for the GL error
+//                 glEnable(GL_DEBUG_OUTPUT);
+//                 glEnable(GL_DEBUG_OUTPUT_SYNCHRONOUS);
+//             }
+//         }
+
+//         if cfg!(graphics_api_opengl_33) || cfg!(graphics_api_opengl_es2) {
+//             // Init default white texture
+//             unsigned char pixels[4] = { 255, 255, 255, 255 };   // 1 pixel RGBA (4 bytes)
+//             RLGL.State.defaultTextureId = rlLoadTexture(pixels, 1, 1, RL_PIXELFORMAT_UNCOMPRESSED_R8G8B8A8, 1);
+
+//             if (RLGL.State.defaultTextureId != 0) TRACELOG(RL_LOG_INFO, "TEXTURE: [ID %i] Default texture loaded successfully", RLGL.State.defaultTextureId);
+//             else TRACELOG(RL_LOG_WARNING, "TEXTURE: Failed to load default texture");
+
+//             // Init default Shader (customized for GL 3.3 and ES2)
+//             // Loaded: RLGL.State.defaultShaderId + RLGL.State.defaultShaderLocs
+//             rlLoadShaderDefault();
+//             RLGL.State.currentShaderId = RLGL.State.defaultShaderId;
+//             RLGL.State.currentShaderLocs = RLGL.State.defaultShaderLocs;
+
+//             // Init default vertex arrays buffers
+//             // Simulate that the default shader has the location RL_SHADER_LOC_VERTEX_NORMAL to bind the normal buffer for the default render batch
+//             RLGL.State.currentShaderLocs[RL_SHADER_LOC_VERTEX_NORMAL] = RL_DEFAULT_SHADER_ATTRIB_LOCATION_NORMAL;
+//             RLGL.defaultBatch = rlLoadRenderBatch(RL_DEFAULT_BATCH_BUFFERS, RL_DEFAULT_BATCH_BUFFER_ELEMENTS);
+//             RLGL.State.currentShaderLocs[RL_SHADER_LOC_VERTEX_NORMAL] = -1;
+//             RLGL.currentBatch = &RLGL.defaultBatch;
+
+//             // Init stack matrices (emulating OpenGL 1.1)
+//             for (int i = 0; i < RL_MAX_MATRIX_STACK_SIZE; i++) RLGL.State.stack[i] = rlMatrixIdentity();
+
+//             // Init internal matrices
+//             RLGL.State.transform = rlMatrixIdentity();
+//             RLGL.State.projection = rlMatrixIdentity();
+//             RLGL.State.modelview = rlMatrixIdentity();
+//             RLGL.State.currentMatrix = &RLGL.State.modelview;
+//         }  // GRAPHICS_API_OPENGL_33 || GRAPHICS_API_OPENGL_ES2
+
+//         // Initialize OpenGL default states
+//         //----------------------------------------------------------
+//         // Init state: Depth test
+//         glDepthFunc(GL_LEQUAL);                                 // Type of depth testing to apply
+//         glDisable(GL_DEPTH_TEST);                               // Disable depth testing for 2D (only used for 3D)
+
+//         // Init state: Blending mode
+//         glBlendFunc(GL_SRC_ALPHA, GL_ONE_MINUS_SRC_ALPHA);      // Color blending function (how colors are mixed)
+//         glEnable(GL_BLEND);                                     // Enable color blending (required to work with transparencies)
+
+//         // Init state: Culling
+//         // NOTE: All shapes/models triangles are drawn CCW
+//         glCullFace(GL_BACK);                                    // Cull the back face (default)
+//         glFrontFace(GL_CCW);                                    // Front face are defined counter clockwise (default)
+//         glEnable(GL_CULL_FACE);                                 // Enable backface culling
+
+//         // Init state: Cubemap seamless
+//         if cfg!(graphics_api_opengl_33) {
+//             glEnable(GL_TEXTURE_CUBE_MAP_SEAMLESS);                 // Seamless cubemaps (not supported on OpenGL ES 2.0)
+//         }
+
+//         if cfg!(graphics_api_opengl_11) {
+//             // Init state: Color hints (deprecated in OpenGL 3.0+)
+//             glHint(GL_PERSPECTIVE_CORRECTION_HINT, GL_NICEST);      // Improve quality of color and texture coordinate interpolation
+//             glShadeModel(GL_SMOOTH);                                // Smooth shading between vertex (vertex colors interpolation)
+//         }
+
+//         if cfg!(graphics_api_opengl_33) || cfg!(graphics_api_opengl_es2) {
+//             // Store screen size into global variables
+//             RLGL.State.framebufferWidth = width;
+//             RLGL.State.framebufferHeight = height;
+
+//             TRACELOG(RL_LOG_INFO, "RLGL: Default OpenGL state initialized successfully");
+//             //----------------------------------------------------------
+//         }
+
+//         // Init state: Color/Depth buffers clear
+//         glClearColor(0.0f, 0.0f, 0.0f, 1.0f);                   // Set clear color (black)
+//         glClearDepth(1.0f);                                     // Set clear depth value (default)
+//         glClear(GL_COLOR_BUFFER_BIT | GL_DEPTH_BUFFER_BIT);     // Clear color and depth buffers (depth buffer required for 3D)
+//     }
+
+//     // Vertex Buffer Object deinitialization (memory free)
+//     pub fn rlglClose() {
+//         if cfg!(graphics_api_opengl_33) || cfg!(graphics_api_opengl_es2) {
+//             rlUnloadRenderBatch(RLGL.defaultBatch);
+
+//             // Unload default shader
+//             rlUnloadShaderDefault();
+
+//             // Unload default texture
+//             glDeleteTextures(1, &RLGL.State.defaultTextureId);
+//             TRACELOG(RL_LOG_INFO, "TEXTURE: [ID %i] Default texture unloaded successfully", RLGL.State.defaultTextureId);
+//         }
+//     }
+// }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{cell::RefCell, rc::Rc};
+    use crate::prelude::{Distance, MatrixTransform, Radians};
+    use super::*;
+    use gl::GL_FRAMEBUFFER_COMPLETE;
+
+    /// Vertex data uploaded with `buffer_sub_data`
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) enum Upload {
+        F32(Vec<f32>),
+        U8(Vec<u8>),
+    }
+
+    /// Batch related OpenGL calls, recorded by [`RecordingGl`]
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) enum GlCall {
+        BufferSubData(Upload),
+        UseProgram(u32),
+        Enable(u32),
+        Disable(u32),
+        Viewport { x: i32, y: i32, width: i32, height: i32 },
+        BlendFunc { src: u32, dst: u32 },
+        BlendFuncSeparate([u32; 4]),
+        BlendEquation(u32),
+        BlendEquationSeparate { rgb: u32, alpha: u32 },
+        UniformMatrix4(Matrix),
+        Uniform4f([f32; 4]),
+        UniformFv { location: i32, values: Vec<f32> },
+        BindTexture(u32),
+        DeleteTexture(u32),
+        DeleteProgram(u32),
+        DrawArrays { mode: DrawMode, first: usize, count: usize },
+        DrawElements { count: usize, offset: usize },
+        ReadPixels { width: usize, height: usize },
+    }
+
+    pub(crate) type CallLog = Rc<RefCell<Vec<GlCall>>>;
+
+    /// OpenGL backend recording batch uploads and draws, objects are just numbered like [`HeadlessGl`]
+    #[derive(Debug, Default)]
+    struct RecordingGl {
+        next_id: u32,
+        calls: CallLog,
+    }
+
+    impl RecordingGl {
+        fn next_id(&mut self) -> u32 {
+            self.next_id += 1;
+            self.next_id
+        }
+
+        fn record(&self, call: GlCall) {
+            self.calls.borrow_mut().push(call);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    impl GlApi for RecordingGl {
+        fn version(&self) -> GlVersion { GlVersion::Gl33 }
+        fn enable(&mut self, cap: u32) { self.record(GlCall::Enable(cap)); }
+        fn disable(&mut self, cap: u32) { self.record(GlCall::Disable(cap)); }
+        fn clear_color(&mut self, _r: f32, _g: f32, _b: f32, _a: f32) {}
+        fn clear(&mut self, _mask: u32) {}
+        fn viewport(&mut self, x: i32, y: i32, width: i32, height: i32) { self.record(GlCall::Viewport { x, y, width, height }); }
+        fn scissor(&mut self, _x: i32, _y: i32, _width: i32, _height: i32) {}
+        fn depth_func(&mut self, _func: u32) {}
+        fn depth_mask(&mut self, _enabled: bool) {}
+        fn cull_face(&mut self, _face: u32) {}
+        fn front_face(&mut self, _mode: u32) {}
+        fn polygon_mode(&mut self, _face: u32, _mode: u32) {}
+        fn blend_func(&mut self, src_factor: u32, dst_factor: u32) { self.record(GlCall::BlendFunc { src: src_factor, dst: dst_factor }); }
+        fn blend_func_separate(&mut self, src_rgb: u32, dst_rgb: u32, src_alpha: u32, dst_alpha: u32) { self.record(GlCall::BlendFuncSeparate([src_rgb, dst_rgb, src_alpha, dst_alpha])); }
+        fn blend_equation(&mut self, equation: u32) { self.record(GlCall::BlendEquation(equation)); }
+        fn blend_equation_separate(&mut self, equation_rgb: u32, equation_alpha: u32) { self.record(GlCall::BlendEquationSeparate { rgb: equation_rgb, alpha: equation_alpha }); }
+        fn gen_vertex_array(&mut self) -> Option<u32> { Some(self.next_id()) }
+        fn bind_vertex_array(&mut self, _id: u32) {}
+        fn delete_vertex_array(&mut self, _id: u32) {}
+        fn gen_buffer(&mut self) -> u32 { self.next_id() }
+        fn bind_buffer(&mut self, _target: BufferTarget, _id: u32) {}
+        fn buffer_data(&mut self, _target: BufferTarget, _data: BufferData<'_>, _usage: BufferUsage) {}
+        fn buffer_sub_data(&mut self, _target: BufferTarget, _offset: usize, data: BufferData<'_>) {
+            let upload = match data {
+                BufferData::F32(data) => Upload::F32(data.to_vec()),
+                BufferData::U8(data) => Upload::U8(data.to_vec()),
+                BufferData::U16(_) | BufferData::U32(_) => return,
+            };
+            self.record(GlCall::BufferSubData(upload));
+        }
+        fn delete_buffer(&mut self, _id: u32) {}
+        fn enable_vertex_attrib_array(&mut self, _index: u32) {}
+        fn disable_vertex_attrib_array(&mut self, _index: u32) {}
+        fn vertex_attrib_pointer(&mut self, _index: u32, _size: i32, _ty: AttribType, _normalized: bool, _stride: usize, _offset: usize) {}
+        fn create_shader(&mut self, _kind: ShaderType) -> u32 { self.next_id() }
+        fn shader_source(&mut self, _shader: u32, _source: &str) {}
+        fn compile_shader(&mut self, _shader: u32) {}
+        fn get_shader_compile_status(&mut self, _shader: u32) -> bool { true }
+        fn get_shader_info_log(&mut self, _shader: u32) -> String { String::new() }
+        fn delete_shader(&mut self, _shader: u32) {}
+        fn create_program(&mut self) -> u32 { self.next_id() }
+        fn attach_shader(&mut self, _program: u32, _shader: u32) {}
+        fn detach_shader(&mut self, _program: u32, _shader: u32) {}
+        fn bind_attrib_location(&mut self, _program: u32, _index: u32, _name: &str) {}
+        fn link_program(&mut self, _program: u32) {}
+        fn get_program_link_status(&mut self, _program: u32) -> bool { true }
+        fn get_program_info_log(&mut self, _program: u32) -> String { String::new() }
+        fn delete_program(&mut self, program: u32) { self.record(GlCall::DeleteProgram(program)); }
+        fn get_attrib_location(&mut self, _program: u32, _name: &str) -> i32 { -1 }
+        fn use_program(&mut self, id: u32) { self.record(GlCall::UseProgram(id)); }
+        fn get_uniform_location(&mut self, _program: u32, _name: &str) -> i32 { -1 }
+        fn uniform_matrix4(&mut self, _location: i32, mat: &Matrix) { self.record(GlCall::UniformMatrix4(*mat)); }
+        fn uniform4f(&mut self, _location: i32, value: [f32; 4]) { self.record(GlCall::Uniform4f(value)); }
+        fn uniform1i(&mut self, _location: i32, _value: i32) {}
+        fn uniform_fv(&mut self, location: i32, _components: usize, values: &[f32]) { self.record(GlCall::UniformFv { location, values: values.to_vec() }); }
+        fn uniform_iv(&mut self, _location: i32, _components: usize, _values: &[i32]) {}
+        fn active_texture(&mut self, _unit: u32) {}
+        fn gen_texture(&mut self) -> u32 { self.next_id() }
+        fn bind_texture(&mut self, id: u32) { self.record(GlCall::BindTexture(id)); }
+        fn delete_texture(&mut self, id: u32) { self.record(GlCall::DeleteTexture(id)); }
+        fn pixel_store_unpack_alignment(&mut self, _alignment: i32) {}
+        fn tex_image_2d(&mut self, _level: usize, _internal_format: u32, _width: usize, _height: usize, _format: u32, _ty: u32, _data: Option<&[u8]>) {}
+        fn compressed_tex_image_2d(&mut self, _level: usize, _internal_format: u32, _width: usize, _height: usize, _size: usize, _data: Option<&[u8]>) {}
+        fn tex_sub_image_2d(&mut self, _level: usize, _offset_x: usize, _offset_y: usize, _width: usize, _height: usize, _format: u32, _ty: u32, _data: &[u8]) {}
+        fn tex_parameteri(&mut self, _pname: u32, _param: i32) {}
+        fn tex_parameteriv(&mut self, _pname: u32, _params: &[i32]) {}
+        fn is_extension_supported(&mut self, _name: &str) -> bool { false }
+        fn get_string(&mut self, _name: u32) -> String { String::new() }
+        fn get_integer(&mut self, _pname: u32) -> i32 { 0 }
+        fn get_float(&mut self, _pname: u32) -> f32 { 0.0 }
+        #[allow(clippy::cast_possible_truncation)]
+        fn read_pixels(&mut self, _x: i32, _y: i32, width: usize, height: usize, _format: u32, _ty: u32, data: &mut [u8]) {
+            // Pixels hold their own coordinates, bottom row first like OpenGL
+            for (i, pixel) in data.chunks_exact_mut(4).enumerate() {
+                pixel.copy_from_slice(&[(i%width) as u8, (i/width) as u8, 0, 0]);
+            }
+            self.record(GlCall::ReadPixels { width, height });
+        }
+        fn gen_framebuffer(&mut self) -> u32 { self.next_id() }
+        fn bind_framebuffer(&mut self, _id: u32) {}
+        fn framebuffer_texture_2d(&mut self, _attachment: u32, _tex_target: u32, _texture: u32, _level: usize) {}
+        fn framebuffer_renderbuffer(&mut self, _attachment: u32, _renderbuffer: u32) {}
+        fn check_framebuffer_status(&mut self) -> u32 { GL_FRAMEBUFFER_COMPLETE }
+        fn delete_framebuffer(&mut self, _id: u32) {}
+        fn gen_renderbuffer(&mut self) -> u32 { self.next_id() }
+        fn bind_renderbuffer(&mut self, _id: u32) {}
+        fn renderbuffer_storage(&mut self, _internal_format: u32, _width: usize, _height: usize) {}
+        fn delete_renderbuffer(&mut self, _id: u32) {}
+        fn draw_arrays(&mut self, mode: DrawMode, first: usize, count: usize) { self.record(GlCall::DrawArrays { mode, first, count }); }
+        fn draw_elements(&mut self, _mode: DrawMode, count: usize, _ty: AttribType, offset: usize) { self.record(GlCall::DrawElements { count, offset }); }
+    }
+
+    /// rlgl on a recording backend, calls made during initialization are discarded
+    pub(crate) fn recording_rlgl() -> (RLGL, CallLog) {
+        let gl = RecordingGl::default();
+        let calls = Rc::clone(&gl.calls);
+        let rlgl = RLGL::new(Box::new(gl));
+        calls.borrow_mut().clear();
+        (rlgl, calls)
+    }
+
+    #[track_caller]
+    fn assert_near(a: Matrix, b: Matrix) {
+        let close = <[f32; 16]>::from(a).iter().zip(<[f32; 16]>::from(b)).all(|(x, y)| (x - y).abs() < 1.0e-5);
+        assert!(close, "{a:?} != {b:?}");
+    }
+
+    #[track_caller]
+    fn assert_near_vec3(a: Vector3, b: Vector3) {
+        assert!(a.distance(b) < 1.0e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_matrix_stack_push_pop() {
+        let mut rlgl = RLGL::default();
+        rlgl.translatef(1.0, 2.0, 3.0);
+        assert_eq!(*rlgl.get_matrix_modelview(), Matrix::translate(1.0, 2.0, 3.0));
+        assert!(!rlgl.is_transform_required());
+
+        // Modelview pushes accumulate on the transform matrix, modelview is kept
+        rlgl.push_matrix();
+        assert!(rlgl.is_transform_required());
+        rlgl.translatef(4.0, 0.0, 0.0);
+        rlgl.push_matrix();
+        rlgl.scalef(2.0, 2.0, 2.0);
+        assert_eq!(*rlgl.get_matrix_transform(), Matrix::scale(2.0, 2.0, 2.0) * Matrix::translate(4.0, 0.0, 0.0));
+        assert_eq!(*rlgl.get_matrix_modelview(), Matrix::translate(1.0, 2.0, 3.0));
+
+        rlgl.pop_matrix();
+        assert_eq!(*rlgl.get_matrix_transform(), Matrix::translate(4.0, 0.0, 0.0));
+        assert!(rlgl.is_transform_required());
+        rlgl.pop_matrix();
+        assert_eq!(*rlgl.get_matrix_transform(), Matrix::IDENTITY);
+        assert!(!rlgl.is_transform_required());
+
+        // Back to modelview once the stack is empty
+        rlgl.load_identity();
+        assert_eq!(*rlgl.get_matrix_modelview(), Matrix::IDENTITY);
+    }
+
+    #[test]
+    fn test_matrix_stack_projection() {
+        let mut rlgl = RLGL::default();
+        rlgl.matrix_mode(MatrixMode::Projection);
+        rlgl.load_identity();
+        rlgl.push_matrix();
+        rlgl.ortho(0.0, 800.0, 600.0, 0.0, 0.0, 1.0);
+        assert_eq!(*rlgl.get_matrix_projection(), Matrix::ortho(0.0, 800.0, 600.0, 0.0, 0.0, 1.0));
+        assert!(!rlgl.is_transform_required());
+        rlgl.pop_matrix();
+        assert_eq!(*rlgl.get_matrix_projection(), Matrix::IDENTITY);
+
+        rlgl.frustum(-1.0, 1.0, -1.0, 1.0, 0.1, 100.0);
+        assert_eq!(*rlgl.get_matrix_projection(), Matrix::frustrum(-1.0, 1.0, -1.0, 1.0, 0.1, 100.0));
+
+        // Texture mode is not supported, projection stays current
+        rlgl.matrix_mode(MatrixMode::Texture);
+        rlgl.load_identity();
+        assert_eq!(*rlgl.get_matrix_projection(), Matrix::IDENTITY);
+        assert_eq!(*rlgl.get_matrix_modelview(), Matrix::IDENTITY);
+    }
+
+    #[test]
+    fn test_matrix_stack_overflow() {
+        let mut rlgl = RLGL::default();
+        for i in 0..RL_MAX_MATRIX_STACK_SIZE + 4 {
+            rlgl.push_matrix();
+            rlgl.translatef(1.0, 0.0, 0.0);
+            assert!(rlgl.stack.len() <= RL_MAX_MATRIX_STACK_SIZE, "push {i} overflowed");
+        }
+        assert_eq!(rlgl.stack.len(), RL_MAX_MATRIX_STACK_SIZE);
+
+        // Extra pops are ignored as well
+        for _ in 0..RL_MAX_MATRIX_STACK_SIZE + 4 {
+            rlgl.pop_matrix();
+        }
+        assert!(rlgl.stack.is_empty());
+        assert!(!rlgl.is_transform_required());
+        assert_eq!(*rlgl.get_matrix_transform(), Matrix::IDENTITY);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_matrix_stack_transforms_vertices() {
+        let mut rlgl = RLGL::default();
+        rlgl.begin(DrawMode::Lines);
+        rlgl.push_matrix();
+        // Last operation applies first, like OpenGL: rotate, scale, then translate
+        rlgl.translatef(10.0, 0.0, 0.0);
+        rlgl.scalef(2.0, 2.0, 2.0);
+        rlgl.rotatef(Degrees(90.0), 0.0, 0.0, 1.0);
+        rlgl.vertex3f(1.0, 0.0, 0.0);
+        rlgl.mult_matrix(Matrix::translate(0.0, 0.0, 5.0));
+        rlgl.vertex3f(1.0, 0.0, 0.0);
+        rlgl.pop_matrix();
+        rlgl.vertex3f(1.0, 0.0, 0.0);
+        rlgl.end();
+
+        let vertices = &rlgl.batch.buffer().vertices;
+        assert_near_vec3(Vector3::new(vertices[0], vertices[1], vertices[2]), Vector3::new(10.0, 2.0, 0.0));
+        assert_near_vec3(Vector3::new(vertices[3], vertices[4], vertices[5]), Vector3::new(10.0, 2.0, 10.0));
+        assert_eq!(vertices[6..9], [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_matrix_stack_mvp_upload() {
+        let (mut rlgl, calls) = recording_rlgl();
+        rlgl.matrix_mode(MatrixMode::Projection);
+        rlgl.load_identity();
+        rlgl.ortho(0.0, 800.0, 600.0, 0.0, 0.0, 1.0);
+        rlgl.matrix_mode(MatrixMode::Modelview);
+        rlgl.load_identity();
+        rlgl.translatef(400.0, 300.0, 0.0);
+        rlgl.rotatef(Degrees(90.0), 0.0, 0.0, 1.0);
+
+        rlgl.begin(DrawMode::Lines);
+        rlgl.vertex2f(0.0, 0.0);
+        rlgl.vertex2f(1.0, 0.0);
+        rlgl.end();
+        rlgl.draw_render_batch(FlushReason::Explicit);
+
+        let mvp = calls.borrow().iter().find_map(|call| match call {
+            GlCall::UniformMatrix4(mat) => Some(*mat),
+            _ => None,
+        }).expect("mvp uploaded");
+        assert_near(mvp, *rlgl.get_matrix_modelview() * *rlgl.get_matrix_projection());
+        assert_near(mvp, Matrix::rotate_z(Radians(std::f32::consts::FRAC_PI_2)) * Matrix::translate(400.0, 300.0, 0.0) * Matrix::ortho(0.0, 800.0, 600.0, 0.0, 0.0, 1.0));
+
+        // Screen center maps to the clip space center (on the near plane), +x is rotated to screen down
+        assert_near_vec3(Vector3::ZERO.transform(&mvp), Vector3::new(0.0, 0.0, -1.0));
+        assert_near_vec3(Vector3::new(300.0, 0.0, 0.0).transform(&mvp), Vector3::new(0.0, -1.0, -1.0));
+    }
+
+    /// Recorded texture binds and draws, uploads and uniforms are skipped
+    pub(crate) fn draw_calls(calls: &CallLog) -> Vec<GlCall> {
+        calls.borrow().iter().filter(|call| matches!(call, GlCall::BindTexture(_) | GlCall::DrawArrays { .. } | GlCall::DrawElements { .. })).cloned().collect()
+    }
+
+    /// Index buffer offset in bytes of a vertex, as passed to `draw_elements`
+    fn index_offset(vertex: usize) -> usize {
+        vertex/4*6*size_of::<VertexIndex>()
+    }
+
+    fn quad(rlgl: &mut RLGL, x: f32, y: f32) {
+        rlgl.begin(DrawMode::Quads);
+        rlgl.vertex2f(x, y);
+        rlgl.vertex2f(x, y + 1.0);
+        rlgl.vertex2f(x + 1.0, y + 1.0);
+        rlgl.vertex2f(x + 1.0, y);
+        rlgl.end();
+    }
+
+    #[test]
+    fn test_draw_call_alignment() {
+        let alignment = |mode, vertex_count| DrawCall { mode, vertex_count, vertex_alignment: 0, texture_id: 1, shader_id: 0 }.alignment();
+        for (mode, vertex_count, expected) in [
+            (DrawMode::Lines, 2, 2),
+            (DrawMode::Lines, 4, 0),
+            (DrawMode::Lines, 6, 2),
+            (DrawMode::Triangles, 3, 1),
+            (DrawMode::Triangles, 6, 2),
+            (DrawMode::Triangles, 9, 3),
+            (DrawMode::Triangles, 12, 4),
+            (DrawMode::Quads, 8, 0),
+        ] {
+            assert_eq!(alignment(mode, vertex_count), expected, "{mode:?} x {vertex_count}");
+            if mode != DrawMode::Triangles {
+                assert_eq!((vertex_count + expected)%4, 0, "{mode:?} x {vertex_count}");
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_batch_vertex_contents() {
+        let (mut rlgl, calls) = recording_rlgl();
+        rlgl.begin(DrawMode::Triangles);
+        rlgl.color4ub(1, 2, 3, 4);
+        rlgl.tex_coord2f(0.5, 0.25);
+        rlgl.normal3f(0.0, 0.0, 2.0);
+        rlgl.vertex3f(1.0, 2.0, 3.0);
+        rlgl.color3f(1.0, 0.0, 0.0);
+        rlgl.vertex2f(4.0, 5.0);
+        rlgl.color4f(0.0, 1.0, 0.0, 1.0);
+        rlgl.tex_coord2f(1.0, 1.0);
+        rlgl.vertex2f(6.0, 7.0);
+        rlgl.end();
+
+        let buffer = rlgl.batch.buffer();
+        assert_eq!(buffer.vertices[..9], [1.0, 2.0, 3.0, 4.0, 5.0, -1.0, 6.0, 7.0, -1.0]);
+        assert_eq!(buffer.texcoords[..6], [0.5, 0.25, 0.5, 0.25, 1.0, 1.0]);
+        assert_eq!(buffer.normals[..9], [0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(buffer.colors[..12], [1, 2, 3, 4, 255, 0, 0, 255, 0, 255, 0, 255]);
+        assert_eq!(rlgl.vertex_counter, 3);
+        // Depth is increased by each primitive
+        assert!(rlgl.batch.current_depth > -1.0);
+
+        // Only the used part of the buffers is uploaded: positions, texcoords, normals and colors
+        rlgl.draw_render_batch(FlushReason::Explicit);
+        let uploads: Vec<_> = calls.borrow().iter().filter_map(|call| match call {
+            GlCall::BufferSubData(upload) => Some(upload.clone()),
+            _ => None,
+        }).collect();
+        assert_eq!(uploads, [
+            Upload::F32(vec![1.0, 2.0, 3.0, 4.0, 5.0, -1.0, 6.0, 7.0, -1.0]),
+            Upload::F32(vec![0.5, 0.25, 0.5, 0.25, 1.0, 1.0]),
+            Upload::F32(vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0]),
+            Upload::U8(vec![1, 2, 3, 4, 255, 0, 0, 255, 0, 255, 0, 255]),
+        ]);
+        assert_eq!(rlgl.vertex_counter, 0);
+        assert_eq!(rlgl.batch.current_depth, -1.0);
+
+        // Empty batches upload and draw nothing
+        calls.borrow_mut().clear();
+        rlgl.draw_render_batch(FlushReason::Explicit);
+        assert!(calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_batch_texture_split() {
+        let (mut rlgl, calls) = recording_rlgl();
+        let default_texture_id = rlgl.default_texture_id;
+        quad(&mut rlgl, 0.0, 0.0);
+        quad(&mut rlgl, 1.0, 0.0);
+        rlgl.set_texture(7);
+        quad(&mut rlgl, 2.0, 0.0);
+        // Same texture keeps the draw call
+        rlgl.set_texture(7);
+        quad(&mut rlgl, 3.0, 0.0);
+        rlgl.set_texture(0);
+        rlgl.set_texture(default_texture_id);
+        quad(&mut rlgl, 4.0, 0.0);
+        assert_eq!(rlgl.batch.draw_counter, 3);
+        rlgl.draw_render_batch(FlushReason::Explicit);
+
+        assert_eq!(draw_calls(&calls), [
+            GlCall::BindTexture(default_texture_id),
+            GlCall::DrawElements { count: 12, offset: 0 },
+            GlCall::BindTexture(7),
+            GlCall::DrawElements { count: 12, offset: index_offset(8) },
+            GlCall::BindTexture(default_texture_id),
+            GlCall::DrawElements { count: 6, offset: index_offset(16) },
+            GlCall::BindTexture(0),
+        ]);
+    }
+
+    #[test]
+    fn test_batch_shader_split() {
+        let (mut rlgl, calls) = recording_rlgl();
+        let default_shader_id = rlgl.default_shader_id;
+        let programs = |calls: &CallLog| -> Vec<u32> {
+            calls.borrow().iter().filter_map(|call| match call {
+                GlCall::UseProgram(id) => Some(*id),
+                _ => None,
+            }).collect()
+        };
+
+        // Alternating shaders need a draw call per sprite
+        for shader in [7, 8, 7, 8] {
+            rlgl.set_shader(shader);
+            quad(&mut rlgl, 0.0, 0.0);
+        }
+        assert_eq!(rlgl.batch.draw_counter, 4);
+        rlgl.draw_render_batch(FlushReason::Explicit);
+        assert_eq!(programs(&calls), [7, 8, 7, 8, 0]);
+
+        // Consecutive sprites with the same shader share a draw call, the default shader is the id 0
+        calls.borrow_mut().clear();
+        for shader in [7, 7, 8, 8, 0, 0] {
+            rlgl.set_shader(shader);
+            quad(&mut rlgl, 0.0, 0.0);
+        }
+        assert_eq!(rlgl.batch.draw_counter, 3);
+        rlgl.draw_render_batch(FlushReason::Explicit);
+        assert_eq!(programs(&calls), [7, 8, default_shader_id, 0]);
+        let counts: Vec<_> = draw_calls(&calls).into_iter().filter_map(|call| match call {
+            GlCall::DrawElements { count, .. } => Some(count),
+            _ => None,
+        }).collect();
+        assert_eq!(counts, [12, 12, 12]);
+
+        // Texture changes break draw calls within a shader, setting the same shader again doesn't
+        rlgl.set_shader(7);
+        quad(&mut rlgl, 0.0, 0.0);
+        rlgl.set_shader(7);
+        rlgl.set_texture(3);
+        quad(&mut rlgl, 0.0, 0.0);
+        rlgl.set_texture(3);
+        quad(&mut rlgl, 0.0, 0.0);
+        assert_eq!(rlgl.batch.draw_counter, 2);
+    }
+
+    #[test]
+    fn test_draw_call_break_reason() {
+        let draw = DrawCall { mode: DrawMode::Quads, vertex_count: 4, vertex_alignment: 0, texture_id: 1, shader_id: 0 };
+        assert_eq!(draw.break_reason(1, 0), None);
+        assert_eq!(draw.break_reason(2, 0), Some(FlushReason::TextureChange { old_id: 1, new_id: 2 }));
+        assert_eq!(draw.break_reason(1, 5), Some(FlushReason::ShaderChange { old_id: 0, new_id: 5 }));
+        // Shader change takes precedence
+        assert_eq!(draw.break_reason(2, 5), Some(FlushReason::ShaderChange { old_id: 0, new_id: 5 }));
+    }
+
+    #[test]
+    fn test_batch_diagnostics() {
+        // NOTE: The only test toggling the global flag, others may record while it's enabled
+        let (mut rlgl, _calls) = recording_rlgl();
+        set_batch_diagnostics(false);
+        quad(&mut rlgl, 0.0, 0.0);
+        rlgl.draw_render_batch(FlushReason::Explicit);
+        assert!(rlgl.diagnostics.records.is_empty(), "disabled");
+
+        set_batch_diagnostics(true);
+        rlgl.diagnostics.tag = Some("ui");
+        quad(&mut rlgl, 0.0, 0.0);
+        rlgl.draw_render_batch(FlushReason::Explicit);
+        rlgl.diagnostics.tag = None;
+
+        // Alternating textures runs out of draw calls, the flush blames the last texture switch
+        let texture = |i: usize| [7, 8][i % 2];
+        for i in 0..RL_DEFAULT_BATCH_DRAWCALLS {
+            rlgl.set_texture(texture(i));
+            quad(&mut rlgl, 0.0, 0.0);
+        }
+        let last = RL_DEFAULT_BATCH_DRAWCALLS - 1;
+        assert_eq!(rlgl.diagnostics.records, [
+            FlushRecord { reason: FlushReason::Explicit, vertex_count: 4, tag: Some("ui") },
+            FlushRecord { reason: FlushReason::TextureChange { old_id: texture(last - 1), new_id: texture(last) }, vertex_count: 4*last, tag: None },
+        ]);
+
+        // Records are capped per frame, and cleared for the next one
+        for _ in 0..RL_MAX_FLUSH_RECORDS {
+            rlgl.draw_render_batch(FlushReason::Explicit);
+        }
+        assert_eq!(rlgl.diagnostics.records.len(), RL_MAX_FLUSH_RECORDS);
+        rlgl.diagnostics.clear();
+        assert!(rlgl.diagnostics.records.is_empty());
+        set_batch_diagnostics(false);
+    }
+
+    #[test]
+    fn test_batch_mode_split_alignment() {
+        let (mut rlgl, calls) = recording_rlgl();
+        let default_texture_id = rlgl.default_texture_id;
+        rlgl.begin(DrawMode::Lines);
+        rlgl.vertex2f(0.0, 0.0);
+        rlgl.vertex2f(1.0, 0.0);
+        rlgl.end();
+        rlgl.begin(DrawMode::Triangles);
+        for _ in 0..2 {
+            rlgl.vertex2f(0.0, 0.0);
+            rlgl.vertex2f(1.0, 0.0);
+            rlgl.vertex2f(0.0, 1.0);
+        }
+        rlgl.end();
+        // Quads start aligned, so the index buffer stays valid
+        quad(&mut rlgl, 0.0, 0.0);
+        assert_eq!(rlgl.vertex_counter, 2 + 2 + 6 + 2 + 4);
+        // NOTE: Mode changes reset the texture, like upstream, so it is set after rlBegin()
+        rlgl.begin(DrawMode::Lines);
+        rlgl.set_texture(3);
+        rlgl.vertex2f(0.0, 0.0);
+        rlgl.vertex2f(1.0, 0.0);
+        rlgl.end();
+        rlgl.draw_render_batch(FlushReason::Explicit);
+
+        assert_eq!(draw_calls(&calls), [
+            GlCall::BindTexture(default_texture_id),
+            GlCall::DrawArrays { mode: DrawMode::Lines, first: 0, count: 2 },
+            GlCall::BindTexture(default_texture_id),
+            GlCall::DrawArrays { mode: DrawMode::Triangles, first: 4, count: 6 },
+            GlCall::BindTexture(default_texture_id),
+            GlCall::DrawElements { count: 6, offset: index_offset(12) },
+            GlCall::BindTexture(3),
+            GlCall::DrawArrays { mode: DrawMode::Lines, first: 16, count: 2 },
+            GlCall::BindTexture(0),
+        ]);
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn test_batch_buffer_overflow() {
+        let (mut rlgl, calls) = recording_rlgl();
+        rlgl.set_texture(5);
+        for i in 0..RL_DEFAULT_BATCH_BUFFER_ELEMENTS + 2 {
+            quad(&mut rlgl, i as f32, 0.0);
+        }
+        // Texture and mode are kept for the quads after the overflow
+        assert_eq!(rlgl.vertex_counter, 8);
+        assert_eq!(rlgl.batch.draw().texture_id, 5);
+        assert_eq!(rlgl.batch.draw().mode, DrawMode::Quads);
+        rlgl.draw_render_batch(FlushReason::Explicit);
+
+        assert_eq!(draw_calls(&calls), [
+            GlCall::BindTexture(5),
+            GlCall::DrawElements { count: RL_DEFAULT_BATCH_BUFFER_ELEMENTS*6, offset: 0 },
+            GlCall::BindTexture(0),
+            GlCall::BindTexture(5),
+            GlCall::DrawElements { count: 12, offset: 0 },
+            GlCall::BindTexture(0),
+        ]);
+        let first_upload = calls.borrow().iter().find_map(|call| match call {
+            GlCall::BufferSubData(Upload::F32(data)) => Some(data.len()),
+            _ => None,
+        });
+        assert_eq!(first_upload, Some(RL_DEFAULT_BATCH_BUFFER_ELEMENTS*4*3));
+    }
+
+    #[test]
+    fn test_batch_lines_overflow_keeps_pairs() {
+        let (mut rlgl, calls) = recording_rlgl();
+        rlgl.begin(DrawMode::Lines);
+        let line_count = RL_DEFAULT_BATCH_BUFFER_ELEMENTS*2 + 1;
+        for _ in 0..line_count {
+            rlgl.vertex2f(0.0, 0.0);
+            rlgl.vertex2f(1.0, 1.0);
+        }
+        rlgl.end();
+        rlgl.draw_render_batch(FlushReason::Explicit);
+
+        let counts: Vec<_> = draw_calls(&calls).into_iter().filter_map(|call| match call {
+            GlCall::DrawArrays { mode: DrawMode::Lines, count, .. } => Some(count),
+            _ => None,
+        }).collect();
+        assert_eq!(counts.iter().sum::<usize>(), line_count*2);
+        assert!(counts.len() > 1 && counts.iter().all(|count| count%2 == 0), "{counts:?}");
+    }
+
+    #[test]
+    fn test_batch_draw_calls_limit() {
+        let (mut rlgl, calls) = recording_rlgl();
+        for id in 0..RL_DEFAULT_BATCH_DRAWCALLS {
+            rlgl.set_texture(10 + u32::try_from(id).unwrap());
+            quad(&mut rlgl, 0.0, 0.0);
+        }
+        // Draw calls ran out before the last texture change, the batch was flushed
+        let flushed = draw_calls(&calls).iter().filter(|call| matches!(call, GlCall::DrawElements { count: 6, .. })).count();
+        assert_eq!(flushed, RL_DEFAULT_BATCH_DRAWCALLS - 1);
+        assert_eq!(rlgl.batch.draw_counter, 1);
+        assert_eq!(rlgl.vertex_counter, 4);
+
+        calls.borrow_mut().clear();
+        rlgl.draw_render_batch(FlushReason::Explicit);
+        let last_texture_id = 10 + u32::try_from(RL_DEFAULT_BATCH_DRAWCALLS - 1).unwrap();
+        assert_eq!(draw_calls(&calls)[..2], [GlCall::BindTexture(last_texture_id), GlCall::DrawElements { count: 6, offset: 0 }]);
+    }
+
+    #[test]
+    fn test_read_screen_pixels() {
+        let (mut rlgl, calls) = recording_rlgl();
+        let pixels = rlgl.read_screen_pixels(3, 2);
+        assert_eq!(*calls.borrow(), [GlCall::ReadPixels { width: 3, height: 2 }]);
+        // Top row first, opaque
+        assert_eq!(pixels, [
+            0, 1, 0, 255, 1, 1, 0, 255, 2, 1, 0, 255,
+            0, 0, 0, 255, 1, 0, 0, 255, 2, 0, 0, 255,
+        ]);
+        assert!(RLGL::default().read_screen_pixels(4, 4).iter().skip(3).step_by(4).all(|&alpha| alpha == 255));
+    }
+}
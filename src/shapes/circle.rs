@@ -1,6 +1,286 @@
-use crate::prelude::*;
+use std::f32::consts::PI;
+use crate::{prelude::*, rlgl::DrawMode};
+use super::{arc_point, draw_shapes_quads, ShapesQuad, SMOOTH_CIRCLE_ERROR_RATE};
 
+/// Circle, center and radius
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Circle {
+    /// Circle center position
     pub center: Position2,
+    /// Circle radius
     pub radius: Pixels,
 }
+
+impl Circle {
+    #[inline]
+    #[must_use]
+    pub const fn new(center: Position2, radius: Pixels) -> Self {
+        Self { center, radius }
+    }
+
+    /// Check if point is inside circle
+    #[inline]
+    #[must_use]
+    pub fn contains_point(&self, point: Position2) -> bool {
+        (point - self.center).magnitude_sqr() <= self.radius*self.radius
+    }
+
+    /// Check collision between two circles
+    #[inline]
+    #[must_use]
+    pub fn overlaps(&self, other: &Circle) -> bool {
+        let radius_sum = self.radius + other.radius;
+        (other.center - self.center).magnitude_sqr() <= radius_sum*radius_sum
+    }
+
+    /// Check collision between circle and rectangle
+    #[must_use]
+    pub fn overlaps_rec(&self, rec: &Rectangle) -> bool {
+        // Closest rectangle point to the circle center
+        let closest = Vector2::new(
+            self.center.x.clamp(rec.x_min().min(rec.x_max()), rec.x_min().max(rec.x_max())),
+            self.center.y.clamp(rec.y_min().min(rec.y_max()), rec.y_min().max(rec.y_max())),
+        );
+        self.contains_point(closest)
+    }
+}
+
+/// Get circle sector angles in increasing order and the number of segments to draw them with
+/// NOTE: Segments are computed from the radius if less than one per 90 degrees are requested
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn sector_segments(radius: f32, start_angle: Degrees, end_angle: Degrees, segments: usize) -> (Degrees, Degrees, usize) {
+    // Function expects (endAngle > startAngle)
+    let (start_angle, end_angle) = if end_angle < start_angle { (end_angle, start_angle) } else { (start_angle, end_angle) };
+
+    let min_segments = ((end_angle - start_angle)/Degrees(90.0)).ceil() as usize;
+    let segments = if segments < min_segments {
+        // Calculate the maximum angle between segments based on the error rate (usually 0.5f)
+        let th = (2.0*(1.0 - SMOOTH_CIRCLE_ERROR_RATE/radius).powi(2) - 1.0).acos();
+        match ((end_angle - start_angle)/Degrees::FULL_TURN*(2.0*PI/th).ceil()) as usize {
+            0 => min_segments,
+            segments => segments,
+        }
+    } else {
+        segments
+    };
+
+    (start_angle, end_angle, segments)
+}
+
+/// Draw a color-filled circle (Vector version)
+#[inline]
+pub fn draw_circle_v(core: &mut Core, center: Position2, radius: Pixels, color: Color) {
+    draw_circle_sector(core, center, radius, Degrees::ZERO, Degrees::FULL_TURN, 36, color);
+}
+
+/// Draw a piece of a circle
+/// NOTE: Angles are swapped if `end_angle < start_angle`, segments are computed from the radius if too few
+#[allow(clippy::cast_precision_loss)]
+pub fn draw_circle_sector(core: &mut Core, center: Position2, radius: Pixels, start_angle: Degrees, end_angle: Degrees, segments: usize, color: Color) {
+    let radius = if radius <= 0.0 { 0.1 } else { radius }; // Avoid div by zero
+    let (start_angle, end_angle, segments) = sector_segments(radius, start_angle, end_angle, segments);
+
+    let step_length = (end_angle - start_angle)/segments as f32;
+    let mut angle = start_angle;
+
+    let mut quads: Vec<ShapesQuad> = Vec::with_capacity(segments.div_ceil(2));
+
+    // NOTE: Every QUAD actually represents two segments
+    for _ in 0..segments/2 {
+        quads.push(([
+            center,
+            arc_point(center, radius, angle + step_length*2.0),
+            arc_point(center, radius, angle + step_length),
+            arc_point(center, radius, angle),
+        ], [color; 4]));
+        angle += step_length*2.0;
+    }
+
+    // NOTE: In case number of segments is odd, we add one last piece to the cake
+    if segments % 2 == 1 {
+        quads.push(([
+            center,
+            arc_point(center, radius, angle + step_length),
+            arc_point(center, radius, angle),
+            center,
+        ], [color; 4]));
+    }
+
+    draw_shapes_quads(core, quads);
+}
+
+/// Draw a piece of a circle outlines
+/// NOTE: Angles are swapped if `end_angle < start_angle`, segments are computed from the radius if too few
+#[allow(clippy::cast_precision_loss)]
+pub fn draw_circle_sector_lines(core: &mut Core, center: Position2, radius: Pixels, start_angle: Degrees, end_angle: Degrees, segments: usize, color: Color) {
+    let radius = if radius <= 0.0 { 0.1 } else { radius }; // Avoid div by zero issue
+    let (start_angle, end_angle, segments) = sector_segments(radius, start_angle, end_angle, segments);
+
+    let step_length = (end_angle - start_angle)/segments as f32;
+    let mut angle = start_angle;
+
+    let rlgl = &mut core.rlgl;
+    rlgl.begin(DrawMode::Lines);
+        rlgl.color4ub(color.r, color.g, color.b, color.a);
+
+        // Start cap line
+        let start = arc_point(center, radius, angle);
+        rlgl.vertex2f(center.x, center.y);
+        rlgl.vertex2f(start.x, start.y);
+
+        for _ in 0..segments {
+            let (from, to) = (arc_point(center, radius, angle), arc_point(center, radius, angle + step_length));
+            rlgl.vertex2f(from.x, from.y);
+            rlgl.vertex2f(to.x, to.y);
+            angle += step_length;
+        }
+
+        // End cap line
+        let end = arc_point(center, radius, angle);
+        rlgl.vertex2f(center.x, center.y);
+        rlgl.vertex2f(end.x, end.y);
+    rlgl.end();
+}
+
+/// Draw a gradient-filled circle
+#[allow(clippy::cast_precision_loss)]
+pub fn draw_circle_gradient(core: &mut Core, center: Position2, radius: Pixels, inner: Color, outer: Color) {
+    let rlgl = &mut core.rlgl;
+    rlgl.begin(DrawMode::Triangles);
+        for i in (0..360).step_by(10) {
+            let angle = Degrees(i as f32);
+            let (from, to) = (arc_point(center, radius, angle), arc_point(center, radius, angle + Degrees(10.0)));
+            rlgl.color4ub(inner.r, inner.g, inner.b, inner.a);
+            rlgl.vertex2f(center.x, center.y);
+            rlgl.color4ub(outer.r, outer.g, outer.b, outer.a);
+            rlgl.vertex2f(to.x, to.y);
+            rlgl.vertex2f(from.x, from.y);
+        }
+    rlgl.end();
+}
+
+/// Draw circle outline (Vector version)
+pub fn draw_circle_lines_v(core: &mut Core, center: Position2, radius: Pixels, color: Color) {
+    draw_ellipse_lines(core, center, radius, radius, color);
+}
+
+/// Get the point at `angle` on an ellipse
+#[inline]
+fn ellipse_point(center: Vector2, radius_h: f32, radius_v: f32, angle: Degrees) -> Vector2 {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    Vector2::new(center.x + cos*radius_h, center.y + sin*radius_v)
+}
+
+/// Draw ellipse
+#[allow(clippy::cast_precision_loss)]
+pub fn draw_ellipse(core: &mut Core, center: Position2, radius_h: Pixels, radius_v: Pixels, color: Color) {
+    let rlgl = &mut core.rlgl;
+    rlgl.begin(DrawMode::Triangles);
+        rlgl.color4ub(color.r, color.g, color.b, color.a);
+        for i in (0..360).step_by(10) {
+            let angle = Degrees(i as f32);
+            let (from, to) = (ellipse_point(center, radius_h, radius_v, angle), ellipse_point(center, radius_h, radius_v, angle + Degrees(10.0)));
+            rlgl.vertex2f(center.x, center.y);
+            rlgl.vertex2f(to.x, to.y);
+            rlgl.vertex2f(from.x, from.y);
+        }
+    rlgl.end();
+}
+
+/// Draw ellipse outline
+#[allow(clippy::cast_precision_loss)]
+pub fn draw_ellipse_lines(core: &mut Core, center: Position2, radius_h: Pixels, radius_v: Pixels, color: Color) {
+    let rlgl = &mut core.rlgl;
+    rlgl.begin(DrawMode::Lines);
+        rlgl.color4ub(color.r, color.g, color.b, color.a);
+        for i in (0..360).step_by(10) {
+            let angle = Degrees(i as f32);
+            let (from, to) = (ellipse_point(center, radius_h, radius_v, angle), ellipse_point(center, radius_h, radius_v, angle + Degrees(10.0)));
+            rlgl.vertex2f(from.x, from.y);
+            rlgl.vertex2f(to.x, to.y);
+        }
+    rlgl.end();
+}
+
+/// Get ring radii in increasing order, the outer one being at least 0.1
+fn ring_radii(inner_radius: Pixels, outer_radius: Pixels) -> (Pixels, Pixels) {
+    // Function expects (outerRadius > innerRadius)
+    let (inner_radius, outer_radius) = if outer_radius < inner_radius { (outer_radius, inner_radius) } else { (inner_radius, outer_radius) };
+    (inner_radius, if outer_radius <= 0.0 { 0.1 } else { outer_radius })
+}
+
+/// Draw ring
+/// NOTE: Radii and angles are swapped if given in decreasing order, a ring without inner radius is a circle sector
+#[allow(clippy::cast_precision_loss, clippy::too_many_arguments, clippy::float_cmp)]
+pub fn draw_ring(core: &mut Core, center: Position2, inner_radius: Pixels, outer_radius: Pixels, start_angle: Degrees, end_angle: Degrees, segments: usize, color: Color) {
+    if start_angle == end_angle {
+        return;
+    }
+
+    let (inner_radius, outer_radius) = ring_radii(inner_radius, outer_radius);
+    let (start_angle, end_angle, segments) = sector_segments(outer_radius, start_angle, end_angle, segments);
+
+    // Not a ring
+    if inner_radius <= 0.0 {
+        draw_circle_sector(core, center, outer_radius, start_angle, end_angle, segments, color);
+        return;
+    }
+
+    let step_length = (end_angle - start_angle)/segments as f32;
+    let quads = (0..segments).map(|i| {
+        let angle = start_angle + step_length*i as f32;
+        ([
+            arc_point(center, outer_radius, angle),
+            arc_point(center, inner_radius, angle),
+            arc_point(center, inner_radius, angle + step_length),
+            arc_point(center, outer_radius, angle + step_length),
+        ], [color; 4])
+    });
+
+    draw_shapes_quads(core, quads);
+}
+
+/// Draw ring outline
+/// NOTE: Radii and angles are swapped if given in decreasing order, a ring without inner radius is a circle sector
+#[allow(clippy::cast_precision_loss, clippy::too_many_arguments, clippy::float_cmp)]
+pub fn draw_ring_lines(core: &mut Core, center: Position2, inner_radius: Pixels, outer_radius: Pixels, start_angle: Degrees, end_angle: Degrees, segments: usize, color: Color) {
+    if start_angle == end_angle {
+        return;
+    }
+
+    let (inner_radius, outer_radius) = ring_radii(inner_radius, outer_radius);
+    let (start_angle, end_angle, segments) = sector_segments(outer_radius, start_angle, end_angle, segments);
+
+    // Not a ring
+    if inner_radius <= 0.0 {
+        draw_circle_sector_lines(core, center, outer_radius, start_angle, end_angle, segments, color);
+        return;
+    }
+
+    let step_length = (end_angle - start_angle)/segments as f32;
+    let mut angle = start_angle;
+
+    let rlgl = &mut core.rlgl;
+    rlgl.begin(DrawMode::Lines);
+        rlgl.color4ub(color.r, color.g, color.b, color.a);
+
+        // Start cap line
+        let (outer, inner) = (arc_point(center, outer_radius, angle), arc_point(center, inner_radius, angle));
+        rlgl.vertex2f(outer.x, outer.y);
+        rlgl.vertex2f(inner.x, inner.y);
+
+        for _ in 0..segments {
+            for radius in [outer_radius, inner_radius] {
+                let (from, to) = (arc_point(center, radius, angle), arc_point(center, radius, angle + step_length));
+                rlgl.vertex2f(from.x, from.y);
+                rlgl.vertex2f(to.x, to.y);
+            }
+            angle += step_length;
+        }
+
+        // End cap line
+        let (outer, inner) = (arc_point(center, outer_radius, angle), arc_point(center, inner_radius, angle));
+        rlgl.vertex2f(outer.x, outer.y);
+        rlgl.vertex2f(inner.x, inner.y);
+    rlgl.end();
+}
@@ -0,0 +1,286 @@
+//! Basic shapes collision detection functions
+
+use crate::prelude::*;
+
+/// Check collision between two rectangles
+#[inline]
+#[must_use]
+pub fn check_collision_recs(rec1: &Rectangle, rec2: &Rectangle) -> bool {
+    rec1.intersects(rec2)
+}
+
+/// Check collision between two circles
+#[inline]
+#[must_use]
+pub fn check_collision_circles(center1: Position2, radius1: Pixels, center2: Position2, radius2: Pixels) -> bool {
+    Circle::new(center1, radius1).overlaps(&Circle::new(center2, radius2))
+}
+
+/// Check collision between circle and rectangle
+#[inline]
+#[must_use]
+pub fn check_collision_circle_rec(center: Position2, radius: Pixels, rec: &Rectangle) -> bool {
+    Circle::new(center, radius).overlaps_rec(rec)
+}
+
+/// Check if circle collides with a line created between two points `p1` and `p2`
+#[must_use]
+pub fn check_collision_circle_line(center: Position2, radius: Pixels, p1: Position2, p2: Position2) -> bool {
+    let delta = p1 - p2;
+    if delta.x.abs() + delta.y.abs() <= f32::EPSILON {
+        return check_collision_circles(p1, 0.0, center, radius);
+    }
+
+    // Closest line point to the circle center
+    let length_sq = delta.magnitude_sqr();
+    let dot_product = ((center - p1).dot(p2 - p1)/length_sq).clamp(0.0, 1.0);
+    let closest = p1 - delta*dot_product;
+
+    (closest - center).magnitude_sqr() <= radius*radius
+}
+
+/// Check if point is inside rectangle
+#[inline]
+#[must_use]
+pub fn check_collision_point_rec(point: Position2, rec: &Rectangle) -> bool {
+    rec.contains_point(point)
+}
+
+/// Check if point is inside circle
+#[inline]
+#[must_use]
+pub fn check_collision_point_circle(point: Position2, center: Position2, radius: Pixels) -> bool {
+    Circle::new(center, radius).contains_point(point)
+}
+
+/// Check if point is inside a triangle defined by three points (p1, p2, p3)
+/// NOTE: Points on the triangle edges and degenerate triangles are not collisions
+#[must_use]
+pub fn check_collision_point_triangle(point: Position2, p1: Position2, p2: Position2, p3: Position2) -> bool {
+    let det = (p2.y - p3.y)*(p1.x - p3.x) + (p3.x - p2.x)*(p1.y - p3.y);
+    let alpha = ((p2.y - p3.y)*(point.x - p3.x) + (p3.x - p2.x)*(point.y - p3.y))/det;
+    let beta = ((p3.y - p1.y)*(point.x - p3.x) + (p1.x - p3.x)*(point.y - p3.y))/det;
+    let gamma = 1.0 - alpha - beta;
+
+    alpha > 0.0 && beta > 0.0 && gamma > 0.0
+}
+
+/// Check if point is within a polygon described by array of vertices
+/// NOTE: Based on <http://jeffreythompson.org/collision-detection/poly-point.php>,
+/// polygons with less than 3 points contain nothing
+#[must_use]
+pub fn check_collision_point_poly(point: Position2, points: &[Position2]) -> bool {
+    if points.len() < 3 {
+        return false;
+    }
+
+    let mut inside = false;
+    let mut previous = points[points.len() - 1];
+    for &current in points {
+        if (current.y > point.y) != (previous.y > point.y) &&
+            point.x < (previous.x - current.x)*(point.y - current.y)/(previous.y - current.y) + current.x
+        {
+            inside = !inside;
+        }
+        previous = current;
+    }
+    inside
+}
+
+/// Check the collision between two lines defined by two points each, returns collision point
+/// NOTE: Parallel lines never collide
+#[must_use]
+pub fn check_collision_lines(start1: Position2, end1: Position2, start2: Position2, end2: Position2) -> Option<Position2> {
+    let div = (end2.y - start2.y)*(end1.x - start1.x) - (end2.x - start2.x)*(end1.y - start1.y);
+    if div.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let cross1 = start1.x*end1.y - start1.y*end1.x;
+    let cross2 = start2.x*end2.y - start2.y*end2.x;
+    let xi = ((start2.x - end2.x)*cross1 - (start1.x - end1.x)*cross2)/div;
+    let yi = ((start2.y - end2.y)*cross1 - (start1.y - end1.y)*cross2)/div;
+
+    // Intersection must be within both segments, axis-aligned segments are not checked on their constant axis
+    let outside = |value: f32, a: f32, b: f32| (a - b).abs() > f32::EPSILON && (value < a.min(b) || value > a.max(b));
+    if outside(xi, start1.x, end1.x) || outside(xi, start2.x, end2.x) ||
+        outside(yi, start1.y, end1.y) || outside(yi, start2.y, end2.y)
+    {
+        return None;
+    }
+
+    Some(Vector2::new(xi, yi))
+}
+
+/// Check if point belongs to line created between two points `p1` and `p2` with defined margin in pixels `threshold`
+#[must_use]
+pub fn check_collision_point_line(point: Position2, p1: Position2, p2: Position2, threshold: Pixels) -> bool {
+    let (dxc, dyc) = (point.x - p1.x, point.y - p1.y);
+    let (dxl, dyl) = (p2.x - p1.x, p2.y - p1.y);
+    let cross = dxc*dyl - dyc*dxl;
+
+    if cross.abs() < threshold*dxl.abs().max(dyl.abs()) {
+        // Check point is between the line ends along its major axis
+        let (value, a, b) = if dxl.abs() >= dyl.abs() { (point.x, p1.x, p2.x) } else { (point.y, p1.y, p2.y) };
+        a.min(b) <= value && value <= a.max(b)
+    } else {
+        false
+    }
+}
+
+/// Get collision rectangle for two rectangles collision
+/// NOTE: Rectangles not overlapping return an empty rectangle
+#[inline]
+#[must_use]
+pub fn get_collision_rec(rec1: &Rectangle, rec2: &Rectangle) -> Rectangle {
+    rec1.intersection(rec2).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    /// NOTE: Zero-sized rectangles still collide, like raylib, but have no collision rectangle
+    fn rectangle() -> impl Strategy<Value = Rectangle> {
+        (-50.0f32..50.0, -50.0f32..50.0, 0.5f32..40.0, 0.5f32..40.0).prop_map(|(x, y, width, height)| Rectangle::new(x, y, width, height))
+    }
+
+    fn position() -> impl Strategy<Value = Position2> {
+        (-60.0f32..60.0, -60.0f32..60.0).prop_map(|(x, y)| Vector2::new(x, y))
+    }
+
+    #[test]
+    fn test_collision_recs() {
+        let rec = Rectangle::new(0.0, 0.0, 10.0, 10.0);
+        assert!(check_collision_recs(&rec, &Rectangle::new(5.0, 5.0, 10.0, 10.0)));
+        assert!(check_collision_recs(&rec, &Rectangle::new(2.0, 2.0, 1.0, 1.0)));
+        assert!(!check_collision_recs(&rec, &Rectangle::new(10.0, 0.0, 10.0, 10.0)), "touching edges");
+        assert!(!check_collision_recs(&rec, &Rectangle::new(20.0, 20.0, 5.0, 5.0)));
+
+        assert_eq!(get_collision_rec(&rec, &Rectangle::new(5.0, -5.0, 10.0, 10.0)), Rectangle::new(5.0, 0.0, 5.0, 5.0));
+        assert_eq!(get_collision_rec(&rec, &Rectangle::new(2.0, 2.0, 1.0, 1.0)), Rectangle::new(2.0, 2.0, 1.0, 1.0));
+        assert_eq!(get_collision_rec(&rec, &Rectangle::new(20.0, 20.0, 5.0, 5.0)), Rectangle::default());
+
+        // Right and bottom edges are outside
+        assert!(check_collision_point_rec(Vector2::new(0.0, 0.0), &rec));
+        assert!(check_collision_point_rec(Vector2::new(9.9, 9.9), &rec));
+        assert!(!check_collision_point_rec(Vector2::new(10.0, 5.0), &rec));
+        assert!(!check_collision_point_rec(Vector2::new(5.0, 10.0), &rec));
+    }
+
+    #[test]
+    fn test_collision_circles() {
+        let origin = Vector2::ZERO;
+        // Touching circles collide, distance 5
+        assert!(check_collision_circles(origin, 2.0, Vector2::new(3.0, 4.0), 3.0));
+        assert!(!check_collision_circles(origin, 2.0, Vector2::new(3.0, 4.0), 2.9));
+        assert!(check_collision_point_circle(Vector2::new(3.0, 4.0), origin, 5.0));
+        assert!(!check_collision_point_circle(Vector2::new(3.0, 4.1), origin, 5.0));
+
+        // Closest point is on a side or on a corner
+        let rec = Rectangle::new(0.0, 0.0, 10.0, 10.0);
+        assert!(check_collision_circle_rec(Vector2::new(15.0, 5.0), 5.5, &rec));
+        assert!(!check_collision_circle_rec(Vector2::new(15.0, 5.0), 4.5, &rec));
+        assert!(check_collision_circle_rec(Vector2::new(13.0, 14.0), 5.1, &rec));
+        assert!(!check_collision_circle_rec(Vector2::new(13.0, 14.0), 4.9, &rec));
+        assert!(check_collision_circle_rec(Vector2::new(5.0, 5.0), 1.0, &rec), "circle inside");
+
+        // Closest point is inside the segment or on one of its ends
+        let (start, end) = (Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0));
+        assert!(check_collision_circle_line(Vector2::new(5.0, 3.0), 3.1, start, end));
+        assert!(!check_collision_circle_line(Vector2::new(5.0, 3.0), 2.9, start, end));
+        assert!(check_collision_circle_line(Vector2::new(13.0, 0.0), 3.1, start, end));
+        assert!(!check_collision_circle_line(Vector2::new(13.0, 0.0), 2.9, start, end));
+        assert!(!check_collision_circle_line(Vector2::new(-3.0, 1.0), 3.1, start, end));
+        // Degenerate line is a point
+        assert!(check_collision_circle_line(Vector2::new(3.0, 4.0), 5.0, origin, origin));
+        assert!(!check_collision_circle_line(Vector2::new(3.0, 4.0), 4.9, origin, origin));
+    }
+
+    #[test]
+    fn test_collision_point_triangle_poly() {
+        let [a, b, c] = [Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0), Vector2::new(0.0, 10.0)];
+        // Winding doesn't matter, edges and degenerate triangles are not collisions
+        assert!(check_collision_point_triangle(Vector2::new(2.0, 2.0), a, b, c));
+        assert!(check_collision_point_triangle(Vector2::new(2.0, 2.0), a, c, b));
+        assert!(!check_collision_point_triangle(Vector2::new(6.0, 6.0), a, b, c));
+        assert!(!check_collision_point_triangle(Vector2::new(5.0, 0.0), a, b, c));
+        assert!(!check_collision_point_triangle(Vector2::new(5.0, 0.0), a, b, Vector2::new(20.0, 0.0)));
+
+        // Concave L shape, the notch is outside
+        let shape = [(0.0, 0.0), (10.0, 0.0), (10.0, 4.0), (4.0, 4.0), (4.0, 10.0), (0.0, 10.0)].map(|(x, y)| Vector2::new(x, y));
+        assert!(check_collision_point_poly(Vector2::new(2.0, 8.0), &shape));
+        assert!(check_collision_point_poly(Vector2::new(8.0, 2.0), &shape));
+        assert!(!check_collision_point_poly(Vector2::new(8.0, 8.0), &shape));
+        assert!(!check_collision_point_poly(Vector2::new(-1.0, 2.0), &shape));
+        let reversed: Vec<_> = shape.iter().rev().copied().collect();
+        assert!(check_collision_point_poly(Vector2::new(2.0, 8.0), &reversed));
+        assert!(!check_collision_point_poly(Vector2::new(8.0, 8.0), &reversed));
+        // Less than 3 points contain nothing
+        assert!(!check_collision_point_poly(Vector2::ZERO, &shape[..2]));
+        assert!(!check_collision_point_poly(Vector2::ZERO, &[]));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_collision_lines() {
+        assert_eq!(check_collision_lines(Vector2::new(0.0, 0.0), Vector2::new(10.0, 10.0), Vector2::new(0.0, 10.0), Vector2::new(10.0, 0.0)), Some(Vector2::new(5.0, 5.0)));
+        // Axis-aligned segments
+        assert_eq!(check_collision_lines(Vector2::new(5.0, 0.0), Vector2::new(5.0, 10.0), Vector2::new(0.0, 3.0), Vector2::new(10.0, 3.0)), Some(Vector2::new(5.0, 3.0)));
+        // Lines cross outside of the segments
+        assert_eq!(check_collision_lines(Vector2::new(0.0, 0.0), Vector2::new(4.0, 4.0), Vector2::new(0.0, 10.0), Vector2::new(10.0, 0.0)), None);
+        assert_eq!(check_collision_lines(Vector2::new(5.0, 0.0), Vector2::new(5.0, 2.0), Vector2::new(0.0, 3.0), Vector2::new(10.0, 3.0)), None);
+        // Parallel and collinear lines
+        assert_eq!(check_collision_lines(Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0), Vector2::new(0.0, 1.0), Vector2::new(10.0, 1.0)), None);
+        assert_eq!(check_collision_lines(Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0), Vector2::new(5.0, 0.0), Vector2::new(15.0, 0.0)), None);
+
+        let (start, end) = (Vector2::new(0.0, 0.0), Vector2::new(10.0, 5.0));
+        assert!(check_collision_point_line(Vector2::new(4.0, 2.0), start, end, 1.0));
+        assert!(check_collision_point_line(Vector2::new(4.0, 2.5), start, end, 1.0));
+        assert!(!check_collision_point_line(Vector2::new(4.0, 4.0), start, end, 1.0));
+        assert!(!check_collision_point_line(Vector2::new(12.0, 6.0), start, end, 1.0), "past the end");
+        // Vertical lines use their y range
+        assert!(check_collision_point_line(Vector2::new(0.0, 7.0), Vector2::new(0.0, 10.0), start, 1.0));
+        assert!(!check_collision_point_line(Vector2::new(0.0, 11.0), Vector2::new(0.0, 10.0), start, 1.0));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_collision_recs(a in rectangle(), b in rectangle()) {
+            prop_assert_eq!(check_collision_recs(&a, &b), check_collision_recs(&b, &a));
+            prop_assert_eq!(get_collision_rec(&a, &b), get_collision_rec(&b, &a));
+
+            // Collision rectangle is inside both, and only empty without collision
+            let overlap = get_collision_rec(&a, &b);
+            prop_assert_eq!(check_collision_recs(&a, &b), overlap != Rectangle::default());
+            if check_collision_recs(&a, &b) {
+                // NOTE: Recomputing the far edges from the size rounds
+                prop_assert!(a.grow(1.0e-4).contains_rect(&overlap) && b.grow(1.0e-4).contains_rect(&overlap), "{:?}", overlap);
+                prop_assert!(overlap.width > 0.0 && overlap.height > 0.0);
+            }
+        }
+
+        #[test]
+        fn prop_collision_circles(a in position(), radius_a in 0.0f32..30.0, b in position(), radius_b in 0.0f32..30.0, point in position()) {
+            prop_assert_eq!(check_collision_circles(a, radius_a, b, radius_b), check_collision_circles(b, radius_b, a, radius_a));
+            prop_assert_eq!(check_collision_point_circle(point, a, radius_a), check_collision_circles(point, 0.0, a, radius_a));
+
+            // Points inside a rectangle collide with any circle centered on them
+            let rec = Rectangle::new(b.x, b.y, radius_b, radius_a);
+            if check_collision_point_rec(point, &rec) {
+                prop_assert!(check_collision_circle_rec(point, 0.0, &rec));
+            }
+        }
+
+        #[test]
+        fn prop_collision_point_poly(rec in rectangle(), point in position()) {
+            // Axis-aligned polygon matches the rectangle away from its edges
+            let corners = [(rec.x, rec.y), (rec.x_max(), rec.y), (rec.x_max(), rec.y_max()), (rec.x, rec.y_max())].map(|(x, y)| Vector2::new(x, y));
+            let on_edge = [point.x - rec.x, point.x - rec.x_max(), point.y - rec.y, point.y - rec.y_max()].iter().any(|d| d.abs() < 1.0e-3);
+            if !on_edge {
+                prop_assert_eq!(check_collision_point_poly(point, &corners), check_collision_point_rec(point, &rec));
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{prelude::*, config::SPLINE_SEGMENT_DIVISIONS, math::easing, rlgl::DrawMode};
+use super::{draw_shapes_quad, draw_shapes_quads};
+
+/// Get the half-thickness offset perpendicular to a segment, zero for degenerate segments
+fn line_radius(delta: Vector2, thick: Pixels) -> Vector2 {
+    let length = delta.magnitude();
+    if length > 0.0 && thick > 0.0 {
+        let scale = thick/(2.0*length);
+        Vector2::new(-scale*delta.y, scale*delta.x)
+    } else {
+        Vector2::ZERO
+    }
+}
+
+/// Draw a pixel (1x1 rectangle) at position
+pub fn draw_pixel(core: &mut Core, position: Position2, color: Color) {
+    let Vector2 { x, y } = position;
+    draw_shapes_quad(core, [
+        Vector2::new(x, y),
+        Vector2::new(x, y + 1.0),
+        Vector2::new(x + 1.0, y + 1.0),
+        Vector2::new(x + 1.0, y),
+    ], color);
+}
+
+/// Draw a line (using gl lines)
+pub fn draw_line(core: &mut Core, start: Position2, end: Position2, color: Color) {
+    let rlgl = &mut core.rlgl;
+    rlgl.begin(DrawMode::Lines);
+        rlgl.color4ub(color.r, color.g, color.b, color.a);
+        rlgl.vertex2f(start.x, start.y);
+        rlgl.vertex2f(end.x, end.y);
+    rlgl.end();
+}
+
+/// Draw a line with thickness (using a quad)
+/// NOTE: Nothing is drawn for zero-length lines or non-positive thickness
+pub fn draw_line_ex(core: &mut Core, start: Position2, end: Position2, thick: Pixels, color: Color) {
+    let radius = line_radius(end - start, thick);
+    if radius != Vector2::ZERO {
+        draw_shapes_quad(core, [start - radius, start + radius, end + radius, end - radius], color);
+    }
+}
+
+/// Draw lines sequence (using gl lines)
+/// NOTE: Nothing is drawn for less than 2 points
+pub fn draw_line_strip(core: &mut Core, points: &[Position2], color: Color) {
+    if points.len() < 2 {
+        return;
+    }
+
+    let rlgl = &mut core.rlgl;
+    rlgl.begin(DrawMode::Lines);
+        rlgl.color4ub(color.r, color.g, color.b, color.a);
+        for segment in points.windows(2) {
+            rlgl.vertex2f(segment[0].x, segment[0].y);
+            rlgl.vertex2f(segment[1].x, segment[1].y);
+        }
+    rlgl.end();
+}
+
+/// Draw line segment cubic-bezier in-out interpolation
+/// NOTE: Curve is split in `SPLINE_SEGMENT_DIVISIONS` quads sharing their edges, zero-length segments are skipped
+#[allow(clippy::cast_precision_loss)]
+pub fn draw_line_bezier(core: &mut Core, start: Position2, end: Position2, thick: Pixels, color: Color) {
+    const DIVISIONS: f32 = SPLINE_SEGMENT_DIVISIONS as f32;
+
+    let mut quads = Vec::with_capacity(SPLINE_SEGMENT_DIVISIONS);
+    let mut previous = start;
+    let mut previous_edge = None;
+    for i in 1..=SPLINE_SEGMENT_DIVISIONS {
+        // Cubic easing in-out
+        // NOTE: Easing is calculated only for y position value
+        let current = Vector2::new(
+            previous.x + (end.x - start.x)/DIVISIONS,
+            easing::cubic_in_out(i as f32, start.y, end.y - start.y, DIVISIONS),
+        );
+
+        let radius = line_radius(current - previous, thick);
+        if radius != Vector2::ZERO {
+            // Start from previous segment edge, so thick segments join without gaps
+            let [top, bottom] = previous_edge.unwrap_or([previous - radius, previous + radius]);
+            quads.push(([top, bottom, current + radius, current - radius], [color; 4]));
+            previous_edge = Some([current - radius, current + radius]);
+        }
+
+        previous = current;
+    }
+
+    draw_shapes_quads(core, quads);
+}
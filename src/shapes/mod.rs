@@ -1,7 +1,135 @@
+use crate::{prelude::*, rlgl::DrawMode};
+
 pub mod rectangle;
 pub mod circle;
 pub mod triangle;
+pub mod sweep;
+pub mod line;
+pub mod spline;
+pub mod collision;
 
 pub enum Shape {
 
 }
+
+/// Maximum distance in pixels between a smooth circle and its segments, used to compute automatic segment counts
+pub(crate) const SMOOTH_CIRCLE_ERROR_RATE: f32 = 0.5;
+
+/// Get the point at `angle` on a circle
+#[inline]
+pub(crate) fn arc_point(center: Vector2, radius: f32, angle: Degrees) -> Vector2 {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    Vector2::new(center.x + cos*radius, center.y + sin*radius)
+}
+
+/// Texture and source rectangle used on shapes drawing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ShapesTexture {
+    /// OpenGL texture id, 0 for the rlgl default texture
+    id: u32,
+    /// Texture width
+    width: f32,
+    /// Texture height
+    height: f32,
+    /// Texture source rectangle
+    rec: Rectangle,
+}
+
+impl Default for ShapesTexture {
+    /// rlgl default texture is a 1x1 pixel `UNCOMPRESSED_R8G8B8A8`
+    fn default() -> Self {
+        Self {
+            id: 0,
+            width: 1.0,
+            height: 1.0,
+            rec: Rectangle::new(0.0, 0.0, 1.0, 1.0),
+        }
+    }
+}
+
+impl ShapesTexture {
+    /// Get source rectangle texture coordinates: left, top, right, bottom
+    fn uv(&self) -> [f32; 4] {
+        [
+            self.rec.x/self.width,
+            self.rec.y/self.height,
+            (self.rec.x + self.rec.width)/self.width,
+            (self.rec.y + self.rec.height)/self.height,
+        ]
+    }
+}
+
+/// Set texture and rectangle to be used on shapes drawing
+/// NOTE: It can be useful when using basic shapes and one single font,
+/// defining a font char white rectangle would allow drawing everything in a single draw call
+/// NOTE: Texture must stay loaded while used by shapes, an empty texture or rectangle resets to the default texture
+#[allow(clippy::cast_precision_loss)]
+pub fn set_shapes_texture(core: &mut Core, texture: impl AsRef<Texture>, source: Rectangle) {
+    let texture = texture.as_ref();
+    // Reset texture to default pixel if required
+    // WARNING: Shapes texture should be probably better validated,
+    // it can break the rendering of all shapes if misused
+    core.shapes_texture = if texture.id == 0 || source.width == 0.0 || source.height == 0.0 {
+        ShapesTexture::default()
+    } else {
+        ShapesTexture {
+            id: texture.id,
+            width: texture.width as f32,
+            height: texture.height as f32,
+            rec: source,
+        }
+    };
+}
+
+/// Get texture source rectangle that is used for shapes drawing
+#[inline]
+#[must_use]
+pub fn get_shapes_texture_rectangle(core: &Core) -> Rectangle {
+    core.shapes_texture.rec
+}
+
+/// Shapes quad corners, top-left, bottom-left, bottom-right, top-right (any convex counter-clockwise order works),
+/// and the color of each corner
+pub(crate) type ShapesQuad = ([Vector2; 4], [Color; 4]);
+
+/// Draw a single color quad with the shapes texture
+#[inline]
+pub(crate) fn draw_shapes_quad(core: &mut Core, corners: [Vector2; 4], color: Color) {
+    draw_shapes_quads(core, [(corners, [color; 4])]);
+}
+
+/// Draw quads with the shapes texture, all of them in a single draw mode
+/// NOTE: Drawn as two triangles each (untextured) if quads draw mode is not supported
+pub(crate) fn draw_shapes_quads(core: &mut Core, quads: impl IntoIterator<Item = ShapesQuad>) {
+    let rlgl = &mut core.rlgl;
+    if cfg!(feature = "support_quads_draw_mode") {
+        let shapes = core.shapes_texture;
+        let [left, top, right, bottom] = shapes.uv();
+        let texcoords = [[left, top], [left, bottom], [right, bottom], [right, top]];
+        rlgl.set_texture(if shapes.id == 0 { rlgl.get_texture_id_default() } else { shapes.id });
+
+        rlgl.begin(DrawMode::Quads);
+            rlgl.normal3f(0.0, 0.0, 1.0);
+            for (corners, colors) in quads {
+                for ((corner, color), [u, v]) in corners.into_iter().zip(colors).zip(texcoords) {
+                    rlgl.color4ub(color.r, color.g, color.b, color.a);
+                    rlgl.tex_coord2f(u, v);
+                    rlgl.vertex2f(corner.x, corner.y);
+                }
+            }
+        rlgl.end();
+
+        rlgl.set_texture(0);
+    } else {
+        rlgl.begin(DrawMode::Triangles);
+            for (corners, colors) in quads {
+                // Split quad along the bottom-left to top-right diagonal
+                for index in [0, 1, 3, 3, 1, 2] {
+                    let (corner, color) = (corners[index], colors[index]);
+                    rlgl.color4ub(color.r, color.g, color.b, color.a);
+                    rlgl.vertex2f(corner.x, corner.y);
+                }
+            }
+        rlgl.end();
+    }
+}
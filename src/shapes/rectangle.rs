@@ -1,6 +1,9 @@
-use crate::prelude::*;
+use std::f32::consts::PI;
+use crate::{prelude::*, rlgl::DrawMode};
+use super::{arc_point, draw_shapes_quad, draw_shapes_quads, ShapesQuad, SMOOTH_CIRCLE_ERROR_RATE};
 
 /// Rectangle, 4 components
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Rectangle {
     /// Rectangle top-left corner position x
     pub x: f32,
@@ -62,4 +65,430 @@ impl Rectangle {
             y: self.center_y(),
         }
     }
+
+    /// Get top-left corner position
+    #[inline]
+    pub const fn position(&self) -> Position2 {
+        Vector2::new(self.x, self.y)
+    }
+
+    /// Get width and height
+    #[inline]
+    pub const fn size(&self) -> Vector2 {
+        Vector2::new(self.width, self.height)
+    }
+
+    /// Get rectangle moved to a new top-left corner position
+    #[inline]
+    #[must_use]
+    pub const fn with_position(self, position: Position2) -> Self {
+        Self { x: position.x, y: position.y, ..self }
+    }
+
+    /// Get rectangle resized, keeping its top-left corner
+    #[inline]
+    #[must_use]
+    pub const fn with_size(self, size: Vector2) -> Self {
+        Self { width: size.x, height: size.y, ..self }
+    }
+
+    /// Get rectangle moved by an offset
+    #[inline]
+    #[must_use]
+    pub fn move_by(self, offset: Offset2) -> Self {
+        Self { x: self.x + offset.x, y: self.y + offset.y, ..self }
+    }
+
+    /// Get the same area with non-negative width and height, moving the top-left corner if required
+    /// NOTE: Containment and overlap methods expect normalized rectangles
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        let (x, width) = if self.width < 0.0 { (self.x + self.width, -self.width) } else { (self.x, self.width) };
+        let (y, height) = if self.height < 0.0 { (self.y + self.height, -self.height) } else { (self.y, self.height) };
+        Self { x, y, width, height }
+    }
+
+    /// Get rectangle expanded by `amount` on every side
+    /// NOTE: Shrinking more than the half size collapses the rectangle to its center
+    #[must_use]
+    pub fn grow(self, amount: f32) -> Self {
+        let width = (self.width + 2.0*amount).max(0.0);
+        let height = (self.height + 2.0*amount).max(0.0);
+        Self::new(self.center_x() - width*0.5, self.center_y() - height*0.5, width, height)
+    }
+
+    /// Get rectangle contracted by `amount` on every side
+    #[inline]
+    #[must_use]
+    pub fn shrink(self, amount: f32) -> Self {
+        self.grow(-amount)
+    }
+
+    /// Check if point is inside rectangle
+    /// NOTE: Right and bottom edges are excluded, so tiling rectangles never share points
+    #[must_use]
+    pub fn contains_point(&self, point: Position2) -> bool {
+        point.x >= self.x && point.x < self.x_max() &&
+        point.y >= self.y && point.y < self.y_max()
+    }
+
+    /// Check if other rectangle is fully inside rectangle
+    #[must_use]
+    pub fn contains_rect(&self, other: &Rectangle) -> bool {
+        other.x >= self.x && other.x_max() <= self.x_max() &&
+        other.y >= self.y && other.y_max() <= self.y_max()
+    }
+
+    /// Check if rectangles overlap, touching edges are not an overlap
+    #[must_use]
+    pub fn intersects(&self, other: &Rectangle) -> bool {
+        self.x < other.x_max() && self.x_max() > other.x &&
+        self.y < other.y_max() && self.y_max() > other.y
+    }
+
+    /// Get overlapping area of both rectangles, `None` if they don't overlap
+    #[must_use]
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        let left = self.x.max(other.x);
+        let right = self.x_max().min(other.x_max());
+        let top = self.y.max(other.y);
+        let bottom = self.y_max().min(other.y_max());
+
+        (left < right && top < bottom).then(|| Rectangle::new(left, top, right - left, bottom - top))
+    }
+
+    /// Get smallest rectangle containing both rectangles
+    #[must_use]
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        let left = self.x.min(other.x);
+        let top = self.y.min(other.y);
+        Rectangle::new(left, top, self.x_max().max(other.x_max()) - left, self.y_max().max(other.y_max()) - top)
+    }
+}
+
+impl From<(Position2, Vector2)> for Rectangle {
+    /// Rectangle from top-left corner position and size
+    #[inline]
+    fn from((position, size): (Position2, Vector2)) -> Self {
+        Self::new(position.x, position.y, size.x, size.y)
+    }
+}
+
+impl From<Rectangle> for [f32; 4] {
+    /// Rectangle as `[x, y, width, height]`
+    #[inline]
+    fn from(rec: Rectangle) -> Self {
+        [rec.x, rec.y, rec.width, rec.height]
+    }
+}
+
+/// Corner angles of rounded rectangles: upper-left, upper-right, lower-right, lower-left
+const ROUNDED_CORNER_ANGLES: [Degrees; 4] = [Degrees(180.0), Degrees(270.0), Degrees(0.0), Degrees(90.0)];
+
+impl Rectangle {
+    /// Get corners: top-left, bottom-left, bottom-right, top-right
+    pub(crate) fn corners(&self) -> [Vector2; 4] {
+        [
+            Vector2::new(self.x, self.y),
+            Vector2::new(self.x, self.y_max()),
+            Vector2::new(self.x_max(), self.y_max()),
+            Vector2::new(self.x_max(), self.y),
+        ]
+    }
+
+    /// Get corner radius and number of segments per corner of the rounded rectangle
+    /// NOTE: Segments are computed from the radius if less than 4 are requested
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn rounded_corners(&self, roundness: Percent, segments: usize) -> (f32, usize) {
+        // Calculate corner radius
+        let radius = self.width.min(self.height)*roundness.min(1.0)/2.0;
+
+        let segments = if segments < 4 {
+            // Calculate the maximum angle between segments based on the error rate (usually 0.5f)
+            let th = (2.0*(1.0 - SMOOTH_CIRCLE_ERROR_RATE/radius).powi(2) - 1.0).acos();
+            match ((2.0*PI/th).ceil()/4.0) as usize {
+                0 => 4,
+                segments => segments,
+            }
+        } else {
+            segments
+        };
+
+        (radius, segments)
+    }
+}
+
+/// Draw a color-filled rectangle
+#[inline]
+pub fn draw_rectangle_rec(core: &mut Core, rec: &Rectangle, color: Color) {
+    draw_shapes_quad(core, rec.corners(), color);
+}
+
+/// Draw a color-filled rectangle with pro parameters
+/// NOTE: Rectangle is rotated around `origin`, relative to its top-left corner
+pub fn draw_rectangle_pro(core: &mut Core, rec: &Rectangle, origin: Offset2, rotation: Degrees, color: Color) {
+    let corners = if rotation == Degrees::ZERO {
+        let offset = Rectangle { x: rec.x - origin.x, y: rec.y - origin.y, ..*rec };
+        offset.corners()
+    } else {
+        let (sin, cos) = rotation.to_radians().sin_cos();
+        let local = Rectangle { x: -origin.x, y: -origin.y, ..*rec }.corners();
+        local.map(|Vector2 { x, y }| Vector2::new(rec.x + x*cos - y*sin, rec.y + x*sin + y*cos))
+    };
+    draw_shapes_quad(core, corners, color);
+}
+
+/// Draw a vertical-gradient-filled rectangle
+#[inline]
+pub fn draw_rectangle_gradient_v(core: &mut Core, rec: &Rectangle, top: Color, bottom: Color) {
+    draw_rectangle_gradient_ex(core, rec, top, bottom, top, bottom);
+}
+
+/// Draw a horizontal-gradient-filled rectangle
+#[inline]
+pub fn draw_rectangle_gradient_h(core: &mut Core, rec: &Rectangle, left: Color, right: Color) {
+    draw_rectangle_gradient_ex(core, rec, left, left, right, right);
+}
+
+/// Draw a gradient-filled rectangle with custom vertex colors
+pub fn draw_rectangle_gradient_ex(core: &mut Core, rec: &Rectangle, top_left: Color, bottom_left: Color, top_right: Color, bottom_right: Color) {
+    draw_shapes_quads(core, [(rec.corners(), [top_left, bottom_left, bottom_right, top_right])]);
+}
+
+/// Draw rectangle outline with extended parameters
+/// NOTE: Outline is drawn inside the rectangle, thickness is clamped to fit it
+pub fn draw_rectangle_lines_ex(core: &mut Core, rec: &Rectangle, line_thick: Pixels, color: Color) {
+    let line_thick = if line_thick > rec.width || line_thick > rec.height {
+        rec.width.min(rec.height)/2.0
+    } else {
+        line_thick
+    };
+
+    // When rec = { x, y, 8.0f, 6.0f } and lineThick = 2, the following
+    // four rectangles are drawn ([T]op, [B]ottom, [L]eft, [R]ight):
+    //
+    //   TTTTTTTT
+    //   TTTTTTTT
+    //   LL    RR
+    //   LL    RR
+    //   BBBBBBBB
+    //   BBBBBBBB
+    //
+    let sides = [
+        Rectangle::new(rec.x, rec.y, rec.width, line_thick),
+        Rectangle::new(rec.x, rec.y - line_thick + rec.height, rec.width, line_thick),
+        Rectangle::new(rec.x, rec.y + line_thick, line_thick, rec.height - line_thick*2.0),
+        Rectangle::new(rec.x - line_thick + rec.width, rec.y + line_thick, line_thick, rec.height - line_thick*2.0),
+    ];
+    draw_shapes_quads(core, sides.map(|side| (side.corners(), [color; 4])));
+}
+
+/// Draw rectangle with rounded edges
+/// NOTE: Corner segments are computed from the radius if `segments < 4`
+pub fn draw_rectangle_rounded(core: &mut Core, rec: &Rectangle, roundness: Percent, segments: usize, color: Color) {
+    // Not a rounded rectangle
+    if roundness <= 0.0 || rec.width < 1.0 || rec.height < 1.0 {
+        draw_rectangle_rec(core, rec, color);
+        return;
+    }
+
+    let (radius, segments) = rec.rounded_corners(roundness, segments);
+    if radius <= 0.0 {
+        return;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let step_length = Degrees(90.0)/segments as f32;
+
+    /*
+    Quick sketch to make sense of all of this,
+    there are 9 parts to draw, also mark the 12 points we'll use
+
+          P0____________________P1
+          /|                    |\
+         /1|          2         |3\
+     P7 /__|____________________|__\ P2
+       |   |P8                P9|   |
+       | 8 |          9         | 4 |
+       | __|____________________|__ |
+     P6 \  |P11              P10|  / P3
+         \7|          6         |5/
+          \|____________________|/
+          P5                    P4
+    */
+    // Coordinates of the 12 points that define the rounded rect
+    let point = [
+        Vector2::new(rec.x + radius, rec.y), Vector2::new(rec.x_max() - radius, rec.y), Vector2::new(rec.x_max(), rec.y + radius), // P0, P1, P2
+        Vector2::new(rec.x_max(), rec.y_max() - radius), Vector2::new(rec.x_max() - radius, rec.y_max()), // P3, P4
+        Vector2::new(rec.x + radius, rec.y_max()), Vector2::new(rec.x, rec.y_max() - radius), Vector2::new(rec.x, rec.y + radius), // P5, P6, P7
+        Vector2::new(rec.x + radius, rec.y + radius), Vector2::new(rec.x_max() - radius, rec.y + radius), // P8, P9
+        Vector2::new(rec.x_max() - radius, rec.y_max() - radius), Vector2::new(rec.x + radius, rec.y_max() - radius), // P10, P11
+    ];
+    let centers = [point[8], point[9], point[10], point[11]];
+
+    let mut quads: Vec<ShapesQuad> = Vec::with_capacity(4*segments.div_ceil(2) + 5);
+
+    // Draw all of the 4 corners: [1] Upper Left Corner, [3] Upper Right Corner, [5] Lower Right Corner, [7] Lower Left Corner
+    for (center, mut angle) in centers.into_iter().zip(ROUNDED_CORNER_ANGLES) {
+        // NOTE: Every QUAD actually represents two segments
+        for _ in 0..segments/2 {
+            quads.push(([
+                center,
+                arc_point(center, radius, angle + step_length*2.0),
+                arc_point(center, radius, angle + step_length),
+                arc_point(center, radius, angle),
+            ], [color; 4]));
+            angle += step_length*2.0;
+        }
+
+        // NOTE: In case number of segments is odd, we add one last piece to the cake
+        if segments % 2 == 1 {
+            quads.push(([
+                center,
+                arc_point(center, radius, angle + step_length),
+                arc_point(center, radius, angle),
+                center,
+            ], [color; 4]));
+        }
+    }
+
+    // [2] Upper Rectangle, [4] Right Rectangle, [6] Bottom Rectangle, [8] Left Rectangle, [9] Middle Rectangle
+    for [a, b, c, d] in [[0, 8, 9, 1], [9, 10, 3, 2], [11, 5, 4, 10], [7, 6, 11, 8], [8, 11, 10, 9]] {
+        quads.push(([point[a], point[b], point[c], point[d]], [color; 4]));
+    }
+
+    draw_shapes_quads(core, quads);
+}
+
+/// Draw rectangle lines with rounded edges
+#[inline]
+pub fn draw_rectangle_rounded_lines(core: &mut Core, rec: &Rectangle, roundness: Percent, segments: usize, color: Color) {
+    draw_rectangle_rounded_lines_ex(core, rec, roundness, segments, 1.0, color);
+}
+
+/// Draw rectangle with rounded edges outline
+/// NOTE: Outline is drawn outside the rectangle, thin outlines (`line_thick <= 1`) are drawn with gl lines
+pub fn draw_rectangle_rounded_lines_ex(core: &mut Core, rec: &Rectangle, roundness: Percent, segments: usize, line_thick: Pixels, color: Color) {
+    let line_thick = line_thick.max(0.0);
+
+    // Not a rounded rectangle
+    if roundness <= 0.0 {
+        let outer = Rectangle::new(rec.x - line_thick, rec.y - line_thick, rec.width + 2.0*line_thick, rec.height + 2.0*line_thick);
+        draw_rectangle_lines_ex(core, &outer, line_thick, color);
+        return;
+    }
+
+    let (radius, segments) = rec.rounded_corners(roundness, segments);
+    if radius <= 0.0 {
+        return;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let step_length = Degrees(90.0)/segments as f32;
+    let outer_radius = radius + line_thick;
+    let inner_radius = radius;
+
+    /*
+    Quick sketch to make sense of all of this,
+    marks the 16 + 4(corner centers P16-19) points we'll use
+
+           P0 ================== P1
+          // P8                P9 \\
+         //                        \\
+     P7 // P15                  P10 \\ P2
+       ||   *P16             P17*    ||
+       ||                            ||
+       || P14                   P11  ||
+     P6 \\  *P19             P18*   // P3
+         \\                        //
+          \\ P13              P12 //
+           P5 ================== P4
+    */
+    let point = [
+        Vector2::new(rec.x + inner_radius, rec.y - line_thick), Vector2::new(rec.x_max() - inner_radius, rec.y - line_thick), Vector2::new(rec.x_max() + line_thick, rec.y + inner_radius), // P0, P1, P2
+        Vector2::new(rec.x_max() + line_thick, rec.y_max() - inner_radius), Vector2::new(rec.x_max() - inner_radius, rec.y_max() + line_thick), // P3, P4
+        Vector2::new(rec.x + inner_radius, rec.y_max() + line_thick), Vector2::new(rec.x - line_thick, rec.y_max() - inner_radius), Vector2::new(rec.x - line_thick, rec.y + inner_radius), // P5, P6, P7
+        Vector2::new(rec.x + inner_radius, rec.y), Vector2::new(rec.x_max() - inner_radius, rec.y), // P8, P9
+        Vector2::new(rec.x_max(), rec.y + inner_radius), Vector2::new(rec.x_max(), rec.y_max() - inner_radius), // P10, P11
+        Vector2::new(rec.x_max() - inner_radius, rec.y_max()), Vector2::new(rec.x + inner_radius, rec.y_max()), // P12, P13
+        Vector2::new(rec.x, rec.y_max() - inner_radius), Vector2::new(rec.x, rec.y + inner_radius), // P14, P15
+    ];
+    let centers = [
+        Vector2::new(rec.x + inner_radius, rec.y + inner_radius), Vector2::new(rec.x_max() - inner_radius, rec.y + inner_radius), // P16, P17
+        Vector2::new(rec.x_max() - inner_radius, rec.y_max() - inner_radius), Vector2::new(rec.x + inner_radius, rec.y_max() - inner_radius), // P18, P19
+    ];
+
+    if line_thick > 1.0 {
+        let mut quads: Vec<ShapesQuad> = Vec::with_capacity(4*segments + 4);
+
+        // Draw all of the 4 corners first: Upper Left Corner, Upper Right Corner, Lower Right Corner, Lower Left Corner
+        for (center, mut angle) in centers.into_iter().zip(ROUNDED_CORNER_ANGLES) {
+            for _ in 0..segments {
+                quads.push(([
+                    arc_point(center, inner_radius, angle),
+                    arc_point(center, outer_radius, angle),
+                    arc_point(center, outer_radius, angle + step_length),
+                    arc_point(center, inner_radius, angle + step_length),
+                ], [color; 4]));
+                angle += step_length;
+            }
+        }
+
+        // Upper, right, lower and left rectangles
+        for [a, b, c, d] in [[0, 8, 9, 1], [2, 10, 11, 3], [13, 5, 4, 12], [15, 7, 6, 14]] {
+            quads.push(([point[a], point[b], point[c], point[d]], [color; 4]));
+        }
+
+        draw_shapes_quads(core, quads);
+    } else {
+        // Use LINES to draw the outline
+        let rlgl = &mut core.rlgl;
+        rlgl.begin(DrawMode::Lines);
+            rlgl.color4ub(color.r, color.g, color.b, color.a);
+
+            // Draw all the 4 corners first: Upper Left Corner, Upper Right Corner, Lower Right Corner, Lower Left Corner
+            for (center, mut angle) in centers.into_iter().zip(ROUNDED_CORNER_ANGLES) {
+                for _ in 0..segments {
+                    let (start, end) = (arc_point(center, outer_radius, angle), arc_point(center, outer_radius, angle + step_length));
+                    rlgl.vertex2f(start.x, start.y);
+                    rlgl.vertex2f(end.x, end.y);
+                    angle += step_length;
+                }
+            }
+
+            // And now the remaining 4 lines
+            for side in point[..8].chunks_exact(2) {
+                rlgl.vertex2f(side[0].x, side[0].y);
+                rlgl.vertex2f(side[1].x, side[1].y);
+            }
+        rlgl.end();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_rectangle_bounds() {
+        let rec = Rectangle::new(1.0, 2.0, 3.0, 4.0);
+        let copy = rec;
+        assert_eq!(copy, rec);
+        assert_eq!((rec.x_max(), rec.y_max(), rec.center()), (4.0, 6.0, Vector2::new(2.5, 4.0)));
+        assert_eq!(Rectangle::default(), Rectangle::new(0.0, 0.0, 0.0, 0.0));
+        assert_eq!(Rectangle::new(4.0, 6.0, -3.0, -4.0).normalized(), rec);
+    }
+
+    #[test]
+    fn test_rectangle_overlap() {
+        let rec = Rectangle::new(0.0, 0.0, 2.0, 2.0);
+        // Touching edges are neither contained nor overlapping
+        assert!(rec.contains_point(Vector2::ZERO));
+        assert!(!rec.contains_point(Vector2::new(2.0, 1.0)));
+        assert!(!rec.intersects(&Rectangle::new(2.0, 0.0, 1.0, 1.0)));
+        assert_eq!(rec.intersection(&Rectangle::new(2.0, 0.0, 1.0, 1.0)), None);
+        assert_eq!(rec.intersection(&Rectangle::new(1.0, 1.0, 2.0, 2.0)), Some(Rectangle::new(1.0, 1.0, 1.0, 1.0)));
+        assert_eq!(rec.union(&Rectangle::new(3.0, -1.0, 1.0, 1.0)), Rectangle::new(0.0, -1.0, 4.0, 3.0));
+    }
 }
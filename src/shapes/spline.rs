@@ -0,0 +1,300 @@
+//! Splines evaluation and drawing: linear, B-Spline (basis), Catmull-Rom and Bezier curves
+
+use crate::{prelude::*, config::SPLINE_SEGMENT_DIVISIONS};
+
+/// Get (evaluate) spline point: Linear
+#[inline]
+pub fn get_spline_point_linear(start: Position2, end: Position2, t: Percent) -> Position2 {
+    start*(1.0 - t) + end*t
+}
+
+/// Get cubic polynomial coefficients of a B-Spline segment, highest degree first
+fn basis_coefficients(p1: f32, p2: f32, p3: f32, p4: f32) -> [f32; 4] {
+    [
+        (-p1 + 3.0*p2 - 3.0*p3 + p4)/6.0,
+        (3.0*p1 - 6.0*p2 + 3.0*p3)/6.0,
+        (-3.0*p1 + 3.0*p3)/6.0,
+        (p1 + 4.0*p2 + p3)/6.0,
+    ]
+}
+
+/// Get (evaluate) spline point: B-Spline
+pub fn get_spline_point_basis(p1: Position2, p2: Position2, p3: Position2, p4: Position2, t: Percent) -> Position2 {
+    let a = basis_coefficients(p1.x, p2.x, p3.x, p4.x);
+    let b = basis_coefficients(p1.y, p2.y, p3.y, p4.y);
+
+    Vector2::new(
+        a[3] + t*(a[2] + t*(a[1] + t*a[0])),
+        b[3] + t*(b[2] + t*(b[1] + t*b[0])),
+    )
+}
+
+/// Get (evaluate) spline point: Catmull-Rom
+pub fn get_spline_point_catmull_rom(p1: Position2, p2: Position2, p3: Position2, p4: Position2, t: Percent) -> Position2 {
+    let q0 = (-t*t*t) + (2.0*t*t) - t;
+    let q1 = (3.0*t*t*t) + (-5.0*t*t) + 2.0;
+    let q2 = (-3.0*t*t*t) + (4.0*t*t) + t;
+    let q3 = t*t*t - t*t;
+
+    (p1*q0 + p2*q1 + p3*q2 + p4*q3)*0.5
+}
+
+/// Get (evaluate) spline point: Quadratic Bezier
+pub fn get_spline_point_bezier_quad(start: Position2, control: Position2, end: Position2, t: Percent) -> Position2 {
+    let a = (1.0 - t).powi(2);
+    let b = 2.0*(1.0 - t)*t;
+    let c = t.powi(2);
+
+    start*a + control*b + end*c
+}
+
+/// Get (evaluate) spline point: Cubic Bezier
+pub fn get_spline_point_bezier_cubic(start: Position2, start_control: Position2, end_control: Position2, end: Position2, t: Percent) -> Position2 {
+    start*(1.0 - t).powi(3)
+        + start_control*(3.0*(1.0 - t).powi(2)*t)
+        + end_control*(3.0*(1.0 - t)*t.powi(2))
+        + end*t.powi(3)
+}
+
+/// Evaluate a spline segment at `SPLINE_SEGMENT_DIVISIONS + 1` evenly spaced `t` values, both ends included
+#[allow(clippy::cast_precision_loss)]
+fn segment_points(point: impl Fn(Percent) -> Position2) -> impl Iterator<Item = Position2> {
+    (0..=SPLINE_SEGMENT_DIVISIONS).map(move |i| point(i as f32/SPLINE_SEGMENT_DIVISIONS as f32))
+}
+
+/// Draw a thick curve through points with a single triangle strip, so joints don't crack
+/// NOTE: Every point is offset along its incoming segment normal, zero-length segments are skipped
+fn draw_thick_curve(core: &mut Core, points: impl IntoIterator<Item = Position2>, thick: Pixels, color: Color) {
+    if thick <= 0.0 {
+        return;
+    }
+
+    let mut points = points.into_iter();
+    let Some(mut previous) = points.next() else { return };
+    let mut strip = Vec::with_capacity(2*SPLINE_SEGMENT_DIVISIONS + 2);
+
+    for current in points {
+        let delta = current - previous;
+        let length = delta.magnitude();
+        if length == 0.0 {
+            continue;
+        }
+
+        let offset = Vector2::new(delta.y, -delta.x)*(0.5*thick/length);
+        if strip.is_empty() {
+            strip.extend([previous + offset, previous - offset]);
+        }
+        strip.extend([current + offset, current - offset]);
+
+        previous = current;
+    }
+
+    draw_triangle_strip(core, &strip, color);
+}
+
+/// Draw spline: Linear, minimum 2 points
+/// NOTE: Segments are drawn as independent quads, joints are not mitered
+pub fn draw_spline_linear(core: &mut Core, points: &[Position2], thick: Pixels, color: Color) {
+    for segment in points.windows(2) {
+        draw_spline_segment_linear(core, segment[0], segment[1], thick, color);
+    }
+}
+
+/// Draw spline: B-Spline, minimum 4 points
+/// NOTE: Ends are capped with circles
+pub fn draw_spline_basis(core: &mut Core, points: &[Position2], thick: Pixels, color: Color) {
+    if points.len() < 4 {
+        return;
+    }
+
+    let start = get_spline_point_basis(points[0], points[1], points[2], points[3], 0.0);
+    let last = &points[points.len() - 4..];
+    let end = get_spline_point_basis(last[0], last[1], last[2], last[3], 1.0);
+
+    let curve = points.windows(4).enumerate().flat_map(|(i, p)| {
+        // NOTE: Segments share their end points, first one is only emitted by the first segment
+        segment_points(move |t| get_spline_point_basis(p[0], p[1], p[2], p[3], t)).skip(usize::from(i > 0))
+    });
+
+    draw_circle_v(core, start, thick/2.0, color); // Draw init line circle-cap
+    draw_thick_curve(core, curve, thick, color);
+    draw_circle_v(core, end, thick/2.0, color); // Draw end line circle-cap
+}
+
+/// Draw spline: Catmull-Rom, minimum 4 points
+/// NOTE: Curve goes through all points but the first and last ones, ends are capped with circles
+pub fn draw_spline_catmull_rom(core: &mut Core, points: &[Position2], thick: Pixels, color: Color) {
+    if points.len() < 4 {
+        return;
+    }
+
+    let curve = points.windows(4).enumerate().flat_map(|(i, p)| {
+        // NOTE: Segments share their end points, first one is only emitted by the first segment
+        segment_points(move |t| get_spline_point_catmull_rom(p[0], p[1], p[2], p[3], t)).skip(usize::from(i > 0))
+    });
+
+    draw_circle_v(core, points[1], thick/2.0, color); // Draw init line circle-cap
+    draw_thick_curve(core, curve, thick, color);
+    draw_circle_v(core, points[points.len() - 2], thick/2.0, color); // Draw end line circle-cap
+}
+
+/// Draw spline: Quadratic Bezier, minimum 3 points (1 control point): [p1, c2, p3, c4...]
+pub fn draw_spline_bezier_quadratic(core: &mut Core, points: &[Position2], thick: Pixels, color: Color) {
+    for p in points.windows(3).step_by(2) {
+        draw_spline_segment_bezier_quadratic(core, p[0], p[1], p[2], thick, color);
+    }
+}
+
+/// Draw spline: Cubic Bezier, minimum 4 points (2 control points): [p1, c2, c3, p4, c5, c6...]
+pub fn draw_spline_bezier_cubic(core: &mut Core, points: &[Position2], thick: Pixels, color: Color) {
+    for p in points.windows(4).step_by(3) {
+        draw_spline_segment_bezier_cubic(core, p[0], p[1], p[2], p[3], thick, color);
+    }
+}
+
+/// Draw spline segment: Linear, 2 points
+/// NOTE: For the linear spline we don't use subdivisions, just a single quad
+#[inline]
+pub fn draw_spline_segment_linear(core: &mut Core, p1: Position2, p2: Position2, thick: Pixels, color: Color) {
+    draw_line_ex(core, p1, p2, thick, color);
+}
+
+/// Draw spline segment: B-Spline, 4 points
+pub fn draw_spline_segment_basis(core: &mut Core, p1: Position2, p2: Position2, p3: Position2, p4: Position2, thick: Pixels, color: Color) {
+    draw_thick_curve(core, segment_points(|t| get_spline_point_basis(p1, p2, p3, p4, t)), thick, color);
+}
+
+/// Draw spline segment: Catmull-Rom, 4 points
+pub fn draw_spline_segment_catmull_rom(core: &mut Core, p1: Position2, p2: Position2, p3: Position2, p4: Position2, thick: Pixels, color: Color) {
+    draw_thick_curve(core, segment_points(|t| get_spline_point_catmull_rom(p1, p2, p3, p4, t)), thick, color);
+}
+
+/// Draw spline segment: Quadratic Bezier, 2 points, 1 control point
+pub fn draw_spline_segment_bezier_quadratic(core: &mut Core, p1: Position2, c2: Position2, p3: Position2, thick: Pixels, color: Color) {
+    draw_thick_curve(core, segment_points(|t| get_spline_point_bezier_quad(p1, c2, p3, t)), thick, color);
+}
+
+/// Draw spline segment: Cubic Bezier, 2 points, 2 control points
+pub fn draw_spline_segment_bezier_cubic(core: &mut Core, p1: Position2, c2: Position2, c3: Position2, p4: Position2, thick: Pixels, color: Color) {
+    draw_thick_curve(core, segment_points(|t| get_spline_point_bezier_cubic(p1, c2, c3, p4, t)), thick, color);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rlgl::{tests::{draw_calls, recording_rlgl, CallLog, GlCall, Upload}, FlushReason};
+
+    #[track_caller]
+    fn assert_near(actual: Position2, expected: Position2) {
+        assert!((actual - expected).magnitude() < 1.0e-5, "{actual:?} != {expected:?}");
+    }
+
+    const P: [Vector2; 4] = [Vector2::new(0.0, 0.0), Vector2::new(1.0, 3.0), Vector2::new(4.0, 3.0), Vector2::new(6.0, -1.0)];
+
+    #[test]
+    fn test_spline_points() {
+        let [p1, p2, p3, p4] = P;
+        assert_near(get_spline_point_linear(p1, p2, 0.0), p1);
+        assert_near(get_spline_point_linear(p1, p2, 0.25), Vector2::new(0.25, 0.75));
+        assert_near(get_spline_point_linear(p1, p2, 1.0), p2);
+
+        // Bezier curves start and end on their end points, controls are only approached
+        assert_near(get_spline_point_bezier_quad(p1, p2, p3, 0.0), p1);
+        assert_near(get_spline_point_bezier_quad(p1, p2, p3, 0.5), (p1 + p2*2.0 + p3)*0.25);
+        assert_near(get_spline_point_bezier_quad(p1, p2, p3, 1.0), p3);
+        assert_near(get_spline_point_bezier_cubic(p1, p2, p3, p4, 0.0), p1);
+        assert_near(get_spline_point_bezier_cubic(p1, p2, p3, p4, 0.5), (p1 + p2*3.0 + p3*3.0 + p4)*0.125);
+        assert_near(get_spline_point_bezier_cubic(p1, p2, p3, p4, 1.0), p4);
+
+        // Catmull-Rom goes through the inner points
+        assert_near(get_spline_point_catmull_rom(p1, p2, p3, p4, 0.0), p2);
+        assert_near(get_spline_point_catmull_rom(p1, p2, p3, p4, 1.0), p3);
+
+        // B-Spline only approaches them
+        assert_near(get_spline_point_basis(p1, p2, p3, p4, 0.0), (p1 + p2*4.0 + p3)/6.0);
+        assert_near(get_spline_point_basis(p1, p2, p3, p4, 1.0), (p2 + p3*4.0 + p4)/6.0);
+
+        // Collinear evenly spaced points give a straight line, for every kind
+        let line = [0.0, 1.0, 2.0, 3.0].map(|x| Vector2::new(x, 2.0*x));
+        for t in [0.0, 0.3, 0.5, 0.9] {
+            for point in [
+                get_spline_point_basis(line[0], line[1], line[2], line[3], t),
+                get_spline_point_catmull_rom(line[0], line[1], line[2], line[3], t),
+                get_spline_point_bezier_quad(line[0], line[1], line[2], t),
+                get_spline_point_bezier_cubic(line[0], line[1], line[2], line[3], t),
+            ] {
+                assert!((point.y - 2.0*point.x).abs() < 1.0e-5, "{point:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_spline_segments_join() {
+        // Consecutive segments of a point list share their end points
+        let points = [P[0], P[1], P[2], P[3], Vector2::new(8.0, 2.0)];
+        let first = |t| get_spline_point_basis(points[0], points[1], points[2], points[3], t);
+        let second = |t| get_spline_point_basis(points[1], points[2], points[3], points[4], t);
+        assert_near(first(1.0), second(0.0));
+        let first = |t| get_spline_point_catmull_rom(points[0], points[1], points[2], points[3], t);
+        let second = |t| get_spline_point_catmull_rom(points[1], points[2], points[3], points[4], t);
+        assert_near(first(1.0), second(0.0));
+
+        // Tangents match too: C1 continuity
+        let h = 1.0e-3;
+        let tangent_in = (first(1.0) - first(1.0 - h))/h;
+        let tangent_out = (second(h) - second(0.0))/h;
+        assert!((tangent_in - tangent_out).magnitude() < 0.05, "{tangent_in:?} != {tangent_out:?}");
+    }
+
+    /// Flush the batch and get the drawn triangle vertices, 2d
+    fn drawn_triangles(core: &mut Core, calls: &CallLog) -> Vec<[Vector2; 3]> {
+        core.rlgl.draw_render_batch(FlushReason::Explicit);
+        let range = draw_calls(calls).iter().find_map(|call| match call {
+            GlCall::DrawArrays { first, count, .. } => Some(3*first..3*(first + count)),
+            _ => None,
+        }).unwrap_or_default();
+        let positions = calls.borrow().iter().find_map(|call| match call {
+            GlCall::BufferSubData(Upload::F32(data)) => Some(data.clone()),
+            _ => None,
+        }).unwrap_or_default();
+        calls.borrow_mut().clear();
+        positions[range].chunks_exact(9).map(|v| [
+            Vector2::new(v[0], v[1]),
+            Vector2::new(v[3], v[4]),
+            Vector2::new(v[6], v[7]),
+        ]).collect()
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_draw_thick_curve() {
+        let mut core = Core::default();
+        let (rlgl, calls) = recording_rlgl();
+        core.rlgl = rlgl;
+
+        // Single strip offset by half the thickness along each segment normal, repeated points are skipped
+        let corner = [Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0), Vector2::new(10.0, 0.0), Vector2::new(10.0, 10.0)];
+        draw_thick_curve(&mut core, corner, 4.0, Color::RED);
+        let triangles = drawn_triangles(&mut core, &calls);
+        assert_eq!(triangles.len(), 4);
+        let strip = [(0.0, -2.0), (0.0, 2.0), (10.0, -2.0), (10.0, 2.0), (12.0, 10.0), (8.0, 10.0)].map(|(x, y)| Vector2::new(x, y));
+        for (i, triangle) in triangles.iter().enumerate() {
+            let [a, b, c] = [strip[i], strip[i + 1], strip[i + 2]];
+            assert_eq!(*triangle, if i % 2 == 0 { [c, a, b] } else { [c, b, a] });
+        }
+
+        // Nothing to draw without thickness or length
+        draw_thick_curve(&mut core, corner, 0.0, Color::RED);
+        draw_thick_curve(&mut core, [Vector2::ZERO; 5], 4.0, Color::RED);
+        draw_thick_curve(&mut core, [], 4.0, Color::RED);
+        assert!(drawn_triangles(&mut core, &calls).is_empty());
+
+        // Segments are subdivided, every cross section of the strip is as wide as the line
+        draw_spline_segment_bezier_cubic(&mut core, P[0], P[1], P[2], P[3], 2.0, Color::RED);
+        let triangles = drawn_triangles(&mut core, &calls);
+        assert_eq!(triangles.len(), 2*SPLINE_SEGMENT_DIVISIONS);
+        for [_, a, b] in triangles.into_iter().step_by(2) {
+            assert!(((a - b).magnitude() - 2.0).abs() < 1.0e-4, "{a:?} {b:?}");
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Swept AABB collision helpers, for resolving moving rectangles against static ones
+
+use crate::prelude::*;
+
+/// Swept collision hit info
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepHit {
+    /// Time of impact, as a fraction of the frame velocity
+    pub time: Percent,
+    /// Surface normal of the solid at the point of impact
+    pub normal: Direction2,
+    /// Moving rectangle top-left corner position at the time of impact
+    pub position: Position2,
+}
+
+/// Get entry and exit times along one axis, `None` if the axis never overlaps
+/// NOTE: Zero velocity only overlaps when strictly inside, so sliding exactly along an edge is not a hit
+fn sweep_axis(pos: f32, vel: f32, min: f32, max: f32) -> Option<(f32, f32)> {
+    if vel == 0.0 {
+        (min < pos && pos < max).then_some((f32::NEG_INFINITY, f32::INFINITY))
+    } else {
+        let t1 = (min - pos) / vel;
+        let t2 = (max - pos) / vel;
+        Some((t1.min(t2), t1.max(t2)))
+    }
+}
+
+/// Compute time of impact of a moving rectangle against a static one within one frame
+/// NOTE: Rectangles already overlapping or moving away from a touching surface are not hits;
+/// on exact corner hits the vertical normal is preferred, so landing on tile corners doesn't snag
+#[must_use]
+pub fn sweep_aabb(moving: Rectangle, velocity: Vector2, solid: Rectangle) -> Option<SweepHit> {
+    if velocity == Vector2::ZERO {
+        return None;
+    }
+
+    // Minkowski sum: sweep the moving rectangle's top-left point against the expanded solid
+    let (entry_x, exit_x) = sweep_axis(moving.x, velocity.x, solid.x - moving.width, solid.x_max())?;
+    let (entry_y, exit_y) = sweep_axis(moving.y, velocity.y, solid.y - moving.height, solid.y_max())?;
+
+    let entry = entry_x.max(entry_y);
+    let exit = exit_x.min(exit_y);
+
+    if entry >= exit || !(0.0..=1.0).contains(&entry) {
+        return None;
+    }
+
+    let normal = if entry_x > entry_y {
+        Vector2::new(-velocity.x.signum(), 0.0)
+    } else {
+        Vector2::new(0.0, -velocity.y.signum())
+    };
+
+    Some(SweepHit {
+        time: entry,
+        normal,
+        position: Vector2::new(moving.x, moving.y) + velocity * entry,
+    })
+}
+
+/// Compute the earliest time of impact of a moving rectangle against multiple static ones
+/// NOTE: On ties the first solid in the slice wins
+#[must_use]
+pub fn sweep_aabb_multi(moving: Rectangle, velocity: Vector2, solids: &[Rectangle]) -> Option<SweepHit> {
+    solids.iter()
+        .filter_map(|&solid| sweep_aabb(moving, velocity, solid))
+        .fold(None, |earliest: Option<SweepHit>, hit| match earliest {
+            Some(earliest) if earliest.time <= hit.time => Some(earliest),
+            _ => Some(hit),
+        })
+}
+
+/// Remove the velocity component going into the surface, keeping the tangential part
+pub fn slide_response(velocity: Vector2, normal: Direction2) -> Vector2 {
+    let into = velocity.dot(normal);
+    if into < 0.0 {
+        velocity - normal * into
+    } else {
+        velocity
+    }
+}
+
+/// Reflect the velocity component going into the surface, scaled by restitution `[0..1]`
+pub fn bounce_response(velocity: Vector2, normal: Direction2, restitution: Percent) -> Vector2 {
+    let into = velocity.dot(normal);
+    if into < 0.0 {
+        velocity - normal * (into * (1.0 + restitution))
+    } else {
+        velocity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1×1 solid tile at grid position
+    fn tile(x: f32, y: f32) -> Rectangle {
+        Rectangle::new(x, y, 1.0, 1.0)
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_sweep_four_sides() {
+        let solid = Rectangle::new(4.0, 4.0, 2.0, 2.0);
+        let cases = [
+            // From the left, right, top and bottom, each stopping 1 unit into a 4 unit move
+            (Rectangle::new(0.0, 4.5, 1.0, 1.0), Vector2::new(4.0, 0.0), Vector2::new(-1.0, 0.0), Vector2::new(3.0, 4.5)),
+            (Rectangle::new(9.0, 4.5, 1.0, 1.0), Vector2::new(-4.0, 0.0), Vector2::new(1.0, 0.0), Vector2::new(6.0, 4.5)),
+            (Rectangle::new(4.5, 0.0, 1.0, 1.0), Vector2::new(0.0, 4.0), Vector2::new(0.0, -1.0), Vector2::new(4.5, 3.0)),
+            (Rectangle::new(4.5, 9.0, 1.0, 1.0), Vector2::new(0.0, -4.0), Vector2::new(0.0, 1.0), Vector2::new(4.5, 6.0)),
+        ];
+        for (moving, velocity, normal, position) in cases {
+            let hit = sweep_aabb(moving, velocity, solid).unwrap();
+            assert_eq!(hit, SweepHit { time: 0.75, normal, position }, "velocity {velocity:?}");
+        }
+    }
+
+    #[test]
+    fn test_sweep_miss() {
+        let solid = tile(4.0, 0.0);
+        // Too short, passing beside, and moving away
+        assert_eq!(sweep_aabb(tile(0.0, 0.0), Vector2::new(2.0, 0.0), solid), None);
+        assert_eq!(sweep_aabb(tile(0.0, 2.0), Vector2::new(8.0, 0.0), solid), None);
+        assert_eq!(sweep_aabb(tile(0.0, 0.0), Vector2::new(-8.0, 0.0), solid), None);
+        // Diagonal passing the corner without touching
+        assert_eq!(sweep_aabb(tile(0.0, 2.0), Vector2::new(8.0, -1.5), solid), None);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_sweep_corner() {
+        // Exactly diagonal into the corner: vertical normal is preferred
+        let hit = sweep_aabb(tile(0.0, 0.0), Vector2::new(4.0, 4.0), tile(2.0, 2.0)).unwrap();
+        assert_eq!((hit.time, hit.normal, hit.position), (0.25, Vector2::new(0.0, -1.0), Vector2::new(1.0, 1.0)));
+        let hit = sweep_aabb(tile(4.0, 4.0), Vector2::new(-4.0, -4.0), tile(2.0, 2.0)).unwrap();
+        assert_eq!((hit.time, hit.normal), (0.25, Vector2::new(0.0, 1.0)));
+
+        // Slightly off the diagonal: the later entering axis decides the normal
+        let hit = sweep_aabb(tile(0.0, 0.0), Vector2::new(4.0, 3.0), tile(2.0, 2.0)).unwrap();
+        assert_eq!(hit.normal, Vector2::new(0.0, -1.0));
+        let hit = sweep_aabb(tile(0.0, 0.0), Vector2::new(3.0, 4.0), tile(2.0, 2.0)).unwrap();
+        assert_eq!(hit.normal, Vector2::new(-1.0, 0.0));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_sweep_tunneling() {
+        // Thin wall far smaller than the frame movement is still hit
+        let wall = Rectangle::new(500.0, -10.0, 0.1, 20.0);
+        let hit = sweep_aabb(tile(0.0, 0.0), Vector2::new(10_000.0, 0.0), wall).unwrap();
+        assert_eq!(hit.normal, Vector2::new(-1.0, 0.0));
+        assert_eq!(hit.position, Vector2::new(499.0, 0.0));
+
+        // Fast and tiny moving rectangle through a thin floor
+        let floor = Rectangle::new(-100.0, 50.0, 200.0, 0.01);
+        let hit = sweep_aabb(Rectangle::new(0.0, 0.0, 0.01, 0.01), Vector2::new(3.0, 1.0e6), floor).unwrap();
+        assert_eq!(hit.normal, Vector2::new(0.0, -1.0));
+        assert!((hit.position.y - 49.99).abs() < 1.0e-3);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_sweep_touching() {
+        let solid = tile(1.0, 0.0);
+        // Touching and moving into the surface hits immediately
+        let hit = sweep_aabb(tile(0.0, 0.0), Vector2::new(1.0, 0.0), solid).unwrap();
+        assert_eq!((hit.time, hit.normal, hit.position), (0.0, Vector2::new(-1.0, 0.0), Vector2::new(0.0, 0.0)));
+        // Touching and moving away or along the surface is not a hit
+        assert_eq!(sweep_aabb(tile(0.0, 0.0), Vector2::new(-1.0, 0.0), solid), None);
+        assert_eq!(sweep_aabb(tile(0.0, 0.0), Vector2::new(0.0, 1.0), solid), None);
+        // Reaching the surface exactly at the end of the frame is a hit
+        assert_eq!(sweep_aabb(tile(-1.0, 0.0), Vector2::new(1.0, 0.0), solid).map(|hit| hit.time), Some(1.0));
+    }
+
+    #[test]
+    fn test_sweep_degenerate() {
+        // Zero velocity, even when overlapping
+        assert_eq!(sweep_aabb(tile(0.0, 0.0), Vector2::ZERO, tile(0.5, 0.5)), None);
+        // Already overlapping
+        assert_eq!(sweep_aabb(tile(0.0, 0.0), Vector2::new(1.0, 0.0), tile(0.5, 0.5)), None);
+        assert_eq!(sweep_aabb_multi(tile(0.0, 0.0), Vector2::new(1.0, 0.0), &[]), None);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_sweep_multi() {
+        let moving = tile(0.0, 0.0);
+        let velocity = Vector2::new(10.0, 0.0);
+        // Earliest hit wins regardless of order
+        let solids = [tile(8.0, 0.0), tile(3.0, 0.5), tile(5.0, 0.0)];
+        let hit = sweep_aabb_multi(moving, velocity, &solids).unwrap();
+        assert_eq!((hit.time, hit.position), (0.2, Vector2::new(2.0, 0.0)));
+        // Ties keep the first solid
+        let solids = [tile(3.0, 0.5), tile(3.0, -0.5)];
+        let hit = sweep_aabb_multi(moving, Vector2::new(10.0, 0.0), &solids).unwrap();
+        assert_eq!(Some(hit), sweep_aabb(moving, velocity, solids[0]));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp, clippy::cast_precision_loss)]
+    fn test_sweep_floor_seam() {
+        // Floor of adjacent tiles, player standing on the first one, right at the seam
+        let floor: Vec<Rectangle> = (0..8).map(|x| tile(x as f32, 1.0)).collect();
+        let mut player = Rectangle::new(0.5, 0.0, 1.0, 1.0);
+
+        // Walking exactly along the floor never snags on tile edges
+        assert_eq!(sweep_aabb_multi(player, Vector2::new(3.0, 0.0), &floor), None);
+
+        // Walking with gravity: every frame hits the floor from above, sliding keeps the horizontal speed
+        for _ in 0..4 {
+            let mut velocity = Vector2::new(1.0, 0.5);
+            let hit = sweep_aabb_multi(player, velocity, &floor).unwrap();
+            assert_eq!((hit.time, hit.normal), (0.0, Vector2::new(0.0, -1.0)));
+            velocity = slide_response(velocity, hit.normal);
+            assert_eq!(velocity, Vector2::new(1.0, 0.0));
+            assert_eq!(sweep_aabb_multi(player, velocity, &floor), None);
+            player = player.move_by(velocity);
+        }
+        assert_eq!(player, Rectangle::new(4.5, 0.0, 1.0, 1.0));
+
+        // Falling onto the seam between two tiles lands on the floor
+        let hit = sweep_aabb_multi(Rectangle::new(1.5, -3.0, 1.0, 1.0), Vector2::new(0.0, 5.0), &floor).unwrap();
+        assert_eq!((hit.time, hit.normal, hit.position), (0.6, Vector2::new(0.0, -1.0), Vector2::new(1.5, 0.0)));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_responses() {
+        let floor = Vector2::new(0.0, -1.0);
+        assert_eq!(slide_response(Vector2::new(2.0, 3.0), floor), Vector2::new(2.0, 0.0));
+        assert_eq!(bounce_response(Vector2::new(2.0, 3.0), floor, 0.0), Vector2::new(2.0, 0.0));
+        assert_eq!(bounce_response(Vector2::new(2.0, 3.0), floor, 1.0), Vector2::new(2.0, -3.0));
+        assert_eq!(bounce_response(Vector2::new(2.0, 4.0), floor, 0.5), Vector2::new(2.0, -2.0));
+
+        // Moving away from or along the surface is unchanged
+        let wall = Vector2::new(1.0, 0.0);
+        for velocity in [Vector2::new(2.0, 3.0), Vector2::new(0.0, -3.0)] {
+            assert_eq!(slide_response(velocity, wall), velocity);
+            assert_eq!(bounce_response(velocity, wall, 1.0), velocity);
+        }
+    }
+}
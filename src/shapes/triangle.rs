@@ -1,11 +1,136 @@
-use crate::prelude::*;
+use crate::{prelude::*, rlgl::DrawMode};
+use super::{arc_point, draw_shapes_quad, draw_shapes_quads};
 
+/// Triangle, 3 points in 2d space
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Triangle2D {
     pub points: [Position2; 3],
 }
 
+/// Triangle, 3 points in 3d space
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Triangle3D {
     pub points: [Position3; 3],
 }
 
 pub type Triangle = Triangle3D;
+
+impl Triangle2D {
+    #[inline]
+    #[must_use]
+    pub const fn new(v1: Position2, v2: Position2, v3: Position2) -> Self {
+        Self { points: [v1, v2, v3] }
+    }
+}
+
+/// Draw a color-filled triangle
+/// NOTE: Vertex must be provided in counter-clockwise order
+pub fn draw_triangle(core: &mut Core, triangle: &Triangle2D, color: Color) {
+    let [v1, v2, v3] = triangle.points;
+    draw_shapes_quad(core, [v1, v2, v2, v3], color);
+}
+
+/// Draw triangle outline
+pub fn draw_triangle_lines(core: &mut Core, triangle: &Triangle2D, color: Color) {
+    let [v1, v2, v3] = triangle.points;
+    let rlgl = &mut core.rlgl;
+    rlgl.begin(DrawMode::Lines);
+        rlgl.color4ub(color.r, color.g, color.b, color.a);
+        for (from, to) in [(v1, v2), (v2, v3), (v3, v1)] {
+            rlgl.vertex2f(from.x, from.y);
+            rlgl.vertex2f(to.x, to.y);
+        }
+    rlgl.end();
+}
+
+/// Draw a triangle fan defined by points
+/// NOTE: First vertex provided is the center, shared by all triangles
+/// By default, following vertex should be provided in counter-clockwise order
+/// NOTE: Nothing is drawn for less than 3 points
+pub fn draw_triangle_fan(core: &mut Core, points: &[Position2], color: Color) {
+    if points.len() < 3 {
+        return;
+    }
+
+    let center = points[0];
+    let quads = points[1..].windows(2).map(|edge| ([center, edge[0], edge[1], edge[1]], [color; 4]));
+    draw_shapes_quads(core, quads);
+}
+
+/// Draw a triangle strip defined by points
+/// NOTE: Every new vertex connects with previous two, nothing is drawn for less than 3 points
+pub fn draw_triangle_strip(core: &mut Core, points: &[Position2], color: Color) {
+    if points.len() < 3 {
+        return;
+    }
+
+    let rlgl = &mut core.rlgl;
+    rlgl.begin(DrawMode::Triangles);
+        rlgl.color4ub(color.r, color.g, color.b, color.a);
+        for (i, triangle) in points.windows(3).enumerate() {
+            // NOTE: Every other triangle is flipped to keep counter-clockwise winding
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+            for vertex in if i % 2 == 0 { [c, a, b] } else { [c, b, a] } {
+                rlgl.vertex2f(vertex.x, vertex.y);
+            }
+        }
+    rlgl.end();
+}
+
+/// Draw a regular polygon (Vector version)
+/// NOTE: Polygons have at least 3 sides
+#[allow(clippy::cast_precision_loss)]
+pub fn draw_poly(core: &mut Core, center: Position2, sides: usize, radius: Pixels, rotation: Degrees, color: Color) {
+    let sides = sides.max(3);
+    let angle_step = Degrees::FULL_TURN/sides as f32;
+
+    let quads = (0..sides).map(|i| {
+        let central_angle = rotation + angle_step*i as f32;
+        let current = arc_point(center, radius, central_angle);
+        let next = arc_point(center, radius, central_angle + angle_step);
+        ([center, next, current, current], [color; 4])
+    });
+
+    draw_shapes_quads(core, quads);
+}
+
+/// Draw a polygon outline of n sides
+/// NOTE: Polygons have at least 3 sides
+#[allow(clippy::cast_precision_loss)]
+pub fn draw_poly_lines(core: &mut Core, center: Position2, sides: usize, radius: Pixels, rotation: Degrees, color: Color) {
+    let sides = sides.max(3);
+    let angle_step = Degrees::FULL_TURN/sides as f32;
+
+    let rlgl = &mut core.rlgl;
+    rlgl.begin(DrawMode::Lines);
+        rlgl.color4ub(color.r, color.g, color.b, color.a);
+        for i in 0..sides {
+            let central_angle = rotation + angle_step*i as f32;
+            let (from, to) = (arc_point(center, radius, central_angle), arc_point(center, radius, central_angle + angle_step));
+            rlgl.vertex2f(from.x, from.y);
+            rlgl.vertex2f(to.x, to.y);
+        }
+    rlgl.end();
+}
+
+/// Draw a polygon outline of n sides with extended parameters
+/// NOTE: Outline is drawn inside the polygon, `line_thick` measured perpendicular to the sides
+#[allow(clippy::cast_precision_loss)]
+pub fn draw_poly_lines_ex(core: &mut Core, center: Position2, sides: usize, radius: Pixels, rotation: Degrees, line_thick: Pixels, color: Color) {
+    let sides = sides.max(3);
+    let exterior_angle = Degrees::FULL_TURN/sides as f32;
+    let inner_radius = radius - line_thick/(exterior_angle/2.0).to_radians().cos();
+
+    let quads = (0..sides).map(|i| {
+        let central_angle = rotation + exterior_angle*i as f32;
+        let next_angle = central_angle + exterior_angle;
+        ([
+            arc_point(center, radius, central_angle),
+            arc_point(center, inner_radius, central_angle),
+            arc_point(center, inner_radius, next_angle),
+            arc_point(center, radius, next_angle),
+        ], [color; 4])
+    });
+
+    draw_shapes_quads(core, quads);
+}
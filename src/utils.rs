@@ -105,3 +105,478 @@ macro_rules! tracelog {
         $crate::utils::trace_log($level, format_args!($($args)+))
     };
 }
+
+/// File data kept in memory, source of all `from_memory` loaders
+/// NOTE: Static data (`include_bytes!`) is never copied, streaming decoders (music)
+/// read it through [`MemoryData::reader`] without loading it whole
+///
+/// Embedding an asset in the executable:
+/// ```ignore
+/// let data = MemoryData::from(include_bytes!("../resources/music.ogg"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryData {
+    /// Data embedded in the executable
+    Static(&'static [u8]),
+    /// Data loaded at runtime, shared between readers
+    Shared(std::sync::Arc<[u8]>),
+}
+
+impl MemoryData {
+    /// Get a seekable reader over the data, cheap to create
+    #[inline]
+    #[must_use]
+    pub fn reader(&self) -> std::io::Cursor<MemoryData> {
+        std::io::Cursor::new(self.clone())
+    }
+}
+
+impl AsRef<[u8]> for MemoryData {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::Static(data) => data,
+            Self::Shared(data) => data,
+        }
+    }
+}
+
+impl std::ops::Deref for MemoryData {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl From<&'static [u8]> for MemoryData {
+    #[inline]
+    fn from(data: &'static [u8]) -> Self {
+        Self::Static(data)
+    }
+}
+
+impl<const N: usize> From<&'static [u8; N]> for MemoryData {
+    #[inline]
+    fn from(data: &'static [u8; N]) -> Self {
+        Self::Static(data)
+    }
+}
+
+impl From<std::sync::Arc<[u8]>> for MemoryData {
+    #[inline]
+    fn from(data: std::sync::Arc<[u8]>) -> Self {
+        Self::Shared(data)
+    }
+}
+
+impl From<Vec<u8>> for MemoryData {
+    #[inline]
+    fn from(data: Vec<u8>) -> Self {
+        Self::Shared(data.into())
+    }
+}
+
+/// Resolve a file referenced by another file (OBJ materials, font pages), by name
+/// NOTE: Required by `from_memory` loaders of multi-file formats, as there is no filesystem to look into
+pub type FileResolver<'a> = dyn FnMut(&str) -> Option<MemoryData> + 'a;
+
+/// Create a resolver reading referenced files relative to a directory, used by file-path loaders
+pub fn directory_resolver(directory: impl AsRef<std::path::Path>) -> impl FnMut(&str) -> Option<MemoryData> {
+    let directory = directory.as_ref().to_path_buf();
+    move |name| {
+        let path = directory.join(name);
+        std::fs::read(&path)
+            .inspect_err(|_| tracelog!(Warning, "FILEIO: [{}] Failed to open referenced file", path.display()))
+            .ok()
+            .map(MemoryData::from)
+    }
+}
+
+/// Get file extension as the `file_type` of `from_memory` loaders (i.e. `.png`), empty if the file has none
+pub(crate) fn file_type(path: &std::path::Path) -> String {
+    path.extension().map(|ext| format!(".{}", ext.to_string_lossy())).unwrap_or_default()
+}
+
+/// Load file data and its `file_type`, used by file-path loaders on top of `from_memory` ones
+pub(crate) fn load_file_data(path: &std::path::Path) -> Option<(String, Vec<u8>)> {
+    let Ok(data) = std::fs::read(path) else {
+        tracelog!(Warning, "FILEIO: [{}] Failed to open file", path.display());
+        return None;
+    };
+    tracelog!(Info, "FILEIO: [{}] File loaded successfully", path.display());
+    Some((file_type(path), data))
+}
+
+/// Error decompressing data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecompressError {
+    /// Data is not a valid DEFLATE stream
+    InvalidData,
+    /// Decompressed data exceeds the size limit, in bytes
+    TooLarge(usize),
+}
+
+impl std::fmt::Display for DecompressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidData => f.write_str("invalid compressed data"),
+            Self::TooLarge(limit) => write!(f, "decompressed data exceeds {limit} bytes"),
+        }
+    }
+}
+
+impl std::error::Error for DecompressError {}
+
+/// Compress data (DEFLATE algorithm)
+#[cfg(feature = "support_compression_api")]
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn compress_data(data: &[u8]) -> Vec<u8> {
+    let compressed = crate::external::sdefl::deflate(data);
+
+    tracelog!(Info, "SYSTEM: Compress data: Original size: {} -> Comp. size: {} ({:.2}%)",
+        data.len(), compressed.len(), compressed.len() as f64*100.0/data.len().max(1) as f64);
+
+    compressed
+}
+
+/// Decompress data (DEFLATE algorithm)
+/// NOTE: Decompressed data is limited to `MAX_DECOMPRESSION_SIZE` megabytes
+/// # Errors
+/// Returns an error if data is not a valid DEFLATE stream or decompressed data is too large
+#[cfg(feature = "support_compression_api")]
+pub fn decompress_data(data: &[u8]) -> Result<Vec<u8>, DecompressError> {
+    let (decompressed, _) = crate::external::sinfl::inflate(data, crate::config::MAX_DECOMPRESSION_SIZE*1024*1024)
+        .inspect_err(|e| tracelog!(Warning, "SYSTEM: Failed to decompress data: {e}"))?;
+
+    tracelog!(Info, "SYSTEM: Decompress data: Comp. size: {} -> Original size: {}", data.len(), decompressed.len());
+
+    Ok(decompressed)
+}
+
+/// Base64 encoding alphabet (standard, RFC 4648)
+const BASE64_ENCODE_TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Error decoding base64 text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Base64Error {
+    /// Character is not part of the base64 alphabet, byte offset in the text
+    InvalidCharacter(usize),
+    /// Padding is misplaced or data length is not a multiple of 4 characters
+    InvalidPadding,
+}
+
+impl std::fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCharacter(index) => write!(f, "invalid base64 character at offset {index}"),
+            Self::InvalidPadding => f.write_str("invalid base64 padding"),
+        }
+    }
+}
+
+impl std::error::Error for Base64Error {}
+
+/// Encode data to Base64 string (with padding)
+#[must_use]
+pub fn encode_data_base64(data: &[u8]) -> String {
+    let mut text = String::with_capacity(data.len().div_ceil(3)*4);
+    for chunk in data.chunks(3) {
+        let octets = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let triple = u32::from(octets[0]) << 16 | u32::from(octets[1]) << 8 | u32::from(octets[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(char::from(BASE64_ENCODE_TABLE[(triple >> (18 - 6*i) & 0x3F) as usize]));
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+/// Decode Base64 string (with padding) data
+/// NOTE: Whitespace (i.e. line breaks) is ignored
+/// # Errors
+/// Returns an error if the text contains invalid characters or invalid padding
+#[allow(clippy::cast_possible_truncation)]
+pub fn decode_data_base64(text: &str) -> Result<Vec<u8>, Base64Error> {
+    let mut data = Vec::with_capacity(text.len()/4*3);
+    let mut quad = [0u8; 4];
+    let (mut count, mut padding) = (0, 0);
+    for (index, byte) in text.bytes().enumerate() {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => {
+                // Padding only fills the last 1 or 2 characters of a group
+                if count < 2 {
+                    return Err(Base64Error::InvalidPadding);
+                }
+                padding += 1;
+                0
+            }
+            _ if byte.is_ascii_whitespace() => continue,
+            _ => return Err(Base64Error::InvalidCharacter(index)),
+        };
+        if padding > 0 && byte != b'=' {
+            return Err(Base64Error::InvalidPadding); // Data after padding
+        }
+
+        quad[count] = value;
+        count += 1;
+        if count == 4 {
+            let triple = quad.iter().fold(0u32, |triple, &value| triple << 6 | u32::from(value));
+            data.extend_from_slice(&triple.to_be_bytes()[1..4 - padding]);
+            count = 0;
+        }
+    }
+    if count != 0 {
+        return Err(Base64Error::InvalidPadding);
+    }
+
+    Ok(data)
+}
+
+/// CRC-32 lookup table (IEEE polynomial, reflected)
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+/// Update a running CRC-32 (not inverted) with more data
+pub(crate) fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    data.iter().fold(crc, |crc, &byte| CRC32_TABLE[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8))
+}
+
+/// Compute CRC32 hash code
+#[must_use]
+pub fn compute_crc32(data: &[u8]) -> u32 {
+    !crc32_update(!0, data)
+}
+
+/// Call `block` for every 64-byte block of data, including the final padding blocks
+/// (0x80, zeros, then the message length in bits as `length_bytes`)
+fn for_each_hash_block(data: &[u8], length_bytes: impl Fn(u64) -> [u8; 8], mut block: impl FnMut(&[u8])) {
+    let mut blocks = data.chunks_exact(64);
+    for chunk in &mut blocks {
+        block(chunk);
+    }
+
+    let tail = blocks.remainder();
+    let mut last = [0u8; 128];
+    last[..tail.len()].copy_from_slice(tail);
+    last[tail.len()] = 0x80;
+    let end = if tail.len() < 56 { 64 } else { 128 };
+    last[end - 8..end].copy_from_slice(&length_bytes((data.len() as u64).wrapping_mul(8)));
+    for chunk in last[..end].chunks_exact(64) {
+        block(chunk);
+    }
+}
+
+/// Compute MD5 hash code, returns 4 integers (little endian words of the digest)
+#[must_use]
+#[allow(clippy::many_single_char_names)]
+pub fn compute_md5(data: &[u8]) -> [u32; 4] {
+    const S: [u32; 64] = [
+        7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+        5,  9, 14, 20, 5,  9, 14, 20, 5,  9, 14, 20, 5,  9, 14, 20,
+        4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+        6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+    ];
+    const K: [u32; 64] = [
+        0xd76a_a478, 0xe8c7_b756, 0x2420_70db, 0xc1bd_ceee, 0xf57c_0faf, 0x4787_c62a, 0xa830_4613, 0xfd46_9501,
+        0x6980_98d8, 0x8b44_f7af, 0xffff_5bb1, 0x895c_d7be, 0x6b90_1122, 0xfd98_7193, 0xa679_438e, 0x49b4_0821,
+        0xf61e_2562, 0xc040_b340, 0x265e_5a51, 0xe9b6_c7aa, 0xd62f_105d, 0x0244_1453, 0xd8a1_e681, 0xe7d3_fbc8,
+        0x21e1_cde6, 0xc337_07d6, 0xf4d5_0d87, 0x455a_14ed, 0xa9e3_e905, 0xfcef_a3f8, 0x676f_02d9, 0x8d2a_4c8a,
+        0xfffa_3942, 0x8771_f681, 0x6d9d_6122, 0xfde5_380c, 0xa4be_ea44, 0x4bde_cfa9, 0xf6bb_4b60, 0xbebf_bc70,
+        0x289b_7ec6, 0xeaa1_27fa, 0xd4ef_3085, 0x0488_1d05, 0xd9d4_d039, 0xe6db_99e5, 0x1fa2_7cf8, 0xc4ac_5665,
+        0xf429_2244, 0x432a_ff97, 0xab94_23a7, 0xfc93_a039, 0x655b_59c3, 0x8f0c_cc92, 0xffef_f47d, 0x8584_5dd1,
+        0x6fa8_7e4f, 0xfe2c_e6e0, 0xa301_4314, 0x4e08_11a1, 0xf753_7e82, 0xbd3a_f235, 0x2ad7_d2bb, 0xeb86_d391,
+    ];
+
+    let mut hash = [0x6745_2301u32, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+    for_each_hash_block(data, u64::to_le_bytes, |block| {
+        let words: [u32; 16] = std::array::from_fn(|i| u32::from_le_bytes([block[i*4], block[i*4 + 1], block[i*4 + 2], block[i*4 + 3]]));
+        let [mut a, mut b, mut c, mut d] = hash;
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5*i + 1) % 16),
+                2 => (b ^ c ^ d, (3*i + 5) % 16),
+                _ => (c ^ (b | !d), (7*i) % 16),
+            };
+            let f = f.wrapping_add(a).wrapping_add(K[i]).wrapping_add(words[g]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(f.rotate_left(S[i]));
+        }
+        for (h, value) in hash.iter_mut().zip([a, b, c, d]) {
+            *h = h.wrapping_add(value);
+        }
+    });
+    hash
+}
+
+/// Compute SHA1 hash code, returns 5 integers (big endian words of the digest)
+#[must_use]
+#[allow(clippy::many_single_char_names)]
+pub fn compute_sha1(data: &[u8]) -> [u32; 5] {
+    let mut hash = [0x6745_2301u32, 0xEFCD_AB89, 0x98BA_DCFE, 0x1032_5476, 0xC3D2_E1F0];
+    for_each_hash_block(data, u64::to_be_bytes, |block| {
+        let mut words = [0u32; 80];
+        for i in 0..16 {
+            words[i] = u32::from_be_bytes([block[i*4], block[i*4 + 1], block[i*4 + 2], block[i*4 + 3]]);
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = hash;
+        for (i, &word) in words.iter().enumerate() {
+            let (f, k) = match i / 20 {
+                0 => ((b & c) | (!b & d), 0x5A82_7999),
+                1 => (b ^ c ^ d, 0x6ED9_EBA1),
+                2 => ((b & c) | (b & d) | (c & d), 0x8F1B_BCDC),
+                _ => (b ^ c ^ d, 0xCA62_C1D6),
+            };
+            let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (h, value) in hash.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(value);
+        }
+    });
+    hash
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::path::PathBuf;
+
+    /// Write data to a file in the system temporary directory, named uniquely for this process
+    pub(crate) fn write_temp_file(name: &str, data: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("raylib_rs_native_{}_{name}", std::process::id()));
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    #[test]
+    #[cfg(feature = "support_compression_api")]
+    fn test_compress_data() {
+        use super::{compress_data, decompress_data, DecompressError};
+
+        let data = b"raylib compression round trip, raylib compression round trip".repeat(50);
+        let compressed = compress_data(&data);
+        assert!(compressed.len() < data.len()/10);
+        assert_eq!(decompress_data(&compressed).unwrap(), data);
+        // Streams from other DEFLATE encoders decompress too
+        assert_eq!(decompress_data(&miniz_oxide::deflate::compress_to_vec(&data, 9)).unwrap(), data);
+        assert_eq!(decompress_data(&compressed[..compressed.len() - 1]), Err(DecompressError::InvalidData));
+        assert_eq!(decompress_data(&compress_data(&[])).unwrap(), []);
+    }
+
+    #[test]
+    fn test_base64() {
+        use super::{decode_data_base64, encode_data_base64, Base64Error};
+
+        // RFC 4648 test vectors
+        let vectors = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foob", "Zm9vYg=="), ("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy")];
+        for (data, text) in vectors {
+            assert_eq!(encode_data_base64(data.as_bytes()), text);
+            assert_eq!(decode_data_base64(text).unwrap(), data.as_bytes());
+        }
+
+        let data: Vec<u8> = (0..=255).collect();
+        let text = encode_data_base64(&data);
+        assert!(text.starts_with("AAECAwQF") && text.ends_with("+/w=="));
+        assert_eq!(decode_data_base64(&text).unwrap(), data);
+        // Whitespace is ignored
+        assert_eq!(decode_data_base64("Zm9v\r\nYmFy\n").unwrap(), b"foobar");
+
+        assert_eq!(decode_data_base64("Zm9v-mFy"), Err(Base64Error::InvalidCharacter(4)));
+        assert_eq!(decode_data_base64("Zm9vYg"), Err(Base64Error::InvalidPadding));
+        assert_eq!(decode_data_base64("Zg==Zm8="), Err(Base64Error::InvalidPadding));
+        assert_eq!(decode_data_base64("Z==="), Err(Base64Error::InvalidPadding));
+        assert_eq!(decode_data_base64("Zm=9"), Err(Base64Error::InvalidPadding));
+    }
+
+    /// Digest words as hex string, big endian
+    fn hex_words(words: impl IntoIterator<Item = u32>) -> String {
+        use std::fmt::Write;
+        words.into_iter().fold(String::new(), |mut text, word| {
+            write!(text, "{word:08x}").unwrap();
+            text
+        })
+    }
+
+    #[test]
+    fn test_compute_crc32() {
+        use super::compute_crc32;
+
+        assert_eq!(compute_crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(compute_crc32(b""), 0);
+        assert_eq!(compute_crc32(b"The quick brown fox jumps over the lazy dog"), 0x414F_A339);
+    }
+
+    #[test]
+    fn test_compute_md5() {
+        use super::compute_md5;
+
+        let hex = |hash: [u32; 4]| hex_words(hash.map(u32::swap_bytes));
+        // RFC 1321 test suite
+        let vectors = [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            ("abcdefghijklmnopqrstuvwxyz", "c3fcd3d76192e4007dfb496cca67e13b"),
+            ("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789", "d174ab98d277d9f5a5611c2c9f419d9f"),
+            ("12345678901234567890123456789012345678901234567890123456789012345678901234567890", "57edf4a22be3c955ac49da2e2107b67a"),
+        ];
+        for (data, digest) in vectors {
+            assert_eq!(hex(compute_md5(data.as_bytes())), digest, "{data:?}");
+        }
+        assert_eq!(compute_md5(b"abc"), [0x9850_0190, 0xb04f_d23c, 0x7d3f_96d6, 0x727f_e128]);
+    }
+
+    #[test]
+    fn test_compute_sha1() {
+        use super::compute_sha1;
+
+        // RFC 3174 test vectors, the two block message pads into a third block
+        let vectors = [
+            ("abc".to_string(), "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            ("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq".to_string(), "84983e441c3bd26ebaae4aa1f95129e5e54670f1"),
+            ("a".repeat(1_000_000), "34aa973cd4c4daa4f61eeb2bdbad27316534016f"),
+            (String::new(), "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+        ];
+        for (data, digest) in vectors {
+            assert_eq!(hex_words(compute_sha1(data.as_bytes())), digest, "{} bytes", data.len());
+        }
+        assert_eq!(compute_sha1(b"abc"), [0xA999_3E36, 0x4706_816A, 0xBA3E_2571, 0x7850_C26C, 0x9CD0_D89D]);
+    }
+}